@@ -0,0 +1,58 @@
+use quickcheck::{Arbitrary, Gen};
+
+use crate::Stack;
+
+/// Any sequence of items, bottom first; shrinks by dropping and shrinking items
+impl<T: Arbitrary> Arbitrary for Stack<T> {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Stack {
+            items: Vec::arbitrary(g),
+            ..Stack::new()
+        }
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        Box::new(self.items.shrink().map(|items| Stack {
+            items,
+            ..Stack::new()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::quickcheck;
+
+    #[test]
+    fn test_push_then_pop_returns_the_item() {
+        fn prop(mut stack: Stack<i32>, item: i32) -> bool {
+            let size = stack.size();
+            stack.push(item).is_ok()
+                && stack.peek() == Some(&item) && stack.pop() == Some(item) && stack.size() == size
+        }
+        quickcheck(prop as fn(Stack<i32>, i32) -> bool);
+    }
+
+    #[test]
+    fn test_pops_come_out_in_reverse_iteration_order() {
+        fn prop(mut stack: Stack<i32>) -> bool {
+            let mut expected: Vec<i32> = stack.iter().copied().collect();
+            expected.reverse();
+            let popped: Vec<i32> = std::iter::from_fn(|| stack.pop()).collect();
+            popped == expected && stack.is_empty()
+        }
+        quickcheck(prop as fn(Stack<i32>) -> bool);
+    }
+
+    #[test]
+    fn test_sort_matches_vec_sort() {
+        fn prop(mut stack: Stack<i32>) -> bool {
+            let mut expected: Vec<i32> = stack.iter().copied().collect();
+            expected.sort();
+            stack.sort();
+            stack.iter().copied().eq(expected)
+        }
+        quickcheck(prop as fn(Stack<i32>) -> bool);
+    }
+}
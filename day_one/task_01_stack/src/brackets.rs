@@ -0,0 +1,143 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::Stack;
+
+/// Where and how a string's brackets fail to balance
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BracketError {
+    /// A closing bracket that doesn't match the innermost open one
+    Mismatched {
+        offset: usize,
+        expected: char,
+        found: char,
+    },
+    /// A closing bracket with nothing open
+    Unopened { offset: usize, found: char },
+    /// The input ended with a bracket still open; `offset` is the input's length
+    Unclosed { offset: usize, expected: char },
+}
+
+impl BracketError {
+    /// The byte offset where the problem was found
+    pub fn offset(&self) -> usize {
+        match *self {
+            BracketError::Mismatched { offset, .. }
+            | BracketError::Unopened { offset, .. }
+            | BracketError::Unclosed { offset, .. } => offset,
+        }
+    }
+}
+
+impl fmt::Display for BracketError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            BracketError::Mismatched {
+                offset,
+                expected,
+                found,
+            } => write!(
+                f,
+                "expected '{expected}' but found '{found}' at byte {offset}"
+            ),
+            BracketError::Unopened { offset, found } => {
+                write!(f, "'{found}' at byte {offset} closes nothing")
+            }
+            BracketError::Unclosed { offset, expected } => {
+                write!(
+                    f,
+                    "input ended at byte {offset} with '{expected}' still expected"
+                )
+            }
+        }
+    }
+}
+
+impl Error for BracketError {}
+
+/// Checks that `()`, `[]` and `{}` in `input` are properly nested
+///
+/// Every other character is ignored. Each opening bracket pushes the closing
+/// bracket it expects onto a `Stack<char>`, and each closing bracket must match
+/// the top of that stack. The error carries the byte offset of the first problem.
+pub fn is_balanced(input: &str) -> Result<(), BracketError> {
+    // Unbounded, so nothing can be rejected and pushes go straight onto the items
+    let mut expected = Stack::new();
+    for (offset, c) in input.char_indices() {
+        match c {
+            '(' => expected.items.push(')'),
+            '[' => expected.items.push(']'),
+            '{' => expected.items.push('}'),
+            ')' | ']' | '}' => match expected.pop() {
+                Some(close) if close == c => {}
+                Some(close) => {
+                    return Err(BracketError::Mismatched {
+                        offset,
+                        expected: close,
+                        found: c,
+                    })
+                }
+                None => return Err(BracketError::Unopened { offset, found: c }),
+            },
+            _ => {}
+        }
+    }
+    match expected.pop() {
+        Some(close) => Err(BracketError::Unclosed {
+            offset: input.len(),
+            expected: close,
+        }),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_happy_path_nested_and_sequential() {
+        assert_eq!(is_balanced("{[()()]}"), Ok(()));
+        assert_eq!(is_balanced("fn main() { let v = [1, (2)]; }"), Ok(()));
+        assert_eq!(is_balanced("()[]{}"), Ok(()));
+    }
+
+    #[test]
+    fn test_unhappy_path_interleaved_brackets() {
+        let error = is_balanced("([)]").unwrap_err();
+        assert_eq!(
+            error,
+            BracketError::Mismatched {
+                offset: 2,
+                expected: ']',
+                found: ')'
+            }
+        );
+        assert_eq!(error.to_string(), "expected ']' but found ')' at byte 2");
+    }
+
+    #[test]
+    fn test_unhappy_path_unterminated_and_unopened() {
+        assert_eq!(
+            is_balanced("{[]"),
+            Err(BracketError::Unclosed {
+                offset: 3,
+                expected: '}'
+            })
+        );
+        assert_eq!(
+            is_balanced("a)"),
+            Err(BracketError::Unopened {
+                offset: 1,
+                found: ')'
+            })
+        );
+    }
+
+    #[test]
+    fn test_edge_case_offsets_count_bytes_not_chars() {
+        assert_eq!(is_balanced(""), Ok(()));
+        // 'é' takes two bytes, so the stray ']' sits at byte 3
+        assert_eq!(is_balanced("(é]").unwrap_err().offset(), 3);
+    }
+}
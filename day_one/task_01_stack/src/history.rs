@@ -0,0 +1,196 @@
+use std::fmt;
+
+use crate::Stack;
+
+/// Undo/redo history of states, kept on two `Stack`s
+///
+/// The undo stack holds every recorded state with the current one on top, and
+/// the redo stack holds the states undone since the last `record`. The undo
+/// stack is bounded: recording past the capacity evicts the oldest state.
+///
+/// States the history throws away are handed to the `on_evict` callback, if
+/// one is set, instead of being dropped quietly. That covers the oldest states
+/// evicted by the capacity and the redo states discarded by a new `record`, so
+/// states that own a resource (a temporary file, a GPU buffer) can release it
+/// or report it. States still held when the history drops are dropped normally.
+pub struct History<T> {
+    undo: Stack<T>,
+    redo: Stack<T>,
+    on_evict: Option<Box<dyn FnMut(T) + Send>>,
+}
+
+impl<T> History<T> {
+    /// Creates an empty history that keeps at most `capacity` states
+    ///
+    /// A capacity of 0 is raised to 1, so the current state is always kept.
+    pub fn new(capacity: usize) -> Self {
+        History {
+            undo: Stack::with_capacity(capacity.max(1)),
+            redo: Stack::new(),
+            on_evict: None,
+        }
+    }
+
+    /// Sets the callback that receives every state the history throws away
+    pub fn on_evict(mut self, on_evict: impl FnMut(T) + Send + 'static) -> Self {
+        self.on_evict = Some(Box::new(on_evict));
+        self
+    }
+
+    /// Makes `state` the current state
+    ///
+    /// The redo states are discarded, since they branched off an older state,
+    /// and the oldest state is evicted if the history is at capacity.
+    pub fn record(&mut self, state: T) {
+        while let Some(discarded) = self.redo.pop() {
+            self.evict(discarded);
+        }
+        if self.undo.is_full() {
+            if let Some(oldest) = self.undo.remove_bottom() {
+                self.evict(oldest);
+            }
+        }
+        // There is room now, so the bound has already been honoured
+        self.undo.items.push(state);
+    }
+
+    /// Returns the current state, or `None` if nothing has been recorded
+    pub fn current(&self) -> Option<&T> {
+        self.undo.peek()
+    }
+
+    /// Steps back to the previous state and returns it
+    ///
+    /// Returns `None`, changing nothing, if there is no earlier state.
+    pub fn undo(&mut self) -> Option<&T> {
+        if !self.can_undo() {
+            return None;
+        }
+        let undone = self.undo.pop()?;
+        // The redo stack is unbounded
+        self.redo.items.push(undone);
+        self.undo.peek()
+    }
+
+    /// Steps forward to the state most recently undone and returns it
+    ///
+    /// Returns `None`, changing nothing, if nothing has been undone since the
+    /// last `record`.
+    pub fn redo(&mut self) -> Option<&T> {
+        let redone = self.redo.pop()?;
+        // Undoing made room for it, so this never evicts
+        self.undo.items.push(redone);
+        self.undo.peek()
+    }
+
+    /// Checks if there is an earlier state to go back to
+    pub fn can_undo(&self) -> bool {
+        self.undo.size() > 1
+    }
+
+    /// Checks if there is an undone state to go forward to
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    fn evict(&mut self, state: T) {
+        if let Some(on_evict) = &mut self.on_evict {
+            on_evict(state);
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for History<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("History")
+            .field("undo", &self.undo)
+            .field("redo", &self.redo)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_happy_path_undo_and_redo() {
+        let mut history = History::new(10);
+        history.record("a");
+        history.record("ab");
+        history.record("abc");
+        assert_eq!(history.undo(), Some(&"ab"));
+        assert_eq!(history.undo(), Some(&"a"));
+        assert_eq!(history.redo(), Some(&"ab"));
+        assert_eq!(history.current(), Some(&"ab"));
+        assert!(history.can_undo() && history.can_redo());
+    }
+
+    #[test]
+    fn test_record_after_undo_discards_the_redo_branch() {
+        let evicted = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&evicted);
+        let mut history = History::new(10).on_evict(move |state| sink.lock().unwrap().push(state));
+        for state in 1..=4 {
+            history.record(state);
+        }
+        history.undo();
+        history.undo();
+        history.record(10);
+        assert_eq!(
+            *evicted.lock().unwrap(),
+            vec![3, 4],
+            "Most recently undone first"
+        );
+        assert!(!history.can_redo());
+        assert_eq!(history.undo(), Some(&2));
+    }
+
+    #[test]
+    fn test_capacity_evicts_the_oldest_state() {
+        let evicted = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&evicted);
+        let mut history = History::new(3).on_evict(move |state| sink.lock().unwrap().push(state));
+        for state in 1..=5 {
+            history.record(state);
+        }
+        assert_eq!(*evicted.lock().unwrap(), vec![1, 2]);
+        assert_eq!(history.undo(), Some(&4));
+        assert_eq!(history.undo(), Some(&3));
+        assert_eq!(history.undo(), None, "1 and 2 are gone");
+        assert_eq!(history.redo(), Some(&4));
+    }
+
+    #[test]
+    fn test_states_owning_resources_are_released_exactly_once() {
+        // Each state holds a handle to a shared resource; the count shows how many are alive
+        let resource = Arc::new(());
+        let released = Arc::new(Mutex::new(0));
+        let counter = Arc::clone(&released);
+        let mut history = History::new(2).on_evict(move |handle: Arc<()>| {
+            drop(handle);
+            *counter.lock().unwrap() += 1;
+        });
+        for _ in 0..5 {
+            history.record(Arc::clone(&resource));
+        }
+        assert_eq!(*released.lock().unwrap(), 3);
+        assert_eq!(Arc::strong_count(&resource), 3, "Two states plus ours");
+        drop(history);
+        assert_eq!(Arc::strong_count(&resource), 1);
+    }
+
+    #[test]
+    fn test_unhappy_path_nothing_to_undo_or_redo() {
+        let mut history: History<i32> = History::new(0);
+        assert_eq!(history.undo(), None);
+        assert_eq!(history.current(), None);
+        assert_eq!(history.redo(), None);
+        history.record(1);
+        history.record(2); // Capacity 0 acts as 1, so 1 is evicted
+        assert_eq!(history.undo(), None);
+        assert_eq!(history.current(), Some(&2));
+        assert!(format!("{:?}", history).starts_with("History"));
+    }
+}
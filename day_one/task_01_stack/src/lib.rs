@@ -0,0 +1,430 @@
+use std::error::Error;
+use std::fmt;
+
+#[cfg(feature = "testing")]
+mod arbitrary_impls;
+mod brackets;
+mod history;
+mod min_max;
+
+pub use brackets::{is_balanced, BracketError};
+pub use history::History;
+pub use min_max::MinMaxStack;
+
+// ------Stack struct
+// With the `serde` feature the stack serializes as a plain sequence, bottom first.
+// The bound is not serialized, so a deserialized stack is unbounded
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[derive(Clone)]
+pub struct Stack<T> {
+    // Bottom first; private so pushes can't bypass the capacity bound
+    items: Vec<T>,
+    // Maximum number of items, or None for an unbounded stack
+    #[cfg_attr(feature = "serde", serde(skip))]
+    capacity: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    policy: OverflowPolicy,
+}
+
+/// What a bounded stack does when an item is pushed while it is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// Refuse the item: `push` hands it back in `StackFull`
+    #[default]
+    Reject,
+    /// Drop the bottom (oldest) item to make room; O(n), since the rest shift down
+    OverwriteBottom,
+    /// Treat the capacity as a size hint and keep growing past it
+    Grow,
+}
+
+/// A push onto a full stack was rejected; the item is handed back
+#[derive(Debug, PartialEq, Eq)]
+pub struct StackFull<T>(pub T);
+
+impl<T> fmt::Display for StackFull<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "pushing onto a full stack")
+    }
+}
+
+impl<T: fmt::Debug> Error for StackFull<T> {}
+
+// ------Construction
+impl<T> Stack<T> {
+    // An empty, unbounded stack
+    pub fn new() -> Self {
+        Stack {
+            items: Vec::new(),
+            capacity: None,
+            policy: OverflowPolicy::default(),
+        }
+    }
+
+    // An empty stack that holds at most `capacity` items, rejecting pushes past that
+    // Pick another response to a full stack with `overflow_policy`
+    pub fn with_capacity(capacity: usize) -> Self {
+        Stack {
+            items: Vec::with_capacity(capacity),
+            capacity: Some(capacity),
+            policy: OverflowPolicy::default(),
+        }
+    }
+
+    // Sets what a push onto a full stack does; has no effect on an unbounded stack
+    pub fn overflow_policy(mut self, policy: OverflowPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    // The bound set by `with_capacity`, or None for an unbounded stack
+    pub fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+
+    // Whether the stack holds as many items as its bound; never true when unbounded
+    pub fn is_full(&self) -> bool {
+        self.capacity.is_some_and(|capacity| self.items.len() >= capacity)
+    }
+}
+
+impl<T> Default for Stack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ------Basic operations
+impl<T> Stack<T> {
+    // Push operation, following the overflow policy
+    // Returns the item in `StackFull` if the stack is full and the policy is `Reject`;
+    // every other push succeeds
+    pub fn push(&mut self, item: T) -> Result<(), StackFull<T>> {
+        if self.is_full() {
+            match self.policy {
+                OverflowPolicy::Reject => return Err(StackFull(item)),
+                OverflowPolicy::OverwriteBottom if self.items.is_empty() => {
+                    return Ok(());  // A zero capacity leaves nothing to overwrite
+                }
+                OverflowPolicy::OverwriteBottom => {
+                    self.remove_bottom();
+                }
+                OverflowPolicy::Grow => {}
+            }
+        }
+        self.items.push(item);
+        Ok(())
+    }
+
+    // Pop operation
+    pub fn pop(&mut self) -> Option<T> {
+        self.items.pop()
+    }
+
+    // Remove bottom: Removes and returns the oldest item
+    // O(n), since every other item shifts down
+    pub fn remove_bottom(&mut self) -> Option<T> {
+        if self.items.is_empty() {
+            return None;
+        }
+        Some(self.items.remove(0))
+    }
+
+    // Peek operation
+    pub fn peek(&self) -> Option<&T> {
+        self.items.last()
+    }
+
+    // Peek mut operation: The top element, for changing in place
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.items.last_mut()
+    }
+
+    // IsEmpty operation
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    // Size operation
+    pub fn size(&self) -> usize {
+        self.items.len()
+    }
+
+    // Clear operation
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+
+    // Iterate from the bottom of the stack to the top
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.items.iter()
+    }
+
+    // Drain operation: Removes every item, yielding them in pop order (top first)
+    // Items the iterator doesn't reach are still removed when it is dropped
+    pub fn drain(&mut self) -> std::iter::Rev<std::vec::Drain<'_, T>> {
+        self.items.drain(..).rev()
+    }
+}
+
+// ------Sorting
+impl<T: Ord> Stack<T> {
+    // Sort operation: The classic exercise, using one other stack as the only extra storage
+    // Leaves the smallest item at the bottom and the largest on top, so `iter()` matches
+    // `Vec::sort`. O(n^2) comparisons in the worst case, O(n) extra space
+    pub fn sort(&mut self) {
+        // `sorted` keeps the largest item at its bottom and the smallest on top. Items
+        // only move between the two stacks, so `self` never passes its bound and the
+        // pushes go straight onto the items
+        let mut sorted = Stack::new();
+        while let Some(item) = self.pop() {
+            // Park anything smaller than `item` back on self until `item` fits
+            while sorted.peek().is_some_and(|top| *top < item) {
+                if let Some(smaller) = sorted.pop() {
+                    self.items.push(smaller);
+                }
+            }
+            sorted.items.push(item);
+        }
+        // Popping yields the smallest first, so it ends up at the bottom
+        while let Some(item) = sorted.pop() {
+            self.items.push(item);
+        }
+    }
+}
+
+// ------Iteration and formatting
+// Iterating, like serializing, goes bottom to top; formatting shows the top first
+impl<'a, T> IntoIterator for &'a Stack<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> IntoIterator for Stack<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+// Debug: [3, 2, 1] for a stack with 3 on top
+impl<T: fmt::Debug> fmt::Debug for Stack<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.items.iter().rev()).finish()
+    }
+}
+
+// Display: [3, 2, 1] for a stack with 3 on top, each item in its Display form
+impl<T: fmt::Display> fmt::Display for Stack<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[")?;
+        for (i, item) in self.items.iter().rev().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", item)?;
+        }
+        write!(f, "]")
+    }
+}
+
+// --------Testing the Stack
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push() {
+        let mut stack = Stack::new();
+        stack.push(1).unwrap();
+        assert_eq!(stack.size(), 1);
+    }
+
+    #[test]
+    fn test_pop() {
+        let mut stack = Stack::new();
+        stack.push(1).unwrap();
+        stack.push(2).unwrap();
+        assert_eq!(stack.pop(), Some(2));
+        assert_eq!(stack.size(), 1);
+    }
+
+    #[test]
+    fn test_iter_and_clear() {
+        let mut stack = Stack::new();
+        stack.push(1).unwrap();
+        stack.push(2).unwrap();
+        stack.push(3).unwrap();
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+        stack.clear();
+        assert!(stack.is_empty());
+        assert_eq!(stack.iter().next(), None);
+    }
+
+    #[test]
+    fn test_peek() {
+        let mut stack = Stack::new();
+        stack.push(1).unwrap();
+        assert_eq!(stack.peek(), Some(&1));
+    }
+
+    #[test]
+    fn test_peek_mut_and_default() {
+        let mut stack: Stack<String> = Stack::default();
+        assert_eq!(stack.peek_mut(), None);
+        stack.push("a".to_string()).unwrap();
+        stack.push("b".to_string()).unwrap();
+        stack.peek_mut().unwrap().push('!');
+        assert_eq!(stack.pop().as_deref(), Some("b!"));
+        assert_eq!(stack.peek().map(String::as_str), Some("a"), "Only the top changed");
+    }
+
+    #[test]
+    fn test_sort_puts_the_largest_on_top() {
+        let mut stack = Stack::new();
+        for i in [3, 1, 4, 1, 5, 9, 2, 6] {
+            stack.push(i).unwrap();
+        }
+        stack.sort();
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&1, &1, &2, &3, &4, &5, &6, &9]);
+        assert_eq!(stack.peek(), Some(&9));
+    }
+
+    #[test]
+    fn test_edge_case_sort_empty_and_full_bounded_stack() {
+        let mut empty: Stack<i32> = Stack::new();
+        empty.sort();
+        assert!(empty.is_empty());
+
+        // Sorting never holds more items than before, so a full bounded stack is fine
+        let mut bounded = Stack::with_capacity(3);
+        for i in [2, 3, 1] {
+            bounded.push(i).unwrap();
+        }
+        bounded.sort();
+        assert_eq!(bounded.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+    }
+
+    #[test]
+    fn test_is_empty() {
+        let mut stack = Stack::new();
+        assert!(stack.is_empty());
+        stack.push(1).unwrap();
+        assert!(!stack.is_empty());
+    }
+
+    #[test]
+    fn test_size() {
+        let mut stack = Stack::new();
+        assert_eq!(stack.size(), 0);
+        stack.push(1).unwrap();
+        assert_eq!(stack.size(), 1);
+    }
+
+    #[test]
+    fn test_bounded_stack_rejects_when_full() {
+        let mut stack = Stack::with_capacity(2);
+        assert_eq!(stack.capacity(), Some(2));
+        stack.push(1).unwrap();
+        assert_eq!(stack.push(2), Ok(()));
+        assert!(stack.is_full());
+        assert_eq!(stack.push(3), Err(StackFull(3)));
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&1, &2]);
+
+        stack.pop();
+        assert_eq!(stack.push(4), Ok(()), "Popping makes room again");
+    }
+
+    #[test]
+    fn test_unhappy_path_push_onto_full_rejecting_stack_hands_the_item_back() {
+        let mut stack = Stack::with_capacity(1);
+        stack.push(1).unwrap();
+        let rejected = stack.push(2).unwrap_err();
+        assert_eq!(rejected.to_string(), "pushing onto a full stack");
+        assert_eq!(rejected.0, 2);
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&1], "Nothing was overwritten");
+    }
+
+    #[test]
+    fn test_overwrite_bottom_and_grow_policies() {
+        let mut stack = Stack::with_capacity(3).overflow_policy(OverflowPolicy::OverwriteBottom);
+        for i in 1..=5 {
+            stack.push(i).unwrap();
+        }
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&3, &4, &5]);
+        assert_eq!(stack.peek(), Some(&5));
+
+        let mut stack = Stack::with_capacity(2).overflow_policy(OverflowPolicy::Grow);
+        for i in 1..=5 {
+            assert_eq!(stack.push(i), Ok(()));
+        }
+        assert_eq!(stack.size(), 5);
+        assert!(stack.is_full());
+    }
+
+    #[test]
+    fn test_edge_case_zero_capacity_and_unbounded() {
+        let mut stack = Stack::with_capacity(0);
+        assert!(stack.is_full());
+        assert_eq!(stack.push(1), Err(StackFull(1)));
+
+        let mut stack = Stack::with_capacity(0).overflow_policy(OverflowPolicy::OverwriteBottom);
+        stack.push(1).unwrap();
+        assert!(stack.is_empty(), "The new item is the one that doesn't fit");
+
+        let mut unbounded = Stack::new().overflow_policy(OverflowPolicy::Reject);
+        for i in 0..100 {
+            unbounded.push(i).unwrap();
+        }
+        assert_eq!((unbounded.capacity(), unbounded.is_full()), (None, false));
+    }
+
+    #[test]
+    fn test_into_iter_and_drain() {
+        let mut stack = Stack::new();
+        for i in 1..=4 {
+            stack.push(i).unwrap();
+        }
+        assert_eq!((&stack).into_iter().sum::<i32>(), 10);
+        assert_eq!(stack.clone().into_iter().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+
+        let mut drain = stack.drain();
+        assert_eq!(drain.next(), Some(4), "Drains top first");
+        assert_eq!(drain.next(), Some(3));
+        drop(drain);
+        assert!(stack.is_empty(), "Dropping the drain removes the rest");
+        assert_eq!(stack.drain().next(), None);
+    }
+
+    #[test]
+    fn test_display_and_debug_show_the_top_first() {
+        let mut stack = Stack::new();
+        assert_eq!(stack.to_string(), "[]");
+        stack.push("bottom").unwrap();
+        stack.push("top").unwrap();
+        assert_eq!(stack.to_string(), "[top, bottom]");
+        assert_eq!(format!("{:?}", stack), r#"["top", "bottom"]"#);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let mut stack = Stack::new();
+        stack.push(1).unwrap();
+        stack.push(2).unwrap();
+        stack.push(3).unwrap();
+        let json = serde_json::to_string(&stack).unwrap();
+        assert_eq!(json, "[1,2,3]");
+
+        let mut restored: Stack<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.pop(), Some(3));
+        assert_eq!(restored.size(), 2);
+    }
+}
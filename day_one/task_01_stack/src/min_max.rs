@@ -0,0 +1,144 @@
+use crate::Stack;
+
+/// A stack that also reports its smallest and largest item in O(1)
+///
+/// Two auxiliary stacks hold the running minimum and maximum. A value only
+/// goes onto one when it ties or beats the current extreme, so pushing already
+/// sorted data keeps one of them small.
+#[derive(Debug, Clone)]
+pub struct MinMaxStack<T> {
+    items: Stack<T>,
+    mins: Stack<T>,
+    maxes: Stack<T>,
+}
+
+impl<T: Ord + Clone> MinMaxStack<T> {
+    /// Creates an empty stack
+    pub fn new() -> Self {
+        MinMaxStack {
+            items: Stack::new(),
+            mins: Stack::new(),
+            maxes: Stack::new(),
+        }
+    }
+
+    /// Pushes `item`, recording it as the new minimum or maximum if it is one
+    pub fn push(&mut self, item: T) {
+        // Ties are pushed too, so popping one copy leaves the other recorded.
+        // All three stacks are unbounded, so the pushes go straight onto their items
+        if self.mins.peek().is_none_or(|min| item <= *min) {
+            self.mins.items.push(item.clone());
+        }
+        if self.maxes.peek().is_none_or(|max| item >= *max) {
+            self.maxes.items.push(item.clone());
+        }
+        self.items.items.push(item);
+    }
+
+    /// Pops the top item, dropping it from the minimum and maximum records too
+    pub fn pop(&mut self) -> Option<T> {
+        let item = self.items.pop()?;
+        if self.mins.peek() == Some(&item) {
+            self.mins.pop();
+        }
+        if self.maxes.peek() == Some(&item) {
+            self.maxes.pop();
+        }
+        Some(item)
+    }
+
+    /// Returns the top item
+    pub fn peek(&self) -> Option<&T> {
+        self.items.peek()
+    }
+
+    /// Returns the smallest item on the stack
+    pub fn min(&self) -> Option<&T> {
+        self.mins.peek()
+    }
+
+    /// Returns the largest item on the stack
+    pub fn max(&self) -> Option<&T> {
+        self.maxes.peek()
+    }
+
+    /// Returns the number of items
+    pub fn size(&self) -> usize {
+        self.items.size()
+    }
+
+    /// Checks if the stack is empty
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+impl<T: Ord + Clone> Default for MinMaxStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_happy_path_min_and_max_follow_pushes_and_pops() {
+        let mut stack = MinMaxStack::new();
+        stack.push(5);
+        stack.push(2);
+        stack.push(8);
+        stack.push(1);
+        assert_eq!((stack.min(), stack.max()), (Some(&1), Some(&8)));
+
+        assert_eq!(stack.pop(), Some(1));
+        assert_eq!((stack.min(), stack.max()), (Some(&2), Some(&8)));
+        assert_eq!(stack.pop(), Some(8));
+        assert_eq!((stack.min(), stack.max()), (Some(&2), Some(&5)));
+        assert_eq!(stack.peek(), Some(&2));
+        assert_eq!(stack.size(), 2);
+    }
+
+    #[test]
+    fn test_interleaved_operations_match_a_full_scan() {
+        // A fixed pseudo-random sequence of pushes and pops, checked at every step
+        let mut stack = MinMaxStack::new();
+        let mut seed: u32 = 7;
+        for _ in 0..2000 {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            let value = (seed >> 16) % 50;
+            if value.is_multiple_of(3) {
+                stack.pop();
+            } else {
+                stack.push(value);
+            }
+            assert_eq!(stack.min(), stack.items.iter().min());
+            assert_eq!(stack.max(), stack.items.iter().max());
+        }
+    }
+
+    #[test]
+    fn test_edge_case_repeated_extremes() {
+        let mut stack = MinMaxStack::new();
+        for value in [3, 1, 1, 9, 9] {
+            stack.push(value);
+        }
+        stack.pop();
+        assert_eq!(stack.max(), Some(&9), "The other 9 is still there");
+        stack.pop();
+        stack.pop();
+        assert_eq!((stack.min(), stack.max()), (Some(&1), Some(&3)));
+    }
+
+    #[test]
+    fn test_unhappy_path_empty_stack() {
+        let mut stack: MinMaxStack<i32> = MinMaxStack::default();
+        assert_eq!(stack.pop(), None);
+        assert_eq!((stack.min(), stack.max()), (None, None));
+        stack.push(4);
+        stack.pop();
+        assert!(stack.is_empty());
+        assert_eq!((stack.min(), stack.max()), (None, None));
+    }
+}
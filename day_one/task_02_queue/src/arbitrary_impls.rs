@@ -0,0 +1,88 @@
+use quickcheck::{Arbitrary, Gen};
+
+use crate::Queue;
+
+/// Generates queues whose items are split between the two internal stacks
+///
+/// A plain run of `enqueue` calls only ever fills the enqueue stack, so a
+/// random prefix of the items is moved over to the dequeue stack first, the
+/// state a queue reaches after a `dequeue` followed by more `enqueue`s.
+impl<T: Arbitrary> Arbitrary for Queue<T> {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let items: Vec<T> = Vec::arbitrary(g);
+        let split = usize::arbitrary(g) % (items.len() + 1);
+        let mut queue = Queue::new();
+        let mut items = items.into_iter();
+        if split > 0 {
+            queue.enqueue(T::arbitrary(g));
+            enqueue_all(&mut queue, items.by_ref().take(split));
+            // Dequeuing the placeholder moves the prefix onto the dequeue stack
+            queue.dequeue();
+        }
+        enqueue_all(&mut queue, items);
+        queue
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let items: Vec<T> = self.iter().cloned().collect();
+        Box::new(items.shrink().map(|items| {
+            let mut queue = Queue::new();
+            enqueue_all(&mut queue, items);
+            queue
+        }))
+    }
+}
+
+fn enqueue_all<T>(queue: &mut Queue<T>, items: impl IntoIterator<Item = T>) {
+    for item in items {
+        queue.enqueue(item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::quickcheck;
+
+    #[test]
+    fn test_dequeue_order_matches_iteration_order() {
+        fn prop(mut queue: Queue<i32>) -> bool {
+            let expected: Vec<i32> = queue.iter().copied().collect();
+            let dequeued: Vec<i32> = std::iter::from_fn(|| queue.dequeue()).collect();
+            dequeued == expected && queue.is_empty()
+        }
+        quickcheck(prop as fn(Queue<i32>) -> bool);
+    }
+
+    #[test]
+    fn test_enqueue_goes_to_the_back() {
+        fn prop(mut queue: Queue<i32>, item: i32) -> bool {
+            let size = queue.size();
+            queue.enqueue(item);
+            queue.size() == size + 1 && queue.iter().next_back() == Some(&item)
+        }
+        quickcheck(prop as fn(Queue<i32>, i32) -> bool);
+    }
+
+    #[test]
+    fn test_enqueue_batch_matches_enqueue_one_by_one() {
+        fn prop(queue: Queue<i32>, items: Vec<i32>) -> bool {
+            let mut batched = queue.clone();
+            batched.enqueue_batch(items.clone());
+            let mut one_by_one = queue;
+            enqueue_all(&mut one_by_one, items);
+            batched.iter().eq(one_by_one.iter())
+                && batched.stats().elements_moved <= one_by_one.stats().elements_moved
+        }
+        quickcheck(prop as fn(Queue<i32>, Vec<i32>) -> bool);
+    }
+
+    #[test]
+    fn test_generator_reaches_split_state() {
+        let mut g = Gen::new(20);
+        let split = (0..200)
+            .map(|_| Queue::<u8>::arbitrary(&mut g))
+            .any(|q| !q.enqueue_stack.is_empty() && !q.dequeue_stack.is_empty());
+        assert!(split, "Some queues should use both stacks");
+    }
+}
@@ -0,0 +1,243 @@
+use std::error::Error;
+use std::fmt;
+
+use sync_shim::{Condvar, Mutex, MutexGuard};
+
+use crate::Queue;
+
+/// An enqueue onto a full `BoundedQueue` was rejected; the item is handed back
+#[derive(Debug, PartialEq, Eq)]
+pub struct TryEnqueueError<T>(pub T);
+
+impl<T> TryEnqueueError<T> {
+    /// Takes back the item that was not enqueued
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> fmt::Display for TryEnqueueError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "enqueueing onto a full queue")
+    }
+}
+
+impl<T: fmt::Debug> Error for TryEnqueueError<T> {}
+
+/// A fixed-capacity `Queue` shared between threads
+///
+/// `enqueue` waits while the queue is full and `dequeue` waits while it is
+/// empty; `try_enqueue` and `try_dequeue` return straight away instead. The
+/// queue sits behind a `Mutex`, and each side sleeps on its own `Condvar`, so
+/// a dequeue only wakes one waiting producer and an enqueue one waiting consumer.
+///
+/// Every method takes `&self`; share the queue between threads with an `Arc`.
+pub struct BoundedQueue<T> {
+    queue: Mutex<Queue<T>>,
+    capacity: usize,
+    /// Signalled when an item is dequeued
+    not_full: Condvar,
+    /// Signalled when an item is enqueued
+    not_empty: Condvar,
+}
+
+impl<T> BoundedQueue<T> {
+    /// Creates an empty queue that holds at most `capacity` items
+    ///
+    /// Panics if `capacity` is 0, since every `enqueue` would then wait forever.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "bounded queue capacity must be non-zero");
+        BoundedQueue {
+            queue: Mutex::new(Queue::new()),
+            capacity,
+            not_full: Condvar::new(),
+            not_empty: Condvar::new(),
+        }
+    }
+
+    /// Adds `item` to the back, waiting for room if the queue is full
+    pub fn enqueue(&self, item: T) {
+        let mut queue = self.lock();
+        while queue.size() == self.capacity {
+            queue = self.not_full.wait(queue).unwrap();
+        }
+        queue.enqueue(item);
+        drop(queue);
+        self.not_empty.notify_one();
+    }
+
+    /// Adds `item` to the back, or hands it back in the error if the queue is full
+    pub fn try_enqueue(&self, item: T) -> Result<(), TryEnqueueError<T>> {
+        let mut queue = self.lock();
+        if queue.size() == self.capacity {
+            return Err(TryEnqueueError(item));
+        }
+        queue.enqueue(item);
+        drop(queue);
+        self.not_empty.notify_one();
+        Ok(())
+    }
+
+    /// Removes the front item, waiting for one if the queue is empty
+    pub fn dequeue(&self) -> T {
+        let mut queue = self.lock();
+        loop {
+            if let Some(item) = queue.dequeue() {
+                drop(queue);
+                self.not_full.notify_one();
+                return item;
+            }
+            queue = self.not_empty.wait(queue).unwrap();
+        }
+    }
+
+    /// Removes the front item, or returns `None` if the queue is empty
+    pub fn try_dequeue(&self) -> Option<T> {
+        let item = self.lock().dequeue()?;
+        self.not_full.notify_one();
+        Some(item)
+    }
+
+    /// Returns the number of items queued right now
+    pub fn len(&self) -> usize {
+        self.lock().size()
+    }
+
+    /// Checks if the queue is empty right now
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Returns the most items the queue holds
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Queue<T>> {
+        self.queue.lock().unwrap()
+    }
+}
+
+impl<T> fmt::Debug for BoundedQueue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoundedQueue")
+            .field("len", &self.len())
+            .field("capacity", &self.capacity)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn test_happy_path_try_enqueue_until_full() {
+        let queue = BoundedQueue::new(2);
+        assert_eq!(queue.try_enqueue(1), Ok(()));
+        assert_eq!(queue.try_enqueue(2), Ok(()));
+        let error = queue.try_enqueue(3).unwrap_err();
+        assert_eq!(error.to_string(), "enqueueing onto a full queue");
+        assert_eq!(error.into_inner(), 3);
+
+        assert_eq!(queue.try_dequeue(), Some(1));
+        assert_eq!(queue.try_enqueue(4), Ok(()), "Dequeuing makes room again");
+        assert_eq!((queue.len(), queue.capacity()), (2, 2));
+        assert_eq!(queue.dequeue(), 2);
+        assert_eq!(queue.dequeue(), 4);
+        assert_eq!(queue.try_dequeue(), None);
+    }
+
+    #[test]
+    fn test_producers_and_consumers_see_every_item_in_order() {
+        // A small capacity keeps the producers blocking on a full queue
+        let queue = Arc::new(BoundedQueue::new(4));
+        let producers: Vec<_> = (0..3u32)
+            .map(|p| {
+                let queue = Arc::clone(&queue);
+                thread::spawn(move || {
+                    for i in 0..1_000 {
+                        queue.enqueue((p, i));
+                    }
+                })
+            })
+            .collect();
+        let consumers: Vec<_> = (0..2)
+            .map(|_| {
+                let queue = Arc::clone(&queue);
+                thread::spawn(move || (0..1_500).map(|_| queue.dequeue()).collect::<Vec<_>>())
+            })
+            .collect();
+
+        for producer in producers {
+            producer.join().unwrap();
+        }
+        let mut received = Vec::new();
+        for consumer in consumers {
+            let items = consumer.join().unwrap();
+            // Each consumer sees any one producer's items in the order they were sent
+            for p in 0..3 {
+                let from_p: Vec<_> = items
+                    .iter()
+                    .filter(|(q, _)| *q == p)
+                    .map(|(_, i)| *i)
+                    .collect();
+                assert!(from_p.windows(2).all(|pair| pair[0] < pair[1]));
+            }
+            received.extend(items);
+        }
+        received.sort_unstable();
+        let expected: Vec<_> = (0..3)
+            .flat_map(|p| (0..1_000).map(move |i| (p, i)))
+            .collect();
+        assert_eq!(received, expected);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_dequeue_waits_for_an_item() {
+        let queue = Arc::new(BoundedQueue::new(1));
+        let consumer = {
+            let queue = Arc::clone(&queue);
+            thread::spawn(move || queue.dequeue())
+        };
+        thread::sleep(Duration::from_millis(20));
+        queue.enqueue("late");
+        assert_eq!(consumer.join().unwrap(), "late");
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity must be non-zero")]
+    fn test_unhappy_path_zero_capacity() {
+        BoundedQueue::<i32>::new(0);
+    }
+}
+
+// Run with `RUSTFLAGS="--cfg loom" cargo test --lib --release -p task_02_queue`
+#[cfg(all(test, loom))]
+mod loom_tests {
+    use super::*;
+    use sync_shim::{thread, Arc};
+
+    #[test]
+    fn loom_blocked_producer_is_woken_by_dequeue() {
+        // With room for one item, the second enqueue must wait for the first
+        // dequeue and then be woken rather than sleep forever
+        sync_shim::model(|| {
+            let queue = Arc::new(BoundedQueue::new(1));
+            let producer = {
+                let queue = Arc::clone(&queue);
+                thread::spawn(move || {
+                    queue.enqueue(1);
+                    queue.enqueue(2);
+                })
+            };
+            assert_eq!(queue.dequeue(), 1);
+            assert_eq!(queue.dequeue(), 2);
+            producer.join().unwrap();
+        });
+    }
+}
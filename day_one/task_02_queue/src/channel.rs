@@ -0,0 +1,282 @@
+//! Unbounded multi-producer multi-consumer channel on a `Queue`
+//!
+//! The queue sits behind a `Mutex` shared by every handle, together with a
+//! count of live senders and receivers. Receivers sleep on a `Condvar` that
+//! each send signals. The error types are the ones task 22's channels use.
+
+use sync_shim::{Arc, Condvar, Mutex, MutexGuard};
+
+pub use task_22_channels::error::{RecvError, SendError, TryRecvError};
+
+use crate::Queue;
+
+struct State<T> {
+    queue: Queue<T>,
+    senders: usize,
+    receivers: usize,
+}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+    /// Signalled when a value is sent or the last sender drops
+    available: Condvar,
+}
+
+impl<T> Shared<T> {
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap()
+    }
+}
+
+/// Creates an unbounded multi-producer multi-consumer channel
+///
+/// Both halves can be cloned; each value is received by exactly one receiver,
+/// in the order it was sent. Receivers see the channel disconnect once every
+/// `Sender` is dropped and the queue is drained, and senders once every
+/// `Receiver` is dropped.
+pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            queue: Queue::new(),
+            senders: 1,
+            receivers: 1,
+        }),
+        available: Condvar::new(),
+    });
+    (
+        Sender {
+            shared: Arc::clone(&shared),
+        },
+        Receiver { shared },
+    )
+}
+
+/// The sending half of a queue channel
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Sender<T> {
+    /// Sends `value`, or hands it back if every receiver has been dropped
+    ///
+    /// The channel is unbounded, so this never waits.
+    pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+        let mut state = self.shared.lock();
+        if state.receivers == 0 {
+            return Err(SendError(value));
+        }
+        state.queue.enqueue(value);
+        drop(state);
+        self.shared.available.notify_one();
+        Ok(())
+    }
+
+    /// Checks if every receiver has been dropped
+    pub fn is_disconnected(&self) -> bool {
+        self.shared.lock().receivers == 0
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.shared.lock().senders += 1;
+        Sender {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.senders -= 1;
+        if state.senders == 0 {
+            drop(state);
+            // Wake every waiting receiver so each can see the disconnect
+            self.shared.available.notify_all();
+        }
+    }
+}
+
+/// The receiving half of a queue channel
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Receiver<T> {
+    /// Receives without blocking
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        let mut state = self.shared.lock();
+        match state.queue.dequeue() {
+            Some(value) => Ok(value),
+            None if state.senders == 0 => Err(TryRecvError::Disconnected),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    /// Receives, waiting for a value; fails once every sender is gone and the channel is drained
+    pub fn recv(&self) -> Result<T, RecvError> {
+        let mut state = self.shared.lock();
+        loop {
+            if let Some(value) = state.queue.dequeue() {
+                return Ok(value);
+            }
+            if state.senders == 0 {
+                return Err(RecvError);
+            }
+            state = self.shared.available.wait(state).unwrap();
+        }
+    }
+
+    /// Iterates over received values until every sender disconnects
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        std::iter::from_fn(|| self.recv().ok())
+    }
+
+    /// Checks if every sender has been dropped
+    pub fn is_disconnected(&self) -> bool {
+        self.shared.lock().senders == 0
+    }
+
+    /// Returns the number of values waiting to be received
+    pub fn len(&self) -> usize {
+        self.shared.lock().queue.size()
+    }
+
+    /// Checks if no values are waiting to be received
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Self {
+        self.shared.lock().receivers += 1;
+        Receiver {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.shared.lock().receivers -= 1;
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn test_happy_path_every_value_received_once() {
+        let (tx, rx) = channel();
+        let producers: Vec<_> = (0..4u32)
+            .map(|p| {
+                let tx = tx.clone();
+                thread::spawn(move || {
+                    for i in 0..2_500 {
+                        tx.send(p * 10_000 + i).unwrap();
+                    }
+                })
+            })
+            .collect();
+        drop(tx);
+        let consumers: Vec<_> = (0..3)
+            .map(|_| {
+                let rx = rx.clone();
+                thread::spawn(move || rx.iter().collect::<Vec<u32>>())
+            })
+            .collect();
+        drop(rx);
+
+        for producer in producers {
+            producer.join().unwrap();
+        }
+        let mut seen = HashSet::new();
+        for consumer in consumers {
+            let values = consumer.join().unwrap();
+            // Each consumer sees any one producer's values in the order they were sent
+            for p in 0..4 {
+                let from_p: Vec<_> = values.iter().filter(|&&v| v / 10_000 == p).collect();
+                assert!(from_p.windows(2).all(|pair| pair[0] < pair[1]));
+            }
+            for value in values {
+                assert!(seen.insert(value), "{value} was received twice");
+            }
+        }
+        assert_eq!(seen.len(), 10_000);
+    }
+
+    #[test]
+    fn test_recv_waits_for_a_send() {
+        let (tx, rx) = channel();
+        let receiver = thread::spawn(move || rx.recv());
+        thread::sleep(Duration::from_millis(20));
+        tx.send("late").unwrap();
+        assert_eq!(receiver.join().unwrap(), Ok("late"));
+    }
+
+    #[test]
+    fn test_dropping_the_last_sender_wakes_blocked_receivers() {
+        let (tx, rx) = channel::<i32>();
+        let receivers: Vec<_> = (0..2)
+            .map(|_| {
+                let rx = rx.clone();
+                thread::spawn(move || rx.recv())
+            })
+            .collect();
+        thread::sleep(Duration::from_millis(20));
+        drop(tx.clone());
+        assert!(
+            !rx.is_disconnected(),
+            "A clone dropping leaves the channel open"
+        );
+        drop(tx);
+        for receiver in receivers {
+            assert_eq!(receiver.join().unwrap(), Err(RecvError));
+        }
+    }
+
+    #[test]
+    fn test_unhappy_path_disconnects() {
+        let (tx, rx) = channel();
+        tx.send(1).unwrap();
+        assert_eq!(rx.try_recv(), Ok(1));
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+
+        tx.send(2).unwrap();
+        drop(tx);
+        assert_eq!((rx.len(), rx.is_disconnected()), (1, true));
+        assert_eq!(rx.recv(), Ok(2), "Queued values outlive the senders");
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Disconnected));
+
+        let (tx, rx) = channel();
+        drop(rx);
+        assert!(tx.is_disconnected());
+        assert_eq!(tx.send("unread"), Err(SendError("unread")));
+    }
+}
+
+// Run with `RUSTFLAGS="--cfg loom" cargo test --lib --release -p task_02_queue`
+#[cfg(all(test, loom))]
+mod loom_tests {
+    use super::*;
+    use sync_shim::thread;
+
+    #[test]
+    fn loom_last_value_is_received_before_disconnect() {
+        // The sender drops right after its send; the receiver must get the
+        // value and then the disconnect, never sleep past either
+        sync_shim::model(|| {
+            let (tx, rx) = channel();
+            let sender = thread::spawn(move || tx.send(1).unwrap());
+            assert_eq!(rx.recv(), Ok(1));
+            assert_eq!(rx.recv(), Err(RecvError));
+            sender.join().unwrap();
+        });
+    }
+}
@@ -0,0 +1,270 @@
+use std::fmt;
+use std::mem;
+
+/// Counters for the moves an `IncrementalQueue` spreads over its operations
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IncrementalStats {
+    /// Rotations started, each one rebuilding the front stack
+    pub rotations: u64,
+    /// Elements moved by all rotations together
+    pub elements_moved: u64,
+    /// The most elements a single `enqueue` or `dequeue` has moved
+    pub max_moves_per_op: usize,
+}
+
+/// A rotation in progress: `next` is being built as `front ++ rev(back)`
+struct Rotation<T> {
+    /// The back stack as it was when the rotation started; popped newest first
+    back: Vec<T>,
+    /// The next front stack, oldest element on top once it is finished
+    next: Vec<Option<T>>,
+    /// How many front slots, counted from the bottom, are already in `next`
+    moved: usize,
+}
+
+/// FIFO queue on stacks whose `enqueue` and `dequeue` each move at most `k`
+/// elements
+///
+/// `Queue` moves the whole enqueue stack across when its dequeue stack runs
+/// dry, which is O(1) amortized but O(n) for that one `dequeue`. This queue
+/// starts the move early instead, as soon as the back stack outgrows the
+/// front, and spreads it over the following operations, `k` elements at a
+/// time. A rotation builds the next front stack out of the back stack,
+/// newest first, followed by the current front read from the bottom up.
+/// Meanwhile dequeues keep popping the current front from the top; when the
+/// two meet, every front element has either been dequeued or moved, and the
+/// next front takes over. A front element that has been moved leaves an
+/// empty slot behind, which is why the front holds `Option<T>`.
+///
+/// A rotation starts once the back holds one more element than the front,
+/// so with `f` elements in the front it has `f + 1` back elements to move,
+/// plus whichever front elements are not dequeued first. Even at one move
+/// per operation the back part is done before `f` dequeues can empty the
+/// front, and by then the front part has nothing left to move. A larger `k`
+/// only finishes rotations sooner. `stats().max_moves_per_op` records the
+/// most any single operation has done.
+pub struct IncrementalQueue<T> {
+    /// Top is the oldest element; during a rotation the bottom `moved` slots are empty
+    front: Vec<Option<T>>,
+    back: Vec<T>,
+    rotation: Option<Rotation<T>>,
+    moves_per_op: usize,
+    stats: IncrementalStats,
+}
+
+impl<T> IncrementalQueue<T> {
+    /// Creates an empty queue that moves at most one element per operation
+    pub fn new() -> Self {
+        Self::with_moves_per_op(1)
+    }
+
+    /// Creates an empty queue that moves at most `k` elements per operation
+    ///
+    /// Panics if `k` is 0, since no rotation would ever make progress.
+    pub fn with_moves_per_op(k: usize) -> Self {
+        assert!(
+            k > 0,
+            "an incremental queue must move at least one element per operation"
+        );
+        IncrementalQueue {
+            front: Vec::new(),
+            back: Vec::new(),
+            rotation: None,
+            moves_per_op: k,
+            stats: IncrementalStats::default(),
+        }
+    }
+
+    /// Adds an element to the back of the queue
+    pub fn enqueue(&mut self, item: T) {
+        self.back.push(item);
+        self.step();
+    }
+
+    /// Removes an element from the front of the queue if available
+    pub fn dequeue(&mut self) -> Option<T> {
+        let item = self.front.pop().map(|slot| {
+            // Moved slots sit at the bottom, and a rotation finishes before the pops reach them
+            slot.expect("the front stack's top slot is never moved")
+        });
+        self.step();
+        item
+    }
+
+    /// Returns the element at the front of the queue without removing it
+    pub fn front(&self) -> Option<&T> {
+        self.front.last()?.as_ref()
+    }
+
+    /// Returns the number of elements in the queue
+    pub fn size(&self) -> usize {
+        let (in_front, in_rotation) = match &self.rotation {
+            // The moved front slots are counted in `next`
+            Some(rotation) => (
+                self.front.len() - rotation.moved,
+                rotation.back.len() + rotation.next.len(),
+            ),
+            None => (self.front.len(), 0),
+        };
+        in_front + in_rotation + self.back.len()
+    }
+
+    /// Checks if the queue is empty
+    pub fn is_empty(&self) -> bool {
+        self.size() == 0
+    }
+
+    /// Returns the move counters accumulated since the queue was created
+    pub fn stats(&self) -> IncrementalStats {
+        self.stats
+    }
+
+    /// Does up to `k` moves of the current rotation, starting one if the back
+    /// stack has outgrown the front
+    fn step(&mut self) {
+        if self.rotation.is_none() {
+            if self.back.len() <= self.front.len() {
+                return;
+            }
+            let back = mem::take(&mut self.back);
+            self.rotation = Some(Rotation {
+                next: Vec::with_capacity(back.len() + self.front.len()),
+                back,
+                moved: 0,
+            });
+            self.stats.rotations += 1;
+        }
+        let Some(rotation) = self.rotation.as_mut() else {
+            return;
+        };
+
+        let mut moves = 0;
+        while moves < self.moves_per_op {
+            if let Some(item) = rotation.back.pop() {
+                rotation.next.push(Some(item));
+            } else if rotation.moved < self.front.len() {
+                // Bottom up, so the oldest remaining front element ends up on top
+                let slot = self.front[rotation.moved].take();
+                rotation.next.push(slot);
+                rotation.moved += 1;
+            } else {
+                break;
+            }
+            moves += 1;
+        }
+        self.stats.elements_moved += moves as u64;
+        self.stats.max_moves_per_op = self.stats.max_moves_per_op.max(moves);
+
+        if rotation.back.is_empty() && rotation.moved == self.front.len() {
+            // Every front element was either dequeued or moved, so only empty slots are left
+            if let Some(rotation) = self.rotation.take() {
+                self.front = rotation.next;
+            }
+        }
+    }
+}
+
+impl<T> Default for IncrementalQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for IncrementalQueue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IncrementalQueue")
+            .field("size", &self.size())
+            .field("front", &self.front())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    /// Runs `ops` (true to enqueue) against a `VecDeque`, checking every
+    /// dequeue, and returns the stats
+    fn check_against_vec_deque(k: usize, ops: impl IntoIterator<Item = bool>) -> IncrementalStats {
+        let mut queue = IncrementalQueue::with_moves_per_op(k);
+        let mut model = VecDeque::new();
+        for (step, enqueue) in ops.into_iter().enumerate() {
+            if enqueue {
+                queue.enqueue(step);
+                model.push_back(step);
+            } else {
+                assert_eq!(queue.dequeue(), model.pop_front());
+            }
+            assert_eq!(queue.size(), model.len());
+            assert_eq!(queue.front(), model.front());
+        }
+        queue.stats()
+    }
+
+    #[test]
+    fn test_happy_path_fifo_order() {
+        let mut queue = IncrementalQueue::new();
+        for i in 1..=5 {
+            queue.enqueue(i);
+        }
+        assert_eq!(queue.front(), Some(&1));
+        assert_eq!(queue.dequeue(), Some(1));
+        queue.enqueue(6);
+        let rest: Vec<_> = std::iter::from_fn(|| queue.dequeue()).collect();
+        assert_eq!(rest, vec![2, 3, 4, 5, 6]);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_no_operation_moves_more_than_k() {
+        // The pattern that makes `Queue` move everything in one dequeue
+        let burst = (0..10_000).map(|_| true).chain((0..10_000).map(|_| false));
+        let stats = check_against_vec_deque(1, burst);
+        assert_eq!(stats.max_moves_per_op, 1);
+        assert!(stats.rotations > 1);
+
+        // A fixed pseudo-random mix, with bursts of each kind, for several k
+        for k in [1, 2, 8] {
+            let mut seed: u32 = 7;
+            let ops = (0..20_000).map(|i| {
+                seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                let bias = if (i / 1_000) % 2 == 0 { 3 } else { 1 };
+                (seed >> 16) % 4 < bias
+            });
+            let stats = check_against_vec_deque(k, ops);
+            assert!(stats.max_moves_per_op <= k, "k = {k}: {stats:?}");
+        }
+    }
+
+    #[test]
+    fn test_every_short_sequence_stays_within_one_move() {
+        // Every enqueue/dequeue sequence up to 14 operations long
+        for len in 1..=14u32 {
+            for bits in 0u32..1 << len {
+                let ops = (0..len).map(|i| (bits >> i) & 1 == 1);
+                assert!(check_against_vec_deque(1, ops).max_moves_per_op <= 1);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one element")]
+    fn test_unhappy_path_zero_moves_per_op_is_rejected() {
+        IncrementalQueue::<i32>::with_moves_per_op(0);
+    }
+
+    #[test]
+    fn test_edge_case_empty_queue() {
+        let mut queue: IncrementalQueue<String> = IncrementalQueue::default();
+        assert_eq!((queue.dequeue(), queue.front()), (None, None));
+        queue.enqueue("only".to_string());
+        assert_eq!(queue.size(), 1);
+        assert_eq!(queue.dequeue().as_deref(), Some("only"));
+        assert_eq!(queue.dequeue(), None);
+        assert_eq!(
+            format!("{:?}", queue),
+            "IncrementalQueue { size: 0, front: None }"
+        );
+    }
+}
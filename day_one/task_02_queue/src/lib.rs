@@ -0,0 +1,382 @@
+use std::fmt;
+use std::iter::Rev;
+use std::slice;
+
+#[cfg(feature = "testing")]
+mod arbitrary_impls;
+mod bounded;
+pub mod channel;
+mod incremental;
+#[cfg(feature = "serde")]
+mod serde_impls;
+
+pub use bounded::{BoundedQueue, TryEnqueueError};
+pub use incremental::{IncrementalQueue, IncrementalStats};
+
+/// Counters for the work a `Queue` does moving elements between its stacks
+///
+/// Each element is moved from the enqueue stack to the dequeue stack at most
+/// once, so `elements_moved` never exceeds the number of elements enqueued.
+/// That is the amortized O(1) bound on `dequeue`, and these counters let
+/// tests and benchmarks check it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueueStats {
+    /// Times `dequeue` found the dequeue stack empty and refilled it
+    pub transfers: u64,
+    /// Elements moved across by all those refills together
+    pub elements_moved: u64,
+    /// The most elements either stack has held at once
+    pub max_stack_depth: usize,
+}
+
+#[derive(Clone)]
+pub struct Queue<T> {
+    enqueue_stack: Vec<T>,
+    dequeue_stack: Vec<T>,
+    stats: QueueStats,
+}
+
+impl<T> Queue<T> {
+    /// Creates an empty queue
+    pub fn new() -> Self {
+        Queue {
+            enqueue_stack: Vec::new(),
+            dequeue_stack: Vec::new(),
+            stats: QueueStats::default(),
+        }
+    }
+
+    /// Adds an element to the back of the queue
+    pub fn enqueue(&mut self, item: T) {
+        self.enqueue_stack.push(item);
+        self.record_depth(self.enqueue_stack.len());
+    }
+
+    /// Adds every item to the back of the queue, in order
+    ///
+    /// Into an empty queue the batch goes straight onto the dequeue stack in
+    /// reverse, so it is never transferred. Otherwise it is appended to the
+    /// enqueue stack in one `extend`, which reserves space once when the
+    /// iterator knows its length, and moves across with the next transfer.
+    pub fn enqueue_batch<I: IntoIterator<Item = T>>(&mut self, items: I) {
+        if self.is_empty() {
+            self.dequeue_stack.extend(items);
+            self.dequeue_stack.reverse();
+            self.record_depth(self.dequeue_stack.len());
+        } else {
+            self.enqueue_stack.extend(items);
+            self.record_depth(self.enqueue_stack.len());
+        }
+    }
+
+    /// Removes an element from the front of the queue if available
+    pub fn dequeue(&mut self) -> Option<T> {
+        if self.dequeue_stack.is_empty() && !self.enqueue_stack.is_empty() {
+            // Transfer elements if dequeue_stack is empty
+            self.stats.transfers += 1;
+            self.stats.elements_moved += self.enqueue_stack.len() as u64;
+            while let Some(item) = self.enqueue_stack.pop() {
+                self.dequeue_stack.push(item);
+            }
+            self.record_depth(self.dequeue_stack.len());
+        }
+        self.dequeue_stack.pop()
+    }
+
+    /// Returns the transfer counters accumulated since the queue was created
+    ///
+    /// `clear` and `drain` leave them alone; clones start with a copy.
+    pub fn stats(&self) -> QueueStats {
+        self.stats
+    }
+
+    /// Returns the number of elements in the queue
+    pub fn size(&self) -> usize {
+        self.enqueue_stack.len() + self.dequeue_stack.len()
+    }
+
+    /// Checks if the queue is empty
+    pub fn is_empty(&self) -> bool {
+        self.size() == 0
+    }
+
+    /// Removes every element
+    pub fn clear(&mut self) {
+        self.enqueue_stack.clear();
+        self.dequeue_stack.clear();
+    }
+
+    /// Removes every element, yielding them from front to back
+    ///
+    /// The queue is empty afterwards even if the iterator is dropped early.
+    pub fn drain(&mut self) -> impl Iterator<Item = T> + '_ {
+        // Same order as `iter`: the dequeue stack top-down, then the enqueue stack
+        self.dequeue_stack
+            .drain(..)
+            .rev()
+            .chain(self.enqueue_stack.drain(..))
+    }
+
+    /// Returns the element at the front of the queue without removing it
+    pub fn front(&self) -> Option<&T> {
+        // The top of the dequeue stack, or the oldest enqueued element if that stack is empty
+        self.dequeue_stack
+            .last()
+            .or_else(|| self.enqueue_stack.first())
+    }
+
+    /// Returns the element at the back of the queue without removing it
+    pub fn back(&self) -> Option<&T> {
+        self.enqueue_stack
+            .last()
+            .or_else(|| self.dequeue_stack.first())
+    }
+
+    fn record_depth(&mut self, depth: usize) {
+        self.stats.max_stack_depth = self.stats.max_stack_depth.max(depth);
+    }
+
+    /// Iterates from the front of the queue to the back
+    pub fn iter(&self) -> Iter<'_, T> {
+        // The dequeue stack holds the front, top first, followed by everything enqueued since
+        Iter {
+            front: self.dequeue_stack.iter().rev(),
+            back: self.enqueue_stack.iter(),
+        }
+    }
+}
+
+/// Borrowing iterator over a `Queue`, front to back; `.rev()` walks back to front
+pub struct Iter<'a, T> {
+    front: Rev<slice::Iter<'a, T>>,
+    back: slice::Iter<'a, T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.front.next().or_else(|| self.back.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.front.len() + self.back.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        self.back.next_back().or_else(|| self.front.next_back())
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<'a, T> IntoIterator for &'a Queue<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+// Shows the elements front to back, however they are split between the two stacks
+impl<T: fmt::Debug> fmt::Debug for Queue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> Default for Queue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FromIterator<T> for Queue<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut queue = Queue::new();
+        queue.enqueue_batch(iter);
+        queue
+    }
+}
+
+impl<T> Extend<T> for Queue<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.enqueue_batch(iter);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Queue, QueueStats};
+
+    #[test]
+    fn test_queue_operations() {
+        let mut queue = Queue::new();
+        assert!(queue.is_empty());
+        assert_eq!(queue.size(), 0);
+
+        queue.enqueue(1);
+        queue.enqueue(2);
+        assert_eq!(queue.size(), 2);
+        assert!(!queue.is_empty());
+
+        assert_eq!(queue.dequeue(), Some(1));
+        assert_eq!(queue.size(), 1);
+        assert_eq!(queue.dequeue(), Some(2));
+        assert_eq!(queue.size(), 0);
+        assert!(queue.is_empty());
+
+        // Test enqueue and dequeue after emptying
+        queue.enqueue(3);
+        assert_eq!(queue.dequeue(), Some(3));
+        assert_eq!(queue.dequeue(), None); // Queue should be empty now
+    }
+
+    #[test]
+    fn test_iter_front_to_back_and_clear() {
+        let mut queue = Queue::new();
+        queue.enqueue(1);
+        queue.enqueue(2);
+        assert_eq!(queue.dequeue(), Some(1)); // Moves 2 onto the dequeue stack
+        queue.enqueue(3);
+        queue.enqueue(4);
+        assert_eq!(queue.iter().collect::<Vec<_>>(), vec![&2, &3, &4]);
+
+        queue.clear();
+        assert!(queue.is_empty());
+        assert_eq!(queue.iter().count(), 0);
+    }
+
+    #[test]
+    fn test_drain_empties_in_fifo_order() {
+        let mut queue = Queue::new();
+        for i in 1..=3 {
+            queue.enqueue(i);
+        }
+        assert_eq!(queue.dequeue(), Some(1)); // 2 and 3 now sit on the dequeue stack
+        queue.enqueue(4);
+        queue.enqueue(5);
+        assert_eq!(queue.drain().collect::<Vec<_>>(), vec![2, 3, 4, 5]);
+        assert!(queue.is_empty());
+
+        queue.enqueue(6);
+        queue.enqueue(7);
+        assert_eq!(queue.drain().next(), Some(6));
+        assert!(
+            queue.is_empty(),
+            "Dropping the drain early still empties the queue"
+        );
+        assert_eq!(queue.dequeue(), None);
+    }
+
+    #[test]
+    fn test_front_back_and_reverse_iteration_across_both_stacks() {
+        let mut queue = Queue::new();
+        queue.enqueue(1);
+        assert_eq!((queue.front(), queue.back()), (Some(&1), Some(&1)));
+        queue.enqueue(2);
+        queue.enqueue(3);
+        assert_eq!((queue.front(), queue.back()), (Some(&1), Some(&3)));
+
+        assert_eq!(queue.dequeue(), Some(1)); // 2 and 3 now sit on the dequeue stack
+        assert_eq!((queue.front(), queue.back()), (Some(&2), Some(&3)));
+        queue.enqueue(4);
+        assert_eq!((queue.front(), queue.back()), (Some(&2), Some(&4)));
+
+        let mut iter = queue.iter();
+        assert_eq!(iter.len(), 3);
+        assert_eq!((iter.next(), iter.next_back()), (Some(&2), Some(&4)));
+        assert_eq!(iter.next_back(), Some(&3), "Reaches into the dequeue stack");
+        assert_eq!(iter.next(), None);
+        assert_eq!(
+            (&queue).into_iter().rev().collect::<Vec<_>>(),
+            vec![&4, &3, &2]
+        );
+    }
+
+    #[test]
+    fn test_batches_keep_fifo_order_with_fewer_transfers() {
+        let mut queue: Queue<i32> = (1..=3).collect();
+        assert_eq!(queue.dequeue(), Some(1));
+        queue.extend([4, 5]);
+        queue.enqueue_batch(vec![6]);
+        assert_eq!(
+            queue.iter().copied().collect::<Vec<_>>(),
+            vec![2, 3, 4, 5, 6]
+        );
+        let dequeued: Vec<_> = std::iter::from_fn(|| queue.dequeue()).collect();
+        assert_eq!(dequeued, vec![2, 3, 4, 5, 6]);
+        assert_eq!(
+            (queue.stats().transfers, queue.stats().elements_moved),
+            (1, 3),
+            "Only the items that joined a non-empty queue were moved across"
+        );
+
+        // One batch into an empty queue is dequeued without any transfer
+        let mut batched = Queue::new();
+        batched.enqueue_batch(0..1000);
+        while batched.dequeue().is_some() {}
+        assert_eq!(batched.stats().elements_moved, 0);
+        batched.enqueue_batch(std::iter::empty());
+        assert!(batched.is_empty());
+    }
+
+    #[test]
+    fn test_stats_count_each_transfer() {
+        let mut queue = Queue::new();
+        for i in 0..4 {
+            queue.enqueue(i);
+        }
+        queue.dequeue(); // Moves all 4 across
+        queue.enqueue(4);
+        queue.dequeue(); // Served from the dequeue stack
+        assert_eq!(
+            queue.stats(),
+            QueueStats {
+                transfers: 1,
+                elements_moved: 4,
+                max_stack_depth: 4
+            }
+        );
+        while queue.dequeue().is_some() {} // One more transfer, for the 4
+        queue.dequeue(); // Nothing to move, so not a transfer
+        assert_eq!(
+            (queue.stats().transfers, queue.stats().elements_moved),
+            (2, 5)
+        );
+    }
+
+    #[test]
+    fn test_each_element_is_moved_at_most_once() {
+        // A fixed pseudo-random mix of enqueues and dequeues
+        let mut queue = Queue::new();
+        let mut enqueued = 0u64;
+        let mut seed: u32 = 3;
+        for step in 0..5000 {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            if (seed >> 16).is_multiple_of(3) {
+                queue.dequeue();
+            } else {
+                queue.enqueue(step);
+                enqueued += 1;
+            }
+        }
+        let stats = queue.stats();
+        assert!(stats.elements_moved <= enqueued);
+        assert!(stats.transfers <= stats.elements_moved);
+        assert!(stats.max_stack_depth <= enqueued as usize);
+    }
+
+    #[test]
+    fn test_edge_case_peeking_an_empty_queue() {
+        let mut queue: Queue<i32> = Queue::new();
+        assert_eq!((queue.front(), queue.back()), (None, None));
+        queue.enqueue(5);
+        queue.dequeue();
+        assert_eq!((queue.front(), queue.back()), (None, None));
+    }
+}
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::Queue;
+
+/// Serializes as a sequence from front to back, whatever the internal stack split
+impl<T: Serialize> Serialize for Queue<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let front = self.dequeue_stack.iter().rev();
+        serializer.collect_seq(front.chain(self.enqueue_stack.iter()))
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Queue<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let items: Vec<T> = Vec::deserialize(deserializer)?;
+        Ok(items.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_keeps_fifo_order() {
+        let mut queue = Queue::new();
+        for i in 1..=4 {
+            queue.enqueue(i);
+        }
+        // Split the items across both internal stacks
+        assert_eq!(queue.dequeue(), Some(1));
+        queue.enqueue(5);
+
+        let json = serde_json::to_string(&queue).unwrap();
+        assert_eq!(json, "[2,3,4,5]");
+
+        let mut restored: Queue<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.size(), 4);
+        let drained: Vec<_> = std::iter::from_fn(|| restored.dequeue()).collect();
+        assert_eq!(drained, vec![2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_edge_case_empty_queue() {
+        let json = serde_json::to_string(&Queue::<String>::new()).unwrap();
+        assert_eq!(json, "[]");
+        let restored: Queue<String> = serde_json::from_str(&json).unwrap();
+        assert!(restored.is_empty());
+    }
+}
@@ -1,20 +1,14 @@
-fn reverse_vector<T>(vec: &mut Vec<T>) {
+pub fn reverse_vector<T>(vec: &mut [T]) {
     let mut left = 0;
-    let mut right = vec.len().checked_sub(1).unwrap_or(0); // Use checked_sub to prevent overflow
+    let mut right = vec.len().saturating_sub(1); // Use saturating_sub to prevent overflow
 
     while left < right {
         vec.swap(left, right);
-        left = left.checked_add(1).unwrap_or(usize::MAX); // Using checked_add
-        right = right.checked_sub(1).unwrap_or(0); // Use checked_sub again for decrementing right
+        left = left.saturating_add(1); // Using saturating_add
+        right = right.saturating_sub(1); // Use saturating_sub again for decrementing right
     }
 }
 
-fn main() {
-    let mut numbers = vec![1, 2, 3, 4, 5];
-    reverse_vector(&mut numbers);
-    println!("Reversed: {:?}", numbers);
-}
-
 #[cfg(test)]
 mod tests {
     use super::reverse_vector;
@@ -58,4 +52,4 @@ mod tests {
         reverse_vector(&mut large_vec);
         assert_eq!(large_vec, expected);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,244 @@
+use quickcheck::{Arbitrary, Gen};
+
+use crate::CircularBuffer;
+
+/// Generates buffers at the fill levels where ring buffers go wrong
+///
+/// The start is rotated by a random number of push/pop pairs, so head and
+/// tail can sit anywhere and the contents may wrap past the end of the
+/// storage. Then the buffer is left empty or partly filled, one short of
+/// full, exactly full, or overfilled so the oldest items were overwritten.
+impl<T: Arbitrary> Arbitrary for CircularBuffer<T> {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let capacity = usize::arbitrary(g) % g.size().max(1) + 1;
+        let mut buffer = CircularBuffer::new(capacity);
+        for _ in 0..usize::arbitrary(g) % capacity {
+            buffer.push(T::arbitrary(g));
+            buffer.pop();
+        }
+        let len = match u8::arbitrary(g) % 4 {
+            0 => usize::arbitrary(g) % (capacity + 1),
+            1 => capacity - 1,
+            2 => capacity,
+            _ => capacity + 1 + usize::arbitrary(g) % capacity,
+        };
+        for _ in 0..len {
+            buffer.push(T::arbitrary(g));
+        }
+        buffer
+    }
+
+    /// Keeps the capacity and shrinks the items, rebuilt from an unrotated start
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let capacity = self.capacity;
+        let items: Vec<T> = self.iter().cloned().collect();
+        Box::new(items.shrink().map(move |items| {
+            let mut buffer = CircularBuffer::new(capacity);
+            for item in items {
+                buffer.push(item);
+            }
+            buffer
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CircularByteBuffer, OverwritePolicy, StaticCircularBuffer};
+    use quickcheck::quickcheck;
+
+    #[test]
+    fn test_size_never_exceeds_capacity() {
+        fn prop(buffer: CircularBuffer<i32>) -> bool {
+            buffer.size() <= buffer.capacity && buffer.iter().count() == buffer.size()
+        }
+        quickcheck(prop as fn(CircularBuffer<i32>) -> bool);
+    }
+
+    #[test]
+    fn test_pop_order_matches_iteration_order() {
+        fn prop(mut buffer: CircularBuffer<i32>) -> bool {
+            let expected: Vec<i32> = buffer.iter().copied().collect();
+            let popped: Vec<i32> = std::iter::from_fn(|| buffer.pop()).collect();
+            popped == expected && buffer.is_empty()
+        }
+        quickcheck(prop as fn(CircularBuffer<i32>) -> bool);
+    }
+
+    #[test]
+    fn test_push_onto_full_buffer_drops_the_oldest() {
+        fn prop(mut buffer: CircularBuffer<i32>, item: i32) -> bool {
+            let mut expected: Vec<i32> = buffer.iter().copied().collect();
+            if expected.len() == buffer.capacity {
+                expected.remove(0);
+            }
+            expected.push(item);
+            buffer.push(item);
+            buffer.iter().copied().collect::<Vec<_>>() == expected
+        }
+        quickcheck(prop as fn(CircularBuffer<i32>, i32) -> bool);
+    }
+
+    #[test]
+    fn test_get_and_as_slices_agree_with_iteration() {
+        fn prop(buffer: CircularBuffer<i32>) -> bool {
+            let (first, second) = buffer.as_slices();
+            let from_slices: Vec<Option<&i32>> =
+                first.iter().chain(second).map(Option::as_ref).collect();
+            let from_get: Vec<Option<&i32>> = (0..buffer.size()).map(|i| buffer.get(i)).collect();
+            let expected: Vec<Option<&i32>> = buffer.iter().map(Some).collect();
+            from_slices == expected && from_get == expected && buffer.get(buffer.size()).is_none()
+        }
+        quickcheck(prop as fn(CircularBuffer<i32>) -> bool);
+    }
+
+    #[test]
+    fn test_growable_buffer_keeps_every_item() {
+        fn prop(mut buffer: CircularBuffer<i32>, items: Vec<i32>) -> bool {
+            let mut expected: Vec<i32> = buffer.iter().copied().collect();
+            expected.extend(&items);
+            let mut growable = CircularBuffer::growable(buffer.capacity());
+            while let Some(item) = buffer.pop() {
+                growable.push(item);
+            }
+            let no_evictions = items.into_iter().all(|item| growable.push(item).is_none());
+            let kept: Vec<i32> = growable.iter().copied().collect();
+            growable.shrink_to_fit();
+            no_evictions
+                && kept == expected
+                && growable.capacity() == expected.len().max(1)
+                && growable.iter().copied().collect::<Vec<_>>() == expected
+        }
+        quickcheck(prop as fn(CircularBuffer<i32>, Vec<i32>) -> bool);
+    }
+
+    #[test]
+    fn test_iterators_match_a_vec_from_both_ends() {
+        // `true` takes from the front, `false` from the back
+        fn prop(mut buffer: CircularBuffer<i32>, ends: Vec<bool>) -> bool {
+            let expected: Vec<i32> = buffer.iter().copied().collect();
+            fn walk<I: DoubleEndedIterator + ExactSizeIterator>(
+                mut iter: I,
+                ends: &[bool],
+            ) -> Vec<I::Item> {
+                let mut taken = Vec::new();
+                for &front in ends {
+                    let before = iter.len();
+                    let item = if front { iter.next() } else { iter.next_back() };
+                    assert_eq!(iter.len(), before.saturating_sub(1));
+                    taken.extend(item);
+                }
+                taken
+            }
+            let mut model = expected.clone();
+            let mut from_model = Vec::new();
+            for &front in &ends {
+                if !model.is_empty() {
+                    from_model.push(if front {
+                        model.remove(0)
+                    } else {
+                        model.pop().unwrap()
+                    });
+                }
+            }
+            let borrowed: Vec<i32> = walk(buffer.iter(), &ends).into_iter().copied().collect();
+            let mutable: Vec<i32> = walk(buffer.iter_mut(), &ends)
+                .into_iter()
+                .map(|item| *item)
+                .collect();
+            let owned = walk(buffer.into_iter(), &ends);
+            borrowed == from_model && mutable == from_model && owned == from_model
+        }
+        quickcheck(prop as fn(CircularBuffer<i32>, Vec<bool>) -> bool);
+    }
+
+    #[test]
+    fn test_pop_n_and_truncate_match_a_vec_deque() {
+        fn prop(mut buffer: CircularBuffer<i32>, n: usize, keep: usize, items: Vec<i32>) -> bool {
+            let mut model: std::collections::VecDeque<i32> = buffer.iter().copied().collect();
+            let n = n % (buffer.capacity() + 2);
+            let popped = buffer.pop_n(n);
+            let expected: Vec<i32> = model.drain(..n.min(model.len())).collect();
+
+            let keep = keep % (buffer.capacity() + 2);
+            buffer.truncate(keep);
+            model.truncate(keep);
+            // Refill through `extend`, so pushes land where the truncation left the tail
+            buffer.extend(items.iter().copied());
+            model.extend(items);
+            while model.len() > buffer.capacity() {
+                model.pop_front();
+            }
+            popped == expected && buffer.iter().eq(model.iter())
+        }
+        quickcheck(prop as fn(CircularBuffer<i32>, usize, usize, Vec<i32>) -> bool);
+    }
+
+    #[test]
+    fn test_static_buffer_matches_the_heap_one() {
+        // `Some` pushes, `None` pops
+        fn prop(reject: bool, ops: Vec<Option<i32>>) -> bool {
+            let policy = if reject {
+                OverwritePolicy::RejectWhenFull
+            } else {
+                OverwritePolicy::OverwriteOldest
+            };
+            let mut fixed: StaticCircularBuffer<i32, 4> = StaticCircularBuffer::with_policy(policy);
+            let mut heap = CircularBuffer::with_policy(4, policy);
+            ops.into_iter().all(|op| {
+                let same_result = match op {
+                    Some(item) => fixed.push(item) == heap.push(item),
+                    None => fixed.pop() == heap.pop(),
+                };
+                same_result && fixed.as_slices() == heap.as_slices()
+            })
+        }
+        quickcheck(prop as fn(bool, Vec<Option<i32>>) -> bool);
+    }
+
+    #[test]
+    fn test_byte_buffer_matches_a_vec_deque() {
+        // Each step writes its bytes, or reads as many as it holds
+        fn prop(capacity: u8, steps: Vec<(bool, Vec<u8>)>) -> bool {
+            let mut buffer = CircularByteBuffer::new(usize::from(capacity % 16));
+            let mut model = std::collections::VecDeque::new();
+            steps.into_iter().all(|(write, bytes)| {
+                if write {
+                    let free = buffer.capacity() - model.len();
+                    let written = buffer.write(&bytes);
+                    model.extend(&bytes[..written]);
+                    written == bytes.len().min(free)
+                } else {
+                    let mut out = vec![0; bytes.len()];
+                    let read = buffer.read(&mut out);
+                    let expected: Vec<u8> = model.drain(..bytes.len().min(model.len())).collect();
+                    out[..read] == expected[..]
+                }
+            })
+        }
+        quickcheck(prop as fn(u8, Vec<(bool, Vec<u8>)>) -> bool);
+    }
+
+    #[test]
+    fn test_generator_reaches_full_and_wrapped_states() {
+        let mut g = Gen::new(8);
+        let buffers: Vec<CircularBuffer<u8>> = (0..300)
+            .map(|_| CircularBuffer::arbitrary(&mut g))
+            .collect();
+        assert!(
+            buffers.iter().any(|b| b.size() == b.capacity),
+            "Some are full"
+        );
+        assert!(
+            buffers.iter().any(|b| b.size() + 1 == b.capacity),
+            "Some are one short of full"
+        );
+        assert!(
+            buffers
+                .iter()
+                .any(|b| b.size() > 0 && b.head + b.size() > b.capacity),
+            "Some wrap past the end of the storage"
+        );
+    }
+}
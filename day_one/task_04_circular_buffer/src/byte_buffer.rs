@@ -0,0 +1,183 @@
+use std::fmt;
+use std::io;
+
+/// Fixed-capacity FIFO of bytes, read and written in bulk
+///
+/// Where `CircularBuffer<u8>` moves one `Option<u8>` slot per call, this
+/// stores plain bytes and copies whole slices with `copy_from_slice`: at most
+/// two copies per call, one up to the end of the storage and one for the part
+/// that wraps round to the start. A write never overwrites unread bytes; it
+/// takes as many as fit and reports how many that was, as `io::Write` does.
+#[derive(Clone)]
+pub struct CircularByteBuffer {
+    buffer: Box<[u8]>,
+    head: usize,
+    len: usize,
+}
+
+impl CircularByteBuffer {
+    /// Creates an empty buffer that holds up to `capacity` bytes.
+    pub fn new(capacity: usize) -> Self {
+        CircularByteBuffer {
+            buffer: vec![0; capacity].into_boxed_slice(),
+            head: 0,
+            len: 0,
+        }
+    }
+
+    /// Appends as much of `data` as fits and returns the number of bytes written.
+    pub fn write(&mut self, data: &[u8]) -> usize {
+        let count = data.len().min(self.capacity() - self.len);
+        if count == 0 {
+            return 0;
+        }
+        let tail = (self.head + self.len) % self.capacity();
+        let before_wrap = count.min(self.capacity() - tail);
+        self.buffer[tail..tail + before_wrap].copy_from_slice(&data[..before_wrap]);
+        self.buffer[..count - before_wrap].copy_from_slice(&data[before_wrap..count]);
+        self.len += count;
+        count
+    }
+
+    /// Moves the oldest bytes into `out`, as many as fit, and returns how many were read.
+    pub fn read(&mut self, out: &mut [u8]) -> usize {
+        let count = out.len().min(self.len);
+        if count == 0 {
+            return 0;
+        }
+        let before_wrap = count.min(self.capacity() - self.head);
+        out[..before_wrap].copy_from_slice(&self.buffer[self.head..self.head + before_wrap]);
+        out[before_wrap..count].copy_from_slice(&self.buffer[..count - before_wrap]);
+        self.head = (self.head + count) % self.capacity();
+        self.len -= count;
+        count
+    }
+
+    /// Returns the unread bytes as two contiguous runs, oldest first.
+    ///
+    /// The second run is non-empty only when the bytes wrap past the end of
+    /// the storage.
+    pub fn as_slices(&self) -> (&[u8], &[u8]) {
+        if self.head + self.len <= self.capacity() {
+            (&self.buffer[self.head..self.head + self.len], &[])
+        } else {
+            let wrapped = self.head + self.len - self.capacity();
+            (&self.buffer[self.head..], &self.buffer[..wrapped])
+        }
+    }
+
+    /// Returns the number of unread bytes.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if there is nothing to read.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Checks if a write would take nothing.
+    pub fn is_full(&self) -> bool {
+        self.len == self.capacity()
+    }
+
+    /// Returns the maximum number of bytes.
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Discards every unread byte, keeping the capacity.
+    pub fn clear(&mut self) {
+        self.head = 0;
+        self.len = 0;
+    }
+}
+
+impl io::Write for CircularByteBuffer {
+    /// Returns `Ok(0)` when full, so `write_all` fails with `WriteZero`
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        Ok(CircularByteBuffer::write(self, data))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl io::Read for CircularByteBuffer {
+    /// Returns `Ok(0)` when empty, which readers take as the end of the data
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        Ok(CircularByteBuffer::read(self, out))
+    }
+}
+
+// Shows the unread bytes rather than the whole storage
+impl fmt::Debug for CircularByteBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (first, second) = self.as_slices();
+        f.debug_struct("CircularByteBuffer")
+            .field("capacity", &self.capacity())
+            .field("head", &self.head)
+            .field("bytes", &[first, second].concat())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+
+    #[test]
+    fn test_happy_path_bulk_copies_across_the_wrap_point() {
+        let mut buffer = CircularByteBuffer::new(8);
+        assert_eq!(buffer.write(b"abcdef"), 6);
+        let mut out = [0; 4];
+        assert_eq!(buffer.read(&mut out), 4);
+        assert_eq!(&out, b"abcd");
+
+        // 2 bytes fit before the end of the storage, the other 4 wrap to the start
+        assert_eq!(buffer.write(b"ghijkl"), 6);
+        assert_eq!(buffer.as_slices(), (&b"efgh"[..], &b"ijkl"[..]));
+        let mut out = [0; 8];
+        assert_eq!(buffer.read(&mut out), 8);
+        assert_eq!(&out, b"efghijkl");
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_unhappy_path_writes_stop_when_full() {
+        let mut buffer = CircularByteBuffer::new(4);
+        assert_eq!(buffer.write(b"abcdef"), 4, "Only what fits is taken");
+        assert!(buffer.is_full());
+        assert_eq!(buffer.write(b"g"), 0);
+        assert_eq!(
+            Write::write_all(&mut buffer, b"g").unwrap_err().kind(),
+            io::ErrorKind::WriteZero
+        );
+
+        let mut out = [0; 2];
+        buffer.read(&mut out);
+        assert_eq!(buffer.write(b"ef"), 2);
+        let mut rest = Vec::new();
+        buffer.read_to_end(&mut rest).unwrap();
+        assert_eq!(rest, b"cdef");
+    }
+
+    #[test]
+    fn test_edge_case_empty_and_zero_capacity() {
+        let mut buffer = CircularByteBuffer::new(0);
+        assert_eq!((buffer.write(b"a"), buffer.read(&mut [0; 1])), (0, 0));
+        assert!(buffer.is_full() && buffer.is_empty());
+
+        let mut buffer = CircularByteBuffer::new(3);
+        assert_eq!(buffer.read(&mut [0; 3]), 0);
+        buffer.write(b"xyz");
+        buffer.clear();
+        assert_eq!(buffer.as_slices(), (&b""[..], &b""[..]));
+        assert_eq!(
+            format!("{:?}", buffer),
+            "CircularByteBuffer { capacity: 3, head: 0, bytes: [] }"
+        );
+    }
+}
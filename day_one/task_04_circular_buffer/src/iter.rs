@@ -0,0 +1,238 @@
+use std::iter::FusedIterator;
+use std::slice;
+
+use crate::CircularBuffer;
+
+// Every slot in `as_slices` holds an element, so the unwraps below never fail
+const FILLED: &str = "slots between head and tail are always filled";
+
+/// Borrowing iterator over a `CircularBuffer`, oldest to newest
+///
+/// Walks the two runs from `as_slices` one after the other, so each step is
+/// a slice iterator step with no modulo arithmetic, and the length is known.
+pub struct Iter<'a, T> {
+    first: slice::Iter<'a, Option<T>>,
+    second: slice::Iter<'a, Option<T>>,
+}
+
+impl<'a, T> Iter<'a, T> {
+    pub(crate) fn new(buffer: &'a CircularBuffer<T>) -> Self {
+        let (first, second) = buffer.as_slices();
+        Iter {
+            first: first.iter(),
+            second: second.iter(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let slot = self.first.next().or_else(|| self.second.next())?;
+        Some(slot.as_ref().expect(FILLED))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let slot = self.second.next_back().or_else(|| self.first.next_back())?;
+        Some(slot.as_ref().expect(FILLED))
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {
+    fn len(&self) -> usize {
+        self.first.len() + self.second.len()
+    }
+}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Iter {
+            first: self.first.clone(),
+            second: self.second.clone(),
+        }
+    }
+}
+
+/// Mutable iterator over a `CircularBuffer`, oldest to newest
+pub struct IterMut<'a, T> {
+    first: slice::IterMut<'a, Option<T>>,
+    second: slice::IterMut<'a, Option<T>>,
+}
+
+impl<'a, T> IterMut<'a, T> {
+    pub(crate) fn new(buffer: &'a mut CircularBuffer<T>) -> Self {
+        let (first, second) = buffer.as_mut_slices();
+        IterMut {
+            first: first.iter_mut(),
+            second: second.iter_mut(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        let slot = self.first.next().or_else(|| self.second.next())?;
+        Some(slot.as_mut().expect(FILLED))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let slot = self.second.next_back().or_else(|| self.first.next_back())?;
+        Some(slot.as_mut().expect(FILLED))
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {
+    fn len(&self) -> usize {
+        self.first.len() + self.second.len()
+    }
+}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+/// Owning iterator over a `CircularBuffer`, oldest to newest
+///
+/// `next` pops from the head and `next_back` from the tail; whatever is not
+/// iterated is dropped with the iterator.
+pub struct IntoIter<T> {
+    buffer: CircularBuffer<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.buffer.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.buffer.size, Some(self.buffer.size))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        let buffer = &mut self.buffer;
+        if buffer.size == 0 {
+            return None;
+        }
+        buffer.size -= 1;
+        buffer.tail = (buffer.tail + buffer.capacity - 1) % buffer.capacity;
+        buffer.buffer[buffer.tail].take()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+impl<'a, T> IntoIterator for &'a CircularBuffer<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut CircularBuffer<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+impl<T> IntoIterator for CircularBuffer<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { buffer: self }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Capacity 4 holding 3 to 6, with 5 and 6 wrapped to the start
+    fn wrapped() -> CircularBuffer<i32> {
+        let mut buffer = CircularBuffer::new(4);
+        buffer.extend(1..=6);
+        buffer
+    }
+
+    #[test]
+    fn test_happy_path_both_ends_across_the_wrap_point() {
+        let buffer = wrapped();
+        let mut iter = buffer.iter();
+        assert_eq!(iter.len(), 4);
+        assert_eq!((iter.next(), iter.next_back()), (Some(&3), Some(&6)));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.clone().collect::<Vec<_>>(), vec![&4, &5]);
+        assert_eq!((iter.next_back(), iter.next()), (Some(&5), Some(&4)));
+        assert_eq!((iter.next(), iter.next_back()), (None, None));
+
+        assert_eq!(
+            buffer.iter().rev().collect::<Vec<_>>(),
+            vec![&6, &5, &4, &3]
+        );
+        let mut total = 0;
+        for item in &buffer {
+            total += item;
+        }
+        assert_eq!(total, 18);
+    }
+
+    #[test]
+    fn test_iter_mut_and_into_iter() {
+        let mut buffer = wrapped();
+        for item in &mut buffer {
+            *item *= 10;
+        }
+        if let Some(newest) = buffer.iter_mut().next_back() {
+            *newest += 1;
+        }
+        assert_eq!(buffer.iter_mut().len(), 4);
+        assert_eq!(buffer.iter().collect::<Vec<_>>(), vec![&30, &40, &50, &61]);
+
+        let mut owned = buffer.into_iter();
+        assert_eq!(owned.len(), 4);
+        assert_eq!((owned.next_back(), owned.next()), (Some(61), Some(30)));
+        assert_eq!(owned.collect::<Vec<_>>(), vec![40, 50]);
+    }
+
+    #[test]
+    fn test_edge_case_empty_and_unfinished_iterators() {
+        let empty = CircularBuffer::<String>::new(2);
+        assert_eq!(empty.iter().len(), 0);
+        assert_eq!(empty.into_iter().next_back(), None);
+
+        // The strings left in an unfinished `IntoIter` are dropped with it
+        let mut buffer = CircularBuffer::new(2);
+        buffer.extend(["a", "b", "c"].map(String::from));
+        let mut owned = buffer.into_iter();
+        assert_eq!(owned.next().as_deref(), Some("b"));
+        assert_eq!(owned.size_hint(), (1, Some(1)));
+    }
+}
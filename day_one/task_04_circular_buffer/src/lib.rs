@@ -0,0 +1,538 @@
+#![cfg_attr(feature = "safe-only", forbid(unsafe_code))]
+// Without `std` only `StaticCircularBuffer` and `OverwritePolicy` are built
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "std")]
+use std::fmt;
+
+#[cfg(feature = "testing")]
+mod arbitrary_impls;
+#[cfg(feature = "std")]
+mod byte_buffer;
+#[cfg(feature = "std")]
+mod iter;
+#[cfg(feature = "serde")]
+mod serde_impls;
+#[cfg(feature = "std")]
+pub mod spsc;
+mod static_buffer;
+
+#[cfg(feature = "std")]
+pub use byte_buffer::CircularByteBuffer;
+#[cfg(feature = "std")]
+pub use iter::{IntoIter, Iter, IterMut};
+pub use static_buffer::StaticCircularBuffer;
+
+/// What `push` does when the buffer is already full
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OverwritePolicy {
+    /// Drop the oldest element to make room, as a ring buffer does
+    #[default]
+    OverwriteOldest,
+    /// Leave the buffer as it is and hand the new element back
+    RejectWhenFull,
+    /// Double the capacity, so nothing is ever dropped or rejected
+    Grow,
+}
+
+/// Fixed-capacity FIFO buffer on a ring of slots
+///
+/// This is also task 5's `RingBuffer`. The slots hold `Option<T>`, so no
+/// bound on `T` is needed to fill the empty ones.
+#[cfg(feature = "std")]
+#[derive(Clone)]
+pub struct CircularBuffer<T> {
+    buffer: Vec<Option<T>>,
+    head: usize,
+    tail: usize,
+    size: usize,
+    capacity: usize,
+    policy: OverwritePolicy,
+}
+
+#[cfg(feature = "std")]
+impl<T> CircularBuffer<T> {
+    /// Creates a new `CircularBuffer` with the given capacity that overwrites the oldest element when full.
+    pub fn new(capacity: usize) -> Self {
+        Self::with_policy(capacity, OverwritePolicy::OverwriteOldest)
+    }
+
+    /// Creates a new `CircularBuffer` with the given capacity and behaviour when full.
+    pub fn with_policy(capacity: usize, policy: OverwritePolicy) -> Self {
+        CircularBuffer {
+            buffer: (0..capacity).map(|_| None).collect(),
+            head: 0,
+            tail: 0,
+            size: 0,
+            capacity,
+            policy,
+        }
+    }
+
+    /// Creates a new `CircularBuffer` that starts with room for `initial` elements and doubles its capacity whenever a push finds it full.
+    pub fn growable(initial: usize) -> Self {
+        Self::with_policy(initial, OverwritePolicy::Grow)
+    }
+
+    /// Returns what `push` does when the buffer is full.
+    pub fn policy(&self) -> OverwritePolicy {
+        self.policy
+    }
+
+    /// Adds an element to the buffer, returning the element that did not fit, if any.
+    ///
+    /// When the buffer is full, `OverwritePolicy::OverwriteOldest` evicts and
+    /// returns the oldest element, and `OverwritePolicy::RejectWhenFull`
+    /// returns `item` itself without changing the buffer. `OverwritePolicy::Grow`
+    /// doubles the capacity first and always returns `None`.
+    pub fn push(&mut self, item: T) -> Option<T> {
+        let mut evicted = None;
+        if self.size == self.capacity {
+            match self.policy {
+                OverwritePolicy::RejectWhenFull => return Some(item),
+                OverwritePolicy::OverwriteOldest => {
+                    evicted = self.buffer[self.head].take();
+                    self.head = (self.head + 1) % self.capacity; // Move head when overwriting
+                }
+                OverwritePolicy::Grow => {
+                    self.relocate((self.capacity * 2).max(1));
+                    self.size += 1;
+                }
+            }
+        } else {
+            self.size += 1;
+        }
+        self.buffer[self.tail] = Some(item);
+        self.tail = (self.tail + 1) % self.capacity;
+        evicted
+    }
+
+    /// Removes and returns the oldest element from the buffer, or `None` if the buffer is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            None
+        } else {
+            let result = self.buffer[self.head].take();
+            self.head = (self.head + 1) % self.capacity;
+            self.size -= 1;
+            result
+        }
+    }
+
+    /// Pushes clones of `items` in order, as `extend` does.
+    pub fn extend_from_slice(&mut self, items: &[T])
+    where
+        T: Clone,
+    {
+        self.extend(items.iter().cloned());
+    }
+
+    /// Removes and returns up to `n` of the oldest elements, oldest first.
+    pub fn pop_n(&mut self, n: usize) -> Vec<T> {
+        let count = n.min(self.size);
+        let popped = (0..count)
+            .filter_map(|i| self.buffer[(self.head + i) % self.capacity].take())
+            .collect();
+        if count > 0 {
+            self.head = (self.head + count) % self.capacity;
+            self.size -= count;
+        }
+        popped
+    }
+
+    /// Keeps the `n` oldest elements and drops the rest, newest first, as `VecDeque::truncate` does.
+    pub fn truncate(&mut self, n: usize) {
+        while self.size > n {
+            self.size -= 1;
+            self.tail = (self.tail + self.capacity - 1) % self.capacity;
+            self.buffer[self.tail] = None;
+        }
+    }
+
+    /// Returns the oldest element without removing it.
+    pub fn peek(&self) -> Option<&T> {
+        if self.is_empty() {
+            None
+        } else {
+            self.buffer[self.head].as_ref()
+        }
+    }
+
+    /// Returns the element `index` places after the oldest one.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.size {
+            return None;
+        }
+        self.buffer[(self.head + index) % self.capacity].as_ref()
+    }
+
+    /// Returns the filled slots as two contiguous runs, oldest first.
+    ///
+    /// The first run starts at the head; the second is non-empty only when
+    /// the elements wrap past the end of the storage. Every slot in both is
+    /// `Some`.
+    pub fn as_slices(&self) -> (&[Option<T>], &[Option<T>]) {
+        if self.head + self.size <= self.capacity {
+            (&self.buffer[self.head..self.head + self.size], &[])
+        } else {
+            let wrapped = self.head + self.size - self.capacity;
+            (&self.buffer[self.head..], &self.buffer[..wrapped])
+        }
+    }
+
+    /// Mutable version of `as_slices`, for `IterMut`
+    fn as_mut_slices(&mut self) -> (&mut [Option<T>], &mut [Option<T>]) {
+        if self.head + self.size <= self.capacity {
+            (&mut self.buffer[self.head..self.head + self.size], &mut [])
+        } else {
+            let wrapped = self.head + self.size - self.capacity;
+            let (start, end) = self.buffer.split_at_mut(self.head);
+            (end, &mut start[..wrapped])
+        }
+    }
+
+    /// Checks if the buffer is empty.
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Checks if the buffer holds `capacity` elements.
+    pub fn is_full(&self) -> bool {
+        self.size == self.capacity
+    }
+
+    /// Returns the current number of elements in the buffer.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Removes every element, keeping the capacity.
+    pub fn clear(&mut self) {
+        self.buffer.iter_mut().for_each(|slot| *slot = None);
+        self.head = 0;
+        self.tail = 0;
+        self.size = 0;
+    }
+
+    /// Iterates from the oldest element to the newest.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::new(self)
+    }
+
+    /// Iterates mutably from the oldest element to the newest.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut::new(self)
+    }
+
+    /// Returns the maximum number of elements.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Makes room for at least `additional` more elements without overwriting or rejecting any.
+    ///
+    /// If the capacity has to grow, it at least doubles, and the elements
+    /// are moved to the start of the new storage, oldest first.
+    pub fn reserve(&mut self, additional: usize) {
+        let needed = self.size + additional;
+        if needed > self.capacity {
+            self.relocate(needed.max(self.capacity * 2));
+        }
+    }
+
+    /// Shrinks the capacity to the number of elements, moving them to the start of the storage.
+    ///
+    /// At least one slot is kept, so `push` always has somewhere to write.
+    pub fn shrink_to_fit(&mut self) {
+        let capacity = self.size.max(1);
+        if capacity < self.capacity {
+            self.relocate(capacity);
+        }
+    }
+
+    /// Moves the elements, oldest first, into fresh storage with `capacity` slots
+    fn relocate(&mut self, capacity: usize) {
+        let mut buffer: Vec<Option<T>> = Vec::with_capacity(capacity);
+        for i in 0..self.size {
+            buffer.push(self.buffer[(self.head + i) % self.capacity].take());
+        }
+        buffer.resize_with(capacity, || None);
+        self.buffer = buffer;
+        self.head = 0;
+        self.tail = self.size % capacity;
+        self.capacity = capacity;
+    }
+
+    /// Returns the slot holding the oldest element.
+    pub fn head_index(&self) -> usize {
+        self.head
+    }
+
+    /// Returns the slot the next push writes to.
+    pub fn tail_index(&self) -> usize {
+        self.tail
+    }
+
+    /// Iterates over the slots in storage order, `None` for empty ones.
+    pub fn slots(&self) -> impl Iterator<Item = Option<&T>> {
+        self.buffer.iter().map(Option::as_ref)
+    }
+}
+
+/// Pushes every item in turn, following the policy. Under
+/// `OverwritePolicy::Grow` the room for the iterator's lower size hint is
+/// reserved first; otherwise evicted or rejected elements are dropped.
+#[cfg(feature = "std")]
+impl<T> Extend<T> for CircularBuffer<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        if self.policy == OverwritePolicy::Grow {
+            self.reserve(iter.size_hint().0);
+        }
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+// Shows the capacity, where the oldest element sits, and the elements oldest first
+#[cfg(feature = "std")]
+impl<T: fmt::Debug> fmt::Debug for CircularBuffer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let items: Vec<&T> = (0..self.size)
+            .filter_map(|i| self.buffer[(self.head + i) % self.capacity].as_ref())
+            .collect();
+        f.debug_struct("CircularBuffer")
+            .field("capacity", &self.capacity)
+            .field("head", &self.head)
+            .field("items", &items)
+            .finish()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::{CircularBuffer, OverwritePolicy};
+
+    #[test]
+fn test_circular_buffer_happy_cases() {
+    let mut cb = CircularBuffer::<i32>::new(3);
+
+    // Push to empty buffer
+    cb.push(1);
+    assert_eq!(cb.size(), 1);
+
+    // Basic push and pop
+    cb.push(2);
+    cb.push(3); // Buffer full
+    assert_eq!(cb.pop(), Some(1)); // Oldest is removed
+    assert_eq!(cb.size(), 2);
+
+    // Check buffer full behavior
+    cb.push(4); // Overwrites the position of 1
+    assert_eq!(cb.pop(), Some(2)); // The next oldest element
+    assert_eq!(cb.pop(), Some(3)); 
+    assert_eq!(cb.pop(), Some(4)); // All elements popped
+    assert_eq!(cb.pop(), None); // Buffer now empty
+
+    // Empty check
+    assert!(cb.is_empty());
+}
+
+#[test]
+fn test_circular_buffer_unhappy_cases() {
+    let mut cb = CircularBuffer::<i32>::new(2);
+
+    // Pop from empty
+    assert_eq!(cb.pop(), None);
+
+    // Push and overwrite
+    cb.push(1);
+    cb.push(2); // Buffer full
+    cb.push(3); // Overwrites 1, now buffer: [3, 2]
+    assert_eq!(cb.pop(), Some(2)); // Oldest (not overwritten)
+    assert_eq!(cb.pop(), Some(3));
+    assert_eq!(cb.pop(), None); // Buffer empty again
+
+    // Multiple operations
+    cb.push(4);
+    cb.push(5);
+    cb.push(6); // Overwrites 4, now buffer: [6, 5]
+    assert_eq!(cb.pop(), Some(5)); // Oldest
+    cb.push(7); // Buffer: [7, 6]
+    assert_eq!(cb.pop(), Some(6));
+    assert_eq!(cb.pop(), Some(7));
+    assert_eq!(cb.pop(), None);
+
+    // Large buffer test
+    let mut large_cb = CircularBuffer::<i32>::new(1000);
+    for i in 0..1000 {
+        large_cb.push(i);
+    }
+    for i in 1000 - large_cb.capacity..1000 {
+        assert_eq!(large_cb.pop(), Some(i as i32));
+    }
+}
+
+#[test]
+fn test_circular_buffer_iter_and_clear() {
+    let mut cb = CircularBuffer::<i32>::new(3);
+    for i in 1..=5 {
+        cb.push(i); // Wraps around, keeping 3, 4, 5
+    }
+    assert_eq!(cb.iter().collect::<Vec<_>>(), vec![&3, &4, &5]);
+
+    cb.clear();
+    assert!(cb.is_empty());
+    assert_eq!(cb.iter().count(), 0);
+    cb.push(6);
+    assert_eq!(cb.pop(), Some(6)); // Still usable after clearing
+}
+
+#[test]
+fn test_circular_buffer_slot_layout() {
+    let mut cb = CircularBuffer::<i32>::new(3);
+    for i in 1..=4 {
+        cb.push(i); // 4 overwrites 1 in slot 0
+    }
+    assert_eq!(cb.capacity(), 3);
+    assert_eq!((cb.head_index(), cb.tail_index()), (1, 1));
+    assert_eq!(cb.slots().collect::<Vec<_>>(), vec![Some(&4), Some(&2), Some(&3)]);
+
+    cb.pop();
+    assert_eq!(cb.head_index(), 2);
+    assert_eq!(cb.slots().filter(Option::is_none).count(), 1);
+}
+
+#[test]
+fn test_circular_buffer_reject_when_full() {
+    let mut cb = CircularBuffer::with_policy(2, OverwritePolicy::RejectWhenFull);
+    assert_eq!(cb.push(1), None);
+    assert_eq!(cb.push(2), None);
+    assert!(cb.is_full());
+    assert_eq!(cb.push(3), Some(3)); // Handed back, nothing overwritten
+    assert_eq!(cb.iter().collect::<Vec<_>>(), vec![&1, &2]);
+
+    assert_eq!(cb.pop(), Some(1));
+    assert_eq!(cb.push(4), None); // Room again
+    assert_eq!(cb.peek(), Some(&2));
+    assert_eq!(cb.policy(), OverwritePolicy::RejectWhenFull);
+}
+
+#[test]
+fn test_circular_buffer_overwrite_returns_the_evicted_element() {
+    // No `Default` bound: a type without one works too
+    struct NoDefault(&'static str);
+    let mut cb = CircularBuffer::new(2);
+    assert!(cb.push(NoDefault("a")).is_none());
+    assert!(cb.push(NoDefault("b")).is_none());
+    assert_eq!(cb.push(NoDefault("c")).map(|evicted| evicted.0), Some("a"));
+    assert_eq!(cb.peek().map(|oldest| oldest.0), Some("b"));
+    assert_eq!(cb.policy(), OverwritePolicy::default());
+}
+
+#[test]
+fn test_circular_buffer_get_and_as_slices() {
+    let mut cb = CircularBuffer::new(4);
+    for i in 1..=3 {
+        cb.push(i);
+    }
+    assert_eq!(cb.as_slices(), (&[Some(1), Some(2), Some(3)][..], &[][..]));
+    for i in 4..=6 {
+        cb.push(i); // Keeps 3 to 6, with 5 and 6 wrapped to the start
+    }
+    assert_eq!((cb.get(0), cb.get(3), cb.get(4)), (Some(&3), Some(&6), None));
+    assert_eq!(cb.peek(), cb.get(0));
+    let (first, second) = cb.as_slices();
+    assert_eq!(first, &[Some(3), Some(4)]);
+    assert_eq!(second, &[Some(5), Some(6)]);
+
+    cb.clear();
+    assert_eq!(cb.get(0), None);
+    assert_eq!(cb.as_slices(), (&[][..], &[][..]));
+}
+
+#[test]
+fn test_circular_buffer_bulk_operations_across_the_wrap_point() {
+    let mut cb = CircularBuffer::new(4);
+    cb.extend(1..=6); // Keeps 3 to 6, with 5 and 6 wrapped to the start
+    assert_eq!(cb.iter().collect::<Vec<_>>(), vec![&3, &4, &5, &6]);
+
+    assert_eq!(cb.pop_n(3), vec![3, 4, 5]); // Crosses the end of the storage
+    assert_eq!(cb.pop_n(5), vec![6]);
+    assert!(cb.pop_n(1).is_empty());
+
+    cb.extend_from_slice(&[7, 8, 9, 10]); // Starts at slot 2 and wraps
+    cb.truncate(1);
+    assert_eq!(cb.iter().collect::<Vec<_>>(), vec![&7]);
+    cb.truncate(3); // Longer than the buffer: nothing happens
+    assert_eq!(cb.size(), 1);
+    cb.push(11);
+    assert_eq!(cb.pop_n(2), vec![7, 11], "The tail moved back with the truncation");
+
+    let mut strict = CircularBuffer::with_policy(2, OverwritePolicy::RejectWhenFull);
+    strict.extend(["a", "b", "c"]);
+    assert_eq!(strict.iter().collect::<Vec<_>>(), vec![&"a", &"b"]);
+
+    let mut growable = CircularBuffer::growable(1);
+    growable.extend(0..10);
+    assert_eq!(growable.capacity(), 10, "Reserved once from the size hint");
+    assert_eq!(growable.pop_n(10), (0..10).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_circular_buffer_growable() {
+    let mut cb = CircularBuffer::growable(2);
+    cb.push(1);
+    cb.push(2);
+    cb.pop();
+    cb.push(3); // Wraps: 3 sits in slot 0, before 2
+    assert!(cb.is_full());
+    assert_eq!(cb.push(4), None); // Doubles instead of overwriting
+    assert_eq!(cb.capacity(), 4);
+    assert_eq!((cb.head_index(), cb.tail_index()), (0, 3));
+    assert_eq!(cb.as_slices(), (&[Some(2), Some(3), Some(4)][..], &[][..]));
+
+    for i in 5..=9 {
+        cb.push(i);
+    }
+    assert_eq!(cb.capacity(), 8);
+    assert_eq!(cb.iter().copied().collect::<Vec<_>>(), (2..=9).collect::<Vec<_>>());
+
+    for _ in 0..6 {
+        cb.pop();
+    }
+    cb.shrink_to_fit();
+    assert_eq!(cb.capacity(), 2);
+    assert_eq!(cb.iter().collect::<Vec<_>>(), vec![&8, &9]);
+    assert_eq!(cb.policy(), OverwritePolicy::Grow);
+}
+
+#[test]
+fn test_circular_buffer_reserve_and_shrink_edge_cases() {
+    // Growing from nothing
+    let mut cb = CircularBuffer::growable(0);
+    assert_eq!(cb.push("a"), None);
+    assert_eq!(cb.capacity(), 1);
+
+    // Reserving keeps a fixed buffer's policy but stops the next pushes overwriting
+    let mut fixed = CircularBuffer::new(2);
+    fixed.push(1);
+    fixed.reserve(3);
+    assert_eq!(fixed.capacity(), 4);
+    fixed.reserve(1); // Already has room
+    assert_eq!(fixed.capacity(), 4);
+    for i in 2..=5 {
+        fixed.push(i);
+    }
+    assert_eq!(fixed.iter().collect::<Vec<_>>(), vec![&2, &3, &4, &5]);
+
+    // An empty buffer keeps one slot
+    fixed.clear();
+    fixed.shrink_to_fit();
+    assert_eq!(fixed.capacity(), 1);
+    fixed.push(6);
+    assert_eq!(fixed.push(7), Some(6));
+}
+}
@@ -0,0 +1,210 @@
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{CircularBuffer, CircularByteBuffer, OverwritePolicy};
+
+/// On-the-wire form: the capacity plus the stored items, oldest first
+///
+/// The policy is only written when it is not the default, so buffers that
+/// overwrite the oldest element keep the format they always had.
+#[derive(Serialize)]
+#[serde(rename = "CircularBuffer")]
+struct Borrowed<'a, T> {
+    capacity: usize,
+    items: Vec<&'a T>,
+    #[serde(skip_serializing_if = "is_default")]
+    policy: OverwritePolicy,
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "CircularBuffer")]
+struct Owned<T> {
+    capacity: usize,
+    items: Vec<T>,
+    #[serde(default)]
+    policy: OverwritePolicy,
+}
+
+fn is_default(policy: &OverwritePolicy) -> bool {
+    *policy == OverwritePolicy::default()
+}
+
+impl<T: Serialize> Serialize for CircularBuffer<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let items = (0..self.size)
+            .filter_map(|i| self.buffer[(self.head + i) % self.capacity].as_ref())
+            .collect();
+        Borrowed {
+            capacity: self.capacity,
+            items,
+            policy: self.policy,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for CircularBuffer<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let Owned {
+            capacity,
+            items,
+            policy,
+        } = Owned::deserialize(deserializer)?;
+        if items.len() > capacity {
+            return Err(D::Error::custom(format!(
+                "{} items do not fit in a buffer of capacity {}",
+                items.len(),
+                capacity
+            )));
+        }
+        let size = items.len();
+        let mut buffer: Vec<Option<T>> = items.into_iter().map(Some).collect();
+        buffer.resize_with(capacity, || None);
+        Ok(CircularBuffer {
+            buffer,
+            head: 0,
+            tail: if size == capacity { 0 } else { size },
+            size,
+            capacity,
+            policy,
+        })
+    }
+}
+
+/// On-the-wire form of a `CircularByteBuffer`: the capacity plus the unread bytes, oldest first
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "CircularByteBuffer")]
+struct ByteSnapshot {
+    capacity: usize,
+    bytes: Vec<u8>,
+}
+
+impl Serialize for CircularByteBuffer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (first, second) = self.as_slices();
+        ByteSnapshot {
+            capacity: self.capacity(),
+            bytes: [first, second].concat(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for CircularByteBuffer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let ByteSnapshot { capacity, bytes } = ByteSnapshot::deserialize(deserializer)?;
+        if bytes.len() > capacity {
+            return Err(D::Error::custom(format!(
+                "{} bytes do not fit in a buffer of capacity {}",
+                bytes.len(),
+                capacity
+            )));
+        }
+        let mut buffer = CircularByteBuffer::new(capacity);
+        buffer.write(&bytes);
+        Ok(buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_after_wrap_around() {
+        let mut buffer = CircularBuffer::new(3);
+        for i in 1..=5 {
+            buffer.push(i);
+        }
+        let json = serde_json::to_string(&buffer).unwrap();
+        assert_eq!(json, r#"{"capacity":3,"items":[3,4,5]}"#);
+
+        let mut restored: CircularBuffer<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.size(), 3);
+        restored.push(6); // still overwrites the oldest
+        assert_eq!(restored.pop(), Some(4));
+        assert_eq!(restored.pop(), Some(5));
+        assert_eq!(restored.pop(), Some(6));
+        assert!(restored.is_empty());
+    }
+
+    #[test]
+    fn test_unhappy_path_too_many_items() {
+        let result =
+            serde_json::from_str::<CircularBuffer<i32>>(r#"{"capacity":2,"items":[1,2,3]}"#);
+        let error = result.expect_err("oversized input must be rejected");
+        assert!(error.to_string().contains("do not fit"));
+    }
+
+    #[test]
+    fn test_edge_case_partially_filled() {
+        let mut restored: CircularBuffer<i32> =
+            serde_json::from_str(r#"{"capacity":4,"items":[7]}"#).unwrap();
+        restored.push(8);
+        assert_eq!(restored.pop(), Some(7));
+        assert_eq!(restored.pop(), Some(8));
+        assert_eq!(restored.pop(), None);
+    }
+
+    #[test]
+    fn test_full_buffer_and_policy_round_trip() {
+        let mut buffer = CircularBuffer::with_policy(2, OverwritePolicy::RejectWhenFull);
+        buffer.push(1);
+        buffer.push(2);
+        let json = serde_json::to_string(&buffer).unwrap();
+        assert_eq!(
+            json,
+            r#"{"capacity":2,"items":[1,2],"policy":"RejectWhenFull"}"#
+        );
+
+        let mut restored: CircularBuffer<i32> = serde_json::from_str(&json).unwrap();
+        assert!(restored.is_full());
+        assert_eq!(restored.push(3), Some(3), "Still rejects once full");
+        assert_eq!(restored.iter().collect::<Vec<_>>(), vec![&1, &2]);
+    }
+
+    #[test]
+    fn test_growable_policy_round_trip() {
+        let mut buffer = CircularBuffer::growable(1);
+        buffer.push(1);
+        buffer.push(2);
+        let json = serde_json::to_string(&buffer).unwrap();
+        assert_eq!(json, r#"{"capacity":2,"items":[1,2],"policy":"Grow"}"#);
+
+        let mut restored: CircularBuffer<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.push(3), None, "Still grows once full");
+        assert_eq!(restored.capacity(), 4);
+    }
+
+    #[test]
+    fn test_byte_buffer_round_trip_after_wrap_around() {
+        let mut buffer = CircularByteBuffer::new(4);
+        buffer.write(b"abc");
+        buffer.read(&mut [0; 2]);
+        buffer.write(b"def"); // Wraps: "c" sits before "def"
+        let json = serde_json::to_string(&buffer).unwrap();
+        assert_eq!(json, r#"{"capacity":4,"bytes":[99,100,101,102]}"#);
+
+        let mut restored: CircularByteBuffer = serde_json::from_str(&json).unwrap();
+        assert!(restored.is_full());
+        let mut out = [0; 4];
+        assert_eq!(restored.read(&mut out), 4);
+        assert_eq!(&out, b"cdef");
+
+        let error = serde_json::from_str::<CircularByteBuffer>(r#"{"capacity":1,"bytes":[1,2]}"#)
+            .expect_err("oversized input must be rejected");
+        assert!(error.to_string().contains("do not fit"));
+    }
+
+    #[test]
+    fn test_edge_case_empty_buffer() {
+        let json = serde_json::to_string(&CircularBuffer::<i32>::new(2)).unwrap();
+        assert_eq!(json, r#"{"capacity":2,"items":[]}"#);
+        let mut restored: CircularBuffer<i32> = serde_json::from_str(&json).unwrap();
+        assert!(restored.is_empty());
+        restored.push(1);
+        restored.push(2);
+        restored.push(3);
+        assert_eq!(restored.iter().collect::<Vec<_>>(), vec![&2, &3]);
+    }
+}
@@ -0,0 +1,341 @@
+//! Circular buffer shared by exactly one producer thread and one consumer thread
+//!
+//! `spsc::buffer(capacity)` returns a `Producer` and a `Consumer`. Either can
+//! be sent to another thread, but neither is `Clone` or `Sync`, so there is
+//! only ever one thread on each side and `push` and `pop` can take `&self`.
+//! Task 22's blocking `spsc::channel` wraps the two ends.
+//! The consumer alone advances `head` and the producer alone advances `tail`:
+//! each side publishes its own index with a release store and reads the
+//! other's with an acquire load, so no `Mutex` or compare-and-swap loop is
+//! needed. Both indices count up forever and are reduced modulo the capacity
+//! when a slot is accessed.
+//!
+//! Under `safe-only` each slot is a `Mutex<Option<T>>` instead of an
+//! `UnsafeCell`. The indices still decide who may touch a slot, so the locks
+//! are never contended.
+
+use std::cell::Cell;
+use std::fmt;
+use std::marker::PhantomData;
+#[cfg(not(feature = "safe-only"))]
+use std::mem::MaybeUninit;
+
+use sync_shim::atomic::{AtomicUsize, Ordering};
+#[cfg(not(feature = "safe-only"))]
+use sync_shim::cell::UnsafeCell;
+use sync_shim::Arc;
+#[cfg(feature = "safe-only")]
+use sync_shim::Mutex;
+
+/// One slot of the ring; only the side that `head` and `tail` give it to touches it
+#[cfg(not(feature = "safe-only"))]
+struct Slot<T>(UnsafeCell<MaybeUninit<T>>);
+
+#[cfg(not(feature = "safe-only"))]
+impl<T> Slot<T> {
+    fn new() -> Self {
+        Slot(UnsafeCell::new(MaybeUninit::uninit()))
+    }
+
+    /// Fills the slot; only for the producer, on a slot outside `head..tail`
+    fn write(&self, item: T) {
+        // SAFETY: the consumer only reads inside head..tail, and there is one producer
+        self.0.with_mut(|slot| unsafe { (*slot).write(item) });
+    }
+
+    /// Empties the slot; only for the consumer, on a slot inside `head..tail`
+    fn take(&self) -> T {
+        // SAFETY: the producer's release store of `tail` published this write, and
+        // moving `head` past the slot afterwards stops it being read twice
+        self.0.with(|slot| unsafe { (*slot).assume_init_read() })
+    }
+}
+
+#[cfg(feature = "safe-only")]
+struct Slot<T>(Mutex<Option<T>>);
+
+#[cfg(feature = "safe-only")]
+impl<T> Slot<T> {
+    fn new() -> Self {
+        Slot(Mutex::new(None))
+    }
+
+    fn write(&self, item: T) {
+        *self.lock() = Some(item);
+    }
+
+    fn take(&self) -> T {
+        self.lock()
+            .take()
+            .expect("slots inside head..tail are always filled")
+    }
+
+    // Nothing can panic while the lock is held, so a poisoned lock is still usable
+    fn lock(&self) -> sync_shim::MutexGuard<'_, Option<T>> {
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+struct Shared<T> {
+    slots: Box<[Slot<T>]>,
+    /// Next slot to pop; only the consumer stores to it
+    head: AtomicUsize,
+    /// Next slot to push into; only the producer stores to it
+    tail: AtomicUsize,
+}
+
+// SAFETY: each item is written by the one producer and read once by the one consumer,
+// which is sound whenever T itself may cross threads
+#[cfg(not(feature = "safe-only"))]
+unsafe impl<T: Send> Sync for Shared<T> {}
+
+impl<T> Shared<T> {
+    fn len(&self) -> usize {
+        let head = self.head.load(Ordering::Acquire);
+        self.tail.load(Ordering::Acquire) - head
+    }
+}
+
+impl<T> Drop for Shared<T> {
+    fn drop(&mut self) {
+        // Both halves are gone, and dropping their `Arc`s ordered all their accesses before this
+        let head = self.head.load(Ordering::Relaxed);
+        let tail = self.tail.load(Ordering::Relaxed);
+        for index in head..tail {
+            drop(self.slots[index % self.slots.len()].take());
+        }
+    }
+}
+
+/// Creates an empty buffer with room for `capacity` items, split into its two ends
+///
+/// Panics if `capacity` is 0.
+pub fn buffer<T>(capacity: usize) -> (Producer<T>, Consumer<T>) {
+    assert!(capacity > 0, "spsc buffer capacity must be non-zero");
+    let shared = Arc::new(Shared {
+        slots: (0..capacity).map(|_| Slot::new()).collect(),
+        head: AtomicUsize::new(0),
+        tail: AtomicUsize::new(0),
+    });
+    (
+        Producer {
+            shared: Arc::clone(&shared),
+            _not_sync: PhantomData,
+        },
+        Consumer {
+            shared,
+            _not_sync: PhantomData,
+        },
+    )
+}
+
+/// The end of an SPSC buffer that pushes items
+pub struct Producer<T> {
+    shared: Arc<Shared<T>>,
+    _not_sync: PhantomData<Cell<()>>,
+}
+
+impl<T> Producer<T> {
+    /// Adds an item at the back, handing it back if the buffer is full
+    ///
+    /// Unlike `CircularBuffer::push` this never overwrites: the oldest item
+    /// may be in the middle of being popped by the other thread.
+    pub fn push(&self, item: T) -> Result<(), T> {
+        let shared = &*self.shared;
+        let tail = shared.tail.load(Ordering::Relaxed);
+        // Acquire, so the consumer has finished reading any slot it has freed
+        if tail - shared.head.load(Ordering::Acquire) == shared.slots.len() {
+            return Err(item);
+        }
+        shared.slots[tail % shared.slots.len()].write(item);
+        shared.tail.store(tail + 1, Ordering::Release);
+        Ok(())
+    }
+
+    /// Returns the number of items waiting to be popped
+    pub fn len(&self) -> usize {
+        self.shared.len()
+    }
+
+    /// Checks if no items are waiting to be popped
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Checks if `push` would fail right now
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    /// Returns the maximum number of items
+    pub fn capacity(&self) -> usize {
+        self.shared.slots.len()
+    }
+}
+
+impl<T> fmt::Debug for Producer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Producer")
+            .field("len", &self.len())
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+/// The end of an SPSC buffer that pops items
+pub struct Consumer<T> {
+    shared: Arc<Shared<T>>,
+    _not_sync: PhantomData<Cell<()>>,
+}
+
+impl<T> Consumer<T> {
+    /// Removes and returns the oldest item, or `None` if the buffer is empty
+    pub fn pop(&self) -> Option<T> {
+        let shared = &*self.shared;
+        let head = shared.head.load(Ordering::Relaxed);
+        // Acquire, so the producer's write to the slot is visible
+        if head == shared.tail.load(Ordering::Acquire) {
+            return None;
+        }
+        let item = shared.slots[head % shared.slots.len()].take();
+        shared.head.store(head + 1, Ordering::Release);
+        Some(item)
+    }
+
+    /// Returns the number of items waiting to be popped
+    pub fn len(&self) -> usize {
+        self.shared.len()
+    }
+
+    /// Checks if no items are waiting to be popped
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the maximum number of items
+    pub fn capacity(&self) -> usize {
+        self.shared.slots.len()
+    }
+}
+
+impl<T> fmt::Debug for Consumer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Consumer")
+            .field("len", &self.len())
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn test_happy_path_fifo_order_and_full_buffer() {
+        let (producer, consumer) = buffer(2);
+        assert_eq!(producer.push(1), Ok(()));
+        assert_eq!(producer.push(2), Ok(()));
+        assert!(producer.is_full());
+        assert_eq!(producer.push(3), Err(3)); // Handed back, nothing overwritten
+        assert_eq!(consumer.pop(), Some(1));
+        assert_eq!(producer.push(3), Ok(())); // Wraps into slot 0
+        assert_eq!(consumer.len(), 2);
+        assert_eq!(consumer.pop(), Some(2));
+        assert_eq!(consumer.pop(), Some(3));
+        assert_eq!(consumer.pop(), None);
+        assert!(producer.is_empty());
+    }
+
+    #[test]
+    fn test_millions_of_items_cross_two_threads_in_order() {
+        const ITEMS: u64 = 4_000_000;
+        let (producer, consumer) = buffer(1024);
+        let handle = thread::spawn(move || {
+            for i in 0..ITEMS {
+                let mut item = i;
+                while let Err(rejected) = producer.push(item) {
+                    item = rejected;
+                    thread::yield_now();
+                }
+            }
+        });
+
+        let mut expected = 0;
+        while expected < ITEMS {
+            match consumer.pop() {
+                Some(item) => {
+                    assert_eq!(item, expected, "items arrive once each, in order");
+                    expected += 1;
+                }
+                None => thread::yield_now(),
+            }
+        }
+        handle.join().unwrap();
+        assert_eq!(consumer.pop(), None);
+    }
+
+    #[test]
+    fn test_edge_case_unpopped_items_are_dropped() {
+        let marker = std::sync::Arc::new(());
+        let (producer, consumer) = buffer(4);
+        for _ in 0..6 {
+            // Two rounds round the ring, so the remaining items wrap
+            producer.push(std::sync::Arc::clone(&marker)).unwrap();
+            consumer.pop();
+        }
+        for _ in 0..3 {
+            producer.push(std::sync::Arc::clone(&marker)).unwrap();
+        }
+        drop(consumer.pop());
+        drop(producer);
+        assert_eq!(std::sync::Arc::strong_count(&marker), 3);
+        drop(consumer);
+        assert_eq!(std::sync::Arc::strong_count(&marker), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity must be non-zero")]
+    fn test_unhappy_path_zero_capacity() {
+        buffer::<u8>(0);
+    }
+}
+
+// Run with `RUSTFLAGS="--cfg loom" cargo test --lib --release -p task_04_circular_buffer`
+#[cfg(all(test, loom))]
+mod loom_tests {
+    use super::*;
+    use sync_shim::thread;
+
+    #[test]
+    fn loom_items_wrap_past_a_full_buffer_in_order() {
+        // Capacity 1 makes the producer wait for the slot the consumer is reading
+        sync_shim::model(|| {
+            let (producer, consumer) = buffer(1);
+            let handle = thread::spawn(move || {
+                for i in 1..=2 {
+                    let mut item = i;
+                    while let Err(rejected) = producer.push(item) {
+                        item = rejected;
+                        thread::yield_now();
+                    }
+                }
+            });
+            for expected in 1..=2 {
+                loop {
+                    match consumer.pop() {
+                        Some(item) => {
+                            assert_eq!(item, expected);
+                            break;
+                        }
+                        None => thread::yield_now(),
+                    }
+                }
+            }
+            handle.join().unwrap();
+        });
+    }
+}
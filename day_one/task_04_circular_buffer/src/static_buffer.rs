@@ -0,0 +1,260 @@
+use core::fmt;
+
+use crate::OverwritePolicy;
+
+/// `CircularBuffer` with its capacity fixed at compile time and its slots inline
+///
+/// The `N` slots are an `[Option<T>; N]` inside the struct, so the buffer
+/// never allocates and can live on the stack or in a `static`. This module
+/// only uses `core`, and with the `std` feature turned off it is all the
+/// crate compiles, which makes the crate `no_std`. The API matches
+/// `CircularBuffer` except where that needs the heap: there is no
+/// `OverwritePolicy::Grow`, `pop_n`, `reserve` or `shrink_to_fit`.
+#[derive(Clone)]
+pub struct StaticCircularBuffer<T, const N: usize> {
+    buffer: [Option<T>; N],
+    head: usize,
+    tail: usize,
+    size: usize,
+    policy: OverwritePolicy,
+}
+
+impl<T, const N: usize> StaticCircularBuffer<T, N> {
+    /// Creates an empty buffer that overwrites the oldest element when full.
+    pub const fn new() -> Self {
+        Self::with_policy(OverwritePolicy::OverwriteOldest)
+    }
+
+    /// Creates an empty buffer with the given behaviour when full.
+    ///
+    /// Panics on `OverwritePolicy::Grow`, since the capacity cannot change.
+    /// `N` must be at least 1, which is checked at compile time.
+    pub const fn with_policy(policy: OverwritePolicy) -> Self {
+        const { assert!(N > 0, "a StaticCircularBuffer needs at least one slot") };
+        assert!(
+            !matches!(policy, OverwritePolicy::Grow),
+            "a StaticCircularBuffer cannot grow"
+        );
+        StaticCircularBuffer {
+            buffer: [const { None }; N],
+            head: 0,
+            tail: 0,
+            size: 0,
+            policy,
+        }
+    }
+
+    /// Returns what `push` does when the buffer is full.
+    pub fn policy(&self) -> OverwritePolicy {
+        self.policy
+    }
+
+    /// Adds an element to the buffer, returning the element that did not fit, if any.
+    pub fn push(&mut self, item: T) -> Option<T> {
+        let mut evicted = None;
+        if self.size == N {
+            match self.policy {
+                OverwritePolicy::RejectWhenFull => return Some(item),
+                // `with_policy` rules out `Grow`, so the only other policy overwrites
+                OverwritePolicy::OverwriteOldest | OverwritePolicy::Grow => {
+                    evicted = self.buffer[self.head].take();
+                    self.head = (self.head + 1) % N;
+                }
+            }
+        } else {
+            self.size += 1;
+        }
+        self.buffer[self.tail] = Some(item);
+        self.tail = (self.tail + 1) % N;
+        evicted
+    }
+
+    /// Removes and returns the oldest element from the buffer, or `None` if the buffer is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let result = self.buffer[self.head].take();
+        self.head = (self.head + 1) % N;
+        self.size -= 1;
+        result
+    }
+
+    /// Pushes clones of `items` in order, as `extend` does.
+    pub fn extend_from_slice(&mut self, items: &[T])
+    where
+        T: Clone,
+    {
+        self.extend(items.iter().cloned());
+    }
+
+    /// Keeps the `n` oldest elements and drops the rest, newest first.
+    pub fn truncate(&mut self, n: usize) {
+        while self.size > n {
+            self.size -= 1;
+            self.tail = (self.tail + N - 1) % N;
+            self.buffer[self.tail] = None;
+        }
+    }
+
+    /// Returns the oldest element without removing it.
+    pub fn peek(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Returns the element `index` places after the oldest one.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.size {
+            return None;
+        }
+        self.buffer[(self.head + index) % N].as_ref()
+    }
+
+    /// Returns the filled slots as two contiguous runs, oldest first; every slot in both is `Some`.
+    pub fn as_slices(&self) -> (&[Option<T>], &[Option<T>]) {
+        if self.head + self.size <= N {
+            (&self.buffer[self.head..self.head + self.size], &[])
+        } else {
+            let wrapped = self.head + self.size - N;
+            (&self.buffer[self.head..], &self.buffer[..wrapped])
+        }
+    }
+
+    /// Checks if the buffer is empty.
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Checks if the buffer holds `N` elements.
+    pub fn is_full(&self) -> bool {
+        self.size == N
+    }
+
+    /// Returns the current number of elements in the buffer.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Removes every element.
+    pub fn clear(&mut self) {
+        self.buffer.iter_mut().for_each(|slot| *slot = None);
+        self.head = 0;
+        self.tail = 0;
+        self.size = 0;
+    }
+
+    /// Iterates from the oldest element to the newest.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        (0..self.size).filter_map(move |i| self.get(i))
+    }
+
+    /// Returns the maximum number of elements, `N`.
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the slot holding the oldest element.
+    pub fn head_index(&self) -> usize {
+        self.head
+    }
+
+    /// Returns the slot the next push writes to.
+    pub fn tail_index(&self) -> usize {
+        self.tail
+    }
+
+    /// Iterates over the slots in storage order, `None` for empty ones.
+    pub fn slots(&self) -> impl Iterator<Item = Option<&T>> {
+        self.buffer.iter().map(Option::as_ref)
+    }
+}
+
+impl<T, const N: usize> Default for StaticCircularBuffer<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Pushes every item in turn, dropping evicted or rejected elements.
+impl<T, const N: usize> Extend<T> for StaticCircularBuffer<T, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+// Same shape as `CircularBuffer`'s, listing the elements without collecting them
+impl<T: fmt::Debug, const N: usize> fmt::Debug for StaticCircularBuffer<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        struct Items<'a, T, const N: usize>(&'a StaticCircularBuffer<T, N>);
+        impl<T: fmt::Debug, const N: usize> fmt::Debug for Items<'_, T, N> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_list().entries(self.0.iter()).finish()
+            }
+        }
+        f.debug_struct("StaticCircularBuffer")
+            .field("capacity", &N)
+            .field("head", &self.head)
+            .field("items", &Items(self))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Usable in a `static`, since `new` is a `const fn`
+    static EMPTY: StaticCircularBuffer<u8, 2> = StaticCircularBuffer::new();
+
+    #[test]
+    fn test_happy_path_wraps_and_overwrites_in_place() {
+        let mut cb: StaticCircularBuffer<i32, 3> = StaticCircularBuffer::new();
+        cb.extend_from_slice(&[1, 2, 3]);
+        assert!(cb.is_full());
+        assert_eq!(cb.push(4), Some(1)); // Overwrites slot 0
+        assert_eq!((cb.head_index(), cb.tail_index()), (1, 1));
+        assert_eq!(cb.as_slices(), (&[Some(2), Some(3)][..], &[Some(4)][..]));
+        assert_eq!(
+            (cb.peek(), cb.get(2), cb.get(3)),
+            (Some(&2), Some(&4), None)
+        );
+
+        cb.truncate(1);
+        assert_eq!(cb.iter().collect::<Vec<_>>(), vec![&2]);
+        cb.push(5);
+        assert_eq!(cb.pop(), Some(2));
+        assert_eq!(cb.pop(), Some(5));
+        assert_eq!(cb.pop(), None);
+    }
+
+    #[test]
+    fn test_unhappy_path_reject_when_full_and_no_growing() {
+        let mut cb: StaticCircularBuffer<&str, 2> =
+            StaticCircularBuffer::with_policy(OverwritePolicy::RejectWhenFull);
+        cb.extend(["a", "b"]);
+        assert_eq!(cb.push("c"), Some("c"));
+        assert_eq!(cb.policy(), OverwritePolicy::RejectWhenFull);
+
+        let result = std::panic::catch_unwind(|| {
+            StaticCircularBuffer::<u8, 2>::with_policy(OverwritePolicy::Grow)
+        });
+        assert!(result.is_err(), "A fixed array cannot grow");
+    }
+
+    #[test]
+    fn test_edge_case_static_and_cleared_buffers() {
+        assert!(EMPTY.is_empty());
+        assert_eq!(EMPTY.capacity(), 2);
+
+        let mut cb = EMPTY.clone();
+        cb.push(1);
+        cb.clear();
+        assert_eq!(cb.slots().filter(Option::is_some).count(), 0);
+        assert_eq!(
+            format!("{:?}", cb),
+            "StaticCircularBuffer { capacity: 2, head: 0, items: [] }"
+        );
+    }
+}
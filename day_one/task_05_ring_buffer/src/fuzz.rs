@@ -0,0 +1,81 @@
+//! Decodes fuzzer input into buffer operations and checks them against `VecDeque`
+
+use std::collections::VecDeque;
+
+use crate::RingBuffer;
+
+/// Runs the operations encoded in `data`, panicking if the buffer misbehaves
+///
+/// The first byte picks a capacity from 1 to 16, small enough that random
+/// input fills and wraps the buffer. Each following byte is one operation:
+/// push (with the next byte as the value), pop, peek, clear or a full scan.
+pub fn fuzz_ops(data: &[u8]) {
+    let mut bytes = data.iter().copied();
+    let capacity = match bytes.next() {
+        Some(byte) => byte as usize % 16 + 1,
+        None => return,
+    };
+    let mut buffer = RingBuffer::new(capacity);
+    let mut model = VecDeque::with_capacity(capacity);
+
+    while let Some(op) = bytes.next() {
+        match op % 5 {
+            0 | 1 => {
+                let value = bytes.next().unwrap_or(op);
+                if model.len() == capacity {
+                    model.pop_front();
+                }
+                model.push_back(value);
+                buffer.push(value);
+            }
+            2 => assert_eq!(buffer.pop(), model.pop_front(), "pop"),
+            3 => assert_eq!(buffer.peek(), model.front(), "peek"),
+            _ => {
+                if op & 0x80 != 0 {
+                    buffer.clear();
+                    model.clear();
+                }
+                assert!(buffer.iter().eq(model.iter()), "iteration order");
+            }
+        }
+        assert_eq!(buffer.size(), model.len(), "size");
+        assert_eq!(buffer.is_empty(), model.is_empty(), "is_empty");
+        assert_eq!(buffer.is_full(), model.len() == capacity, "is_full");
+    }
+    assert!(buffer.iter().eq(model.iter()), "final contents");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap_around_sequence() {
+        // Capacity 3, push 1..=5 (overwriting 1 and 2), pop twice, then scan
+        fuzz_ops(&[2, 0, 1, 0, 2, 0, 3, 0, 4, 0, 5, 2, 2, 4]);
+    }
+
+    #[test]
+    fn test_edge_case_empty_and_truncated_input() {
+        fuzz_ops(&[]);
+        fuzz_ops(&[0]);
+        fuzz_ops(&[0, 0]); // Push with its value byte missing
+        fuzz_ops(&[5, 2, 3, 0x81]); // Pop, peek and clear on an empty buffer
+    }
+
+    #[test]
+    fn test_pseudo_random_inputs() {
+        let mut state = 0x2545_f491_u32;
+        for len in 0..300 {
+            let data: Vec<u8> = (0..len)
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 17;
+                    state ^= state << 5;
+                    state as u8
+                })
+                .collect();
+            fuzz_ops(&data);
+        }
+    }
+}
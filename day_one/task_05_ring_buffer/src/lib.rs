@@ -0,0 +1,146 @@
+//! Task 5's ring buffer is task 4's `CircularBuffer`: the two were the same
+//! ring of `Option<T>` slots, so there is now one implementation with an
+//! `OverwritePolicy`. `RingBuffer::new` overwrites the oldest element when
+//! full, as it always has. This crate adds the rolling `window` statistics on
+//! top, and the fuzzing harness.
+
+#[cfg(any(test, feature = "testing"))]
+pub mod fuzz;
+pub mod window;
+
+pub use task_04_circular_buffer::{
+    CircularBuffer as RingBuffer, IntoIter, Iter, IterMut, OverwritePolicy,
+};
+pub use window::{Window, WindowAggregator};
+
+#[cfg(test)]
+mod tests {
+    use super::RingBuffer;
+
+    #[test]
+    fn test_push_and_pop() {
+        let mut buffer = RingBuffer::new(3);
+
+        buffer.push(1);
+        buffer.push(2);
+        buffer.push(3);
+
+        assert_eq!(buffer.pop(), Some(1)); // Oldest element
+        assert_eq!(buffer.pop(), Some(2));
+        assert_eq!(buffer.pop(), Some(3));
+        assert_eq!(buffer.pop(), None); // Buffer is empty
+    }
+
+    #[test]
+    fn test_overwrite_when_full() {
+        let mut buffer = RingBuffer::new(3);
+
+        buffer.push(1);
+        buffer.push(2);
+        buffer.push(3);
+        buffer.push(4); // Overwrites 1
+
+        assert_eq!(buffer.pop(), Some(2));
+        assert_eq!(buffer.pop(), Some(3));
+        assert_eq!(buffer.pop(), Some(4));
+        assert_eq!(buffer.pop(), None);
+    }
+
+    #[test]
+    fn test_peek() {
+        let mut buffer = RingBuffer::new(3);
+
+        buffer.push(10);
+        assert_eq!(buffer.peek(), Some(&10));
+        buffer.push(20);
+        assert_eq!(buffer.peek(), Some(&10));
+        buffer.pop();
+        assert_eq!(buffer.peek(), Some(&20));
+    }
+
+    #[test]
+    fn test_size_and_is_empty() {
+        let mut buffer = RingBuffer::new(2);
+
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.size(), 0);
+
+        buffer.push(1);
+        buffer.push(2);
+
+        assert!(!buffer.is_empty());
+        assert_eq!(buffer.size(), 2);
+
+        buffer.pop();
+        assert_eq!(buffer.size(), 1);
+
+        buffer.pop();
+        assert_eq!(buffer.size(), 0);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_is_full() {
+        let mut buffer = RingBuffer::new(2);
+
+        assert!(!buffer.is_full());
+
+        buffer.push(1);
+        buffer.push(2);
+
+        assert!(buffer.is_full());
+
+        buffer.pop();
+        assert!(!buffer.is_full());
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut buffer = RingBuffer::new(3);
+
+        buffer.push(1);
+        buffer.push(2);
+        buffer.push(3);
+
+        assert_eq!(buffer.size(), 3);
+
+        buffer.clear();
+
+        assert_eq!(buffer.size(), 0);
+        assert_eq!(buffer.pop(), None); // Buffer is empty
+    }
+
+    #[test]
+    fn test_iter() {
+        let mut buffer = RingBuffer::new(3);
+
+        buffer.push(10);
+        buffer.push(20);
+        buffer.push(30);
+
+        let collected: Vec<_> = buffer.iter().collect();
+        assert_eq!(collected, vec![&10, &20, &30]);
+
+        buffer.pop();
+        let collected: Vec<_> = buffer.iter().collect();
+        assert_eq!(collected, vec![&20, &30]);
+    }
+
+    #[test]
+    fn test_slot_layout() {
+        let mut buffer = RingBuffer::new(3);
+        for i in 1..=4 {
+            buffer.push(i);
+        }
+        assert_eq!(buffer.capacity(), 3);
+        assert_eq!((buffer.head_index(), buffer.tail_index()), (1, 1));
+        assert_eq!(
+            buffer.slots().collect::<Vec<_>>(),
+            vec![Some(&4), Some(&2), Some(&3)]
+        );
+
+        buffer.pop();
+        assert_eq!(buffer.head_index(), 2);
+        assert_eq!(buffer.slots().filter(Option::is_none).count(), 1);
+    }
+}
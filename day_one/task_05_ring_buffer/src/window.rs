@@ -0,0 +1,327 @@
+//! Rolling statistics over the most recent samples.
+//!
+//! `WindowAggregator` keeps timestamped samples in a `RingBuffer` and maintains
+//! count, sum, min and max as samples enter and leave the window, so each
+//! update is O(1) amortized. Quantiles are computed on demand.
+
+use std::collections::VecDeque;
+use std::ops::{Add, Sub};
+use std::time::{Duration, Instant};
+
+use crate::RingBuffer;
+
+/// Numeric sample types the aggregator can sum and compare
+///
+/// Floats are supported, but a running float sum collects rounding error as
+/// samples are added and subtracted; integer sums are exact but can overflow.
+pub trait Num: Copy + PartialOrd + Add<Output = Self> + Sub<Output = Self> {
+    const ZERO: Self;
+
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_num {
+    ($($ty:ty),*) => {
+        $(impl Num for $ty {
+            const ZERO: Self = 0 as $ty;
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        })*
+    };
+}
+
+impl_num!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64);
+
+/// Which samples count as recent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Window {
+    /// The last `n` samples
+    Count(usize),
+    /// Samples no older than the span, keeping at most `max_samples` of them
+    Time { span: Duration, max_samples: usize },
+}
+
+/// Rolling count, sum, mean, min, max and quantiles over a `Window`
+///
+/// Min and max come from monotonic deques: each holds the samples that could
+/// still become the extreme once older ones leave, so every sample is pushed
+/// and popped at most once per deque.
+///
+/// Time windows only drop old samples when the aggregator is told the time,
+/// through `push_at` or `advance`; call `advance(Instant::now())` before
+/// reading if samples may have aged out since the last push.
+pub struct WindowAggregator<T> {
+    window: Window,
+    samples: RingBuffer<(u64, Instant, T)>,
+    /// Sequence number of the next sample, used to match deque entries to evictions
+    next_seq: u64,
+    sum: T,
+    /// Increasing values, front is the minimum
+    mins: VecDeque<(u64, T)>,
+    /// Decreasing values, front is the maximum
+    maxes: VecDeque<(u64, T)>,
+}
+
+impl<T: Num> WindowAggregator<T> {
+    /// Creates an aggregator over `window`
+    pub fn new(window: Window) -> Self {
+        let capacity = match window {
+            Window::Count(n) => n,
+            Window::Time { max_samples, .. } => max_samples,
+        };
+        assert!(
+            capacity > 0,
+            "WindowAggregator needs room for at least one sample"
+        );
+        WindowAggregator {
+            window,
+            samples: RingBuffer::new(capacity),
+            next_seq: 0,
+            sum: T::ZERO,
+            mins: VecDeque::new(),
+            maxes: VecDeque::new(),
+        }
+    }
+
+    /// Aggregates the last `n` samples
+    pub fn count_window(n: usize) -> Self {
+        Self::new(Window::Count(n))
+    }
+
+    /// Aggregates samples no older than `span`, keeping at most `max_samples`
+    pub fn time_window(span: Duration, max_samples: usize) -> Self {
+        Self::new(Window::Time { span, max_samples })
+    }
+
+    /// Adds a sample taken now
+    pub fn push(&mut self, value: T) {
+        self.push_at(Instant::now(), value);
+    }
+
+    /// Adds a sample taken at `at`, first dropping samples that are out of the window by then
+    ///
+    /// Timestamps should not go backwards; a sample older than the newest one
+    /// is still added, but only ages out once the samples before it have.
+    pub fn push_at(&mut self, at: Instant, value: T) {
+        self.advance(at);
+        if self.samples.is_full() {
+            self.evict_oldest();
+        }
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.samples.push((seq, at, value));
+        self.sum = self.sum + value;
+
+        while self.mins.back().is_some_and(|&(_, min)| min > value) {
+            self.mins.pop_back();
+        }
+        self.mins.push_back((seq, value));
+        while self.maxes.back().is_some_and(|&(_, max)| max < value) {
+            self.maxes.pop_back();
+        }
+        self.maxes.push_back((seq, value));
+    }
+
+    /// Drops samples that have left a time window by `now`; count windows ignore the time
+    pub fn advance(&mut self, now: Instant) {
+        let Window::Time { span, .. } = self.window else {
+            return;
+        };
+        while self
+            .samples
+            .peek()
+            .is_some_and(|&(_, at, _)| now.saturating_duration_since(at) > span)
+        {
+            self.evict_oldest();
+        }
+    }
+
+    /// Returns the number of samples in the window
+    pub fn count(&self) -> usize {
+        self.samples.size()
+    }
+
+    /// Checks if the window holds no samples
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Returns the sum of the samples in the window, zero if it is empty
+    pub fn sum(&self) -> T {
+        self.sum
+    }
+
+    /// Returns the mean of the samples in the window
+    pub fn mean(&self) -> Option<f64> {
+        (!self.is_empty()).then(|| self.sum.to_f64() / self.count() as f64)
+    }
+
+    /// Returns the smallest sample in the window
+    pub fn min(&self) -> Option<T> {
+        self.mins.front().map(|&(_, min)| min)
+    }
+
+    /// Returns the largest sample in the window
+    pub fn max(&self) -> Option<T> {
+        self.maxes.front().map(|&(_, max)| max)
+    }
+
+    /// Returns the `q`-quantile (0.0 to 1.0) by nearest rank, e.g. `quantile(0.99)` for p99
+    ///
+    /// Sorts a copy of the window, so this is O(n log n) rather than O(1).
+    pub fn quantile(&self, q: f64) -> Option<T> {
+        if self.is_empty() || !(0.0..=1.0).contains(&q) {
+            return None;
+        }
+        let mut values: Vec<T> = self.values().collect();
+        // NaN samples have no place in the order, so they sort last
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Greater));
+        let rank = (q * values.len() as f64).ceil() as usize;
+        Some(values[rank.saturating_sub(1)])
+    }
+
+    /// Iterates over the samples in the window, oldest first
+    pub fn values(&self) -> impl Iterator<Item = T> + '_ {
+        self.samples.iter().map(|&(_, _, value)| value)
+    }
+
+    /// Returns the window this aggregator covers
+    pub fn window(&self) -> Window {
+        self.window
+    }
+
+    /// Removes every sample
+    pub fn clear(&mut self) {
+        self.samples.clear();
+        self.mins.clear();
+        self.maxes.clear();
+        self.sum = T::ZERO;
+    }
+
+    fn evict_oldest(&mut self) {
+        let Some((seq, _, value)) = self.samples.pop() else {
+            return;
+        };
+        self.sum = self.sum - value;
+        if self.mins.front().is_some_and(|&(front, _)| front == seq) {
+            self.mins.pop_front();
+        }
+        if self.maxes.front().is_some_and(|&(front, _)| front == seq) {
+            self.maxes.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MS: Duration = Duration::from_millis(1);
+
+    #[test]
+    fn test_happy_path_count_window_rolls() {
+        let mut window = WindowAggregator::count_window(3);
+        for value in [5, 1, 4] {
+            window.push(value);
+        }
+        assert_eq!((window.count(), window.sum()), (3, 10));
+        assert_eq!((window.min(), window.max()), (Some(1), Some(5)));
+
+        // 5 leaves the window, 1 is still the minimum
+        window.push(2);
+        assert_eq!(window.values().collect::<Vec<_>>(), vec![1, 4, 2]);
+        assert_eq!((window.min(), window.max()), (Some(1), Some(4)));
+        assert_eq!(window.mean(), Some(7.0 / 3.0));
+
+        window.push(3);
+        assert_eq!(
+            (window.min(), window.max(), window.sum()),
+            (Some(2), Some(4), 9)
+        );
+    }
+
+    #[test]
+    fn test_happy_path_time_window_ages_out() {
+        let start = Instant::now();
+        let mut window = WindowAggregator::time_window(10 * MS, 100);
+        window.push_at(start, 9.0);
+        window.push_at(start + 5 * MS, 1.0);
+        window.push_at(start + 10 * MS, 3.0);
+        assert_eq!(
+            window.count(),
+            3,
+            "A sample exactly one span old is still in"
+        );
+
+        window.advance(start + 11 * MS);
+        assert_eq!(window.count(), 2);
+        assert_eq!(window.max(), Some(3.0));
+        assert_eq!(window.mean(), Some(2.0));
+
+        window.advance(start + 30 * MS);
+        assert!(window.is_empty());
+        assert_eq!((window.min(), window.mean()), (None, None));
+    }
+
+    #[test]
+    fn test_time_window_is_capped_by_max_samples() {
+        let start = Instant::now();
+        let mut window = WindowAggregator::time_window(Duration::from_secs(60), 2);
+        for (i, value) in [7u32, 8, 9].into_iter().enumerate() {
+            window.push_at(start + i as u32 * MS, value);
+        }
+        assert_eq!(window.values().collect::<Vec<_>>(), vec![8, 9]);
+        assert_eq!(window.sum(), 17);
+    }
+
+    #[test]
+    fn test_quantiles() {
+        let mut window = WindowAggregator::count_window(100);
+        for value in (1..=100).rev() {
+            window.push(value);
+        }
+        assert_eq!(window.quantile(0.5), Some(50));
+        assert_eq!(window.quantile(0.99), Some(99));
+        assert_eq!(window.quantile(0.0), Some(1));
+        assert_eq!(window.quantile(1.0), Some(100));
+        assert_eq!(window.quantile(1.5), None);
+    }
+
+    #[test]
+    fn test_extremes_match_a_brute_force_scan() {
+        let mut window = WindowAggregator::count_window(8);
+        let mut state = 12345u32;
+        let mut recent = Vec::new();
+        for _ in 0..500 {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            let value = (state >> 16) as i64 % 50 - 25;
+            window.push(value);
+            recent.push(value);
+            let tail = &recent[recent.len().saturating_sub(8)..];
+            assert_eq!(window.min(), tail.iter().copied().min());
+            assert_eq!(window.max(), tail.iter().copied().max());
+            assert_eq!(window.sum(), tail.iter().sum::<i64>());
+        }
+    }
+
+    #[test]
+    fn test_edge_case_duplicates_and_clear() {
+        let mut window = WindowAggregator::count_window(2);
+        window.push(4);
+        window.push(4);
+        window.push(4);
+        // Equal values each keep their own deque entry
+        assert_eq!((window.min(), window.max()), (Some(4), Some(4)));
+        window.clear();
+        assert_eq!((window.count(), window.sum(), window.max()), (0, 0, None));
+        assert_eq!(window.quantile(0.5), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one sample")]
+    fn test_unhappy_path_empty_window_panics() {
+        WindowAggregator::<f64>::count_window(0);
+    }
+}
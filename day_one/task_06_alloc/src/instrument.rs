@@ -0,0 +1,58 @@
+//! Hooks behind the `tracing` feature. Without it every function is empty and
+//! inlines away.
+//!
+//! Subscribers and metric recorders allocate themselves, and once
+//! `CustomAllocator` is the global allocator those allocations come straight
+//! back here. A per-thread flag drops the nested reports instead of recursing.
+
+#[cfg(feature = "tracing")]
+use std::cell::Cell;
+
+#[cfg(feature = "tracing")]
+thread_local! {
+    static REPORTING: Cell<bool> = const { Cell::new(false) };
+}
+
+// Runs `report` unless this thread is already inside one, or is tearing down
+// its thread locals
+#[cfg(feature = "tracing")]
+fn guarded(report: impl FnOnce()) {
+    let entered = REPORTING
+        .try_with(|reporting| !reporting.replace(true))
+        .unwrap_or(false);
+    if entered {
+        report();
+        let _ = REPORTING.try_with(|reporting| reporting.set(false));
+    }
+}
+
+/// Records an allocation; `live_bytes` is the total once it succeeded
+#[inline]
+pub(crate) fn allocation(size: usize, succeeded: bool, live_bytes: usize) {
+    #[cfg(feature = "tracing")]
+    guarded(|| {
+        tracing::trace!(size, succeeded, live_bytes, "custom allocator alloc");
+        if succeeded {
+            metrics::counter!("custom_allocator.allocations").increment(1);
+            metrics::histogram!("custom_allocator.allocation_bytes").record(size as f64);
+            metrics::gauge!("custom_allocator.bytes_allocated").set(live_bytes as f64);
+        } else {
+            metrics::counter!("custom_allocator.allocation_failures").increment(1);
+        }
+    });
+    #[cfg(not(feature = "tracing"))]
+    let _ = (size, succeeded, live_bytes);
+}
+
+/// Records a deallocation; `live_bytes` is the total after it
+#[inline]
+pub(crate) fn deallocation(size: usize, live_bytes: usize) {
+    #[cfg(feature = "tracing")]
+    guarded(|| {
+        tracing::trace!(size, live_bytes, "custom allocator dealloc");
+        metrics::counter!("custom_allocator.deallocations").increment(1);
+        metrics::gauge!("custom_allocator.bytes_allocated").set(live_bytes as f64);
+    });
+    #[cfg(not(feature = "tracing"))]
+    let _ = (size, live_bytes);
+}
@@ -1,43 +1,52 @@
-use std::alloc::{GlobalAlloc, Layout};
+use std::alloc::{GlobalAlloc, Layout, System};
 use std::sync::atomic::{AtomicUsize, Ordering};
 
-struct CustomAllocator;
+mod instrument;
+
+pub struct CustomAllocator;
 
 static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
 
 impl CustomAllocator {
-    fn now_allocated() -> usize {
+    pub fn now_allocated() -> usize {
         ALLOCATED.load(Ordering::Relaxed)
     }
+
+    // Number of allocations that have not been freed yet
+    pub fn live_allocations() -> usize {
+        LIVE.load(Ordering::Relaxed)
+    }
+
+    // The most bytes that were ever allocated at the same time
+    pub fn peak_allocated() -> usize {
+        PEAK.load(Ordering::Relaxed)
+    }
 }
 
 unsafe impl GlobalAlloc for CustomAllocator {
     unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
-        let memory = std::alloc::alloc(layout);
+        // Go to System directly: std::alloc::alloc would recurse into this allocator once it is global
+        let memory = System.alloc(layout);
         if !memory.is_null() {
-            ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+            let now = ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            LIVE.fetch_add(1, Ordering::Relaxed);
+            PEAK.fetch_max(now, Ordering::Relaxed);
+            instrument::allocation(layout.size(), true, now);
+        } else {
+            instrument::allocation(layout.size(), false, ALLOCATED.load(Ordering::Relaxed));
         }
         memory
     }
 
     unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
-        std::alloc::dealloc(ptr, layout);
-        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
-    }
-}
-
-fn main() {
-    // Example:
-    let layout = Layout::from_size_align(1024, 8).unwrap();
-    let ptr = unsafe { CustomAllocator.alloc(layout) };
-    if !ptr.is_null() {
-        println!("Allocated memory at: {:?}", ptr);
-        println!("Current allocated bytes: {}", CustomAllocator::now_allocated());
-        // Use the memory...
-        unsafe { CustomAllocator.dealloc(ptr, layout) };
-        println!("After deallocation, current allocated bytes: {}", CustomAllocator::now_allocated());
-    } else {
-        println!("Memory allocation failed");
+        System.dealloc(ptr, layout);
+        let now = ALLOCATED
+            .fetch_sub(layout.size(), Ordering::Relaxed)
+            .wrapping_sub(layout.size());
+        LIVE.fetch_sub(1, Ordering::Relaxed);
+        instrument::deallocation(layout.size(), now);
     }
 }
 
@@ -79,7 +88,6 @@ mod tests {
             }
             Err(_) => {
                 // Layout creation failed, which is expected for invalid sizes
-                println!("Layout creation failed as expected for an enormous size");
             }
         }
     }    
@@ -106,6 +114,22 @@ mod tests {
         assert_eq!(CustomAllocator::now_allocated(), 0, "After all deallocations, allocated bytes should be zero");
     }
 
+    #[test]
+    fn test_live_allocations_and_peak() {
+        let layout = Layout::from_size_align(4096, 8).unwrap();
+        let ptr = unsafe { CustomAllocator.alloc(layout) };
+
+        // Other tests share the counters, so only check lower bounds here
+        assert!(CustomAllocator::live_allocations() >= 1, "The allocation should be counted as live");
+        assert!(CustomAllocator::peak_allocated() >= 4096, "The peak should include this allocation");
+
+        unsafe {
+            CustomAllocator.dealloc(ptr, layout);
+        }
+
+        assert!(CustomAllocator::peak_allocated() >= 4096, "Freeing memory should not lower the peak");
+    }
+
     #[test]
     fn test_zero_sized_allocation() {
         let layout = Layout::from_size_align(0, 8).unwrap();
@@ -120,4 +144,4 @@ mod tests {
         
         assert_eq!(CustomAllocator::now_allocated(), 0, "Deallocation of zero-sized should not affect allocated bytes");
     }
-}
\ No newline at end of file
+}
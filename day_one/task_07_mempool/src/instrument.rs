@@ -0,0 +1,33 @@
+//! Hooks behind the `tracing` feature. Without it every function is empty and
+//! inlines away.
+
+/// Records an `allocate` call: a hit handed out a chunk, a miss found the pool empty
+#[inline]
+pub(crate) fn allocation(hit: bool, chunk_size: usize, in_use: usize) {
+    #[cfg(feature = "tracing")]
+    {
+        tracing::trace!(hit, chunk_size, in_use, "mempool allocate");
+        if hit {
+            metrics::counter!("mempool.hits").increment(1);
+            metrics::histogram!("mempool.allocation_bytes").record(chunk_size as f64);
+        } else {
+            metrics::counter!("mempool.misses").increment(1);
+        }
+        metrics::gauge!("mempool.chunks_in_use").set(in_use as f64);
+    }
+    #[cfg(not(feature = "tracing"))]
+    let _ = (hit, chunk_size, in_use);
+}
+
+/// Records a chunk coming back to the pool
+#[inline]
+pub(crate) fn deallocation(in_use: usize) {
+    #[cfg(feature = "tracing")]
+    {
+        tracing::trace!(in_use, "mempool deallocate");
+        metrics::counter!("mempool.deallocations").increment(1);
+        metrics::gauge!("mempool.chunks_in_use").set(in_use as f64);
+    }
+    #[cfg(not(feature = "tracing"))]
+    let _ = in_use;
+}
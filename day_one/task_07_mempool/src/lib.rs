@@ -0,0 +1,190 @@
+use std::io;
+use std::ops::Deref;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use task_27_page_alloc::{PageAllocator, Pages};
+
+mod instrument;
+pub mod resource;
+
+pub use resource::{PoolError, Pooled, ResourcePool, ResourcePoolOptions};
+
+/// A pool of equally sized chunks, each a `Vec<u8>` by default
+///
+/// `with_pages` builds a pool whose chunks are `Pages` mapped straight from
+/// the OS instead, one mapping per chunk.
+pub struct MemoryPool<C = Vec<u8>> {
+    pool: Mutex<Vec<C>>,
+    chunk_size: usize,
+    capacity: usize,
+    in_use: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+impl MemoryPool {
+    /// Creates a new memory pool with a specified chunk size and number of chunks.
+    pub fn new(chunk_size: usize, capacity: usize) -> Arc<Self> {
+        let pool = (0..capacity)
+            .map(|_| vec![0; chunk_size])
+            .collect::<Vec<_>>();
+        MemoryPool::from_chunks(pool, chunk_size, capacity)
+    }
+}
+
+impl MemoryPool<Pages> {
+    /// Creates a pool whose chunks are whole pages from the OS instead of the heap.
+    /// The chunk size is rounded up to a multiple of the page size; a size of zero,
+    /// or one too large to round up, is rejected with `InvalidInput`.
+    pub fn with_pages(chunk_size: usize, capacity: usize) -> io::Result<Arc<Self>> {
+        let chunk_size = PageAllocator::round_up(chunk_size)
+            .filter(|&size| size > 0)
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidInput, "invalid page pool chunk size")
+            })?;
+        let pool = (0..capacity)
+            .map(|_| PageAllocator::allocate(chunk_size))
+            .collect::<io::Result<Vec<_>>>()?;
+        Ok(MemoryPool::from_chunks(pool, chunk_size, capacity))
+    }
+}
+
+impl<C: Deref<Target = [u8]>> MemoryPool<C> {
+    fn from_chunks(pool: Vec<C>, chunk_size: usize, capacity: usize) -> Arc<Self> {
+        Arc::new(Self {
+            pool: Mutex::new(pool),
+            chunk_size,
+            capacity,
+            in_use: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+        })
+    }
+
+    /// Allocates a chunk from the pool. Returns None if the pool is exhausted.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
+    pub fn allocate(&self) -> Option<C> {
+        let mut pool = self.pool.lock().unwrap();
+        let Some(chunk) = pool.pop() else {
+            instrument::allocation(false, self.chunk_size, self.chunks_in_use());
+            return None;
+        };
+        let in_use = self.in_use.fetch_add(1, Ordering::Relaxed) + 1;
+        self.peak.fetch_max(in_use, Ordering::Relaxed);
+        instrument::allocation(true, self.chunk_size, in_use);
+        Some(chunk)
+    }
+
+    /// Returns a chunk back to the pool.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
+    pub fn deallocate(&self, chunk: C) {
+        if chunk.len() == self.chunk_size {
+            let mut pool = self.pool.lock().unwrap();
+            // Chunks that never came from the pool don't count against it
+            let _ = self
+                .in_use
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1));
+            if pool.len() < self.capacity {
+                pool.push(chunk);
+            }
+            instrument::deallocation(self.chunks_in_use());
+        } else {
+            panic!("Chunk size does not match the pool's chunk size.");
+        }
+    }
+
+    /// Checks the number of available chunks in the pool.
+    pub fn available_chunks(&self) -> usize {
+        let pool = self.pool.lock().unwrap();
+        pool.len()
+    }
+
+    /// Returns the size in bytes of every chunk.
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    /// Returns how many chunks are currently handed out.
+    pub fn chunks_in_use(&self) -> usize {
+        self.in_use.load(Ordering::Relaxed)
+    }
+
+    /// Returns the most chunks that were ever handed out at once.
+    pub fn peak_chunks_in_use(&self) -> usize {
+        self.peak.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_pool() {
+        let pool = MemoryPool::new(1024, 10);
+
+        // Allocate all chunks
+        let mut allocated = Vec::new();
+        for _ in 0..10 {
+            let chunk = pool.allocate().expect("Should allocate successfully");
+            assert_eq!(chunk.len(), 1024);
+            allocated.push(chunk);
+        }
+
+        // Pool should be exhausted
+        assert!(pool.allocate().is_none(), "Pool should be exhausted");
+
+        // Deallocate a chunk
+        pool.deallocate(allocated.pop().unwrap());
+        assert_eq!(pool.available_chunks(), 1, "One chunk should be available");
+
+        // Reallocate the chunk
+        let chunk = pool.allocate().expect("Should allocate successfully");
+        assert_eq!(chunk.len(), 1024);
+    }
+
+    #[test]
+    fn test_chunks_in_use() {
+        let pool = MemoryPool::new(64, 4);
+        let a = pool.allocate().unwrap();
+        let b = pool.allocate().unwrap();
+        assert_eq!(pool.chunks_in_use(), 2);
+
+        pool.deallocate(a);
+        assert_eq!(pool.chunks_in_use(), 1, "Returned chunks are no longer in use");
+        assert_eq!(pool.peak_chunks_in_use(), 2, "The peak is kept");
+
+        pool.deallocate(b);
+        pool.deallocate(vec![0; 64]);
+        assert_eq!(pool.chunks_in_use(), 0, "Foreign chunks don't underflow the count");
+    }
+
+    #[test]
+    fn test_page_backed_pool() {
+        let page = PageAllocator::page_size();
+        let pool = MemoryPool::with_pages(1000, 3).unwrap();
+        assert_eq!(pool.chunk_size(), page, "Chunks are rounded up to whole pages");
+        assert_eq!(pool.available_chunks(), 3);
+
+        let mut chunk = pool.allocate().unwrap();
+        assert_eq!(chunk.as_ptr() as usize % page, 0, "Each chunk is its own mapping");
+        assert!(chunk.iter().all(|&b| b == 0));
+        chunk[999] = 7;
+        pool.deallocate(chunk);
+        let chunk = pool.allocate().unwrap();
+        assert_eq!(chunk[999], 7, "Chunks are reused, not remapped");
+        assert_eq!(pool.chunks_in_use(), 1);
+
+        for chunk_size in [0, usize::MAX] {
+            let err = MemoryPool::with_pages(chunk_size, 2).err().unwrap();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_deallocate() {
+        let pool = MemoryPool::new(1024, 10);
+        // Attempt to deallocate a chunk with an invalid size
+        pool.deallocate(vec![0; 512]);
+    }
+}
@@ -0,0 +1,616 @@
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
+
+type BoxError = Box<dyn Error + Send + Sync>;
+type Factory<R> = Box<dyn Fn() -> Result<R, BoxError> + Send + Sync>;
+type HealthCheck<R> = Arc<dyn Fn(&R) -> bool + Send + Sync>;
+
+/// Why a resource could not be handed out
+#[derive(Debug)]
+pub enum PoolError {
+    /// Every resource is in use and the pool is at its maximum size
+    Exhausted,
+    /// No resource came free before the timeout
+    Timeout,
+    /// The factory failed to create a new resource
+    Create(BoxError),
+}
+
+impl fmt::Display for PoolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PoolError::Exhausted => write!(f, "every pooled resource is in use"),
+            PoolError::Timeout => write!(f, "timed out waiting for a pooled resource"),
+            PoolError::Create(err) => write!(f, "failed to create a pooled resource: {err}"),
+        }
+    }
+}
+
+impl Error for PoolError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PoolError::Create(err) => Some(err.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+struct Idle<R> {
+    resource: R,
+    created_at: Instant,
+    idle_since: Instant,
+}
+
+struct State<R> {
+    /// Most recently returned last, so the stalest resources sit at the front
+    idle: Vec<Idle<R>>,
+    /// Resources alive, idle or checked out, plus creations in progress
+    total: usize,
+    /// Async `acquire` calls waiting for a resource
+    wakers: Vec<Waker>,
+}
+
+struct Inner<R> {
+    state: Mutex<State<R>>,
+    /// Signalled whenever a resource is returned or a slot frees up
+    available: Condvar,
+    create: Factory<R>,
+    is_healthy: Option<HealthCheck<R>>,
+    max_size: usize,
+    idle_timeout: Option<Duration>,
+    max_lifetime: Option<Duration>,
+}
+
+impl<R> Inner<R> {
+    fn state(&self) -> MutexGuard<'_, State<R>> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn is_expired(&self, created_at: Instant, idle_since: Option<Instant>, now: Instant) -> bool {
+        let too_old = self
+            .max_lifetime
+            .is_some_and(|lifetime| now.duration_since(created_at) >= lifetime);
+        let idle_too_long = self
+            .idle_timeout
+            .zip(idle_since)
+            .is_some_and(|(timeout, since)| now.duration_since(since) >= timeout);
+        too_old || idle_too_long
+    }
+
+    // Something changed that may let a waiter proceed
+    fn notify(&self, state: MutexGuard<'_, State<R>>, wakers: Vec<Waker>) {
+        drop(state);
+        self.available.notify_one();
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+
+    // Forgets a resource that was dropped instead of returned
+    fn discard(&self) {
+        let mut state = self.state();
+        state.total -= 1;
+        let wakers = std::mem::take(&mut state.wakers);
+        self.notify(state, wakers);
+    }
+}
+
+/// Builder for a `ResourcePool`
+///
+/// Defaults: at most 10 resources, no idle timeout, no maximum lifetime and no
+/// health check.
+pub struct ResourcePoolOptions<R> {
+    max_size: usize,
+    idle_timeout: Option<Duration>,
+    max_lifetime: Option<Duration>,
+    is_healthy: Option<HealthCheck<R>>,
+}
+
+impl<R> ResourcePoolOptions<R> {
+    /// Most resources alive at once, checked out or idle
+    pub fn max_size(&mut self, max_size: usize) -> &mut Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Drop resources that have sat idle this long instead of handing them out
+    pub fn idle_timeout(&mut self, idle_timeout: Duration) -> &mut Self {
+        self.idle_timeout = Some(idle_timeout);
+        self
+    }
+
+    /// Recycle resources this long after they were created
+    pub fn max_lifetime(&mut self, max_lifetime: Duration) -> &mut Self {
+        self.max_lifetime = Some(max_lifetime);
+        self
+    }
+
+    /// Run `is_healthy` on an idle resource before handing it out; failing ones are dropped
+    pub fn health_check(
+        &mut self,
+        is_healthy: impl Fn(&R) -> bool + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.is_healthy = Some(Arc::new(is_healthy));
+        self
+    }
+
+    /// Builds a pool that makes new resources with `create`
+    pub fn build<E>(
+        &self,
+        create: impl Fn() -> Result<R, E> + Send + Sync + 'static,
+    ) -> ResourcePool<R>
+    where
+        E: Into<BoxError>,
+    {
+        assert!(self.max_size > 0, "ResourcePool max_size must be non-zero");
+        ResourcePool {
+            inner: Arc::new(Inner {
+                state: Mutex::new(State {
+                    idle: Vec::new(),
+                    total: 0,
+                    wakers: Vec::new(),
+                }),
+                available: Condvar::new(),
+                create: Box::new(move || create().map_err(Into::into)),
+                is_healthy: self.is_healthy.clone(),
+                max_size: self.max_size,
+                idle_timeout: self.idle_timeout,
+                max_lifetime: self.max_lifetime,
+            }),
+        }
+    }
+}
+
+/// Pool of reusable resources such as connections, created on demand
+///
+/// Where `MemoryPool` hands out pre-allocated byte chunks, this pool holds any
+/// `R` made by a user-supplied factory, up to `max_size` at once. Resources
+/// come back automatically when their `Pooled` guard is dropped. Before an idle
+/// resource is handed out it is checked against the idle timeout, the maximum
+/// lifetime and the health check, and dropped if any of them fails.
+///
+/// `acquire_timeout` blocks the thread while the pool is exhausted; `acquire`
+/// is a future that waits without blocking and works on any executor. Both run
+/// the factory and health check inline, on the acquiring thread or task.
+/// Cloning the pool gives another handle to the same resources.
+pub struct ResourcePool<R> {
+    inner: Arc<Inner<R>>,
+}
+
+impl<R> ResourcePool<R> {
+    /// Creates a pool of at most `max_size` resources made by `create`
+    pub fn new<E>(
+        max_size: usize,
+        create: impl Fn() -> Result<R, E> + Send + Sync + 'static,
+    ) -> Self
+    where
+        E: Into<BoxError>,
+    {
+        Self::options().max_size(max_size).build(create)
+    }
+
+    /// Starts configuring a pool
+    pub fn options() -> ResourcePoolOptions<R> {
+        ResourcePoolOptions {
+            max_size: 10,
+            idle_timeout: None,
+            max_lifetime: None,
+            is_healthy: None,
+        }
+    }
+
+    /// Takes a usable idle resource or creates one, failing with `Exhausted`
+    /// instead of waiting
+    pub fn try_acquire(&self) -> Result<Pooled<R>, PoolError> {
+        self.checkout()?.ok_or(PoolError::Exhausted)
+    }
+
+    /// Like `try_acquire`, but blocks up to `timeout` for a resource to come free
+    pub fn acquire_timeout(&self, timeout: Duration) -> Result<Pooled<R>, PoolError> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(pooled) = self.checkout()? {
+                return Ok(pooled);
+            }
+            let state = self.inner.state();
+            if self.can_proceed(&state) {
+                continue;
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(PoolError::Timeout);
+            }
+            drop(
+                self.inner
+                    .available
+                    .wait_timeout(state, remaining)
+                    .unwrap_or_else(|poisoned| poisoned.into_inner()),
+            );
+        }
+    }
+
+    /// Waits for a resource without blocking the thread
+    ///
+    /// Combine with the runtime's timeout to bound the wait.
+    pub fn acquire(&self) -> Acquire<'_, R> {
+        Acquire { pool: self }
+    }
+
+    /// Drops every idle resource past its idle timeout or lifetime, returning how many
+    ///
+    /// Expired resources are also dropped lazily when they would be handed out;
+    /// call this periodically to release them sooner.
+    pub fn evict_expired(&self) -> usize {
+        let now = Instant::now();
+        let mut state = self.inner.state();
+        let before = state.idle.len();
+        state.idle.retain(|idle| {
+            !self
+                .inner
+                .is_expired(idle.created_at, Some(idle.idle_since), now)
+        });
+        let evicted = before - state.idle.len();
+        state.total -= evicted;
+        evicted
+    }
+
+    /// Returns the number of live resources, checked out or idle
+    pub fn size(&self) -> usize {
+        self.inner.state().total
+    }
+
+    /// Returns the number of idle resources
+    pub fn idle(&self) -> usize {
+        self.inner.state().idle.len()
+    }
+
+    /// Returns the most resources the pool will keep alive at once
+    pub fn max_size(&self) -> usize {
+        self.inner.max_size
+    }
+
+    // A resource may be available without waiting
+    fn can_proceed(&self, state: &State<R>) -> bool {
+        !state.idle.is_empty() || state.total < self.inner.max_size
+    }
+
+    // Hands out an idle resource that passes every check, or creates one if
+    // there is room; `None` means the caller has to wait
+    fn checkout(&self) -> Result<Option<Pooled<R>>, PoolError> {
+        loop {
+            let mut state = self.inner.state();
+            let Some(idle) = state.idle.pop() else {
+                if state.total >= self.inner.max_size {
+                    return Ok(None);
+                }
+                // Reserve the slot, then create without holding the lock
+                state.total += 1;
+                drop(state);
+                return match (self.inner.create)() {
+                    Ok(resource) => Ok(Some(self.wrap(resource, Instant::now()))),
+                    Err(err) => {
+                        self.inner.discard();
+                        Err(PoolError::Create(err))
+                    }
+                };
+            };
+            drop(state);
+
+            let expired =
+                self.inner
+                    .is_expired(idle.created_at, Some(idle.idle_since), Instant::now());
+            let healthy = !expired
+                && self
+                    .inner
+                    .is_healthy
+                    .as_ref()
+                    .is_none_or(|check| check(&idle.resource));
+            if healthy {
+                return Ok(Some(self.wrap(idle.resource, idle.created_at)));
+            }
+            drop(idle);
+            self.inner.discard();
+        }
+    }
+
+    fn wrap(&self, resource: R, created_at: Instant) -> Pooled<R> {
+        Pooled {
+            resource: Some(resource),
+            created_at,
+            pool: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<R> Clone for ResourcePool<R> {
+    fn clone(&self) -> Self {
+        ResourcePool {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<R> fmt::Debug for ResourcePool<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.inner.state();
+        f.debug_struct("ResourcePool")
+            .field("size", &state.total)
+            .field("idle", &state.idle.len())
+            .field("max_size", &self.inner.max_size)
+            .finish()
+    }
+}
+
+/// Future returned by `ResourcePool::acquire`
+pub struct Acquire<'a, R> {
+    pool: &'a ResourcePool<R>,
+}
+
+impl<R> Future for Acquire<'_, R> {
+    type Output = Result<Pooled<R>, PoolError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        loop {
+            if let Some(pooled) = self.pool.checkout()? {
+                return Poll::Ready(Ok(pooled));
+            }
+            // Register under the lock that returns take, so a return can't slip
+            // in between the failed checkout and the registration unnoticed
+            let mut state = self.pool.inner.state();
+            if self.pool.can_proceed(&state) {
+                continue;
+            }
+            if !state.wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+                state.wakers.push(cx.waker().clone());
+            }
+            return Poll::Pending;
+        }
+    }
+}
+
+/// A resource checked out of a `ResourcePool`; dropping it returns the resource
+pub struct Pooled<R> {
+    resource: Option<R>,
+    created_at: Instant,
+    pool: Arc<Inner<R>>,
+}
+
+impl<R> Pooled<R> {
+    /// Takes the resource out of the pool for good, freeing its slot
+    pub fn detach(mut self) -> R {
+        let resource = self
+            .resource
+            .take()
+            .expect("resource is present until drop");
+        self.pool.discard();
+        resource
+    }
+
+    /// Returns when the resource was created
+    pub fn created_at(&self) -> Instant {
+        self.created_at
+    }
+}
+
+impl<R> Deref for Pooled<R> {
+    type Target = R;
+
+    fn deref(&self) -> &R {
+        self.resource
+            .as_ref()
+            .expect("resource is present until drop")
+    }
+}
+
+impl<R> DerefMut for Pooled<R> {
+    fn deref_mut(&mut self) -> &mut R {
+        self.resource
+            .as_mut()
+            .expect("resource is present until drop")
+    }
+}
+
+impl<R> Drop for Pooled<R> {
+    fn drop(&mut self) {
+        let Some(resource) = self.resource.take() else {
+            return;
+        };
+        let now = Instant::now();
+        if self.pool.is_expired(self.created_at, None, now) {
+            // Past its lifetime: recycle the slot instead of the resource
+            drop(resource);
+            self.pool.discard();
+            return;
+        }
+        let mut state = self.pool.state();
+        state.idle.push(Idle {
+            resource,
+            created_at: self.created_at,
+            idle_since: now,
+        });
+        let wakers = std::mem::take(&mut state.wakers);
+        self.pool.notify(state, wakers);
+    }
+}
+
+impl<R: fmt::Debug> fmt::Debug for Pooled<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Pooled").field(&**self).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::task::Wake;
+    use std::thread;
+
+    // Counts connections opened so tests can tell reuse from creation
+    fn counting_pool(max_size: usize) -> (ResourcePool<usize>, Arc<AtomicUsize>) {
+        let opened = Arc::new(AtomicUsize::new(0));
+        let pool = ResourcePool::new(max_size, {
+            let opened = Arc::clone(&opened);
+            move || Ok::<_, PoolError>(opened.fetch_add(1, Ordering::SeqCst))
+        });
+        (pool, opened)
+    }
+
+    struct ThreadWaker(thread::Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = std::pin::pin!(future);
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+            thread::park();
+        }
+    }
+
+    #[test]
+    fn test_happy_path_resources_are_reused() {
+        let (pool, opened) = counting_pool(2);
+        let first = pool.try_acquire().unwrap();
+        assert_eq!(*first, 0);
+        drop(first);
+        assert_eq!(pool.idle(), 1);
+
+        let again = pool.try_acquire().unwrap();
+        assert_eq!(*again, 0, "The idle connection is handed out again");
+        let second = pool.try_acquire().unwrap();
+        assert_eq!(*second, 1);
+        assert_eq!(opened.load(Ordering::SeqCst), 2);
+        assert!(matches!(pool.try_acquire(), Err(PoolError::Exhausted)));
+    }
+
+    #[test]
+    fn test_health_check_drops_broken_resources() {
+        let healthy = Arc::new(AtomicBool::new(true));
+        let pool = ResourcePool::options()
+            .max_size(1)
+            .health_check({
+                let healthy = Arc::clone(&healthy);
+                move |_: &String| healthy.load(Ordering::SeqCst)
+            })
+            .build(|| Ok::<_, PoolError>("conn".to_string()));
+
+        let mut conn = pool.try_acquire().unwrap();
+        conn.push_str("-used");
+        drop(conn);
+        healthy.store(false, Ordering::SeqCst);
+        // The unhealthy idle connection is dropped and a fresh one made in its place
+        assert_eq!(*pool.try_acquire().unwrap(), "conn");
+        assert_eq!(pool.size(), 1);
+    }
+
+    #[test]
+    fn test_idle_timeout_and_max_lifetime() {
+        let opened = Arc::new(AtomicUsize::new(0));
+        let pool = ResourcePool::options()
+            .idle_timeout(Duration::from_millis(20))
+            .max_lifetime(Duration::from_millis(60))
+            .build({
+                let opened = Arc::clone(&opened);
+                move || Ok::<_, PoolError>(opened.fetch_add(1, Ordering::SeqCst))
+            });
+
+        drop(pool.try_acquire().unwrap());
+        thread::sleep(Duration::from_millis(30));
+        assert_eq!(pool.evict_expired(), 1);
+        assert_eq!((pool.size(), pool.idle()), (0, 0));
+
+        // Held past its lifetime, the resource is not returned to the pool
+        let long_lived = pool.try_acquire().unwrap();
+        assert_eq!(*long_lived, 1);
+        thread::sleep(Duration::from_millis(70));
+        drop(long_lived);
+        assert_eq!((pool.size(), pool.idle()), (0, 0));
+        assert_eq!(*pool.try_acquire().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_acquire_timeout_waits_for_a_return() {
+        let (pool, _) = counting_pool(1);
+        let held = pool.try_acquire().unwrap();
+        assert!(matches!(
+            pool.acquire_timeout(Duration::from_millis(10)),
+            Err(PoolError::Timeout)
+        ));
+
+        let returner = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(10));
+            drop(held);
+        });
+        let conn = pool.acquire_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(*conn, 0);
+        returner.join().unwrap();
+    }
+
+    #[test]
+    fn test_async_acquire_is_woken_by_a_return() {
+        let (pool, _) = counting_pool(1);
+        let held = pool.try_acquire().unwrap();
+        let returner = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(10));
+            drop(held);
+        });
+        let conn = block_on(pool.acquire()).unwrap();
+        assert_eq!(*conn, 0);
+        returner.join().unwrap();
+
+        // Many tasks on many threads share one connection without losing a wakeup
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let pool = pool.clone();
+                thread::spawn(move || {
+                    for _ in 0..50 {
+                        drop(block_on(pool.acquire()).unwrap());
+                    }
+                })
+            })
+            .collect();
+        drop(conn);
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(pool.size(), 1);
+    }
+
+    #[test]
+    fn test_unhappy_path_factory_errors() {
+        let pool: ResourcePool<u8> = ResourcePool::new(1, || Err("connection refused"));
+        let err = pool.try_acquire().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "failed to create a pooled resource: connection refused"
+        );
+        assert!(err.source().is_some());
+        assert_eq!(pool.size(), 0, "The reserved slot is given back");
+    }
+
+    #[test]
+    fn test_edge_case_detach_frees_the_slot() {
+        let (pool, _) = counting_pool(1);
+        let conn = pool.try_acquire().unwrap();
+        assert_eq!(conn.detach(), 0);
+        assert_eq!(pool.size(), 0);
+        assert_eq!(*pool.try_acquire().unwrap(), 1);
+    }
+}
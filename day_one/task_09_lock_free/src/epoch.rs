@@ -0,0 +1,231 @@
+//! Epoch-based memory reclamation for the lock-free structures.
+//!
+//! A lock-free structure cannot free a node as soon as it unlinks it: another
+//! thread may have loaded a pointer to the node just before and still be
+//! reading it. Threads therefore `pin` a `Collector` for the duration of each
+//! operation, and unlinked nodes are `retire`d instead of freed. The collector
+//! keeps a global epoch that only moves on once no thread is still pinned in
+//! the epoch before it, so anything retired two epochs ago can no longer be
+//! seen by anyone and is dropped.
+//!
+//! Pinned threads are counted per epoch (modulo 3) rather than registered one
+//! by one, so pinning is two atomic operations and the collector needs no
+//! thread-locals. Retiring takes a short lock on the garbage bags, the same
+//! trade-off `AtomicCow` makes for its grace period.
+
+use std::mem;
+
+use sync_shim::atomic::{AtomicUsize, Ordering};
+use sync_shim::{Mutex, MutexGuard};
+
+/// Owns the global epoch and everything retired but not yet freed
+///
+/// Dropping the collector frees whatever is still waiting.
+pub struct Collector {
+    epoch: AtomicUsize,
+    // Threads currently pinned in each epoch, indexed by epoch % 3
+    pinned: [AtomicUsize; 3],
+    // Retired objects by the epoch they were retired in, indexed the same way
+    garbage: Mutex<[Vec<Retired>; 3]>,
+}
+
+impl Collector {
+    /// Creates a collector at epoch 0 with nothing retired
+    pub fn new() -> Self {
+        Collector {
+            epoch: AtomicUsize::new(0),
+            pinned: [
+                AtomicUsize::new(0),
+                AtomicUsize::new(0),
+                AtomicUsize::new(0),
+            ],
+            garbage: Mutex::new([Vec::new(), Vec::new(), Vec::new()]),
+        }
+    }
+
+    /// Pins the current thread; nothing retired from now on is freed until the guard drops
+    pub fn pin(&self) -> Guard<'_> {
+        loop {
+            let epoch = self.epoch.load(Ordering::SeqCst);
+            let slot = epoch % 3;
+            self.pinned[slot].fetch_add(1, Ordering::SeqCst);
+            // If the epoch moved on while we registered, the counter we bumped
+            // may already have been checked (or belong to a later epoch)
+            if self.epoch.load(Ordering::SeqCst) == epoch {
+                return Guard {
+                    collector: self,
+                    slot,
+                };
+            }
+            self.pinned[slot].fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    /// Returns the number of retired objects not yet freed
+    pub fn pending(&self) -> usize {
+        self.lock().iter().map(Vec::len).sum()
+    }
+
+    // Moves to the next epoch if nobody is pinned in the previous one, and
+    // hands back the garbage that has become unreachable
+    fn try_advance(&self, garbage: &mut [Vec<Retired>; 3]) -> Vec<Retired> {
+        let epoch = self.epoch.load(Ordering::SeqCst);
+        // (epoch + 2) % 3 is the slot of epoch - 1
+        let previous = (epoch + 2) % 3;
+        if self.pinned[previous].load(Ordering::SeqCst) != 0 {
+            return Vec::new();
+        }
+        self.epoch.store(epoch + 1, Ordering::SeqCst);
+        // Everyone pinned now is in `epoch` or `epoch + 1`, and whatever was
+        // retired in `epoch - 1` was already unlinked before they pinned
+        mem::take(&mut garbage[previous])
+    }
+
+    // Garbage is only ever moved in and out, so a poisoned lock is still usable
+    fn lock(&self) -> MutexGuard<'_, [Vec<Retired>; 3]> {
+        self.garbage
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for Collector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Collector {
+    fn drop(&mut self) {
+        // `&mut self` means no guards are left
+        for bag in self.lock().iter_mut() {
+            for retired in bag.drain(..) {
+                retired.free();
+            }
+        }
+    }
+}
+
+/// Proof that the current thread is pinned; unpins on drop
+pub struct Guard<'a> {
+    collector: &'a Collector,
+    slot: usize,
+}
+
+impl Guard<'_> {
+    /// Schedules `ptr` to be dropped as a `Box<T>` once no pinned thread can still see it
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must come from `Box::into_raw`, must already be unreachable for
+    /// threads that pin after this call, and must be retired only once.
+    pub unsafe fn retire<T: Send>(&self, ptr: *mut T) {
+        let freed = {
+            let mut garbage = self.collector.lock();
+            let epoch = self.collector.epoch.load(Ordering::SeqCst);
+            garbage[epoch % 3].push(Retired::new(ptr));
+            self.collector.try_advance(&mut garbage)
+        };
+        // Drop outside the lock, since a destructor might retire something too
+        for retired in freed {
+            retired.free();
+        }
+    }
+}
+
+impl Drop for Guard<'_> {
+    fn drop(&mut self) {
+        self.collector.pinned[self.slot].fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+// A type-erased `Box<T>` waiting to be freed
+struct Retired {
+    ptr: *mut (),
+    drop: unsafe fn(*mut ()),
+}
+
+// SAFETY: `retire` only accepts `T: Send`, so the box may be dropped on any thread
+unsafe impl Send for Retired {}
+
+impl Retired {
+    fn new<T>(ptr: *mut T) -> Self {
+        unsafe fn drop_box<T>(ptr: *mut ()) {
+            drop(Box::from_raw(ptr.cast::<T>()));
+        }
+        Retired {
+            ptr: ptr.cast(),
+            drop: drop_box::<T>,
+        }
+    }
+
+    fn free(self) {
+        // SAFETY: `retire`'s contract makes this the one and only drop of a live box
+        unsafe { (self.drop)(self.ptr) }
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_retired_objects_wait_for_pinned_threads() {
+        let collector = Collector::new();
+        let value = Arc::new(());
+        let reader = collector.pin();
+        {
+            let writer = collector.pin();
+            for _ in 0..10 {
+                unsafe { writer.retire(Box::into_raw(Box::new(Arc::clone(&value)))) };
+            }
+        }
+        assert_eq!(
+            Arc::strong_count(&value),
+            11,
+            "An older pin holds everything back"
+        );
+
+        drop(reader);
+        for _ in 0..2 {
+            let guard = collector.pin();
+            unsafe { guard.retire(Box::into_raw(Box::new(Arc::clone(&value)))) };
+        }
+        assert_eq!(
+            Arc::strong_count(&value),
+            2,
+            "Two epochs on, only the latest retirement is left"
+        );
+        drop(collector);
+        assert_eq!(
+            Arc::strong_count(&value),
+            1,
+            "Dropping the collector frees the rest"
+        );
+    }
+
+    #[test]
+    fn test_concurrent_retire_frees_everything_exactly_once() {
+        let collector = Arc::new(Collector::new());
+        let value = Arc::new(());
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let collector = Arc::clone(&collector);
+                let value = Arc::clone(&value);
+                std::thread::spawn(move || {
+                    for _ in 0..1000 {
+                        let guard = collector.pin();
+                        unsafe { guard.retire(Box::into_raw(Box::new(Arc::clone(&value)))) };
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert!(collector.pending() < 4000, "Epochs advanced along the way");
+        drop(collector);
+        assert_eq!(Arc::strong_count(&value), 1);
+    }
+}
@@ -0,0 +1,24 @@
+//! Hooks behind the `tracing` feature. Without it every function is empty and
+//! inlines away, so the CAS loops pay nothing for them.
+
+/// Reports how many compare-exchange attempts failed before `op` succeeded
+#[cfg(not(feature = "safe-only"))]
+#[inline]
+pub(crate) fn cas_retries(op: &'static str, retries: u64) {
+    #[cfg(feature = "tracing")]
+    if retries > 0 {
+        tracing::trace!(op, retries, "lock-free stack CAS contention");
+        metrics::counter!("lock_free_stack.cas_retries", "op" => op).increment(retries);
+    }
+    #[cfg(not(feature = "tracing"))]
+    let _ = (op, retries);
+}
+
+/// Counts a finished push or pop
+#[inline]
+pub(crate) fn operation(op: &'static str) {
+    #[cfg(feature = "tracing")]
+    metrics::counter!("lock_free_stack.operations", "op" => op).increment(1);
+    #[cfg(not(feature = "tracing"))]
+    let _ = op;
+}
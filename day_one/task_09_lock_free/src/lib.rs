@@ -1,17 +1,52 @@
-use std::sync::atomic::{AtomicPtr, Ordering};
+#![cfg_attr(feature = "safe-only", forbid(unsafe_code))]
+
+#[cfg(not(feature = "safe-only"))]
 use std::ptr;
-use std::sync::Arc;
-use std::thread;
+#[cfg(not(feature = "safe-only"))]
+use sync_shim::atomic::{AtomicPtr, Ordering};
+
+#[cfg(not(feature = "safe-only"))]
+pub mod epoch;
+mod instrument;
+#[cfg(not(feature = "safe-only"))]
+mod list;
+// With `safe-only` the atomic stack below, the Harris list and the Chase-Lev deque are
+// swapped for mutex-backed ones
+#[cfg(feature = "safe-only")]
+mod mutex_list;
+#[cfg(feature = "safe-only")]
+mod mutex_stack;
+#[cfg(feature = "safe-only")]
+mod mutex_work_steal;
+mod set;
+#[cfg(not(feature = "safe-only"))]
+pub mod work_steal;
+
+#[cfg(not(feature = "safe-only"))]
+pub use list::LockFreeList;
+#[cfg(feature = "safe-only")]
+pub use mutex_list::LockFreeList;
+#[cfg(feature = "safe-only")]
+pub use mutex_stack::LockFreeStack;
+pub use set::LockFreeSet;
+/// `safe-only` keeps the module path, so callers use `work_steal::Worker` either way
+#[cfg(feature = "safe-only")]
+pub mod work_steal {
+    pub use crate::mutex_work_steal::{Steal, Stealer, Worker};
+}
 
+#[cfg(not(feature = "safe-only"))]
 pub struct LockFreeStack<T> {
     head: AtomicPtr<Node<T>>,
 }
 
+#[cfg(not(feature = "safe-only"))]
 struct Node<T> {
     value: T,
     next: *mut Node<T>, // Pointer to the next node in the stack
 }
 
+#[cfg(not(feature = "safe-only"))]
 impl<T> LockFreeStack<T> {
     // Create a new empty stack
     pub fn new() -> Self {
@@ -27,6 +62,7 @@ impl<T> LockFreeStack<T> {
             next: ptr::null_mut(),
         }));
 
+        let mut retries = 0;
         loop {
             let head = self.head.load(Ordering::Acquire);
             unsafe {
@@ -37,11 +73,15 @@ impl<T> LockFreeStack<T> {
             if self.head.compare_exchange(head, new_node, Ordering::Release, Ordering::Acquire).is_ok() {
                 break;
             }
+            retries += 1;
         }
+        instrument::cas_retries("push", retries);
+        instrument::operation("push");
     }
 
     // Pop an element from the stack
     pub fn pop(&self) -> Option<T> {
+        let mut retries = 0;
         loop {
             let head = self.head.load(Ordering::Acquire);
             if head.is_null() {
@@ -51,13 +91,24 @@ impl<T> LockFreeStack<T> {
             // Attempt to atomically set the head to the next node.
             let next = unsafe { (*head).next };
             if self.head.compare_exchange(head, next, Ordering::Release, Ordering::Acquire).is_ok() {
+                instrument::cas_retries("pop", retries);
+                instrument::operation("pop");
                 let boxed_node = unsafe { Box::from_raw(head) };
                 return Some(boxed_node.value);
             }
+            retries += 1;
         }
     }
 }
 
+#[cfg(not(feature = "safe-only"))]
+impl<T> Default for LockFreeStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(not(feature = "safe-only"))]
 impl<T> Clone for LockFreeStack<T> {
     fn clone(&self) -> Self {
         LockFreeStack {
@@ -66,40 +117,11 @@ impl<T> Clone for LockFreeStack<T> {
     }
 }
 
-fn main() {
-    let stack = Arc::new(LockFreeStack::new());
-    let mut handles = vec![];
-
-    // Spawn threads to push values to the stack
-    for i in 0..10 {
-        let stack = Arc::clone(&stack);
-        let handle = thread::spawn(move || {
-            stack.push(i);
-            println!("Pushed: {}", i);
-        });
-        handles.push(handle);
-    }
-
-    // Wait for all threads to finish pushing
-    for handle in handles {
-        handle.join().unwrap();
-    }
-
-    // Pop elements from the stack
-    let mut values = vec![];
-    while let Some(value) = stack.pop() {
-        values.push(value);
-    }
-
-    // Ensure all values are popped
-    println!("Popped values: {:?}", values);
-    assert_eq!(values.len(), 10);
-}
-
-#[cfg(test)]
+#[cfg(all(test, not(loom)))]
 mod tests {
 
     use super::*;
+    use std::sync::Arc;
 
     #[test]
     fn test_push_and_pop() {
@@ -155,3 +177,48 @@ mod tests {
         assert_eq!(stack.pop(), None);
     }
 }
+
+// Run with `RUSTFLAGS="--cfg loom" cargo test --lib --release -p task_09_lock_free`
+#[cfg(all(test, loom))]
+mod loom_tests {
+    use super::*;
+    use sync_shim::{thread, Arc};
+
+    #[test]
+    fn loom_concurrent_pushes_keep_both_values() {
+        sync_shim::model(|| {
+            let stack = Arc::new(LockFreeStack::new());
+            let other = thread::spawn({
+                let stack = Arc::clone(&stack);
+                move || stack.push(1)
+            });
+            stack.push(2);
+            other.join().unwrap();
+
+            let mut values = vec![stack.pop().unwrap(), stack.pop().unwrap()];
+            values.sort();
+            assert_eq!(values, [1, 2]);
+            assert_eq!(stack.pop(), None);
+        });
+    }
+
+    #[test]
+    fn loom_pop_races_a_push() {
+        sync_shim::model(|| {
+            let stack = Arc::new(LockFreeStack::new());
+            stack.push(1);
+            let pusher = thread::spawn({
+                let stack = Arc::clone(&stack);
+                move || stack.push(2)
+            });
+            // Whichever order the two land in, nothing is lost or popped twice
+            let popped = stack.pop().unwrap();
+            pusher.join().unwrap();
+            let rest = stack.pop().unwrap();
+            let mut values = [popped, rest];
+            values.sort();
+            assert_eq!(values, [1, 2]);
+            assert_eq!(stack.pop(), None);
+        });
+    }
+}
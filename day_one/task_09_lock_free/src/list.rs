@@ -0,0 +1,451 @@
+use std::cmp::Ordering as CmpOrdering;
+use std::fmt;
+use std::ptr;
+
+use sync_shim::atomic::{AtomicPtr, AtomicUsize, Ordering};
+
+use crate::epoch::{Collector, Guard};
+
+/// A sorted, duplicate-free linked list that threads can update concurrently without locks
+///
+/// This is Harris's list: `remove` first marks a node as deleted by setting
+/// the low bit of its `next` pointer, and only then unlinks it with a second
+/// CAS. A marked `next` can no longer be CAS'd, so nothing can be inserted
+/// after a node that is on its way out. Any thread that walks past a marked
+/// node helps unlink it. Unlinked nodes go to the list's epoch `Collector`
+/// and are freed once no thread can still be reading them.
+pub struct LockFreeList<T> {
+    head: AtomicPtr<Node<T>>,
+    len: AtomicUsize,
+    collector: Collector,
+}
+
+struct Node<T> {
+    value: T,
+    // The low bit set means this node is logically deleted
+    next: AtomicPtr<Node<T>>,
+}
+
+// SAFETY: values are moved in from and dropped on any thread (`Send`), and
+// read through shared references from several threads at once (`Sync`)
+unsafe impl<T: Send + Sync> Send for LockFreeList<T> {}
+unsafe impl<T: Send + Sync> Sync for LockFreeList<T> {}
+
+fn is_marked<T>(ptr: *mut Node<T>) -> bool {
+    ptr.addr() & 1 == 1
+}
+
+fn marked<T>(ptr: *mut Node<T>) -> *mut Node<T> {
+    ptr.map_addr(|addr| addr | 1)
+}
+
+fn unmarked<T>(ptr: *mut Node<T>) -> *mut Node<T> {
+    ptr.map_addr(|addr| addr & !1)
+}
+
+impl<T: Ord + Send + Sync> LockFreeList<T> {
+    /// Creates an empty list
+    pub fn new() -> Self {
+        LockFreeList {
+            head: AtomicPtr::new(ptr::null_mut()),
+            len: AtomicUsize::new(0),
+            collector: Collector::new(),
+        }
+    }
+
+    /// Inserts `value` in order; returns `false` if an equal value is already present
+    pub fn insert(&self, value: T) -> bool {
+        let guard = self.collector.pin();
+        // Counted up front so a racing remove can never take `len` below zero
+        self.len.fetch_add(1, Ordering::Relaxed);
+        let node = Box::into_raw(Box::new(Node {
+            value,
+            next: AtomicPtr::new(ptr::null_mut()),
+        }));
+        loop {
+            // SAFETY: `node` is ours until the CAS below publishes it
+            let (prev, curr) = self.find(unsafe { &(*node).value }, &guard);
+            // SAFETY: `curr` is protected by the guard
+            if !curr.is_null() && unsafe { &(*curr).value } == unsafe { &(*node).value } {
+                // SAFETY: never published, so nobody else can see it
+                drop(unsafe { Box::from_raw(node) });
+                self.len.fetch_sub(1, Ordering::Relaxed);
+                return false;
+            }
+            unsafe { (*node).next.store(curr, Ordering::Relaxed) };
+            if prev
+                .compare_exchange(curr, node, Ordering::Release, Ordering::Relaxed)
+                .is_ok()
+            {
+                return true;
+            }
+        }
+    }
+
+    /// Removes the value equal to `value`; returns `false` if there was none
+    pub fn remove(&self, value: &T) -> bool {
+        let guard = self.collector.pin();
+        loop {
+            let (prev, curr) = self.find(value, &guard);
+            // SAFETY: `curr` is protected by the guard
+            if curr.is_null() || unsafe { &(*curr).value } != value {
+                return false;
+            }
+            let next_field = unsafe { &(*curr).next };
+            let next = next_field.load(Ordering::Acquire);
+            if is_marked(next) {
+                // Another remove got there first; `find` will unlink it
+                continue;
+            }
+            // Marking is the linearization point: whoever marks, removes
+            if next_field
+                .compare_exchange(next, marked(next), Ordering::AcqRel, Ordering::Relaxed)
+                .is_err()
+            {
+                continue;
+            }
+            self.len.fetch_sub(1, Ordering::Relaxed);
+            if prev
+                .compare_exchange(curr, next, Ordering::AcqRel, Ordering::Relaxed)
+                .is_ok()
+            {
+                // SAFETY: just unlinked by us, and only the unlinking CAS retires
+                unsafe { guard.retire(curr) };
+            } else {
+                // Something changed around it; a search unlinks it on the way
+                self.find(value, &guard);
+            }
+            return true;
+        }
+    }
+
+    /// Checks if a value equal to `value` is present
+    ///
+    /// Only reads: marked nodes are stepped over rather than unlinked.
+    pub fn contains(&self, value: &T) -> bool {
+        let _guard = self.collector.pin();
+        let mut curr = self.head.load(Ordering::Acquire);
+        while !curr.is_null() {
+            // SAFETY: reached from the head while pinned, so not yet freed
+            let node = unsafe { &*curr };
+            let next = node.next.load(Ordering::Acquire);
+            match node.value.cmp(value) {
+                CmpOrdering::Less => curr = unmarked(next),
+                CmpOrdering::Equal => return !is_marked(next),
+                CmpOrdering::Greater => return false,
+            }
+        }
+        false
+    }
+
+    /// Returns the number of values; exact once concurrent updates have finished
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::Relaxed)
+    }
+
+    /// Checks if the list is empty, with the same caveat as `len`
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Copies the values that were present during the walk, in order
+    ///
+    /// Each value reflects some moment of the walk, but concurrent updates
+    /// mean the whole may never have existed at once.
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        let _guard = self.collector.pin();
+        let mut values = Vec::new();
+        let mut curr = self.head.load(Ordering::Acquire);
+        while !curr.is_null() {
+            // SAFETY: as in `contains`
+            let node = unsafe { &*curr };
+            let next = node.next.load(Ordering::Acquire);
+            if !is_marked(next) {
+                values.push(node.value.clone());
+            }
+            curr = unmarked(next);
+        }
+        values
+    }
+
+    // Returns the link to the first unmarked node not less than `value`, and
+    // that node (or null), unlinking and retiring marked nodes on the way
+    fn find<'g>(
+        &'g self,
+        value: &T,
+        guard: &'g Guard<'_>,
+    ) -> (&'g AtomicPtr<Node<T>>, *mut Node<T>) {
+        'retry: loop {
+            let mut prev = &self.head;
+            let mut curr = prev.load(Ordering::Acquire);
+            while !curr.is_null() {
+                // SAFETY: reached while pinned, so not yet freed
+                let node: &'g Node<T> = unsafe { &*curr };
+                let next = node.next.load(Ordering::Acquire);
+                if is_marked(next) {
+                    let next = unmarked(next);
+                    // Fails if `prev` was itself marked or changed since we read it
+                    if prev
+                        .compare_exchange(curr, next, Ordering::AcqRel, Ordering::Acquire)
+                        .is_err()
+                    {
+                        continue 'retry;
+                    }
+                    // SAFETY: unlinked by our CAS, so nobody else retires it
+                    unsafe { guard.retire(curr) };
+                    curr = next;
+                } else {
+                    if node.value >= *value {
+                        return (prev, curr);
+                    }
+                    prev = &node.next;
+                    curr = next;
+                }
+            }
+            return (prev, curr);
+        }
+    }
+}
+
+impl<T: Ord + Send + Sync> Default for LockFreeList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for LockFreeList<T> {
+    fn drop(&mut self) {
+        // Nodes still linked, marked or not, were never retired; the collector
+        // frees the retired ones when it drops after this
+        let mut curr = self.head.load(Ordering::Relaxed);
+        while !curr.is_null() {
+            // SAFETY: `&mut self` means no other thread is looking
+            let node = unsafe { Box::from_raw(curr) };
+            curr = unmarked(node.next.load(Ordering::Relaxed));
+        }
+    }
+}
+
+impl<T: fmt::Debug + Clone + Ord + Send + Sync> fmt::Debug for LockFreeList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.to_vec()).finish()
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize as StdAtomicUsize;
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+
+    #[test]
+    fn test_happy_path_insert_keeps_order() {
+        let list = LockFreeList::new();
+        for value in [5, 1, 4, 2, 3] {
+            assert!(list.insert(value));
+        }
+        assert_eq!(list.to_vec(), [1, 2, 3, 4, 5]);
+        assert_eq!(list.len(), 5);
+        assert!(list.contains(&3));
+        assert!(!list.contains(&6));
+    }
+
+    #[test]
+    fn test_duplicates_and_missing_values() {
+        let list = LockFreeList::new();
+        assert!(list.insert(1));
+        assert!(!list.insert(1), "Duplicates are rejected");
+        assert!(!list.remove(&2));
+        assert!(list.remove(&1));
+        assert!(!list.remove(&1));
+        assert!(list.is_empty());
+        assert_eq!(format!("{list:?}"), "[]");
+    }
+
+    #[test]
+    fn test_edge_case_remove_head_middle_and_tail() {
+        let list: LockFreeList<i32> = (1..=5).fold(LockFreeList::new(), |list, value| {
+            list.insert(value);
+            list
+        });
+        assert!(list.remove(&1));
+        assert!(list.remove(&3));
+        assert!(list.remove(&5));
+        assert_eq!(list.to_vec(), [2, 4]);
+        assert!(list.insert(3));
+        assert_eq!(format!("{list:?}"), "[2, 3, 4]");
+    }
+
+    // Counts drops so leaks and double frees show up
+    #[derive(Debug)]
+    struct Counted(i32, Arc<StdAtomicUsize>);
+
+    impl PartialEq for Counted {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+    impl Eq for Counted {}
+    impl PartialOrd for Counted {
+        fn partial_cmp(&self, other: &Self) -> Option<CmpOrdering> {
+            Some(self.cmp(other))
+        }
+    }
+    impl Ord for Counted {
+        fn cmp(&self, other: &Self) -> CmpOrdering {
+            self.0.cmp(&other.0)
+        }
+    }
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            self.1.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn test_every_value_is_dropped_exactly_once() {
+        let drops = Arc::new(StdAtomicUsize::new(0));
+        let list = Arc::new(LockFreeList::new());
+        let handles: Vec<_> = (0..4)
+            .map(|t| {
+                let list = Arc::clone(&list);
+                let drops = Arc::clone(&drops);
+                thread::spawn(move || {
+                    for i in 0..500 {
+                        list.insert(Counted(i % 50, Arc::clone(&drops)));
+                        if i % 3 == t % 3 {
+                            list.remove(&Counted(i % 50, Arc::clone(&drops)));
+                        }
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        drop(list);
+        // Every value created was inserted (kept or rejected) or used as a removal key
+        let removal_keys: usize = (0..4)
+            .map(|t| (0..500).filter(|i| i % 3 == t % 3).count())
+            .sum();
+        assert_eq!(drops.load(Ordering::Relaxed), 4 * 500 + removal_keys);
+    }
+
+    #[test]
+    fn test_stress_disjoint_threads_see_their_own_writes() {
+        let list = Arc::new(LockFreeList::new());
+        let handles: Vec<_> = (0..4)
+            .map(|t| {
+                let list = Arc::clone(&list);
+                thread::spawn(move || {
+                    // Thread t owns the values congruent to t mod 4
+                    let mine: Vec<i32> = (0..400).map(|i| i * 4 + t).collect();
+                    for &value in &mine {
+                        assert!(list.insert(value));
+                        assert!(list.contains(&value));
+                    }
+                    for &value in mine.iter().filter(|v| *v % 8 == t) {
+                        assert!(list.remove(&value));
+                        assert!(!list.contains(&value));
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        let expected: Vec<i32> = (0..1600).filter(|v| v % 8 >= 4).collect();
+        assert_eq!(list.to_vec(), expected);
+        assert_eq!(list.len(), expected.len());
+    }
+
+    #[test]
+    fn test_stress_contended_keys_are_linearizable() {
+        // Every thread fights over the same few keys. Per key, successful
+        // inserts and removes must alternate, so their difference is 0 or 1
+        // and matches whether the key is present at the end.
+        const KEYS: usize = 8;
+        let list = Arc::new(LockFreeList::new());
+        let inserted: Arc<Vec<StdAtomicUsize>> =
+            Arc::new((0..KEYS).map(|_| StdAtomicUsize::new(0)).collect());
+        let removed: Arc<Vec<StdAtomicUsize>> =
+            Arc::new((0..KEYS).map(|_| StdAtomicUsize::new(0)).collect());
+        let barrier = Arc::new(Barrier::new(4));
+        let handles: Vec<_> = (0..4)
+            .map(|t| {
+                let (list, inserted, removed, barrier) = (
+                    Arc::clone(&list),
+                    Arc::clone(&inserted),
+                    Arc::clone(&removed),
+                    Arc::clone(&barrier),
+                );
+                thread::spawn(move || {
+                    barrier.wait();
+                    for i in 0..5000 {
+                        let key = (i * 7 + t) % KEYS;
+                        if (i + t) % 2 == 0 {
+                            if list.insert(key) {
+                                inserted[key].fetch_add(1, Ordering::Relaxed);
+                            }
+                        } else if list.remove(&key) {
+                            removed[key].fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        for key in 0..KEYS {
+            let net = inserted[key].load(Ordering::Relaxed) - removed[key].load(Ordering::Relaxed);
+            assert!(net <= 1, "Key {key} was removed more often than inserted");
+            assert_eq!(list.contains(&key), net == 1, "Key {key}");
+        }
+        assert_eq!(list.len(), list.to_vec().len());
+    }
+}
+
+// Run with `RUSTFLAGS="--cfg loom" cargo test --lib --release -p task_09_lock_free`
+#[cfg(all(test, loom))]
+mod loom_tests {
+    use super::*;
+    use sync_shim::{thread, Arc};
+
+    #[test]
+    fn loom_insert_races_removal_of_its_predecessor() {
+        sync_shim::model(|| {
+            let list = Arc::new(LockFreeList::new());
+            list.insert(1);
+            let inserter = thread::spawn({
+                let list = Arc::clone(&list);
+                move || list.insert(2)
+            });
+            // The new node must not end up hanging off the removed one
+            assert!(list.remove(&1));
+            assert!(inserter.join().unwrap());
+            assert_eq!(list.to_vec(), [2]);
+        });
+    }
+
+    #[test]
+    fn loom_concurrent_removes_of_one_value() {
+        sync_shim::model(|| {
+            let list = Arc::new(LockFreeList::new());
+            list.insert(1);
+            list.insert(2);
+            let other = thread::spawn({
+                let list = Arc::clone(&list);
+                move || list.remove(&1)
+            });
+            let mine = list.remove(&1);
+            let theirs = other.join().unwrap();
+            assert!(mine ^ theirs, "Exactly one remove wins");
+            assert_eq!(list.to_vec(), [2]);
+        });
+    }
+}
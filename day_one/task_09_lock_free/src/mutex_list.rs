@@ -0,0 +1,88 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use sync_shim::{Mutex, MutexGuard};
+
+/// Stand-in for the Harris list when unsafe code is ruled out. It keeps the
+/// same API but keeps the values in a `BTreeSet` behind a mutex.
+pub struct LockFreeList<T> {
+    items: Mutex<BTreeSet<T>>,
+}
+
+impl<T: Ord + Send + Sync> LockFreeList<T> {
+    /// Creates an empty list
+    pub fn new() -> Self {
+        LockFreeList {
+            items: Mutex::new(BTreeSet::new()),
+        }
+    }
+
+    /// Inserts `value` in order; returns `false` if an equal value is already present
+    pub fn insert(&self, value: T) -> bool {
+        self.lock().insert(value)
+    }
+
+    /// Removes the value equal to `value`; returns `false` if there was none
+    pub fn remove(&self, value: &T) -> bool {
+        self.lock().remove(value)
+    }
+
+    /// Checks if a value equal to `value` is present
+    pub fn contains(&self, value: &T) -> bool {
+        self.lock().contains(value)
+    }
+
+    /// Returns the number of values
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Checks if the list is empty
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Copies the values out in order
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.lock().iter().cloned().collect()
+    }
+
+    // A panic elsewhere can't leave the set half-updated, so a poisoned lock is still usable
+    fn lock(&self) -> MutexGuard<'_, BTreeSet<T>> {
+        self.items
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl<T: Ord + Send + Sync> Default for LockFreeList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: fmt::Debug + Clone + Ord + Send + Sync> fmt::Debug for LockFreeList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.to_vec()).finish()
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_the_atomic_list_api() {
+        let list = LockFreeList::new();
+        assert!(list.insert(2));
+        assert!(list.insert(1));
+        assert!(!list.insert(2));
+        assert!(list.remove(&1));
+        assert!(list.contains(&2));
+        assert_eq!((list.len(), list.to_vec()), (1, vec![2]));
+        assert_eq!(format!("{list:?}"), "[2]");
+    }
+}
@@ -0,0 +1,79 @@
+use sync_shim::{Mutex, MutexGuard};
+
+use crate::instrument;
+
+/// Stand-in for the atomic stack when unsafe code is ruled out. It keeps the
+/// same API but serialises every operation on a mutex, so there are never any
+/// CAS retries to report.
+pub struct LockFreeStack<T> {
+    items: Mutex<Vec<T>>,
+}
+
+impl<T> LockFreeStack<T> {
+    // Create a new empty stack
+    pub fn new() -> Self {
+        LockFreeStack {
+            items: Mutex::new(Vec::new()),
+        }
+    }
+
+    // Push an element onto the stack
+    pub fn push(&self, value: T) {
+        self.lock().push(value);
+        instrument::operation("push");
+    }
+
+    // Pop an element from the stack
+    pub fn pop(&self) -> Option<T> {
+        let value = self.lock().pop()?;
+        instrument::operation("pop");
+        Some(value)
+    }
+
+    // A panic elsewhere can't leave the Vec half-updated, so a poisoned lock is still usable
+    fn lock(&self) -> MutexGuard<'_, Vec<T>> {
+        self.items
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl<T> Default for LockFreeStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for LockFreeStack<T> {
+    fn clone(&self) -> Self {
+        // Matches the atomic stack, which can't clone its contents either
+        Self::new()
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clone_starts_empty() {
+        let stack = LockFreeStack::new();
+        stack.push(1);
+        let copy = stack.clone();
+        assert_eq!(copy.pop(), None);
+        assert_eq!(stack.pop(), Some(1));
+    }
+
+    #[test]
+    fn test_survives_a_panicking_holder() {
+        let stack = LockFreeStack::new();
+        stack.push(1);
+        let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _guard = stack.lock();
+            panic!("poison the lock");
+        }));
+        stack.push(2);
+        assert_eq!(stack.pop(), Some(2));
+        assert_eq!(stack.pop(), Some(1));
+    }
+}
@@ -0,0 +1,152 @@
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::fmt;
+use std::marker::PhantomData;
+
+use sync_shim::{Arc, Mutex, MutexGuard};
+
+/// What a `Stealer::steal` came back with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Steal<T> {
+    /// The deque was empty
+    Empty,
+    /// Took the oldest item
+    Success(T),
+    /// Lost a race for the oldest item; the mutex-backed deque never returns this
+    Retry,
+}
+
+impl<T> Steal<T> {
+    /// Returns the stolen item, if there was one
+    pub fn success(self) -> Option<T> {
+        match self {
+            Steal::Success(item) => Some(item),
+            Steal::Empty | Steal::Retry => None,
+        }
+    }
+}
+
+/// Stand-in for the Chase-Lev deque when unsafe code is ruled out. It keeps
+/// the same API, but both ends share one mutex-guarded `VecDeque`.
+pub struct Worker<T> {
+    items: Arc<Mutex<VecDeque<T>>>,
+    _not_sync: PhantomData<Cell<()>>,
+}
+
+impl<T: Send> Worker<T> {
+    /// Creates an empty deque, owned by the returned `Worker`
+    pub fn new() -> Self {
+        Worker {
+            items: Arc::new(Mutex::new(VecDeque::new())),
+            _not_sync: PhantomData,
+        }
+    }
+
+    /// Returns a handle other threads can steal through
+    pub fn stealer(&self) -> Stealer<T> {
+        Stealer {
+            items: Arc::clone(&self.items),
+        }
+    }
+
+    /// Adds an item at the bottom
+    pub fn push(&self, item: T) {
+        lock(&self.items).push_back(item);
+    }
+
+    /// Takes the newest item
+    pub fn pop(&self) -> Option<T> {
+        lock(&self.items).pop_back()
+    }
+
+    /// Returns the number of items
+    pub fn len(&self) -> usize {
+        lock(&self.items).len()
+    }
+
+    /// Checks if the deque is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T: Send> Default for Worker<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for Worker<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Worker")
+            .field("len", &lock(&self.items).len())
+            .finish()
+    }
+}
+
+/// A handle for taking the oldest items from another thread's `Worker`
+pub struct Stealer<T> {
+    items: Arc<Mutex<VecDeque<T>>>,
+}
+
+impl<T: Send> Stealer<T> {
+    /// Takes the oldest item
+    pub fn steal(&self) -> Steal<T> {
+        match lock(&self.items).pop_front() {
+            Some(item) => Steal::Success(item),
+            None => Steal::Empty,
+        }
+    }
+
+    /// Returns the number of items
+    pub fn len(&self) -> usize {
+        lock(&self.items).len()
+    }
+
+    /// Checks if the deque is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Clone for Stealer<T> {
+    fn clone(&self) -> Self {
+        Stealer {
+            items: Arc::clone(&self.items),
+        }
+    }
+}
+
+impl<T> fmt::Debug for Stealer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Stealer")
+            .field("len", &lock(&self.items).len())
+            .finish()
+    }
+}
+
+// A panic elsewhere can't leave the VecDeque half-updated, so a poisoned lock is still usable
+fn lock<T>(items: &Mutex<VecDeque<T>>) -> MutexGuard<'_, VecDeque<T>> {
+    items
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_owner_is_lifo_and_thief_is_fifo() {
+        let worker = Worker::new();
+        let stealer = worker.stealer();
+        for i in 1..=3 {
+            worker.push(i);
+        }
+        assert_eq!(worker.pop(), Some(3));
+        assert_eq!(stealer.steal(), Steal::Success(1));
+        assert_eq!(stealer.clone().steal().success(), Some(2));
+        assert_eq!(stealer.steal(), Steal::Empty);
+        assert!(worker.is_empty());
+    }
+}
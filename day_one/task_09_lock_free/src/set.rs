@@ -0,0 +1,129 @@
+use std::fmt;
+
+use crate::LockFreeList;
+
+/// A concurrent ordered set backed by a `LockFreeList`
+///
+/// Every operation is the list's, so inserts, removes and lookups walk and
+/// link nodes without taking a lock. Retiring a removed node is not lock-free:
+/// `epoch::Guard::retire` briefly locks the collector's garbage `Mutex`.
+/// Lookups walk the list, which makes them O(n): this suits sets of up to a
+/// few hundred values that many threads update at once. Under `safe-only` the
+/// list is the `Mutex<BTreeSet<T>>` fallback, so every operation takes a lock.
+pub struct LockFreeSet<T> {
+    list: LockFreeList<T>,
+}
+
+impl<T: Ord + Send + Sync> LockFreeSet<T> {
+    /// Creates an empty set
+    pub fn new() -> Self {
+        LockFreeSet {
+            list: LockFreeList::new(),
+        }
+    }
+
+    /// Adds `value`; returns `false` if it was already present
+    pub fn insert(&self, value: T) -> bool {
+        self.list.insert(value)
+    }
+
+    /// Removes `value`; returns `false` if it was not present
+    pub fn remove(&self, value: &T) -> bool {
+        self.list.remove(value)
+    }
+
+    /// Checks if `value` is present
+    pub fn contains(&self, value: &T) -> bool {
+        self.list.contains(value)
+    }
+
+    /// Returns the number of values; exact once concurrent updates have finished
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    /// Checks if the set is empty
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Copies the values out in ascending order
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.list.to_vec()
+    }
+}
+
+impl<T: Ord + Send + Sync> Default for LockFreeSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Send + Sync> FromIterator<T> for LockFreeSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let set = LockFreeSet::new();
+        for value in iter {
+            set.insert(value);
+        }
+        set
+    }
+}
+
+impl<T: Ord + Send + Sync> Extend<T> for LockFreeSet<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<T: fmt::Debug + Clone + Ord + Send + Sync> fmt::Debug for LockFreeSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.to_vec()).finish()
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_happy_path_set_operations() {
+        let mut set: LockFreeSet<&str> = ["pear", "apple"].into_iter().collect();
+        set.extend(["fig", "apple"]);
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(&"fig"));
+        assert!(set.remove(&"pear"));
+        assert!(!set.contains(&"pear"));
+        assert_eq!(format!("{set:?}"), r#"{"apple", "fig"}"#);
+    }
+
+    #[test]
+    fn test_edge_case_empty_set() {
+        let set: LockFreeSet<u8> = LockFreeSet::default();
+        assert!(set.is_empty());
+        assert!(!set.remove(&0));
+        assert_eq!(set.to_vec(), []);
+    }
+
+    #[test]
+    fn test_threads_inserting_overlapping_ranges_keep_one_copy() {
+        let set = Arc::new(LockFreeSet::new());
+        let wins: usize = (0..4)
+            .map(|t| {
+                let set = Arc::clone(&set);
+                thread::spawn(move || (t * 50..t * 50 + 200).filter(|&v| set.insert(v)).count())
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .sum();
+        assert_eq!(wins, 350, "Each value is inserted by exactly one thread");
+        assert_eq!(set.to_vec(), (0..350).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,471 @@
+//! Work-stealing deque for task schedulers.
+//!
+//! Each worker thread owns a `Worker` and pushes the tasks it spawns onto it,
+//! popping the newest one back while it is still hot in cache. Idle threads
+//! hold `Stealer`s for the other workers' deques and take the oldest tasks,
+//! which tend to be the biggest pieces of work left.
+
+use std::cell::Cell;
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+use std::ptr;
+
+use sync_shim::atomic::{fence, AtomicIsize, AtomicPtr, Ordering};
+use sync_shim::cell::UnsafeCell;
+use sync_shim::Arc;
+
+use crate::epoch::Collector;
+
+/// Slots in a new deque's buffer; it doubles whenever the owner fills it
+const MIN_CAPACITY: usize = 16;
+
+/// What a `Stealer::steal` came back with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Steal<T> {
+    /// The deque was empty
+    Empty,
+    /// Took the oldest item
+    Success(T),
+    /// Lost a race for the oldest item to the owner or another stealer; try again
+    Retry,
+}
+
+impl<T> Steal<T> {
+    /// Returns the stolen item, if there was one
+    pub fn success(self) -> Option<T> {
+        match self {
+            Steal::Success(item) => Some(item),
+            Steal::Empty | Steal::Retry => None,
+        }
+    }
+}
+
+/// A fixed-size ring of slots, indexed by the deque's ever-growing positions
+struct Buffer<T> {
+    slots: Box<[UnsafeCell<MaybeUninit<T>>]>,
+}
+
+impl<T> Buffer<T> {
+    fn alloc(capacity: usize) -> *mut Self {
+        debug_assert!(capacity.is_power_of_two());
+        let slots = (0..capacity)
+            .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+            .collect();
+        Box::into_raw(Box::new(Buffer { slots }))
+    }
+
+    fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    fn slot(&self, position: isize) -> &UnsafeCell<MaybeUninit<T>> {
+        // The capacity is a power of two, so masking wraps the position around
+        &self.slots[position as usize & (self.capacity() - 1)]
+    }
+
+    /// # Safety
+    ///
+    /// No other thread may be reading the slot; the item is not dropped if the slot was full.
+    unsafe fn write(&self, position: isize, item: MaybeUninit<T>) {
+        self.slot(position).with_mut(|slot| ptr::write(slot, item));
+    }
+
+    /// Copies the slot out bitwise; only whoever wins the slot may `assume_init` the copy
+    ///
+    /// A stealer reads before its CAS on `top`, and by then the owner may have
+    /// wrapped round the buffer and be writing this very slot. The read is
+    /// volatile, as in crossbeam-deque, so the compiler neither assumes the
+    /// slot is unchanged nor folds the racy copy into anything else; a torn
+    /// copy is harmless because a stealer that sees one loses the CAS and
+    /// discards it.
+    ///
+    /// # Safety
+    ///
+    /// The copy must not be used unless the caller then wins the slot.
+    unsafe fn read(&self, position: isize) -> MaybeUninit<T> {
+        self.slot(position).with(|slot| ptr::read_volatile(slot))
+    }
+}
+
+struct Inner<T> {
+    /// Position of the oldest item; stealers advance it with a CAS
+    top: AtomicIsize,
+    /// One past the newest item; only the owner moves it
+    bottom: AtomicIsize,
+    buffer: AtomicPtr<Buffer<T>>,
+    /// Frees outgrown buffers once no stealer can still be reading them
+    collector: Collector,
+}
+
+// SAFETY: items are moved in on the owner's thread and out on any thread, but
+// never shared, so `T: Send` is enough for both
+unsafe impl<T: Send> Send for Inner<T> {}
+unsafe impl<T: Send> Sync for Inner<T> {}
+
+impl<T> Inner<T> {
+    fn len(&self) -> usize {
+        let bottom = self.bottom.load(Ordering::Relaxed);
+        let top = self.top.load(Ordering::Relaxed);
+        bottom.saturating_sub(top).max(0) as usize
+    }
+}
+
+impl<T> Drop for Inner<T> {
+    fn drop(&mut self) {
+        // `&mut self` means every `Worker` and `Stealer` is gone
+        let top = self.top.load(Ordering::Relaxed);
+        let bottom = self.bottom.load(Ordering::Relaxed);
+        let buffer = self.buffer.load(Ordering::Relaxed);
+        // SAFETY: positions top..bottom hold the items nobody took, and the
+        // current buffer was never retired, so it is freed exactly once here
+        unsafe {
+            for position in top..bottom {
+                (*buffer).read(position).assume_init_drop();
+            }
+            drop(Box::from_raw(buffer));
+        }
+    }
+}
+
+/// The owner's end of a Chase-Lev work-stealing deque
+///
+/// The owner pushes and pops at the bottom without taking a lock: the only
+/// contended operation is a CAS on `top` when it takes the very last item.
+/// `Stealer`s take the oldest item from the top with a CAS, so a thief and
+/// the owner only ever race for a single item. When the buffer fills up the
+/// owner copies the items into one twice the size; stealers may still be
+/// reading the old one, so it is retired to an epoch `Collector` rather than
+/// freed straight away.
+///
+/// A `Worker` can be sent to another thread but not shared, since the bottom
+/// end is only safe for one thread at a time.
+pub struct Worker<T> {
+    inner: Arc<Inner<T>>,
+    _not_sync: PhantomData<Cell<()>>,
+}
+
+impl<T: Send> Worker<T> {
+    /// Creates an empty deque, owned by the returned `Worker`
+    pub fn new() -> Self {
+        Worker {
+            inner: Arc::new(Inner {
+                top: AtomicIsize::new(0),
+                bottom: AtomicIsize::new(0),
+                buffer: AtomicPtr::new(Buffer::alloc(MIN_CAPACITY)),
+                collector: Collector::new(),
+            }),
+            _not_sync: PhantomData,
+        }
+    }
+
+    /// Returns a handle other threads can steal through
+    pub fn stealer(&self) -> Stealer<T> {
+        Stealer {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+
+    /// Adds an item at the bottom
+    pub fn push(&self, item: T) {
+        let inner = &*self.inner;
+        let bottom = inner.bottom.load(Ordering::Relaxed);
+        let top = inner.top.load(Ordering::Acquire);
+        let mut buffer = inner.buffer.load(Ordering::Relaxed);
+        // SAFETY: only the owner replaces the buffer, so the current one is live
+        if bottom - top >= unsafe { (*buffer).capacity() } as isize {
+            buffer = self.grow(top, bottom, buffer);
+        }
+        // SAFETY: the slot at `bottom` is outside top..bottom, so no stealer
+        // wins it, and the Release store publishes the item with the new bottom
+        unsafe { (*buffer).write(bottom, MaybeUninit::new(item)) };
+        inner.bottom.store(bottom + 1, Ordering::Release);
+    }
+
+    /// Takes the newest item
+    pub fn pop(&self) -> Option<T> {
+        let inner = &*self.inner;
+        let bottom = inner.bottom.load(Ordering::Relaxed) - 1;
+        let buffer = inner.buffer.load(Ordering::Relaxed);
+        // Claim the slot before looking at `top`; the fence pairs with the one
+        // in `steal`, so at least one side sees the other's claim. Every store
+        // to `bottom` is Release, since a stealer that reads any of them must
+        // also see the slots pushed below it
+        inner.bottom.store(bottom, Ordering::Release);
+        fence(Ordering::SeqCst);
+        let top = inner.top.load(Ordering::Relaxed);
+
+        if top > bottom {
+            // Empty: undo the claim
+            inner.bottom.store(bottom + 1, Ordering::Release);
+            return None;
+        }
+        // SAFETY: only the owner writes slots, and the buffer is current
+        let item = unsafe { (*buffer).read(bottom) };
+        if top == bottom {
+            // The last item, which a stealer may be after too: whoever moves `top` wins it
+            let won = inner
+                .top
+                .compare_exchange(top, top + 1, Ordering::SeqCst, Ordering::Relaxed)
+                .is_ok();
+            inner.bottom.store(bottom + 1, Ordering::Release);
+            if !won {
+                // The stealer owns it; our copy is never used
+                return None;
+            }
+        }
+        // SAFETY: the slot was published by `push` and this thread won it
+        Some(unsafe { item.assume_init() })
+    }
+
+    /// Returns the number of items; a concurrent steal can make it stale at once
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Checks if the deque is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // Moves top..bottom into a buffer twice the size and retires the old one
+    fn grow(&self, top: isize, bottom: isize, old: *mut Buffer<T>) -> *mut Buffer<T> {
+        let inner = &*self.inner;
+        // SAFETY: `old` is the live current buffer; the copies are bitwise, so
+        // each item still has exactly one owner, decided by the CAS on `top`
+        unsafe {
+            let new = Buffer::alloc((*old).capacity() * 2);
+            for position in top..bottom {
+                (*new).write(position, (*old).read(position));
+            }
+            inner.buffer.store(new, Ordering::Release);
+            // Stealers pin before loading the buffer, so any still reading
+            // `old` hold the collector back until they are done
+            inner.collector.pin().retire(old);
+            new
+        }
+    }
+}
+
+impl<T: Send> Default for Worker<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for Worker<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Worker")
+            .field("len", &self.inner.len())
+            .finish()
+    }
+}
+
+/// A handle for taking the oldest items from another thread's `Worker`
+pub struct Stealer<T> {
+    inner: Arc<Inner<T>>,
+}
+
+impl<T: Send> Stealer<T> {
+    /// Tries to take the oldest item
+    pub fn steal(&self) -> Steal<T> {
+        let inner = &*self.inner;
+        let _guard = inner.collector.pin();
+        let top = inner.top.load(Ordering::Acquire);
+        fence(Ordering::SeqCst);
+        let bottom = inner.bottom.load(Ordering::Acquire);
+        if top >= bottom {
+            return Steal::Empty;
+        }
+        let buffer = inner.buffer.load(Ordering::Acquire);
+        // SAFETY: pinned, so the buffer is not freed under us. The copy is
+        // speculative: it only becomes ours if the CAS below succeeds
+        let item = unsafe { (*buffer).read(top) };
+        if inner
+            .top
+            .compare_exchange(top, top + 1, Ordering::SeqCst, Ordering::Relaxed)
+            .is_err()
+        {
+            return Steal::Retry;
+        }
+        // SAFETY: the CAS made this thread the item's only owner
+        Steal::Success(unsafe { item.assume_init() })
+    }
+
+    /// Returns the number of items; it can be stale by the time it is read
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Checks if the deque is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Clone for Stealer<T> {
+    fn clone(&self) -> Self {
+        Stealer {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<T> fmt::Debug for Stealer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Stealer")
+            .field("len", &self.inner.len())
+            .finish()
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::atomic::AtomicUsize;
+    use std::thread;
+
+    #[test]
+    fn test_owner_is_lifo_and_thief_is_fifo() {
+        let worker = Worker::new();
+        let stealer = worker.stealer();
+        for i in 1..=4 {
+            worker.push(i);
+        }
+        assert_eq!(worker.pop(), Some(4));
+        assert_eq!(stealer.steal(), Steal::Success(1));
+        assert_eq!(stealer.len(), 2);
+        assert_eq!(stealer.steal().success(), Some(2));
+        assert_eq!(worker.pop(), Some(3));
+        assert!(worker.is_empty());
+        assert_eq!(worker.pop(), None);
+        assert_eq!(stealer.steal(), Steal::Empty);
+    }
+
+    #[test]
+    fn test_growing_keeps_every_item() {
+        let worker = Worker::new();
+        let stealer = worker.stealer();
+        for i in 0..100 {
+            worker.push(i);
+        }
+        assert_eq!(stealer.steal(), Steal::Success(0));
+        for i in 100..1000 {
+            worker.push(i); // Wraps around the buffer and doubles it again
+        }
+        assert_eq!(worker.len(), 999);
+        let popped: Vec<_> = std::iter::from_fn(|| worker.pop()).collect();
+        assert_eq!(popped, (1..1000).rev().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_concurrent_stealers_take_every_item_once() {
+        // The owner keeps pushing and popping, growing the buffer, while three threads steal
+        let worker = Worker::new();
+        let done = std::sync::atomic::AtomicBool::new(false);
+        let (popped, stolen) = thread::scope(|scope| {
+            let thieves: Vec<_> = (0..3)
+                .map(|_| {
+                    let stealer = worker.stealer();
+                    let done = &done;
+                    scope.spawn(move || {
+                        let mut stolen = Vec::new();
+                        while !done.load(Ordering::Acquire) || !stealer.is_empty() {
+                            if let Steal::Success(item) = stealer.steal() {
+                                stolen.push(item);
+                            }
+                        }
+                        stolen
+                    })
+                })
+                .collect();
+            let mut popped = Vec::new();
+            for i in 0..20_000 {
+                worker.push(i);
+                if i % 3 == 0 {
+                    popped.extend(worker.pop());
+                }
+            }
+            popped.extend(std::iter::from_fn(|| worker.pop()));
+            done.store(true, Ordering::Release);
+            let stolen: Vec<_> = thieves
+                .into_iter()
+                .flat_map(|t| t.join().unwrap())
+                .collect();
+            (popped, stolen)
+        });
+
+        let mut seen = HashSet::new();
+        for item in popped.iter().chain(&stolen) {
+            assert!(seen.insert(*item), "{item} was taken twice");
+        }
+        assert_eq!(seen.len(), 20_000);
+    }
+
+    #[test]
+    fn test_edge_case_dropping_with_items_left() {
+        struct Counted<'a>(&'a AtomicUsize);
+        impl Drop for Counted<'_> {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let drops = AtomicUsize::new(0);
+        let worker = Worker::new();
+        let stealer = worker.stealer();
+        for _ in 0..40 {
+            worker.push(Counted(&drops));
+        }
+        drop(stealer.steal());
+        drop(worker.pop());
+        assert_eq!(drops.load(Ordering::Relaxed), 2);
+        drop(worker);
+        assert_eq!(
+            drops.load(Ordering::Relaxed),
+            2,
+            "The stealer keeps the deque alive"
+        );
+        drop(stealer);
+        assert_eq!(drops.load(Ordering::Relaxed), 40);
+    }
+}
+
+// Run with `RUSTFLAGS="--cfg loom" cargo test --lib --release -p task_09_lock_free`
+#[cfg(all(test, loom))]
+mod loom_tests {
+    use super::*;
+    use sync_shim::thread;
+
+    #[test]
+    fn loom_pop_and_steal_race_for_the_last_item() {
+        // Exactly one of the owner and the thief may get the single item
+        sync_shim::model(|| {
+            let worker = Worker::new();
+            worker.push(1);
+            let stealer = worker.stealer();
+            let thief = thread::spawn(move || stealer.steal().success());
+            let popped = worker.pop();
+            let stolen = thief.join().unwrap();
+            assert_eq!(
+                popped.into_iter().chain(stolen).collect::<Vec<_>>(),
+                vec![1]
+            );
+        });
+    }
+
+    #[test]
+    fn loom_steal_sees_a_concurrent_push() {
+        // A push from the owner is either stolen whole or left for the owner
+        sync_shim::model(|| {
+            let worker = Worker::new();
+            let stealer = worker.stealer();
+            let thief = thread::spawn(move || stealer.steal().success());
+            worker.push(String::from("job"));
+            let stolen = thief.join().unwrap();
+            let popped = worker.pop();
+            assert_eq!(stolen.or(popped).as_deref(), Some("job"));
+        });
+    }
+}
@@ -0,0 +1,96 @@
+//! Decodes fuzzer input into arena operations and checks every allocation stays intact
+
+use crate::{ArenaHandle, MemoryArena};
+
+/// Runs the operations encoded in `data`, panicking if the arena misbehaves
+///
+/// The first byte sizes the arena (up to 4 KiB). Each following byte either
+/// resets the arena or allocates the size given by the next byte. Every
+/// allocation is filled with its own marker through its handle, and all live
+/// ones are checked after each step, so overlapping chunks show up as
+/// corrupted markers and out-of-bounds ones as failed slice indexing. Handles
+/// from before a reset must stop resolving.
+pub fn fuzz_ops(data: &[u8]) {
+    let mut bytes = data.iter().copied();
+    let capacity = match bytes.next() {
+        Some(byte) => byte as usize * 16,
+        None => return,
+    };
+    let mut arena = MemoryArena::new(capacity);
+    // (handle, marker) for every chunk since the last reset
+    let mut live: Vec<(ArenaHandle, u8)> = Vec::new();
+    let mut expired: Option<ArenaHandle> = None;
+
+    while let Some(op) = bytes.next() {
+        if op % 8 == 0 {
+            arena.reset();
+            expired = live.last().map(|&(handle, _)| handle).or(expired);
+            live.clear();
+        } else {
+            let size = bytes.next().unwrap_or(op) as usize;
+            let used: usize = live.iter().map(|(handle, _)| handle.len()).sum();
+            match arena.allocate_handle(size) {
+                Some(handle) => {
+                    assert!(size > 0 && used + size <= capacity, "allocation must fit");
+                    assert_eq!(handle.len(), size, "handle must cover the request");
+                    let marker = live.len() as u8;
+                    arena.get_mut(handle).expect("fresh handle").fill(marker);
+                    live.push((handle, marker));
+                }
+                None => assert!(
+                    size == 0 || used + size > capacity,
+                    "allocation must succeed"
+                ),
+            }
+        }
+
+        let used: usize = live.iter().map(|(handle, _)| handle.len()).sum();
+        assert_eq!(arena.remaining(), capacity - used, "remaining");
+        assert_eq!(arena.used(), used, "used");
+        assert_eq!(arena.live_allocations(), live.len(), "live allocations");
+        for &(handle, marker) in &live {
+            let chunk = arena.get(handle).expect("handle outlived its chunk");
+            assert!(
+                chunk.iter().all(|&b| b == marker),
+                "a chunk was overwritten"
+            );
+        }
+        if let Some(handle) = expired {
+            assert!(arena.get(handle).is_none(), "a reset handle still resolves");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fill_reset_refill() {
+        // 64-byte arena: 40 + 24 bytes fill it, 1 more fails, reset, 64 fits again
+        fuzz_ops(&[4, 1, 40, 1, 24, 1, 1, 0, 1, 64]);
+    }
+
+    #[test]
+    fn test_edge_case_empty_arena_and_zero_sizes() {
+        fuzz_ops(&[]);
+        fuzz_ops(&[0, 1, 1, 0]); // Zero-byte arena
+        fuzz_ops(&[1, 1, 0, 1]); // Zero-sized allocation, then a truncated one
+    }
+
+    #[test]
+    fn test_pseudo_random_inputs() {
+        let mut state = 0x1234_5678_u32;
+        for len in 0..300 {
+            let data: Vec<u8> = (0..len)
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 17;
+                    state ^= state << 5;
+                    state as u8
+                })
+                .collect();
+            fuzz_ops(&data);
+        }
+    }
+}
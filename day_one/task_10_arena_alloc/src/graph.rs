@@ -0,0 +1,475 @@
+//! A directed graph whose adjacency lists live in a `MemoryArena`.
+//!
+//! Nodes are named by `NodeId` indices instead of `Rc` pointers, and the
+//! edges are laid out in compressed sparse row form: one array of offsets
+//! (where each node's edges start) and one array of edge targets, both
+//! stored as little-endian `u32`s in a single arena. A pointer-based graph
+//! makes an allocation per node and per edge list; this one makes a handful
+//! in total, however many nodes it has.
+
+use std::fmt;
+
+use crate::{ArenaHandle, MemoryArena};
+
+/// Index of a node in an `ArenaGraph`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(u32);
+
+impl NodeId {
+    /// Returns the node's position, counting from zero in insertion order
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl fmt::Display for NodeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "n{}", self.0)
+    }
+}
+
+/// Collects nodes and edges for a bulk `ArenaGraph` build
+pub struct GraphBuilder<T> {
+    values: Vec<T>,
+    edges: Vec<(u32, u32)>,
+}
+
+impl<T> GraphBuilder<T> {
+    /// Creates an empty builder
+    pub fn new() -> Self {
+        GraphBuilder {
+            values: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    /// Adds a node carrying `value`
+    pub fn add_node(&mut self, value: T) -> NodeId {
+        let id = u32::try_from(self.values.len()).expect("graph is out of node ids");
+        self.values.push(value);
+        NodeId(id)
+    }
+
+    /// Adds an edge from `from` to `to`
+    ///
+    /// # Panics
+    ///
+    /// Panics if either node has not been added.
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId) {
+        let count = self.values.len();
+        assert!(
+            from.index() < count && to.index() < count,
+            "edge {from} -> {to} refers to a missing node"
+        );
+        self.edges.push((from.0, to.0));
+    }
+
+    /// Adds edges both ways between `a` and `b`
+    pub fn add_undirected_edge(&mut self, a: NodeId, b: NodeId) {
+        self.add_edge(a, b);
+        self.add_edge(b, a);
+    }
+
+    /// Lays the adjacency lists out in one arena; each node's edges keep the order they were added in
+    pub fn build(self) -> ArenaGraph<T> {
+        let nodes = self.values.len();
+        // Count each node's out-degree, then turn the counts into start offsets
+        let mut offsets = vec![0u32; nodes + 1];
+        for &(from, _) in &self.edges {
+            offsets[from as usize + 1] += 1;
+        }
+        for i in 0..nodes {
+            offsets[i + 1] += offsets[i];
+        }
+
+        let mut arena = MemoryArena::new(4 * (nodes + 1 + self.edges.len()));
+        let offsets_handle = arena
+            .allocate_handle(4 * (nodes + 1))
+            .expect("arena is sized for the offsets");
+        write_u32s(arena.get_mut(offsets_handle).unwrap(), &offsets);
+
+        let targets_handle = (!self.edges.is_empty()).then(|| {
+            let handle = arena
+                .allocate_handle(4 * self.edges.len())
+                .expect("arena is sized for the edges");
+            let targets = arena.get_mut(handle).unwrap();
+            // Reuse the offsets as per-node write cursors
+            let mut cursor = offsets;
+            for &(from, to) in &self.edges {
+                let slot = cursor[from as usize] as usize;
+                targets[4 * slot..4 * slot + 4].copy_from_slice(&to.to_le_bytes());
+                cursor[from as usize] += 1;
+            }
+            handle
+        });
+
+        ArenaGraph {
+            arena,
+            values: self.values,
+            offsets: offsets_handle,
+            targets: targets_handle,
+            edge_count: self.edges.len(),
+        }
+    }
+}
+
+impl<T> Default for GraphBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Immutable directed graph with arena-allocated adjacency lists
+///
+/// Build one with `GraphBuilder` or `from_edges`. Node values stay in a
+/// `Vec<T>`, since the arena hands out bytes rather than typed slots; only
+/// the graph's structure is in the arena.
+pub struct ArenaGraph<T> {
+    arena: MemoryArena,
+    values: Vec<T>,
+    offsets: ArenaHandle,
+    // `None` when there are no edges: the arena refuses empty allocations
+    targets: Option<ArenaHandle>,
+    edge_count: usize,
+}
+
+impl<T> ArenaGraph<T> {
+    /// Starts a `GraphBuilder`
+    pub fn builder() -> GraphBuilder<T> {
+        GraphBuilder::new()
+    }
+
+    /// Builds a graph from node values and `(from, to)` index pairs
+    ///
+    /// # Panics
+    ///
+    /// Panics if an edge refers to an index past the end of `values`.
+    pub fn from_edges(values: Vec<T>, edges: impl IntoIterator<Item = (usize, usize)>) -> Self {
+        let mut builder = GraphBuilder::new();
+        let ids: Vec<NodeId> = values.into_iter().map(|v| builder.add_node(v)).collect();
+        for (from, to) in edges {
+            let (Some(&from), Some(&to)) = (ids.get(from), ids.get(to)) else {
+                panic!("edge {from} -> {to} refers to a missing node");
+            };
+            builder.add_edge(from, to);
+        }
+        builder.build()
+    }
+
+    /// Returns the number of nodes
+    pub fn node_count(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns the number of edges
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    /// Returns the node at `index`, if there is one
+    pub fn node(&self, index: usize) -> Option<NodeId> {
+        (index < self.values.len()).then_some(NodeId(index as u32))
+    }
+
+    /// Iterates over every node id, in insertion order
+    pub fn nodes(&self) -> impl Iterator<Item = NodeId> {
+        (0..self.values.len() as u32).map(NodeId)
+    }
+
+    /// Returns the value of `node`
+    pub fn value(&self, node: NodeId) -> &T {
+        &self.values[node.index()]
+    }
+
+    /// Returns the value of `node` mutably; the graph's shape cannot change
+    pub fn value_mut(&mut self, node: NodeId) -> &mut T {
+        &mut self.values[node.index()]
+    }
+
+    /// Iterates over the targets of `node`'s outgoing edges
+    pub fn neighbors(&self, node: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        let (offsets, targets) = self.layout();
+        let start = read_u32(offsets, node.index()) as usize;
+        let end = read_u32(offsets, node.index() + 1) as usize;
+        targets[4 * start..4 * end]
+            .chunks_exact(4)
+            .map(|bytes| NodeId(u32::from_le_bytes(bytes.try_into().unwrap())))
+    }
+
+    /// Returns the number of outgoing edges of `node`
+    pub fn degree(&self, node: NodeId) -> usize {
+        let (offsets, _) = self.layout();
+        (read_u32(offsets, node.index() + 1) - read_u32(offsets, node.index())) as usize
+    }
+
+    /// Returns the bytes of arena the adjacency lists take
+    pub fn arena_bytes(&self) -> usize {
+        self.arena.used()
+    }
+
+    /// Calls `visit` on every node reachable from `start` in breadth-first order
+    ///
+    /// The visited set and queue are carved out of `scratch`, so repeated
+    /// traversals with the same scratch make no allocations.
+    pub fn bfs(&self, start: NodeId, scratch: &mut Scratch, mut visit: impl FnMut(NodeId)) {
+        let (offsets, targets) = self.layout();
+        let nodes = self.node_count();
+        assert!(
+            start.index() < nodes,
+            "start node {start} is not in the graph"
+        );
+        let (visited, queue) = scratch.buffers(nodes, 4);
+
+        visited[start.index()] = 1;
+        write_u32(queue, 0, start.0);
+        let (mut head, mut tail) = (0, 1);
+        while head < tail {
+            let node = read_u32(queue, head) as usize;
+            head += 1;
+            visit(NodeId(node as u32));
+            let (start, end) = (read_u32(offsets, node), read_u32(offsets, node + 1));
+            for edge in start as usize..end as usize {
+                let next = read_u32(targets, edge);
+                if visited[next as usize] == 0 {
+                    visited[next as usize] = 1;
+                    write_u32(queue, tail, next);
+                    tail += 1;
+                }
+            }
+        }
+    }
+
+    /// Calls `visit` on every node reachable from `start` in depth-first preorder
+    ///
+    /// Neighbors are explored in the order their edges were added. Uses an
+    /// explicit stack in `scratch` rather than recursion, so deep graphs
+    /// cannot overflow the call stack.
+    pub fn dfs(&self, start: NodeId, scratch: &mut Scratch, mut visit: impl FnMut(NodeId)) {
+        let (offsets, targets) = self.layout();
+        let nodes = self.node_count();
+        assert!(
+            start.index() < nodes,
+            "start node {start} is not in the graph"
+        );
+        // Each stack frame is a node and the index of the next edge to follow
+        let (visited, stack) = scratch.buffers(nodes, 8);
+
+        visited[start.index()] = 1;
+        visit(start);
+        write_u32(stack, 0, start.0);
+        write_u32(stack, 1, read_u32(offsets, start.index()));
+        let mut depth = 1;
+        while depth > 0 {
+            let frame = 2 * (depth - 1);
+            let node = read_u32(stack, frame) as usize;
+            let edge = read_u32(stack, frame + 1);
+            if edge == read_u32(offsets, node + 1) {
+                depth -= 1;
+                continue;
+            }
+            write_u32(stack, frame + 1, edge + 1);
+            let next = read_u32(targets, edge as usize);
+            if visited[next as usize] == 0 {
+                visited[next as usize] = 1;
+                visit(NodeId(next));
+                write_u32(stack, 2 * depth, next);
+                write_u32(stack, 2 * depth + 1, read_u32(offsets, next as usize));
+                depth += 1;
+            }
+        }
+    }
+
+    // Borrows the offsets and edge targets as byte slices
+    fn layout(&self) -> (&[u8], &[u8]) {
+        let offsets = self
+            .arena
+            .get(self.offsets)
+            .expect("graph arena is never reset");
+        let targets = self.targets.map_or(&[][..], |handle| {
+            self.arena.get(handle).expect("graph arena is never reset")
+        });
+        (offsets, targets)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ArenaGraph<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut map = f.debug_map();
+        for node in self.nodes() {
+            let neighbors: Vec<usize> = self.neighbors(node).map(NodeId::index).collect();
+            map.entry(&(node.index(), self.value(node)), &neighbors);
+        }
+        map.finish()
+    }
+}
+
+/// Reusable working memory for `ArenaGraph::bfs` and `dfs`
+///
+/// Holds an arena that is reset at the start of every traversal and only
+/// replaced when a bigger graph needs more room.
+pub struct Scratch {
+    arena: MemoryArena,
+}
+
+impl Scratch {
+    /// Creates scratch space; it grows on first use
+    pub fn new() -> Self {
+        Scratch {
+            arena: MemoryArena::new(0),
+        }
+    }
+
+    /// Returns the bytes of working memory currently reserved
+    pub fn capacity(&self) -> usize {
+        self.arena.used() + self.arena.remaining()
+    }
+
+    // Hands out a zeroed visited byte per node and `width` more bytes per node
+    fn buffers(&mut self, nodes: usize, width: usize) -> (&mut [u8], &mut [u8]) {
+        let needed = nodes * (1 + width);
+        self.arena.reset();
+        if self.arena.remaining() < needed {
+            self.arena = MemoryArena::new(needed);
+        }
+        let handle = self
+            .arena
+            .allocate_handle(needed)
+            .expect("scratch arena was sized for this graph");
+        let bytes = self.arena.get_mut(handle).unwrap();
+        let (visited, rest) = bytes.split_at_mut(nodes);
+        visited.fill(0);
+        (visited, rest)
+    }
+}
+
+impl Default for Scratch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn read_u32(bytes: &[u8], index: usize) -> u32 {
+    u32::from_le_bytes(bytes[4 * index..4 * index + 4].try_into().unwrap())
+}
+
+fn write_u32(bytes: &mut [u8], index: usize, value: u32) {
+    bytes[4 * index..4 * index + 4].copy_from_slice(&value.to_le_bytes());
+}
+
+fn write_u32s(bytes: &mut [u8], values: &[u32]) {
+    for (i, &value) in values.iter().enumerate() {
+        write_u32(bytes, i, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 -> 1 -> 3, 0 -> 2 -> 3, 3 -> 0, and 4 off on its own
+    fn diamond() -> ArenaGraph<&'static str> {
+        ArenaGraph::from_edges(
+            vec!["a", "b", "c", "d", "e"],
+            [(0, 1), (0, 2), (1, 3), (2, 3), (3, 0)],
+        )
+    }
+
+    fn bfs_order<T>(graph: &ArenaGraph<T>, scratch: &mut Scratch) -> Vec<usize> {
+        let mut seen = Vec::new();
+        graph.bfs(graph.node(0).unwrap(), scratch, |n| seen.push(n.index()));
+        seen
+    }
+
+    fn dfs_order<T>(graph: &ArenaGraph<T>, scratch: &mut Scratch) -> Vec<usize> {
+        let mut seen = Vec::new();
+        graph.dfs(graph.node(0).unwrap(), scratch, |n| seen.push(n.index()));
+        seen
+    }
+
+    #[test]
+    fn test_happy_path_build_and_query() {
+        let graph = diamond();
+        let a = graph.node(0).unwrap();
+        assert_eq!((graph.node_count(), graph.edge_count()), (5, 5));
+        assert_eq!(*graph.value(a), "a");
+        assert_eq!(
+            graph.neighbors(a).map(NodeId::index).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert_eq!(graph.degree(graph.node(4).unwrap()), 0);
+        assert_eq!(graph.arena_bytes(), 4 * (6 + 5));
+    }
+
+    #[test]
+    fn test_bfs_and_dfs_orders() {
+        // 0 -> 1 -> 3 -> 4, 0 -> 2 -> 4
+        let graph = ArenaGraph::from_edges(vec![""; 5], [(0, 1), (0, 2), (1, 3), (3, 4), (2, 4)]);
+        let mut scratch = Scratch::new();
+        let bfs = bfs_order(&graph, &mut scratch);
+        let dfs = dfs_order(&graph, &mut scratch);
+        assert_eq!(bfs, vec![0, 1, 2, 3, 4]);
+        assert_eq!(dfs, vec![0, 1, 3, 4, 2]);
+    }
+
+    #[test]
+    fn test_traversals_skip_unreachable_nodes_and_survive_cycles() {
+        let graph = diamond();
+        let mut scratch = Scratch::new();
+        let bfs = bfs_order(&graph, &mut scratch);
+        let dfs = dfs_order(&graph, &mut scratch);
+        assert_eq!(bfs, vec![0, 1, 2, 3]);
+        assert_eq!(dfs, vec![0, 1, 3, 2]);
+    }
+
+    #[test]
+    fn test_scratch_is_reused_until_a_bigger_graph_needs_more() {
+        let mut scratch = Scratch::new();
+        let small = diamond();
+        small.dfs(small.node(0).unwrap(), &mut scratch, |_| {});
+        let reserved = scratch.capacity();
+        assert_eq!(reserved, 5 * 9);
+        small.bfs(small.node(0).unwrap(), &mut scratch, |_| {});
+        assert_eq!(scratch.capacity(), reserved, "BFS fits in the DFS's space");
+
+        let path = ArenaGraph::from_edges(vec![(); 100], (0..99).map(|i| (i, i + 1)));
+        let mut count = 0;
+        path.dfs(path.node(0).unwrap(), &mut scratch, |_| count += 1);
+        assert_eq!(count, 100, "A long path is walked without recursion");
+        assert!(scratch.capacity() > reserved);
+    }
+
+    #[test]
+    fn test_builder_and_undirected_edges() {
+        let mut builder = ArenaGraph::builder();
+        let hub = builder.add_node(0);
+        let spokes: Vec<NodeId> = (1..=3).map(|i| builder.add_node(i)).collect();
+        for &spoke in &spokes {
+            builder.add_undirected_edge(hub, spoke);
+        }
+        let mut graph = builder.build();
+        assert_eq!(graph.degree(hub), 3);
+        assert!(spokes.iter().all(|&s| graph.neighbors(s).eq([hub])));
+
+        *graph.value_mut(hub) = 10;
+        assert_eq!(graph.nodes().map(|n| *graph.value(n)).sum::<i32>(), 16);
+    }
+
+    #[test]
+    fn test_edge_case_no_edges_and_debug() {
+        let graph = ArenaGraph::from_edges(vec!['x', 'y'], []);
+        assert_eq!(graph.edge_count(), 0);
+        assert_eq!(graph.node(2), None);
+        let mut seen = Vec::new();
+        graph.bfs(graph.node(1).unwrap(), &mut Scratch::new(), |n| {
+            seen.push(n)
+        });
+        assert_eq!(seen, vec![graph.node(1).unwrap()]);
+        assert_eq!(format!("{graph:?}"), "{(0, 'x'): [], (1, 'y'): []}");
+    }
+
+    #[test]
+    #[should_panic(expected = "missing node")]
+    fn test_unhappy_path_edge_to_missing_node_panics() {
+        ArenaGraph::from_edges(vec![1, 2], [(0, 2)]);
+    }
+}
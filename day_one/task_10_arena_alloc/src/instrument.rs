@@ -0,0 +1,33 @@
+//! Hooks behind the `tracing` feature. Without it every function is empty and
+//! inlines away.
+
+/// Records a bump allocation, or a request the arena had no room for
+#[inline]
+pub(crate) fn allocation(size: usize, succeeded: bool, used: usize) {
+    #[cfg(feature = "tracing")]
+    {
+        tracing::trace!(size, succeeded, used, "arena allocate");
+        if succeeded {
+            metrics::counter!("arena.allocations").increment(1);
+            metrics::histogram!("arena.allocation_bytes").record(size as f64);
+            metrics::gauge!("arena.bytes_used").set(used as f64);
+        } else {
+            metrics::counter!("arena.allocation_failures").increment(1);
+        }
+    }
+    #[cfg(not(feature = "tracing"))]
+    let _ = (size, succeeded, used);
+}
+
+/// Records a reset, with how much of the arena was in use before it
+#[inline]
+pub(crate) fn reset(released: usize) {
+    #[cfg(feature = "tracing")]
+    {
+        tracing::debug!(released, "arena reset");
+        metrics::counter!("arena.resets").increment(1);
+        metrics::gauge!("arena.bytes_used").set(0.0);
+    }
+    #[cfg(not(feature = "tracing"))]
+    let _ = released;
+}
@@ -0,0 +1,422 @@
+//! String interning on top of `MemoryArena`.
+//!
+//! Each distinct string is copied into an arena once and named by a `Symbol`,
+//! a `u32` that is cheap to copy, hash and compare. Strings are reached through
+//! `ArenaHandle`s, so the interner works unchanged under `safe-only`.
+
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::BuildHasher;
+use std::ops::Deref;
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::{ArenaHandle, MemoryArena};
+
+/// Size of each arena the interner allocates; longer strings get an arena of their own
+const CHUNK_SIZE: usize = 4096;
+
+/// Handle to a string stored in an `Interner`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    /// Rebuilds a symbol from its index, e.g. to name a pre-interned string in a `const`
+    pub const fn from_u32(index: u32) -> Self {
+        Symbol(index)
+    }
+
+    /// Returns the symbol's index; symbols are numbered from zero in interning order
+    pub const fn as_u32(self) -> u32 {
+        self.0
+    }
+}
+
+// Where a symbol's text lives
+enum Entry {
+    Static(&'static str),
+    Arena { chunk: usize, handle: ArenaHandle },
+    // The arena refuses zero-sized allocations
+    Empty,
+}
+
+/// Deduplicating string store that hands out `Symbol`s
+///
+/// Strings are never removed; the arenas only grow, one `CHUNK_SIZE` block at a time.
+/// The index maps string hashes to symbols rather than holding the strings
+/// themselves, so every string is stored exactly once.
+pub struct Interner {
+    chunks: Vec<MemoryArena>,
+    entries: Vec<Entry>,
+    index: HashMap<u64, Vec<Symbol>>,
+    hasher: RandomState,
+}
+
+impl Interner {
+    /// Creates an empty interner; no arena is allocated until the first string arrives
+    pub fn new() -> Self {
+        Interner {
+            chunks: Vec::new(),
+            entries: Vec::new(),
+            index: HashMap::new(),
+            hasher: RandomState::new(),
+        }
+    }
+
+    /// Creates an interner whose first symbols are `words`, in order
+    ///
+    /// Static strings are referenced rather than copied. If `words` has no
+    /// duplicates, `words[i]` is `Symbol::from_u32(i)`, so known names such as
+    /// keywords can be declared as constants.
+    pub fn with_static(words: &[&'static str]) -> Self {
+        let mut interner = Self::new();
+        for &word in words {
+            interner.intern_static(word);
+        }
+        interner
+    }
+
+    /// Returns the symbol for `string`, copying it into the arena if it is new
+    pub fn intern(&mut self, string: &str) -> Symbol {
+        if let Some(symbol) = self.get(string) {
+            return symbol;
+        }
+        let entry = if string.is_empty() {
+            Entry::Empty
+        } else {
+            self.store(string.as_bytes())
+        };
+        self.push(string, entry)
+    }
+
+    /// Returns the symbol for `string`, keeping a reference instead of copying it if it is new
+    pub fn intern_static(&mut self, string: &'static str) -> Symbol {
+        match self.get(string) {
+            Some(symbol) => symbol,
+            None => self.push(string, Entry::Static(string)),
+        }
+    }
+
+    /// Returns the symbol for `string` if it has been interned
+    pub fn get(&self, string: &str) -> Option<Symbol> {
+        let hash = self.hasher.hash_one(string);
+        self.index
+            .get(&hash)?
+            .iter()
+            .copied()
+            .find(|&symbol| self.try_resolve(symbol) == Some(string))
+    }
+
+    /// Returns the string behind `symbol`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `symbol` did not come from this interner.
+    pub fn resolve(&self, symbol: Symbol) -> &str {
+        self.try_resolve(symbol)
+            .expect("symbol was not created by this interner")
+    }
+
+    /// Returns the string behind `symbol`, or `None` if it did not come from this interner
+    pub fn try_resolve(&self, symbol: Symbol) -> Option<&str> {
+        match *self.entries.get(symbol.0 as usize)? {
+            Entry::Static(string) => Some(string),
+            Entry::Arena { chunk, handle } => {
+                let bytes = self.chunks[chunk].get(handle)?;
+                // Only whole `str`s are ever copied in, so this cannot fail
+                Some(std::str::from_utf8(bytes).expect("interned bytes are UTF-8"))
+            }
+            Entry::Empty => Some(""),
+        }
+    }
+
+    /// Returns the number of distinct strings
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Checks if nothing has been interned
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterates over every symbol and its string, in interning order
+    pub fn iter(&self) -> impl Iterator<Item = (Symbol, &str)> {
+        (0..self.entries.len() as u32).map(|index| {
+            let symbol = Symbol(index);
+            (symbol, self.resolve(symbol))
+        })
+    }
+
+    /// Returns the bytes copied into arenas so far; static strings take none
+    pub fn arena_bytes(&self) -> usize {
+        self.chunks.iter().map(MemoryArena::used).sum()
+    }
+
+    // Copies `bytes` into the newest arena, starting a new one if it is too full
+    fn store(&mut self, bytes: &[u8]) -> Entry {
+        let fits = self
+            .chunks
+            .last()
+            .is_some_and(|chunk| chunk.remaining() >= bytes.len());
+        if !fits {
+            self.chunks
+                .push(MemoryArena::new(CHUNK_SIZE.max(bytes.len())));
+        }
+        let chunk = self.chunks.len() - 1;
+        let arena = &mut self.chunks[chunk];
+        let handle = arena
+            .allocate_handle(bytes.len())
+            .expect("a fresh chunk has room for the string");
+        arena
+            .get_mut(handle)
+            .expect("handle is from the current generation")
+            .copy_from_slice(bytes);
+        Entry::Arena { chunk, handle }
+    }
+
+    fn push(&mut self, string: &str, entry: Entry) -> Symbol {
+        let index = u32::try_from(self.entries.len()).expect("interner is out of symbols");
+        let symbol = Symbol(index);
+        self.entries.push(entry);
+        let hash = self.hasher.hash_one(string);
+        self.index.entry(hash).or_default().push(symbol);
+        symbol
+    }
+}
+
+impl Default for Interner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Interner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// `Interner` that can be shared between threads
+///
+/// Lookups of strings that are already interned only take a read lock;
+/// interning a new string takes the write lock.
+#[derive(Default)]
+pub struct SyncInterner {
+    inner: RwLock<Interner>,
+}
+
+impl SyncInterner {
+    /// Creates an empty interner
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an interner whose first symbols are `words`, in order; see `Interner::with_static`
+    pub fn with_static(words: &[&'static str]) -> Self {
+        SyncInterner {
+            inner: RwLock::new(Interner::with_static(words)),
+        }
+    }
+
+    /// Returns the symbol for `string`, copying it into the arena if it is new
+    pub fn intern(&self, string: &str) -> Symbol {
+        if let Some(symbol) = self.get(string) {
+            return symbol;
+        }
+        // Another thread may have interned it between the two locks; `intern` checks again
+        self.write().intern(string)
+    }
+
+    /// Returns the symbol for `string` if it has been interned
+    pub fn get(&self, string: &str) -> Option<Symbol> {
+        self.read().get(string)
+    }
+
+    /// Returns the string behind `symbol`, holding a read lock until the result is dropped
+    ///
+    /// # Panics
+    ///
+    /// Panics if `symbol` did not come from this interner.
+    pub fn resolve(&self, symbol: Symbol) -> Resolved<'_> {
+        let guard = self.read();
+        assert!(
+            guard.try_resolve(symbol).is_some(),
+            "symbol was not created by this interner"
+        );
+        Resolved { guard, symbol }
+    }
+
+    /// Returns the number of distinct strings
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    /// Checks if nothing has been interned
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    /// Unwraps the single-threaded interner
+    pub fn into_inner(self) -> Interner {
+        self.inner
+            .into_inner()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    // A panic while interning cannot leave the interner half-updated in a way
+    // that breaks lookups, so poisoning is ignored
+    fn read(&self) -> RwLockReadGuard<'_, Interner> {
+        self.inner
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, Interner> {
+        self.inner
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// A string borrowed from a `SyncInterner`, keeping it read-locked
+pub struct Resolved<'a> {
+    guard: RwLockReadGuard<'a, Interner>,
+    symbol: Symbol,
+}
+
+impl Deref for Resolved<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.guard.resolve(self.symbol)
+    }
+}
+
+impl fmt::Display for Resolved<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self)
+    }
+}
+
+impl fmt::Debug for Resolved<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_happy_path_intern_and_resolve() {
+        let mut interner = Interner::new();
+        let hello = interner.intern("hello");
+        let world = interner.intern("world");
+        assert_ne!(hello, world);
+        assert_eq!(interner.intern("hello"), hello, "Same string, same symbol");
+        assert_eq!(interner.resolve(hello), "hello");
+        assert_eq!(interner.resolve(world), "world");
+        assert_eq!(interner.len(), 2);
+        assert_eq!(interner.arena_bytes(), 10);
+    }
+
+    #[test]
+    fn test_static_words_get_the_first_symbols() {
+        const FN: Symbol = Symbol::from_u32(0);
+        const LET: Symbol = Symbol::from_u32(1);
+
+        let mut interner = Interner::with_static(&["fn", "let"]);
+        assert_eq!(interner.get("let"), Some(LET));
+        assert_eq!(interner.resolve(FN), "fn");
+        assert_eq!(interner.arena_bytes(), 0, "Static strings are not copied");
+
+        assert_eq!(interner.intern("fn"), FN);
+        assert_eq!(interner.intern("x").as_u32(), 2);
+    }
+
+    #[test]
+    fn test_strings_spill_into_new_chunks() {
+        let mut interner = Interner::new();
+        let symbols: Vec<Symbol> = (0..2000)
+            .map(|i| interner.intern(&format!("identifier_{i}")))
+            .collect();
+        let long = "x".repeat(CHUNK_SIZE * 2);
+        let long_symbol = interner.intern(&long);
+
+        assert!(interner.chunks.len() > 2);
+        for (i, &symbol) in symbols.iter().enumerate() {
+            assert_eq!(interner.resolve(symbol), format!("identifier_{i}"));
+        }
+        assert_eq!(interner.resolve(long_symbol), long);
+    }
+
+    #[test]
+    fn test_iter_and_debug_follow_interning_order() {
+        let mut interner = Interner::with_static(&["a"]);
+        interner.intern("b");
+        interner.intern("a");
+        let pairs: Vec<(u32, &str)> = interner
+            .iter()
+            .map(|(symbol, string)| (symbol.as_u32(), string))
+            .collect();
+        assert_eq!(pairs, vec![(0, "a"), (1, "b")]);
+        assert_eq!(
+            format!("{interner:?}"),
+            r#"{Symbol(0): "a", Symbol(1): "b"}"#
+        );
+    }
+
+    #[test]
+    fn test_unhappy_path_foreign_symbols() {
+        let mut interner = Interner::new();
+        assert_eq!(interner.get("missing"), None);
+        assert_eq!(interner.try_resolve(Symbol::from_u32(0)), None);
+        interner.intern("only");
+        assert_eq!(interner.try_resolve(Symbol::from_u32(1)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "not created by this interner")]
+    fn test_unhappy_path_resolve_foreign_symbol_panics() {
+        Interner::new().resolve(Symbol::from_u32(3));
+    }
+
+    #[test]
+    fn test_edge_case_empty_and_unicode_strings() {
+        let mut interner = Interner::new();
+        let empty = interner.intern("");
+        let crab = interner.intern("🦀 ferris");
+        assert_eq!(interner.intern(""), empty);
+        assert_eq!(interner.resolve(empty), "");
+        assert_eq!(interner.resolve(crab), "🦀 ferris");
+        assert!(!interner.is_empty());
+    }
+
+    #[test]
+    fn test_sync_interner_agrees_across_threads() {
+        let interner = Arc::new(SyncInterner::with_static(&["main"]));
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let interner = Arc::clone(&interner);
+                thread::spawn(move || {
+                    (0..100)
+                        .map(|i| interner.intern(&format!("name{i}")))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        let results: Vec<Vec<Symbol>> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+        assert!(results.windows(2).all(|pair| pair[0] == pair[1]));
+        assert_eq!(interner.len(), 101);
+        assert_eq!(&*interner.resolve(results[0][7]), "name7");
+        assert_eq!(interner.resolve(Symbol::from_u32(0)).to_string(), "main");
+
+        let interner = Arc::into_inner(interner).unwrap().into_inner();
+        assert_eq!(interner.get("name99"), Some(results[0][99]));
+    }
+}
@@ -0,0 +1,336 @@
+#![cfg_attr(feature = "safe-only", forbid(unsafe_code))]
+
+use std::io;
+use std::ops::{Deref, DerefMut};
+
+use task_27_page_alloc::{PageAllocator, Pages};
+
+#[cfg(any(test, feature = "testing"))]
+pub mod fuzz;
+pub mod graph;
+mod instrument;
+pub mod interner;
+
+pub use graph::{ArenaGraph, GraphBuilder, NodeId, Scratch};
+pub use interner::{Interner, Symbol, SyncInterner};
+
+// Where the arena's bytes live: the heap, or pages mapped straight from the OS
+enum Backing {
+    Vec(Vec<u8>),
+    Pages(Pages),
+}
+
+impl Deref for Backing {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Backing::Vec(vec) => vec,
+            Backing::Pages(pages) => pages,
+        }
+    }
+}
+
+impl DerefMut for Backing {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        match self {
+            Backing::Vec(vec) => vec,
+            Backing::Pages(pages) => pages,
+        }
+    }
+}
+
+pub struct MemoryArena {
+    memory: Backing,    // This will hold the pre-allocated memory block.
+    current: usize,     // The current position to allocate from.
+    allocations: usize, // Allocations handed out since the last reset.
+    peak: usize,        // The highest position ever reached.
+    generation: u64,    // Bumped on every reset so older handles stop resolving.
+}
+
+/// A chunk of a `MemoryArena`, named by its offset rather than a raw pointer.
+/// It stays valid until the arena is reset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArenaHandle {
+    offset: usize,
+    len: usize,
+    generation: u64,
+}
+
+impl ArenaHandle {
+    // Return the size of the chunk in bytes
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    // Always false: the arena refuses zero-sized allocations
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl MemoryArena {
+    // Create a new arena with a given size
+    pub fn new(size: usize) -> Self {
+        // Fill the allocated memory with zeroes (pre-allocation)
+        let memory = vec![0; size];
+        MemoryArena {
+            memory: Backing::Vec(memory),
+            current: 0,  // Start at the beginning of the arena.
+            allocations: 0,
+            peak: 0,
+            generation: 0,
+        }
+    }
+
+    // Create an arena backed by whole pages from the OS instead of the heap.
+    // The size is rounded up to a multiple of the page size.
+    pub fn with_pages(size: usize) -> io::Result<Self> {
+        let pages = PageAllocator::allocate(size)?;
+        Ok(MemoryArena {
+            memory: Backing::Pages(pages),
+            current: 0,
+            allocations: 0,
+            peak: 0,
+            generation: 0,
+        })
+    }
+
+    // Allocate a chunk of memory from the arena
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip(self)))]
+    pub fn allocate(&mut self, size: usize) -> Option<*mut u8> {
+        let offset = self.bump(size)?;
+        Some(self.memory[offset..].as_mut_ptr())
+    }
+
+    // Allocate a chunk and refer to it by position instead of by pointer, so it
+    // can be read and written through `get`/`get_mut` without unsafe code
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip(self)))]
+    pub fn allocate_handle(&mut self, size: usize) -> Option<ArenaHandle> {
+        let offset = self.bump(size)?;
+        Some(ArenaHandle {
+            offset,
+            len: size,
+            generation: self.generation,
+        })
+    }
+
+    // Borrow a chunk's bytes; handles from before the last reset give None
+    pub fn get(&self, handle: ArenaHandle) -> Option<&[u8]> {
+        self.is_current(handle)
+            .then(|| &self.memory[handle.offset..handle.offset + handle.len])
+    }
+
+    // Mutably borrow a chunk's bytes; handles from before the last reset give None
+    pub fn get_mut(&mut self, handle: ArenaHandle) -> Option<&mut [u8]> {
+        if !self.is_current(handle) {
+            return None;
+        }
+        Some(&mut self.memory[handle.offset..handle.offset + handle.len])
+    }
+
+    // Reserve `size` bytes and return where they start
+    fn bump(&mut self, size: usize) -> Option<usize> {
+        // If size is 0, allocation should fail
+        if size == 0 {
+            instrument::allocation(size, false, self.current);
+            return None;
+        }
+
+        // Ensure there is enough space in the arena
+        if self.current + size <= self.memory.len() {
+            let offset = self.current;
+            self.current += size;
+            self.allocations += 1;
+            self.peak = self.peak.max(self.current);
+            instrument::allocation(size, true, self.current);
+            Some(offset)
+        } else {
+            // Not enough space
+            instrument::allocation(size, false, self.current);
+            None
+        }
+    }
+
+    // A handle is only valid for the generation it was allocated in, and the
+    // range check guards against handles that came from a larger arena
+    fn is_current(&self, handle: ArenaHandle) -> bool {
+        handle.generation == self.generation && handle.offset + handle.len <= self.current
+    }
+
+    // Reset the arena (optional, for reusing the memory block)
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip(self)))]
+    pub fn reset(&mut self) {
+        instrument::reset(self.current);
+        self.current = 0; // Reset the allocation pointer to the start
+        self.allocations = 0;
+        self.generation += 1; // Invalidate every handle handed out so far
+    }
+
+    // Return the remaining available memory in the arena
+    pub fn remaining(&self) -> usize {
+        self.memory.len() - self.current // Calculate how much memory is left
+    }
+
+    // Return how many bytes have been handed out since the last reset
+    pub fn used(&self) -> usize {
+        self.current
+    }
+
+    // Return how many allocations have been made since the last reset
+    pub fn live_allocations(&self) -> usize {
+        self.allocations
+    }
+
+    // Return the most bytes that were ever in use at once; survives resets
+    pub fn peak_used(&self) -> usize {
+        self.peak
+    }
+
+    // Check whether a pointer points into the arena's memory block
+    pub fn contains(&self, ptr: *const u8) -> bool {
+        self.memory.as_ptr_range().contains(&ptr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allocate_successfully() {
+        let mut arena = MemoryArena::new(1024); // Create arena with 1024 bytes
+        let chunk = arena.allocate(512); // Allocate 512 bytes
+        assert!(chunk.is_some(), "Allocation should be successful");
+        assert_eq!(arena.remaining(), 512, "Arena should have 512 bytes remaining");
+    }
+
+    #[test]
+    fn test_allocate_multiple_chunks() {
+        let mut arena = MemoryArena::new(1024); // Create arena with 1024 bytes
+        let chunk1 = arena.allocate(256); // Allocate 256 bytes
+        let chunk2 = arena.allocate(256); // Allocate another 256 bytes
+        let chunk3 = arena.allocate(256); // Allocate another 256 bytes
+
+        assert!(chunk1.is_some(), "First allocation should be successful");
+        assert!(chunk2.is_some(), "Second allocation should be successful");
+        assert!(chunk3.is_some(), "Third allocation should be successful");
+
+        // Check remaining memory
+        assert_eq!(arena.remaining(), 256, "Arena should have 256 bytes remaining");
+    }
+
+    #[test]
+    fn test_allocate_more_than_available_space() {
+        let mut arena = MemoryArena::new(1024); // Create arena with 1024 bytes
+        let chunk = arena.allocate(1100); // Try to allocate 1100 bytes (more than available)
+
+        assert!(chunk.is_none(), "Allocation should fail if there is not enough memory");
+    }
+
+    #[test]
+    fn test_allocate_zero_size() {
+        let mut arena = MemoryArena::new(1024); // Create arena with 1024 bytes
+        let chunk = arena.allocate(0); // Try to allocate 0 bytes
+
+        assert!(chunk.is_none(), "Allocation of 0 bytes should fail");
+    }
+
+    #[test]
+    fn test_allocate_large_chunk() {
+        let mut arena = MemoryArena::new(1024); // Create arena with 1024 bytes
+        let chunk = arena.allocate(1025); // Try to allocate 1025 bytes (larger than arena)
+
+        assert!(chunk.is_none(), "Allocation should fail if the requested size is larger than the arena");
+    }
+
+    #[test]
+    fn test_contains_only_arena_pointers() {
+        let mut arena = MemoryArena::new(64);
+        let chunk = arena.allocate(16).unwrap();
+        assert!(arena.contains(chunk), "Allocated chunk lies inside the arena");
+
+        let outside = [0u8; 4];
+        assert!(!arena.contains(outside.as_ptr()), "Unrelated memory is not part of the arena");
+    }
+
+    #[test]
+    fn test_usage_counters() {
+        let mut arena = MemoryArena::new(1024);
+        arena.allocate(100);
+        arena.allocate(200);
+        assert_eq!(arena.used(), 300, "Used bytes should add up");
+        assert_eq!(arena.live_allocations(), 2, "Both allocations should be counted");
+
+        arena.reset();
+        arena.allocate(50);
+        assert_eq!(arena.used(), 50, "Reset should clear the used bytes");
+        assert_eq!(arena.live_allocations(), 1, "Reset should clear the allocation count");
+        assert_eq!(arena.peak_used(), 300, "The peak should survive a reset");
+    }
+
+    #[test]
+    fn test_page_backed_arena() {
+        let mut arena = MemoryArena::with_pages(1000).unwrap();
+        let capacity = arena.remaining();
+        assert!(capacity >= 1000, "Size is rounded up to whole pages");
+
+        let chunk = arena.allocate_handle(100).unwrap();
+        arena.get_mut(chunk).unwrap().fill(0xAB);
+        let bytes = arena.get(chunk).unwrap();
+        assert!(bytes.iter().all(|&b| b == 0xAB), "The mapped pages are writable");
+        assert!(arena.contains(bytes.as_ptr()), "Chunk lies inside the mapped pages");
+        assert_eq!(arena.remaining(), capacity - 100);
+        assert!(MemoryArena::with_pages(0).is_err(), "Empty mappings are rejected");
+    }
+
+    #[test]
+    fn test_reset_arena() {
+        let mut arena = MemoryArena::new(1024); // Create arena with 1024 bytes
+        let chunk1 = arena.allocate(512); // Allocate 512 bytes
+        let chunk2 = arena.allocate(256); // Allocate 256 bytes
+        assert!(chunk1.is_some(), "First allocation should be successful");
+        assert!(chunk2.is_some(), "Second allocation should be successful");
+
+        // Reset the arena and check remaining memory
+        arena.reset();
+        assert_eq!(arena.remaining(), 1024, "Arena should be reset to full capacity");
+    }
+
+    #[test]
+    fn test_handles_read_and_write() {
+        let mut arena = MemoryArena::new(64);
+        let first = arena.allocate_handle(4).unwrap();
+        let second = arena.allocate_handle(8).unwrap();
+        arena.get_mut(first).unwrap().copy_from_slice(b"rust");
+        arena.get_mut(second).unwrap().fill(7);
+
+        assert_eq!(arena.get(first), Some(&b"rust"[..]));
+        assert_eq!(arena.get(second).unwrap(), &[7; 8]);
+        assert_eq!(second.len(), 8);
+        assert_eq!(arena.used(), 12, "Handles and pointers share the same bump");
+    }
+
+    #[test]
+    fn test_handles_expire_on_reset() {
+        let mut arena = MemoryArena::new(16);
+        let stale = arena.allocate_handle(8).unwrap();
+        arena.reset();
+        let fresh = arena.allocate_handle(8).unwrap();
+
+        assert_eq!(arena.get(stale), None, "A reset invalidates older handles");
+        assert_eq!(arena.get_mut(stale), None);
+        assert!(arena.get(fresh).is_some());
+        assert_eq!(arena.allocate_handle(0), None, "Zero sizes are refused here too");
+        assert_eq!(arena.allocate_handle(9), None, "So are chunks that don't fit");
+    }
+
+    #[test]
+    fn test_handle_from_a_larger_arena_does_not_resolve() {
+        let mut large = MemoryArena::new(64);
+        let mut small = MemoryArena::new(8);
+        let handle = large.allocate_handle(32).unwrap();
+        assert_eq!(small.get(handle), None);
+        assert_eq!(small.get_mut(handle), None);
+    }
+}
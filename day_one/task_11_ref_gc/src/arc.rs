@@ -1,5 +1,4 @@
 use std::sync::{Arc, Mutex};
-use std::thread;
 
 #[derive(Debug)]
 pub struct MyData {
@@ -33,40 +32,3 @@ impl ReferenceCountedGC {
     
     // The memory will be freed when the last reference to `Arc` is dropped.
 }
-
-pub fn run_arc_example() {
-    let gc = ReferenceCountedGC::new(42);
-
-    // Create multiple references using Arc
-    let data_ref1 = gc.get_data();
-    let data_ref2 = gc.get_data();
-
-    println!("Data via reference 1: {}", data_ref1.lock().unwrap().get_value());
-    println!("Data via reference 2: {}", data_ref2.lock().unwrap().get_value());
-
-    // Now let's spawn threads and share the data between them using Arc
-    let handle1 = thread::spawn({
-        let data_ref1 = Arc::clone(&data_ref1); // Clone the Arc before moving it into the thread
-        move || {
-            println!("Thread 1: {}", data_ref1.lock().unwrap().get_value());
-        }
-    });
-
-    let handle2 = thread::spawn({
-        let data_ref2 = Arc::clone(&data_ref2); // Clone the Arc before moving it into the thread
-        move || {
-            println!("Thread 2: {}", data_ref2.lock().unwrap().get_value());
-        }
-    });
-
-    // Wait for threads to complete
-    handle1.join().unwrap();
-    handle2.join().unwrap();
-
-    // The memory is freed automatically once both references are dropped and no longer in scope
-    drop(data_ref1);
-    drop(data_ref2);
-
-    println!("Memory will be cleaned up when the last reference goes out of scope.");
-}
-
@@ -1,70 +1,41 @@
 use std::rc::Rc;
 use std::cell::RefCell;
 
-pub mod arc; 
+pub mod arc;
 
 #[derive(Debug)]
-struct MyData {
-    value: i32,
+pub struct MyData {
+    pub value: i32,
 }
 
 impl MyData {
-    fn new(value: i32) -> Self {
+    pub fn new(value: i32) -> Self {
         MyData { value }
     }
 
-    fn get_value(&self) -> i32 {
+    pub fn get_value(&self) -> i32 {
         self.value
     }
 }
 
 #[derive(Debug)]
-struct ReferenceCountedGC {
-    data: Rc<RefCell<MyData>>,
+pub struct ReferenceCountedGC {
+    pub data: Rc<RefCell<MyData>>,
 }
 
 impl ReferenceCountedGC {
-    fn new(value: i32) -> Self {
+    pub fn new(value: i32) -> Self {
         let data = Rc::new(RefCell::new(MyData::new(value)));
         ReferenceCountedGC { data }
     }
 
-    fn get_data(&self) -> Rc<RefCell<MyData>> {
+    pub fn get_data(&self) -> Rc<RefCell<MyData>> {
         Rc::clone(&self.data)
     }
         
     // Rust will automatically clean up when no references exist, 
 }
 
-fn main() {
-    let gc = ReferenceCountedGC::new(42);
-
-    // Create multiple references using Rc
-    let data_ref1 = gc.get_data();
-    let data_ref2 = gc.get_data();
-
-    println!("Data via reference 1: {}", data_ref1.borrow().get_value());
-    println!("Data via reference 2: {}", data_ref2.borrow().get_value());
-
-    // After this point, both references are still valid, and the memory is not freed.
-    // Once the references are dropped, the memory will be freed automatically.
-
-    drop(data_ref1);  // Dropping the first reference.
-    println!("Reference count after dropping one reference: {}",
-             Rc::strong_count(&gc.data));
-
-    // Once both references go out of scope, `MyData` will be deallocated automatically.
-
-    drop(data_ref2);  // Dropping the second reference.
-    println!("Reference count after dropping second reference: {}",
-             Rc::strong_count(&gc.data));
-
-    // gc will be cleaned up at the end of main, when no more references remain.
-
-    println!("Running arc example...");
-    arc::run_arc_example();
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -159,8 +130,6 @@ mod tests {
     }
 }
 
-
-
 #[cfg(test)]
 mod arc_tests {
     use crate::arc::ReferenceCountedGC as ArcReferenceCountedGC;
@@ -0,0 +1,89 @@
+//! Decodes fuzzer input into pool operations and checks the pool's bookkeeping
+
+use crate::{MemoryBlock, MemoryPool};
+
+/// Runs the operations encoded in `data`, panicking if the pool misbehaves
+///
+/// Each byte is one operation: allocate a fixed size, allocate from a size
+/// range, or return one of the blocks currently held. Sizes come from the
+/// next bytes and stay small so random input reuses blocks. The blocks held
+/// by the caller are the model for `bytes_in_use` and `blocks_in_use`.
+pub fn fuzz_ops(data: &[u8]) {
+    let mut bytes = data.iter().copied();
+    let mut pool = MemoryPool::new();
+    let mut held: Vec<MemoryBlock> = Vec::new();
+
+    while let Some(op) = bytes.next() {
+        match op % 3 {
+            0 => {
+                let size = bytes.next().unwrap_or(op) as usize % 64;
+                let block = pool
+                    .allocate(size)
+                    .expect("fixed-size allocation always succeeds");
+                assert_eq!(block.size, size, "requested size");
+                held.push(block);
+            }
+            1 => {
+                let min = bytes.next().unwrap_or(0) as usize % 64;
+                let max = min + bytes.next().unwrap_or(0) as usize % 8;
+                if let Some(block) = pool.allocate_variable_size(min, max) {
+                    assert!((min..=max).contains(&block.size), "size within range");
+                    held.push(block);
+                }
+            }
+            _ => {
+                if !held.is_empty() {
+                    let index = bytes.next().unwrap_or(0) as usize % held.len();
+                    pool.deallocate(held.swap_remove(index));
+                }
+            }
+        }
+
+        for block in &held {
+            assert_eq!(block.data.len(), block.size, "block data matches its size");
+        }
+        let bytes_held: usize = held.iter().map(|block| block.size).sum();
+        assert_eq!(pool.bytes_in_use(), bytes_held, "bytes_in_use");
+        assert_eq!(pool.blocks_in_use(), held.len(), "blocks_in_use");
+        assert!(pool.peak_bytes_in_use() >= bytes_held, "peak");
+    }
+
+    for block in held.drain(..) {
+        pool.deallocate(block);
+    }
+    assert_eq!(pool.bytes_in_use(), 0, "everything was returned");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allocate_return_and_reuse() {
+        // Allocate 10 bytes, return it, then take it back through a 8..=12 range
+        fuzz_ops(&[0, 10, 2, 0, 1, 8, 4]);
+    }
+
+    #[test]
+    fn test_edge_case_empty_and_truncated_input() {
+        fuzz_ops(&[]);
+        fuzz_ops(&[2, 1]); // Return with nothing held, then a range with no bytes left
+        fuzz_ops(&[0]); // Allocate with its size byte missing
+    }
+
+    #[test]
+    fn test_pseudo_random_inputs() {
+        let mut state = 0xdead_beef_u32;
+        for len in 0..300 {
+            let data: Vec<u8> = (0..len)
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 17;
+                    state ^= state << 5;
+                    state as u8
+                })
+                .collect();
+            fuzz_ops(&data);
+        }
+    }
+}
@@ -0,0 +1,45 @@
+//! Hooks behind the `tracing` feature. Without it every function is empty and
+//! inlines away.
+
+/// Records a block handed out: a hit reused a free block, a miss had to create one
+#[inline]
+pub(crate) fn allocation(hit: bool, size: usize, bytes_in_use: usize) {
+    #[cfg(feature = "tracing")]
+    {
+        tracing::trace!(hit, size, bytes_in_use, "alloc_mempool allocate");
+        if hit {
+            metrics::counter!("alloc_mempool.hits").increment(1);
+        } else {
+            metrics::counter!("alloc_mempool.misses").increment(1);
+        }
+        metrics::histogram!("alloc_mempool.allocation_bytes").record(size as f64);
+        metrics::gauge!("alloc_mempool.bytes_in_use").set(bytes_in_use as f64);
+    }
+    #[cfg(not(feature = "tracing"))]
+    let _ = (hit, size, bytes_in_use);
+}
+
+/// Records a variable-size request that found no free block in its range
+#[inline]
+pub(crate) fn range_miss(min_size: usize, max_size: usize) {
+    #[cfg(feature = "tracing")]
+    {
+        tracing::trace!(min_size, max_size, "alloc_mempool has no block in range");
+        metrics::counter!("alloc_mempool.misses").increment(1);
+    }
+    #[cfg(not(feature = "tracing"))]
+    let _ = (min_size, max_size);
+}
+
+/// Records a block coming back to the pool
+#[inline]
+pub(crate) fn deallocation(size: usize, bytes_in_use: usize) {
+    #[cfg(feature = "tracing")]
+    {
+        tracing::trace!(size, bytes_in_use, "alloc_mempool deallocate");
+        metrics::counter!("alloc_mempool.deallocations").increment(1);
+        metrics::gauge!("alloc_mempool.bytes_in_use").set(bytes_in_use as f64);
+    }
+    #[cfg(not(feature = "tracing"))]
+    let _ = (size, bytes_in_use);
+}
@@ -0,0 +1,274 @@
+use std::collections::HashMap;
+
+use task_27_page_alloc::{PageAllocator, Pages};
+
+#[cfg(any(test, feature = "testing"))]
+pub mod fuzz;
+mod instrument;
+
+/// A structure representing a block of memory in the pool.
+///
+/// `data` is a `Vec<u8>` of exactly `size` bytes by default. In a pool from
+/// `MemoryPool::with_pages` it is `Pages`, rounded up to whole pages.
+#[derive(Debug)]
+pub struct MemoryBlock<D = Vec<u8>> {
+    pub size: usize,
+    pub data: D,
+}
+
+/// The memory pool, which manages multiple blocks of memory.
+#[derive(Debug)]
+pub struct MemoryPool<D = Vec<u8>> {
+    pool: HashMap<usize, Vec<MemoryBlock<D>>>, // Keyed by block size.
+    new_data: fn(usize) -> Option<D>,         // Backs a block on a miss.
+    bytes_in_use: usize,
+    blocks_in_use: usize,
+    peak_bytes_in_use: usize,
+}
+
+impl MemoryPool {
+    /// Create a new memory pool.
+    pub fn new() -> Self {
+        MemoryPool::with_backing(|size| Some(vec![0; size]))
+    }
+}
+
+impl MemoryPool<Pages> {
+    /// Create a pool whose blocks are whole pages from the OS instead of the heap.
+    /// Every new block is its own mapping, so a zero size, or a failed mapping,
+    /// makes `allocate` return `None`.
+    pub fn with_pages() -> Self {
+        MemoryPool::with_backing(|size| PageAllocator::allocate(size).ok())
+    }
+}
+
+impl<D> MemoryPool<D> {
+    fn with_backing(new_data: fn(usize) -> Option<D>) -> Self {
+        MemoryPool {
+            pool: HashMap::new(),
+            new_data,
+            bytes_in_use: 0,
+            blocks_in_use: 0,
+            peak_bytes_in_use: 0,
+        }
+    }
+
+    /// Allocate a block of memory from the pool.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip(self)))]
+    pub fn allocate(&mut self, size: usize) -> Option<MemoryBlock<D>> {
+        let block = self.pool.entry(size).or_default();
+        
+        // If there are no free blocks of this size, create a new one.
+        let hit = !block.is_empty();
+        if !hit {
+            if let Some(data) = (self.new_data)(size) {
+                block.push(MemoryBlock { size, data });
+            }
+        }
+
+        let block = block.pop();
+        self.track_handed_out(block.as_ref());
+        instrument::allocation(hit, size, self.bytes_in_use);
+        block
+    }
+
+    /// Deallocate a block of memory and return it to the pool.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
+    pub fn deallocate(&mut self, block: MemoryBlock<D>) {
+        let block_size = block.size;
+        // Blocks built by hand were never counted, so saturate instead of underflowing
+        self.bytes_in_use = self.bytes_in_use.saturating_sub(block_size);
+        self.blocks_in_use = self.blocks_in_use.saturating_sub(1);
+        instrument::deallocation(block_size, self.bytes_in_use);
+        let entry = self.pool.entry(block_size).or_default();
+        entry.push(block);
+    }
+
+    /// Allocate fixed-size blocks.
+    pub fn allocate_fixed_size(&mut self, size: usize) -> Option<MemoryBlock<D>> {
+        self.allocate(size)
+    }
+
+    /// Allocate variable-size blocks.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip(self)))]
+    pub fn allocate_variable_size(&mut self, min_size: usize, max_size: usize) -> Option<MemoryBlock<D>> {
+        // Find the smallest block that fits within the specified range
+        for size in min_size..=max_size {
+            if let Some(blocks) = self.pool.get_mut(&size) {
+                if let Some(block) = blocks.pop() {
+                    self.track_handed_out(Some(&block));
+                    instrument::allocation(true, size, self.bytes_in_use);
+                    return Some(block); // Return only if an existing block is found
+                }
+            }
+        }
+        instrument::range_miss(min_size, max_size);
+        None // Return None if no block is found in the range
+    }
+
+    /// Deallocate a block of memory.
+    pub fn deallocate_block(&mut self, block: MemoryBlock<D>) {
+        self.deallocate(block);
+    }
+
+    /// Bytes in blocks that are handed out and not yet returned.
+    pub fn bytes_in_use(&self) -> usize {
+        self.bytes_in_use
+    }
+
+    /// Number of blocks that are handed out and not yet returned.
+    pub fn blocks_in_use(&self) -> usize {
+        self.blocks_in_use
+    }
+
+    /// The most bytes that were ever handed out at once.
+    pub fn peak_bytes_in_use(&self) -> usize {
+        self.peak_bytes_in_use
+    }
+
+    fn track_handed_out(&mut self, block: Option<&MemoryBlock<D>>) {
+        if let Some(block) = block {
+            self.bytes_in_use += block.size;
+            self.blocks_in_use += 1;
+            self.peak_bytes_in_use = self.peak_bytes_in_use.max(self.bytes_in_use);
+        }
+    }
+}
+
+impl Default for MemoryPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allocate_fixed_size() {
+        let mut pool = MemoryPool::new();
+        
+        // Allocate a fixed-size block
+        let block = pool.allocate_fixed_size(1024).expect("Allocation failed");
+        assert_eq!(block.size, 1024);
+        assert_eq!(block.data.len(), 1024);
+    }
+
+    #[test]
+    fn test_allocate_variable_size() {
+        let mut pool = MemoryPool::new();
+    
+        // Prepopulate the pool with blocks of various sizes
+        pool.deallocate_block(MemoryBlock {
+            size: 512,
+            data: vec![0; 512],
+        });
+        pool.deallocate_block(MemoryBlock {
+            size: 1024,
+            data: vec![0; 1024],
+        });
+        pool.deallocate_block(MemoryBlock {
+            size: 2048,
+            data: vec![0; 2048],
+        });
+    
+        // Allocate a variable-size block between 512 and 2048 bytes
+        let block = pool
+            .allocate_variable_size(512, 2048)
+            .expect("Allocation failed");
+        assert!(block.size >= 512 && block.size <= 2048);
+        assert_eq!(block.data.len(), block.size);
+    }
+    
+
+    #[test]
+    fn test_deallocate_block() {
+        let mut pool = MemoryPool::new();
+        
+        // Allocate and deallocate a block
+        let block = pool.allocate_fixed_size(1024).expect("Allocation failed");
+        pool.deallocate_block(block);
+        
+        // Verify that the pool has the deallocated block
+        let deallocated_block = pool.allocate_fixed_size(1024).expect("Allocation failed");
+        assert_eq!(deallocated_block.size, 1024);
+    }
+
+    #[test]
+    fn test_bytes_in_use_tracking() {
+        let mut pool = MemoryPool::new();
+
+        let small = pool.allocate(100).expect("Allocation failed");
+        let large = pool.allocate(400).expect("Allocation failed");
+        assert_eq!(pool.bytes_in_use(), 500);
+        assert_eq!(pool.blocks_in_use(), 2);
+
+        pool.deallocate(large);
+        assert_eq!(pool.bytes_in_use(), 100);
+        assert_eq!(pool.peak_bytes_in_use(), 500, "The peak is kept after a free");
+
+        let reused = pool.allocate_variable_size(300, 500).expect("Allocation failed");
+        assert_eq!(pool.bytes_in_use(), 500, "Reused blocks count again");
+        pool.deallocate(reused);
+        pool.deallocate(small);
+        assert_eq!(pool.blocks_in_use(), 0);
+    }
+
+    #[test]
+    fn test_page_backed_pool() {
+        let mut pool = MemoryPool::with_pages();
+        let mut block = pool.allocate(1000).expect("Allocation failed");
+        assert_eq!(block.size, 1000);
+        assert_eq!(block.data.len(), PageAllocator::page_size(), "Rounded up to a whole page");
+        assert_eq!(block.data.as_ptr() as usize % PageAllocator::page_size(), 0);
+        block.data[999] = 7;
+        pool.deallocate(block);
+
+        let reused = pool.allocate_variable_size(900, 1100).expect("Allocation failed");
+        assert_eq!(reused.data[999], 7, "Blocks are reused, not remapped");
+        assert_eq!(pool.bytes_in_use(), 1000);
+
+        assert!(pool.allocate(0).is_none(), "Empty mappings are rejected");
+        assert_eq!(pool.blocks_in_use(), 1, "A failed mapping is not counted");
+    }
+
+    #[test]
+    fn test_allocate_variable_size_no_blocks() {
+        let mut pool = MemoryPool::new();
+        
+        // Try to allocate a block with a size range that doesn't exist in the pool
+        let block = pool.allocate_variable_size(5000, 10000);
+        assert!(block.is_none(), "Expected None, but got a block");
+    }
+
+    #[test]
+    fn test_deallocate_empty_block() {
+        let mut pool = MemoryPool::new();
+        
+        // Deallocate an empty block (which should not exist)
+        let block = MemoryBlock {
+            size: 0,
+            data: Vec::new(),
+        };
+        pool.deallocate_block(block); // Should not panic
+    }
+
+    #[test]
+    fn test_allocate_minimum_block_size() {
+        let mut pool = MemoryPool::new();
+        
+        // Allocate the smallest possible block (e.g., 1 byte)
+        let block = pool.allocate_fixed_size(1).expect("Allocation failed");
+        assert_eq!(block.size, 1);
+    }
+
+    #[test]
+    fn test_allocate_maximum_block_size() {
+        let mut pool = MemoryPool::new();
+        
+        // Allocate a large block, assuming the system can handle large allocations
+        let block = pool.allocate_fixed_size(1000000).expect("Allocation failed");
+        assert_eq!(block.size, 1000000);
+    }
+}
@@ -1,26 +1,26 @@
-use std::sync::{Arc, RwLock};
+use sync_shim::{Arc, RwLock};
 
 /// A Zero-Copy Buffer structure for managing data.
 #[derive(Debug, Clone)]
-struct ZeroCopyBuffer {
+pub struct ZeroCopyBuffer {
     data: Arc<RwLock<Vec<u8>>>,
 }
 
 impl ZeroCopyBuffer {
     /// Create a new buffer with the given data.
-    fn new(data: Vec<u8>) -> Self {
+    pub fn new(data: Vec<u8>) -> Self {
         Self {
             data: Arc::new(RwLock::new(data)),
         }
     }
 
     /// Read data from the buffer. Multiple consumers can read concurrently.
-    fn read(&self) -> Option<Vec<u8>> {
+    pub fn read(&self) -> Option<Vec<u8>> {
         self.data.read().ok().map(|guard| guard.clone())
     }
 
     /// Update the buffer's data. Only one writer is allowed at a time.
-    fn write(&self, new_data: Vec<u8>) -> Result<(), String> {
+    pub fn write(&self, new_data: Vec<u8>) -> Result<(), String> {
         self.data
             .write()
             .map(|mut guard| {
@@ -30,49 +30,7 @@ impl ZeroCopyBuffer {
     }
 }
 
-fn main() {
-    // Create a new Zero-Copy Buffer with initial data
-    let buffer = ZeroCopyBuffer::new(vec![1, 2, 3, 4, 5]);
-
-    // Multiple readers
-    let reader1 = buffer.clone();
-    let reader2 = buffer.clone();
-
-    // Spawn threads to simulate concurrent reads
-    let handle1 = std::thread::spawn(move || {
-        if let Some(data) = reader1.read() {
-            println!("Reader 1: {:?}", data);
-        } else {
-            println!("Reader 1: Failed to read data");
-        }
-    });
-
-    let handle2 = std::thread::spawn(move || {
-        if let Some(data) = reader2.read() {
-            println!("Reader 2: {:?}", data);
-        } else {
-            println!("Reader 2: Failed to read data");
-        }
-    });
-
-    // Wait for readers to finish
-    handle1.join().unwrap();
-    handle2.join().unwrap();
-
-    // Update the buffer's data
-    if let Err(err) = buffer.write(vec![6, 7, 8, 9, 10]) {
-        println!("Writer: {}", err);
-    } else {
-        println!("Writer: Updated the buffer");
-    }
-
-    // Verify updated data
-    if let Some(data) = buffer.read() {
-        println!("Main Thread: Updated Data: {:?}", data);
-    }
-}
-
-#[cfg(test)]
+#[cfg(all(test, not(loom)))]
 mod tests {
     use super::*;
 
@@ -137,3 +95,25 @@ mod tests {
         assert_eq!(buffer.read(), Some(Vec::new()));
     }
 }
+
+// Run with `RUSTFLAGS="--cfg loom" cargo test --lib --release -p task_13_buffer`
+#[cfg(all(test, loom))]
+mod loom_tests {
+    use super::*;
+    use sync_shim::thread;
+
+    #[test]
+    fn loom_reader_sees_whole_writes_only() {
+        sync_shim::model(|| {
+            let buffer = ZeroCopyBuffer::new(vec![1, 1]);
+            let writer = thread::spawn({
+                let buffer = buffer.clone();
+                move || buffer.write(vec![2, 2, 2]).unwrap()
+            });
+            let seen = buffer.read().unwrap();
+            assert!(seen == [1, 1] || seen == [2, 2, 2], "torn read: {seen:?}");
+            writer.join().unwrap();
+            assert_eq!(buffer.read(), Some(vec![2, 2, 2]));
+        });
+    }
+}
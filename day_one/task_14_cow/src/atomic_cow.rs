@@ -0,0 +1,223 @@
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use sync_shim::atomic::{AtomicPtr, AtomicUsize, Ordering};
+use sync_shim::hint;
+use sync_shim::Mutex;
+
+/// A lock-free Copy-on-Write cell.
+///
+/// Readers perform a single atomic load of the current `Arc<T>` pointer and never
+/// block. Writers clone the current snapshot, modify the clone and publish it with
+/// `compare_exchange`, retrying if another writer got there first.
+///
+/// Old snapshots are reclaimed with a small two-phase grace period: every reader
+/// registers itself in one of two counters while it bumps the reference count, and
+/// a writer only releases the replaced `Arc` once both counters have drained.
+pub struct AtomicCow<T> {
+    ptr: AtomicPtr<T>,
+    epoch: AtomicUsize,
+    readers: [AtomicUsize; 2],
+    reclaim: Mutex<()>,
+    _marker: PhantomData<Arc<T>>,
+}
+
+impl<T> AtomicCow<T> {
+    /// Create a new AtomicCow instance.
+    pub fn new(data: T) -> Self {
+        Self {
+            ptr: AtomicPtr::new(Arc::into_raw(Arc::new(data)) as *mut T),
+            epoch: AtomicUsize::new(0),
+            readers: [AtomicUsize::new(0), AtomicUsize::new(0)],
+            reclaim: Mutex::new(()),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Read the current data. Wait-free: a fixed number of atomic operations.
+    pub fn load(&self) -> Arc<T> {
+        let slot = self.epoch.load(Ordering::SeqCst) & 1;
+        self.readers[slot].fetch_add(1, Ordering::SeqCst);
+        let raw = self.ptr.load(Ordering::SeqCst);
+        // SAFETY: `raw` came from `Arc::into_raw` and cannot be released while we are
+        // registered in `readers[slot]`, see `retire`.
+        let snapshot = unsafe {
+            Arc::increment_strong_count(raw);
+            Arc::from_raw(raw)
+        };
+        self.readers[slot].fetch_sub(1, Ordering::SeqCst);
+        snapshot
+    }
+
+    /// Publish new data unconditionally, returning the previous snapshot.
+    pub fn store(&self, data: T) -> Arc<T> {
+        let new = Arc::into_raw(Arc::new(data)) as *mut T;
+        let old = self.ptr.swap(new, Ordering::SeqCst);
+        self.retire(old)
+    }
+
+    /// Clone the current data, modify it and publish the result.
+    ///
+    /// If another writer publishes in the meantime the closure is re-run against the
+    /// newer snapshot, so it may be called more than once.
+    pub fn update(&self, mut modify_fn: impl FnMut(&mut T)) -> Arc<T>
+    where
+        T: Clone,
+    {
+        loop {
+            let current = self.load();
+            let mut data = (*current).clone();
+            modify_fn(&mut data);
+
+            let expected = Arc::as_ptr(&current) as *mut T;
+            let new = Arc::into_raw(Arc::new(data)) as *mut T;
+            match self
+                .ptr
+                .compare_exchange(expected, new, Ordering::SeqCst, Ordering::SeqCst)
+            {
+                Ok(old) => {
+                    drop(current);
+                    return self.retire(old);
+                }
+                Err(_) => {
+                    // SAFETY: `new` was never published, we still own it.
+                    drop(unsafe { Arc::from_raw(new) });
+                }
+            }
+        }
+    }
+
+    /// Wait until no reader can still be dereferencing `old`, then hand back the
+    /// reference the cell owned.
+    fn retire(&self, old: *mut T) -> Arc<T> {
+        let _guard = self.reclaim.lock().unwrap_or_else(|e| e.into_inner());
+        for _ in 0..2 {
+            let previous = self.epoch.fetch_add(1, Ordering::SeqCst) & 1;
+            while self.readers[previous].load(Ordering::SeqCst) != 0 {
+                hint::spin_loop();
+            }
+        }
+        // SAFETY: `old` was unlinked before the grace period, so no reader can observe it.
+        unsafe { Arc::from_raw(old) }
+    }
+}
+
+impl<T> Drop for AtomicCow<T> {
+    fn drop(&mut self) {
+        // `&mut self` rules out other accesses, so a plain load is enough (loom's
+        // atomics have no `get_mut`)
+        let raw = self.ptr.load(Ordering::Relaxed);
+        // SAFETY: `&mut self` guarantees there are no concurrent readers.
+        drop(unsafe { Arc::from_raw(raw) });
+    }
+}
+
+unsafe impl<T: Send + Sync> Send for AtomicCow<T> {}
+unsafe impl<T: Send + Sync> Sync for AtomicCow<T> {}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn test_happy_path_load_and_update() {
+        let cow = AtomicCow::new(vec![1, 2, 3]);
+        assert_eq!(*cow.load(), vec![1, 2, 3]);
+
+        let old = cow.update(|data| data.push(4));
+        assert_eq!(*old, vec![1, 2, 3]);
+        assert_eq!(*cow.load(), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_store_returns_previous_snapshot() {
+        let cow = AtomicCow::new(String::from("old"));
+        let previous = cow.store(String::from("new"));
+        assert_eq!(*previous, "old");
+        assert_eq!(*cow.load(), "new");
+    }
+
+    #[test]
+    fn test_readers_keep_their_snapshot() {
+        let cow = AtomicCow::new(vec![1]);
+        let snapshot = cow.load();
+        cow.update(|data| data.push(2));
+
+        assert_eq!(*snapshot, vec![1]);
+        assert_eq!(*cow.load(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_concurrent_updates_are_not_lost() {
+        let cow = Arc::new(AtomicCow::new(0usize));
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let cow = Arc::clone(&cow);
+                thread::spawn(move || {
+                    for _ in 0..500 {
+                        cow.update(|value| *value += 1);
+                        assert!(*cow.load() > 0);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(*cow.load(), 8 * 500);
+    }
+
+    #[test]
+    fn test_edge_case_snapshots_are_released() {
+        let cow = AtomicCow::new(vec![0; 16]);
+        let first = cow.load();
+        cow.store(vec![1; 16]);
+        cow.store(vec![2; 16]);
+
+        // Only our own handle keeps the first snapshot alive now.
+        assert_eq!(Arc::strong_count(&first), 1);
+        assert_eq!(Arc::strong_count(&cow.load()), 2);
+    }
+}
+
+// Run with `RUSTFLAGS="--cfg loom" cargo test --lib --release -p task_14_cow`
+#[cfg(all(test, loom))]
+mod loom_tests {
+    use super::*;
+    use sync_shim::thread;
+
+    #[test]
+    fn loom_load_races_update() {
+        // The grace period in `retire` must not hand back the old snapshot
+        // while the reader is still between its pointer load and refcount bump
+        sync_shim::model(|| {
+            let cow = sync_shim::Arc::new(AtomicCow::new(1));
+            let reader = thread::spawn({
+                let cow = sync_shim::Arc::clone(&cow);
+                move || *cow.load()
+            });
+            let old = cow.update(|value| *value += 1);
+            assert_eq!(*old, 1);
+            let seen = reader.join().unwrap();
+            assert!(seen == 1 || seen == 2);
+            assert_eq!(*cow.load(), 2);
+        });
+    }
+
+    #[test]
+    fn loom_concurrent_updates_both_apply() {
+        sync_shim::model(|| {
+            let cow = sync_shim::Arc::new(AtomicCow::new(0));
+            let other = thread::spawn({
+                let cow = sync_shim::Arc::clone(&cow);
+                move || {
+                    cow.update(|value| *value += 1);
+                }
+            });
+            cow.update(|value| *value += 10);
+            other.join().unwrap();
+            assert_eq!(*cow.load(), 11);
+        });
+    }
+}
@@ -0,0 +1,257 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::sync::Arc;
+
+/// Bits of the hash consumed at each level of the trie.
+const BITS: u32 = 4;
+const WIDTH: usize = 1 << BITS;
+const MASK: u64 = (WIDTH - 1) as u64;
+
+#[derive(Debug, Clone)]
+enum Node<K, V> {
+    Branch(Vec<Option<Arc<Node<K, V>>>>),
+    /// All entries of a leaf share the same full hash.
+    Leaf(u64, Vec<(K, V)>),
+}
+
+/// A hash-array-mapped-trie style map with structural sharing.
+///
+/// Each level of the trie consumes four bits of the key's hash. A write path-copies
+/// only the nodes from the root down to the touched leaf, so a clone of a large map
+/// (as done by `CopyOnWrite::write` while readers hold a snapshot) costs O(depth)
+/// instead of O(n).
+#[derive(Debug, Clone)]
+pub struct CowMap<K, V> {
+    root: Arc<Node<K, V>>,
+    len: usize,
+    hasher: RandomState,
+}
+
+impl<K, V> CowMap<K, V>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+{
+    /// Create an empty CowMap.
+    pub fn new() -> Self {
+        Self {
+            root: Arc::new(Node::Branch(vec![None; WIDTH])),
+            len: 0,
+            hasher: RandomState::new(),
+        }
+    }
+
+    /// Number of entries in the map.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check if the map is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get a reference to the value stored for `key`.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let hash = self.hasher.hash_one(key);
+        let mut node = &self.root;
+        let mut shift = 0;
+        loop {
+            match node.as_ref() {
+                Node::Branch(children) => {
+                    node = children[((hash >> shift) & MASK) as usize].as_ref()?;
+                    shift += BITS;
+                }
+                Node::Leaf(leaf_hash, entries) => {
+                    if *leaf_hash != hash {
+                        return None;
+                    }
+                    return entries.iter().find(|(k, _)| k == key).map(|(_, v)| v);
+                }
+            }
+        }
+    }
+
+    /// Check whether the map contains `key`.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Insert a key-value pair, returning the previous value if any.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let hash = self.hasher.hash_one(&key);
+        let previous = Self::insert_at(&mut self.root, 0, hash, key, value);
+        if previous.is_none() {
+            self.len += 1;
+        }
+        previous
+    }
+
+    /// Remove `key` from the map, returning its value if it was present.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        if !self.contains_key(key) {
+            // Avoid path-copying shared nodes for a no-op.
+            return None;
+        }
+        let hash = self.hasher.hash_one(key);
+        let removed = Self::remove_at(&mut self.root, 0, hash, key);
+        if removed.is_some() {
+            self.len -= 1;
+        }
+        removed
+    }
+
+    /// Iterate over all entries in unspecified order.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            stack: vec![&self.root],
+            current: [].iter(),
+        }
+    }
+
+    fn insert_at(node: &mut Arc<Node<K, V>>, shift: u32, hash: u64, key: K, value: V) -> Option<V> {
+        match Arc::make_mut(node) {
+            Node::Branch(children) => {
+                let slot = &mut children[((hash >> shift) & MASK) as usize];
+                match slot {
+                    None => {
+                        *slot = Some(Arc::new(Node::Leaf(hash, vec![(key, value)])));
+                        None
+                    }
+                    Some(child) => {
+                        if let Node::Leaf(leaf_hash, _) = child.as_ref() {
+                            if *leaf_hash != hash {
+                                // Split the leaf into a branch one level deeper.
+                                let index = ((leaf_hash >> (shift + BITS)) & MASK) as usize;
+                                let mut children = vec![None; WIDTH];
+                                children[index] = Some(Arc::clone(child));
+                                *child = Arc::new(Node::Branch(children));
+                            }
+                        }
+                        Self::insert_at(child, shift + BITS, hash, key, value)
+                    }
+                }
+            }
+            Node::Leaf(_, entries) => match entries.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, v)) => Some(std::mem::replace(v, value)),
+                None => {
+                    entries.push((key, value));
+                    None
+                }
+            },
+        }
+    }
+
+    fn remove_at(node: &mut Arc<Node<K, V>>, shift: u32, hash: u64, key: &K) -> Option<V> {
+        match Arc::make_mut(node) {
+            Node::Branch(children) => {
+                let slot = &mut children[((hash >> shift) & MASK) as usize];
+                let child = slot.as_mut()?;
+                let removed = Self::remove_at(child, shift + BITS, hash, key);
+                if let Node::Leaf(_, entries) = child.as_ref() {
+                    if entries.is_empty() {
+                        *slot = None;
+                    }
+                }
+                removed
+            }
+            Node::Leaf(_, entries) => {
+                let index = entries.iter().position(|(k, _)| k == key)?;
+                Some(entries.swap_remove(index).1)
+            }
+        }
+    }
+}
+
+impl<K, V> Default for CowMap<K, V>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Iterator over the entries of a `CowMap`.
+pub struct Iter<'a, K, V> {
+    stack: Vec<&'a Arc<Node<K, V>>>,
+    current: std::slice::Iter<'a, (K, V)>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((k, v)) = self.current.next() {
+                return Some((k, v));
+            }
+            match self.stack.pop()?.as_ref() {
+                Node::Branch(children) => self.stack.extend(children.iter().flatten()),
+                Node::Leaf(_, entries) => self.current = entries.iter(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_happy_path_insert_get_remove() {
+        let mut map = CowMap::new();
+        assert_eq!(map.insert("a", 1), None);
+        assert_eq!(map.insert("b", 2), None);
+        assert_eq!(map.insert("a", 3), Some(1));
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(&"a"), Some(&3));
+        assert!(map.contains_key(&"b"));
+
+        assert_eq!(map.remove(&"a"), Some(3));
+        assert_eq!(map.get(&"a"), None);
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn test_clone_is_isolated_from_writes() {
+        let mut original = CowMap::new();
+        for i in 0..1000 {
+            original.insert(i, i * 2);
+        }
+        let mut copy = original.clone();
+        copy.insert(5, -1);
+        copy.remove(&6);
+
+        assert_eq!(original.get(&5), Some(&10));
+        assert_eq!(original.get(&6), Some(&12));
+        assert_eq!(copy.get(&5), Some(&-1));
+        assert_eq!(copy.get(&6), None);
+        assert_eq!(original.len(), 1000);
+        assert_eq!(copy.len(), 999);
+    }
+
+    #[test]
+    fn test_iter_visits_every_entry() {
+        let map: CowMap<i32, i32> = {
+            let mut map = CowMap::new();
+            for i in 0..300 {
+                map.insert(i, i);
+            }
+            map
+        };
+        let mut keys: Vec<_> = map.iter().map(|(k, _)| *k).collect();
+        keys.sort();
+        assert_eq!(keys, (0..300).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_unhappy_path_remove_missing_key() {
+        let mut map: CowMap<i32, i32> = CowMap::default();
+        assert!(map.is_empty());
+        assert_eq!(map.remove(&1), None);
+        assert_eq!(map.get(&1), None);
+    }
+}
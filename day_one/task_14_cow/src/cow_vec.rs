@@ -0,0 +1,164 @@
+use std::sync::Arc;
+
+/// Number of elements stored in each shared chunk.
+const CHUNK_SIZE: usize = 64;
+
+/// A vector split into reference-counted chunks.
+///
+/// Cloning a `CowVec` only clones the chunk pointers. A write clones just the chunk
+/// it touches (via `Arc::make_mut`), so wrapping a large `CowVec` in `CopyOnWrite`
+/// no longer deep-copies the whole collection on the first write after a read.
+#[derive(Debug, Clone)]
+pub struct CowVec<T> {
+    chunks: Vec<Arc<Vec<T>>>,
+    len: usize,
+}
+
+impl<T: Clone> CowVec<T> {
+    /// Create an empty CowVec.
+    pub fn new() -> Self {
+        Self {
+            chunks: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Number of elements in the vector.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check if the vector is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get a reference to the element at `index`.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len {
+            return None;
+        }
+        self.chunks[index / CHUNK_SIZE].get(index % CHUNK_SIZE)
+    }
+
+    /// Get a mutable reference to the element at `index`, cloning its chunk if shared.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len {
+            return None;
+        }
+        Arc::make_mut(&mut self.chunks[index / CHUNK_SIZE]).get_mut(index % CHUNK_SIZE)
+    }
+
+    /// Replace the element at `index`, returning the old value.
+    pub fn set(&mut self, index: usize, value: T) -> Option<T> {
+        self.get_mut(index).map(|slot| std::mem::replace(slot, value))
+    }
+
+    /// Append an element, cloning only the last chunk if it is shared.
+    pub fn push(&mut self, value: T) {
+        match self.chunks.last_mut() {
+            Some(chunk) if chunk.len() < CHUNK_SIZE => Arc::make_mut(chunk).push(value),
+            _ => {
+                let mut chunk = Vec::with_capacity(CHUNK_SIZE);
+                chunk.push(value);
+                self.chunks.push(Arc::new(chunk));
+            }
+        }
+        self.len += 1;
+    }
+
+    /// Remove and return the last element.
+    pub fn pop(&mut self) -> Option<T> {
+        let chunk = self.chunks.last_mut()?;
+        let value = Arc::make_mut(chunk).pop();
+        if chunk.is_empty() {
+            self.chunks.pop();
+        }
+        self.len -= 1;
+        value
+    }
+
+    /// Iterate over the elements in order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.chunks.iter().flat_map(|chunk| chunk.iter())
+    }
+
+    /// Number of chunks physically shared with `other`.
+    pub fn shared_chunks(&self, other: &Self) -> usize {
+        self.chunks
+            .iter()
+            .zip(other.chunks.iter())
+            .filter(|(a, b)| Arc::ptr_eq(a, b))
+            .count()
+    }
+}
+
+impl<T: Clone> Default for CowVec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> FromIterator<T> for CowVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut vec = CowVec::new();
+        for value in iter {
+            vec.push(value);
+        }
+        vec
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_happy_path_push_get_pop() {
+        let mut vec = CowVec::new();
+        for i in 0..200 {
+            vec.push(i);
+        }
+        assert_eq!(vec.len(), 200);
+        assert_eq!(vec.get(0), Some(&0));
+        assert_eq!(vec.get(199), Some(&199));
+        assert_eq!(vec.pop(), Some(199));
+        assert_eq!(vec.len(), 199);
+        assert_eq!(vec.iter().copied().collect::<Vec<_>>(), (0..199).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_write_only_clones_touched_chunk() {
+        let original: CowVec<i32> = (0..1000).collect();
+        let mut copy = original.clone();
+        assert_eq!(copy.shared_chunks(&original), 16);
+
+        copy.set(500, -1);
+        assert_eq!(copy.shared_chunks(&original), 15);
+        assert_eq!(original.get(500), Some(&500));
+        assert_eq!(copy.get(500), Some(&-1));
+    }
+
+    #[test]
+    fn test_unhappy_path_out_of_bounds() {
+        let mut vec: CowVec<i32> = (0..10).collect();
+        assert_eq!(vec.get(10), None);
+        assert_eq!(vec.set(10, 1), None);
+        assert!(vec.get_mut(42).is_none());
+    }
+
+    #[test]
+    fn test_edge_case_empty_and_chunk_boundary() {
+        let mut vec: CowVec<i32> = CowVec::default();
+        assert!(vec.is_empty());
+        assert_eq!(vec.pop(), None);
+
+        for i in 0..CHUNK_SIZE as i32 {
+            vec.push(i);
+        }
+        vec.push(64);
+        assert_eq!(vec.pop(), Some(64));
+        assert_eq!(vec.pop(), Some(63));
+        assert_eq!(vec.len(), CHUNK_SIZE - 1);
+    }
+}
@@ -0,0 +1,469 @@
+#![cfg_attr(feature = "safe-only", forbid(unsafe_code))]
+
+use std::fmt;
+use std::sync::mpsc::{self, Receiver, Sender};
+// Snapshots are handed to callers, so they stay `std::sync::Arc` (which also
+// has the `make_mut` the writes rely on); only the locks and counters are shimmed
+use std::sync::Arc;
+
+use sync_shim::atomic::{AtomicUsize, Ordering};
+use sync_shim::{Mutex, RwLock};
+
+#[cfg(feature = "async")]
+pub mod async_cow;
+// Built on raw `Arc` pointers, so `safe-only` leaves it out
+#[cfg(not(feature = "safe-only"))]
+pub mod atomic_cow;
+pub mod cow_map;
+pub mod cow_vec;
+
+/// Counters describing how a `CopyOnWrite` instance has been used.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CowStats {
+    /// Number of `read` calls.
+    pub reads: usize,
+    /// Number of published writes (`write` calls and committed transactions).
+    pub writes: usize,
+    /// Writes that had to deep-clone the data because a reader still held a snapshot.
+    pub deep_clones: usize,
+    /// Writes that mutated the data in place through `Arc::make_mut`.
+    pub in_place_writes: usize,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    reads: AtomicUsize,
+    writes: AtomicUsize,
+    deep_clones: AtomicUsize,
+}
+
+#[derive(Debug, Clone)]
+pub struct CopyOnWrite<T>
+where
+    T: Clone,
+{
+    inner: Arc<RwLock<Arc<T>>>,
+    subscribers: Arc<Mutex<Vec<Sender<Arc<T>>>>>,
+    counters: Arc<Counters>,
+}
+
+impl<T> CopyOnWrite<T>
+where
+    T: Clone,
+{
+    /// Create a new CopyOnWrite instance.
+    pub fn new(data: T) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(Arc::new(data))),
+            subscribers: Arc::new(Mutex::new(Vec::new())),
+            counters: Arc::new(Counters::default()),
+        }
+    }
+
+    /// Read the current data.
+    pub fn read(&self) -> Arc<T> {
+        self.counters.reads.fetch_add(1, Ordering::Relaxed);
+        self.inner.read().unwrap().clone()
+    }
+
+    /// Write new data (cloning only if necessary).
+    pub fn write(&self, modify_fn: impl FnOnce(&mut T)) {
+        let mut lock = self.inner.write().unwrap();
+        // `writes` first, like `transaction`, so the deep clones never run ahead of it
+        self.counters.writes.fetch_add(1, Ordering::Relaxed);
+        if Arc::strong_count(&lock) > 1 {
+            self.counters.deep_clones.fetch_add(1, Ordering::Relaxed);
+        }
+        let mut_data = Arc::make_mut(&mut lock);
+        modify_fn(mut_data);
+        self.notify(&lock);
+    }
+
+    /// Apply several mutations as one atomic update.
+    ///
+    /// The closure works on a private copy of the data. The copy is only published if
+    /// the closure returns `Ok`; on `Err` it is discarded and readers never observe any
+    /// of the intermediate states.
+    pub fn transaction<E>(&self, modify_fn: impl FnOnce(&mut T) -> Result<(), E>) -> Result<(), E> {
+        let mut lock = self.inner.write().unwrap();
+        let mut draft = T::clone(&lock);
+        modify_fn(&mut draft)?;
+        *lock = Arc::new(draft);
+        self.counters.writes.fetch_add(1, Ordering::Relaxed);
+        self.counters.deep_clones.fetch_add(1, Ordering::Relaxed);
+        self.notify(&lock);
+        Ok(())
+    }
+
+    /// Report how many reads and writes happened and how many writes needed a deep
+    /// clone versus an in-place mutation. Shared by all clones of this handle.
+    pub fn stats(&self) -> CowStats {
+        let writes = self.counters.writes.load(Ordering::Relaxed);
+        let deep_clones = self.counters.deep_clones.load(Ordering::Relaxed);
+        CowStats {
+            reads: self.counters.reads.load(Ordering::Relaxed),
+            writes,
+            deep_clones,
+            // The counters are read one at a time, so a write landing between the two loads
+            // can leave `deep_clones` one ahead of `writes`
+            in_place_writes: writes.saturating_sub(deep_clones),
+        }
+    }
+
+    /// Subscribe to changes. The receiver gets the new snapshot after every successful
+    /// write or committed transaction, so consumers don't have to poll `read()`.
+    pub fn subscribe(&self) -> Receiver<Arc<T>> {
+        let (sender, receiver) = mpsc::channel();
+        self.subscribers.lock().unwrap().push(sender);
+        receiver
+    }
+
+    /// Deliver a snapshot to all subscribers, forgetting the ones that hung up.
+    /// Called with the write lock held so every subscriber sees writes in order.
+    fn notify(&self, snapshot: &Arc<T>) {
+        let mut subscribers = self.subscribers.lock().unwrap();
+        if subscribers.is_empty() {
+            return;
+        }
+        subscribers.retain(|sender| sender.send(Arc::clone(snapshot)).is_ok());
+    }
+}
+
+impl<T> PartialEq for CopyOnWrite<T>
+where
+    T: Clone + PartialEq,
+{
+    /// Compare the currently published snapshots.
+    fn eq(&self, other: &Self) -> bool {
+        // Take each snapshot separately so we never hold two read locks at once.
+        let left = self.inner.read().unwrap().clone();
+        let right = other.inner.read().unwrap().clone();
+        left == right
+    }
+}
+
+impl<T> Eq for CopyOnWrite<T> where T: Clone + Eq {}
+
+impl<T> Default for CopyOnWrite<T>
+where
+    T: Clone + Default,
+{
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> From<T> for CopyOnWrite<T>
+where
+    T: Clone,
+{
+    fn from(data: T) -> Self {
+        Self::new(data)
+    }
+}
+
+impl<T> fmt::Display for CopyOnWrite<T>
+where
+    T: Clone + fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let snapshot = self.inner.read().unwrap().clone();
+        write!(f, "{}", snapshot)
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_happy_path_read() {
+        let cow = CopyOnWrite::new(vec![1, 2, 3]);
+        let data = cow.read();
+        assert_eq!(*data, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_happy_path_write() {
+        let cow = CopyOnWrite::new(vec![1, 2, 3]);
+        cow.write(|data| {
+            data.push(4);
+        });
+        let data = cow.read();
+        assert_eq!(*data, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_unhappy_path_write_with_failed_lock() {
+        let cow = CopyOnWrite::new(vec![1, 2, 3]);
+    
+        // Simulate a lock poisoning scenario
+        let poisoned_lock = cow.inner.clone();
+        std::thread::spawn(move || {
+            drop(poisoned_lock.write().unwrap()); // Explicitly drop the lock
+            panic!("Simulated lock failure");
+        })
+        .join()
+        .unwrap_err();
+    
+        // Verify the lock is functional again
+        cow.write(|data| {
+            data.push(4);
+        });
+        let data = cow.read();
+        assert_eq!(*data, vec![1, 2, 3, 4]);
+    }    
+
+    #[test]
+    fn test_happy_path_transaction_commits_all_changes() {
+        let cow = CopyOnWrite::new(vec![1, 2, 3]);
+        let result: Result<(), ()> = cow.transaction(|data| {
+            data.push(4);
+            data.push(5);
+            Ok(())
+        });
+        assert!(result.is_ok());
+        assert_eq!(*cow.read(), vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_unhappy_path_transaction_rolls_back_on_error() {
+        let cow = CopyOnWrite::new(vec![1, 2, 3]);
+        let snapshot = cow.read();
+        let result = cow.transaction(|data| {
+            data.push(4);
+            data.retain(|x| *x > 10);
+            Err("rejected")
+        });
+        assert_eq!(result, Err("rejected"));
+        assert_eq!(*cow.read(), vec![1, 2, 3]);
+        assert!(Arc::ptr_eq(&snapshot, &cow.read()));
+    }
+
+    #[test]
+    fn test_transaction_hides_intermediate_states() {
+        let cow = CopyOnWrite::new(Vec::new());
+        let reader = cow.clone();
+        let handle = std::thread::spawn(move || {
+            for _ in 0..1000 {
+                // Every transaction pushes a pair, so readers never see an odd length.
+                assert_eq!(reader.read().len() % 2, 0);
+            }
+        });
+
+        for i in 0..1000 {
+            let result: Result<(), ()> = cow.transaction(|data| {
+                data.push(i);
+                data.push(i);
+                Ok(())
+            });
+            assert!(result.is_ok());
+        }
+        handle.join().unwrap();
+        assert_eq!(cow.read().len(), 2000);
+    }
+
+    #[test]
+    fn test_happy_path_subscribe_receives_writes() {
+        let cow = CopyOnWrite::new(vec![1]);
+        let updates = cow.subscribe();
+
+        cow.write(|data| data.push(2));
+        let _: Result<(), ()> = cow.transaction(|data| {
+            data.push(3);
+            Ok(())
+        });
+
+        assert_eq!(*updates.try_recv().unwrap(), vec![1, 2]);
+        assert_eq!(*updates.try_recv().unwrap(), vec![1, 2, 3]);
+        assert!(updates.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_subscribe_skips_rolled_back_transactions() {
+        let cow = CopyOnWrite::new(0);
+        let updates = cow.subscribe();
+        let _ = cow.transaction(|data| {
+            *data = 1;
+            Err(())
+        });
+        assert!(updates.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_edge_case_dropped_subscriber_is_removed() {
+        let cow = CopyOnWrite::new(0);
+        let kept = cow.subscribe();
+        drop(cow.subscribe());
+
+        cow.write(|data| *data += 1);
+        assert_eq!(cow.subscribers.lock().unwrap().len(), 1);
+        assert_eq!(*kept.recv().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_subscribe_across_threads() {
+        let cow = CopyOnWrite::new(String::from("v0"));
+        let updates = cow.subscribe();
+        let writer = cow.clone();
+        std::thread::spawn(move || {
+            writer.write(|config| *config = String::from("v1"));
+        })
+        .join()
+        .unwrap();
+        assert_eq!(*updates.recv().unwrap(), "v1");
+    }
+
+    #[test]
+    fn test_stats_count_in_place_writes_and_deep_clones() {
+        let cow = CopyOnWrite::new(vec![1]);
+        cow.write(|data| data.push(2));
+
+        let snapshot = cow.read();
+        cow.write(|data| data.push(3));
+        drop(snapshot);
+        cow.write(|data| data.push(4));
+
+        assert_eq!(
+            cow.stats(),
+            CowStats {
+                reads: 1,
+                writes: 3,
+                deep_clones: 1,
+                in_place_writes: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn test_stats_never_underflow_while_writers_run() {
+        // Every write here is a transaction, so each one bumps both counters
+        let cow = CopyOnWrite::new(0u64);
+        let writer = cow.clone();
+        let handle = std::thread::spawn(move || {
+            for _ in 0..20_000 {
+                let _: Result<(), ()> = writer.transaction(|data| {
+                    *data += 1;
+                    Ok(())
+                });
+            }
+        });
+        while !handle.is_finished() {
+            assert!(cow.stats().in_place_writes <= 1);
+        }
+        handle.join().unwrap();
+        assert_eq!(cow.stats().in_place_writes, 0);
+    }
+
+    #[test]
+    fn test_stats_count_committed_transactions_only() {
+        let cow = CopyOnWrite::new(0);
+        let _: Result<(), ()> = cow.transaction(|data| {
+            *data = 1;
+            Ok(())
+        });
+        let _ = cow.transaction(|_| Err(()));
+
+        let stats = cow.stats();
+        assert_eq!(stats.writes, 1);
+        assert_eq!(stats.deep_clones, 1);
+    }
+
+    #[test]
+    fn test_edge_case_stats_shared_between_clones() {
+        let cow = CopyOnWrite::new(0);
+        let other = cow.clone();
+        assert_eq!(cow.stats(), CowStats::default());
+
+        other.write(|data| *data += 1);
+        cow.read();
+        assert_eq!(cow.stats(), other.stats());
+        assert_eq!(cow.stats().writes, 1);
+    }
+
+    #[test]
+    fn test_partial_eq_compares_snapshots() {
+        let a = CopyOnWrite::new(vec![1, 2]);
+        let b = CopyOnWrite::new(vec![1, 2]);
+        assert_eq!(a, b);
+
+        b.write(|data| data.push(3));
+        assert_ne!(a, b);
+
+        // A handle always equals its clones, even though they share the same lock.
+        let c = a.clone();
+        assert_eq!(a, c);
+    }
+
+    #[test]
+    fn test_default_and_from() {
+        let default: CopyOnWrite<Vec<i32>> = CopyOnWrite::default();
+        assert!(default.read().is_empty());
+
+        let from: CopyOnWrite<i32> = 42.into();
+        assert_eq!(*from.read(), 42);
+        assert_eq!(CopyOnWrite::from(42), from);
+    }
+
+    #[test]
+    fn test_display_shows_current_snapshot() {
+        let cow = CopyOnWrite::new(String::from("v1"));
+        assert_eq!(cow.to_string(), "v1");
+        cow.write(|data| *data = String::from("v2"));
+        assert_eq!(format!("config: {}", cow), "config: v2");
+    }
+
+    #[test]
+    fn test_edge_case_empty_data() {
+        let cow = CopyOnWrite::new(Vec::<i32>::new());
+        assert!(cow.read().is_empty());
+
+        cow.write(|data| {
+            data.push(42);
+        });
+        let data = cow.read();
+        assert_eq!(*data, vec![42]);
+    }
+
+    #[test]
+    fn test_edge_case_large_data() {
+        let large_data: Vec<i32> = (0..10_000).collect();
+        let cow = CopyOnWrite::new(large_data.clone());
+        assert_eq!(*cow.read(), large_data);
+
+        cow.write(|data| {
+            data.push(10_001);
+        });
+        let mut expected = large_data.clone();
+        expected.push(10_001);
+        assert_eq!(*cow.read(), expected);
+    }
+}
+
+// Run with `RUSTFLAGS="--cfg loom" cargo test --lib --release -p task_14_cow`
+#[cfg(all(test, loom))]
+mod loom_tests {
+    use super::*;
+    use sync_shim::thread;
+
+    #[test]
+    fn loom_reader_never_sees_a_half_applied_write() {
+        sync_shim::model(|| {
+            let cow = CopyOnWrite::new(vec![0, 0]);
+            let writer = thread::spawn({
+                let cow = cow.clone();
+                move || {
+                    cow.write(|data| {
+                        data[0] = 1;
+                        data[1] = 1;
+                    })
+                }
+            });
+            let snapshot = cow.read();
+            assert!(*snapshot == [0, 0] || *snapshot == [1, 1]);
+            writer.join().unwrap();
+            assert_eq!(*cow.read(), [1, 1]);
+            assert_eq!(cow.stats().writes, 1);
+        });
+    }
+}
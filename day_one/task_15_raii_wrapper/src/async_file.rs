@@ -0,0 +1,159 @@
+use std::io;
+use std::path::Path;
+use tokio::fs::{File, OpenOptions};
+use tokio::io::{AsyncWriteExt, BufWriter};
+use tokio::runtime::Handle;
+
+use crate::FlushPolicy;
+
+/// Async counterpart of `FileWrapper` built on `tokio::fs`
+///
+/// `Drop` cannot `.await`, so the reliable way to release the file is
+/// `close().await`, which flushes, syncs and reports errors. If the wrapper is
+/// dropped with buffered data inside a tokio runtime, the flush is handed to a
+/// background task; that task is lost if the runtime shuts down first. Outside
+/// a runtime the buffered data is discarded with a warning.
+pub struct AsyncFileWrapper {
+    file: Option<BufWriter<File>>,
+    flush_policy: FlushPolicy,
+}
+
+impl AsyncFileWrapper {
+    /// Create or truncate the file at `path`, flushing only on close
+    pub async fn new(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::with_policy(path, FlushPolicy::OnDrop).await
+    }
+
+    /// Create or truncate the file at `path` with the given flush policy
+    pub async fn with_policy(path: impl AsRef<Path>, flush_policy: FlushPolicy) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)
+            .await?;
+        Ok(Self {
+            file: Some(BufWriter::new(file)),
+            flush_policy,
+        })
+    }
+
+    /// Write data to the file, flushing according to the flush policy
+    pub async fn write(&mut self, data: &str) -> io::Result<()> {
+        let flush_policy = self.flush_policy;
+        let file = self.file_mut()?;
+        file.write_all(data.as_bytes()).await?;
+        match flush_policy {
+            FlushPolicy::EveryWrite => file.flush().await,
+            FlushPolicy::Threshold(limit) if file.buffer().len() >= limit => file.flush().await,
+            _ => Ok(()),
+        }
+    }
+
+    /// Push all buffered data to the file
+    pub async fn flush(&mut self) -> io::Result<()> {
+        self.file_mut()?.flush().await
+    }
+
+    /// Flush, sync and close the file, returning any error
+    pub async fn close(mut self) -> io::Result<()> {
+        if let Some(mut file) = self.file.take() {
+            file.flush().await?;
+            file.get_ref().sync_all().await?;
+        }
+        Ok(())
+    }
+
+    fn file_mut(&mut self) -> io::Result<&mut BufWriter<File>> {
+        self.file
+            .as_mut()
+            .ok_or_else(|| io::Error::other("File is not available"))
+    }
+}
+
+impl Drop for AsyncFileWrapper {
+    /// Best-effort cleanup: flush in a background task if there is anything to flush
+    fn drop(&mut self) {
+        let Some(mut file) = self.file.take() else {
+            return;
+        };
+        if file.buffer().is_empty() {
+            return;
+        }
+        match Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn(async move {
+                    if let Err(e) = file.flush().await {
+                        eprintln!("Error flushing file in background: {}", e);
+                    }
+                });
+            }
+            Err(_) => eprintln!(
+                "AsyncFileWrapper dropped outside a runtime, discarding {} buffered bytes",
+                file.buffer().len()
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_happy_path_write_and_close() -> io::Result<()> {
+        let test_path = "test_async_close.txt";
+        let mut file_wrapper = AsyncFileWrapper::new(test_path).await?;
+        file_wrapper.write("Hello, ").await?;
+        file_wrapper.write("async!").await?;
+        assert_eq!(tokio::fs::read_to_string(test_path).await?, "", "Still buffered");
+
+        file_wrapper.close().await?;
+        assert_eq!(tokio::fs::read_to_string(test_path).await?, "Hello, async!");
+        tokio::fs::remove_file(test_path).await
+    }
+
+    #[tokio::test]
+    async fn test_every_write_policy_flushes_immediately() -> io::Result<()> {
+        let test_path = "test_async_every_write.txt";
+        let mut file_wrapper = AsyncFileWrapper::with_policy(test_path, FlushPolicy::EveryWrite).await?;
+        file_wrapper.write("visible").await?;
+        assert_eq!(tokio::fs::read_to_string(test_path).await?, "visible");
+        file_wrapper.close().await?;
+        tokio::fs::remove_file(test_path).await
+    }
+
+    #[tokio::test]
+    async fn test_drop_flushes_in_background() -> io::Result<()> {
+        let test_path = "test_async_drop.txt";
+        {
+            let mut file_wrapper = AsyncFileWrapper::new(test_path).await?;
+            file_wrapper.write("best effort").await?;
+        }
+        // Give the background flush a chance to run on this runtime
+        let mut content = String::new();
+        for _ in 0..1000 {
+            content = tokio::fs::read_to_string(test_path).await?;
+            if !content.is_empty() {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(content, "best effort");
+        tokio::fs::remove_file(test_path).await
+    }
+
+    #[tokio::test]
+    async fn test_unhappy_path_invalid_path() {
+        let result = AsyncFileWrapper::new("/definitely/not/a/real/dir/file.txt").await;
+        assert_eq!(result.err().map(|e| e.kind()), Some(io::ErrorKind::NotFound));
+    }
+
+    #[tokio::test]
+    async fn test_edge_case_close_without_writes() -> io::Result<()> {
+        let test_path = "test_async_empty.txt";
+        AsyncFileWrapper::new(test_path).await?.close().await?;
+        assert_eq!(tokio::fs::read_to_string(test_path).await?, "");
+        tokio::fs::remove_file(test_path).await
+    }
+}
@@ -0,0 +1,180 @@
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long to sleep between attempts while waiting with a timeout
+const RETRY_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Kind of advisory lock held by a `FileLock`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockMode {
+    /// Many holders at once, excludes exclusive holders
+    Shared,
+    /// A single holder, excludes everyone else
+    Exclusive,
+}
+
+/// RAII guard holding an OS advisory lock (flock / LockFileEx) on a file
+///
+/// The lock is released when the guard goes out of scope. Advisory locks only
+/// coordinate processes that also take the lock; they don't stop plain reads or writes.
+#[derive(Debug)]
+pub struct FileLock {
+    file: File,
+    path: PathBuf,
+    mode: LockMode,
+}
+
+impl FileLock {
+    /// Block until an exclusive lock on `path` is acquired
+    pub fn exclusive(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = open_lock_file(path.as_ref())?;
+        file.lock()?;
+        Ok(Self::held(file, path, LockMode::Exclusive))
+    }
+
+    /// Block until a shared lock on `path` is acquired
+    pub fn shared(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = open_lock_file(path.as_ref())?;
+        file.lock_shared()?;
+        Ok(Self::held(file, path, LockMode::Shared))
+    }
+
+    /// Try to acquire the lock without blocking, `Ok(None)` if it is held elsewhere
+    pub fn try_lock(path: impl AsRef<Path>, mode: LockMode) -> io::Result<Option<Self>> {
+        let file = open_lock_file(path.as_ref())?;
+        let result = match mode {
+            LockMode::Shared => file.try_lock_shared(),
+            LockMode::Exclusive => file.try_lock(),
+        };
+        match result {
+            Ok(()) => Ok(Some(Self::held(file, path, mode))),
+            Err(TryLockError::WouldBlock) => Ok(None),
+            Err(TryLockError::Error(e)) => Err(e),
+        }
+    }
+
+    /// Keep trying to acquire the lock until `timeout` elapses
+    pub fn lock_timeout(path: impl AsRef<Path>, mode: LockMode, timeout: Duration) -> io::Result<Self> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(lock) = Self::try_lock(path.as_ref(), mode)? {
+                return Ok(lock);
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    format!("Timed out waiting for lock on {}", path.as_ref().display()),
+                ));
+            }
+            thread::sleep(RETRY_INTERVAL.min(deadline - now));
+        }
+    }
+
+    /// Path of the locked file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Kind of lock held
+    pub fn mode(&self) -> LockMode {
+        self.mode
+    }
+
+    fn held(file: File, path: impl AsRef<Path>, mode: LockMode) -> Self {
+        Self {
+            file,
+            path: path.as_ref().to_path_buf(),
+            mode,
+        }
+    }
+}
+
+impl Drop for FileLock {
+    /// Release the lock when the guard goes out of scope
+    fn drop(&mut self) {
+        if let Err(e) = self.file.unlock() {
+            eprintln!("Error unlocking {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+/// Open (creating if needed) the file used for locking without truncating it
+fn open_lock_file(path: &Path) -> io::Result<File> {
+    OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::temp_file::TempFile;
+
+    #[test]
+    fn test_happy_path_exclusive_lock_released_on_drop() -> io::Result<()> {
+        let temp = TempFile::new()?;
+        {
+            let lock = FileLock::exclusive(temp.path())?;
+            assert_eq!(lock.mode(), LockMode::Exclusive);
+            assert_eq!(lock.path(), temp.path());
+            assert!(FileLock::try_lock(temp.path(), LockMode::Exclusive)?.is_none());
+        }
+        assert!(FileLock::try_lock(temp.path(), LockMode::Exclusive)?.is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_shared_locks_coexist() -> io::Result<()> {
+        let temp = TempFile::new()?;
+        let first = FileLock::shared(temp.path())?;
+        let second = FileLock::try_lock(temp.path(), LockMode::Shared)?;
+        assert!(second.is_some());
+        assert!(FileLock::try_lock(temp.path(), LockMode::Exclusive)?.is_none());
+        drop(first);
+        Ok(())
+    }
+
+    #[test]
+    fn test_unhappy_path_lock_timeout() -> io::Result<()> {
+        let temp = TempFile::new()?;
+        let _held = FileLock::exclusive(temp.path())?;
+
+        let start = Instant::now();
+        let result = FileLock::lock_timeout(temp.path(), LockMode::Shared, Duration::from_millis(50));
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::TimedOut);
+        assert!(start.elapsed() >= Duration::from_millis(50));
+        Ok(())
+    }
+
+    #[test]
+    fn test_lock_timeout_waits_for_release() -> io::Result<()> {
+        let temp = TempFile::new()?;
+        let held = FileLock::exclusive(temp.path())?;
+        let releaser = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(30));
+            drop(held);
+        });
+
+        let lock = FileLock::lock_timeout(temp.path(), LockMode::Exclusive, Duration::from_secs(5))?;
+        assert_eq!(lock.mode(), LockMode::Exclusive);
+        releaser.join().unwrap();
+        Ok(())
+    }
+
+    #[test]
+    fn test_edge_case_lock_does_not_truncate() -> io::Result<()> {
+        let mut temp = TempFile::new()?;
+        temp.write("keep me")?;
+        assert_eq!(temp.read_to_string()?, "keep me");
+        let _lock = FileLock::exclusive(temp.path())?;
+        assert_eq!(std::fs::read_to_string(temp.path())?, "keep me");
+        Ok(())
+    }
+}
@@ -0,0 +1,765 @@
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+
+#[cfg(feature = "async")]
+pub mod async_file;
+pub mod file_lock;
+pub mod rotating_file;
+pub mod scope_guard;
+pub mod socket;
+pub mod temp_file;
+pub mod timer;
+
+/// Default capacity of the write buffer, same as `BufWriter`
+const DEFAULT_BUFFER_SIZE: usize = 8 * 1024;
+
+/// When buffered data is pushed from the write buffer to the file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlushPolicy {
+    /// Flush after every `write` call
+    EveryWrite,
+    /// Flush once at least this many bytes are buffered
+    Threshold(usize),
+    /// Only flush when the buffer is full, on `flush()`, or on drop
+    OnDrop,
+}
+
+/// What `Drop` does with an error it cannot return to the caller
+///
+/// Call `close()` instead of relying on drop to get the error as a `Result`.
+#[derive(Default)]
+pub enum DropErrorPolicy {
+    /// Silently discard the error
+    Ignore,
+    /// Print the error to stderr
+    #[default]
+    Log,
+    /// Panic with the error; falls back to `Log` if the thread is already
+    /// panicking, since a second panic would abort the process
+    Panic,
+    /// Hand the error to a closure
+    Callback(Box<dyn FnMut(io::Error) + Send>),
+}
+
+impl DropErrorPolicy {
+    /// Wrap a closure as a drop-error policy
+    pub fn callback(f: impl FnMut(io::Error) + Send + 'static) -> Self {
+        DropErrorPolicy::Callback(Box::new(f))
+    }
+}
+
+impl fmt::Debug for DropErrorPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DropErrorPolicy::Ignore => write!(f, "Ignore"),
+            DropErrorPolicy::Log => write!(f, "Log"),
+            DropErrorPolicy::Panic => write!(f, "Panic"),
+            DropErrorPolicy::Callback(_) => write!(f, "Callback(..)"),
+        }
+    }
+}
+
+/// RAII wrapper for managing file resources
+pub struct FileWrapper {
+    file: Option<BufWriter<File>>,
+    flush_policy: FlushPolicy,
+    readable: bool,
+    atomic: Option<AtomicTarget>,
+    drop_error_policy: DropErrorPolicy,
+}
+
+/// Where an atomic FileWrapper is writing, and what it will replace
+struct AtomicTarget {
+    temp_path: PathBuf,
+    target: PathBuf,
+}
+
+/// Builder for opening a FileWrapper in a specific mode
+///
+/// Defaults match `FileWrapper::new`: write-only, create if missing, truncate.
+#[derive(Debug, Clone)]
+pub struct FileWrapperOptions {
+    read: bool,
+    append: bool,
+    truncate: bool,
+    create_new: bool,
+    buffer_size: usize,
+    flush_policy: FlushPolicy,
+}
+
+impl FileWrapperOptions {
+    /// Also open the file for reading
+    pub fn read(&mut self, read: bool) -> &mut Self {
+        self.read = read;
+        self
+    }
+
+    /// Append to the end of the file instead of truncating it
+    pub fn append(&mut self, append: bool) -> &mut Self {
+        self.append = append;
+        self
+    }
+
+    /// Truncate an existing file to zero length (ignored in append mode)
+    pub fn truncate(&mut self, truncate: bool) -> &mut Self {
+        self.truncate = truncate;
+        self
+    }
+
+    /// Fail with `AlreadyExists` instead of opening an existing file
+    pub fn create_new(&mut self, create_new: bool) -> &mut Self {
+        self.create_new = create_new;
+        self
+    }
+
+    /// Capacity of the write buffer in bytes
+    pub fn buffer_size(&mut self, buffer_size: usize) -> &mut Self {
+        self.buffer_size = buffer_size;
+        self
+    }
+
+    /// When buffered writes reach the file
+    pub fn flush_policy(&mut self, flush_policy: FlushPolicy) -> &mut Self {
+        self.flush_policy = flush_policy;
+        self
+    }
+
+    /// Open the file with the configured mode
+    pub fn open(&self, path: impl AsRef<Path>) -> io::Result<FileWrapper> {
+        let file = OpenOptions::new()
+            .read(self.read)
+            .write(true)
+            .append(self.append)
+            .truncate(self.truncate && !self.append)
+            .create(!self.create_new)
+            .create_new(self.create_new)
+            .open(path)?;
+        Ok(FileWrapper {
+            file: Some(BufWriter::with_capacity(self.buffer_size, file)),
+            flush_policy: self.flush_policy,
+            readable: self.read,
+            atomic: None,
+            drop_error_policy: DropErrorPolicy::default(),
+        })
+    }
+}
+
+impl FileWrapper {
+    /// Create a new FileWrapper by opening a file
+    pub fn new(path: &str) -> io::Result<Self> {
+        Self::options().open(path)
+    }
+
+    /// Write to a temporary sibling of `path` that atomically replaces `path` on a
+    /// successful drop or `commit()`
+    ///
+    /// If the wrapper is dropped during a panic, or `abort()` is called, the temporary
+    /// file is removed and `path` is left untouched, so consumers never see a partially
+    /// written file.
+    pub fn atomic(path: impl AsRef<Path>) -> io::Result<Self> {
+        let target = path.as_ref().to_path_buf();
+        let file_name = target
+            .file_name()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Path has no file name"))?;
+        let temp_name = format!(".{}.{}", file_name.to_string_lossy(), temp_file::unique_name("tmp"));
+        let temp_path = target.with_file_name(temp_name);
+
+        let mut file_wrapper = Self::options().create_new(true).open(&temp_path)?;
+        file_wrapper.atomic = Some(AtomicTarget { temp_path, target });
+        Ok(file_wrapper)
+    }
+
+    /// Flush, sync and close the file, returning any error instead of leaving it
+    /// to the drop-error policy
+    ///
+    /// In atomic mode this is the same as `commit()`.
+    pub fn close(self) -> io::Result<()> {
+        self.commit()
+    }
+
+    /// Choose what happens to errors raised while dropping the wrapper
+    pub fn set_drop_error_policy(&mut self, policy: DropErrorPolicy) {
+        self.drop_error_policy = policy;
+    }
+
+    /// Flush, sync and close the file; in atomic mode rename it over the target
+    pub fn commit(mut self) -> io::Result<()> {
+        let result = self.sync_and_close();
+        match self.atomic.take() {
+            Some(atomic) if result.is_ok() => fs::rename(&atomic.temp_path, &atomic.target),
+            Some(atomic) => {
+                let _ = fs::remove_file(&atomic.temp_path);
+                result
+            }
+            None => result,
+        }
+    }
+
+    /// Close the file discarding any buffered data; in atomic mode remove the
+    /// temporary file and leave the target untouched
+    pub fn abort(mut self) -> io::Result<()> {
+        if let Some(file) = self.file.take() {
+            // into_parts hands back the file without flushing the buffer
+            let (file, _unflushed) = file.into_parts();
+            drop(file);
+        }
+        match self.atomic.take() {
+            Some(atomic) => fs::remove_file(&atomic.temp_path),
+            None => Ok(()),
+        }
+    }
+
+    /// Start building a FileWrapper with a custom open mode
+    pub fn options() -> FileWrapperOptions {
+        FileWrapperOptions {
+            read: false,
+            append: false,
+            truncate: true,
+            create_new: false,
+            buffer_size: DEFAULT_BUFFER_SIZE,
+            flush_policy: FlushPolicy::OnDrop,
+        }
+    }
+
+    /// Write data to the file, flushing according to the flush policy
+    pub fn write(&mut self, data: &str) -> io::Result<()> {
+        self.write_bytes(data.as_bytes())
+    }
+
+    /// Write raw bytes to the file, flushing according to the flush policy
+    pub fn write_bytes(&mut self, data: &[u8]) -> io::Result<()> {
+        if let Some(file) = self.file.as_mut() {
+            file.write_all(data)?;
+            match self.flush_policy {
+                FlushPolicy::EveryWrite => file.flush(),
+                FlushPolicy::Threshold(limit) if file.buffer().len() >= limit => file.flush(),
+                _ => Ok(()),
+            }
+        } else {
+            Err(io::Error::other("File is not available"))
+        }
+    }
+
+    /// Push all buffered data to the file
+    pub fn flush(&mut self) -> io::Result<()> {
+        if let Some(file) = self.file.as_mut() {
+            file.flush()
+        } else {
+            Err(io::Error::other("File is not available"))
+        }
+    }
+
+    /// Read the whole file from the start into a String
+    ///
+    /// Leaves the cursor at the end of the file.
+    pub fn read_to_string(&mut self) -> io::Result<String> {
+        let file = self.reader()?;
+        file.rewind()?;
+        let mut content = String::new();
+        Read::read_to_string(file, &mut content)?;
+        Ok(content)
+    }
+
+    /// Fill `buf` with the bytes starting at `offset` without moving the cursor
+    pub fn read_exact_at(&mut self, offset: u64, buf: &mut [u8]) -> io::Result<()> {
+        let file = self.reader()?;
+        let position = file.stream_position()?;
+        file.seek(SeekFrom::Start(offset))?;
+        let result = file.read_exact(buf);
+        file.seek(SeekFrom::Start(position))?;
+        result
+    }
+
+    /// Flush buffered data, sync it to disk and close the file
+    fn sync_and_close(&mut self) -> io::Result<()> {
+        if let Some(mut file) = self.file.take() {
+            file.flush()?;
+            file.get_ref().sync_all()?;
+        }
+        Ok(())
+    }
+
+    /// Deal with an error raised in `Drop` according to the drop-error policy
+    fn handle_drop_error(&mut self, context: &str, error: io::Error) {
+        match &mut self.drop_error_policy {
+            DropErrorPolicy::Ignore => {}
+            DropErrorPolicy::Panic if !thread::panicking() => panic!("{}: {}", context, error),
+            DropErrorPolicy::Log | DropErrorPolicy::Panic => eprintln!("{}: {}", context, error),
+            DropErrorPolicy::Callback(f) => f(error),
+        }
+    }
+
+    /// Flush pending writes and hand out the underlying file for reading
+    fn reader(&mut self) -> io::Result<&mut File> {
+        if !self.readable {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "File was not opened for reading",
+            ));
+        }
+        match self.file.as_mut() {
+            Some(file) => {
+                file.flush()?;
+                Ok(file.get_mut())
+            }
+            None => Err(io::Error::other("File is not available")),
+        }
+    }
+}
+
+impl Read for FileWrapper {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.reader()?.read(buf)
+    }
+}
+
+/// Byte-level writes go through `write_bytes`, so the flush policy still applies
+impl Write for FileWrapper {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write_bytes(buf)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        FileWrapper::flush(self)
+    }
+}
+
+impl Seek for FileWrapper {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        match self.file.as_mut() {
+            // BufWriter flushes pending writes before seeking
+            Some(file) => file.seek(pos),
+            None => Err(io::Error::other("File is not available")),
+        }
+    }
+}
+
+impl Drop for FileWrapper {
+    /// Release the file resource when the struct goes out of scope
+    fn drop(&mut self) {
+        if let Some(atomic) = self.atomic.take() {
+            // Never publish a file that was being written when a panic started
+            let result = if thread::panicking() {
+                drop(self.file.take().map(BufWriter::into_parts));
+                Err(io::Error::other("Panicked while writing"))
+            } else {
+                self.sync_and_close()
+            };
+            let result = result.and_then(|()| fs::rename(&atomic.temp_path, &atomic.target));
+            if let Err(e) = result {
+                let _ = fs::remove_file(&atomic.temp_path);
+                self.handle_drop_error(&format!("Discarding {}", atomic.temp_path.display()), e);
+            }
+            return;
+        }
+
+        if let Some(mut file) = self.file.take() {
+            if let Err(e) = file.flush() {
+                self.handle_drop_error("Error flushing file", e);
+            }
+            if let Err(e) = file.get_ref().sync_all() {
+                self.handle_drop_error("Error syncing file", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::io::{self, Read};
+
+    #[test]
+    fn test_happy_path_write_and_drop() -> io::Result<()> {
+        let test_path = "test_happy.txt";
+
+        // Write to file using FileWrapper
+        {
+            let mut file_wrapper = FileWrapper::new(test_path)?;
+            file_wrapper.write("Testing RAII implementation!")?;
+        } // FileWrapper goes out of scope here, and the file is automatically closed.
+
+        // Verify file content
+        let mut content = String::new();
+        let mut file = File::open(test_path)?;
+        file.read_to_string(&mut content)?;
+        assert_eq!(content, "Testing RAII implementation!");
+
+        // Clean up test file
+        fs::remove_file(test_path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_unhappy_path_write_without_file() {
+        let mut file_wrapper = FileWrapper {
+            file: None,
+            flush_policy: FlushPolicy::OnDrop,
+            readable: true,
+            atomic: None,
+            drop_error_policy: DropErrorPolicy::Log,
+        };
+        let result = file_wrapper.write("This should fail.");
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::Other);
+        assert!(file_wrapper.flush().is_err());
+        assert!(file_wrapper.read_to_string().is_err());
+        assert!(file_wrapper.seek(SeekFrom::Start(0)).is_err());
+    }
+
+    #[test]
+    fn test_read_to_string_sees_buffered_writes() -> io::Result<()> {
+        let test_path = "test_read_to_string.txt";
+        {
+            let mut file_wrapper = FileWrapper::options().read(true).open(test_path)?;
+            file_wrapper.write("written then read")?;
+            assert_eq!(file_wrapper.read_to_string()?, "written then read");
+        }
+        fs::remove_file(test_path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_exact_at_keeps_cursor() -> io::Result<()> {
+        let test_path = "test_read_exact_at.txt";
+        {
+            let mut file_wrapper = FileWrapper::options().read(true).open(test_path)?;
+            file_wrapper.write("0123456789")?;
+            file_wrapper.seek(SeekFrom::Start(2))?;
+
+            let mut buf = [0u8; 3];
+            file_wrapper.read_exact_at(5, &mut buf)?;
+            assert_eq!(&buf, b"567");
+            assert_eq!(file_wrapper.stream_position()?, 2);
+
+            // Reading past the end is an error
+            let mut too_long = [0u8; 4];
+            let result = file_wrapper.read_exact_at(8, &mut too_long);
+            assert_eq!(result.unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+        }
+        fs::remove_file(test_path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_and_seek_traits() -> io::Result<()> {
+        let test_path = "test_read_seek.txt";
+        {
+            let mut file_wrapper = FileWrapper::options().read(true).open(test_path)?;
+            file_wrapper.write("hello world")?;
+            file_wrapper.seek(SeekFrom::End(-5))?;
+            let mut content = String::new();
+            Read::read_to_string(&mut file_wrapper, &mut content)?;
+            assert_eq!(content, "world");
+        }
+        fs::remove_file(test_path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_trait_and_raw_bytes() -> io::Result<()> {
+        let test_path = "test_write_bytes.txt";
+        {
+            let mut file_wrapper = FileWrapper::new(test_path)?;
+            file_wrapper.write_bytes(&[0, 159, 146, 150])?;
+            write!(file_wrapper, "-{}", 42)?;
+            Write::flush(&mut file_wrapper)?;
+        }
+        assert_eq!(fs::read(test_path)?, b"\0\x9f\x92\x96-42");
+        fs::remove_file(test_path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_unhappy_path_read_write_only_file() -> io::Result<()> {
+        let test_path = "test_read_write_only.txt";
+        {
+            let mut file_wrapper = FileWrapper::new(test_path)?;
+            file_wrapper.write("not readable")?;
+            let result = file_wrapper.read_to_string();
+            assert_eq!(result.unwrap_err().kind(), io::ErrorKind::PermissionDenied);
+        }
+        fs::remove_file(test_path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_flush_policy_on_drop_buffers_writes() -> io::Result<()> {
+        let test_path = "test_flush_on_drop.txt";
+        {
+            let mut file_wrapper = FileWrapper::options()
+                .flush_policy(FlushPolicy::OnDrop)
+                .open(test_path)?;
+            file_wrapper.write("buffered")?;
+            assert_eq!(fs::read_to_string(test_path)?, "");
+
+            file_wrapper.flush()?;
+            assert_eq!(fs::read_to_string(test_path)?, "buffered");
+            file_wrapper.write(" until drop")?;
+        }
+        assert_eq!(fs::read_to_string(test_path)?, "buffered until drop");
+        fs::remove_file(test_path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_flush_policy_every_write() -> io::Result<()> {
+        let test_path = "test_flush_every_write.txt";
+        let mut file_wrapper = FileWrapper::options()
+            .flush_policy(FlushPolicy::EveryWrite)
+            .open(test_path)?;
+        file_wrapper.write("visible")?;
+        assert_eq!(fs::read_to_string(test_path)?, "visible");
+        drop(file_wrapper);
+        fs::remove_file(test_path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_flush_policy_threshold() -> io::Result<()> {
+        let test_path = "test_flush_threshold.txt";
+        let mut file_wrapper = FileWrapper::options()
+            .buffer_size(1024)
+            .flush_policy(FlushPolicy::Threshold(10))
+            .open(test_path)?;
+        file_wrapper.write("12345")?;
+        assert_eq!(fs::read_to_string(test_path)?, "");
+        file_wrapper.write("67890")?;
+        assert_eq!(fs::read_to_string(test_path)?, "1234567890");
+        drop(file_wrapper);
+        fs::remove_file(test_path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_edge_case_empty_write() -> io::Result<()> {
+        let test_path = "test_empty.txt";
+
+        // Write empty content to file
+        {
+            let mut file_wrapper = FileWrapper::new(test_path)?;
+            file_wrapper.write("")?;
+        }
+
+        // Verify file content is empty
+        let mut content = String::new();
+        let mut file = File::open(test_path)?;
+        file.read_to_string(&mut content)?;
+        assert!(content.is_empty());
+
+        // Clean up test file
+        fs::remove_file(test_path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_options_append_mode() -> io::Result<()> {
+        let test_path = "test_options_append.txt";
+        FileWrapper::new(test_path)?.write("first")?;
+        FileWrapper::options().append(true).open(test_path)?.write(" second")?;
+
+        assert_eq!(fs::read_to_string(test_path)?, "first second");
+        fs::remove_file(test_path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_options_default_truncates() -> io::Result<()> {
+        let test_path = "test_options_truncate.txt";
+        FileWrapper::new(test_path)?.write("a long first line")?;
+        FileWrapper::options().open(test_path)?.write("short")?;
+        assert_eq!(fs::read_to_string(test_path)?, "short");
+
+        // Without truncation the old tail survives
+        FileWrapper::options().truncate(false).open(test_path)?.write("SH")?;
+        assert_eq!(fs::read_to_string(test_path)?, "SHort");
+        fs::remove_file(test_path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_options_read_write_mode() -> io::Result<()> {
+        let test_path = "test_options_read.txt";
+        {
+            let mut file_wrapper = FileWrapper::options().read(true).open(test_path)?;
+            file_wrapper.write("readable")?;
+            assert_eq!(file_wrapper.read_to_string()?, "readable");
+        }
+        fs::remove_file(test_path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_unhappy_path_create_new_on_existing_file() -> io::Result<()> {
+        let test_path = "test_options_create_new.txt";
+        let _ = fs::remove_file(test_path);
+        FileWrapper::options().create_new(true).open(test_path)?.write("once")?;
+
+        let result = FileWrapper::options().create_new(true).open(test_path);
+        assert_eq!(result.err().map(|e| e.kind()), Some(io::ErrorKind::AlreadyExists));
+        assert_eq!(fs::read_to_string(test_path)?, "once");
+        fs::remove_file(test_path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_atomic_replaces_target_on_drop() -> io::Result<()> {
+        let test_path = "test_atomic_drop.txt";
+        fs::write(test_path, "old")?;
+        {
+            let mut file_wrapper = FileWrapper::atomic(test_path)?;
+            file_wrapper.write("new")?;
+            file_wrapper.flush()?;
+            // Nothing is visible at the target until the wrapper is dropped
+            assert_eq!(fs::read_to_string(test_path)?, "old");
+        }
+        assert_eq!(fs::read_to_string(test_path)?, "new");
+        fs::remove_file(test_path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_atomic_commit_creates_target() -> io::Result<()> {
+        let test_path = "test_atomic_commit.txt";
+        let _ = fs::remove_file(test_path);
+        let mut file_wrapper = FileWrapper::atomic(test_path)?;
+        file_wrapper.write("committed")?;
+        file_wrapper.commit()?;
+        assert_eq!(fs::read_to_string(test_path)?, "committed");
+        fs::remove_file(test_path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_atomic_abort_leaves_target_untouched() -> io::Result<()> {
+        let test_path = "test_atomic_abort.txt";
+        fs::write(test_path, "original")?;
+        let mut file_wrapper = FileWrapper::atomic(test_path)?;
+        file_wrapper.write("partial")?;
+        let temp_path = file_wrapper.atomic.as_ref().unwrap().temp_path.clone();
+        file_wrapper.abort()?;
+
+        assert_eq!(fs::read_to_string(test_path)?, "original");
+        assert!(!temp_path.exists());
+        fs::remove_file(test_path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_atomic_discards_on_panic() -> io::Result<()> {
+        let test_path = "test_atomic_panic.txt";
+        fs::write(test_path, "original")?;
+        let result = std::panic::catch_unwind(|| {
+            let mut file_wrapper = FileWrapper::atomic(test_path).unwrap();
+            file_wrapper.write("crashed halfway").unwrap();
+            panic!("simulated crash");
+        });
+        assert!(result.is_err());
+        assert_eq!(fs::read_to_string(test_path)?, "original");
+
+        // No temporary siblings are left behind
+        let leftovers = fs::read_dir(".")?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_name().to_string_lossy().starts_with(".test_atomic_panic.txt."))
+            .count();
+        assert_eq!(leftovers, 0);
+        fs::remove_file(test_path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_happy_path_close_reports_success() -> io::Result<()> {
+        let test_path = "test_close.txt";
+        let mut file_wrapper = FileWrapper::new(test_path)?;
+        file_wrapper.write("closed")?;
+        file_wrapper.close()?;
+        assert_eq!(fs::read_to_string(test_path)?, "closed");
+        fs::remove_file(test_path)?;
+        Ok(())
+    }
+
+    #[cfg(target_os = "linux")]
+    fn dev_full() -> io::Result<FileWrapper> {
+        // Every write to /dev/full fails with "no space left on device"
+        FileWrapper::options().truncate(false).open("/dev/full")
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_unhappy_path_close_returns_flush_error() -> io::Result<()> {
+        let mut file_wrapper = dev_full()?;
+        file_wrapper.write("lost")?;
+        assert_eq!(file_wrapper.close().unwrap_err().kind(), io::ErrorKind::StorageFull);
+        Ok(())
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_drop_error_callback_receives_error() -> io::Result<()> {
+        let errors = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen = std::sync::Arc::clone(&errors);
+        {
+            let mut file_wrapper = dev_full()?;
+            file_wrapper.set_drop_error_policy(DropErrorPolicy::callback(move |e| {
+                seen.lock().unwrap().push(e.kind())
+            }));
+            file_wrapper.write("lost")?;
+        }
+        assert_eq!(errors.lock().unwrap().first(), Some(&io::ErrorKind::StorageFull));
+        Ok(())
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_drop_error_panic_policy() -> io::Result<()> {
+        let mut file_wrapper = dev_full()?;
+        file_wrapper.set_drop_error_policy(DropErrorPolicy::Panic);
+        file_wrapper.write("lost")?;
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(move || drop(file_wrapper)));
+        assert!(result.is_err());
+        Ok(())
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_edge_case_panic_policy_while_already_panicking() -> io::Result<()> {
+        let mut file_wrapper = dev_full()?;
+        file_wrapper.set_drop_error_policy(DropErrorPolicy::Panic);
+        file_wrapper.write("lost")?;
+        // Dropping during unwinding must not panic again (which would abort)
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(move || {
+            let _file_wrapper = file_wrapper;
+            panic!("original failure");
+        }));
+        let message = result.unwrap_err();
+        assert_eq!(message.downcast_ref::<&str>(), Some(&"original failure"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_large_file_write() -> io::Result<()> {
+        let test_path = "test_large.txt";
+        let large_data = "A".repeat(10_000);
+
+        // Write large content to file
+        {
+            let mut file_wrapper = FileWrapper::new(test_path)?;
+            file_wrapper.write(&large_data)?;
+        }
+
+        // Verify file content
+        let mut content = String::new();
+        let mut file = File::open(test_path)?;
+        file.read_to_string(&mut content)?;
+        assert_eq!(content, large_data);
+
+        // Clean up test file
+        fs::remove_file(test_path)?;
+        Ok(())
+    }
+}
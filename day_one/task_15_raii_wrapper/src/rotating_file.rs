@@ -0,0 +1,193 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::FileWrapper;
+
+/// Size-capped log file that rotates into numbered backups
+///
+/// When a write would push `path` past `max_bytes`, the file is closed and shifted
+/// to `path.1`, older backups move up (`path.1` -> `path.2`, ...), anything beyond
+/// `max_files` backups is deleted, and writing continues in a fresh `path`. A single
+/// write is never split across files, so a write larger than `max_bytes` gets a
+/// file to itself.
+pub struct RotatingFileWrapper {
+    path: PathBuf,
+    max_bytes: u64,
+    max_files: usize,
+    file: Option<FileWrapper>,
+    written: u64,
+}
+
+impl RotatingFileWrapper {
+    /// Open `path` for appending, keeping at most `max_files` rotated backups
+    pub fn new(path: impl AsRef<Path>, max_bytes: u64, max_files: usize) -> io::Result<Self> {
+        if max_bytes == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "max_bytes must be greater than zero",
+            ));
+        }
+        let path = path.as_ref().to_path_buf();
+        let file = FileWrapper::options().append(true).open(&path)?;
+        let written = fs::metadata(&path)?.len();
+        Ok(Self {
+            path,
+            max_bytes,
+            max_files,
+            file: Some(file),
+            written,
+        })
+    }
+
+    /// Write data, rotating first if it would not fit in the current file
+    pub fn write(&mut self, data: &str) -> io::Result<()> {
+        let len = data.len() as u64;
+        if self.written > 0 && self.written + len > self.max_bytes {
+            self.rotate()?;
+        }
+        self.file_mut()?.write(data)?;
+        self.written += len;
+        Ok(())
+    }
+
+    /// Push all buffered data to the current file
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.file_mut()?.flush()
+    }
+
+    /// Close the current file, shift the backups and start a new file
+    pub fn rotate(&mut self) -> io::Result<()> {
+        if let Some(file) = self.file.take() {
+            file.close()?;
+        }
+        if self.max_files > 0 {
+            let oldest = self.backup_path(self.max_files);
+            if oldest.exists() {
+                fs::remove_file(&oldest)?;
+            }
+            for index in (1..self.max_files).rev() {
+                let from = self.backup_path(index);
+                if from.exists() {
+                    fs::rename(&from, self.backup_path(index + 1))?;
+                }
+            }
+            fs::rename(&self.path, self.backup_path(1))?;
+        }
+        // With no backups allowed, truncating is all the rotation there is
+        self.file = Some(FileWrapper::options().open(&self.path)?);
+        self.written = 0;
+        Ok(())
+    }
+
+    /// Path of the file currently being written
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Bytes written to the current file
+    pub fn current_size(&self) -> u64 {
+        self.written
+    }
+
+    /// Path of the `index`-th backup, e.g. `app.log.2`
+    pub fn backup_path(&self, index: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{}", index));
+        PathBuf::from(name)
+    }
+
+    fn file_mut(&mut self) -> io::Result<&mut FileWrapper> {
+        self.file
+            .as_mut()
+            .ok_or_else(|| io::Error::other("File is not available"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::temp_file::TempFile;
+
+    /// Fresh empty directory so rotated files from different tests never mix
+    fn scratch_dir() -> io::Result<PathBuf> {
+        let dir = TempFile::new()?.keep();
+        fs::remove_file(&dir)?;
+        fs::create_dir(&dir)?;
+        Ok(dir)
+    }
+
+    #[test]
+    fn test_happy_path_rotates_into_numbered_backups() -> io::Result<()> {
+        let dir = scratch_dir()?;
+        let path = dir.join("app.log");
+        {
+            let mut log = RotatingFileWrapper::new(&path, 10, 3)?;
+            for line in ["first\n", "second\n", "third\n"] {
+                log.write(line)?;
+            }
+            assert_eq!(log.path(), path);
+            assert_eq!(log.current_size(), 6);
+        }
+        assert_eq!(fs::read_to_string(&path)?, "third\n");
+        assert_eq!(fs::read_to_string(dir.join("app.log.1"))?, "second\n");
+        assert_eq!(fs::read_to_string(dir.join("app.log.2"))?, "first\n");
+        fs::remove_dir_all(&dir)
+    }
+
+    #[test]
+    fn test_oldest_backup_is_deleted() -> io::Result<()> {
+        let dir = scratch_dir()?;
+        let path = dir.join("app.log");
+        {
+            let mut log = RotatingFileWrapper::new(&path, 4, 2)?;
+            for line in ["aaa\n", "bbb\n", "ccc\n", "ddd\n"] {
+                log.write(line)?;
+            }
+        }
+        assert_eq!(fs::read_to_string(&path)?, "ddd\n");
+        assert_eq!(fs::read_to_string(dir.join("app.log.1"))?, "ccc\n");
+        assert_eq!(fs::read_to_string(dir.join("app.log.2"))?, "bbb\n");
+        assert!(!dir.join("app.log.3").exists());
+        fs::remove_dir_all(&dir)
+    }
+
+    #[test]
+    fn test_reopen_appends_and_counts_existing_size() -> io::Result<()> {
+        let dir = scratch_dir()?;
+        let path = dir.join("app.log");
+        RotatingFileWrapper::new(&path, 10, 1)?.write("12345")?;
+
+        let mut log = RotatingFileWrapper::new(&path, 10, 1)?;
+        assert_eq!(log.current_size(), 5);
+        log.write("6789")?;
+        log.write("overflow")?;
+        drop(log);
+
+        assert_eq!(fs::read_to_string(dir.join("app.log.1"))?, "123456789");
+        assert_eq!(fs::read_to_string(&path)?, "overflow");
+        fs::remove_dir_all(&dir)
+    }
+
+    #[test]
+    fn test_edge_case_oversized_write_and_no_backups() -> io::Result<()> {
+        let dir = scratch_dir()?;
+        let path = dir.join("app.log");
+        {
+            let mut log = RotatingFileWrapper::new(&path, 4, 0)?;
+            log.write("small")?;
+            log.write("much larger than the limit")?;
+            log.flush()?;
+            assert_eq!(fs::read_to_string(&path)?, "much larger than the limit");
+        }
+        assert!(!dir.join("app.log.1").exists());
+        fs::remove_dir_all(&dir)
+    }
+
+    #[test]
+    fn test_unhappy_path_zero_max_bytes() {
+        let result = RotatingFileWrapper::new("unused.log", 0, 1);
+        assert_eq!(result.err().map(|e| e.kind()), Some(io::ErrorKind::InvalidInput));
+        assert!(!Path::new("unused.log").exists());
+    }
+}
@@ -0,0 +1,136 @@
+use std::ops::{Deref, DerefMut};
+
+/// Generic RAII guard that runs a cleanup closure on its state when dropped
+///
+/// Cleanup also runs while unwinding from a panic, so it suits any resource that
+/// needs releasing, not just files. Call `dismiss()` to take the state back
+/// without running the cleanup.
+pub struct ScopeGuard<S, F>
+where
+    F: FnOnce(S),
+{
+    state: Option<S>,
+    cleanup: Option<F>,
+}
+
+impl<S, F> ScopeGuard<S, F>
+where
+    F: FnOnce(S),
+{
+    /// Guard `state`, calling `cleanup(state)` when the guard goes out of scope
+    pub fn new(state: S, cleanup: F) -> Self {
+        Self {
+            state: Some(state),
+            cleanup: Some(cleanup),
+        }
+    }
+
+    /// Disarm the guard and return the state without running the cleanup
+    pub fn dismiss(mut self) -> S {
+        self.cleanup = None;
+        self.state.take().expect("ScopeGuard state is only taken once")
+    }
+}
+
+impl<S, F> Deref for ScopeGuard<S, F>
+where
+    F: FnOnce(S),
+{
+    type Target = S;
+
+    fn deref(&self) -> &S {
+        self.state.as_ref().expect("ScopeGuard state is only taken once")
+    }
+}
+
+impl<S, F> DerefMut for ScopeGuard<S, F>
+where
+    F: FnOnce(S),
+{
+    fn deref_mut(&mut self) -> &mut S {
+        self.state.as_mut().expect("ScopeGuard state is only taken once")
+    }
+}
+
+impl<S, F> Drop for ScopeGuard<S, F>
+where
+    F: FnOnce(S),
+{
+    /// Run the cleanup unless the guard was dismissed
+    fn drop(&mut self) {
+        if let (Some(state), Some(cleanup)) = (self.state.take(), self.cleanup.take()) {
+            cleanup(state);
+        }
+    }
+}
+
+/// Run the given statements when the current scope ends, including on panic
+///
+/// ```ignore
+/// defer! { println!("leaving scope"); }
+/// ```
+#[macro_export]
+macro_rules! defer {
+    ($($body:tt)*) => {
+        let _guard = $crate::scope_guard::ScopeGuard::new((), |()| { $($body)* });
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::panic::{self, AssertUnwindSafe};
+
+    #[test]
+    fn test_happy_path_cleanup_runs_on_drop() {
+        let log = RefCell::new(Vec::new());
+        {
+            let mut guard = ScopeGuard::new(vec![1], |state| log.borrow_mut().push(state));
+            guard.push(2);
+            assert_eq!(*guard, vec![1, 2]);
+        }
+        assert_eq!(*log.borrow(), vec![vec![1, 2]]);
+    }
+
+    #[test]
+    fn test_dismiss_skips_cleanup() {
+        let cleaned = RefCell::new(false);
+        let guard = ScopeGuard::new(42, |_| *cleaned.borrow_mut() = true);
+        assert_eq!(guard.dismiss(), 42);
+        assert!(!*cleaned.borrow());
+    }
+
+    #[test]
+    fn test_cleanup_runs_during_panic_unwinding() {
+        let cleaned = RefCell::new(false);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let _guard = ScopeGuard::new((), |()| *cleaned.borrow_mut() = true);
+            panic!("simulated failure");
+        }));
+        assert!(result.is_err());
+        assert!(*cleaned.borrow());
+    }
+
+    #[test]
+    fn test_defer_runs_in_reverse_order() {
+        let log = RefCell::new(Vec::new());
+        {
+            crate::defer! { log.borrow_mut().push("first"); }
+            crate::defer! { log.borrow_mut().push("second"); }
+            log.borrow_mut().push("body");
+        }
+        assert_eq!(*log.borrow(), vec!["body", "second", "first"]);
+    }
+
+    #[test]
+    fn test_defer_runs_on_panic() {
+        let log = RefCell::new(Vec::new());
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            crate::defer! { log.borrow_mut().push("cleanup"); }
+            panic!("simulated failure");
+        }));
+        assert!(result.is_err());
+        assert_eq!(*log.borrow(), vec!["cleanup"]);
+    }
+}
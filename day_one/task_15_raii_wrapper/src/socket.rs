@@ -0,0 +1,288 @@
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufWriter, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+/// Errors reported by the socket wrappers
+#[derive(Debug)]
+pub enum SocketError {
+    /// Could not connect to any address the target resolved to
+    Connect { addr: String, source: io::Error },
+    /// Could not bind the listener
+    Bind { addr: String, source: io::Error },
+    /// A read or write did not complete within the configured timeout
+    Timeout(io::Error),
+    /// The socket was already shut down
+    Closed,
+    /// Any other I/O failure
+    Io(io::Error),
+}
+
+impl fmt::Display for SocketError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SocketError::Connect { addr, source } => {
+                write!(f, "failed to connect to {}: {}", addr, source)
+            }
+            SocketError::Bind { addr, source } => write!(f, "failed to bind {}: {}", addr, source),
+            SocketError::Timeout(e) => write!(f, "socket operation timed out: {}", e),
+            SocketError::Closed => write!(f, "socket is closed"),
+            SocketError::Io(e) => write!(f, "socket I/O error: {}", e),
+        }
+    }
+}
+
+impl Error for SocketError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SocketError::Connect { source, .. } | SocketError::Bind { source, .. } => Some(source),
+            SocketError::Timeout(e) | SocketError::Io(e) => Some(e),
+            SocketError::Closed => None,
+        }
+    }
+}
+
+impl From<io::Error> for SocketError {
+    /// Timeouts surface as `WouldBlock` on Unix and `TimedOut` on Windows
+    fn from(e: io::Error) -> Self {
+        match e.kind() {
+            io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => SocketError::Timeout(e),
+            _ => SocketError::Io(e),
+        }
+    }
+}
+
+/// Timeouts applied when a socket is created; `None` means block forever
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SocketTimeouts {
+    pub connect: Option<Duration>,
+    pub read: Option<Duration>,
+    pub write: Option<Duration>,
+}
+
+/// RAII wrapper for a TCP connection
+///
+/// Writes are buffered. On drop the buffer is flushed and the connection is shut
+/// down in both directions, so the peer sees a clean end-of-stream.
+pub struct TcpStreamWrapper {
+    stream: Option<BufWriter<TcpStream>>,
+}
+
+impl TcpStreamWrapper {
+    /// Connect to `addr`, trying every resolved address in turn
+    pub fn connect(
+        addr: impl ToSocketAddrs + fmt::Debug,
+        timeouts: SocketTimeouts,
+    ) -> Result<Self, SocketError> {
+        let connect_error = |source| SocketError::Connect {
+            addr: format!("{:?}", addr),
+            source,
+        };
+        let mut last_error =
+            io::Error::new(io::ErrorKind::InvalidInput, "address resolved to nothing");
+        for candidate in addr.to_socket_addrs().map_err(connect_error)? {
+            let attempt = match timeouts.connect {
+                Some(timeout) => TcpStream::connect_timeout(&candidate, timeout),
+                None => TcpStream::connect(candidate),
+            };
+            match attempt {
+                Ok(stream) => return Self::from_stream(stream, timeouts),
+                Err(e) => last_error = e,
+            }
+        }
+        Err(connect_error(last_error))
+    }
+
+    /// Wrap an already connected stream, applying the read/write timeouts
+    pub fn from_stream(stream: TcpStream, timeouts: SocketTimeouts) -> Result<Self, SocketError> {
+        stream.set_read_timeout(timeouts.read)?;
+        stream.set_write_timeout(timeouts.write)?;
+        Ok(Self {
+            stream: Some(BufWriter::new(stream)),
+        })
+    }
+
+    /// Queue data for sending
+    pub fn write(&mut self, data: &[u8]) -> Result<(), SocketError> {
+        Ok(self.stream_mut()?.write_all(data)?)
+    }
+
+    /// Send all queued data
+    pub fn flush(&mut self) -> Result<(), SocketError> {
+        Ok(self.stream_mut()?.flush()?)
+    }
+
+    /// Read into `buf`, returning the number of bytes read (0 at end-of-stream)
+    pub fn read(&mut self, buf: &mut [u8]) -> Result<usize, SocketError> {
+        let stream = self.stream_mut()?;
+        // Make sure a request is on the wire before waiting for the answer
+        stream.flush()?;
+        Ok(stream.get_mut().read(buf)?)
+    }
+
+    /// Address of the remote peer
+    pub fn peer_addr(&self) -> Result<SocketAddr, SocketError> {
+        match self.stream.as_ref() {
+            Some(stream) => Ok(stream.get_ref().peer_addr()?),
+            None => Err(SocketError::Closed),
+        }
+    }
+
+    /// Flush and shut down the connection, reporting any error
+    pub fn shutdown(&mut self) -> Result<(), SocketError> {
+        let mut stream = self.stream.take().ok_or(SocketError::Closed)?;
+        stream.flush()?;
+        match stream.get_ref().shutdown(Shutdown::Both) {
+            // The peer may already have closed its side
+            Err(e) if e.kind() != io::ErrorKind::NotConnected => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn stream_mut(&mut self) -> Result<&mut BufWriter<TcpStream>, SocketError> {
+        self.stream.as_mut().ok_or(SocketError::Closed)
+    }
+}
+
+impl Drop for TcpStreamWrapper {
+    /// Flush and shut down gracefully when the wrapper goes out of scope
+    fn drop(&mut self) {
+        if self.stream.is_some() {
+            if let Err(e) = self.shutdown() {
+                eprintln!("Error shutting down socket: {}", e);
+            }
+        }
+    }
+}
+
+/// RAII wrapper for a TCP listener; the socket is closed when dropped
+pub struct ListenerWrapper {
+    listener: TcpListener,
+    timeouts: SocketTimeouts,
+}
+
+impl ListenerWrapper {
+    /// Bind to `addr`; `timeouts` are applied to every accepted connection
+    pub fn bind(
+        addr: impl ToSocketAddrs + fmt::Debug,
+        timeouts: SocketTimeouts,
+    ) -> Result<Self, SocketError> {
+        let listener = TcpListener::bind(&addr).map_err(|source| SocketError::Bind {
+            addr: format!("{:?}", addr),
+            source,
+        })?;
+        Ok(Self { listener, timeouts })
+    }
+
+    /// Wait for the next connection
+    pub fn accept(&self) -> Result<(TcpStreamWrapper, SocketAddr), SocketError> {
+        let (stream, addr) = self.listener.accept()?;
+        Ok((TcpStreamWrapper::from_stream(stream, self.timeouts)?, addr))
+    }
+
+    /// Address the listener is bound to
+    pub fn local_addr(&self) -> Result<SocketAddr, SocketError> {
+        Ok(self.listener.local_addr()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    fn short_timeouts() -> SocketTimeouts {
+        SocketTimeouts {
+            connect: Some(Duration::from_secs(1)),
+            read: Some(Duration::from_millis(50)),
+            write: Some(Duration::from_secs(1)),
+        }
+    }
+
+    #[test]
+    fn test_happy_path_echo() -> Result<(), SocketError> {
+        let listener = ListenerWrapper::bind("127.0.0.1:0", SocketTimeouts::default())?;
+        let addr = listener.local_addr()?;
+        let server = thread::spawn(move || -> Result<(), SocketError> {
+            let (mut conn, _) = listener.accept()?;
+            let mut buf = [0u8; 5];
+            let n = conn.read(&mut buf)?;
+            conn.write(&buf[..n])?;
+            Ok(())
+        });
+
+        let mut client = TcpStreamWrapper::connect(addr, short_timeouts())?;
+        assert_eq!(client.peer_addr()?, addr);
+        client.write(b"hello")?;
+        let mut reply = [0u8; 5];
+        let mut read = 0;
+        while read < reply.len() {
+            read += client.read(&mut reply[read..])?;
+        }
+        assert_eq!(&reply, b"hello");
+        server.join().unwrap()
+    }
+
+    #[test]
+    fn test_drop_flushes_and_shuts_down() -> Result<(), SocketError> {
+        let listener = ListenerWrapper::bind("127.0.0.1:0", SocketTimeouts::default())?;
+        let addr = listener.local_addr()?;
+        {
+            let mut client = TcpStreamWrapper::connect(addr, SocketTimeouts::default())?;
+            client.write(b"buffered until drop")?;
+        }
+
+        let (mut conn, _) = listener.accept()?;
+        let mut received = Vec::new();
+        let mut buf = [0u8; 64];
+        loop {
+            match conn.read(&mut buf)? {
+                0 => break, // clean end-of-stream thanks to the shutdown
+                n => received.extend_from_slice(&buf[..n]),
+            }
+        }
+        assert_eq!(received, b"buffered until drop");
+        Ok(())
+    }
+
+    #[test]
+    fn test_unhappy_path_read_timeout() -> Result<(), SocketError> {
+        let listener = ListenerWrapper::bind("127.0.0.1:0", SocketTimeouts::default())?;
+        let mut client = TcpStreamWrapper::connect(listener.local_addr()?, short_timeouts())?;
+        let _silent_peer = listener.accept()?;
+
+        let mut buf = [0u8; 1];
+        assert!(matches!(
+            client.read(&mut buf),
+            Err(SocketError::Timeout(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_unhappy_path_connection_refused() -> Result<(), SocketError> {
+        // Grab a free port, then close the listener so nothing is listening there
+        let addr = ListenerWrapper::bind("127.0.0.1:0", SocketTimeouts::default())?.local_addr()?;
+        let result = TcpStreamWrapper::connect(addr, short_timeouts());
+        match result {
+            Err(SocketError::Connect { source, .. }) => {
+                assert_eq!(source.kind(), io::ErrorKind::ConnectionRefused)
+            }
+            other => panic!("expected a connect error, got {:?}", other.err()),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_edge_case_use_after_shutdown() -> Result<(), SocketError> {
+        let listener = ListenerWrapper::bind("127.0.0.1:0", SocketTimeouts::default())?;
+        let mut client =
+            TcpStreamWrapper::connect(listener.local_addr()?, SocketTimeouts::default())?;
+        client.shutdown()?;
+        assert!(matches!(client.write(b"late"), Err(SocketError::Closed)));
+        assert!(matches!(client.shutdown(), Err(SocketError::Closed)));
+        assert_eq!(client.flush().unwrap_err().to_string(), "socket is closed");
+        Ok(())
+    }
+}
@@ -0,0 +1,159 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::FileWrapper;
+
+/// Counter making names unique within this process
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// How many names to try before giving up on creating a temp file
+const MAX_ATTEMPTS: usize = 16;
+
+/// RAII wrapper for a uniquely named temporary file that is deleted on drop
+pub struct TempFile {
+    path: PathBuf,
+    file: Option<FileWrapper>,
+    keep: bool,
+}
+
+impl TempFile {
+    /// Create a temporary file in the system temp directory
+    pub fn new() -> io::Result<Self> {
+        Self::new_in(std::env::temp_dir())
+    }
+
+    /// Create a temporary file in the given directory
+    pub fn new_in(dir: impl AsRef<Path>) -> io::Result<Self> {
+        for _ in 0..MAX_ATTEMPTS {
+            let path = dir.as_ref().join(unique_name("tmp"));
+            match FileWrapper::options().read(true).create_new(true).open(&path) {
+                Ok(file) => {
+                    return Ok(Self {
+                        path,
+                        file: Some(file),
+                        keep: false,
+                    })
+                }
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            "Could not find an unused temp file name",
+        ))
+    }
+
+    /// Location of the temporary file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Write data to the temporary file
+    pub fn write(&mut self, data: &str) -> io::Result<()> {
+        self.file_mut()?.write(data)
+    }
+
+    /// Read the whole temporary file into a String
+    pub fn read_to_string(&mut self) -> io::Result<String> {
+        self.file_mut()?.read_to_string()
+    }
+
+    /// Disarm deletion: close the file, leave it on disk and return its path
+    pub fn keep(mut self) -> PathBuf {
+        self.keep = true;
+        self.path.clone()
+    }
+
+    fn file_mut(&mut self) -> io::Result<&mut FileWrapper> {
+        self.file
+            .as_mut()
+            .ok_or_else(|| io::Error::other("File is not available"))
+    }
+}
+
+/// Build a file name that is unique within this process and unlikely to clash
+/// with other processes: `<prefix>-<pid>-<nanos>-<counter>`
+pub(crate) fn unique_name(prefix: &str) -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    format!("{}-{}-{}-{}", prefix, process::id(), nanos, id)
+}
+
+impl Drop for TempFile {
+    /// Close the file first, then remove it unless `keep()` was called
+    fn drop(&mut self) {
+        drop(self.file.take());
+        if !self.keep {
+            if let Err(e) = fs::remove_file(&self.path) {
+                eprintln!("Error removing temp file {}: {}", self.path.display(), e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_happy_path_write_read_and_delete() -> io::Result<()> {
+        let path = {
+            let mut temp = TempFile::new()?;
+            temp.write("scratch data")?;
+            assert_eq!(temp.read_to_string()?, "scratch data");
+            assert!(temp.path().exists());
+            temp.path().to_path_buf()
+        };
+        assert!(!path.exists(), "Temp file should be removed on drop");
+        Ok(())
+    }
+
+    #[test]
+    fn test_names_are_unique() -> io::Result<()> {
+        let a = TempFile::new()?;
+        let b = TempFile::new()?;
+        assert_ne!(a.path(), b.path());
+        Ok(())
+    }
+
+    #[test]
+    fn test_new_in_directory() -> io::Result<()> {
+        let dir = TempFile::new()?.keep();
+        fs::remove_file(&dir)?;
+        fs::create_dir(&dir)?;
+
+        let path = {
+            let temp = TempFile::new_in(&dir)?;
+            assert_eq!(temp.path().parent(), Some(dir.as_path()));
+            temp.path().to_path_buf()
+        };
+        assert!(!path.exists());
+        fs::remove_dir(&dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_keep_disarms_deletion() -> io::Result<()> {
+        let mut temp = TempFile::new()?;
+        temp.write("kept")?;
+        let path = temp.keep();
+
+        assert_eq!(fs::read_to_string(&path)?, "kept");
+        fs::remove_file(&path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_unhappy_path_missing_directory() {
+        let result = TempFile::new_in("/definitely/not/a/real/dir");
+        assert_eq!(result.err().map(|e| e.kind()), Some(io::ErrorKind::NotFound));
+    }
+}
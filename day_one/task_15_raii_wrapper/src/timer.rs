@@ -0,0 +1,200 @@
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use task_05_ring_buffer::RingBuffer;
+
+/// One finished measurement
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timing {
+    pub label: String,
+    pub elapsed: Duration,
+}
+
+impl fmt::Display for Timing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[timer] {} took {:?}", self.label, self.elapsed)
+    }
+}
+
+/// Where a `TimerGuard` reports its measurement
+pub enum TimingSink {
+    /// Print the timing to stdout
+    Stdout,
+    /// Hand the timing to a closure
+    Callback(Box<dyn FnMut(Timing) + Send>),
+    /// Append the timing to a shared ring buffer
+    Ring(TimingRing),
+}
+
+impl TimingSink {
+    /// Wrap a closure as a sink
+    pub fn callback(f: impl FnMut(Timing) + Send + 'static) -> Self {
+        TimingSink::Callback(Box::new(f))
+    }
+
+    fn record(&mut self, timing: Timing) {
+        match self {
+            TimingSink::Stdout => println!("{}", timing),
+            TimingSink::Callback(f) => f(timing),
+            TimingSink::Ring(ring) => ring.push(timing),
+        }
+    }
+}
+
+/// Fixed-capacity, shareable log of recent timings
+///
+/// A task_05 `RingBuffer` behind a mutex, so pushing into a full ring
+/// overwrites the oldest entry. Clones share the same buffer, so many guards
+/// can feed one ring.
+#[derive(Debug, Clone)]
+pub struct TimingRing {
+    entries: Arc<Mutex<RingBuffer<Timing>>>,
+}
+
+impl TimingRing {
+    /// Create a ring keeping at most `capacity` timings
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "TimingRing capacity must be non-zero");
+        Self {
+            entries: Arc::new(Mutex::new(RingBuffer::new(capacity))),
+        }
+    }
+
+    /// Record a timing, dropping the oldest one if the ring is full
+    pub fn push(&self, timing: Timing) {
+        self.entries.lock().unwrap().push(timing);
+    }
+
+    /// Copy of the stored timings, oldest first
+    pub fn snapshot(&self) -> Vec<Timing> {
+        self.entries.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// Number of stored timings
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().size()
+    }
+
+    /// Check if no timings have been recorded
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// RAII guard that measures how long it lived and reports it on drop
+pub struct TimerGuard {
+    label: String,
+    start: Instant,
+    sink: TimingSink,
+}
+
+impl TimerGuard {
+    /// Start timing, reporting to stdout
+    pub fn new(label: impl Into<String>) -> Self {
+        Self::with_sink(label, TimingSink::Stdout)
+    }
+
+    /// Start timing, reporting to the given sink
+    pub fn with_sink(label: impl Into<String>, sink: TimingSink) -> Self {
+        Self {
+            label: label.into(),
+            start: Instant::now(),
+            sink,
+        }
+    }
+
+    /// Time elapsed so far
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+impl Drop for TimerGuard {
+    /// Report the elapsed time when the guard goes out of scope
+    fn drop(&mut self) {
+        let timing = Timing {
+            label: std::mem::take(&mut self.label),
+            elapsed: self.start.elapsed(),
+        };
+        self.sink.record(timing);
+    }
+}
+
+/// Time the rest of the current scope
+///
+/// ```ignore
+/// time_scope!("load config");                         // prints to stdout
+/// time_scope!("parse", TimingSink::Ring(ring.clone())); // any other sink
+/// ```
+#[macro_export]
+macro_rules! time_scope {
+    ($label:expr) => {
+        let _timer = $crate::timer::TimerGuard::new($label);
+    };
+    ($label:expr, $sink:expr) => {
+        let _timer = $crate::timer::TimerGuard::with_sink($label, $sink);
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn test_happy_path_reports_on_drop() {
+        let ring = TimingRing::new(4);
+        {
+            let guard = TimerGuard::with_sink("work", TimingSink::Ring(ring.clone()));
+            thread::sleep(Duration::from_millis(10));
+            assert!(ring.is_empty(), "Nothing is reported before drop");
+            assert!(guard.elapsed() >= Duration::from_millis(10));
+        }
+        let timings = ring.snapshot();
+        assert_eq!(timings.len(), 1);
+        assert_eq!(timings[0].label, "work");
+        assert!(timings[0].elapsed >= Duration::from_millis(10));
+    }
+
+    #[test]
+    fn test_callback_sink() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&seen);
+        drop(TimerGuard::with_sink(
+            "callback",
+            TimingSink::callback(move |t| log.lock().unwrap().push(t.label)),
+        ));
+        assert_eq!(*seen.lock().unwrap(), vec!["callback"]);
+    }
+
+    #[test]
+    fn test_time_scope_macro_nests() {
+        let ring = TimingRing::new(4);
+        {
+            crate::time_scope!("outer", TimingSink::Ring(ring.clone()));
+            {
+                crate::time_scope!("inner", TimingSink::Ring(ring.clone()));
+            }
+        }
+        let labels: Vec<_> = ring.snapshot().into_iter().map(|t| t.label).collect();
+        assert_eq!(labels, vec!["inner", "outer"]);
+    }
+
+    #[test]
+    fn test_edge_case_ring_overwrites_oldest() {
+        let ring = TimingRing::new(2);
+        for label in ["a", "b", "c"] {
+            drop(TimerGuard::with_sink(label, TimingSink::Ring(ring.clone())));
+        }
+        let labels: Vec<_> = ring.snapshot().into_iter().map(|t| t.label).collect();
+        assert_eq!(labels, vec!["b", "c"]);
+        assert_eq!(ring.len(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity must be non-zero")]
+    fn test_unhappy_path_zero_capacity_ring() {
+        TimingRing::new(0);
+    }
+}
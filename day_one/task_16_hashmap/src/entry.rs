@@ -0,0 +1,191 @@
+use crate::{MyHashMap, Slot};
+
+/// A view into a single slot of a `MyHashMap`, from `MyHashMap::entry`
+pub enum Entry<'a, K, V, S> {
+    Occupied(OccupiedEntry<'a, K, V, S>),
+    Vacant(VacantEntry<'a, K, V, S>),
+}
+
+impl<'a, K, V, S> Entry<'a, K, V, S> {
+    /// Returns the value, inserting `default` first if the key is missing
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    /// Returns the value, inserting the result of `f` first if the key is missing
+    pub fn or_insert_with<F: FnOnce() -> V>(self, f: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(f()),
+        }
+    }
+
+    /// Returns the value, inserting `V::default()` first if the key is missing
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    /// Runs `f` on the value if the key is present
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> Self {
+        if let Entry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+
+    /// Returns the entry's key
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+}
+
+/// An entry whose key is already in the map
+pub struct OccupiedEntry<'a, K, V, S> {
+    pub(crate) map: &'a mut MyHashMap<K, V, S>,
+    pub(crate) index: usize,
+}
+
+impl<'a, K, V, S> OccupiedEntry<'a, K, V, S> {
+    /// Returns the key stored in the map
+    pub fn key(&self) -> &K {
+        self.slot().0
+    }
+
+    /// Returns a reference to the value
+    pub fn get(&self) -> &V {
+        self.slot().1
+    }
+
+    /// Returns a mutable reference to the value
+    pub fn get_mut(&mut self) -> &mut V {
+        self.slot_mut().1
+    }
+
+    /// Converts the entry into a mutable reference tied to the map's lifetime
+    pub fn into_mut(self) -> &'a mut V {
+        self.map.slots[self.index]
+            .as_entry_mut()
+            .expect("occupied entry points at a live slot")
+            .1
+    }
+
+    /// Replaces the value, returning the old one
+    pub fn insert(&mut self, value: V) -> V {
+        std::mem::replace(self.get_mut(), value)
+    }
+
+    /// Removes the entry from the map, returning its value
+    pub fn remove(self) -> V {
+        self.remove_entry().1
+    }
+
+    /// Removes the entry from the map, returning the key and value
+    pub fn remove_entry(self) -> (K, V) {
+        self.map.remove_at(self.index)
+    }
+
+    fn slot(&self) -> (&K, &V) {
+        self.map.slots[self.index]
+            .as_entry()
+            .expect("occupied entry points at a live slot")
+    }
+
+    fn slot_mut(&mut self) -> (&K, &mut V) {
+        self.map.slots[self.index]
+            .as_entry_mut()
+            .expect("occupied entry points at a live slot")
+    }
+}
+
+/// An entry whose key is not in the map yet
+pub struct VacantEntry<'a, K, V, S> {
+    pub(crate) map: &'a mut MyHashMap<K, V, S>,
+    pub(crate) hash: u64,
+    pub(crate) key: K,
+    /// Free slot (empty or tombstone) found while probing
+    pub(crate) index: usize,
+}
+
+impl<'a, K, V, S> VacantEntry<'a, K, V, S> {
+    /// Returns the key that would be inserted
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Gives the key back without inserting anything
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Inserts `value` under the entry's key, returning a reference to it
+    pub fn insert(self, value: V) -> &'a mut V {
+        let slot = &mut self.map.slots[self.index];
+        if matches!(slot, Slot::Tombstone) {
+            self.map.tombstones -= 1;
+        }
+        *slot = Slot::Occupied {
+            hash: self.hash,
+            key: self.key,
+            value,
+        };
+        self.map.len += 1;
+        match slot {
+            Slot::Occupied { value, .. } => value,
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::MyHashMap;
+
+    #[test]
+    fn test_happy_path_or_insert_and_modify() {
+        let mut map = MyHashMap::new();
+        map.entry("hits").and_modify(|v| *v += 1).or_insert(1);
+        map.entry("hits").and_modify(|v| *v += 1).or_insert(1);
+        *map.entry("misses").or_default() += 3;
+        assert_eq!(map.get("hits"), Some(&2));
+        assert_eq!(map.get("misses"), Some(&3));
+    }
+
+    #[test]
+    fn test_occupied_entry_insert_and_remove() {
+        let mut map = MyHashMap::new();
+        map.insert("key", 1);
+        match map.entry("key") {
+            crate::Entry::Occupied(mut entry) => {
+                assert_eq!(entry.key(), &"key");
+                assert_eq!(entry.insert(2), 1);
+                assert_eq!(entry.get(), &2);
+                assert_eq!(entry.remove_entry(), ("key", 2));
+            }
+            crate::Entry::Vacant(_) => panic!("key should be present"),
+        }
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn test_edge_case_vacant_entry_reuses_tombstone() {
+        let mut map = MyHashMap::with_hasher(crate::FxBuildHasher::default());
+        for i in 0..4 {
+            map.insert(i, i);
+        }
+        map.remove(&1);
+        map.remove(&2);
+        let tombstones = map.tombstones;
+        let entry = map.entry(2);
+        assert_eq!(entry.key(), &2);
+        entry.or_insert_with(|| 20);
+        assert!(map.tombstones <= tombstones);
+        assert_eq!(map.get(&2), Some(&20));
+        assert_eq!(map.len(), 3);
+    }
+}
@@ -0,0 +1,90 @@
+use std::hash::{BuildHasherDefault, Hasher};
+
+const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+/// The fast, non-cryptographic hash used inside rustc (FxHash)
+///
+/// Much cheaper than the default SipHash for small keys such as integers, but
+/// offers no protection against inputs crafted to collide. Use it for trusted keys.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FxHasher {
+    hash: u64,
+}
+
+impl FxHasher {
+    fn add_to_hash(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(SEED);
+    }
+}
+
+impl Hasher for FxHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            self.add_to_hash(u64::from_le_bytes(chunk.try_into().unwrap()));
+        }
+        for &byte in chunks.remainder() {
+            self.add_to_hash(byte as u64);
+        }
+    }
+
+    fn write_u8(&mut self, i: u8) {
+        self.add_to_hash(i as u64);
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.add_to_hash(i as u64);
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.add_to_hash(i as u64);
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.add_to_hash(i);
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.add_to_hash(i as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+/// Builds `FxHasher`s; pass it to `MyHashMap::with_hasher`
+pub type FxBuildHasher = BuildHasherDefault<FxHasher>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::hash::{BuildHasher, Hash};
+
+    fn fx_hash<T: Hash>(value: &T) -> u64 {
+        FxBuildHasher::default().hash_one(value)
+    }
+
+    #[test]
+    fn test_happy_path_deterministic() {
+        assert_eq!(fx_hash(&"hello"), fx_hash(&"hello"));
+        assert_eq!(fx_hash(&42u64), fx_hash(&42u64));
+    }
+
+    #[test]
+    fn test_different_inputs_differ() {
+        assert_ne!(fx_hash(&"hello"), fx_hash(&"world"));
+        assert_ne!(fx_hash(&1u32), fx_hash(&2u32));
+    }
+
+    #[test]
+    fn test_edge_case_byte_tail() {
+        // 9 bytes: one full word plus a single leftover byte
+        let mut a = FxHasher::default();
+        a.write(b"abcdefghi");
+        let mut b = FxHasher::default();
+        b.write(b"abcdefghj");
+        assert_ne!(a.finish(), b.finish());
+        assert_eq!(FxHasher::default().finish(), 0);
+    }
+}
@@ -0,0 +1,503 @@
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+use std::mem;
+
+pub mod entry;
+pub mod hasher;
+
+pub use entry::{Entry, OccupiedEntry, VacantEntry};
+pub use hasher::{FxBuildHasher, FxHasher};
+
+/// Smallest table allocated once the first entry arrives
+const MIN_SLOTS: usize = 8;
+
+enum Slot<K, V> {
+    Empty,
+    /// A removed entry: lookups must keep probing past it, inserts may reuse it
+    Tombstone,
+    Occupied { hash: u64, key: K, value: V },
+}
+
+impl<K, V> Slot<K, V> {
+    fn as_entry(&self) -> Option<(&K, &V)> {
+        match self {
+            Slot::Occupied { key, value, .. } => Some((key, value)),
+            _ => None,
+        }
+    }
+
+    fn as_entry_mut(&mut self) -> Option<(&K, &mut V)> {
+        match self {
+            Slot::Occupied { key, value, .. } => Some((key, value)),
+            _ => None,
+        }
+    }
+}
+
+/// Hash map using open addressing with linear probing
+///
+/// All entries live in one flat `Vec` of slots. A key is placed at the slot its hash
+/// points to, or the next free one after it. Removed entries leave a tombstone so
+/// later keys in the same probe run stay reachable. The table doubles once live
+/// entries plus tombstones pass 3/4 of the slots, and is rebuilt in place when
+/// tombstones alone are the problem.
+///
+/// The hasher is pluggable: the default `RandomState` (SipHash) resists crafted
+/// collisions, `FxBuildHasher` is faster for trusted keys.
+pub struct MyHashMap<K, V, S = RandomState> {
+    slots: Vec<Slot<K, V>>,
+    len: usize,
+    tombstones: usize,
+    hash_builder: S,
+}
+
+impl<K, V> MyHashMap<K, V, RandomState> {
+    /// Creates an empty map; nothing is allocated until the first insert
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+
+    /// Creates an empty map that can hold `capacity` entries without growing
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_and_hasher(capacity, RandomState::new())
+    }
+}
+
+impl<K, V, S> MyHashMap<K, V, S> {
+    /// Creates an empty map using `hash_builder` to hash keys
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self::with_capacity_and_hasher(0, hash_builder)
+    }
+
+    /// Creates an empty map with room for `capacity` entries, using `hash_builder`
+    pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> Self {
+        let slot_count = if capacity == 0 { 0 } else { slots_for(capacity) };
+        MyHashMap {
+            slots: empty_slots(slot_count),
+            len: 0,
+            tombstones: 0,
+            hash_builder,
+        }
+    }
+
+    /// Returns the number of entries in the map
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if the map is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns how many entries fit before the table has to grow
+    pub fn capacity(&self) -> usize {
+        self.slots.len() / 4 * 3
+    }
+
+    /// Removes every entry, keeping the allocated table
+    pub fn clear(&mut self) {
+        self.slots.iter_mut().for_each(|slot| *slot = Slot::Empty);
+        self.len = 0;
+        self.tombstones = 0;
+    }
+
+    /// Returns the map's hash builder
+    pub fn hasher(&self) -> &S {
+        &self.hash_builder
+    }
+
+    /// Iterates over all entries in table order
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            slots: self.slots.iter(),
+            remaining: self.len,
+        }
+    }
+
+    /// Iterates over all entries with mutable access to the values
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut {
+            slots: self.slots.iter_mut(),
+            remaining: self.len,
+        }
+    }
+
+    /// Iterates over all keys
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    /// Iterates over all values
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    /// Takes the entry out of slot `index`, leaving a tombstone if needed
+    fn remove_at(&mut self, index: usize) -> (K, V) {
+        let mask = self.slots.len() - 1;
+        // If the next slot is empty no probe run continues past this one,
+        // so the slot can become empty again instead of a tombstone
+        let replacement = match self.slots[(index + 1) & mask] {
+            Slot::Empty => Slot::Empty,
+            _ => {
+                self.tombstones += 1;
+                Slot::Tombstone
+            }
+        };
+        self.len -= 1;
+        match mem::replace(&mut self.slots[index], replacement) {
+            Slot::Occupied { key, value, .. } => (key, value),
+            _ => unreachable!("remove_at called on a slot without an entry"),
+        }
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher> MyHashMap<K, V, S> {
+    /// Inserts a key-value pair, returning the previous value for the key if any
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.entry(key) {
+            Entry::Occupied(mut entry) => Some(entry.insert(value)),
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+                None
+            }
+        }
+    }
+
+    /// Returns a reference to the value for `key`
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.find(key)?;
+        self.slots[index].as_entry().map(|(_, value)| value)
+    }
+
+    /// Returns the stored key and its value for `key`
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.find(key)?;
+        self.slots[index].as_entry()
+    }
+
+    /// Returns a mutable reference to the value for `key`
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.find(key)?;
+        self.slots[index].as_entry_mut().map(|(_, value)| value)
+    }
+
+    /// Checks if the map contains `key`
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+
+    /// Removes `key` from the map, returning its value if it was present
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.find(key)?;
+        Some(self.remove_at(index).1)
+    }
+
+    /// Gets the entry for `key` for in-place insertion or update
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, S> {
+        // Make room first so the slot found below stays valid for a vacant insert
+        self.reserve(1);
+        let hash = self.hash_builder.hash_one(&key);
+        match self.probe(hash, &key) {
+            Ok(index) => Entry::Occupied(OccupiedEntry { map: self, index }),
+            Err(index) => Entry::Vacant(VacantEntry {
+                map: self,
+                hash,
+                key,
+                index,
+            }),
+        }
+    }
+
+    /// Makes sure `additional` more entries can be inserted without growing
+    pub fn reserve(&mut self, additional: usize) {
+        let used = self.len + self.tombstones + additional;
+        if used * 4 > self.slots.len() * 3 {
+            // Grow only if the live entries need it; otherwise rebuilding at the
+            // same size is enough to clear out the tombstones
+            let slot_count = slots_for(self.len + additional).max(self.slots.len());
+            self.rehash(slot_count);
+        }
+    }
+
+    fn find<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if self.len == 0 {
+            return None;
+        }
+        self.probe(self.hash_builder.hash_one(key), key).ok()
+    }
+
+    /// Walks the probe run for `hash`: `Ok(index)` of the matching entry, or
+    /// `Err(index)` of the slot a new entry should take
+    fn probe<Q>(&self, hash: u64, key: &Q) -> Result<usize, usize>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        let mask = self.slots.len() - 1;
+        let mut index = home_slot(hash, mask);
+        let mut first_tombstone = None;
+        // The load factor guarantees an empty slot, so this always terminates
+        loop {
+            match &self.slots[index] {
+                Slot::Empty => return Err(first_tombstone.unwrap_or(index)),
+                Slot::Tombstone => {
+                    first_tombstone.get_or_insert(index);
+                }
+                Slot::Occupied { hash: h, key: k, .. } => {
+                    if *h == hash && k.borrow() == key {
+                        return Ok(index);
+                    }
+                }
+            }
+            index = (index + 1) & mask;
+        }
+    }
+
+    fn rehash(&mut self, slot_count: usize) {
+        let old_slots = mem::replace(&mut self.slots, empty_slots(slot_count));
+        let mask = slot_count - 1;
+        for slot in old_slots {
+            if let Slot::Occupied { hash, key, value } = slot {
+                // Keys are already unique, so only a free slot is needed
+                let mut index = home_slot(hash, mask);
+                while !matches!(self.slots[index], Slot::Empty) {
+                    index = (index + 1) & mask;
+                }
+                self.slots[index] = Slot::Occupied { hash, key, value };
+            }
+        }
+        self.tombstones = 0;
+    }
+}
+
+/// Number of slots needed to hold `entries` under the 3/4 load factor
+fn slots_for(entries: usize) -> usize {
+    (entries * 4).div_ceil(3).max(MIN_SLOTS).next_power_of_two()
+}
+
+fn empty_slots<K, V>(count: usize) -> Vec<Slot<K, V>> {
+    (0..count).map(|_| Slot::Empty).collect()
+}
+
+/// Slot a hash starts probing from; folds in the high bits, which weak hashes
+/// like FxHash mix better than the low ones
+fn home_slot(hash: u64, mask: usize) -> usize {
+    (hash ^ (hash >> 32)) as usize & mask
+}
+
+impl<K, V, S: Default> Default for MyHashMap<K, V, S> {
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, S> fmt::Debug for MyHashMap<K, V, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher> Extend<(K, V)> for MyHashMap<K, V, S> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher + Default> FromIterator<(K, V)> for MyHashMap<K, V, S> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::default();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a MyHashMap<K, V, S> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the entries of a `MyHashMap`
+pub struct Iter<'a, K, V> {
+    slots: std::slice::Iter<'a, Slot<K, V>>,
+    remaining: usize,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.slots.by_ref().find_map(Slot::as_entry)?;
+        self.remaining -= 1;
+        Some(entry)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+/// Iterator over the entries of a `MyHashMap` with mutable values
+pub struct IterMut<'a, K, V> {
+    slots: std::slice::IterMut<'a, Slot<K, V>>,
+    remaining: usize,
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.slots.by_ref().find_map(Slot::as_entry_mut)?;
+        self.remaining -= 1;
+        Some(entry)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_happy_path_insert_get_remove() {
+        let mut map = MyHashMap::new();
+        assert_eq!(map.insert("one", 1), None);
+        assert_eq!(map.insert("two", 2), None);
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get("one"), Some(&1));
+        assert_eq!(map.get_key_value("two"), Some((&"two", &2)));
+
+        assert_eq!(map.insert("one", 10), Some(1), "Overwrite returns the old value");
+        assert_eq!(map.len(), 2);
+
+        *map.get_mut("two").unwrap() += 5;
+        assert_eq!(map.remove("two"), Some(7));
+        assert!(!map.contains_key("two"));
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn test_grows_and_keeps_every_entry() {
+        let mut map = MyHashMap::with_hasher(FxBuildHasher::default());
+        for i in 0..1000 {
+            map.insert(i, i * 2);
+        }
+        assert_eq!(map.len(), 1000);
+        assert!(map.capacity() >= 1000);
+        assert!((0..1000).all(|i| map.get(&i) == Some(&(i * 2))));
+        assert_eq!(map.iter().count(), 1000);
+    }
+
+    #[test]
+    fn test_tombstones_keep_probe_runs_reachable() {
+        let mut map = MyHashMap::with_hasher(FxBuildHasher::default());
+        for i in 0..6 {
+            map.insert(i, i);
+        }
+        // Removing keys from the middle of probe runs must not hide later keys
+        for i in (0..6).step_by(2) {
+            assert_eq!(map.remove(&i), Some(i));
+        }
+        assert!((1..6).step_by(2).all(|i| map.get(&i) == Some(&i)));
+        assert!((0..6).step_by(2).all(|i| map.get(&i).is_none()));
+    }
+
+    #[test]
+    fn test_churn_does_not_grow_the_table() {
+        let mut map = MyHashMap::new();
+        for i in 0..10_000 {
+            map.insert(i, i);
+            map.remove(&i);
+        }
+        assert!(map.is_empty());
+        assert_eq!(map.capacity(), MIN_SLOTS / 4 * 3, "Tombstones are cleared, not grown around");
+    }
+
+    #[test]
+    fn test_entry_api_counts_words() {
+        let mut counts: MyHashMap<String, usize> = MyHashMap::new();
+        for word in "the cat and the hat and the bat".split_whitespace() {
+            *counts.entry(word.to_string()).or_insert(0) += 1;
+        }
+        assert_eq!(counts.get("the"), Some(&3));
+        assert_eq!(counts.get("and"), Some(&2));
+        assert_eq!(counts.get("cat"), Some(&1));
+        assert_eq!(counts.len(), 5);
+    }
+
+    #[test]
+    fn test_collect_extend_and_iterate() {
+        let mut map: MyHashMap<u32, &str, FxBuildHasher> = [(1, "a"), (2, "b")].into_iter().collect();
+        map.extend([(3, "c"), (1, "z")]);
+        for (_, value) in map.iter_mut() {
+            *value = if *value == "z" { "A" } else { *value };
+        }
+        let mut entries: Vec<_> = (&map).into_iter().map(|(k, v)| (*k, *v)).collect();
+        entries.sort();
+        assert_eq!(entries, vec![(1, "A"), (2, "b"), (3, "c")]);
+        assert_eq!(map.keys().sum::<u32>(), 6);
+        assert_eq!(map.values().count(), 3);
+    }
+
+    #[test]
+    fn test_unhappy_path_missing_keys() {
+        let mut map: MyHashMap<&str, i32> = MyHashMap::new();
+        assert_eq!(map.get("nope"), None);
+        assert_eq!(map.remove("nope"), None);
+        map.insert("yes", 1);
+        assert_eq!(map.get_mut("nope"), None);
+        assert_eq!(map.remove("nope"), None);
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn test_edge_case_empty_and_cleared_maps() {
+        let map: MyHashMap<i32, i32> = MyHashMap::new();
+        assert_eq!(map.capacity(), 0, "Nothing is allocated up front");
+        assert_eq!(format!("{:?}", map), "{}");
+
+        let mut map = MyHashMap::with_capacity(100);
+        assert!(map.capacity() >= 100);
+        map.insert(1, 1);
+        map.clear();
+        assert!(map.is_empty());
+        assert!(map.capacity() >= 100, "Clear keeps the table");
+        assert_eq!(map.get(&1), None);
+    }
+}
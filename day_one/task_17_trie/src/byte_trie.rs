@@ -0,0 +1,284 @@
+/// One trie node; children are kept sorted by byte so iteration is lexicographic
+struct Node<V> {
+    children: Vec<(u8, usize)>,
+    value: Option<V>,
+}
+
+impl<V> Node<V> {
+    fn new() -> Self {
+        Node {
+            children: Vec::new(),
+            value: None,
+        }
+    }
+
+    fn child(&self, byte: u8) -> Option<usize> {
+        self.children
+            .binary_search_by_key(&byte, |&(b, _)| b)
+            .ok()
+            .map(|pos| self.children[pos].1)
+    }
+}
+
+/// Prefix tree mapping byte strings to values
+///
+/// Nodes live in a single arena (`Vec`) and refer to each other by index, in the
+/// spirit of task_10's memory arena: one allocation for the whole tree, no
+/// per-node boxes. Slots freed by `remove` go on a free list and are reused.
+pub struct ByteTrie<V> {
+    nodes: Vec<Node<V>>,
+    free: Vec<usize>,
+    len: usize,
+}
+
+const ROOT: usize = 0;
+
+impl<V> ByteTrie<V> {
+    /// Creates an empty trie
+    pub fn new() -> Self {
+        ByteTrie {
+            nodes: vec![Node::new()],
+            free: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Returns the number of keys stored
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if the trie holds no keys
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Inserts `key`, returning the previous value if the key was present
+    pub fn insert(&mut self, key: &[u8], value: V) -> Option<V> {
+        let mut node = ROOT;
+        for &byte in key {
+            node = match self.nodes[node].child(byte) {
+                Some(child) => child,
+                None => self.add_child(node, byte),
+            };
+        }
+        let previous = self.nodes[node].value.replace(value);
+        if previous.is_none() {
+            self.len += 1;
+        }
+        previous
+    }
+
+    /// Returns the value stored for `key`
+    pub fn get(&self, key: &[u8]) -> Option<&V> {
+        self.find(key)
+            .and_then(|node| self.nodes[node].value.as_ref())
+    }
+
+    /// Returns a mutable reference to the value stored for `key`
+    pub fn get_mut(&mut self, key: &[u8]) -> Option<&mut V> {
+        let node = self.find(key)?;
+        self.nodes[node].value.as_mut()
+    }
+
+    /// Checks if `key` is stored
+    pub fn contains(&self, key: &[u8]) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Checks if any stored key starts with `prefix`
+    pub fn starts_with(&self, prefix: &[u8]) -> bool {
+        // Removal prunes dead branches, so any reachable node leads to a key
+        self.find(prefix).is_some() && (!prefix.is_empty() || !self.is_empty())
+    }
+
+    /// Removes `key`, returning its value; branches left without keys are freed
+    pub fn remove(&mut self, key: &[u8]) -> Option<V> {
+        let mut path = Vec::with_capacity(key.len() + 1);
+        let mut node = ROOT;
+        path.push(node);
+        for &byte in key {
+            node = self.nodes[node].child(byte)?;
+            path.push(node);
+        }
+        let value = self.nodes[node].value.take()?;
+        self.len -= 1;
+
+        // Walk back up, unlinking nodes that no longer lead to any key
+        for depth in (1..path.len()).rev() {
+            let node = &self.nodes[path[depth]];
+            if node.value.is_some() || !node.children.is_empty() {
+                break;
+            }
+            let parent = &mut self.nodes[path[depth - 1]].children;
+            let byte = key[depth - 1];
+            if let Ok(pos) = parent.binary_search_by_key(&byte, |&(b, _)| b) {
+                parent.remove(pos);
+            }
+            self.free.push(path[depth]);
+        }
+        Some(value)
+    }
+
+    /// Iterates over every key starting with `prefix`, with its value, in byte order
+    pub fn iter_prefix(&self, prefix: &[u8]) -> PrefixIter<'_, V> {
+        let stack = match self.find(prefix) {
+            Some(node) => vec![(node, prefix.to_vec())],
+            None => Vec::new(),
+        };
+        PrefixIter { trie: self, stack }
+    }
+
+    /// Iterates over every key with its value, in byte order
+    pub fn iter(&self) -> PrefixIter<'_, V> {
+        self.iter_prefix(&[])
+    }
+
+    /// Removes every key, keeping the arena's allocation
+    pub fn clear(&mut self) {
+        self.nodes.truncate(1);
+        self.nodes[ROOT] = Node::new();
+        self.free.clear();
+        self.len = 0;
+    }
+
+    fn find(&self, key: &[u8]) -> Option<usize> {
+        key.iter()
+            .try_fold(ROOT, |node, &byte| self.nodes[node].child(byte))
+    }
+
+    fn add_child(&mut self, parent: usize, byte: u8) -> usize {
+        let child = match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = Node::new();
+                index
+            }
+            None => {
+                self.nodes.push(Node::new());
+                self.nodes.len() - 1
+            }
+        };
+        let children = &mut self.nodes[parent].children;
+        let pos = children.partition_point(|&(b, _)| b < byte);
+        children.insert(pos, (byte, child));
+        child
+    }
+}
+
+impl<V> Default for ByteTrie<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Depth-first iterator over the keys below a node, from `ByteTrie::iter_prefix`
+pub struct PrefixIter<'a, V> {
+    trie: &'a ByteTrie<V>,
+    stack: Vec<(usize, Vec<u8>)>,
+}
+
+impl<'a, V> Iterator for PrefixIter<'a, V> {
+    type Item = (Vec<u8>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((index, key)) = self.stack.pop() {
+            let node = &self.trie.nodes[index];
+            // Push in reverse so the smallest byte is visited first
+            for &(byte, child) in node.children.iter().rev() {
+                let mut child_key = key.clone();
+                child_key.push(byte);
+                self.stack.push((child, child_key));
+            }
+            if let Some(value) = node.value.as_ref() {
+                return Some((key, value));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_happy_path_insert_get_overwrite() {
+        let mut trie = ByteTrie::new();
+        assert_eq!(trie.insert(b"car", 1), None);
+        assert_eq!(trie.insert(b"cart", 2), None);
+        assert_eq!(trie.insert(b"car", 3), Some(1));
+        assert_eq!(trie.len(), 2);
+        assert_eq!(trie.get(b"car"), Some(&3));
+        assert_eq!(trie.get(b"ca"), None, "Prefixes are not keys");
+        *trie.get_mut(b"cart").unwrap() += 10;
+        assert_eq!(trie.get(b"cart"), Some(&12));
+    }
+
+    #[test]
+    fn test_iteration_is_lexicographic() {
+        let mut trie = ByteTrie::new();
+        for (i, key) in [&b"b"[..], b"ab", b"a", b"abc", b"ba"]
+            .into_iter()
+            .enumerate()
+        {
+            trie.insert(key, i);
+        }
+        let keys: Vec<_> = trie.iter().map(|(k, _)| k).collect();
+        assert_eq!(
+            keys,
+            vec![
+                b"a".to_vec(),
+                b"ab".to_vec(),
+                b"abc".to_vec(),
+                b"b".to_vec(),
+                b"ba".to_vec()
+            ]
+        );
+        let under_ab: Vec<_> = trie.iter_prefix(b"ab").map(|(k, v)| (k, *v)).collect();
+        assert_eq!(under_ab, vec![(b"ab".to_vec(), 1), (b"abc".to_vec(), 3)]);
+    }
+
+    #[test]
+    fn test_remove_prunes_and_reuses_nodes() {
+        let mut trie = ByteTrie::new();
+        trie.insert(b"team", 1);
+        trie.insert(b"tea", 2);
+        let arena_size = trie.nodes.len();
+
+        assert_eq!(trie.remove(b"team"), Some(1));
+        assert!(!trie.starts_with(b"team"));
+        assert!(trie.starts_with(b"tea"), "The shared branch is kept");
+        assert_eq!(trie.free.len(), 1);
+
+        trie.insert(b"teal", 3);
+        assert_eq!(trie.nodes.len(), arena_size, "Freed slot was reused");
+        assert_eq!(trie.get(b"teal"), Some(&3));
+    }
+
+    #[test]
+    fn test_unhappy_path_missing_keys() {
+        let mut trie: ByteTrie<u8> = ByteTrie::new();
+        trie.insert(b"abc", 1);
+        assert_eq!(trie.remove(b"ab"), None, "Prefix without a value");
+        assert_eq!(trie.remove(b"abcd"), None);
+        assert_eq!(trie.remove(b"xyz"), None);
+        assert_eq!(trie.iter_prefix(b"x").count(), 0);
+        assert_eq!(trie.len(), 1);
+    }
+
+    #[test]
+    fn test_edge_case_empty_key_and_clear() {
+        let mut trie = ByteTrie::new();
+        assert!(!trie.starts_with(b""));
+        trie.insert(b"", "root");
+        assert!(trie.contains(b""));
+        assert!(trie.starts_with(b""));
+        trie.insert(&[0xff, 0x00], "binary");
+        assert_eq!(trie.iter().count(), 2);
+
+        trie.clear();
+        assert!(trie.is_empty());
+        assert_eq!(trie.get(b""), None);
+        assert_eq!(trie.nodes.len(), 1);
+    }
+}
@@ -0,0 +1,131 @@
+pub mod byte_trie;
+
+pub use byte_trie::{ByteTrie, PrefixIter};
+
+/// Set of strings stored as a prefix tree, for prefix queries and autocomplete
+#[derive(Default)]
+pub struct Trie {
+    inner: ByteTrie<()>,
+}
+
+impl Trie {
+    /// Creates an empty trie
+    pub fn new() -> Self {
+        Trie {
+            inner: ByteTrie::new(),
+        }
+    }
+
+    /// Adds a word, returning `false` if it was already present
+    pub fn insert(&mut self, word: &str) -> bool {
+        self.inner.insert(word.as_bytes(), ()).is_none()
+    }
+
+    /// Checks if the exact word is stored
+    pub fn contains(&self, word: &str) -> bool {
+        self.inner.contains(word.as_bytes())
+    }
+
+    /// Removes a word, returning `true` if it was present
+    pub fn remove(&mut self, word: &str) -> bool {
+        self.inner.remove(word.as_bytes()).is_some()
+    }
+
+    /// Checks if any stored word starts with `prefix`
+    pub fn starts_with(&self, prefix: &str) -> bool {
+        self.inner.starts_with(prefix.as_bytes())
+    }
+
+    /// Iterates over every stored word starting with `prefix`, in byte order
+    pub fn words_with_prefix<'a>(&'a self, prefix: &str) -> impl Iterator<Item = String> + 'a {
+        self.inner
+            .iter_prefix(prefix.as_bytes())
+            .map(|(key, _)| String::from_utf8(key).expect("words are inserted as str"))
+    }
+
+    /// Iterates over every stored word, in byte order
+    pub fn words(&self) -> impl Iterator<Item = String> + '_ {
+        self.words_with_prefix("")
+    }
+
+    /// Returns the number of stored words
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Checks if the trie holds no words
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Removes every word
+    pub fn clear(&mut self) {
+        self.inner = ByteTrie::new();
+    }
+}
+
+impl<'a> FromIterator<&'a str> for Trie {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(iter: I) -> Self {
+        let mut trie = Trie::new();
+        for word in iter {
+            trie.insert(word);
+        }
+        trie
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_happy_path_autocomplete() {
+        let trie: Trie = ["apple", "app", "application", "banana", "apply"]
+            .into_iter()
+            .collect();
+        let suggestions: Vec<_> = trie.words_with_prefix("appl").collect();
+        assert_eq!(suggestions, vec!["apple", "application", "apply"]);
+        assert!(trie.starts_with("ban"));
+        assert!(trie.contains("app"));
+        assert!(!trie.contains("ap"));
+    }
+
+    #[test]
+    fn test_insert_and_remove_report_changes() {
+        let mut trie = Trie::new();
+        assert!(trie.insert("rust"));
+        assert!(!trie.insert("rust"), "Duplicate insert");
+        assert_eq!(trie.len(), 1);
+        assert!(trie.remove("rust"));
+        assert!(!trie.remove("rust"));
+        assert!(trie.is_empty());
+        assert!(!trie.starts_with("r"));
+    }
+
+    #[test]
+    fn test_unhappy_path_unknown_prefix() {
+        let trie: Trie = ["one", "two"].into_iter().collect();
+        assert!(!trie.starts_with("three"));
+        assert_eq!(trie.words_with_prefix("th").count(), 0);
+    }
+
+    #[test]
+    fn test_edge_case_multibyte_words() {
+        let trie: Trie = ["café", "cafe", "über"].into_iter().collect();
+        assert_eq!(
+            trie.words().collect::<Vec<_>>(),
+            vec!["cafe", "café", "über"]
+        );
+        assert!(trie.starts_with("caf"));
+        assert_eq!(trie.words_with_prefix("ü").count(), 1);
+    }
+
+    #[test]
+    fn test_clear_removes_every_word() {
+        let mut trie: Trie = ["a", "ab", "abc"].into_iter().collect();
+        trie.clear();
+        assert!(trie.is_empty());
+        assert!(!trie.starts_with("a"));
+        assert!(trie.insert("ab"));
+    }
+}
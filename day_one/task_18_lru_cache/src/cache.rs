@@ -0,0 +1,338 @@
+use std::borrow::Borrow;
+use std::hash::Hash;
+use std::mem;
+
+use task_16_hashmap::MyHashMap;
+
+use crate::policy::EvictionPolicy;
+use crate::CacheStats;
+
+/// Bounded cache whose eviction strategy is a pluggable `EvictionPolicy`
+///
+/// Values live in a `MyHashMap`; the policy only tracks keys. `Cache<K, V, Lru<K>>`
+/// behaves like `LruCache` without per-entry TTLs, but pays O(log n) per
+/// operation for the policy's ordered index, so `LruCache` stays the faster
+/// choice when LRU is all that is needed.
+///
+/// Expiring policies such as `Ttl` are checked lazily: an expired entry is
+/// dropped when it is looked up, so `len` can include entries that have
+/// expired but not been noticed yet.
+pub struct Cache<K, V, P> {
+    map: MyHashMap<K, V>,
+    policy: P,
+    capacity: usize,
+    stats: CacheStats,
+}
+
+impl<K: Hash + Eq + Clone, V, P: EvictionPolicy<K>> Cache<K, V, P> {
+    /// Creates a cache holding at most `capacity` entries, evicting by `policy`
+    pub fn new(capacity: usize, policy: P) -> Self {
+        assert!(capacity > 0, "Cache capacity must be non-zero");
+        Cache {
+            map: MyHashMap::with_capacity(capacity),
+            policy,
+            capacity,
+            stats: CacheStats::default(),
+        }
+    }
+
+    /// Inserts or replaces an entry, returning the old value if it was unexpired
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cache is full and the policy has no victim to offer,
+    /// which means it lost track of the cache's keys.
+    pub fn put(&mut self, key: K, value: V) -> Option<V> {
+        let expired = self.map.contains_key(&key) && self.policy.is_expired(&key);
+        if let Some(slot) = self.map.get_mut(&key) {
+            let old = mem::replace(slot, value);
+            self.policy.on_insert(&key);
+            if expired {
+                self.stats.expirations += 1;
+                return None;
+            }
+            return Some(old);
+        }
+
+        while self.map.len() >= self.capacity {
+            let victim = self
+                .policy
+                .select_victim()
+                .expect("eviction policy must offer a victim when the cache is full");
+            if self.map.remove(&victim).is_some() {
+                self.stats.evictions += 1;
+            }
+        }
+        self.policy.on_insert(&key);
+        self.map.insert(key, value);
+        None
+    }
+
+    /// Returns the value for `key`, reporting the hit to the policy
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if !self.lookup(key) {
+            return None;
+        }
+        self.map.get(key)
+    }
+
+    /// Returns a mutable reference to the value for `key`, reporting the hit to the policy
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if !self.lookup(key) {
+            return None;
+        }
+        self.map.get_mut(key)
+    }
+
+    /// Returns the value for `key` without telling the policy or touching statistics
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (stored, value) = self.map.get_key_value(key)?;
+        (!self.policy.is_expired(stored)).then_some(value)
+    }
+
+    /// Checks if `key` is cached and unexpired, without telling the policy
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.peek(key).is_some()
+    }
+
+    /// Removes `key`, returning its value if it was cached and unexpired
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (stored, _) = self.map.get_key_value(key)?;
+        let expired = self.policy.is_expired(stored);
+        self.policy.on_remove(stored);
+        let value = self.map.remove(key)?;
+        (!expired).then_some(value)
+    }
+
+    /// Returns the number of cached entries, including expired ones not yet dropped
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Checks if the cache holds no entries
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Returns the maximum number of entries
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the hit/miss statistics collected so far
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    /// Returns the eviction policy
+    pub fn policy(&self) -> &P {
+        &self.policy
+    }
+
+    /// Iterates over unexpired entries in no particular order, without telling the policy
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.map
+            .iter()
+            .filter(|(key, _)| !self.policy.is_expired(key))
+    }
+
+    /// Removes every entry; statistics are kept
+    pub fn clear(&mut self) {
+        for key in self.map.keys() {
+            self.policy.on_remove(key);
+        }
+        self.map.clear();
+    }
+
+    // Counts a hit or miss for `key`, dropping it first if it has expired
+    fn lookup<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let Some((stored, _)) = self.map.get_key_value(key) else {
+            self.stats.misses += 1;
+            return false;
+        };
+        if self.policy.is_expired(stored) {
+            self.policy.on_remove(stored);
+            self.map.remove(key);
+            self.stats.expirations += 1;
+            self.stats.misses += 1;
+            return false;
+        }
+        self.policy.on_hit(stored);
+        self.stats.hits += 1;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::{Fifo, Lfu, Lru, Random, Ttl};
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn test_happy_path_lru_policy_evicts_least_recent() {
+        let mut cache = Cache::new(2, Lru::new());
+        cache.put("a", 1);
+        cache.put("b", 2);
+        assert_eq!(cache.get("a"), Some(&1));
+        cache.put("c", 3);
+
+        assert!(!cache.contains("b"), "b was least recently used");
+        assert!(cache.contains("a") && cache.contains("c"));
+        assert_eq!(cache.stats().evictions, 1);
+    }
+
+    #[test]
+    fn test_happy_path_lfu_policy_keeps_popular_keys() {
+        let mut cache = Cache::new(2, Lfu::new());
+        cache.put("a", 1);
+        cache.put("b", 2);
+        cache.get("a");
+        cache.get("a");
+        cache.get("b");
+        assert_eq!(cache.policy().frequency(&"a"), Some(3));
+
+        cache.put("c", 3);
+        assert!(!cache.contains("b"));
+        // c now has the lowest count, so it goes before a
+        cache.put("d", 4);
+        assert!(cache.contains("a") && cache.contains("d"));
+        assert!(!cache.contains("c"));
+    }
+
+    #[test]
+    fn test_happy_path_fifo_policy_ignores_hits_and_updates() {
+        let mut cache = Cache::new(2, Fifo::new());
+        cache.put("a", 1);
+        cache.put("b", 2);
+        cache.get("a");
+        assert_eq!(cache.put("a", 10), Some(1));
+        cache.put("c", 3);
+        assert_eq!(cache.peek("a"), None, "a was inserted first");
+        assert_eq!(cache.peek("b"), Some(&2));
+    }
+
+    #[test]
+    fn test_random_policy_is_reproducible_for_a_seed() {
+        let survivors = |seed| {
+            let mut cache = Cache::new(4, Random::with_seed(seed));
+            for key in 0..20 {
+                cache.put(key, key);
+            }
+            let mut keys: Vec<i32> = cache.iter().map(|(&key, _)| key).collect();
+            keys.sort();
+            keys
+        };
+        assert_eq!(survivors(7).len(), 4);
+        assert_eq!(survivors(7), survivors(7));
+    }
+
+    #[test]
+    fn test_ttl_policy_expires_entries() {
+        let mut cache = Cache::new(4, Ttl::new(Duration::from_millis(20)));
+        cache.put("a", 1);
+        cache.put("b", 2);
+        thread::sleep(Duration::from_millis(30));
+        cache.put("b", 20);
+
+        assert_eq!(cache.get("a"), None);
+        assert_eq!(cache.get("b"), Some(&20), "Rewriting b restarted its clock");
+        assert_eq!(cache.len(), 1);
+        let stats = cache.stats();
+        // One expiry noticed by the rewrite of b, one by the lookup of a
+        assert_eq!((stats.expirations, stats.hits, stats.misses), (2, 1, 1));
+    }
+
+    #[test]
+    fn test_custom_policy_plugs_in() {
+        // Always evicts the largest key
+        struct LargestFirst(std::collections::BTreeSet<u32>);
+
+        impl EvictionPolicy<u32> for LargestFirst {
+            fn on_hit(&mut self, _key: &u32) {}
+            fn on_insert(&mut self, key: &u32) {
+                self.0.insert(*key);
+            }
+            fn on_remove(&mut self, key: &u32) {
+                self.0.remove(key);
+            }
+            fn select_victim(&mut self) -> Option<u32> {
+                self.0.pop_last()
+            }
+        }
+
+        let mut cache = Cache::new(3, LargestFirst(Default::default()));
+        for key in [5, 1, 9, 3] {
+            cache.put(key, ());
+        }
+        let mut keys: Vec<u32> = cache.iter().map(|(&key, _)| key).collect();
+        keys.sort();
+        assert_eq!(keys, vec![1, 3, 5]);
+    }
+
+    #[test]
+    fn test_unhappy_path_misses_and_removals() {
+        let mut cache: Cache<String, i32, _> = Cache::new(2, Lru::new());
+        assert_eq!(cache.get("missing"), None);
+        assert_eq!(cache.remove("missing"), None);
+        cache.put("a".to_string(), 1);
+        assert_eq!(cache.remove("a"), Some(1));
+        assert_eq!(cache.stats().misses, 1);
+
+        // The removed key must not come back as a victim
+        cache.put("b".to_string(), 2);
+        cache.put("c".to_string(), 3);
+        cache.put("d".to_string(), 4);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.stats().evictions, 1);
+    }
+
+    #[test]
+    fn test_edge_case_capacity_one_and_clear() {
+        let mut cache = Cache::new(1, Lfu::new());
+        cache.put(1, "one");
+        cache.put(2, "two");
+        assert_eq!(cache.peek(&1), None);
+        if let Some(value) = cache.get_mut(&2) {
+            *value = "TWO";
+        }
+        assert_eq!(cache.peek(&2), Some(&"TWO"));
+
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(cache.policy().frequency(&2), None);
+        cache.put(3, "three");
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity must be non-zero")]
+    fn test_unhappy_path_zero_capacity_panics() {
+        Cache::<i32, i32, _>::new(0, Fifo::new());
+    }
+}
@@ -0,0 +1,459 @@
+use std::borrow::Borrow;
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+
+use task_16_hashmap::MyHashMap;
+
+pub mod cache;
+pub mod policy;
+
+pub use cache::Cache;
+pub use policy::{EvictionPolicy, Fifo, Lfu, Lru, Random, Ttl};
+
+/// Hit/miss counters for an `LruCache` or `Cache`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Entries pushed out because the cache was full
+    pub evictions: u64,
+    /// Entries dropped because their TTL ran out
+    pub expirations: u64,
+}
+
+impl CacheStats {
+    /// Fraction of lookups that were hits, 0.0 if there were none
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    expires_at: Option<Instant>,
+    /// Neighbour closer to the most recently used end
+    prev: Option<usize>,
+    /// Neighbour closer to the least recently used end
+    next: Option<usize>,
+}
+
+impl<K, V> Node<K, V> {
+    // Only entries with a TTL read the clock, so caches without TTLs also work
+    // where `Instant` is unavailable (wasm32)
+    fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|deadline| deadline <= Instant::now())
+    }
+}
+
+/// Least-recently-used cache with O(1) `get`, `put` and `pop_lru`
+///
+/// A `MyHashMap` from task_16 maps each key to a slot in `nodes`; the slots form
+/// a doubly linked list by index, most recently used at the head. Touching an
+/// entry moves it to the head, and a full cache evicts from the tail.
+///
+/// Entries may carry a time-to-live. Expired entries are dropped lazily when
+/// they are looked up or reach the tail, or eagerly via `purge_expired`, so
+/// `len` can include expired entries that have not been noticed yet.
+pub struct LruCache<K, V> {
+    map: MyHashMap<K, usize>,
+    nodes: Vec<Option<Node<K, V>>>,
+    free: Vec<usize>,
+    head: Option<usize>,
+    tail: Option<usize>,
+    capacity: usize,
+    default_ttl: Option<Duration>,
+    stats: CacheStats,
+}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+    /// Creates a cache holding at most `capacity` entries, which never expire
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "LruCache capacity must be non-zero");
+        LruCache {
+            map: MyHashMap::with_capacity(capacity),
+            nodes: Vec::with_capacity(capacity),
+            free: Vec::new(),
+            head: None,
+            tail: None,
+            capacity,
+            default_ttl: None,
+            stats: CacheStats::default(),
+        }
+    }
+
+    /// Creates a cache whose entries expire `ttl` after they were last `put`
+    pub fn with_ttl(capacity: usize, ttl: Duration) -> Self {
+        let mut cache = Self::new(capacity);
+        cache.default_ttl = Some(ttl);
+        cache
+    }
+
+    /// Inserts or replaces an entry using the default TTL, returning the old value
+    pub fn put(&mut self, key: K, value: V) -> Option<V> {
+        self.put_with_deadline(key, value, self.default_ttl)
+    }
+
+    /// Inserts or replaces an entry that expires after `ttl`, returning the old value
+    pub fn put_with_ttl(&mut self, key: K, value: V, ttl: Duration) -> Option<V> {
+        self.put_with_deadline(key, value, Some(ttl))
+    }
+
+    /// Returns the value for `key` and marks it most recently used
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.lookup(key)?;
+        self.node(index).map(|node| &node.value)
+    }
+
+    /// Returns a mutable reference to the value for `key` and marks it most recently used
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.lookup(key)?;
+        self.nodes[index].as_mut().map(|node| &mut node.value)
+    }
+
+    /// Returns the value for `key` without touching recency or statistics
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let node = self.node(*self.map.get(key)?)?;
+        (!node.is_expired()).then_some(&node.value)
+    }
+
+    /// Checks if `key` is cached and unexpired, without touching recency or statistics
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.peek(key).is_some()
+    }
+
+    /// Removes `key`, returning its value if it was cached and unexpired
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = *self.map.get(key)?;
+        let node = self.unlink(index);
+        (!node.is_expired()).then_some(node.value)
+    }
+
+    /// Removes and returns the least recently used unexpired entry
+    pub fn pop_lru(&mut self) -> Option<(K, V)> {
+        while let Some(tail) = self.tail {
+            let node = self.unlink(tail);
+            if node.is_expired() {
+                self.stats.expirations += 1;
+                continue;
+            }
+            return Some((node.key, node.value));
+        }
+        None
+    }
+
+    /// Drops every expired entry, returning how many were removed
+    pub fn purge_expired(&mut self) -> usize {
+        let expired: Vec<usize> = (0..self.nodes.len())
+            .filter(|&index| self.node(index).is_some_and(|node| node.is_expired()))
+            .collect();
+        for &index in &expired {
+            self.unlink(index);
+        }
+        self.stats.expirations += expired.len() as u64;
+        expired.len()
+    }
+
+    /// Returns the number of cached entries, including expired ones not yet dropped
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Checks if the cache holds no entries
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Returns the maximum number of entries
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the hit/miss statistics collected so far
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    /// Iterates over unexpired entries from most to least recently used,
+    /// without touching recency or statistics
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        let mut cursor = self.head;
+        std::iter::from_fn(move || {
+            let node = self.node(cursor?)?;
+            cursor = node.next;
+            Some(node)
+        })
+        .filter(|node| !node.is_expired())
+        .map(|node| (&node.key, &node.value))
+    }
+
+    /// Removes every entry; statistics are kept
+    pub fn clear(&mut self) {
+        self.map.clear();
+        self.nodes.clear();
+        self.free.clear();
+        self.head = None;
+        self.tail = None;
+    }
+
+    fn put_with_deadline(&mut self, key: K, value: V, ttl: Option<Duration>) -> Option<V> {
+        let expires_at = ttl.map(|ttl| Instant::now() + ttl);
+        if let Some(&index) = self.map.get(&key) {
+            let node = self.nodes[index].as_mut().expect("mapped slot is occupied");
+            let expired = node.is_expired();
+            node.expires_at = expires_at;
+            let old = std::mem::replace(&mut node.value, value);
+            self.move_to_front(index);
+            return (!expired).then_some(old);
+        }
+
+        if self.map.len() == self.capacity {
+            self.evict_one();
+        }
+        let node = Node {
+            key: key.clone(),
+            value,
+            expires_at,
+            prev: None,
+            next: None,
+        };
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = Some(node);
+                index
+            }
+            None => {
+                self.nodes.push(Some(node));
+                self.nodes.len() - 1
+            }
+        };
+        self.map.insert(key, index);
+        self.attach_front(index);
+        None
+    }
+
+    /// Makes room for one entry by dropping the tail
+    fn evict_one(&mut self) {
+        if let Some(tail) = self.tail {
+            let node = self.unlink(tail);
+            if node.is_expired() {
+                self.stats.expirations += 1;
+            } else {
+                self.stats.evictions += 1;
+            }
+        }
+    }
+
+    /// Finds a live entry, updating statistics and recency
+    fn lookup<Q>(&mut self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let Some(&index) = self.map.get(key) else {
+            self.stats.misses += 1;
+            return None;
+        };
+        if self
+            .node(index)
+            .is_some_and(|node| node.is_expired())
+        {
+            self.unlink(index);
+            self.stats.expirations += 1;
+            self.stats.misses += 1;
+            return None;
+        }
+        self.stats.hits += 1;
+        self.move_to_front(index);
+        Some(index)
+    }
+
+    fn node(&self, index: usize) -> Option<&Node<K, V>> {
+        self.nodes.get(index).and_then(Option::as_ref)
+    }
+
+    fn links_mut(&mut self, index: usize) -> &mut Node<K, V> {
+        self.nodes[index].as_mut().expect("linked slot is occupied")
+    }
+
+    fn move_to_front(&mut self, index: usize) {
+        if self.head != Some(index) {
+            self.detach(index);
+            self.attach_front(index);
+        }
+    }
+
+    fn attach_front(&mut self, index: usize) {
+        let old_head = self.head;
+        {
+            let node = self.links_mut(index);
+            node.prev = None;
+            node.next = old_head;
+        }
+        match old_head {
+            Some(head) => self.links_mut(head).prev = Some(index),
+            None => self.tail = Some(index),
+        }
+        self.head = Some(index);
+    }
+
+    fn detach(&mut self, index: usize) {
+        let (prev, next) = {
+            let node = self.links_mut(index);
+            (node.prev.take(), node.next.take())
+        };
+        match prev {
+            Some(prev) => self.links_mut(prev).next = next,
+            None => self.head = next,
+        }
+        match next {
+            Some(next) => self.links_mut(next).prev = prev,
+            None => self.tail = prev,
+        }
+    }
+
+    /// Removes the entry in slot `index` from the list, the map and the slab
+    fn unlink(&mut self, index: usize) -> Node<K, V> {
+        self.detach(index);
+        let node = self.nodes[index].take().expect("linked slot is occupied");
+        self.map.remove(&node.key);
+        self.free.push(index);
+        node
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn test_happy_path_evicts_least_recently_used() {
+        let mut cache = LruCache::new(2);
+        cache.put("a", 1);
+        cache.put("b", 2);
+        assert_eq!(cache.get("a"), Some(&1), "Touching a makes b the LRU entry");
+        cache.put("c", 3);
+
+        assert!(!cache.contains("b"));
+        assert_eq!(cache.peek("a"), Some(&1));
+        assert_eq!(cache.peek("c"), Some(&3));
+        assert_eq!(cache.stats().evictions, 1);
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_put_replaces_and_refreshes() {
+        let mut cache = LruCache::new(2);
+        cache.put("a", 1);
+        cache.put("b", 2);
+        assert_eq!(cache.put("a", 10), Some(1));
+        cache.put("c", 3);
+        assert_eq!(
+            cache.pop_lru(),
+            Some(("a", 10)),
+            "b was evicted, a is now oldest"
+        );
+        assert_eq!(cache.pop_lru(), Some(("c", 3)));
+        assert_eq!(cache.pop_lru(), None);
+    }
+
+    #[test]
+    fn test_iter_goes_from_most_to_least_recent() {
+        let mut cache = LruCache::new(3);
+        cache.put("a", 1);
+        cache.put("b", 2);
+        cache.put_with_ttl("gone", 0, Duration::ZERO);
+        cache.get("a");
+        cache.put("c", 3);
+        let order: Vec<_> = cache.iter().map(|(key, value)| (*key, *value)).collect();
+        assert_eq!(order, vec![("c", 3), ("a", 1)], "b was evicted, gone expired");
+        assert_eq!(cache.stats().hits, 1, "Iterating is not a lookup");
+    }
+
+    #[test]
+    fn test_stats_track_hits_and_misses() {
+        let mut cache = LruCache::new(4);
+        cache.put(1, "one");
+        cache.get(&1);
+        cache.get(&1);
+        cache.get(&2);
+        *cache.get_mut(&1).unwrap() = "uno";
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (3, 1));
+        assert_eq!(stats.hit_rate(), 0.75);
+        assert_eq!(cache.remove(&1), Some("uno"));
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_entries_expire_after_ttl() {
+        let mut cache = LruCache::with_ttl(4, Duration::from_millis(20));
+        cache.put("short", 1);
+        cache.put_with_ttl("long", 2, Duration::from_secs(60));
+        thread::sleep(Duration::from_millis(30));
+
+        assert_eq!(cache.get("short"), None);
+        assert_eq!(cache.get("long"), Some(&2));
+        assert_eq!(cache.stats().expirations, 1);
+        assert_eq!(cache.stats().misses, 1);
+
+        cache.put_with_ttl("gone", 3, Duration::ZERO);
+        assert_eq!(cache.purge_expired(), 1);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_edge_case_expired_tail_counts_as_expiration() {
+        let mut cache = LruCache::new(2);
+        cache.put_with_ttl("stale", 1, Duration::ZERO);
+        cache.put("fresh", 2);
+        cache.put("new", 3);
+        let stats = cache.stats();
+        assert_eq!((stats.evictions, stats.expirations), (0, 1));
+        assert_eq!(cache.pop_lru(), Some(("fresh", 2)));
+    }
+
+    #[test]
+    fn test_edge_case_slots_are_reused() {
+        let mut cache = LruCache::new(3);
+        for i in 0..100 {
+            cache.put(i, i);
+        }
+        assert_eq!(cache.nodes.len(), 3);
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(cache.get(&99), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity must be non-zero")]
+    fn test_unhappy_path_zero_capacity() {
+        LruCache::<u8, u8>::new(0);
+    }
+}
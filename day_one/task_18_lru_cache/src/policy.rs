@@ -0,0 +1,333 @@
+//! Eviction policies for `Cache`.
+//!
+//! A policy only sees keys: the cache tells it about hits, inserts and
+//! removals, and asks it for a victim when it is full. Implementing
+//! `EvictionPolicy` is enough to plug a new strategy into `Cache`.
+
+use std::collections::hash_map::RandomState;
+use std::collections::BTreeMap;
+use std::hash::{BuildHasher, Hash};
+use std::time::{Duration, Instant};
+
+use task_16_hashmap::MyHashMap;
+
+/// Decides which entry a full `Cache` throws out
+///
+/// The cache calls `on_insert` for every key it stores, so a policy can track
+/// the same set of keys as the cache. `select_victim` must forget the key it
+/// returns; the cache then drops that entry.
+pub trait EvictionPolicy<K> {
+    /// Called when `key` is looked up and found
+    fn on_hit(&mut self, key: &K);
+
+    /// Called when `key` is inserted, or its value is replaced
+    fn on_insert(&mut self, key: &K);
+
+    /// Called when `key` leaves the cache for any reason other than `select_victim`
+    fn on_remove(&mut self, key: &K);
+
+    /// Picks the key to evict and stops tracking it, `None` if no keys are tracked
+    fn select_victim(&mut self) -> Option<K>;
+
+    /// Checks if `key` should be treated as absent; policies without expiry keep the default
+    fn is_expired(&self, _key: &K) -> bool {
+        false
+    }
+}
+
+// Keys ordered by a rank, lowest first; the shared core of the ordered policies
+struct RankedKeys<K, R> {
+    ranks: MyHashMap<K, R>,
+    order: BTreeMap<R, K>,
+}
+
+impl<K: Hash + Eq + Clone, R: Ord + Copy> RankedKeys<K, R> {
+    fn new() -> Self {
+        RankedKeys {
+            ranks: MyHashMap::new(),
+            order: BTreeMap::new(),
+        }
+    }
+
+    fn rank(&self, key: &K) -> Option<R> {
+        self.ranks.get(key).copied()
+    }
+
+    fn set(&mut self, key: &K, rank: R) {
+        if let Some(old) = self.ranks.insert(key.clone(), rank) {
+            self.order.remove(&old);
+        }
+        self.order.insert(rank, key.clone());
+    }
+
+    fn remove(&mut self, key: &K) {
+        if let Some(rank) = self.ranks.remove(key) {
+            self.order.remove(&rank);
+        }
+    }
+
+    fn pop_lowest(&mut self) -> Option<K> {
+        let (_, key) = self.order.pop_first()?;
+        self.ranks.remove(&key);
+        Some(key)
+    }
+}
+
+/// Evicts the least recently used key
+pub struct Lru<K> {
+    keys: RankedKeys<K, u64>,
+    tick: u64,
+}
+
+impl<K: Hash + Eq + Clone> Lru<K> {
+    pub fn new() -> Self {
+        Lru {
+            keys: RankedKeys::new(),
+            tick: 0,
+        }
+    }
+
+    fn touch(&mut self, key: &K) {
+        self.tick += 1;
+        self.keys.set(key, self.tick);
+    }
+}
+
+impl<K: Hash + Eq + Clone> Default for Lru<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq + Clone> EvictionPolicy<K> for Lru<K> {
+    fn on_hit(&mut self, key: &K) {
+        self.touch(key);
+    }
+
+    fn on_insert(&mut self, key: &K) {
+        self.touch(key);
+    }
+
+    fn on_remove(&mut self, key: &K) {
+        self.keys.remove(key);
+    }
+
+    fn select_victim(&mut self) -> Option<K> {
+        self.keys.pop_lowest()
+    }
+}
+
+/// Evicts the least frequently used key, breaking ties by least recent use
+pub struct Lfu<K> {
+    keys: RankedKeys<K, (u64, u64)>,
+    tick: u64,
+}
+
+impl<K: Hash + Eq + Clone> Lfu<K> {
+    pub fn new() -> Self {
+        Lfu {
+            keys: RankedKeys::new(),
+            tick: 0,
+        }
+    }
+
+    /// Returns how often `key` has been inserted or hit since it entered the cache
+    pub fn frequency(&self, key: &K) -> Option<u64> {
+        self.keys.rank(key).map(|(frequency, _)| frequency)
+    }
+
+    fn count_use(&mut self, key: &K) {
+        self.tick += 1;
+        let frequency = self.frequency(key).unwrap_or(0) + 1;
+        self.keys.set(key, (frequency, self.tick));
+    }
+}
+
+impl<K: Hash + Eq + Clone> Default for Lfu<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq + Clone> EvictionPolicy<K> for Lfu<K> {
+    fn on_hit(&mut self, key: &K) {
+        self.count_use(key);
+    }
+
+    fn on_insert(&mut self, key: &K) {
+        self.count_use(key);
+    }
+
+    fn on_remove(&mut self, key: &K) {
+        self.keys.remove(key);
+    }
+
+    fn select_victim(&mut self) -> Option<K> {
+        self.keys.pop_lowest()
+    }
+}
+
+/// Evicts the key that was inserted first; hits and replacements don't reorder
+pub struct Fifo<K> {
+    keys: RankedKeys<K, u64>,
+    tick: u64,
+}
+
+impl<K: Hash + Eq + Clone> Fifo<K> {
+    pub fn new() -> Self {
+        Fifo {
+            keys: RankedKeys::new(),
+            tick: 0,
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone> Default for Fifo<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq + Clone> EvictionPolicy<K> for Fifo<K> {
+    fn on_hit(&mut self, _key: &K) {}
+
+    fn on_insert(&mut self, key: &K) {
+        if self.keys.rank(key).is_none() {
+            self.tick += 1;
+            self.keys.set(key, self.tick);
+        }
+    }
+
+    fn on_remove(&mut self, key: &K) {
+        self.keys.remove(key);
+    }
+
+    fn select_victim(&mut self) -> Option<K> {
+        self.keys.pop_lowest()
+    }
+}
+
+/// Evicts a uniformly random key
+///
+/// Uses an xorshift64 generator, which is plenty for picking victims but not
+/// for anything security sensitive.
+pub struct Random<K> {
+    keys: Vec<K>,
+    positions: MyHashMap<K, usize>,
+    state: u64,
+}
+
+impl<K: Hash + Eq + Clone> Random<K> {
+    /// Creates a policy seeded from the process's random hasher keys
+    pub fn new() -> Self {
+        Self::with_seed(RandomState::new().hash_one(0u64))
+    }
+
+    /// Creates a policy whose victims are reproducible for a given seed
+    pub fn with_seed(seed: u64) -> Self {
+        Random {
+            keys: Vec::new(),
+            positions: MyHashMap::new(),
+            // xorshift never leaves the all-zero state
+            state: seed.max(1),
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    fn take(&mut self, position: usize) -> K {
+        let key = self.keys.swap_remove(position);
+        self.positions.remove(&key);
+        if let Some(moved) = self.keys.get(position) {
+            self.positions.insert(moved.clone(), position);
+        }
+        key
+    }
+}
+
+impl<K: Hash + Eq + Clone> Default for Random<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq + Clone> EvictionPolicy<K> for Random<K> {
+    fn on_hit(&mut self, _key: &K) {}
+
+    fn on_insert(&mut self, key: &K) {
+        if !self.positions.contains_key(key) {
+            self.positions.insert(key.clone(), self.keys.len());
+            self.keys.push(key.clone());
+        }
+    }
+
+    fn on_remove(&mut self, key: &K) {
+        if let Some(&position) = self.positions.get(key) {
+            self.take(position);
+        }
+    }
+
+    fn select_victim(&mut self) -> Option<K> {
+        if self.keys.is_empty() {
+            return None;
+        }
+        let position = (self.next_u64() % self.keys.len() as u64) as usize;
+        Some(self.take(position))
+    }
+}
+
+/// Expires keys a fixed time after they were last written, evicting the one
+/// closest to (or furthest past) its deadline
+///
+/// Reads the clock on every lookup, so unlike the other policies it needs a
+/// target where `Instant` works.
+pub struct Ttl<K> {
+    keys: RankedKeys<K, (Instant, u64)>,
+    ttl: Duration,
+    tick: u64,
+}
+
+impl<K: Hash + Eq + Clone> Ttl<K> {
+    /// Creates a policy giving every entry `ttl` to live from its last insert
+    pub fn new(ttl: Duration) -> Self {
+        Ttl {
+            keys: RankedKeys::new(),
+            ttl,
+            tick: 0,
+        }
+    }
+
+    /// Returns the time-to-live given to each entry
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+}
+
+impl<K: Hash + Eq + Clone> EvictionPolicy<K> for Ttl<K> {
+    fn on_hit(&mut self, _key: &K) {}
+
+    fn on_insert(&mut self, key: &K) {
+        // The tick keeps ranks unique when two inserts share an `Instant`
+        self.tick += 1;
+        self.keys.set(key, (Instant::now() + self.ttl, self.tick));
+    }
+
+    fn on_remove(&mut self, key: &K) {
+        self.keys.remove(key);
+    }
+
+    fn select_victim(&mut self) -> Option<K> {
+        self.keys.pop_lowest()
+    }
+
+    fn is_expired(&self, key: &K) -> bool {
+        self.keys
+            .rank(key)
+            .is_some_and(|(deadline, _)| deadline <= Instant::now())
+    }
+}
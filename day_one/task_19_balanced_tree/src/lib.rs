@@ -0,0 +1,588 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt;
+
+type Link<K, V> = Option<Box<Node<K, V>>>;
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    /// Height of the subtree rooted here; a leaf has height 1
+    height: usize,
+    /// Number of entries in the subtree rooted here, used for rank/select
+    size: usize,
+    left: Link<K, V>,
+    right: Link<K, V>,
+}
+
+impl<K, V> Node<K, V> {
+    fn leaf(key: K, value: V) -> Box<Self> {
+        Box::new(Node {
+            key,
+            value,
+            height: 1,
+            size: 1,
+            left: None,
+            right: None,
+        })
+    }
+
+    /// Recomputes height and size from the children
+    fn update(&mut self) {
+        self.height = 1 + height(&self.left).max(height(&self.right));
+        self.size = 1 + size(&self.left) + size(&self.right);
+    }
+
+    fn balance_factor(&self) -> isize {
+        height(&self.left) as isize - height(&self.right) as isize
+    }
+}
+
+fn height<K, V>(link: &Link<K, V>) -> usize {
+    link.as_ref().map_or(0, |node| node.height)
+}
+
+fn size<K, V>(link: &Link<K, V>) -> usize {
+    link.as_ref().map_or(0, |node| node.size)
+}
+
+fn rotate_right<K, V>(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+    let mut pivot = node.left.take().expect("rotate_right needs a left child");
+    node.left = pivot.right.take();
+    node.update();
+    pivot.right = Some(node);
+    pivot.update();
+    pivot
+}
+
+fn rotate_left<K, V>(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+    let mut pivot = node.right.take().expect("rotate_left needs a right child");
+    node.right = pivot.left.take();
+    node.update();
+    pivot.left = Some(node);
+    pivot.update();
+    pivot
+}
+
+/// Restores the AVL property at `node`, assuming both subtrees are already balanced
+fn rebalance<K, V>(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+    node.update();
+    match node.balance_factor() {
+        2.. => {
+            // Left-right case: turn it into a left-left case first
+            if node
+                .left
+                .as_ref()
+                .is_some_and(|left| left.balance_factor() < 0)
+            {
+                node.left = node.left.take().map(rotate_left);
+            }
+            rotate_right(node)
+        }
+        ..=-2 => {
+            if node
+                .right
+                .as_ref()
+                .is_some_and(|right| right.balance_factor() > 0)
+            {
+                node.right = node.right.take().map(rotate_right);
+            }
+            rotate_left(node)
+        }
+        _ => node,
+    }
+}
+
+fn insert<K: Ord, V>(link: Link<K, V>, key: K, value: V, old: &mut Option<V>) -> Box<Node<K, V>> {
+    let Some(mut node) = link else {
+        return Node::leaf(key, value);
+    };
+    match key.cmp(&node.key) {
+        Ordering::Less => node.left = Some(insert(node.left.take(), key, value, old)),
+        Ordering::Greater => node.right = Some(insert(node.right.take(), key, value, old)),
+        Ordering::Equal => {
+            *old = Some(std::mem::replace(&mut node.value, value));
+            return node;
+        }
+    }
+    rebalance(node)
+}
+
+/// Detaches the smallest node of a subtree, returning the rest of the subtree and that node
+fn remove_min<K, V>(mut node: Box<Node<K, V>>) -> (Link<K, V>, Box<Node<K, V>>) {
+    match node.left.take() {
+        None => (node.right.take(), node),
+        Some(left) => {
+            let (rest, min) = remove_min(left);
+            node.left = rest;
+            (Some(rebalance(node)), min)
+        }
+    }
+}
+
+fn remove<K, V, Q>(link: Link<K, V>, key: &Q, removed: &mut Option<(K, V)>) -> Link<K, V>
+where
+    K: Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    let mut node = link?;
+    match key.cmp(node.key.borrow()) {
+        Ordering::Less => node.left = remove(node.left.take(), key, removed),
+        Ordering::Greater => node.right = remove(node.right.take(), key, removed),
+        Ordering::Equal => {
+            let replacement = match (node.left.take(), node.right.take()) {
+                (None, None) => None,
+                (Some(child), None) | (None, Some(child)) => Some(child),
+                (Some(left), Some(right)) => {
+                    // Replace the node with its in-order successor
+                    let (rest, mut successor) = remove_min(right);
+                    successor.left = Some(left);
+                    successor.right = rest;
+                    Some(rebalance(successor))
+                }
+            };
+            let Node { key, value, .. } = *node;
+            *removed = Some((key, value));
+            return replacement;
+        }
+    }
+    Some(rebalance(node))
+}
+
+/// Ordered map backed by an AVL tree
+///
+/// Every insert and remove rebalances the path it touched, so the heights of
+/// any node's two subtrees differ by at most one and lookups stay O(log n).
+/// Each node also records the size of its subtree, which makes `rank` and
+/// `select` O(log n) as well.
+pub struct BalancedTree<K, V> {
+    root: Link<K, V>,
+}
+
+impl<K, V> BalancedTree<K, V> {
+    /// Creates an empty tree
+    pub fn new() -> Self {
+        BalancedTree { root: None }
+    }
+
+    /// Returns the number of entries
+    pub fn len(&self) -> usize {
+        size(&self.root)
+    }
+
+    /// Checks if the tree is empty
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Returns the height of the tree; 0 when empty
+    pub fn height(&self) -> usize {
+        height(&self.root)
+    }
+
+    /// Removes every entry
+    pub fn clear(&mut self) {
+        self.root = None;
+    }
+
+    /// Returns the entry with the smallest key
+    pub fn first(&self) -> Option<(&K, &V)> {
+        let mut node = self.root.as_deref()?;
+        while let Some(left) = node.left.as_deref() {
+            node = left;
+        }
+        Some((&node.key, &node.value))
+    }
+
+    /// Returns the entry with the largest key
+    pub fn last(&self) -> Option<(&K, &V)> {
+        let mut node = self.root.as_deref()?;
+        while let Some(right) = node.right.as_deref() {
+            node = right;
+        }
+        Some((&node.key, &node.value))
+    }
+
+    /// Returns the entry at position `index` in key order (0-based)
+    pub fn select(&self, mut index: usize) -> Option<(&K, &V)> {
+        let mut current = self.root.as_deref();
+        while let Some(node) = current {
+            let left_size = size(&node.left);
+            match index.cmp(&left_size) {
+                Ordering::Less => current = node.left.as_deref(),
+                Ordering::Equal => return Some((&node.key, &node.value)),
+                Ordering::Greater => {
+                    index -= left_size + 1;
+                    current = node.right.as_deref();
+                }
+            }
+        }
+        None
+    }
+
+    /// Iterates over the entries in ascending key order
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        let mut iter = Iter {
+            stack: Vec::with_capacity(self.height()),
+            remaining: self.len(),
+        };
+        iter.push_left_spine(self.root.as_deref());
+        iter
+    }
+
+    /// Iterates over the keys in ascending order
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    /// Iterates over the values in ascending key order
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    /// Returns the root node, for walking the tree's shape; `None` when empty
+    pub fn root(&self) -> Option<NodeRef<'_, K, V>> {
+        self.root.as_deref().map(|node| NodeRef { node })
+    }
+}
+
+impl<K: Ord, V> BalancedTree<K, V> {
+    /// Inserts a key-value pair, returning the previous value for the key if any
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let mut old = None;
+        self.root = Some(insert(self.root.take(), key, value, &mut old));
+        old
+    }
+
+    /// Removes `key`, returning its value if it was present
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut removed = None;
+        self.root = remove(self.root.take(), key, &mut removed);
+        removed.map(|(_, value)| value)
+    }
+
+    /// Returns the value for `key`
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut current = self.root.as_deref();
+        while let Some(node) = current {
+            match key.cmp(node.key.borrow()) {
+                Ordering::Less => current = node.left.as_deref(),
+                Ordering::Greater => current = node.right.as_deref(),
+                Ordering::Equal => return Some(&node.value),
+            }
+        }
+        None
+    }
+
+    /// Returns a mutable reference to the value for `key`
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut current = self.root.as_deref_mut();
+        while let Some(node) = current {
+            match key.cmp(node.key.borrow()) {
+                Ordering::Less => current = node.left.as_deref_mut(),
+                Ordering::Greater => current = node.right.as_deref_mut(),
+                Ordering::Equal => return Some(&mut node.value),
+            }
+        }
+        None
+    }
+
+    /// Checks if `key` is in the tree
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Returns how many keys are strictly smaller than `key`
+    pub fn rank<Q>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut rank = 0;
+        let mut current = self.root.as_deref();
+        while let Some(node) = current {
+            match key.cmp(node.key.borrow()) {
+                Ordering::Less => current = node.left.as_deref(),
+                Ordering::Equal => return rank + size(&node.left),
+                Ordering::Greater => {
+                    rank += size(&node.left) + 1;
+                    current = node.right.as_deref();
+                }
+            }
+        }
+        rank
+    }
+
+    /// Checks every invariant: key order, cached heights and sizes, and AVL balance
+    ///
+    /// Meant for tests; walks the whole tree.
+    pub fn validate(&self) -> Result<(), String>
+    where
+        K: fmt::Debug,
+    {
+        validate_node(&self.root, None, None).map(|_| ())
+    }
+}
+
+/// Returns the (height, size) of a valid subtree whose keys lie strictly between the bounds
+fn validate_node<K: Ord + fmt::Debug, V>(
+    link: &Link<K, V>,
+    lower: Option<&K>,
+    upper: Option<&K>,
+) -> Result<(usize, usize), String> {
+    let Some(node) = link else {
+        return Ok((0, 0));
+    };
+    if lower.is_some_and(|lower| node.key <= *lower)
+        || upper.is_some_and(|upper| node.key >= *upper)
+    {
+        return Err(format!("key {:?} is out of order", node.key));
+    }
+    let (left_height, left_size) = validate_node(&node.left, lower, Some(&node.key))?;
+    let (right_height, right_size) = validate_node(&node.right, Some(&node.key), upper)?;
+    if left_height.abs_diff(right_height) > 1 {
+        return Err(format!(
+            "node {:?} is unbalanced: left height {}, right height {}",
+            node.key, left_height, right_height
+        ));
+    }
+    let expected = (
+        1 + left_height.max(right_height),
+        1 + left_size + right_size,
+    );
+    if (node.height, node.size) != expected {
+        return Err(format!(
+            "node {:?} caches height {} and size {}, expected {} and {}",
+            node.key, node.height, node.size, expected.0, expected.1
+        ));
+    }
+    Ok(expected)
+}
+
+impl<K, V> Default for BalancedTree<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for BalancedTree<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for BalancedTree<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut tree = BalancedTree::new();
+        for (key, value) in iter {
+            tree.insert(key, value);
+        }
+        tree
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a BalancedTree<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Read-only view of one node of a `BalancedTree`
+pub struct NodeRef<'a, K, V> {
+    node: &'a Node<K, V>,
+}
+
+impl<'a, K, V> NodeRef<'a, K, V> {
+    pub fn key(&self) -> &'a K {
+        &self.node.key
+    }
+
+    pub fn value(&self) -> &'a V {
+        &self.node.value
+    }
+
+    /// Height of the subtree rooted here; a leaf has height 1
+    pub fn height(&self) -> usize {
+        self.node.height
+    }
+
+    pub fn left(&self) -> Option<NodeRef<'a, K, V>> {
+        self.node.left.as_deref().map(|node| NodeRef { node })
+    }
+
+    pub fn right(&self) -> Option<NodeRef<'a, K, V>> {
+        self.node.right.as_deref().map(|node| NodeRef { node })
+    }
+}
+
+impl<K, V> Clone for NodeRef<'_, K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, V> Copy for NodeRef<'_, K, V> {}
+
+/// In-order iterator over a `BalancedTree`
+pub struct Iter<'a, K, V> {
+    /// Nodes whose left subtree has been visited but which have not been yielded yet
+    stack: Vec<&'a Node<K, V>>,
+    remaining: usize,
+}
+
+impl<'a, K, V> Iter<'a, K, V> {
+    fn push_left_spine(&mut self, mut node: Option<&'a Node<K, V>>) {
+        while let Some(current) = node {
+            self.stack.push(current);
+            node = current.left.as_deref();
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.push_left_spine(node.right.as_deref());
+        self.remaining -= 1;
+        Some((&node.key, &node.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_happy_path_insert_get_remove() {
+        let mut tree = BalancedTree::new();
+        assert_eq!(tree.insert(5, "five"), None);
+        assert_eq!(tree.insert(3, "three"), None);
+        assert_eq!(tree.insert(8, "eight"), None);
+        assert_eq!(tree.insert(5, "FIVE"), Some("five"));
+        assert_eq!(tree.len(), 3);
+        assert_eq!(tree.get(&5), Some(&"FIVE"));
+
+        *tree.get_mut(&3).unwrap() = "THREE";
+        assert_eq!(tree.remove(&3), Some("THREE"));
+        assert!(!tree.contains_key(&3));
+        assert_eq!(tree.len(), 2);
+        tree.validate().unwrap();
+    }
+
+    #[test]
+    fn test_sorted_inserts_stay_balanced() {
+        let mut tree = BalancedTree::new();
+        for i in 0..1024 {
+            tree.insert(i, i);
+        }
+        tree.validate().unwrap();
+        // A perfectly balanced tree of 1024 nodes has height 11
+        assert!(tree.height() <= 11, "height {} is too tall", tree.height());
+        assert!(tree.iter().map(|(k, _)| *k).eq(0..1024));
+    }
+
+    #[test]
+    fn test_removals_keep_invariants() {
+        let mut tree: BalancedTree<u32, u32> = (0..200).map(|i| ((i * 37) % 200, i)).collect();
+        tree.validate().unwrap();
+        for key in (0..200).filter(|k| k % 3 != 0) {
+            assert!(tree.remove(&key).is_some());
+            tree.validate().unwrap();
+        }
+        assert_eq!(tree.len(), 67);
+        assert!(tree.keys().all(|k| k % 3 == 0));
+    }
+
+    #[test]
+    fn test_rank_and_select() {
+        let tree: BalancedTree<i32, ()> =
+            [10, 20, 30, 40, 50].into_iter().map(|k| (k, ())).collect();
+        assert_eq!(tree.rank(&10), 0);
+        assert_eq!(
+            tree.rank(&35),
+            3,
+            "Rank of a missing key counts the smaller keys"
+        );
+        assert_eq!(tree.rank(&99), 5);
+        assert_eq!(tree.select(0).map(|(k, _)| *k), Some(10));
+        assert_eq!(tree.select(3).map(|(k, _)| *k), Some(40));
+        for i in 0..tree.len() {
+            let (key, _) = tree.select(i).unwrap();
+            assert_eq!(tree.rank(key), i);
+        }
+        assert_eq!(tree.first().map(|(k, _)| *k), Some(10));
+        assert_eq!(tree.last().map(|(k, _)| *k), Some(50));
+    }
+
+    #[test]
+    fn test_borrowed_lookups_and_debug() {
+        let mut tree = BalancedTree::new();
+        tree.insert("b".to_string(), 2);
+        tree.insert("a".to_string(), 1);
+        assert_eq!(tree.get("a"), Some(&1));
+        assert_eq!(tree.rank("b"), 1);
+        assert_eq!(format!("{:?}", tree), r#"{"a": 1, "b": 2}"#);
+        assert_eq!(tree.remove("a"), Some(1));
+    }
+
+    #[test]
+    fn test_root_exposes_shape() {
+        let tree: BalancedTree<_, _> = (1..=3).map(|i| (i, i * 10)).collect();
+        let root = tree.root().unwrap();
+        assert_eq!((*root.key(), *root.value(), root.height()), (2, 20, 2));
+        assert_eq!(root.left().map(|node| *node.key()), Some(1));
+        assert_eq!(root.right().map(|node| *node.key()), Some(3));
+        assert!(root.left().unwrap().left().is_none());
+        assert!(BalancedTree::<i32, i32>::new().root().is_none());
+    }
+
+    #[test]
+    fn test_unhappy_path_missing_keys() {
+        let mut tree: BalancedTree<i32, i32> = (0..10).map(|i| (i * 2, i)).collect();
+        assert_eq!(tree.get(&3), None);
+        assert_eq!(tree.remove(&3), None);
+        assert_eq!(tree.select(10), None);
+        assert_eq!(tree.len(), 10);
+        tree.validate().unwrap();
+    }
+
+    #[test]
+    fn test_edge_case_empty_tree() {
+        let mut tree: BalancedTree<i32, i32> = BalancedTree::default();
+        assert!(tree.is_empty());
+        assert_eq!(tree.height(), 0);
+        assert_eq!(tree.first(), None);
+        assert_eq!(tree.select(0), None);
+        assert_eq!(tree.rank(&1), 0);
+        assert_eq!(tree.iter().next(), None);
+        tree.validate().unwrap();
+        tree.insert(1, 1);
+        tree.clear();
+        assert!(tree.is_empty());
+    }
+}
@@ -0,0 +1,404 @@
+use std::error::Error;
+use std::fmt;
+
+/// Identifies an element pushed into a `DaryHeap`, for `decrease_key` and `remove`
+///
+/// Handles carry a generation, so a handle to an element that has since been
+/// popped or removed is rejected even if its slot was reused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Handle {
+    slot: usize,
+    generation: u32,
+}
+
+/// Errors reported by `DaryHeap::decrease_key`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeapError {
+    /// The element was already popped or removed
+    StaleHandle,
+    /// The new key is greater than the current one
+    KeyIncreased,
+}
+
+impl fmt::Display for HeapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HeapError::StaleHandle => {
+                write!(f, "handle refers to an element no longer in the heap")
+            }
+            HeapError::KeyIncreased => write!(f, "new key is greater than the current key"),
+        }
+    }
+}
+
+impl Error for HeapError {}
+
+struct Slot {
+    /// Index into `heap` while the element is live
+    position: Option<usize>,
+    generation: u32,
+}
+
+/// Min-heap where every node has up to `D` children
+///
+/// A wider node makes the tree shallower, so `push` and `decrease_key` (which
+/// sift up) get cheaper, while `pop` (which sifts down past `D` children per
+/// level) gets more expensive. Dijkstra-style workloads do many more
+/// decrease-keys than pops, which is where `D = 4` or more pays off.
+pub struct DaryHeap<T, const D: usize> {
+    heap: Vec<(T, usize)>,
+    slots: Vec<Slot>,
+    free_slots: Vec<usize>,
+}
+
+impl<T: Ord, const D: usize> DaryHeap<T, D> {
+    /// Creates an empty heap
+    pub fn new() -> Self {
+        const { assert!(D >= 2, "a d-ary heap needs at least two children per node") };
+        DaryHeap {
+            heap: Vec::new(),
+            slots: Vec::new(),
+            free_slots: Vec::new(),
+        }
+    }
+
+    /// Creates an empty heap with room for `capacity` elements
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut heap = Self::new();
+        heap.heap.reserve(capacity);
+        heap.slots.reserve(capacity);
+        heap
+    }
+
+    /// Builds a heap from `items` in O(n), returning a handle for each item
+    ///
+    /// `handles[i]` refers to `items[i]`. Sifting down from the last parent to
+    /// the root does O(n) work in total, against O(n log n) for n pushes.
+    pub fn heapify(items: Vec<T>) -> (Self, Vec<Handle>) {
+        let len = items.len();
+        let mut heap = Self::new();
+        heap.heap = items.into_iter().zip(0..len).collect();
+        heap.slots = (0..len)
+            .map(|position| Slot {
+                position: Some(position),
+                generation: 0,
+            })
+            .collect();
+        if len > 1 {
+            for position in (0..=(len - 2) / D).rev() {
+                heap.sift_down(position);
+            }
+        }
+        let handles = (0..len)
+            .map(|slot| Handle {
+                slot,
+                generation: 0,
+            })
+            .collect();
+        (heap, handles)
+    }
+
+    /// Returns the number of elements
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Checks if the heap is empty
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Adds an element, returning a handle to it
+    pub fn push(&mut self, item: T) -> Handle {
+        let position = self.heap.len();
+        let slot = match self.free_slots.pop() {
+            Some(slot) => {
+                self.slots[slot].position = Some(position);
+                slot
+            }
+            None => {
+                self.slots.push(Slot {
+                    position: Some(position),
+                    generation: 0,
+                });
+                self.slots.len() - 1
+            }
+        };
+        self.heap.push((item, slot));
+        self.sift_up(position);
+        Handle {
+            slot,
+            generation: self.slots[slot].generation,
+        }
+    }
+
+    /// Returns the smallest element
+    pub fn peek(&self) -> Option<&T> {
+        self.heap.first().map(|(item, _)| item)
+    }
+
+    /// Removes and returns the smallest element
+    pub fn pop(&mut self) -> Option<T> {
+        if self.heap.is_empty() {
+            None
+        } else {
+            Some(self.remove_at(0))
+        }
+    }
+
+    /// Returns the element behind `handle`, if it is still in the heap
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        let position = self.position(handle)?;
+        Some(&self.heap[position].0)
+    }
+
+    /// Checks if the element behind `handle` is still in the heap
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.position(handle).is_some()
+    }
+
+    /// Replaces the element behind `handle` with a smaller (or equal) one
+    pub fn decrease_key(&mut self, handle: Handle, item: T) -> Result<(), HeapError> {
+        let position = self.position(handle).ok_or(HeapError::StaleHandle)?;
+        if item > self.heap[position].0 {
+            return Err(HeapError::KeyIncreased);
+        }
+        self.heap[position].0 = item;
+        self.sift_up(position);
+        Ok(())
+    }
+
+    /// Removes the element behind `handle`, returning it if it was still in the heap
+    pub fn remove(&mut self, handle: Handle) -> Option<T> {
+        let position = self.position(handle)?;
+        Some(self.remove_at(position))
+    }
+
+    /// Removes every element; outstanding handles become stale
+    pub fn clear(&mut self) {
+        while let Some((_, slot)) = self.heap.pop() {
+            self.release(slot);
+        }
+    }
+
+    /// Consumes the heap, returning its elements smallest first
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        std::iter::from_fn(|| self.pop()).collect()
+    }
+
+    fn position(&self, handle: Handle) -> Option<usize> {
+        let slot = self.slots.get(handle.slot)?;
+        if slot.generation == handle.generation {
+            slot.position
+        } else {
+            None
+        }
+    }
+
+    fn remove_at(&mut self, position: usize) -> T {
+        let last = self.heap.len() - 1;
+        self.swap(position, last);
+        let (item, slot) = self.heap.pop().expect("heap is not empty");
+        self.release(slot);
+        if position < self.heap.len() {
+            // The element moved in from the end may belong above or below
+            self.sift_down(position);
+            self.sift_up(position);
+        }
+        item
+    }
+
+    /// Frees a slot and bumps its generation so old handles stop matching
+    fn release(&mut self, slot: usize) {
+        let entry = &mut self.slots[slot];
+        entry.position = None;
+        entry.generation = entry.generation.wrapping_add(1);
+        self.free_slots.push(slot);
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        self.slots[self.heap[a].1].position = Some(a);
+        self.slots[self.heap[b].1].position = Some(b);
+    }
+
+    fn sift_up(&mut self, mut position: usize) {
+        while position > 0 {
+            let parent = (position - 1) / D;
+            if self.heap[position].0 >= self.heap[parent].0 {
+                break;
+            }
+            self.swap(position, parent);
+            position = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut position: usize) {
+        loop {
+            let first_child = position * D + 1;
+            let last_child = (first_child + D).min(self.heap.len());
+            let Some(smallest) =
+                (first_child..last_child).min_by(|&a, &b| self.heap[a].0.cmp(&self.heap[b].0))
+            else {
+                break;
+            };
+            if self.heap[smallest].0 >= self.heap[position].0 {
+                break;
+            }
+            self.swap(position, smallest);
+            position = smallest;
+        }
+    }
+}
+
+impl<T: Ord, const D: usize> Default for DaryHeap<T, D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord, const D: usize> From<Vec<T>> for DaryHeap<T, D> {
+    fn from(items: Vec<T>) -> Self {
+        Self::heapify(items).0
+    }
+}
+
+impl<T: Ord, const D: usize> FromIterator<T> for DaryHeap<T, D> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl<T: fmt::Debug, const D: usize> fmt::Debug for DaryHeap<T, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Heap order: the first element is the smallest, the rest are only partially ordered
+        f.debug_list()
+            .entries(self.heap.iter().map(|(item, _)| item))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drain<T: Ord, const D: usize>(heap: &mut DaryHeap<T, D>) -> Vec<T> {
+        std::iter::from_fn(|| heap.pop()).collect()
+    }
+
+    #[test]
+    fn test_happy_path_pops_in_order() {
+        let mut heap: DaryHeap<i32, 3> = DaryHeap::new();
+        for value in [5, 1, 8, 3, 9, 2, 7] {
+            heap.push(value);
+        }
+        assert_eq!(heap.peek(), Some(&1));
+        assert_eq!(heap.len(), 7);
+        assert_eq!(drain(&mut heap), vec![1, 2, 3, 5, 7, 8, 9]);
+    }
+
+    #[test]
+    fn test_different_arities_agree() {
+        let values: Vec<u32> = (0..500).map(|i| (i * 7919) % 1013).collect();
+        let mut binary: DaryHeap<u32, 2> = DaryHeap::new();
+        let mut wide: DaryHeap<u32, 8> = DaryHeap::new();
+        for &value in &values {
+            binary.push(value);
+            wide.push(value);
+        }
+        let mut sorted = values.clone();
+        sorted.sort();
+        assert_eq!(drain(&mut binary), sorted);
+        assert_eq!(drain(&mut wide), sorted);
+    }
+
+    #[test]
+    fn test_heapify_matches_pushing_one_by_one() {
+        let values: Vec<u32> = (0..500).map(|i| (i * 7919) % 1013).collect();
+        for len in [0, 1, 2, 3, 4, 5, values.len()] {
+            let prefix = values[..len].to_vec();
+            let mut expected = prefix.clone();
+            expected.sort();
+            assert_eq!(
+                DaryHeap::<u32, 2>::from(prefix.clone()).into_sorted_vec(),
+                expected
+            );
+            assert_eq!(DaryHeap::<u32, 3>::from(prefix).into_sorted_vec(), expected);
+        }
+
+        let (mut heap, handles) = DaryHeap::<_, 4>::heapify(vec![(30, "a"), (10, "b"), (20, "c")]);
+        assert_eq!(
+            heap.get(handles[0]),
+            Some(&(30, "a")),
+            "Handles follow input order"
+        );
+        heap.decrease_key(handles[0], (5, "a")).unwrap();
+        assert_eq!(heap.pop(), Some((5, "a")));
+        let d = heap.push((15, "d"));
+        assert_eq!(d.slot, handles[0].slot, "The popped slot was reused");
+        assert_eq!(heap.get(handles[0]), None);
+        assert_eq!(
+            format!("{:?}", heap),
+            "[(10, \"b\"), (20, \"c\"), (15, \"d\")]"
+        );
+    }
+
+    #[test]
+    fn test_decrease_key_moves_element_up() {
+        let mut heap: DaryHeap<(u32, &str), 4> = DaryHeap::new();
+        heap.push((10, "a"));
+        let b = heap.push((20, "b"));
+        heap.push((30, "c"));
+
+        heap.decrease_key(b, (5, "b")).unwrap();
+        assert_eq!(heap.get(b), Some(&(5, "b")));
+        assert_eq!(heap.pop(), Some((5, "b")));
+        assert!(!heap.contains(b));
+    }
+
+    #[test]
+    fn test_remove_by_handle() {
+        let mut heap: DaryHeap<i32, 2> = DaryHeap::new();
+        let handles: Vec<_> = (0..10).map(|i| heap.push(i)).collect();
+        assert_eq!(heap.remove(handles[0]), Some(0));
+        assert_eq!(heap.remove(handles[7]), Some(7));
+        assert_eq!(drain(&mut heap), vec![1, 2, 3, 4, 5, 6, 8, 9]);
+    }
+
+    #[test]
+    fn test_unhappy_path_decrease_key_errors() {
+        let mut heap: DaryHeap<i32, 2> = DaryHeap::new();
+        let handle = heap.push(10);
+        assert_eq!(heap.decrease_key(handle, 11), Err(HeapError::KeyIncreased));
+        assert_eq!(
+            heap.peek(),
+            Some(&10),
+            "Rejected update leaves the heap unchanged"
+        );
+
+        heap.pop();
+        assert_eq!(heap.decrease_key(handle, 1), Err(HeapError::StaleHandle));
+        assert_eq!(heap.remove(handle), None);
+        assert_eq!(
+            HeapError::StaleHandle.to_string(),
+            "handle refers to an element no longer in the heap"
+        );
+    }
+
+    #[test]
+    fn test_edge_case_reused_slot_rejects_old_handle() {
+        let mut heap: DaryHeap<i32, 2> = DaryHeap::new();
+        let old = heap.push(1);
+        heap.pop();
+        let new = heap.push(2);
+        assert_eq!(old.slot, new.slot, "The slot was reused");
+        assert_eq!(heap.get(old), None);
+        assert_eq!(heap.get(new), Some(&2));
+
+        heap.clear();
+        assert!(heap.is_empty());
+        assert!(!heap.contains(new));
+        assert_eq!(heap.pop(), None);
+        assert_eq!(heap.peek(), None);
+    }
+}
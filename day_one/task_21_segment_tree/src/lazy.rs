@@ -0,0 +1,199 @@
+use std::marker::PhantomData;
+use std::ops::RangeBounds;
+
+use crate::{resolve, Max, Min, Number, Operation, Sum};
+
+/// Operation whose aggregate can absorb "add `delta` to every element" directly
+pub trait RangeAdd<T>: Operation<T> {
+    /// New aggregate of `len` elements after adding `delta` to each of them
+    fn add(aggregate: &T, delta: &T, len: usize) -> T;
+}
+
+impl<T: Number> RangeAdd<T> for Sum {
+    fn add(aggregate: &T, delta: &T, len: usize) -> T {
+        *aggregate + *delta * T::from_usize(len)
+    }
+}
+
+impl<T: Number> RangeAdd<T> for Min {
+    fn add(aggregate: &T, delta: &T, _len: usize) -> T {
+        *aggregate + *delta
+    }
+}
+
+impl<T: Number> RangeAdd<T> for Max {
+    fn add(aggregate: &T, delta: &T, _len: usize) -> T {
+        *aggregate + *delta
+    }
+}
+
+/// Segment tree with O(log n) range-add updates as well as range queries
+///
+/// An update stops at the nodes that exactly cover its range and leaves a
+/// pending delta there; the delta is pushed down to the children only when a
+/// later update or query needs to look inside. Nodes are laid out recursively
+/// (node `i` has children `2i` and `2i + 1`) over exactly `n` leaves, so every
+/// node covers at least one real element.
+pub struct LazySegmentTree<T, Op> {
+    len: usize,
+    tree: Vec<T>,
+    pending: Vec<Option<T>>,
+    _op: PhantomData<Op>,
+}
+
+impl<T: Number, Op: RangeAdd<T>> LazySegmentTree<T, Op> {
+    /// Builds a tree over `values`
+    pub fn from_slice(values: &[T]) -> Self {
+        let len = values.len();
+        let nodes = 4 * len.max(1);
+        let mut tree = LazySegmentTree {
+            len,
+            tree: vec![Op::identity(); nodes],
+            pending: vec![None; nodes],
+            _op: PhantomData,
+        };
+        if len > 0 {
+            tree.build(1, 0, len, values);
+        }
+        tree
+    }
+
+    /// Returns the number of elements
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if the tree has no elements
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Adds `delta` to every element in `range`
+    pub fn add(&mut self, range: impl RangeBounds<usize>, delta: T) {
+        let (start, end) = resolve(range, self.len);
+        if start < end {
+            self.add_node(1, 0, self.len, start, end, delta);
+        }
+    }
+
+    /// Aggregates the elements in `range`; an empty range gives the identity
+    pub fn query(&mut self, range: impl RangeBounds<usize>) -> T {
+        let (start, end) = resolve(range, self.len);
+        if start == end {
+            return Op::identity();
+        }
+        self.query_node(1, 0, self.len, start, end)
+    }
+
+    fn build(&mut self, node: usize, lo: usize, hi: usize, values: &[T]) {
+        if hi - lo == 1 {
+            self.tree[node] = values[lo];
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        self.build(2 * node, lo, mid, values);
+        self.build(2 * node + 1, mid, hi, values);
+        self.tree[node] = Op::combine(&self.tree[2 * node], &self.tree[2 * node + 1]);
+    }
+
+    /// Applies `delta` to the whole subtree at `node`, covering `len` elements
+    fn apply(&mut self, node: usize, len: usize, delta: T) {
+        self.tree[node] = Op::add(&self.tree[node], &delta, len);
+        self.pending[node] = Some(match self.pending[node] {
+            Some(existing) => existing + delta,
+            None => delta,
+        });
+    }
+
+    fn push_down(&mut self, node: usize, lo: usize, mid: usize, hi: usize) {
+        if let Some(delta) = self.pending[node].take() {
+            self.apply(2 * node, mid - lo, delta);
+            self.apply(2 * node + 1, hi - mid, delta);
+        }
+    }
+
+    fn add_node(&mut self, node: usize, lo: usize, hi: usize, start: usize, end: usize, delta: T) {
+        if start <= lo && hi <= end {
+            self.apply(node, hi - lo, delta);
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        self.push_down(node, lo, mid, hi);
+        if start < mid {
+            self.add_node(2 * node, lo, mid, start, end, delta);
+        }
+        if mid < end {
+            self.add_node(2 * node + 1, mid, hi, start, end, delta);
+        }
+        self.tree[node] = Op::combine(&self.tree[2 * node], &self.tree[2 * node + 1]);
+    }
+
+    fn query_node(&mut self, node: usize, lo: usize, hi: usize, start: usize, end: usize) -> T {
+        if start <= lo && hi <= end {
+            return self.tree[node];
+        }
+        let mid = (lo + hi) / 2;
+        self.push_down(node, lo, mid, hi);
+        match (start < mid, mid < end) {
+            (true, true) => Op::combine(
+                &self.query_node(2 * node, lo, mid, start, end),
+                &self.query_node(2 * node + 1, mid, hi, start, end),
+            ),
+            (true, false) => self.query_node(2 * node, lo, mid, start, end),
+            _ => self.query_node(2 * node + 1, mid, hi, start, end),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_happy_path_range_add_and_sum() {
+        let mut tree: LazySegmentTree<i64, Sum> = LazySegmentTree::from_slice(&[1, 2, 3, 4, 5]);
+        tree.add(1..4, 10);
+        assert_eq!(tree.query(..), 45);
+        assert_eq!(tree.query(0..2), 13);
+        assert_eq!(tree.query(3..), 19);
+    }
+
+    #[test]
+    fn test_matches_brute_force_for_min_and_max() {
+        let mut values: Vec<i32> = (0..29).map(|i| (i * 37 % 23) - 11).collect();
+        let mut mins: LazySegmentTree<i32, Min> = LazySegmentTree::from_slice(&values);
+        let mut maxs: LazySegmentTree<i32, Max> = LazySegmentTree::from_slice(&values);
+        for step in 0..40usize {
+            let (start, end) = (step * 7 % 29, (step * 7 % 29 + step % 11 + 1).min(29));
+            let delta = step as i32 % 9 - 4;
+            values[start..end].iter_mut().for_each(|v| *v += delta);
+            mins.add(start..end, delta);
+            maxs.add(start..end, delta);
+
+            let (qs, qe) = (step % 13, 13 + step % 16);
+            assert_eq!(mins.query(qs..qe), *values[qs..qe].iter().min().unwrap());
+            assert_eq!(maxs.query(qs..qe), *values[qs..qe].iter().max().unwrap());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "range starts at 3 but ends at 1")]
+    fn test_unhappy_path_reversed_range() {
+        let mut tree: LazySegmentTree<u32, Sum> = LazySegmentTree::from_slice(&[1, 2, 3, 4]);
+        #[allow(clippy::reversed_empty_ranges)]
+        tree.add(3..1, 1);
+    }
+
+    #[test]
+    fn test_edge_case_empty_tree_and_ranges() {
+        let mut empty: LazySegmentTree<i32, Min> = LazySegmentTree::from_slice(&[]);
+        assert!(empty.is_empty());
+        empty.add(.., 5);
+        assert_eq!(empty.query(..), i32::MAX);
+
+        let mut tree: LazySegmentTree<u8, Sum> = LazySegmentTree::from_slice(&[1, 1, 1]);
+        tree.add(1..1, 100);
+        assert_eq!(tree.query(..), 3, "Empty update changes nothing");
+        assert_eq!(tree.len(), 3);
+    }
+}
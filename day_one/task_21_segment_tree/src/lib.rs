@@ -0,0 +1,275 @@
+use std::marker::PhantomData;
+use std::ops::{Add, Bound, Mul, RangeBounds};
+
+pub mod lazy;
+
+pub use lazy::{LazySegmentTree, RangeAdd};
+
+/// Associative operation a segment tree aggregates with
+///
+/// `combine` must be associative and `identity` must leave any value unchanged;
+/// commutativity is not required, the tree always combines left to right.
+pub trait Operation<T> {
+    fn identity() -> T;
+    fn combine(left: &T, right: &T) -> T;
+}
+
+/// Integer types the built-in `Sum`, `Min` and `Max` operations work on
+pub trait Number: Copy + Ord + Default + Add<Output = Self> + Mul<Output = Self> {
+    const MIN: Self;
+    const MAX: Self;
+    fn from_usize(n: usize) -> Self;
+}
+
+macro_rules! impl_number {
+    ($($t:ty),*) => {
+        $(impl Number for $t {
+            const MIN: Self = <$t>::MIN;
+            const MAX: Self = <$t>::MAX;
+            fn from_usize(n: usize) -> Self {
+                n as $t
+            }
+        })*
+    };
+}
+
+impl_number!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// Range sum
+pub struct Sum;
+/// Range minimum
+pub struct Min;
+/// Range maximum
+pub struct Max;
+
+impl<T: Number> Operation<T> for Sum {
+    fn identity() -> T {
+        T::default()
+    }
+
+    fn combine(left: &T, right: &T) -> T {
+        *left + *right
+    }
+}
+
+impl<T: Number> Operation<T> for Min {
+    fn identity() -> T {
+        T::MAX
+    }
+
+    fn combine(left: &T, right: &T) -> T {
+        *left.min(right)
+    }
+}
+
+impl<T: Number> Operation<T> for Max {
+    fn identity() -> T {
+        T::MIN
+    }
+
+    fn combine(left: &T, right: &T) -> T {
+        *left.max(right)
+    }
+}
+
+/// Resolves a range against a length, panicking like slice indexing when it is out of bounds
+pub(crate) fn resolve(range: impl RangeBounds<usize>, len: usize) -> (usize, usize) {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start + 1,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end + 1,
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    assert!(
+        start <= end,
+        "range starts at {} but ends at {}",
+        start,
+        end
+    );
+    assert!(
+        end <= len,
+        "range end {} out of bounds for length {}",
+        end,
+        len
+    );
+    (start, end)
+}
+
+/// Array with O(log n) point updates and range queries under an associative `Op`
+///
+/// Stored bottom-up in a single `Vec` of `2n` values: the leaves are at `n..2n`
+/// and node `i` aggregates nodes `2i` and `2i + 1`.
+pub struct SegmentTree<T, Op> {
+    len: usize,
+    tree: Vec<T>,
+    _op: PhantomData<Op>,
+}
+
+impl<T: Clone, Op: Operation<T>> SegmentTree<T, Op> {
+    /// Creates a tree of `len` identity values
+    pub fn new(len: usize) -> Self {
+        SegmentTree {
+            len,
+            tree: vec![Op::identity(); 2 * len],
+            _op: PhantomData,
+        }
+    }
+
+    /// Builds a tree over `values` in O(n)
+    pub fn from_slice(values: &[T]) -> Self {
+        let len = values.len();
+        let mut tree = vec![Op::identity(); 2 * len];
+        tree[len..].clone_from_slice(values);
+        for i in (1..len).rev() {
+            tree[i] = Op::combine(&tree[2 * i], &tree[2 * i + 1]);
+        }
+        SegmentTree {
+            len,
+            tree,
+            _op: PhantomData,
+        }
+    }
+
+    /// Returns the number of elements
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if the tree has no elements
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the element at `index`
+    pub fn get(&self, index: usize) -> &T {
+        assert!(
+            index < self.len,
+            "index {} out of bounds for length {}",
+            index,
+            self.len
+        );
+        &self.tree[self.len + index]
+    }
+
+    /// Replaces the element at `index` and updates the aggregates above it
+    pub fn update(&mut self, index: usize, value: T) {
+        assert!(
+            index < self.len,
+            "index {} out of bounds for length {}",
+            index,
+            self.len
+        );
+        let mut node = self.len + index;
+        self.tree[node] = value;
+        while node > 1 {
+            node /= 2;
+            self.tree[node] = Op::combine(&self.tree[2 * node], &self.tree[2 * node + 1]);
+        }
+    }
+
+    /// Aggregates the elements in `range`; an empty range gives the identity
+    pub fn query(&self, range: impl RangeBounds<usize>) -> T {
+        let (start, end) = resolve(range, self.len);
+        // Accumulate from both ends separately to keep the left-to-right order
+        let mut left = Op::identity();
+        let mut right = Op::identity();
+        let (mut lo, mut hi) = (start + self.len, end + self.len);
+        while lo < hi {
+            if lo % 2 == 1 {
+                left = Op::combine(&left, &self.tree[lo]);
+                lo += 1;
+            }
+            if hi % 2 == 1 {
+                hi -= 1;
+                right = Op::combine(&self.tree[hi], &right);
+            }
+            lo /= 2;
+            hi /= 2;
+        }
+        Op::combine(&left, &right)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_happy_path_sum_min_max() {
+        let values = [5, 3, 8, 6, 1, 4];
+        let sums: SegmentTree<i32, Sum> = SegmentTree::from_slice(&values);
+        let mins: SegmentTree<i32, Min> = SegmentTree::from_slice(&values);
+        let maxs: SegmentTree<i32, Max> = SegmentTree::from_slice(&values);
+        assert_eq!(sums.query(..), 27);
+        assert_eq!(sums.query(1..4), 17);
+        assert_eq!(mins.query(0..3), 3);
+        assert_eq!(mins.query(2..=4), 1);
+        assert_eq!(maxs.query(3..), 6);
+    }
+
+    #[test]
+    fn test_point_updates_match_brute_force() {
+        let mut values: Vec<i64> = (0..37).map(|i| (i * 7919 % 101) - 50).collect();
+        let mut tree: SegmentTree<i64, Sum> = SegmentTree::from_slice(&values);
+        for step in 0..50 {
+            let index = step * 13 % values.len();
+            values[index] = step as i64 - 25;
+            tree.update(index, values[index]);
+            let (start, end) = (step % 20, 20 + step % 17);
+            assert_eq!(
+                tree.query(start..end),
+                values[start..end].iter().sum::<i64>()
+            );
+        }
+        assert_eq!(*tree.get(0), values[0]);
+    }
+
+    #[test]
+    fn test_non_commutative_operation_keeps_order() {
+        struct Concat;
+        impl Operation<String> for Concat {
+            fn identity() -> String {
+                String::new()
+            }
+            fn combine(left: &String, right: &String) -> String {
+                format!("{}{}", left, right)
+            }
+        }
+        let letters: Vec<String> = "abcdefg".chars().map(String::from).collect();
+        let mut tree: SegmentTree<String, Concat> = SegmentTree::from_slice(&letters);
+        assert_eq!(tree.query(..), "abcdefg");
+        assert_eq!(tree.query(2..6), "cdef");
+        tree.update(3, "X".to_string());
+        assert_eq!(tree.query(1..5), "bcXe");
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_unhappy_path_range_past_end() {
+        let tree: SegmentTree<u8, Sum> = SegmentTree::from_slice(&[1, 2, 3]);
+        tree.query(1..4);
+    }
+
+    #[test]
+    fn test_edge_case_empty_ranges_and_trees() {
+        let tree: SegmentTree<u32, Min> = SegmentTree::new(4);
+        assert_eq!(
+            tree.query(2..2),
+            u32::MAX,
+            "Empty range yields the identity"
+        );
+        assert_eq!(tree.query(..), u32::MAX);
+
+        let empty: SegmentTree<i32, Sum> = SegmentTree::from_slice(&[]);
+        assert!(empty.is_empty());
+        assert_eq!(empty.query(..), 0);
+
+        let single: SegmentTree<i32, Max> = SegmentTree::from_slice(&[-7]);
+        assert_eq!(single.query(..), -7);
+        assert_eq!(single.len(), 1);
+    }
+}
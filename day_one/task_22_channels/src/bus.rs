@@ -0,0 +1,450 @@
+use std::fmt;
+// Topics are handed to subscribers as `Arc<str>`, which loom's `Arc` can't
+// hold, so this module uses `std::sync::Arc`; only the locks are shimmed
+use std::sync::Arc;
+
+use sync_shim::atomic::{AtomicUsize, Ordering};
+use sync_shim::{Condvar, Mutex, MutexGuard};
+use task_05_ring_buffer::RingBuffer;
+
+use crate::error::{RecvError, TryRecvError};
+
+/// What a publisher does when a subscriber's buffer is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LagPolicy {
+    /// Overwrite the subscriber's oldest event; `Subscription::dropped` counts the losses
+    DropOldest,
+    /// Wait until the subscriber makes room, slowing every publisher down to its pace
+    Block,
+    /// Cut the subscriber off; it can still drain what it has before `recv` fails
+    Disconnect,
+}
+
+/// A published event as seen by a subscriber
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event<T> {
+    pub topic: Arc<str>,
+    pub payload: T,
+}
+
+struct Inbox<T> {
+    events: RingBuffer<Event<T>>,
+    dropped: u64,
+    /// Cut off by `LagPolicy::Disconnect`
+    lagged: bool,
+    /// The `Subscription` was dropped, or every `EventBus` handle was
+    closed: bool,
+}
+
+struct Subscriber<T> {
+    filter: String,
+    policy: LagPolicy,
+    inbox: Mutex<Inbox<T>>,
+    /// Signalled whenever an event arrives, one is taken, or the inbox closes
+    changed: Condvar,
+}
+
+impl<T> Subscriber<T> {
+    // Topics are dot-separated; `*` matches everything and `a.*` matches
+    // anything below `a`, such as `a.b` or `a.b.c`
+    fn accepts(&self, topic: &str) -> bool {
+        match self.filter.strip_suffix('*') {
+            Some("") => true,
+            Some(prefix) if prefix.ends_with('.') => topic.starts_with(prefix),
+            _ => self.filter == topic,
+        }
+    }
+
+    fn inbox(&self) -> MutexGuard<'_, Inbox<T>> {
+        self.inbox.lock().unwrap()
+    }
+
+    fn is_live(&self) -> bool {
+        let inbox = self.inbox();
+        !inbox.lagged && !inbox.closed
+    }
+
+    fn close(&self) {
+        self.inbox().closed = true;
+        self.changed.notify_all();
+    }
+}
+
+struct Shared<T> {
+    subscribers: Mutex<Vec<Arc<Subscriber<T>>>>,
+    publishers: AtomicUsize,
+}
+
+/// In-process publish/subscribe bus
+///
+/// Every subscriber gets its own bounded `RingBuffer` inbox and a topic
+/// filter. Publishing clones the event into each matching inbox; what happens
+/// when an inbox is full is the subscriber's `LagPolicy`, so one slow
+/// subscriber can be made to lose events, hold up the publishers, or drop off
+/// without affecting how the others are treated.
+///
+/// Clone the bus to publish from several places. Once every handle is
+/// dropped, subscribers drain what is left and then `recv` returns `RecvError`.
+pub struct EventBus<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T: Clone> EventBus<T> {
+    pub fn new() -> Self {
+        EventBus {
+            shared: Arc::new(Shared {
+                subscribers: Mutex::new(Vec::new()),
+                publishers: AtomicUsize::new(1),
+            }),
+        }
+    }
+
+    /// Subscribes to topics matching `filter`, buffering up to `capacity` events
+    ///
+    /// `filter` is a topic such as `orders.created`, a prefix such as `orders.*`,
+    /// or `*` for every topic.
+    pub fn subscribe(&self, filter: &str, capacity: usize, policy: LagPolicy) -> Subscription<T> {
+        assert!(capacity > 0, "subscription capacity must be non-zero");
+        let subscriber = Arc::new(Subscriber {
+            filter: filter.to_string(),
+            policy,
+            inbox: Mutex::new(Inbox {
+                events: RingBuffer::new(capacity),
+                dropped: 0,
+                lagged: false,
+                closed: false,
+            }),
+            changed: Condvar::new(),
+        });
+        self.subscribers().push(Arc::clone(&subscriber));
+        Subscription { subscriber }
+    }
+
+    /// Delivers `payload` to every live subscriber whose filter matches `topic`,
+    /// returning how many received it
+    ///
+    /// Blocks while a matching `LagPolicy::Block` subscriber is full.
+    pub fn publish(&self, topic: &str, payload: T) -> usize {
+        let topic: Arc<str> = Arc::from(topic);
+        // Deliver outside the list lock so a blocked delivery doesn't stall
+        // subscribe and other publishers' lookups
+        let matching: Vec<Arc<Subscriber<T>>> = self
+            .subscribers()
+            .iter()
+            .filter(|subscriber| subscriber.accepts(&topic))
+            .cloned()
+            .collect();
+
+        let mut delivered = 0;
+        for subscriber in matching {
+            let mut inbox = subscriber.inbox();
+            if subscriber.policy == LagPolicy::Block {
+                while inbox.events.is_full() && !inbox.closed {
+                    inbox = subscriber.changed.wait(inbox).unwrap();
+                }
+            }
+            if inbox.lagged || inbox.closed {
+                continue;
+            }
+            if inbox.events.is_full() {
+                match subscriber.policy {
+                    LagPolicy::DropOldest => inbox.dropped += 1,
+                    LagPolicy::Disconnect => {
+                        inbox.lagged = true;
+                        drop(inbox);
+                        subscriber.changed.notify_all();
+                        continue;
+                    }
+                    LagPolicy::Block => unreachable!("blocking publishers wait for room"),
+                }
+            }
+            inbox.events.push(Event {
+                topic: Arc::clone(&topic),
+                payload: payload.clone(),
+            });
+            drop(inbox);
+            subscriber.changed.notify_all();
+            delivered += 1;
+        }
+        self.subscribers().retain(|subscriber| subscriber.is_live());
+        delivered
+    }
+
+    /// Returns the number of subscribers still receiving events
+    pub fn subscriber_count(&self) -> usize {
+        let mut subscribers = self.subscribers();
+        subscribers.retain(|subscriber| subscriber.is_live());
+        subscribers.len()
+    }
+
+    fn subscribers(&self) -> MutexGuard<'_, Vec<Arc<Subscriber<T>>>> {
+        self.shared.subscribers.lock().unwrap()
+    }
+}
+
+impl<T: Clone> Default for EventBus<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for EventBus<T> {
+    fn clone(&self) -> Self {
+        self.shared.publishers.fetch_add(1, Ordering::Relaxed);
+        EventBus {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl<T> Drop for EventBus<T> {
+    fn drop(&mut self) {
+        if self.shared.publishers.fetch_sub(1, Ordering::AcqRel) == 1 {
+            let subscribers = std::mem::take(&mut *self.shared.subscribers.lock().unwrap());
+            for subscriber in subscribers {
+                subscriber.close();
+            }
+        }
+    }
+}
+
+impl<T> fmt::Debug for EventBus<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventBus")
+            .field(
+                "publishers",
+                &self.shared.publishers.load(Ordering::Relaxed),
+            )
+            .finish_non_exhaustive()
+    }
+}
+
+/// A subscriber's end of the bus; dropping it unsubscribes
+pub struct Subscription<T> {
+    subscriber: Arc<Subscriber<T>>,
+}
+
+impl<T> Subscription<T> {
+    /// Takes the oldest buffered event without waiting
+    pub fn try_recv(&self) -> Result<Event<T>, TryRecvError> {
+        let mut inbox = self.subscriber.inbox();
+        match self.take(&mut inbox) {
+            Some(event) => Ok(event),
+            None if inbox.lagged || inbox.closed => Err(TryRecvError::Disconnected),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    /// Waits for the next event; fails once the subscription is cut off or the
+    /// bus is gone and the buffer is drained
+    pub fn recv(&self) -> Result<Event<T>, RecvError> {
+        let mut inbox = self.subscriber.inbox();
+        loop {
+            if let Some(event) = self.take(&mut inbox) {
+                return Ok(event);
+            }
+            if inbox.lagged || inbox.closed {
+                return Err(RecvError);
+            }
+            inbox = self.subscriber.changed.wait(inbox).unwrap();
+        }
+    }
+
+    /// Iterates over buffered events without waiting
+    pub fn try_iter(&self) -> impl Iterator<Item = Event<T>> + '_ {
+        std::iter::from_fn(|| self.try_recv().ok())
+    }
+
+    /// Returns how many events were overwritten under `LagPolicy::DropOldest`
+    pub fn dropped(&self) -> u64 {
+        self.subscriber.inbox().dropped
+    }
+
+    /// Checks if a `LagPolicy::Disconnect` subscription fell behind and was cut off
+    pub fn is_lagged(&self) -> bool {
+        self.subscriber.inbox().lagged
+    }
+
+    /// Returns the filter this subscription was created with
+    pub fn filter(&self) -> &str {
+        &self.subscriber.filter
+    }
+
+    fn take(&self, inbox: &mut Inbox<T>) -> Option<Event<T>> {
+        let event = inbox.events.pop()?;
+        // A blocked publisher may be waiting for this slot
+        self.subscriber.changed.notify_all();
+        Some(event)
+    }
+}
+
+impl<T> Drop for Subscription<T> {
+    fn drop(&mut self) {
+        // Wakes a publisher blocked on this inbox; the bus prunes it on the next publish
+        self.subscriber.close();
+    }
+}
+
+impl<T> fmt::Debug for Subscription<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Subscription")
+            .field("filter", &self.subscriber.filter)
+            .field("policy", &self.subscriber.policy)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::*;
+    use std::thread;
+    use std::time::Duration;
+
+    fn payloads<T>(subscription: &Subscription<T>) -> Vec<T> {
+        subscription.try_iter().map(|event| event.payload).collect()
+    }
+
+    #[test]
+    fn test_happy_path_broadcast_to_every_subscriber() {
+        let bus = EventBus::new();
+        let first = bus.subscribe("*", 4, LagPolicy::DropOldest);
+        let second = bus.subscribe("*", 4, LagPolicy::DropOldest);
+        assert_eq!(bus.publish("tick", 1), 2);
+        assert_eq!(bus.publish("tick", 2), 2);
+
+        let event = first.recv().unwrap();
+        assert_eq!((&*event.topic, event.payload), ("tick", 1));
+        assert_eq!(payloads(&first), vec![2]);
+        assert_eq!(payloads(&second), vec![1, 2]);
+        assert_eq!(first.try_recv(), Err(TryRecvError::Empty));
+    }
+
+    #[test]
+    fn test_topic_filters() {
+        let bus = EventBus::new();
+        let exact = bus.subscribe("orders.created", 8, LagPolicy::DropOldest);
+        let prefix = bus.subscribe("orders.*", 8, LagPolicy::DropOldest);
+        let everything = bus.subscribe("*", 8, LagPolicy::DropOldest);
+        for topic in [
+            "orders.created",
+            "orders.paid.card",
+            "orders",
+            "users.created",
+        ] {
+            bus.publish(topic, topic);
+        }
+        assert_eq!(payloads(&exact), vec!["orders.created"]);
+        assert_eq!(
+            payloads(&prefix),
+            vec!["orders.created", "orders.paid.card"]
+        );
+        assert_eq!(payloads(&everything).len(), 4);
+    }
+
+    #[test]
+    fn test_drop_oldest_counts_losses() {
+        let bus = EventBus::new();
+        let slow = bus.subscribe("*", 2, LagPolicy::DropOldest);
+        for value in 1..=5 {
+            bus.publish("n", value);
+        }
+        assert_eq!(payloads(&slow), vec![4, 5]);
+        assert_eq!(slow.dropped(), 3);
+    }
+
+    #[test]
+    fn test_disconnect_cuts_off_a_lagging_subscriber() {
+        let bus = EventBus::new();
+        let slow = bus.subscribe("*", 2, LagPolicy::Disconnect);
+        let fast = bus.subscribe("*", 8, LagPolicy::DropOldest);
+        assert_eq!(bus.publish("n", 1), 2);
+        assert_eq!(bus.publish("n", 2), 2);
+        assert_eq!(bus.publish("n", 3), 1, "The slow inbox was full");
+        assert_eq!(bus.subscriber_count(), 1);
+
+        assert!(slow.is_lagged());
+        assert_eq!(
+            slow.recv().unwrap().payload,
+            1,
+            "Buffered events still drain"
+        );
+        assert_eq!(slow.recv().unwrap().payload, 2);
+        assert_eq!(slow.recv(), Err(RecvError));
+        assert_eq!(payloads(&fast), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_block_waits_for_the_subscriber() {
+        let bus = EventBus::new();
+        let subscription = bus.subscribe("*", 1, LagPolicy::Block);
+        let publisher = thread::spawn({
+            let bus = bus.clone();
+            move || {
+                for value in 0..100 {
+                    bus.publish("n", value);
+                }
+            }
+        });
+        drop(bus);
+        let mut received = Vec::new();
+        while let Ok(event) = subscription.recv() {
+            received.push(event.payload);
+            if received.len() % 10 == 0 {
+                thread::sleep(Duration::from_millis(1));
+            }
+        }
+        publisher.join().unwrap();
+        assert_eq!(received, (0..100).collect::<Vec<_>>(), "Nothing is lost");
+    }
+
+    #[test]
+    fn test_unhappy_path_dropped_subscription_releases_a_blocked_publisher() {
+        let bus = EventBus::new();
+        let subscription = bus.subscribe("*", 1, LagPolicy::Block);
+        bus.publish("n", 1);
+        let publisher = thread::spawn({
+            let bus = bus.clone();
+            move || bus.publish("n", 2)
+        });
+        thread::sleep(Duration::from_millis(10));
+        drop(subscription);
+        assert_eq!(publisher.join().unwrap(), 0);
+        assert_eq!(bus.subscriber_count(), 0);
+    }
+
+    #[test]
+    fn test_edge_case_bus_dropped_and_no_subscribers() {
+        let bus: EventBus<u8> = EventBus::new();
+        assert_eq!(bus.publish("nobody", 0), 0);
+        let subscription = bus.subscribe("*", 2, LagPolicy::DropOldest);
+        bus.publish("last", 9);
+        drop(bus);
+        assert_eq!(subscription.recv().unwrap().payload, 9);
+        assert_eq!(subscription.try_recv(), Err(TryRecvError::Disconnected));
+        assert_eq!(subscription.filter(), "*");
+    }
+}
+
+#[cfg(all(test, loom))]
+mod loom_tests {
+    use super::*;
+    use sync_shim::thread;
+
+    #[test]
+    fn loom_blocked_publisher_is_woken_by_recv() {
+        // With room for one event, the second publish must wait for the first
+        // recv and then be woken rather than sleep forever
+        sync_shim::model(|| {
+            let bus = EventBus::new();
+            let subscription = bus.subscribe("*", 1, LagPolicy::Block);
+            let publisher = thread::spawn(move || {
+                bus.publish("n", 1);
+                bus.publish("n", 2);
+            });
+            assert_eq!(subscription.recv().unwrap().payload, 1);
+            assert_eq!(subscription.recv().unwrap().payload, 2);
+            assert_eq!(subscription.recv(), Err(RecvError));
+            publisher.join().unwrap();
+        });
+    }
+}
@@ -0,0 +1,14 @@
+//! Bounded channels for passing values between threads
+//!
+//! Each flavour lives in its own module and hands out a `Sender`/`Receiver`
+//! pair from a `channel(capacity)` function; the error types are shared.
+//! `select!` waits on several `mpmc` receivers at once. `bus` builds a
+//! publish/subscribe `EventBus` on top of per-subscriber ring buffers.
+
+pub mod bus;
+pub mod error;
+pub mod mpmc;
+pub mod spsc;
+
+pub use bus::{Event, EventBus, LagPolicy, Subscription};
+pub use error::{RecvError, SendError, TryRecvError, TrySendError};
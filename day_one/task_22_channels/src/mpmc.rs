@@ -0,0 +1,703 @@
+use std::mem::MaybeUninit;
+
+use sync_shim::atomic::{fence, AtomicUsize, Ordering};
+use sync_shim::cell::UnsafeCell;
+use sync_shim::thread::{self, Thread};
+use sync_shim::{Arc, Mutex};
+
+use crate::error::{RecvError, SendError, TryRecvError, TrySendError};
+
+struct Slot<T> {
+    /// `2 * pos` while the slot is free for the producer at `pos`, and
+    /// `2 * pos + 1` once that producer's value can be consumed
+    ///
+    /// Vyukov's original uses `pos` and `pos + 1`, which cannot tell a full
+    /// slot from a free one when the capacity is 1; doubling keeps them apart.
+    sequence: AtomicUsize,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+/// Dmitry Vyukov's bounded multi-producer multi-consumer queue
+///
+/// Producers and consumers each claim a position with a compare-and-swap on
+/// their own counter, then use the slot's sequence number to tell whether it is
+/// ready for them. Nobody ever waits on a lock, and a producer and a consumer
+/// only touch the same cache line when they work on the same slot.
+struct Ring<T> {
+    slots: Box<[Slot<T>]>,
+    enqueue_pos: AtomicUsize,
+    dequeue_pos: AtomicUsize,
+}
+
+impl<T> Ring<T> {
+    fn new(capacity: usize) -> Self {
+        Ring {
+            slots: (0..capacity)
+                .map(|i| Slot {
+                    sequence: AtomicUsize::new(2 * i),
+                    value: UnsafeCell::new(MaybeUninit::uninit()),
+                })
+                .collect(),
+            enqueue_pos: AtomicUsize::new(0),
+            dequeue_pos: AtomicUsize::new(0),
+        }
+    }
+
+    fn push(&self, value: T) -> Result<(), T> {
+        let mut pos = self.enqueue_pos.load(Ordering::Relaxed);
+        loop {
+            let slot = &self.slots[pos % self.slots.len()];
+            let diff = slot.sequence.load(Ordering::Acquire) as isize - (2 * pos) as isize;
+            if diff == 0 {
+                match self.enqueue_pos.compare_exchange_weak(
+                    pos,
+                    pos + 1,
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        // SAFETY: winning the CAS gives this producer sole use of the slot
+                        slot.value.with_mut(|cell| unsafe { (*cell).write(value) });
+                        slot.sequence.store(2 * pos + 1, Ordering::Release);
+                        return Ok(());
+                    }
+                    Err(current) => pos = current,
+                }
+            } else if diff < 0 {
+                // The slot still holds the value from one lap ago
+                return Err(value);
+            } else {
+                pos = self.enqueue_pos.load(Ordering::Relaxed);
+            }
+        }
+    }
+
+    fn pop(&self) -> Option<T> {
+        let mut pos = self.dequeue_pos.load(Ordering::Relaxed);
+        loop {
+            let slot = &self.slots[pos % self.slots.len()];
+            let diff = slot.sequence.load(Ordering::Acquire) as isize - (2 * pos + 1) as isize;
+            if diff == 0 {
+                match self.dequeue_pos.compare_exchange_weak(
+                    pos,
+                    pos + 1,
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        // SAFETY: winning the CAS gives this consumer sole use of the slot,
+                        // and the sequence check saw the producer's write
+                        let value = slot.value.with(|cell| unsafe { (*cell).assume_init_read() });
+                        slot.sequence
+                            .store(2 * (pos + self.slots.len()), Ordering::Release);
+                        return Some(value);
+                    }
+                    Err(current) => pos = current,
+                }
+            } else if diff < 0 {
+                // The producer for this position has not finished yet
+                return None;
+            } else {
+                pos = self.dequeue_pos.load(Ordering::Relaxed);
+            }
+        }
+    }
+
+    fn has_value(&self) -> bool {
+        let pos = self.dequeue_pos.load(Ordering::Acquire);
+        let slot = &self.slots[pos % self.slots.len()];
+        slot.sequence.load(Ordering::Acquire) == 2 * pos + 1
+    }
+
+    fn has_space(&self) -> bool {
+        let pos = self.enqueue_pos.load(Ordering::Acquire);
+        let slot = &self.slots[pos % self.slots.len()];
+        slot.sequence.load(Ordering::Acquire) == 2 * pos
+    }
+
+    fn len(&self) -> usize {
+        let dequeue = self.dequeue_pos.load(Ordering::Acquire);
+        let enqueue = self.enqueue_pos.load(Ordering::Acquire);
+        enqueue.saturating_sub(dequeue).min(self.slots.len())
+    }
+}
+
+impl<T> Drop for Ring<T> {
+    fn drop(&mut self) {
+        while self.pop().is_some() {}
+    }
+}
+
+/// Threads parked until the channel changes
+///
+/// `len` mirrors the list so the common case, nobody waiting, costs a fence
+/// and a load instead of a lock.
+struct Waiters {
+    len: AtomicUsize,
+    threads: Mutex<Vec<Thread>>,
+}
+
+impl Waiters {
+    fn new() -> Self {
+        Waiters {
+            len: AtomicUsize::new(0),
+            threads: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn register(&self) {
+        let mut threads = self.threads.lock().unwrap();
+        threads.push(thread::current());
+        self.len.store(threads.len(), Ordering::SeqCst);
+    }
+
+    fn unregister(&self) {
+        let id = thread::current().id();
+        let mut threads = self.threads.lock().unwrap();
+        threads.retain(|thread| thread.id() != id);
+        self.len.store(threads.len(), Ordering::SeqCst);
+    }
+
+    /// Parks the current thread unless `ready` holds once it is registered
+    fn wait_unless(&self, ready: impl Fn() -> bool) {
+        self.register();
+        // Pairs with the fence in `notify_*`: either the notifier sees us
+        // registered or we see the change it published
+        fence(Ordering::SeqCst);
+        if !ready() {
+            thread::park();
+        }
+        self.unregister();
+    }
+
+    fn notify_one(&self) {
+        fence(Ordering::SeqCst);
+        if self.len.load(Ordering::SeqCst) == 0 {
+            return;
+        }
+        // Unpark after the guard is gone so the woken thread isn't straight
+        // back to waiting on the lock in `unregister`
+        let woken = {
+            let mut threads = self.threads.lock().unwrap();
+            let woken = threads.pop();
+            self.len.store(threads.len(), Ordering::SeqCst);
+            woken
+        };
+        if let Some(thread) = woken {
+            thread.unpark();
+        }
+    }
+
+    fn notify_all(&self) {
+        fence(Ordering::SeqCst);
+        if self.len.load(Ordering::SeqCst) == 0 {
+            return;
+        }
+        let woken = {
+            let mut threads = self.threads.lock().unwrap();
+            self.len.store(0, Ordering::SeqCst);
+            std::mem::take(&mut *threads)
+        };
+        for thread in woken {
+            thread.unpark();
+        }
+    }
+}
+
+struct Shared<T> {
+    ring: Ring<T>,
+    senders: AtomicUsize,
+    receivers: AtomicUsize,
+    /// Senders waiting for free space
+    send_waiters: Waiters,
+    /// Receivers waiting for a value
+    recv_waiters: Waiters,
+    /// Threads inside `select!` watching this channel among others
+    selectors: Waiters,
+}
+
+// SAFETY: each value is written by one producer and read by one consumer,
+// with the slot's sequence number ordering the two
+unsafe impl<T: Send> Sync for Shared<T> {}
+
+impl<T> Shared<T> {
+    fn is_disconnected_for_receivers(&self) -> bool {
+        self.senders.load(Ordering::SeqCst) == 0
+    }
+
+    fn is_disconnected_for_senders(&self) -> bool {
+        self.receivers.load(Ordering::SeqCst) == 0
+    }
+}
+
+/// Creates a bounded multi-producer multi-consumer channel
+///
+/// Both halves can be cloned; each value is received by exactly one receiver.
+/// The channel disconnects for receivers once every `Sender` is dropped, and
+/// for senders once every `Receiver` is dropped.
+pub fn channel<T>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(capacity > 0, "mpmc channel capacity must be non-zero");
+    let shared = Arc::new(Shared {
+        ring: Ring::new(capacity),
+        senders: AtomicUsize::new(1),
+        receivers: AtomicUsize::new(1),
+        send_waiters: Waiters::new(),
+        recv_waiters: Waiters::new(),
+        selectors: Waiters::new(),
+    });
+    (
+        Sender {
+            shared: Arc::clone(&shared),
+        },
+        Receiver { shared },
+    )
+}
+
+/// The sending half of an MPMC channel
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Sender<T> {
+    /// Sends without blocking, failing if the channel is full or disconnected
+    pub fn try_send(&self, value: T) -> Result<(), TrySendError<T>> {
+        if self.shared.is_disconnected_for_senders() {
+            return Err(TrySendError::Disconnected(value));
+        }
+        match self.shared.ring.push(value) {
+            Ok(()) => {
+                self.shared.recv_waiters.notify_one();
+                self.shared.selectors.notify_all();
+                Ok(())
+            }
+            Err(value) => Err(TrySendError::Full(value)),
+        }
+    }
+
+    /// Sends, waiting for free space if the channel is full
+    pub fn send(&self, mut value: T) -> Result<(), SendError<T>> {
+        loop {
+            match self.try_send(value) {
+                Ok(()) => return Ok(()),
+                Err(TrySendError::Disconnected(v)) => return Err(SendError(v)),
+                Err(TrySendError::Full(v)) => value = v,
+            }
+            let shared = &self.shared;
+            shared
+                .send_waiters
+                .wait_unless(|| shared.ring.has_space() || shared.is_disconnected_for_senders());
+        }
+    }
+
+    /// Checks if every receiver has been dropped
+    pub fn is_disconnected(&self) -> bool {
+        self.shared.is_disconnected_for_senders()
+    }
+
+    /// Returns the number of values waiting to be received
+    pub fn len(&self) -> usize {
+        self.shared.ring.len()
+    }
+
+    /// Checks if no values are waiting to be received
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the channel's capacity
+    pub fn capacity(&self) -> usize {
+        self.shared.ring.slots.len()
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.shared.senders.fetch_add(1, Ordering::SeqCst);
+        Sender {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        if self.shared.senders.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.shared.recv_waiters.notify_all();
+            self.shared.selectors.notify_all();
+        }
+    }
+}
+
+/// The receiving half of an MPMC channel
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Receiver<T> {
+    /// Receives without blocking
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        if let Some(value) = self.shared.ring.pop() {
+            self.shared.send_waiters.notify_one();
+            return Ok(value);
+        }
+        if !self.shared.is_disconnected_for_receivers() {
+            return Err(TryRecvError::Empty);
+        }
+        // A sender may have pushed one last value right before dropping
+        match self.shared.ring.pop() {
+            Some(value) => Ok(value),
+            None => Err(TryRecvError::Disconnected),
+        }
+    }
+
+    /// Receives, waiting for a value; fails once every sender is gone and the channel is drained
+    pub fn recv(&self) -> Result<T, RecvError> {
+        loop {
+            match self.try_recv() {
+                Ok(value) => return Ok(value),
+                Err(TryRecvError::Disconnected) => return Err(RecvError),
+                Err(TryRecvError::Empty) => {}
+            }
+            self.shared.recv_waiters.wait_unless(|| self.is_ready());
+        }
+    }
+
+    /// Iterates over received values until every sender disconnects
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        std::iter::from_fn(|| self.recv().ok())
+    }
+
+    /// Checks if every sender has been dropped
+    pub fn is_disconnected(&self) -> bool {
+        self.shared.is_disconnected_for_receivers()
+    }
+
+    /// Returns the number of values waiting to be received
+    pub fn len(&self) -> usize {
+        self.shared.ring.len()
+    }
+
+    /// Checks if no values are waiting to be received
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Receives for `select!`: `None` means nothing has happened yet
+    #[doc(hidden)]
+    pub fn poll_select(&self) -> Option<Result<T, RecvError>> {
+        match self.try_recv() {
+            Ok(value) => Some(Ok(value)),
+            Err(TryRecvError::Disconnected) => Some(Err(RecvError)),
+            Err(TryRecvError::Empty) => None,
+        }
+    }
+
+    /// A `recv` would return right away
+    fn is_ready(&self) -> bool {
+        self.shared.ring.has_value() || self.shared.is_disconnected_for_receivers()
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Self {
+        self.shared.receivers.fetch_add(1, Ordering::SeqCst);
+        Receiver {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        if self.shared.receivers.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.shared.send_waiters.notify_all();
+        }
+    }
+}
+
+/// A receiver `select!` can wait on, regardless of its value type
+#[doc(hidden)]
+pub trait Selectable {
+    fn is_ready(&self) -> bool;
+    fn watch(&self);
+    fn unwatch(&self);
+}
+
+impl<T> Selectable for Receiver<T> {
+    fn is_ready(&self) -> bool {
+        Receiver::is_ready(self)
+    }
+
+    fn watch(&self) {
+        self.shared.selectors.register();
+    }
+
+    fn unwatch(&self) {
+        self.shared.selectors.unregister();
+    }
+}
+
+impl<S: Selectable + ?Sized> Selectable for &S {
+    fn is_ready(&self) -> bool {
+        (**self).is_ready()
+    }
+
+    fn watch(&self) {
+        (**self).watch()
+    }
+
+    fn unwatch(&self) {
+        (**self).unwatch()
+    }
+}
+
+/// Parks until at least one of `receivers` has a value or has disconnected
+#[doc(hidden)]
+pub fn wait_any(receivers: &[&dyn Selectable]) {
+    for receiver in receivers {
+        receiver.watch();
+    }
+    fence(Ordering::SeqCst);
+    if !receivers.iter().any(|receiver| receiver.is_ready()) {
+        thread::park();
+    }
+    for receiver in receivers {
+        receiver.unwatch();
+    }
+}
+
+/// Waits on several `mpmc::Receiver`s at once and runs the arm of the first one that is ready
+///
+/// Each arm binds `Ok(value)` when a value arrives, or `Err(RecvError)` when
+/// that channel has disconnected and is drained. Arms are tried in order, so an
+/// earlier receiver wins when several are ready; the receiver expressions are
+/// evaluated on every attempt and should be plain variables or references. The
+/// whole `select!` evaluates to the value of the arm that ran.
+///
+/// ```
+/// use task_22_channels::{mpmc, select};
+///
+/// let (numbers_tx, numbers) = mpmc::channel::<u32>(4);
+/// let (words_tx, words) = mpmc::channel::<&str>(4);
+/// words_tx.send("hello").unwrap();
+/// drop(numbers_tx);
+///
+/// let got = select! {
+///     recv(numbers) -> msg => format!("number {:?}", msg),
+///     recv(words) -> msg => format!("word {:?}", msg),
+/// };
+/// assert_eq!(got, "number Err(RecvError)");
+/// ```
+#[macro_export]
+macro_rules! select {
+    ($(recv($rx:expr) -> $msg:pat => $body:expr),+ $(,)?) => {
+        loop {
+            $(
+                if let ::std::option::Option::Some(__result) =
+                    $crate::mpmc::Receiver::poll_select(&$rx)
+                {
+                    let $msg = __result;
+                    break $body;
+                }
+            )+
+            $crate::mpmc::wait_any(&[$(&$rx as &dyn $crate::mpmc::Selectable),+]);
+        }
+    };
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn test_happy_path_every_value_received_once() {
+        let (tx, rx) = channel(8);
+        let producers: Vec<_> = (0..4u32)
+            .map(|p| {
+                let tx = tx.clone();
+                thread::spawn(move || {
+                    for i in 0..2_500 {
+                        tx.send(p * 10_000 + i).unwrap();
+                    }
+                })
+            })
+            .collect();
+        drop(tx);
+        let consumers: Vec<_> = (0..3)
+            .map(|_| {
+                let rx = rx.clone();
+                thread::spawn(move || rx.iter().collect::<Vec<u32>>())
+            })
+            .collect();
+        drop(rx);
+
+        for producer in producers {
+            producer.join().unwrap();
+        }
+        let mut seen = HashSet::new();
+        for consumer in consumers {
+            for value in consumer.join().unwrap() {
+                assert!(seen.insert(value), "{} was received twice", value);
+            }
+        }
+        assert_eq!(seen.len(), 10_000);
+    }
+
+    #[test]
+    fn test_try_send_reports_full_and_keeps_fifo() {
+        let (tx, rx) = channel(3);
+        for i in 0..3 {
+            tx.try_send(i).unwrap();
+        }
+        assert_eq!(tx.try_send(3), Err(TrySendError::Full(3)));
+        assert_eq!(tx.len(), 3);
+        assert_eq!(tx.capacity(), 3);
+        assert_eq!(rx.try_recv(), Ok(0));
+        tx.try_send(3).unwrap();
+        let rest: Vec<_> = std::iter::from_fn(|| rx.try_recv().ok()).collect();
+        assert_eq!(rest, vec![1, 2, 3]);
+        assert!(rx.is_empty());
+    }
+
+    #[test]
+    fn test_blocking_send_waits_for_space() {
+        let (tx, rx) = channel(1);
+        tx.send(1).unwrap();
+        let producer = thread::spawn(move || tx.send(2));
+        thread::sleep(Duration::from_millis(20));
+        assert!(
+            !producer.is_finished(),
+            "Sender blocks while the channel is full"
+        );
+        assert_eq!(rx.recv(), Ok(1));
+        producer.join().unwrap().unwrap();
+        assert_eq!(rx.recv(), Ok(2));
+    }
+
+    #[test]
+    fn test_unhappy_path_disconnect_waits_for_every_clone() {
+        let (tx, rx) = channel(4);
+        let tx2 = tx.clone();
+        drop(tx);
+        assert!(!rx.is_disconnected(), "One sender is still alive");
+        tx2.send("last").unwrap();
+        drop(tx2);
+        assert_eq!(rx.recv(), Ok("last"));
+        assert_eq!(rx.recv(), Err(RecvError));
+
+        let (tx, rx) = channel(4);
+        let rx2 = rx.clone();
+        drop(rx);
+        tx.send(1).unwrap();
+        drop(rx2);
+        assert_eq!(tx.send(2), Err(SendError(2)));
+    }
+
+    #[test]
+    fn test_select_prefers_ready_receiver() {
+        let (numbers_tx, numbers) = channel::<u32>(4);
+        let (words_tx, words) = channel::<&str>(4);
+        words_tx.send("hi").unwrap();
+
+        let got = select! {
+            recv(numbers) -> msg => format!("number {:?}", msg),
+            recv(words) -> msg => format!("word {:?}", msg),
+        };
+        assert_eq!(got, "word Ok(\"hi\")");
+
+        numbers_tx.send(7).unwrap();
+        words_tx.send("later").unwrap();
+        let got = select! {
+            recv(numbers) -> msg => format!("number {:?}", msg),
+            recv(words) -> msg => format!("word {:?}", msg),
+        };
+        assert_eq!(got, "number Ok(7)", "Earlier arms win ties");
+    }
+
+    #[test]
+    fn test_select_blocks_until_another_thread_sends() {
+        let (a_tx, a) = channel::<u8>(1);
+        let (b_tx, b) = channel::<u8>(1);
+        let sender = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            b_tx.send(9).unwrap();
+            a_tx
+        });
+        let got = select! {
+            recv(a) -> msg => ('a', msg),
+            recv(b) -> msg => ('b', msg),
+        };
+        assert_eq!(got, ('b', Ok(9)));
+        drop(sender.join().unwrap());
+        let got = select! {
+            recv(a) -> msg => ('a', msg),
+            recv(b) -> msg => ('b', msg),
+        };
+        assert_eq!(got, ('a', Err(RecvError)), "Disconnect wakes the select");
+    }
+
+    #[test]
+    fn test_edge_case_unreceived_values_are_dropped() {
+        let tracker = Arc::new(());
+        let (tx, rx) = channel(8);
+        for _ in 0..5 {
+            tx.send(Arc::clone(&tracker)).unwrap();
+        }
+        drop(rx.recv().unwrap());
+        drop(tx);
+        drop(rx);
+        assert_eq!(Arc::strong_count(&tracker), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity must be non-zero")]
+    fn test_edge_case_zero_capacity() {
+        channel::<u8>(0);
+    }
+}
+
+// Run with `RUSTFLAGS="--cfg loom" cargo test --lib --release -p task_22_channels`
+#[cfg(all(test, loom))]
+mod loom_tests {
+    use super::*;
+
+    #[test]
+    fn loom_two_producers_race_for_positions() {
+        // Both producers CAS on `enqueue_pos`; each value must land in its own slot
+        sync_shim::model(|| {
+            let (tx, rx) = channel(2);
+            let other = thread::spawn({
+                let tx = tx.clone();
+                move || tx.send(1).unwrap()
+            });
+            tx.send(2).unwrap();
+            drop(tx);
+
+            let mut values = vec![rx.recv().unwrap(), rx.recv().unwrap()];
+            values.sort();
+            assert_eq!(values, [1, 2]);
+            other.join().unwrap();
+            assert_eq!(rx.recv(), Err(RecvError));
+        });
+    }
+
+    #[test]
+    fn loom_two_consumers_split_the_values() {
+        sync_shim::model(|| {
+            let (tx, rx) = channel(2);
+            tx.send(1).unwrap();
+            tx.send(2).unwrap();
+            drop(tx);
+            let other = thread::spawn({
+                let rx = rx.clone();
+                move || rx.recv().unwrap()
+            });
+            let mine = rx.recv().unwrap();
+            let theirs = other.join().unwrap();
+            assert_eq!(mine + theirs, 3, "each value is received once");
+        });
+    }
+}
@@ -0,0 +1,361 @@
+use sync_shim::atomic::{fence, AtomicBool, Ordering};
+use sync_shim::thread::{self, Thread};
+use sync_shim::{Arc, Mutex};
+use task_04_circular_buffer::spsc::{self, Consumer, Producer};
+
+use crate::error::{RecvError, SendError, TryRecvError, TrySendError};
+
+/// Lets one side sleep until the other side has made progress
+struct Waiter {
+    parked: AtomicBool,
+    thread: Mutex<Option<Thread>>,
+}
+
+impl Waiter {
+    fn new() -> Self {
+        Waiter {
+            parked: AtomicBool::new(false),
+            thread: Mutex::new(None),
+        }
+    }
+
+    /// Parks the current thread unless `ready` already holds; callers loop
+    fn wait_unless(&self, ready: impl Fn() -> bool) {
+        *self.thread.lock().unwrap() = Some(thread::current());
+        self.parked.store(true, Ordering::SeqCst);
+        // Re-check after announcing ourselves: a notify that raced with the
+        // announcement has either been seen here or will unpark us. The fence
+        // pairs with the one in `notify`: the ring only publishes its indices
+        // with release stores, which alone don't order a store before a later
+        // load of a different location
+        fence(Ordering::SeqCst);
+        if !ready() {
+            thread::park();
+        }
+        self.parked.store(false, Ordering::SeqCst);
+    }
+
+    fn notify(&self) {
+        fence(Ordering::SeqCst);
+        if self.parked.load(Ordering::SeqCst) {
+            if let Some(thread) = self.thread.lock().unwrap().as_ref() {
+                thread.unpark();
+            }
+        }
+    }
+}
+
+/// What the two halves share besides the ring: liveness flags and sleeping threads
+struct Shared {
+    sender_alive: AtomicBool,
+    receiver_alive: AtomicBool,
+    /// The receiver waiting for a value
+    receiver_waiter: Waiter,
+    /// The sender waiting for free space
+    sender_waiter: Waiter,
+}
+
+/// Creates a bounded single-producer single-consumer channel
+///
+/// Backed by the lock-free `spsc` ring from the circular buffer task: the
+/// `Sender` owns its `Producer` and the `Receiver` its `Consumer`, so the fast
+/// path of `try_send`/`try_recv` is a couple of atomic loads and stores. This
+/// module only adds blocking and disconnection: blocking calls park the thread
+/// until the other side makes progress or disconnects, and dropping either
+/// half disconnects it.
+pub fn channel<T>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(capacity > 0, "spsc channel capacity must be non-zero");
+    let (producer, consumer) = spsc::buffer(capacity);
+    let shared = Arc::new(Shared {
+        sender_alive: AtomicBool::new(true),
+        receiver_alive: AtomicBool::new(true),
+        receiver_waiter: Waiter::new(),
+        sender_waiter: Waiter::new(),
+    });
+    (
+        Sender {
+            producer,
+            shared: Arc::clone(&shared),
+        },
+        Receiver { consumer, shared },
+    )
+}
+
+/// The sending half of an SPSC channel; neither `Clone` nor `Sync`, there is only one producer
+pub struct Sender<T> {
+    producer: Producer<T>,
+    shared: Arc<Shared>,
+}
+
+impl<T> Sender<T> {
+    /// Sends without blocking, failing if the channel is full or disconnected
+    pub fn try_send(&self, value: T) -> Result<(), TrySendError<T>> {
+        if !self.shared.receiver_alive.load(Ordering::SeqCst) {
+            return Err(TrySendError::Disconnected(value));
+        }
+        match self.producer.push(value) {
+            Ok(()) => {
+                self.shared.receiver_waiter.notify();
+                Ok(())
+            }
+            Err(value) => Err(TrySendError::Full(value)),
+        }
+    }
+
+    /// Sends, waiting for free space if the channel is full
+    pub fn send(&self, mut value: T) -> Result<(), SendError<T>> {
+        loop {
+            match self.try_send(value) {
+                Ok(()) => return Ok(()),
+                Err(TrySendError::Disconnected(v)) => return Err(SendError(v)),
+                Err(TrySendError::Full(v)) => value = v,
+            }
+            let shared = &self.shared;
+            shared.sender_waiter.wait_unless(|| {
+                !self.producer.is_full() || !shared.receiver_alive.load(Ordering::SeqCst)
+            });
+        }
+    }
+
+    /// Checks if the receiver has been dropped
+    pub fn is_disconnected(&self) -> bool {
+        !self.shared.receiver_alive.load(Ordering::SeqCst)
+    }
+
+    /// Returns the number of values waiting to be received
+    pub fn len(&self) -> usize {
+        self.producer.len()
+    }
+
+    /// Checks if no values are waiting to be received
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the channel's capacity
+    pub fn capacity(&self) -> usize {
+        self.producer.capacity()
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        self.shared.sender_alive.store(false, Ordering::SeqCst);
+        self.shared.receiver_waiter.notify();
+    }
+}
+
+/// The receiving half of an SPSC channel
+pub struct Receiver<T> {
+    consumer: Consumer<T>,
+    shared: Arc<Shared>,
+}
+
+impl<T> Receiver<T> {
+    /// Receives without blocking
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        if let Some(value) = self.consumer.pop() {
+            self.shared.sender_waiter.notify();
+            return Ok(value);
+        }
+        if self.shared.sender_alive.load(Ordering::SeqCst) {
+            return Err(TryRecvError::Empty);
+        }
+        // The sender may have pushed one last value right before dropping
+        self.consumer.pop().ok_or(TryRecvError::Disconnected)
+    }
+
+    /// Receives, waiting for a value; fails once the sender is gone and the channel is drained
+    pub fn recv(&self) -> Result<T, RecvError> {
+        loop {
+            match self.try_recv() {
+                Ok(value) => return Ok(value),
+                Err(TryRecvError::Disconnected) => return Err(RecvError),
+                Err(TryRecvError::Empty) => {}
+            }
+            let shared = &self.shared;
+            shared.receiver_waiter.wait_unless(|| {
+                !self.consumer.is_empty() || !shared.sender_alive.load(Ordering::SeqCst)
+            });
+        }
+    }
+
+    /// Iterates over received values until the sender disconnects
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        std::iter::from_fn(|| self.recv().ok())
+    }
+
+    /// Checks if the sender has been dropped
+    pub fn is_disconnected(&self) -> bool {
+        !self.shared.sender_alive.load(Ordering::SeqCst)
+    }
+
+    /// Returns the number of values waiting to be received
+    pub fn len(&self) -> usize {
+        self.consumer.len()
+    }
+
+    /// Checks if no values are waiting to be received
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.shared.receiver_alive.store(false, Ordering::SeqCst);
+        self.shared.sender_waiter.notify();
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn test_happy_path_values_arrive_in_order_across_threads() {
+        let (tx, rx) = channel(16);
+        let producer = thread::spawn(move || {
+            for i in 0..10_000u32 {
+                tx.send(i).unwrap();
+            }
+        });
+        let received: Vec<u32> = rx.iter().collect();
+        producer.join().unwrap();
+        assert_eq!(received, (0..10_000).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_try_send_reports_full() {
+        let (tx, rx) = channel(2);
+        tx.try_send(1).unwrap();
+        tx.try_send(2).unwrap();
+        assert_eq!(tx.try_send(3), Err(TrySendError::Full(3)));
+        assert_eq!(tx.len(), 2);
+        assert_eq!(rx.try_recv(), Ok(1));
+        tx.try_send(3).unwrap();
+        assert_eq!(rx.try_recv(), Ok(2));
+        assert_eq!(rx.try_recv(), Ok(3));
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+    }
+
+    #[test]
+    fn test_blocking_send_waits_for_space() {
+        let (tx, rx) = channel(1);
+        tx.send("first").unwrap();
+        let producer = thread::spawn(move || tx.send("second"));
+        thread::sleep(Duration::from_millis(20));
+        assert!(
+            !producer.is_finished(),
+            "Sender blocks while the channel is full"
+        );
+        assert_eq!(rx.recv(), Ok("first"));
+        producer.join().unwrap().unwrap();
+        assert_eq!(rx.recv(), Ok("second"));
+    }
+
+    #[test]
+    fn test_unhappy_path_sender_drop_disconnects_after_drain() {
+        let (tx, rx) = channel(4);
+        tx.send(1).unwrap();
+        tx.send(2).unwrap();
+        drop(tx);
+        assert!(rx.is_disconnected());
+        assert_eq!(rx.recv(), Ok(1));
+        assert_eq!(rx.recv(), Ok(2));
+        assert_eq!(rx.recv(), Err(RecvError));
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Disconnected));
+    }
+
+    #[test]
+    fn test_unhappy_path_receiver_drop_returns_value() {
+        let (tx, rx) = channel(4);
+        drop(rx);
+        assert!(tx.is_disconnected());
+        assert_eq!(tx.send(7), Err(SendError(7)));
+        assert_eq!(tx.try_send(8).unwrap_err().into_inner(), 8);
+    }
+
+    #[test]
+    fn test_blocked_receiver_wakes_on_disconnect() {
+        let (tx, rx) = channel::<u8>(1);
+        let consumer = thread::spawn(move || rx.recv());
+        thread::sleep(Duration::from_millis(20));
+        drop(tx);
+        assert_eq!(consumer.join().unwrap(), Err(RecvError));
+    }
+
+    #[test]
+    fn test_edge_case_unreceived_values_are_dropped() {
+        #[derive(Debug)]
+        struct Counted(Arc<AtomicUsize>);
+        impl Drop for Counted {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+        let drops = Arc::new(AtomicUsize::new(0));
+        let (tx, rx) = channel(8);
+        for _ in 0..5 {
+            tx.send(Counted(Arc::clone(&drops))).unwrap();
+        }
+        drop(rx.recv().unwrap());
+        drop(tx);
+        drop(rx);
+        assert_eq!(drops.load(Ordering::SeqCst), 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity must be non-zero")]
+    fn test_edge_case_zero_capacity() {
+        channel::<u8>(0);
+    }
+}
+
+// Run with `RUSTFLAGS="--cfg loom" cargo test --lib --release -p task_22_channels`
+#[cfg(all(test, loom))]
+mod loom_tests {
+    use super::*;
+
+    #[test]
+    fn loom_blocking_send_and_recv_never_lose_a_wakeup() {
+        // Capacity 1 forces the producer to park on the second send
+        sync_shim::model(|| {
+            let (tx, rx) = channel(1);
+            let producer = thread::spawn(move || {
+                tx.send(1).unwrap();
+                tx.send(2).unwrap();
+            });
+            assert_eq!(rx.recv(), Ok(1));
+            assert_eq!(rx.recv(), Ok(2));
+            assert_eq!(rx.recv(), Err(RecvError));
+            producer.join().unwrap();
+        });
+    }
+
+    #[test]
+    fn loom_last_value_survives_disconnect() {
+        sync_shim::model(|| {
+            let (tx, rx) = channel(2);
+            let producer = thread::spawn(move || {
+                tx.try_send(7).unwrap();
+            });
+            // Either the value is still in flight or the sender is gone and it is queued
+            loop {
+                match rx.try_recv() {
+                    Ok(value) => {
+                        assert_eq!(value, 7);
+                        break;
+                    }
+                    Err(TryRecvError::Empty) => thread::yield_now(),
+                    Err(TryRecvError::Disconnected) => panic!("value was dropped"),
+                }
+            }
+            producer.join().unwrap();
+            assert_eq!(rx.try_recv(), Err(TryRecvError::Disconnected));
+        });
+    }
+}
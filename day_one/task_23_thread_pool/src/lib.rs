@@ -0,0 +1,379 @@
+use std::cell::RefCell;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, JoinHandle};
+
+use task_02_queue::Queue;
+use task_09_lock_free::work_steal::{Steal, Stealer, Worker};
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+thread_local! {
+    /// The pool the current thread works for and its end of that worker's deque, if any
+    static CURRENT_WORKER: RefCell<Option<(*const Shared, Worker<Job>)>> =
+        const { RefCell::new(None) };
+}
+
+/// Counters describing the work a pool has done
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PoolStats {
+    /// Tasks that ran to completion
+    pub completed: usize,
+    /// Tasks that panicked; the worker survived and moved on
+    pub panicked: usize,
+    /// Tasks an idle worker took from another worker's deque
+    pub stolen: usize,
+}
+
+struct Shared {
+    /// Tasks spawned from outside the pool
+    injector: Mutex<Queue<Job>>,
+    /// The stealing ends of the workers' deques, which hold tasks spawned from inside the pool
+    stealers: Vec<Stealer<Job>>,
+    /// Tasks spawned but not yet picked up by a worker
+    queued: AtomicUsize,
+    shutdown: AtomicBool,
+    sleep: Mutex<()>,
+    wake: Condvar,
+    completed: AtomicUsize,
+    panicked: AtomicUsize,
+    stolen: AtomicUsize,
+}
+
+impl Shared {
+    fn push(&self, job: Job) {
+        self.queued.fetch_add(1, Ordering::SeqCst);
+        let me: *const Shared = self;
+        let outside = CURRENT_WORKER.with(|current| match &*current.borrow() {
+            Some((pool, worker)) if *pool == me => {
+                worker.push(job);
+                None
+            }
+            _ => Some(job),
+        });
+        if let Some(job) = outside {
+            self.injector.lock().unwrap().enqueue(job);
+        }
+        // Taking the lock orders this with a worker's "nothing queued" check
+        let _guard = self.sleep.lock().unwrap();
+        self.wake.notify_one();
+    }
+
+    /// Own deque first, then the shared injector, then steal from the others
+    fn find_job(&self, index: usize) -> Option<Job> {
+        let own = CURRENT_WORKER.with(|current| {
+            current
+                .borrow()
+                .as_ref()
+                .and_then(|(_, worker)| worker.pop())
+        });
+        if let Some(job) = own {
+            return Some(job);
+        }
+        if let Some(job) = self.injector.lock().unwrap().dequeue() {
+            return Some(job);
+        }
+        self.steal(index).inspect(|_| {
+            self.stolen.fetch_add(1, Ordering::Relaxed);
+        })
+    }
+
+    /// Tries every other worker's deque, oldest task first, until one hands
+    /// over a task or all of them come back empty without a lost race
+    fn steal(&self, index: usize) -> Option<Job> {
+        let workers = self.stealers.len();
+        loop {
+            let mut retry = false;
+            for victim in (1..workers).map(|offset| (index + offset) % workers) {
+                match self.stealers[victim].steal() {
+                    Steal::Success(job) => return Some(job),
+                    Steal::Retry => retry = true,
+                    Steal::Empty => {}
+                }
+            }
+            if !retry {
+                return None;
+            }
+        }
+    }
+
+    fn run_worker(self: Arc<Self>, index: usize, worker: Worker<Job>) {
+        CURRENT_WORKER.with(|current| *current.borrow_mut() = Some((Arc::as_ptr(&self), worker)));
+        loop {
+            if let Some(job) = self.find_job(index) {
+                self.queued.fetch_sub(1, Ordering::SeqCst);
+                match panic::catch_unwind(AssertUnwindSafe(job)) {
+                    Ok(()) => self.completed.fetch_add(1, Ordering::Relaxed),
+                    Err(_) => self.panicked.fetch_add(1, Ordering::Relaxed),
+                };
+                continue;
+            }
+            let guard = self.sleep.lock().unwrap();
+            if self.queued.load(Ordering::SeqCst) > 0 {
+                // Counted but not pushed yet, or sitting in a deque we just missed
+                drop(guard);
+                thread::yield_now();
+                continue;
+            }
+            if self.shutdown.load(Ordering::SeqCst) {
+                break;
+            }
+            drop(self.wake.wait(guard).unwrap());
+        }
+        CURRENT_WORKER.with(|current| *current.borrow_mut() = None);
+    }
+}
+
+/// Fixed set of worker threads running spawned tasks
+///
+/// Tasks spawned from outside go through a shared injector queue (the repo's
+/// `Queue` behind a mutex). Tasks spawned by a running task go to that worker's
+/// own Chase-Lev deque from task 9's `work_steal`, and workers that run dry
+/// steal from each other, so recursive fork-style workloads spread across the
+/// pool without every spawn contending on one lock. With `safe-only` that
+/// deque is task 9's mutex-backed stand-in instead. A panicking task is caught and counted; it never
+/// takes its worker down.
+pub struct ThreadPool {
+    shared: Arc<Shared>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl ThreadPool {
+    /// Starts a pool with `threads` workers
+    pub fn new(threads: usize) -> Self {
+        assert!(threads > 0, "ThreadPool needs at least one thread");
+        let deques: Vec<Worker<Job>> = (0..threads).map(|_| Worker::new()).collect();
+        let shared = Arc::new(Shared {
+            injector: Mutex::new(Queue::new()),
+            stealers: deques.iter().map(Worker::stealer).collect(),
+            queued: AtomicUsize::new(0),
+            shutdown: AtomicBool::new(false),
+            sleep: Mutex::new(()),
+            wake: Condvar::new(),
+            completed: AtomicUsize::new(0),
+            panicked: AtomicUsize::new(0),
+            stolen: AtomicUsize::new(0),
+        });
+        let workers = deques
+            .into_iter()
+            .enumerate()
+            .map(|(index, worker)| {
+                let shared = Arc::clone(&shared);
+                thread::Builder::new()
+                    .name(format!("pool-worker-{}", index))
+                    .spawn(move || shared.run_worker(index, worker))
+                    .expect("failed to spawn a worker thread")
+            })
+            .collect();
+        ThreadPool { shared, workers }
+    }
+
+    /// Starts a pool with one worker per available CPU
+    pub fn with_available_parallelism() -> Self {
+        Self::new(thread::available_parallelism().map_or(1, |n| n.get()))
+    }
+
+    /// Returns the number of worker threads
+    pub fn threads(&self) -> usize {
+        self.shared.stealers.len()
+    }
+
+    /// Queues `task` to run on one of the workers
+    pub fn spawn<F>(&self, task: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.shared.push(Box::new(task));
+    }
+
+    /// Returns a handle that running tasks can use to spawn more tasks
+    pub fn spawner(&self) -> Spawner {
+        Spawner {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+
+    /// Returns a snapshot of the pool's counters
+    pub fn stats(&self) -> PoolStats {
+        PoolStats {
+            completed: self.shared.completed.load(Ordering::Relaxed),
+            panicked: self.shared.panicked.load(Ordering::Relaxed),
+            stolen: self.shared.stolen.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Runs every queued task, including ones they spawn, then stops the workers
+    pub fn shutdown(mut self) -> PoolStats {
+        self.stop();
+        self.stats()
+    }
+
+    fn stop(&mut self) {
+        self.shared.shutdown.store(true, Ordering::SeqCst);
+        {
+            let _guard = self.shared.sleep.lock().unwrap();
+            self.shared.wake.notify_all();
+        }
+        for worker in self.workers.drain(..) {
+            worker.join().expect("worker threads catch task panics");
+        }
+    }
+}
+
+impl Drop for ThreadPool {
+    /// Dropping the pool shuts it down gracefully as well
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Cloneable handle for spawning onto a `ThreadPool`, typically from inside a task
+///
+/// Tasks spawned through it from a worker thread land on that worker's own
+/// deque. A `Spawner` does not keep the workers alive: spawning after the pool
+/// has shut down queues a task that never runs.
+#[derive(Clone)]
+pub struct Spawner {
+    shared: Arc<Shared>,
+}
+
+impl Spawner {
+    /// Queues `task` to run on one of the workers
+    pub fn spawn<F>(&self, task: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.shared.push(Box::new(task));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_happy_path_runs_every_task() {
+        let pool = ThreadPool::new(4);
+        let counter = Arc::new(AtomicUsize::new(0));
+        for _ in 0..1_000 {
+            let counter = Arc::clone(&counter);
+            pool.spawn(move || {
+                counter.fetch_add(1, Ordering::Relaxed);
+            });
+        }
+        let stats = pool.shutdown();
+        assert_eq!(counter.load(Ordering::Relaxed), 1_000);
+        assert_eq!(stats.completed, 1_000);
+        assert_eq!(stats.panicked, 0);
+    }
+
+    #[test]
+    fn test_shutdown_waits_for_queued_and_nested_tasks() {
+        let pool = ThreadPool::new(2);
+        let spawner = pool.spawner();
+        let done = Arc::new(AtomicUsize::new(0));
+        for _ in 0..4 {
+            let (spawner, done) = (spawner.clone(), Arc::clone(&done));
+            pool.spawn(move || {
+                thread::sleep(Duration::from_millis(10));
+                let done = Arc::clone(&done);
+                spawner.spawn(move || {
+                    done.fetch_add(1, Ordering::SeqCst);
+                });
+            });
+        }
+        pool.shutdown();
+        assert_eq!(done.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn test_unhappy_path_panicking_task_is_isolated() {
+        let pool = ThreadPool::new(1);
+        let survived = Arc::new(AtomicBool::new(false));
+        pool.spawn(|| panic!("task failure"));
+        let flag = Arc::clone(&survived);
+        pool.spawn(move || flag.store(true, Ordering::SeqCst));
+        let stats = pool.shutdown();
+        assert!(
+            survived.load(Ordering::SeqCst),
+            "The single worker outlived the panic"
+        );
+        assert_eq!(stats.panicked, 1);
+        assert_eq!(stats.completed, 1);
+    }
+
+    #[test]
+    fn test_idle_workers_steal_nested_tasks() {
+        let pool = ThreadPool::new(4);
+        let spawner = pool.spawner();
+        let workers_seen = Arc::new(Mutex::new(std::collections::HashSet::new()));
+        let seen = Arc::clone(&workers_seen);
+        // One task fans out onto its own deque; the others can only get work by stealing
+        pool.spawn(move || {
+            for _ in 0..64 {
+                let seen = Arc::clone(&seen);
+                spawner.spawn(move || {
+                    thread::sleep(Duration::from_millis(1));
+                    seen.lock().unwrap().insert(thread::current().id());
+                });
+            }
+        });
+        let stats = pool.shutdown();
+        assert_eq!(stats.completed, 65);
+        assert!(stats.stolen > 0, "Idle workers stole from the busy one");
+        assert!(workers_seen.lock().unwrap().len() > 1);
+    }
+
+    #[test]
+    fn test_load_recursive_fan_out() {
+        fn fan_out(spawner: Spawner, depth: u32, leaves: Arc<AtomicUsize>) {
+            if depth == 0 {
+                leaves.fetch_add(1, Ordering::Relaxed);
+                return;
+            }
+            for _ in 0..4 {
+                let (next, leaves) = (spawner.clone(), Arc::clone(&leaves));
+                spawner.spawn(move || fan_out(next, depth - 1, leaves));
+            }
+        }
+
+        let pool = ThreadPool::new(8);
+        let leaves = Arc::new(AtomicUsize::new(0));
+        let (spawner, counter) = (pool.spawner(), Arc::clone(&leaves));
+        pool.spawn(move || fan_out(spawner, 7, counter));
+        for _ in 0..10_000 {
+            let counter = Arc::clone(&leaves);
+            pool.spawn(move || {
+                counter.fetch_add(1, Ordering::Relaxed);
+            });
+        }
+        let stats = pool.shutdown();
+        let tree_tasks: usize = (0..=7).map(|d| 4usize.pow(d)).sum();
+        assert_eq!(leaves.load(Ordering::Relaxed), 4usize.pow(7) + 10_000);
+        assert_eq!(stats.completed, tree_tasks + 10_000);
+    }
+
+    #[test]
+    fn test_edge_case_drop_is_a_graceful_shutdown() {
+        let counter = Arc::new(AtomicUsize::new(0));
+        {
+            let pool = ThreadPool::new(3);
+            assert_eq!(pool.threads(), 3);
+            for _ in 0..50 {
+                let counter = Arc::clone(&counter);
+                pool.spawn(move || {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                });
+            }
+        }
+        assert_eq!(counter.load(Ordering::SeqCst), 50);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one thread")]
+    fn test_edge_case_zero_threads() {
+        ThreadPool::new(0);
+    }
+}
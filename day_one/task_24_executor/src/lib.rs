@@ -0,0 +1,227 @@
+//! A minimal futures runtime built from the repo's own structures
+//!
+//! `block_on` drives one future on the current thread. `Executor` runs many
+//! spawned tasks on one thread; with the `multi-thread` feature,
+//! `ThreadPoolExecutor` runs them on the work-stealing `ThreadPool`.
+
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::{pin, Pin};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+
+pub mod local;
+#[cfg(feature = "multi-thread")]
+pub mod multi_thread;
+
+pub use local::Executor;
+#[cfg(feature = "multi-thread")]
+pub use multi_thread::ThreadPoolExecutor;
+
+/// Wakes a thread parked in `block_on`
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Runs `future` to completion on the current thread, parking while it is pending
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        // A wake that arrived during the poll left an unpark token, so this returns at once
+        thread::park();
+    }
+}
+
+/// Returns `Pending` once, letting other tasks on the executor run
+pub fn yield_now() -> YieldNow {
+    YieldNow { yielded: false }
+}
+
+/// Future returned by `yield_now`
+pub struct YieldNow {
+    yielded: bool,
+}
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.yielded {
+            return Poll::Ready(());
+        }
+        self.yielded = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+struct JoinState<T> {
+    result: Option<thread::Result<T>>,
+    waker: Option<Waker>,
+}
+
+/// Resolves to the output of a spawned task
+///
+/// If the task panicked, awaiting the handle panics as well. Dropping the
+/// handle detaches the task; it keeps running.
+pub struct JoinHandle<T> {
+    state: Arc<Mutex<JoinState<T>>>,
+}
+
+impl<T> JoinHandle<T> {
+    /// Checks if the task has finished, either by completing or by panicking
+    pub fn is_finished(&self) -> bool {
+        self.state.lock().unwrap().result.is_some()
+    }
+}
+
+impl<T> Future for JoinHandle<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut state = self.state.lock().unwrap();
+        match state.result.take() {
+            Some(Ok(output)) => Poll::Ready(output),
+            Some(Err(_)) => panic!("spawned task panicked"),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Wraps `future` so its output (or panic) is delivered to the returned handle
+pub(crate) fn join_pair<F: Future + 'static>(
+    future: F,
+) -> (impl Future<Output = ()>, JoinHandle<F::Output>) {
+    let state = Arc::new(Mutex::new(JoinState {
+        result: None,
+        waker: None,
+    }));
+    let handle = JoinHandle {
+        state: Arc::clone(&state),
+    };
+    let task = async move {
+        let result = CatchUnwind(Box::pin(future)).await;
+        let waker = {
+            let mut state = state.lock().unwrap();
+            state.result = Some(result);
+            state.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    };
+    (task, handle)
+}
+
+/// Turns a panic during `poll` into an `Err` output
+struct CatchUnwind<F>(Pin<Box<F>>);
+
+impl<F: Future> Future for CatchUnwind<F> {
+    type Output = thread::Result<F::Output>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match panic::catch_unwind(AssertUnwindSafe(|| self.0.as_mut().poll(cx))) {
+            Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+            Ok(Poll::Pending) => Poll::Pending,
+            Err(payload) => Poll::Ready(Err(payload)),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_util {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::{Arc, Mutex};
+    use std::task::{Context, Poll, Waker};
+    use std::thread;
+    use std::time::Duration;
+
+    /// Completes after `millis`, woken by a helper thread
+    pub(crate) fn sleep(millis: u64) -> Sleep {
+        Sleep {
+            state: Arc::new(Mutex::new((false, None))),
+            started: false,
+            millis,
+        }
+    }
+
+    pub(crate) struct Sleep {
+        state: Arc<Mutex<(bool, Option<Waker>)>>,
+        started: bool,
+        millis: u64,
+    }
+
+    impl Future for Sleep {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            let mut state = self.state.lock().unwrap();
+            if state.0 {
+                return Poll::Ready(());
+            }
+            state.1 = Some(cx.waker().clone());
+            drop(state);
+            if !self.started {
+                self.started = true;
+                let (state, millis) = (Arc::clone(&self.state), self.millis);
+                thread::spawn(move || {
+                    thread::sleep(Duration::from_millis(millis));
+                    let mut state = state.lock().unwrap();
+                    state.0 = true;
+                    if let Some(waker) = state.1.take() {
+                        waker.wake();
+                    }
+                });
+            }
+            Poll::Pending
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_util::sleep;
+    use super::*;
+
+    #[test]
+    fn test_happy_path_block_on_ready_future() {
+        assert_eq!(block_on(async { 6 * 7 }), 42);
+    }
+
+    #[test]
+    fn test_block_on_parks_until_woken_from_another_thread() {
+        let value = block_on(async {
+            sleep(20).await;
+            yield_now().await;
+            "woken"
+        });
+        assert_eq!(value, "woken");
+    }
+
+    #[test]
+    fn test_edge_case_join_handle_reports_panic() {
+        let (task, handle) = join_pair(async { panic!("boom") });
+        block_on(task);
+        assert!(handle.is_finished());
+        let awaited = panic::catch_unwind(AssertUnwindSafe(|| block_on(handle)));
+        assert!(awaited.is_err(), "Awaiting a panicked task panics");
+    }
+}
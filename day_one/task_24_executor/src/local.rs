@@ -0,0 +1,257 @@
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+
+use task_02_queue::Queue;
+use task_09_lock_free::LockFreeStack;
+
+use crate::{join_pair, JoinHandle};
+
+type LocalTask = Pin<Box<dyn Future<Output = ()>>>;
+
+/// Where wakers, possibly on other threads, report tasks that are ready again
+///
+/// Only the executor thread ever pops, so the Treiber stack's single-consumer
+/// use avoids the ABA problem a concurrent `pop` would face.
+struct Inbox {
+    woken: LockFreeStack<usize>,
+    executor: Thread,
+}
+
+impl Drop for Inbox {
+    fn drop(&mut self) {
+        // LockFreeStack does not free its nodes on drop
+        while self.woken.pop().is_some() {}
+    }
+}
+
+struct TaskWaker {
+    inbox: Arc<Inbox>,
+    id: usize,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.inbox.woken.push(self.id);
+        self.inbox.executor.unpark();
+    }
+}
+
+/// Single-threaded executor for spawned tasks
+///
+/// Tasks never leave the thread that created the executor, so they don't need
+/// to be `Send`. Woken task ids arrive on a `LockFreeStack`, which any thread
+/// can push to; each round the executor moves them into a `Queue` in wake
+/// order and polls them first come, first served.
+pub struct Executor {
+    inbox: Arc<Inbox>,
+    /// Spawned tasks by id; a slot is `None` while its task is being polled or after it finished
+    tasks: RefCell<Vec<Option<LocalTask>>>,
+    free_ids: RefCell<Vec<usize>>,
+    live: RefCell<usize>,
+}
+
+impl Executor {
+    /// Creates an executor bound to the current thread
+    pub fn new() -> Self {
+        Executor {
+            inbox: Arc::new(Inbox {
+                woken: LockFreeStack::new(),
+                executor: thread::current(),
+            }),
+            tasks: RefCell::new(Vec::new()),
+            free_ids: RefCell::new(Vec::new()),
+            live: RefCell::new(0),
+        }
+    }
+
+    /// Returns the number of spawned tasks that have not finished
+    pub fn len(&self) -> usize {
+        *self.live.borrow()
+    }
+
+    /// Checks if every spawned task has finished
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Queues `future` as a new task; it starts running on the next `run` or `block_on`
+    pub fn spawn<F>(&self, future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + 'static,
+    {
+        let (task, handle) = join_pair(future);
+        let id = match self.free_ids.borrow_mut().pop() {
+            Some(id) => {
+                self.tasks.borrow_mut()[id] = Some(Box::pin(task));
+                id
+            }
+            None => {
+                let mut tasks = self.tasks.borrow_mut();
+                tasks.push(Some(Box::pin(task)));
+                tasks.len() - 1
+            }
+        };
+        *self.live.borrow_mut() += 1;
+        self.inbox.woken.push(id);
+        handle
+    }
+
+    /// Runs spawned tasks until all of them have finished
+    pub fn run(&self) {
+        while !self.is_empty() {
+            if !self.run_ready() {
+                thread::park();
+            }
+        }
+    }
+
+    /// Runs `future` to completion, running spawned tasks alongside it
+    pub fn block_on<F>(&self, future: F) -> F::Output
+    where
+        F: Future + 'static,
+    {
+        let mut handle = self.spawn(future);
+        loop {
+            if handle.is_finished() {
+                let waker = Waker::noop();
+                match Pin::new(&mut handle).poll(&mut Context::from_waker(waker)) {
+                    Poll::Ready(output) => return output,
+                    Poll::Pending => unreachable!("a finished task is ready"),
+                }
+            }
+            if !self.run_ready() {
+                thread::park();
+            }
+        }
+    }
+
+    /// Polls every task woken so far; returns whether there were any
+    fn run_ready(&self) -> bool {
+        // The stack hands ids back newest first; reverse them into wake order
+        let mut woken = Vec::new();
+        while let Some(id) = self.inbox.woken.pop() {
+            woken.push(id);
+        }
+        let mut ready = Queue::new();
+        for id in woken.into_iter().rev() {
+            ready.enqueue(id);
+        }
+        let any = !ready.is_empty();
+
+        while let Some(id) = ready.dequeue() {
+            // Take the task out so it can spawn or wake others while it is polled
+            let Some(mut task) = self.tasks.borrow_mut().get_mut(id).and_then(Option::take) else {
+                // Woken twice, or already finished
+                continue;
+            };
+            let waker = Waker::from(Arc::new(TaskWaker {
+                inbox: Arc::clone(&self.inbox),
+                id,
+            }));
+            match task.as_mut().poll(&mut Context::from_waker(&waker)) {
+                Poll::Pending => self.tasks.borrow_mut()[id] = Some(task),
+                Poll::Ready(()) => {
+                    self.free_ids.borrow_mut().push(id);
+                    *self.live.borrow_mut() -= 1;
+                }
+            }
+        }
+        any
+    }
+}
+
+impl Default for Executor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::sleep;
+    use crate::yield_now;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_happy_path_tasks_interleave_in_wake_order() {
+        let executor = Executor::new();
+        let log = Rc::new(RefCell::new(Vec::new()));
+        for name in ["a", "b", "c"] {
+            let log = Rc::clone(&log);
+            executor.spawn(async move {
+                for step in 0..2 {
+                    log.borrow_mut().push(format!("{}{}", name, step));
+                    yield_now().await;
+                }
+            });
+        }
+        assert_eq!(executor.len(), 3);
+        executor.run();
+        assert!(executor.is_empty());
+        assert_eq!(*log.borrow(), ["a0", "b0", "c0", "a1", "b1", "c1"]);
+    }
+
+    #[test]
+    fn test_block_on_awaits_spawned_tasks() {
+        let executor = Executor::new();
+        let first = executor.spawn(async {
+            sleep(10).await;
+            20
+        });
+        let second = executor.spawn(async { 22 });
+        let total = executor.block_on(async move { first.await + second.await });
+        assert_eq!(total, 42);
+    }
+
+    #[test]
+    fn test_wakes_from_other_threads() {
+        let executor = Executor::new();
+        let handles: Vec<_> = (0..5)
+            .map(|i| {
+                executor.spawn(async move {
+                    sleep(5 * i).await;
+                    i
+                })
+            })
+            .collect();
+        let sum = executor.block_on(async move {
+            let mut sum = 0;
+            for handle in handles {
+                sum += handle.await;
+            }
+            sum
+        });
+        assert_eq!(sum, 10);
+    }
+
+    #[test]
+    fn test_tasks_can_spawn_tasks() {
+        let executor = Rc::new(Executor::new());
+        let inner = Rc::clone(&executor);
+        let value = executor.block_on(async move {
+            let child = inner.spawn(async { "from child" });
+            child.await
+        });
+        assert_eq!(value, "from child");
+        assert!(executor.is_empty());
+    }
+
+    #[test]
+    fn test_edge_case_panicking_task_does_not_stop_others() {
+        let executor = Executor::new();
+        let failed = executor.spawn(async { panic!("task failure") });
+        let fine = executor.spawn(async { 1 });
+        executor.run();
+        assert!(failed.is_finished());
+        assert_eq!(executor.block_on(fine), 1);
+    }
+}
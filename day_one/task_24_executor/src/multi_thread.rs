@@ -0,0 +1,167 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Wake, Waker};
+
+use task_23_thread_pool::{PoolStats, Spawner, ThreadPool};
+
+use crate::{join_pair, JoinHandle};
+
+type SendTask = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// A spawned future plus what it needs to reschedule itself
+struct Task {
+    /// `None` once the future has finished
+    future: Mutex<Option<SendTask>>,
+    spawner: Spawner,
+    /// Set while a poll of this task is queued on the pool, so repeated wakes queue it once
+    scheduled: AtomicBool,
+}
+
+impl Task {
+    fn schedule(self: &Arc<Self>) {
+        if !self.scheduled.swap(true, Ordering::AcqRel) {
+            let task = Arc::clone(self);
+            self.spawner.spawn(move || task.poll());
+        }
+    }
+
+    fn poll(self: Arc<Self>) {
+        // Cleared before polling: a wake during the poll queues another one,
+        // which waits on the mutex until this poll is done
+        self.scheduled.store(false, Ordering::Release);
+        let mut future = self.future.lock().unwrap();
+        if let Some(task) = future.as_mut() {
+            let waker = Waker::from(Arc::clone(&self));
+            if task
+                .as_mut()
+                .poll(&mut Context::from_waker(&waker))
+                .is_ready()
+            {
+                *future = None;
+            }
+        }
+    }
+}
+
+impl Wake for Task {
+    fn wake(self: Arc<Self>) {
+        self.schedule();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.schedule();
+    }
+}
+
+/// Executor that polls tasks on a work-stealing `ThreadPool`
+///
+/// Every wake queues one poll of the task as a pool job, so a task can move
+/// between workers from one poll to the next and must be `Send`. Tasks woken
+/// from inside the pool land on the waking worker's own deque.
+pub struct ThreadPoolExecutor {
+    pool: ThreadPool,
+}
+
+impl ThreadPoolExecutor {
+    /// Starts an executor with `threads` worker threads
+    pub fn new(threads: usize) -> Self {
+        ThreadPoolExecutor {
+            pool: ThreadPool::new(threads),
+        }
+    }
+
+    /// Queues `future` as a new task
+    pub fn spawn<F>(&self, future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send,
+    {
+        let (task, handle) = join_pair(future);
+        let task = Arc::new(Task {
+            future: Mutex::new(Some(Box::pin(task))),
+            spawner: self.pool.spawner(),
+            scheduled: AtomicBool::new(false),
+        });
+        task.schedule();
+        handle
+    }
+
+    /// Spawns `future` and blocks the calling thread until it finishes
+    pub fn block_on<F>(&self, future: F) -> F::Output
+    where
+        F: Future + Send + 'static,
+        F::Output: Send,
+    {
+        crate::block_on(self.spawn(future))
+    }
+
+    /// Stops the pool once every queued poll has run; tasks still pending are dropped
+    pub fn shutdown(self) -> PoolStats {
+        self.pool.shutdown()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::sleep;
+    use crate::yield_now;
+    use std::sync::atomic::AtomicUsize;
+
+    #[test]
+    fn test_happy_path_runs_tasks_on_the_pool() {
+        let executor = ThreadPoolExecutor::new(4);
+        let handles: Vec<_> = (0..100u64)
+            .map(|i| {
+                executor.spawn(async move {
+                    yield_now().await;
+                    i * i
+                })
+            })
+            .collect();
+        let total = executor.block_on(async move {
+            let mut total = 0;
+            for handle in handles {
+                total += handle.await;
+            }
+            total
+        });
+        assert_eq!(total, (0..100u64).map(|i| i * i).sum());
+    }
+
+    #[test]
+    fn test_tasks_woken_from_outside_the_pool() {
+        let executor = ThreadPoolExecutor::new(2);
+        let finished = Arc::new(AtomicUsize::new(0));
+        let handles: Vec<_> = (0..10)
+            .map(|i| {
+                let finished = Arc::clone(&finished);
+                executor.spawn(async move {
+                    sleep(i).await;
+                    finished.fetch_add(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+        for handle in handles {
+            crate::block_on(handle);
+        }
+        assert_eq!(finished.load(Ordering::SeqCst), 10);
+        let stats = executor.shutdown();
+        assert_eq!(stats.panicked, 0);
+    }
+
+    #[test]
+    fn test_edge_case_panicking_task_reaches_its_handle() {
+        let executor = ThreadPoolExecutor::new(1);
+        let failed = executor.spawn(async { panic!("task failure") });
+        let fine = executor.spawn(async { "still running" });
+        assert_eq!(crate::block_on(fine), "still running");
+        while !failed.is_finished() {
+            std::thread::yield_now();
+        }
+        let stats = executor.shutdown();
+        assert_eq!(stats.panicked, 0, "The panic is caught inside the task");
+    }
+}
@@ -0,0 +1,405 @@
+#![cfg_attr(not(test), no_std)]
+
+use core::alloc::{GlobalAlloc, Layout};
+use core::cell::UnsafeCell;
+use core::hint;
+use core::ptr;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// Block starts and sizes are multiples of this
+const GRANULE: usize = 16;
+/// Written just before every pointer handed out
+const HEADER: usize = core::mem::size_of::<AllocHeader>();
+/// Smallest block worth splitting off: room for a free header and a little data
+const MIN_BLOCK: usize = 2 * GRANULE;
+/// Marks the end of the free list
+const NONE: usize = usize::MAX;
+
+/// Stored at the start of every free block
+#[repr(C)]
+struct FreeHeader {
+    size: usize,
+    next: usize,
+}
+
+/// Stored just before an allocation, so `dealloc` can find the whole block
+#[repr(C)]
+struct AllocHeader {
+    start: usize,
+    size: usize,
+}
+
+#[repr(C, align(16))]
+struct Region<const N: usize>([u8; N]);
+
+struct State {
+    /// Offset of the first free block; the list is sorted by address
+    head: usize,
+    initialized: bool,
+    allocated: usize,
+}
+
+/// First-fit allocator over a fixed byte region, usable as the `#[global_allocator]`
+///
+/// Free blocks form a singly linked list threaded through the region itself
+/// and kept sorted by address, so a freed block is merged with free neighbours
+/// on both sides and the region does not fragment into ever smaller pieces.
+/// Every allocation carries a 16-byte header recording its block. A spin lock
+/// guards the list, since a global allocator cannot rely on anything that
+/// might allocate. The crate is `no_std`.
+pub struct FreeListAllocator<const N: usize> {
+    region: UnsafeCell<Region<N>>,
+    state: UnsafeCell<State>,
+    locked: AtomicBool,
+}
+
+// SAFETY: every access to `region` and `state` happens with `locked` held
+unsafe impl<const N: usize> Sync for FreeListAllocator<N> {}
+
+impl<const N: usize> FreeListAllocator<N> {
+    /// Creates an allocator over an `N`-byte region; usable in a `static`
+    pub const fn new() -> Self {
+        const {
+            assert!(
+                N >= MIN_BLOCK && N.is_multiple_of(GRANULE),
+                "the region must be a non-zero multiple of 16 bytes"
+            )
+        };
+        FreeListAllocator {
+            region: UnsafeCell::new(Region([0; N])),
+            state: UnsafeCell::new(State {
+                head: 0,
+                initialized: false,
+                allocated: 0,
+            }),
+            locked: AtomicBool::new(false),
+        }
+    }
+
+    /// Returns the size of the managed region in bytes
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Checks if `ptr` points into the managed region
+    pub fn contains(&self, ptr: *const u8) -> bool {
+        let start = self.region.get() as *const u8 as usize;
+        (start..start + N).contains(&(ptr as usize))
+    }
+
+    /// Returns the bytes held by live allocations, including headers and padding
+    pub fn allocated_bytes(&self) -> usize {
+        self.with_heap(|heap| heap.state.allocated)
+    }
+
+    /// Returns the number of blocks on the free list
+    pub fn free_blocks(&self) -> usize {
+        self.with_heap(|heap| heap.free_list().count())
+    }
+
+    /// Returns the size of the largest free block, an upper bound on the next allocation
+    pub fn largest_free_block(&self) -> usize {
+        self.with_heap(|heap| heap.free_list().map(|(_, size)| size).max().unwrap_or(0))
+    }
+
+    fn with_heap<R>(&self, f: impl FnOnce(&mut Heap<'_>) -> R) -> R {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            hint::spin_loop();
+        }
+        // SAFETY: the lock gives this thread exclusive access until it is released
+        let state = unsafe { &mut *self.state.get() };
+        let base = self.region.get().cast::<u8>();
+        if !state.initialized {
+            state.initialized = true;
+            // SAFETY: offset 0 is in bounds and aligned, and N >= MIN_BLOCK
+            unsafe { write_free(base, 0, N, NONE) };
+        }
+        let result = f(&mut Heap {
+            base,
+            capacity: N,
+            state,
+        });
+        self.locked.store(false, Ordering::Release);
+        result
+    }
+}
+
+impl<const N: usize> Default for FreeListAllocator<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+unsafe impl<const N: usize> GlobalAlloc for FreeListAllocator<N> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.with_heap(|heap| heap.alloc(layout))
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, _layout: Layout) {
+        self.with_heap(|heap| heap.dealloc(ptr))
+    }
+}
+
+/// The region and its bookkeeping while the lock is held
+struct Heap<'a> {
+    base: *mut u8,
+    capacity: usize,
+    state: &'a mut State,
+}
+
+/// Writes a free block header at `offset`
+///
+/// SAFETY: `offset` must be GRANULE-aligned with room for a header inside the region
+unsafe fn write_free(base: *mut u8, offset: usize, size: usize, next: usize) {
+    base.add(offset)
+        .cast::<FreeHeader>()
+        .write(FreeHeader { size, next });
+}
+
+fn align_up(value: usize, align: usize) -> usize {
+    (value + align - 1) & !(align - 1)
+}
+
+impl Heap<'_> {
+    fn read_free(&self, offset: usize) -> FreeHeader {
+        // SAFETY: only offsets of free blocks, which hold a header, reach here
+        unsafe { self.base.add(offset).cast::<FreeHeader>().read() }
+    }
+
+    fn set_next(&mut self, prev: Option<usize>, next: usize) {
+        match prev {
+            // SAFETY: `prev` is a free block, so its header is valid
+            Some(prev) => unsafe { (*self.base.add(prev).cast::<FreeHeader>()).next = next },
+            None => self.state.head = next,
+        }
+    }
+
+    /// Yields `(offset, size)` for every free block in address order
+    fn free_list(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let mut current = self.state.head;
+        core::iter::from_fn(move || {
+            if current == NONE {
+                return None;
+            }
+            let block = self.read_free(current);
+            let item = (current, block.size);
+            current = block.next;
+            Some(item)
+        })
+    }
+
+    fn alloc(&mut self, layout: Layout) -> *mut u8 {
+        if layout.size() > self.capacity || layout.align() > self.capacity {
+            // Could never fit, and would overflow the offset arithmetic below
+            return ptr::null_mut();
+        }
+        let base_addr = self.base as usize;
+        let mut prev = None;
+        let mut current = self.state.head;
+        while current != NONE {
+            let block = self.read_free(current);
+            let block_end = current + block.size;
+            // Align the address the caller sees, not just the offset
+            let user =
+                align_up(base_addr + current + HEADER, layout.align().max(GRANULE)) - base_addr;
+            let end = align_up(user + layout.size(), GRANULE);
+            if end > block_end {
+                prev = Some(current);
+                current = block.next;
+                continue;
+            }
+
+            // Give back what is left after the allocation
+            let tail = block_end - end;
+            let (alloc_end, next_free) = if tail >= MIN_BLOCK {
+                // SAFETY: `end` is GRANULE-aligned and `tail` bytes fit before `block_end`
+                unsafe { write_free(self.base, end, tail, block.next) };
+                (end, end)
+            } else {
+                (block_end, block.next)
+            };
+            // Keep the padding before a strongly aligned allocation if it is big enough
+            let lead = user - HEADER - current;
+            let alloc_start = if lead >= MIN_BLOCK {
+                // SAFETY: `current` is this block's own header
+                unsafe { write_free(self.base, current, lead, next_free) };
+                user - HEADER
+            } else {
+                self.set_next(prev, next_free);
+                current
+            };
+
+            let size = alloc_end - alloc_start;
+            self.state.allocated += size;
+            // SAFETY: `user - HEADER` is GRANULE-aligned and inside the taken block
+            unsafe {
+                self.base
+                    .add(user - HEADER)
+                    .cast::<AllocHeader>()
+                    .write(AllocHeader {
+                        start: alloc_start,
+                        size,
+                    });
+                return self.base.add(user);
+            }
+        }
+        ptr::null_mut()
+    }
+
+    fn dealloc(&mut self, ptr: *mut u8) {
+        let user = ptr as usize - self.base as usize;
+        // SAFETY: `ptr` came from `alloc`, which wrote this header
+        let AllocHeader {
+            mut start,
+            mut size,
+        } = unsafe { self.base.add(user - HEADER).cast::<AllocHeader>().read() };
+        self.state.allocated -= size;
+
+        let mut prev = None;
+        let mut next = self.state.head;
+        while next != NONE && next < start {
+            prev = Some(next);
+            next = self.read_free(next).next;
+        }
+
+        if next != NONE && start + size == next {
+            let following = self.read_free(next);
+            size += following.size;
+            next = following.next;
+        }
+        if let Some(p) = prev {
+            let preceding = self.read_free(p);
+            if p + preceding.size == start {
+                start = p;
+                size += preceding.size;
+                // SAFETY: `p` is a free block being extended in place
+                unsafe { write_free(self.base, start, size, next) };
+                return;
+            }
+        }
+        // SAFETY: `start` is the freed block's GRANULE-aligned start
+        unsafe { write_free(self.base, start, size, next) };
+        self.set_next(prev, start);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout(size: usize, align: usize) -> Layout {
+        Layout::from_size_align(size, align).unwrap()
+    }
+
+    #[test]
+    fn test_happy_path_alloc_write_and_free() {
+        let heap = FreeListAllocator::<4096>::new();
+        let a = unsafe { heap.alloc(layout(100, 8)) };
+        let b = unsafe { heap.alloc(layout(200, 8)) };
+        assert!(!a.is_null() && !b.is_null());
+        unsafe {
+            a.write_bytes(0xAA, 100);
+            b.write_bytes(0xBB, 200);
+            assert_eq!(*a.add(99), 0xAA, "Allocations do not overlap");
+        }
+        assert!(heap.allocated_bytes() >= 300);
+        assert!(heap.contains(a) && heap.contains(b));
+        assert!(!heap.contains([0u8; 1].as_ptr()));
+
+        unsafe {
+            heap.dealloc(a, layout(100, 8));
+            heap.dealloc(b, layout(200, 8));
+        }
+        assert_eq!(heap.allocated_bytes(), 0);
+        assert_eq!(heap.largest_free_block(), 4096);
+    }
+
+    #[test]
+    fn test_alignment_is_honoured() {
+        let heap = FreeListAllocator::<8192>::new();
+        for align in [1, 2, 8, 16, 64, 256, 1024] {
+            let ptr = unsafe { heap.alloc(layout(24, align)) };
+            assert!(!ptr.is_null());
+            assert_eq!(ptr as usize % align, 0, "Aligned to {}", align);
+        }
+    }
+
+    #[test]
+    fn test_first_fit_reuses_freed_block() {
+        let heap = FreeListAllocator::<4096>::new();
+        let blocks: Vec<_> = (0..4)
+            .map(|_| unsafe { heap.alloc(layout(64, 8)) })
+            .collect();
+        unsafe { heap.dealloc(blocks[1], layout(64, 8)) };
+        let reused = unsafe { heap.alloc(layout(48, 8)) };
+        assert_eq!(reused, blocks[1], "The first hole that fits is used");
+    }
+
+    #[test]
+    fn test_adjacent_free_blocks_coalesce() {
+        let heap = FreeListAllocator::<4096>::new();
+        let l = layout(100, 8);
+        let [a, b, c, d] = [(); 4].map(|_| unsafe { heap.alloc(l) });
+        unsafe {
+            heap.dealloc(a, l);
+            heap.dealloc(c, l);
+        }
+        assert_eq!(heap.free_blocks(), 3, "a, c and the tail are separate");
+        unsafe { heap.dealloc(b, l) };
+        assert_eq!(heap.free_blocks(), 2, "a, b and c merged");
+        unsafe { heap.dealloc(d, l) };
+        assert_eq!(heap.free_blocks(), 1);
+        assert_eq!(heap.largest_free_block(), 4096);
+    }
+
+    #[test]
+    fn test_unhappy_path_out_of_memory_returns_null() {
+        let heap = FreeListAllocator::<1024>::new();
+        assert!(unsafe { heap.alloc(layout(2048, 8)) }.is_null());
+        assert!(unsafe { heap.alloc(layout(isize::MAX as usize - 64, 8)) }.is_null());
+        let big = unsafe { heap.alloc(layout(900, 8)) };
+        assert!(!big.is_null());
+        assert!(unsafe { heap.alloc(layout(200, 8)) }.is_null());
+        assert_eq!(heap.capacity(), 1024);
+    }
+
+    #[test]
+    fn test_edge_case_churn_leaves_no_fragments() {
+        let heap = FreeListAllocator::<65536>::new();
+        let mut live: Vec<(*mut u8, Layout, u8)> = Vec::new();
+        for step in 0..2_000usize {
+            if step % 3 == 2 && !live.is_empty() {
+                let (ptr, l, tag) = live.swap_remove(step * 7 % live.len());
+                unsafe {
+                    assert!(
+                        (0..l.size()).all(|i| *ptr.add(i) == tag),
+                        "Memory was not clobbered"
+                    );
+                    heap.dealloc(ptr, l);
+                }
+            } else {
+                let l = layout(1 + step * 37 % 300, 1 << (step % 7));
+                let ptr = unsafe { heap.alloc(l) };
+                if ptr.is_null() {
+                    continue;
+                }
+                let tag = step as u8;
+                unsafe { ptr.write_bytes(tag, l.size()) };
+                live.push((ptr, l, tag));
+            }
+        }
+        for (ptr, l, _) in live {
+            unsafe { heap.dealloc(ptr, l) };
+        }
+        assert_eq!(heap.allocated_bytes(), 0);
+        assert_eq!(
+            heap.free_blocks(),
+            1,
+            "Everything merged back into one block"
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+
+use task_25_free_list_alloc::FreeListAllocator;
+
+// Every allocation in this test binary, including the test harness's own, comes from here
+#[global_allocator]
+static HEAP: FreeListAllocator<{ 4 * 1024 * 1024 }> = FreeListAllocator::new();
+
+#[test]
+fn test_std_collections_run_on_the_static_region() {
+    let before = HEAP.allocated_bytes();
+    {
+        let mut words: HashMap<String, usize> = HashMap::new();
+        for i in 0..2_000 {
+            *words.entry(format!("word{}", i % 300)).or_default() += i;
+        }
+        let mut values: Vec<usize> = words.into_values().collect();
+        values.sort();
+        assert_eq!(values.len(), 300);
+        assert!(HEAP.allocated_bytes() > before);
+    }
+
+    let boxed: Vec<Box<[u8]>> = (1..50)
+        .map(|n| vec![n as u8; n * 10].into_boxed_slice())
+        .collect();
+    assert!(boxed
+        .iter()
+        .enumerate()
+        .all(|(i, b)| b.iter().all(|&x| x as usize == i + 1)));
+}
+
+#[test]
+fn test_threads_share_the_allocator() {
+    let handles: Vec<_> = (0..4)
+        .map(|t| {
+            std::thread::spawn(move || {
+                let mut total = 0;
+                for i in 0..500 {
+                    let v: Vec<u64> = (0..(i % 64) as u64).map(|x| x + t).collect();
+                    total += v.len();
+                }
+                total
+            })
+        })
+        .collect();
+    for handle in handles {
+        assert!(handle.join().unwrap() > 0);
+    }
+}
@@ -0,0 +1,79 @@
+use std::alloc::{GlobalAlloc, Layout};
+use std::ptr;
+use std::sync::Mutex;
+
+use task_10_arena_alloc::MemoryArena;
+
+use crate::Owns;
+
+/// `MemoryArena` behind the `GlobalAlloc` interface
+///
+/// The arena hands out unaligned byte ranges and only frees everything at
+/// once, so this adapter over-allocates to honour the alignment and treats
+/// `dealloc` as a no-op until `reset`. That makes it a good primary for
+/// short-lived bursts of allocations in front of a general-purpose secondary.
+pub struct ArenaAllocator {
+    arena: Mutex<MemoryArena>,
+}
+
+impl ArenaAllocator {
+    /// Creates an adapter over a new arena of `size` bytes
+    pub fn new(size: usize) -> Self {
+        ArenaAllocator {
+            arena: Mutex::new(MemoryArena::new(size)),
+        }
+    }
+
+    /// Returns the bytes left in the arena
+    pub fn remaining(&self) -> usize {
+        self.arena.lock().unwrap().remaining()
+    }
+
+    /// Frees everything at once; every pointer handed out so far becomes dangling
+    ///
+    /// # Safety
+    ///
+    /// No allocation from this arena may be used after the reset.
+    pub unsafe fn reset(&self) {
+        self.arena.lock().unwrap().reset();
+    }
+}
+
+unsafe impl GlobalAlloc for ArenaAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let padded = layout.size() + layout.align() - 1;
+        match self.arena.lock().unwrap().allocate(padded) {
+            Some(ptr) => ptr.add(ptr.align_offset(layout.align())),
+            None => ptr::null_mut(),
+        }
+    }
+
+    unsafe fn dealloc(&self, _ptr: *mut u8, _layout: Layout) {
+        // Arena memory is only reclaimed by `reset`
+    }
+}
+
+impl Owns for ArenaAllocator {
+    fn owns(&self, ptr: *const u8) -> bool {
+        self.arena.lock().unwrap().contains(ptr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aligned_allocations_inside_the_arena() {
+        let arena = ArenaAllocator::new(1024);
+        for align in [1, 8, 64] {
+            let layout = Layout::from_size_align(10, align).unwrap();
+            let ptr = unsafe { arena.alloc(layout) };
+            assert_eq!(ptr as usize % align, 0);
+            assert!(arena.owns(ptr));
+        }
+        assert!(arena.remaining() < 1024);
+        unsafe { arena.reset() };
+        assert_eq!(arena.remaining(), 1024);
+    }
+}
@@ -0,0 +1,189 @@
+use std::alloc::{GlobalAlloc, Layout};
+use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use task_25_free_list_alloc::FreeListAllocator;
+
+pub mod arena;
+
+pub use arena::ArenaAllocator;
+
+/// Allocators that can tell whether a pointer came from them
+///
+/// `FallbackAllocator` asks its primary, so it can send every `dealloc` and
+/// `realloc` back to the allocator that produced the pointer.
+pub trait Owns {
+    fn owns(&self, ptr: *const u8) -> bool;
+}
+
+impl<const N: usize> Owns for FreeListAllocator<N> {
+    fn owns(&self, ptr: *const u8) -> bool {
+        self.contains(ptr)
+    }
+}
+
+/// Tries `primary` first and uses `secondary` when it runs out
+///
+/// Typical pairs are a fixed region or arena in front of the system allocator,
+/// so the common small allocations stay cheap and local while nothing fails
+/// once the region is full. The primary must implement `Owns`; anything it
+/// doesn't own is assumed to belong to the secondary. Chains of more than two
+/// are built by nesting, with a `FallbackAllocator` as the secondary.
+pub struct FallbackAllocator<P, S> {
+    primary: P,
+    secondary: S,
+    fallbacks: AtomicUsize,
+}
+
+impl<P, S> FallbackAllocator<P, S> {
+    /// Combines two allocators; usable in a `static`
+    pub const fn new(primary: P, secondary: S) -> Self {
+        FallbackAllocator {
+            primary,
+            secondary,
+            fallbacks: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the allocator tried first
+    pub fn primary(&self) -> &P {
+        &self.primary
+    }
+
+    /// Returns the allocator used when the primary fails
+    pub fn secondary(&self) -> &S {
+        &self.secondary
+    }
+
+    /// Returns how many allocations the secondary has served
+    pub fn fallback_count(&self) -> usize {
+        self.fallbacks.load(Ordering::Relaxed)
+    }
+}
+
+unsafe impl<P: GlobalAlloc + Owns, S: GlobalAlloc> GlobalAlloc for FallbackAllocator<P, S> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.primary.alloc(layout);
+        if !ptr.is_null() {
+            return ptr;
+        }
+        self.fallbacks.fetch_add(1, Ordering::Relaxed);
+        self.secondary.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if self.primary.owns(ptr) {
+            self.primary.dealloc(ptr, layout);
+        } else {
+            self.secondary.dealloc(ptr, layout);
+        }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        if !self.primary.owns(ptr) {
+            return self.secondary.realloc(ptr, layout, new_size);
+        }
+        let grown = self.primary.realloc(ptr, layout, new_size);
+        if !grown.is_null() {
+            return grown;
+        }
+        // The primary is full: move the block over to the secondary
+        let new_layout = Layout::from_size_align_unchecked(new_size, layout.align());
+        self.fallbacks.fetch_add(1, Ordering::Relaxed);
+        let moved = self.secondary.alloc(new_layout);
+        if !moved.is_null() {
+            ptr::copy_nonoverlapping(ptr, moved, layout.size().min(new_size));
+            self.primary.dealloc(ptr, layout);
+        }
+        moved
+    }
+}
+
+impl<P: Owns, S: Owns> Owns for FallbackAllocator<P, S> {
+    fn owns(&self, ptr: *const u8) -> bool {
+        self.primary.owns(ptr) || self.secondary.owns(ptr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::alloc::System;
+
+    fn layout(size: usize) -> Layout {
+        Layout::from_size_align(size, 8).unwrap()
+    }
+
+    #[test]
+    fn test_happy_path_primary_first_then_secondary() {
+        let alloc = FallbackAllocator::new(FreeListAllocator::<1024>::new(), System);
+        let small = unsafe { alloc.alloc(layout(256)) };
+        assert!(alloc.primary().contains(small), "Fits in the primary");
+        assert_eq!(alloc.fallback_count(), 0);
+
+        let large = unsafe { alloc.alloc(layout(4096)) };
+        assert!(!large.is_null());
+        assert!(
+            !alloc.primary().contains(large),
+            "Too big, served by the system"
+        );
+        assert_eq!(alloc.fallback_count(), 1);
+
+        unsafe {
+            alloc.dealloc(large, layout(4096));
+            alloc.dealloc(small, layout(256));
+        }
+        assert_eq!(alloc.primary().allocated_bytes(), 0, "Each block went home");
+    }
+
+    #[test]
+    fn test_realloc_moves_block_out_of_full_primary() {
+        let alloc = FallbackAllocator::new(FreeListAllocator::<512>::new(), System);
+        unsafe {
+            let ptr = alloc.alloc(layout(64));
+            for i in 0..64 {
+                *ptr.add(i) = i as u8;
+            }
+            let grown = alloc.realloc(ptr, layout(64), 2048);
+            assert!(!alloc.primary().contains(grown));
+            assert!(
+                (0..64).all(|i| *grown.add(i) == i as u8),
+                "Contents were copied"
+            );
+            assert_eq!(alloc.primary().allocated_bytes(), 0);
+
+            let shrunk = alloc.realloc(grown, layout(2048), 32);
+            assert!((0..32).all(|i| *shrunk.add(i) == i as u8));
+            alloc.dealloc(shrunk, layout(32));
+        }
+    }
+
+    #[test]
+    fn test_nested_chain_of_three() {
+        let alloc = FallbackAllocator::new(
+            FreeListAllocator::<256>::new(),
+            FallbackAllocator::new(ArenaAllocator::new(1024), System),
+        );
+        let sizes = [128, 512, 4096];
+        let ptrs: Vec<_> = sizes
+            .iter()
+            .map(|&size| unsafe { alloc.alloc(layout(size)) })
+            .collect();
+        assert!(alloc.primary().contains(ptrs[0]));
+        assert!(alloc.secondary().primary().owns(ptrs[1]));
+        assert!(!alloc.secondary().primary().owns(ptrs[2]));
+        assert_eq!(alloc.fallback_count(), 2);
+        assert_eq!(alloc.secondary().fallback_count(), 1);
+        for (ptr, size) in ptrs.into_iter().zip(sizes) {
+            unsafe { alloc.dealloc(ptr, layout(size)) };
+        }
+    }
+
+    #[test]
+    fn test_unhappy_path_both_exhausted_returns_null() {
+        let alloc =
+            FallbackAllocator::new(FreeListAllocator::<256>::new(), ArenaAllocator::new(256));
+        assert!(unsafe { alloc.alloc(layout(1024)) }.is_null());
+        assert_eq!(alloc.fallback_count(), 1);
+    }
+}
@@ -0,0 +1,26 @@
+use task_06_alloc::CustomAllocator;
+use task_25_free_list_alloc::FreeListAllocator;
+use task_26_fallback_alloc::FallbackAllocator;
+
+// A small static region first, then the tracking system allocator from task_06
+#[global_allocator]
+static HEAP: FallbackAllocator<FreeListAllocator<{ 256 * 1024 }>, CustomAllocator> =
+    FallbackAllocator::new(FreeListAllocator::new(), CustomAllocator);
+
+#[test]
+fn test_overflowing_the_region_falls_back_to_the_system() {
+    let small: Vec<u8> = vec![1; 1024];
+    assert!(HEAP.primary().contains(small.as_ptr()));
+
+    let large: Vec<u8> = vec![2; 1024 * 1024];
+    assert!(!HEAP.primary().contains(large.as_ptr()));
+    assert!(HEAP.fallback_count() > 0);
+    assert!(CustomAllocator::now_allocated() >= large.len());
+
+    // Growing a vector out of the region moves it to the system allocator
+    let mut growing = vec![3u8; 16];
+    assert!(HEAP.primary().contains(growing.as_ptr()));
+    growing.resize(512 * 1024, 3);
+    assert!(!HEAP.primary().contains(growing.as_ptr()));
+    assert!(growing.iter().all(|&b| b == 3));
+}
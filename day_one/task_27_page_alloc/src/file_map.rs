@@ -0,0 +1,160 @@
+//! Mapping files into memory instead of reading them.
+
+use std::fs::File;
+use std::io;
+use std::ops::{Deref, DerefMut};
+use std::slice;
+
+use crate::{sys, PageAllocator, Reservation};
+
+impl PageAllocator {
+    /// Maps the whole of `file` read-only, sharing the OS page cache
+    ///
+    /// Only Unix is supported; other targets return `Unsupported`. Empty
+    /// files cannot be mapped and give `InvalidInput`.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be truncated or written to while the mapping is
+    /// alive: a shrunk file makes reads fault, and writes may or may not show
+    /// through. Replace it by writing a new file and renaming it over the old
+    /// one (as `FileWrapper::atomic` does); the mapping keeps the old contents.
+    pub unsafe fn map_file(file: &File) -> io::Result<MappedFile> {
+        let (mapping, len) = map(file, false)?;
+        Ok(MappedFile { mapping, len })
+    }
+
+    /// Maps the whole of `file` copy-on-write: writes go to private copies of the pages they touch
+    ///
+    /// The file itself never changes, and pages that are only read are
+    /// shared with the page cache rather than copied.
+    ///
+    /// # Safety
+    ///
+    /// As for `map_file`.
+    pub unsafe fn map_file_private(file: &File) -> io::Result<PrivateMappedFile> {
+        let (mapping, len) = map(file, true)?;
+        Ok(PrivateMappedFile { mapping, len })
+    }
+}
+
+unsafe fn map(file: &File, private_writable: bool) -> io::Result<(Reservation, usize)> {
+    let len = usize::try_from(file.metadata()?.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "file is too large to map"))?;
+    if len == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "cannot map an empty file",
+        ));
+    }
+    let mapped_len = PageAllocator::round_up(len)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "file is too large to map"))?;
+    let ptr = sys::map_file(file, len, private_writable)?;
+    // Reusing `Reservation` gets the unmapping on drop; its length is the
+    // whole pages the OS mapped
+    let mapping = Reservation {
+        ptr,
+        len: mapped_len,
+    };
+    Ok((mapping, len))
+}
+
+/// A file's contents mapped read-only, unmapped on drop
+#[derive(Debug)]
+pub struct MappedFile {
+    mapping: Reservation,
+    len: usize,
+}
+
+impl Deref for MappedFile {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        // SAFETY: the first `len` bytes are the file's, mapped readable for our lifetime
+        unsafe { slice::from_raw_parts(self.mapping.ptr, self.len) }
+    }
+}
+
+/// A file's contents mapped copy-on-write, unmapped (and any changes discarded) on drop
+#[derive(Debug)]
+pub struct PrivateMappedFile {
+    mapping: Reservation,
+    len: usize,
+}
+
+impl Deref for PrivateMappedFile {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        // SAFETY: the first `len` bytes are mapped readable and writable for our lifetime
+        unsafe { slice::from_raw_parts(self.mapping.ptr, self.len) }
+    }
+}
+
+impl DerefMut for PrivateMappedFile {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        // SAFETY: as above, and `&mut self` makes the access exclusive
+        unsafe { slice::from_raw_parts_mut(self.mapping.ptr, self.len) }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::io::Write;
+    use std::path::PathBuf;
+
+    fn file_with(name: &str, contents: &[u8]) -> (PathBuf, File) {
+        let path = std::env::temp_dir().join(format!("{name}-{}", std::process::id()));
+        fs::File::create(&path)
+            .unwrap()
+            .write_all(contents)
+            .unwrap();
+        let file = File::open(&path).unwrap();
+        (path, file)
+    }
+
+    #[test]
+    fn test_happy_path_read_only_mapping_sees_the_file() {
+        let (path, file) = file_with("page-alloc-map", b"mapped bytes");
+        let map = unsafe { PageAllocator::map_file(&file).unwrap() };
+        assert_eq!(&*map, b"mapped bytes");
+        drop(map);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_private_mapping_writes_do_not_reach_the_file() {
+        let (path, file) = file_with("page-alloc-cow", b"original");
+        let mut map = unsafe { PageAllocator::map_file_private(&file).unwrap() };
+        map[..3].copy_from_slice(b"NEW");
+        assert_eq!(&*map, b"NEWginal");
+
+        let view = unsafe { PageAllocator::map_file(&file).unwrap() };
+        assert_eq!(&*view, b"original");
+        assert_eq!(fs::read(&path).unwrap(), b"original");
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_mapping_outlives_a_rename_over_the_file() {
+        let (path, file) = file_with("page-alloc-rename", b"old");
+        let map = unsafe { PageAllocator::map_file(&file).unwrap() };
+        let (next, _) = file_with("page-alloc-rename-next", b"new contents");
+        fs::rename(&next, &path).unwrap();
+        assert_eq!(
+            &*map, b"old",
+            "The mapping still refers to the replaced file"
+        );
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_unhappy_path_empty_file_is_rejected() {
+        let (path, file) = file_with("page-alloc-empty", b"");
+        let error = unsafe { PageAllocator::map_file(&file).unwrap_err() };
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        fs::remove_file(path).unwrap();
+    }
+}
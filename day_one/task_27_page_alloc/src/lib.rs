@@ -0,0 +1,324 @@
+use std::alloc::{GlobalAlloc, Layout};
+use std::io;
+use std::ops::{Deref, DerefMut};
+use std::ptr;
+use std::slice;
+use std::sync::OnceLock;
+
+mod file_map;
+mod sys;
+
+pub use file_map::{MappedFile, PrivateMappedFile};
+
+/// Access allowed to a range of pages
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protection {
+    /// Any access faults
+    None,
+    Read,
+    ReadWrite,
+}
+
+/// Requests whole pages straight from the OS (`mmap` on Unix, `VirtualAlloc` on Windows)
+///
+/// `reserve` claims address space without backing it with memory, and the
+/// returned `Reservation` commits, decommits and protects page ranges inside
+/// it. `allocate` is the common shortcut for committed, writable `Pages`.
+/// As a `GlobalAlloc`, every allocation gets its own mapping, rounded up to
+/// whole pages: wasteful for small objects, but a good backing store for
+/// allocators that carve up large blocks themselves.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PageAllocator;
+
+impl PageAllocator {
+    /// Returns the OS page size in bytes
+    pub fn page_size() -> usize {
+        static PAGE_SIZE: OnceLock<usize> = OnceLock::new();
+        *PAGE_SIZE.get_or_init(sys::page_size)
+    }
+
+    /// Rounds `len` up to a whole number of pages, or `None` if that overflows `usize`
+    pub fn round_up(len: usize) -> Option<usize> {
+        let page = Self::page_size();
+        len.div_ceil(page).checked_mul(page)
+    }
+
+    /// Reserves at least `len` bytes of address space; nothing is accessible until committed
+    pub fn reserve(len: usize) -> io::Result<Reservation> {
+        if len == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "cannot reserve zero bytes",
+            ));
+        }
+        let len = Self::round_up(len).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "reservation size overflows usize",
+            )
+        })?;
+        // SAFETY: a fresh mapping does not alias anything
+        let ptr = unsafe { sys::reserve(len)? };
+        Ok(Reservation { ptr, len })
+    }
+
+    /// Maps at least `len` bytes of zeroed, writable memory
+    pub fn allocate(len: usize) -> io::Result<Pages> {
+        let reservation = Self::reserve(len)?;
+        reservation.commit(0, reservation.len())?;
+        Ok(Pages { reservation })
+    }
+}
+
+unsafe impl GlobalAlloc for PageAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        // Mappings are page-aligned and no more
+        if layout.align() > Self::page_size() {
+            return ptr::null_mut();
+        }
+        // `Layout` caps the size at `isize::MAX`, which always rounds up within `usize`
+        let Some(len) = Self::round_up(layout.size()) else {
+            return ptr::null_mut();
+        };
+        match sys::reserve(len) {
+            Ok(ptr) => match sys::commit(ptr, len) {
+                Ok(()) => ptr,
+                Err(_) => {
+                    let _ = sys::release(ptr, len);
+                    ptr::null_mut()
+                }
+            },
+            Err(_) => ptr::null_mut(),
+        }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if let Some(len) = Self::round_up(layout.size()) {
+            let _ = sys::release(ptr, len);
+        }
+    }
+}
+
+/// A range of reserved address space, released on drop
+///
+/// Offsets passed to `commit`, `decommit` and `protect` must be page-aligned;
+/// lengths are rounded up to whole pages. Ranges outside the reservation panic.
+#[derive(Debug)]
+pub struct Reservation {
+    ptr: *mut u8,
+    len: usize,
+}
+
+// SAFETY: the reservation owns its mapping; page operations are thread-safe OS calls
+unsafe impl Send for Reservation {}
+unsafe impl Sync for Reservation {}
+
+impl Reservation {
+    /// Returns the start of the reserved range
+    pub fn as_ptr(&self) -> *mut u8 {
+        self.ptr
+    }
+
+    /// Returns the reserved size in bytes, a whole number of pages
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Always false: reservations are at least one page
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Backs a range with zeroed, readable and writable memory
+    pub fn commit(&self, offset: usize, len: usize) -> io::Result<()> {
+        let (ptr, len) = self.range(offset, len);
+        // SAFETY: the range lies inside our mapping
+        unsafe { sys::commit(ptr, len) }
+    }
+
+    /// Returns a range's memory to the OS; it reads as zero if committed again
+    ///
+    /// # Safety
+    ///
+    /// No reference into the range may be alive, and nothing may access it
+    /// until it is committed again: its contents are gone and any access faults.
+    pub unsafe fn decommit(&self, offset: usize, len: usize) -> io::Result<()> {
+        let (ptr, len) = self.range(offset, len);
+        // SAFETY: the range lies inside our mapping, and the caller vouches for its users
+        unsafe { sys::decommit(ptr, len) }
+    }
+
+    /// Changes the access allowed to a committed range
+    ///
+    /// # Safety
+    ///
+    /// Every live reference into the range, and every later access, must be
+    /// allowed by `protection`; anything else faults. `Protection::Read`
+    /// also rules out live `&mut` references.
+    pub unsafe fn protect(
+        &self,
+        offset: usize,
+        len: usize,
+        protection: Protection,
+    ) -> io::Result<()> {
+        let (ptr, len) = self.range(offset, len);
+        // SAFETY: the range lies inside our mapping, and the caller vouches for its users
+        unsafe { sys::protect(ptr, len, protection) }
+    }
+
+    fn range(&self, offset: usize, len: usize) -> (*mut u8, usize) {
+        let page = PageAllocator::page_size();
+        assert!(
+            offset.is_multiple_of(page),
+            "offset {} is not a multiple of the page size {}",
+            offset,
+            page
+        );
+        // Overflowing `usize` counts as out of bounds too
+        let end = PageAllocator::round_up(len).and_then(|rounded| offset.checked_add(rounded));
+        let end = match end {
+            Some(end) if end <= self.len => end,
+            _ => panic!(
+                "{} bytes at offset {} out of bounds for a reservation of {} bytes",
+                len, offset, self.len
+            ),
+        };
+        // SAFETY: in bounds, checked above
+        (unsafe { self.ptr.add(offset) }, end - offset)
+    }
+}
+
+impl Drop for Reservation {
+    fn drop(&mut self) {
+        // SAFETY: the mapping is ours and nothing borrows it past this point
+        unsafe {
+            let _ = sys::release(self.ptr, self.len);
+        }
+    }
+}
+
+/// Committed, writable pages that dereference to a byte slice
+///
+/// The reservation is not exposed: decommitting or protecting a page would
+/// break the slice. Use `PageAllocator::reserve` for that kind of control.
+#[derive(Debug)]
+pub struct Pages {
+    reservation: Reservation,
+}
+
+impl Deref for Pages {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        // SAFETY: every page is committed read-write and `Pages` never changes that
+        unsafe { slice::from_raw_parts(self.reservation.ptr, self.reservation.len) }
+    }
+}
+
+impl DerefMut for Pages {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        // SAFETY: as above, and `&mut self` makes the access exclusive
+        unsafe { slice::from_raw_parts_mut(self.reservation.ptr, self.reservation.len) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_size_is_a_power_of_two() {
+        let page = PageAllocator::page_size();
+        assert!(page >= 4096 && page.is_power_of_two());
+        assert_eq!(PageAllocator::round_up(1), Some(page));
+        assert_eq!(PageAllocator::round_up(page + 1), Some(2 * page));
+    }
+
+    #[test]
+    fn test_happy_path_allocate_zeroed_writable_pages() {
+        let mut pages = PageAllocator::allocate(10_000).unwrap();
+        assert_eq!(Some(pages.len()), PageAllocator::round_up(10_000));
+        assert_eq!(pages.as_ptr() as usize % PageAllocator::page_size(), 0);
+        assert!(pages.iter().all(|&b| b == 0));
+        pages[9_999] = 42;
+        assert_eq!(pages[9_999], 42);
+    }
+
+    #[test]
+    fn test_reserve_commit_decommit() {
+        let page = PageAllocator::page_size();
+        let reservation = PageAllocator::reserve(4 * page).unwrap();
+        reservation.commit(page, 2 * page).unwrap();
+        unsafe {
+            let second = reservation.as_ptr().add(page);
+            second.write_bytes(7, 2 * page);
+            assert_eq!(*second.add(page), 7);
+
+            reservation.decommit(page, page).unwrap();
+            reservation.commit(page, page).unwrap();
+            assert_eq!(*second, 0, "Decommitted memory comes back zeroed");
+            assert_eq!(*second.add(page), 7, "The neighbouring page kept its data");
+        }
+    }
+
+    #[test]
+    fn test_protect_read_only_then_writable_again() {
+        let reservation = PageAllocator::reserve(1).unwrap();
+        reservation.commit(0, 1).unwrap();
+        // SAFETY: only raw pointers touch the page, and only as each protection allows
+        unsafe {
+            *reservation.as_ptr() = 1;
+            reservation.protect(0, 1, Protection::Read).unwrap();
+            assert_eq!(*reservation.as_ptr(), 1, "Still readable");
+            reservation.protect(0, 1, Protection::ReadWrite).unwrap();
+            *reservation.as_ptr() = 2;
+            assert_eq!(*reservation.as_ptr(), 2);
+        }
+    }
+
+    #[test]
+    fn test_global_alloc_interface() {
+        let layout = Layout::from_size_align(100, 64).unwrap();
+        unsafe {
+            let ptr = PageAllocator.alloc(layout);
+            assert!(!ptr.is_null());
+            assert_eq!(ptr as usize % PageAllocator::page_size(), 0);
+            ptr.write_bytes(1, 100);
+            PageAllocator.dealloc(ptr, layout);
+
+            let huge_align = Layout::from_size_align(8, 1 << 20).unwrap();
+            assert!(PageAllocator.alloc(huge_align).is_null());
+        }
+    }
+
+    #[test]
+    fn test_unhappy_path_zero_length_reserve() {
+        let err = PageAllocator::reserve(0).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_unhappy_path_sizes_that_overflow_when_rounded_up() {
+        assert_eq!(PageAllocator::round_up(usize::MAX), None);
+        for len in [usize::MAX, usize::MAX - PageAllocator::page_size() + 2] {
+            let err = PageAllocator::reserve(len).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+            let err = PageAllocator::allocate(len).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_edge_case_overflowing_range_panics_as_out_of_bounds() {
+        let reservation = PageAllocator::reserve(1).unwrap();
+        let _ = reservation.commit(PageAllocator::page_size(), usize::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_edge_case_range_outside_reservation() {
+        let reservation = PageAllocator::reserve(1).unwrap();
+        let _ = reservation.commit(0, 2 * PageAllocator::page_size());
+    }
+}
@@ -0,0 +1,217 @@
+//! Thin wrappers over the OS virtual memory calls
+
+use std::fs::File;
+use std::io;
+
+use crate::Protection;
+
+#[cfg(unix)]
+mod imp {
+    use super::*;
+    use std::ptr;
+
+    fn prot(protection: Protection) -> libc::c_int {
+        match protection {
+            Protection::None => libc::PROT_NONE,
+            Protection::Read => libc::PROT_READ,
+            Protection::ReadWrite => libc::PROT_READ | libc::PROT_WRITE,
+        }
+    }
+
+    fn check(result: libc::c_int) -> io::Result<()> {
+        if result == 0 {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+
+    pub fn page_size() -> usize {
+        // SAFETY: sysconf has no preconditions
+        unsafe { libc::sysconf(libc::_SC_PAGESIZE) as usize }
+    }
+
+    pub unsafe fn reserve(len: usize) -> io::Result<*mut u8> {
+        let ptr = libc::mmap(
+            ptr::null_mut(),
+            len,
+            libc::PROT_NONE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        );
+        if ptr == libc::MAP_FAILED {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(ptr.cast())
+        }
+    }
+
+    pub unsafe fn commit(ptr: *mut u8, len: usize) -> io::Result<()> {
+        // Anonymous pages get physical memory on first touch
+        protect(ptr, len, Protection::ReadWrite)
+    }
+
+    pub unsafe fn decommit(ptr: *mut u8, len: usize) -> io::Result<()> {
+        check(libc::madvise(ptr.cast(), len, libc::MADV_DONTNEED))?;
+        protect(ptr, len, Protection::None)
+    }
+
+    pub unsafe fn protect(ptr: *mut u8, len: usize, protection: Protection) -> io::Result<()> {
+        check(libc::mprotect(ptr.cast(), len, prot(protection)))
+    }
+
+    pub unsafe fn release(ptr: *mut u8, len: usize) -> io::Result<()> {
+        check(libc::munmap(ptr.cast(), len))
+    }
+
+    pub unsafe fn map_file(file: &File, len: usize, private_writable: bool) -> io::Result<*mut u8> {
+        use std::os::fd::AsRawFd;
+
+        // A private mapping copies a page the first time it is written, so
+        // writes never reach the file; a read-only one just shares the page cache
+        let prot = if private_writable {
+            libc::PROT_READ | libc::PROT_WRITE
+        } else {
+            libc::PROT_READ
+        };
+        let ptr = libc::mmap(
+            ptr::null_mut(),
+            len,
+            prot,
+            libc::MAP_PRIVATE,
+            file.as_raw_fd(),
+            0,
+        );
+        if ptr == libc::MAP_FAILED {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(ptr.cast())
+        }
+    }
+}
+
+#[cfg(windows)]
+mod imp {
+    use super::*;
+    use std::mem::MaybeUninit;
+    use std::ptr;
+    use windows_sys::Win32::System::Memory::{
+        VirtualAlloc, VirtualFree, VirtualProtect, MEM_COMMIT, MEM_DECOMMIT, MEM_RELEASE,
+        MEM_RESERVE, PAGE_NOACCESS, PAGE_PROTECTION_FLAGS, PAGE_READONLY, PAGE_READWRITE,
+    };
+    use windows_sys::Win32::System::SystemInformation::GetSystemInfo;
+
+    fn prot(protection: Protection) -> PAGE_PROTECTION_FLAGS {
+        match protection {
+            Protection::None => PAGE_NOACCESS,
+            Protection::Read => PAGE_READONLY,
+            Protection::ReadWrite => PAGE_READWRITE,
+        }
+    }
+
+    fn check(result: i32) -> io::Result<()> {
+        if result != 0 {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+
+    pub fn page_size() -> usize {
+        let mut info = MaybeUninit::uninit();
+        // SAFETY: GetSystemInfo fills in the whole struct
+        unsafe {
+            GetSystemInfo(info.as_mut_ptr());
+            info.assume_init().dwPageSize as usize
+        }
+    }
+
+    pub unsafe fn reserve(len: usize) -> io::Result<*mut u8> {
+        let ptr = VirtualAlloc(ptr::null(), len, MEM_RESERVE, PAGE_NOACCESS);
+        if ptr.is_null() {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(ptr.cast())
+        }
+    }
+
+    pub unsafe fn commit(ptr: *mut u8, len: usize) -> io::Result<()> {
+        if VirtualAlloc(ptr.cast(), len, MEM_COMMIT, PAGE_READWRITE).is_null() {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
+
+    pub unsafe fn decommit(ptr: *mut u8, len: usize) -> io::Result<()> {
+        check(VirtualFree(ptr.cast(), len, MEM_DECOMMIT))
+    }
+
+    pub unsafe fn protect(ptr: *mut u8, len: usize, protection: Protection) -> io::Result<()> {
+        let mut old = 0;
+        check(VirtualProtect(ptr.cast(), len, prot(protection), &mut old))
+    }
+
+    pub unsafe fn release(ptr: *mut u8, _len: usize) -> io::Result<()> {
+        check(VirtualFree(ptr.cast(), 0, MEM_RELEASE))
+    }
+
+    // Views are released with UnmapViewOfFile rather than VirtualFree, and
+    // Windows will not rename over a file that has a view open, so file
+    // mappings are left to the Unix implementation
+    pub unsafe fn map_file(
+        _file: &File,
+        _len: usize,
+        _private_writable: bool,
+    ) -> io::Result<*mut u8> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "file mappings are only implemented on Unix",
+        ))
+    }
+}
+
+// No virtual memory API (e.g. wasm32): every reservation fails
+#[cfg(not(any(unix, windows)))]
+mod imp {
+    use super::*;
+
+    fn unsupported() -> io::Error {
+        io::Error::new(io::ErrorKind::Unsupported, "no virtual memory on this target")
+    }
+
+    pub fn page_size() -> usize {
+        64 * 1024
+    }
+
+    pub unsafe fn reserve(_len: usize) -> io::Result<*mut u8> {
+        Err(unsupported())
+    }
+
+    pub unsafe fn commit(_ptr: *mut u8, _len: usize) -> io::Result<()> {
+        Err(unsupported())
+    }
+
+    pub unsafe fn decommit(_ptr: *mut u8, _len: usize) -> io::Result<()> {
+        Err(unsupported())
+    }
+
+    pub unsafe fn protect(_ptr: *mut u8, _len: usize, _protection: Protection) -> io::Result<()> {
+        Err(unsupported())
+    }
+
+    pub unsafe fn release(_ptr: *mut u8, _len: usize) -> io::Result<()> {
+        Err(unsupported())
+    }
+
+    pub unsafe fn map_file(
+        _file: &File,
+        _len: usize,
+        _private_writable: bool,
+    ) -> io::Result<*mut u8> {
+        Err(unsupported())
+    }
+}
+
+pub use imp::*;
@@ -0,0 +1,245 @@
+use std::fmt;
+
+use task_06_alloc::CustomAllocator;
+use task_07_mempool::MemoryPool as ChunkPool;
+use task_10_arena_alloc::MemoryArena;
+use task_12_alloc_mempool::MemoryPool as BlockPool;
+
+/// Allocators that can report how much of their memory is handed out
+///
+/// `bytes_in_use` and `allocations` describe what is live right now and
+/// should drop back once everything is returned; `high_water` is the peak of
+/// `bytes_in_use` and never goes down. `assert_no_leaks!` builds on this to
+/// check any allocator the same way.
+pub trait TrackedAlloc {
+    /// Bytes handed out and not yet returned
+    fn bytes_in_use(&self) -> usize;
+
+    /// Allocations handed out and not yet returned
+    fn allocations(&self) -> usize;
+
+    /// The most bytes that were ever in use at once
+    fn high_water(&self) -> usize;
+
+    /// Snapshots all three counters
+    fn leak_report(&self) -> LeakReport {
+        LeakReport {
+            bytes_in_use: self.bytes_in_use(),
+            allocations: self.allocations(),
+            high_water: self.high_water(),
+        }
+    }
+}
+
+/// A snapshot of an allocator's counters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LeakReport {
+    pub bytes_in_use: usize,
+    pub allocations: usize,
+    pub high_water: usize,
+}
+
+impl LeakReport {
+    /// True when nothing is in use
+    pub fn is_clean(&self) -> bool {
+        self.bytes_in_use == 0 && self.allocations == 0
+    }
+
+    /// Returns what is in use now but wasn't at `baseline`, keeping the current high water
+    pub fn since(&self, baseline: &LeakReport) -> LeakReport {
+        LeakReport {
+            bytes_in_use: self.bytes_in_use.saturating_sub(baseline.bytes_in_use),
+            allocations: self.allocations.saturating_sub(baseline.allocations),
+            high_water: self.high_water,
+        }
+    }
+}
+
+impl fmt::Display for LeakReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} bytes in {} allocations still in use (high water {} bytes)",
+            self.bytes_in_use, self.allocations, self.high_water
+        )
+    }
+}
+
+/// Counts process-wide, so it only sees allocations made through `CustomAllocator`
+impl TrackedAlloc for CustomAllocator {
+    fn bytes_in_use(&self) -> usize {
+        CustomAllocator::now_allocated()
+    }
+
+    fn allocations(&self) -> usize {
+        CustomAllocator::live_allocations()
+    }
+
+    fn high_water(&self) -> usize {
+        CustomAllocator::peak_allocated()
+    }
+}
+
+/// Everything allocated since the last `reset` counts as in use
+impl TrackedAlloc for MemoryArena {
+    fn bytes_in_use(&self) -> usize {
+        self.used()
+    }
+
+    fn allocations(&self) -> usize {
+        self.live_allocations()
+    }
+
+    fn high_water(&self) -> usize {
+        self.peak_used()
+    }
+}
+
+/// Task 07's fixed-size pool, counted in whole chunks
+impl TrackedAlloc for ChunkPool {
+    fn bytes_in_use(&self) -> usize {
+        self.chunks_in_use() * self.chunk_size()
+    }
+
+    fn allocations(&self) -> usize {
+        self.chunks_in_use()
+    }
+
+    fn high_water(&self) -> usize {
+        self.peak_chunks_in_use() * self.chunk_size()
+    }
+}
+
+/// Task 12's size-keyed pool
+impl TrackedAlloc for BlockPool {
+    fn bytes_in_use(&self) -> usize {
+        BlockPool::bytes_in_use(self)
+    }
+
+    fn allocations(&self) -> usize {
+        self.blocks_in_use()
+    }
+
+    fn high_water(&self) -> usize {
+        self.peak_bytes_in_use()
+    }
+}
+
+/// Runs a closure and panics if `alloc` has more memory in use afterwards than before
+///
+/// Evaluates to the closure's result. `alloc` is evaluated before and after
+/// the closure rather than borrowed across it, so the closure may use the
+/// allocator mutably; pass a variable or other place expression.
+///
+/// ```
+/// use task_12_alloc_mempool::MemoryPool;
+/// use task_28_tracked_alloc::assert_no_leaks;
+///
+/// let mut pool = MemoryPool::new();
+/// let size = assert_no_leaks!(pool, || {
+///     let block = pool.allocate(256).unwrap();
+///     let size = block.size;
+///     pool.deallocate(block);
+///     size
+/// });
+/// assert_eq!(size, 256);
+/// ```
+#[macro_export]
+macro_rules! assert_no_leaks {
+    ($alloc:expr, $body:expr $(,)?) => {{
+        use $crate::TrackedAlloc as _;
+        let before = ($alloc).leak_report();
+        let result = ($body)();
+        let leaked = ($alloc).leak_report().since(&before);
+        assert!(
+            leaked.is_clean(),
+            "assert_no_leaks!({}) failed: {}",
+            stringify!($alloc),
+            leaked
+        );
+        result
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::alloc::{GlobalAlloc, Layout};
+
+    #[test]
+    fn test_happy_path_balanced_custom_allocator() {
+        let layout = Layout::from_size_align(512, 8).unwrap();
+        assert_no_leaks!(CustomAllocator, || unsafe {
+            let ptrs: Vec<_> = (0..4).map(|_| CustomAllocator.alloc(layout)).collect();
+            assert_eq!(CustomAllocator.allocations(), 4);
+            for ptr in ptrs {
+                CustomAllocator.dealloc(ptr, layout);
+            }
+        });
+        assert!(CustomAllocator.high_water() >= 4 * 512);
+    }
+
+    #[test]
+    fn test_arena_is_clean_only_after_reset() {
+        let mut arena = MemoryArena::new(1024);
+        assert_no_leaks!(arena, || {
+            arena.allocate(100);
+            arena.allocate(28);
+            assert_eq!(arena.leak_report().allocations, 2);
+            arena.reset();
+        });
+        assert_eq!(arena.high_water(), 128);
+    }
+
+    #[test]
+    fn test_chunk_pool_through_arc() {
+        let pool = ChunkPool::new(64, 4);
+        let chunks = assert_no_leaks!(pool, || {
+            let chunk = pool.allocate().unwrap();
+            pool.deallocate(chunk);
+            pool.available_chunks()
+        });
+        assert_eq!(chunks, 4, "The macro returns the closure's result");
+        assert_eq!(pool.high_water(), 64);
+    }
+
+    #[test]
+    fn test_block_pool_report() {
+        let mut pool = BlockPool::new();
+        let block = pool.allocate(300).unwrap();
+        let report = pool.leak_report();
+        assert_eq!(
+            report,
+            LeakReport {
+                bytes_in_use: 300,
+                allocations: 1,
+                high_water: 300,
+            }
+        );
+        assert_eq!(
+            report.to_string(),
+            "300 bytes in 1 allocations still in use (high water 300 bytes)"
+        );
+        pool.deallocate(block);
+        assert!(pool.leak_report().is_clean());
+    }
+
+    #[test]
+    #[should_panic(expected = "assert_no_leaks!(pool) failed: 256 bytes in 1 allocations")]
+    fn test_unhappy_path_leaked_block_panics() {
+        let mut pool = BlockPool::new();
+        assert_no_leaks!(pool, || {
+            std::mem::forget(pool.allocate(256));
+        });
+    }
+
+    #[test]
+    fn test_edge_case_memory_in_use_beforehand_is_ignored() {
+        let mut arena = MemoryArena::new(256);
+        arena.allocate(64);
+        assert_no_leaks!(arena, || ());
+        let leaked = arena.leak_report().since(&LeakReport::default());
+        assert_eq!(leaked.bytes_in_use, 64);
+        assert!(!leaked.is_clean());
+    }
+}
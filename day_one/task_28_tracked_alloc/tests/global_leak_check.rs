@@ -0,0 +1,26 @@
+use std::panic;
+
+use task_06_alloc::CustomAllocator;
+use task_28_tracked_alloc::assert_no_leaks;
+
+// With the tracking allocator installed globally, every Box and Vec is counted
+#[global_allocator]
+static GLOBAL: CustomAllocator = CustomAllocator;
+
+// A single test, so no other test thread allocates while the counters are compared
+#[test]
+fn test_leak_check_over_the_global_heap() {
+    let total = assert_no_leaks!(CustomAllocator, || {
+        let numbers: Vec<u64> = (0..1000).collect();
+        let words: Vec<String> = ["alpha", "beta"].iter().map(|w| w.to_string()).collect();
+        numbers.iter().sum::<u64>() + words.len() as u64
+    });
+    assert_eq!(total, 499_502);
+
+    let leaked = panic::catch_unwind(|| {
+        assert_no_leaks!(CustomAllocator, || {
+            Box::leak(Box::new([0u8; 4096]));
+        })
+    });
+    assert!(leaked.is_err(), "A leaked Box must be reported");
+}
@@ -0,0 +1,436 @@
+use std::mem;
+use std::time::{Duration, Instant};
+
+/// Slots per level; a power of two so slot indices are bit fields of the tick
+const SLOTS: usize = 64;
+const SLOT_BITS: u32 = SLOTS.trailing_zeros();
+/// Six levels of 64 slots cover 2^36 ticks, over two years at 1ms resolution
+const LEVELS: usize = 6;
+
+/// Identifies a scheduled timer so it can be cancelled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimerHandle {
+    index: usize,
+    generation: u64,
+}
+
+struct Entry<T> {
+    /// Tick at which the timer fires
+    deadline: u64,
+    payload: T,
+}
+
+// A slab slot; `generation` changes whenever the slot is freed, so handles and
+// bucket references to an old timer stop matching
+struct Timer<T> {
+    generation: u64,
+    entry: Option<Entry<T>>,
+}
+
+/// Hierarchical timer wheel
+///
+/// Time is counted in ticks of a fixed resolution. Level 0 has one slot per
+/// tick; each level above has slots 64 times as wide. Every level is a circular
+/// array: a slot index is the tick shifted down by the level and wrapped, the
+/// same index arithmetic a circular buffer uses, so levels never move or grow.
+/// A timer is filed at the lowest level whose current span contains its
+/// deadline. When time crosses into a higher level's slot, that slot is
+/// cascaded: its timers are refiled into the finer levels below. Each timer is
+/// moved at most once per level, so scheduling, cancelling and firing are
+/// O(1) amortized.
+///
+/// Payloads can be plain tokens or callbacks such as `Box<dyn FnOnce()>`;
+/// `tick` hands back whatever is due and leaves running it to the caller.
+pub struct TimerWheel<T> {
+    /// `LEVELS * SLOTS` buckets of slab references, level by level
+    buckets: Vec<Vec<(usize, u64)>>,
+    /// Per level, a bit for each slot whose bucket is non-empty
+    occupied: [u64; LEVELS],
+    timers: Vec<Timer<T>>,
+    free: Vec<usize>,
+    len: usize,
+    start: Instant,
+    resolution: Duration,
+    /// Ticks processed so far
+    elapsed: u64,
+}
+
+impl<T> TimerWheel<T> {
+    /// Creates a wheel whose clock starts now and advances in steps of `resolution`
+    pub fn new(resolution: Duration) -> Self {
+        Self::starting_at(resolution, Instant::now())
+    }
+
+    /// Creates a wheel whose clock starts at `start`
+    pub fn starting_at(resolution: Duration, start: Instant) -> Self {
+        assert!(
+            !resolution.is_zero(),
+            "TimerWheel resolution must be non-zero"
+        );
+        TimerWheel {
+            buckets: (0..LEVELS * SLOTS).map(|_| Vec::new()).collect(),
+            occupied: [0; LEVELS],
+            timers: Vec::new(),
+            free: Vec::new(),
+            len: 0,
+            start,
+            resolution,
+            elapsed: 0,
+        }
+    }
+
+    /// Schedules `payload` to be returned by the first `tick` at least `delay`
+    /// after the wheel's current time
+    ///
+    /// The current time is the last time passed to `tick`, not the wall clock.
+    /// Delays round up to whole ticks, and even a zero delay waits for the next one.
+    pub fn schedule(&mut self, delay: Duration, payload: T) -> TimerHandle {
+        let ticks = delay.as_nanos().div_ceil(self.resolution.as_nanos()).max(1);
+        let deadline = self
+            .elapsed
+            .saturating_add(u64::try_from(ticks).unwrap_or(u64::MAX));
+        let entry = Some(Entry { deadline, payload });
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.timers[index].entry = entry;
+                index
+            }
+            None => {
+                self.timers.push(Timer {
+                    generation: 0,
+                    entry,
+                });
+                self.timers.len() - 1
+            }
+        };
+        self.len += 1;
+        let generation = self.timers[index].generation;
+        self.file(index, generation, deadline);
+        TimerHandle { index, generation }
+    }
+
+    /// Cancels a pending timer, returning its payload; `None` if it already fired
+    /// or was cancelled
+    pub fn cancel(&mut self, handle: TimerHandle) -> Option<T> {
+        // The bucket still references the slot; the new generation makes it stale
+        self.release(handle.index, handle.generation)
+    }
+
+    /// Checks if the timer behind `handle` is still pending
+    pub fn is_pending(&self, handle: TimerHandle) -> bool {
+        self.timers
+            .get(handle.index)
+            .is_some_and(|timer| timer.generation == handle.generation && timer.entry.is_some())
+    }
+
+    /// Advances the wheel to `now`, returning the payloads of every timer that
+    /// came due, earliest first
+    pub fn tick(&mut self, now: Instant) -> Vec<T> {
+        let since_start = now.saturating_duration_since(self.start).as_nanos();
+        let target = u64::try_from(since_start / self.resolution.as_nanos()).unwrap_or(u64::MAX);
+        let mut expired = Vec::new();
+        // Ticks with nothing to cascade or fire are skipped, so idle time is free
+        while let Some(next) = self.next_event().filter(|&next| next <= target) {
+            self.elapsed = next;
+            self.cascade();
+            for (index, generation) in self.take_bucket(0) {
+                expired.extend(self.release(index, generation));
+            }
+        }
+        self.elapsed = self.elapsed.max(target);
+        expired
+    }
+
+    /// Returns the number of pending timers
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if no timers are pending
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the length of one tick
+    pub fn resolution(&self) -> Duration {
+        self.resolution
+    }
+
+    /// Returns the wheel's current time, the start plus every tick processed
+    pub fn now(&self) -> Instant {
+        let nanos = self.resolution.as_nanos() * u128::from(self.elapsed);
+        self.start + Duration::from_nanos(u64::try_from(nanos).unwrap_or(u64::MAX))
+    }
+
+    // Empties the slot of `level` that covers the current tick
+    fn take_bucket(&mut self, level: usize) -> Vec<(usize, u64)> {
+        self.occupied[level] &= !(1 << digit(level, self.elapsed));
+        mem::take(&mut self.buckets[slot(level, self.elapsed)])
+    }
+
+    // The first tick after now at which some occupied slot starts
+    fn next_event(&self) -> Option<u64> {
+        (0..LEVELS)
+            .filter_map(|level| {
+                // Rotate so bit `i` stands for the slot `i + 1` after the current one
+                let current = digit(level, self.elapsed) as u32;
+                let ahead = self.occupied[level].rotate_right(current + 1);
+                if ahead == 0 {
+                    return None;
+                }
+                let offset = u64::from(ahead.trailing_zeros()) + 1;
+                let shift = SLOT_BITS * level as u32;
+                Some(((self.elapsed >> shift) + offset) << shift)
+            })
+            .min()
+    }
+
+    // Files a timer in the lowest level whose current span contains `deadline`
+    fn file(&mut self, index: usize, generation: u64, deadline: u64) {
+        let bucket = if deadline <= self.elapsed {
+            // Only reached while cascading into the tick about to fire
+            slot(0, self.elapsed)
+        } else {
+            // The highest bit where the deadline differs from now picks the level
+            let differing = 63 - (self.elapsed ^ deadline).leading_zeros();
+            let level = (differing / SLOT_BITS) as usize;
+            if level < LEVELS {
+                slot(level, deadline)
+            } else {
+                // The top level wraps, so a deadline in its next rotation still
+                // has a slot; anything further is parked in the last slot of the
+                // range and refiled when that slot comes round
+                let range_end = self
+                    .elapsed
+                    .saturating_add((1 << (SLOT_BITS * LEVELS as u32)) - 1);
+                slot(LEVELS - 1, deadline.min(range_end))
+            }
+        };
+        self.buckets[bucket].push((index, generation));
+        self.occupied[bucket / SLOTS] |= 1 << (bucket % SLOTS);
+    }
+
+    // Refiles the timers of every higher-level slot that starts at this tick,
+    // coarsest first so they can trickle all the way down
+    fn cascade(&mut self) {
+        let levels = (1..LEVELS)
+            .take_while(|&level| self.elapsed & ((1 << (SLOT_BITS * level as u32)) - 1) == 0)
+            .count();
+        for level in (1..=levels).rev() {
+            for (index, generation) in self.take_bucket(level) {
+                let timer = &self.timers[index];
+                if let Some(entry) = timer
+                    .entry
+                    .as_ref()
+                    .filter(|_| timer.generation == generation)
+                {
+                    let deadline = entry.deadline;
+                    self.file(index, generation, deadline);
+                }
+            }
+        }
+    }
+
+    // Frees the slab slot if `generation` still names its timer
+    fn release(&mut self, index: usize, generation: u64) -> Option<T> {
+        let timer = self.timers.get_mut(index)?;
+        if timer.generation != generation {
+            return None;
+        }
+        let entry = timer.entry.take()?;
+        timer.generation += 1;
+        self.free.push(index);
+        self.len -= 1;
+        Some(entry.payload)
+    }
+}
+
+// Index into `buckets` of the slot covering `tick` at `level`
+fn slot(level: usize, tick: u64) -> usize {
+    level * SLOTS + digit(level, tick)
+}
+
+// The slot index of `tick` at `level`: its base-64 digit for that level
+fn digit(level: usize, tick: u64) -> usize {
+    (tick >> (SLOT_BITS * level as u32)) as usize & (SLOTS - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MS: Duration = Duration::from_millis(1);
+
+    fn wheel() -> (TimerWheel<&'static str>, Instant) {
+        let start = Instant::now();
+        (TimerWheel::starting_at(MS, start), start)
+    }
+
+    #[test]
+    fn test_happy_path_timers_fire_in_deadline_order() {
+        let (mut wheel, start) = wheel();
+        wheel.schedule(30 * MS, "c");
+        wheel.schedule(10 * MS, "a");
+        wheel.schedule(20 * MS, "b");
+        assert_eq!(wheel.len(), 3);
+
+        assert!(wheel.tick(start + 9 * MS).is_empty());
+        assert_eq!(wheel.tick(start + 10 * MS), vec!["a"]);
+        assert_eq!(wheel.tick(start + 40 * MS), vec!["b", "c"]);
+        assert!(wheel.is_empty());
+    }
+
+    #[test]
+    fn test_happy_path_cancel_returns_payload() {
+        let (mut wheel, start) = wheel();
+        let keep = wheel.schedule(5 * MS, "keep");
+        let drop = wheel.schedule(5 * MS, "drop");
+        assert_eq!(wheel.cancel(drop), Some("drop"));
+        assert!(!wheel.is_pending(drop));
+        assert!(wheel.is_pending(keep));
+        assert_eq!(wheel.tick(start + 5 * MS), vec!["keep"]);
+    }
+
+    #[test]
+    fn test_far_timers_cascade_through_levels() {
+        let start = Instant::now();
+        let mut wheel = TimerWheel::starting_at(MS, start);
+        // One timer per level boundary, plus a few just off them
+        let delays: [u32; 8] = [63, 64, 65, 4095, 4096, 4097, 262_144, 300_000];
+        for &delay in delays.iter().rev() {
+            wheel.schedule(delay * MS, delay);
+        }
+        let mut fired = Vec::new();
+        for &delay in &delays {
+            assert!(wheel.tick(start + (delay - 1) * MS).is_empty());
+            fired.extend(wheel.tick(start + delay * MS));
+        }
+        assert_eq!(fired, delays);
+    }
+
+    #[test]
+    fn test_fires_exactly_when_due_under_random_schedules() {
+        let start = Instant::now();
+        let mut wheel = TimerWheel::starting_at(MS, start);
+        let mut state = 0x9e37_79b9_7f4a_7c15_u64;
+        let mut random = move |bound: u64| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state % bound
+        };
+        let mut now = 0;
+        let mut pending = Vec::new();
+        let mut deadlines = Vec::new();
+        for id in 0..2000 {
+            let delay = 1 + random([10, 1_000, 100_000][id % 3]);
+            wheel.schedule(Duration::from_millis(delay), id);
+            pending.push((now + delay, id));
+            deadlines.push(now + delay);
+            if id % 7 == 0 {
+                now += random(5_000);
+                let mut due: Vec<(u64, usize)> = pending
+                    .iter()
+                    .copied()
+                    .filter(|&(at, _)| at <= now)
+                    .collect();
+                pending.retain(|&(at, _)| at > now);
+                // Timers sharing a deadline may fire in any order
+                let mut fired: Vec<(u64, usize)> = wheel
+                    .tick(start + Duration::from_millis(now))
+                    .into_iter()
+                    .map(|id| (deadlines[id], id))
+                    .collect();
+                assert!(fired.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+                fired.sort();
+                due.sort();
+                assert_eq!(fired, due);
+            }
+        }
+        assert_eq!(wheel.len(), pending.len());
+    }
+
+    #[test]
+    fn test_callbacks_as_payloads() {
+        let start = Instant::now();
+        let mut wheel: TimerWheel<Box<dyn FnOnce() -> u32>> = TimerWheel::starting_at(MS, start);
+        wheel.schedule(2 * MS, Box::new(|| 2));
+        wheel.schedule(MS, Box::new(|| 1));
+        let results: Vec<u32> = wheel
+            .tick(start + 2 * MS)
+            .into_iter()
+            .map(|callback| callback())
+            .collect();
+        assert_eq!(results, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_unhappy_path_stale_handles() {
+        let (mut wheel, start) = wheel();
+        let fired = wheel.schedule(MS, "fired");
+        assert_eq!(wheel.tick(start + MS), vec!["fired"]);
+        assert_eq!(wheel.cancel(fired), None);
+
+        // The freed slot is reused, but the old handle must not reach the new timer
+        let reused = wheel.schedule(MS, "reused");
+        assert_eq!(wheel.cancel(fired), None);
+        assert_eq!(wheel.cancel(reused), Some("reused"));
+        assert_eq!(wheel.cancel(reused), None);
+        assert!(wheel.tick(start + 10 * MS).is_empty());
+    }
+
+    #[test]
+    fn test_edge_case_rounding_and_delays_are_relative_to_the_wheel() {
+        let (mut wheel, start) = wheel();
+        wheel.schedule(Duration::ZERO, "zero");
+        wheel.schedule(Duration::from_micros(1500), "rounded up");
+        assert!(wheel.tick(start).is_empty(), "Time has not moved");
+        assert_eq!(wheel.tick(start + MS), vec!["zero"]);
+        assert_eq!(wheel.tick(start + 2 * MS), vec!["rounded up"]);
+
+        // Idle time is skipped, then delays count from the wheel's new time
+        assert!(wheel.tick(start + 1000 * MS).is_empty());
+        assert_eq!(wheel.now(), start + 1000 * MS);
+        wheel.schedule(5 * MS, "later");
+        assert!(wheel.tick(start + 1004 * MS).is_empty());
+        assert_eq!(wheel.tick(start + 1005 * MS), vec!["later"]);
+        assert!(
+            wheel.tick(start).is_empty(),
+            "Going back in time is a no-op"
+        );
+    }
+
+    #[test]
+    fn test_edge_case_beyond_wheel_range() {
+        let start = Instant::now();
+        let mut wheel = TimerWheel::starting_at(Duration::from_nanos(1), start);
+        let span = 1u64 << (SLOT_BITS as usize * LEVELS);
+        wheel.schedule(Duration::from_nanos(span + 10), "far");
+        wheel.schedule(Duration::from_nanos(5), "near");
+        assert_eq!(wheel.tick(start + Duration::from_nanos(5)), vec!["near"]);
+        assert!(wheel
+            .tick(start + Duration::from_nanos(span + 9))
+            .is_empty());
+        assert_eq!(
+            wheel.tick(start + Duration::from_nanos(span + 10)),
+            vec!["far"]
+        );
+
+        // Several rotations out, parked and refiled more than once
+        let now = wheel.now();
+        wheel.schedule(Duration::from_nanos(3 * span + 7), "farther");
+        assert!(wheel
+            .tick(now + Duration::from_nanos(3 * span + 6))
+            .is_empty());
+        assert_eq!(
+            wheel.tick(now + Duration::from_nanos(3 * span + 7)),
+            vec!["farther"]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "resolution must be non-zero")]
+    fn test_unhappy_path_zero_resolution_panics() {
+        TimerWheel::<()>::new(Duration::ZERO);
+    }
+}
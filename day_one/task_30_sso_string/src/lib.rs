@@ -0,0 +1,391 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+
+/// A string that keeps up to `N` bytes inline and moves to a heap `String` beyond that
+///
+/// Most strings in a typical program are short (identifiers, keys, small
+/// labels), and storing them inline saves an allocation per string. With the
+/// default `N = 23`, the inline buffer plus its length byte take the same 24
+/// bytes as a `String`'s pointer, length and capacity.
+#[derive(Clone)]
+pub struct SsoString<const N: usize = 23> {
+    repr: Repr<N>,
+}
+
+#[derive(Clone)]
+enum Repr<const N: usize> {
+    Inline { len: u8, buf: [u8; N] },
+    Heap(String),
+}
+
+impl<const N: usize> SsoString<N> {
+    /// Bytes that fit without a heap allocation
+    pub const INLINE_CAPACITY: usize = {
+        assert!(
+            N <= u8::MAX as usize,
+            "SsoString can keep at most 255 bytes inline"
+        );
+        N
+    };
+
+    /// Creates an empty, inline string
+    pub const fn new() -> Self {
+        // Evaluating the constant rejects an `N` too large for the length byte
+        let _ = Self::INLINE_CAPACITY;
+        SsoString {
+            repr: Repr::Inline {
+                len: 0,
+                buf: [0; N],
+            },
+        }
+    }
+
+    /// Returns the contents as a `&str`
+    pub fn as_str(&self) -> &str {
+        match &self.repr {
+            Repr::Inline { len, buf } => {
+                // SAFETY: the inline bytes are only ever written from whole
+                // `str`s, so the first `len` of them are valid UTF-8
+                unsafe { std::str::from_utf8_unchecked(&buf[..*len as usize]) }
+            }
+            Repr::Heap(string) => string,
+        }
+    }
+
+    /// Appends `string`, moving to the heap if the result does not fit inline
+    pub fn push_str(&mut self, string: &str) {
+        match &mut self.repr {
+            Repr::Inline { len, buf } => {
+                let start = *len as usize;
+                let end = start + string.len();
+                if end <= Self::INLINE_CAPACITY {
+                    buf[start..end].copy_from_slice(string.as_bytes());
+                    *len = end as u8;
+                } else {
+                    let mut heap = String::with_capacity(end);
+                    heap.push_str(self.as_str());
+                    heap.push_str(string);
+                    self.repr = Repr::Heap(heap);
+                }
+            }
+            Repr::Heap(heap) => heap.push_str(string),
+        }
+    }
+
+    /// Appends a single character
+    pub fn push(&mut self, ch: char) {
+        self.push_str(ch.encode_utf8(&mut [0; 4]));
+    }
+
+    /// Shortens the string to `new_len` bytes; does nothing if it is already shorter
+    ///
+    /// Stays on the heap if it was there; follow with `shrink_to_fit` to move back inline.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `new_len` is not on a character boundary.
+    pub fn truncate(&mut self, new_len: usize) {
+        if new_len >= self.len() {
+            return;
+        }
+        assert!(
+            self.is_char_boundary(new_len),
+            "new_len is not on a character boundary"
+        );
+        match &mut self.repr {
+            Repr::Inline { len, .. } => *len = new_len as u8,
+            Repr::Heap(string) => string.truncate(new_len),
+        }
+    }
+
+    /// Returns the length in bytes
+    pub fn len(&self) -> usize {
+        match &self.repr {
+            Repr::Inline { len, .. } => *len as usize,
+            Repr::Heap(string) => string.len(),
+        }
+    }
+
+    /// Checks if the string is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Checks if the contents are stored inline rather than on the heap
+    pub fn is_inline(&self) -> bool {
+        matches!(self.repr, Repr::Inline { .. })
+    }
+
+    /// Returns how many bytes fit before the next reallocation
+    pub fn capacity(&self) -> usize {
+        match &self.repr {
+            Repr::Inline { .. } => Self::INLINE_CAPACITY,
+            Repr::Heap(string) => string.capacity(),
+        }
+    }
+
+    /// Empties the string, releasing any heap allocation
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Moves the contents back inline if they fit, releasing the heap allocation
+    pub fn shrink_to_fit(&mut self) {
+        if let Repr::Heap(string) = &mut self.repr {
+            match Self::try_inline(string) {
+                Some(inline) => *self = inline,
+                None => string.shrink_to_fit(),
+            }
+        }
+    }
+
+    /// Converts into a `String`; an inline string is copied into a fresh allocation
+    pub fn into_string(self) -> String {
+        match self.repr {
+            Repr::Inline { .. } => self.as_str().to_owned(),
+            Repr::Heap(string) => string,
+        }
+    }
+
+    // Builds an inline string if `string` fits
+    fn try_inline(string: &str) -> Option<Self> {
+        if string.len() > Self::INLINE_CAPACITY {
+            return None;
+        }
+        let mut buf = [0; N];
+        buf[..string.len()].copy_from_slice(string.as_bytes());
+        Some(SsoString {
+            repr: Repr::Inline {
+                len: string.len() as u8,
+                buf,
+            },
+        })
+    }
+}
+
+impl<const N: usize> Default for SsoString<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Deref for SsoString<N> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> AsRef<str> for SsoString<N> {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl<const N: usize> Borrow<str> for SsoString<N> {
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
+impl<const N: usize> From<&str> for SsoString<N> {
+    fn from(string: &str) -> Self {
+        Self::try_inline(string).unwrap_or_else(|| SsoString {
+            repr: Repr::Heap(string.to_owned()),
+        })
+    }
+}
+
+/// Short strings are copied inline and their allocation freed; longer ones keep it
+impl<const N: usize> From<String> for SsoString<N> {
+    fn from(string: String) -> Self {
+        Self::try_inline(&string).unwrap_or(SsoString {
+            repr: Repr::Heap(string),
+        })
+    }
+}
+
+impl<const N: usize> From<SsoString<N>> for String {
+    fn from(string: SsoString<N>) -> Self {
+        string.into_string()
+    }
+}
+
+impl<const N: usize> fmt::Write for SsoString<N> {
+    fn write_str(&mut self, string: &str) -> fmt::Result {
+        self.push_str(string);
+        Ok(())
+    }
+}
+
+impl<const N: usize> fmt::Debug for SsoString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize> fmt::Display for SsoString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self)
+    }
+}
+
+// Comparisons and hashing go by contents, so an inline and a heap string with
+// the same text are equal and hash alike, matching `Borrow<str>`
+impl<const N: usize> PartialEq for SsoString<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<const N: usize> Eq for SsoString<N> {}
+
+impl<const N: usize> PartialEq<str> for SsoString<N> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<const N: usize> PartialEq<&str> for SsoString<N> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<const N: usize> PartialOrd for SsoString<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Ord for SsoString<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl<const N: usize> Hash for SsoString<N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::fmt::Write;
+
+    #[test]
+    fn test_happy_path_short_strings_stay_inline() {
+        let mut string: SsoString = SsoString::from("hello");
+        string.push(',');
+        string.push_str(" world");
+        assert!(string.is_inline());
+        assert_eq!(string, "hello, world");
+        assert_eq!(string.len(), 12);
+        assert_eq!(string.to_uppercase(), "HELLO, WORLD", "Derefs to str");
+    }
+
+    #[test]
+    fn test_happy_path_spills_at_the_inline_boundary() {
+        let mut string = SsoString::<8>::from("1234567");
+        string.push('8');
+        assert!(string.is_inline(), "Exactly N bytes still fit");
+        assert_eq!(string.capacity(), 8);
+
+        string.push('9');
+        assert!(!string.is_inline());
+        assert_eq!(string, "123456789");
+        assert!(string.capacity() >= 9);
+    }
+
+    #[test]
+    fn test_conversions_to_and_from_string() {
+        let short: SsoString<8> = String::from("short").into();
+        assert!(short.is_inline());
+        let long: SsoString<8> = String::from("definitely long").into();
+        assert!(!long.is_inline());
+
+        assert_eq!(String::from(short), "short");
+        assert_eq!(long.into_string(), "definitely long");
+    }
+
+    #[test]
+    fn test_truncate_then_shrink_to_fit_returns_inline() {
+        let mut string = SsoString::<4>::from("abcdef");
+        string.truncate(5);
+        string.shrink_to_fit();
+        assert!(!string.is_inline(), "Five bytes still do not fit");
+
+        string.truncate(3);
+        assert!(
+            !string.is_inline(),
+            "Truncating alone keeps the heap buffer"
+        );
+        string.shrink_to_fit();
+        assert!(string.is_inline());
+        assert_eq!(string, "abc");
+
+        string.truncate(10);
+        assert_eq!(string, "abc");
+        string.clear();
+        assert!(string.is_inline() && string.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "character boundary")]
+    fn test_unhappy_path_truncate_inside_a_character_panics() {
+        SsoString::<8>::from("aé").truncate(2);
+    }
+
+    #[test]
+    fn test_inline_and_heap_strings_compare_and_hash_equal() {
+        let inline = SsoString::<16>::from("same");
+        let mut heap = SsoString::<16>::from("a much longer string that spills");
+        heap.clear();
+        heap.push_str("same");
+        let spilled = {
+            let mut string = SsoString::<2>::from("sa");
+            string.push_str("me");
+            string
+        };
+        assert!(!spilled.is_inline());
+        assert_eq!(inline, heap);
+        assert_eq!(spilled, "same");
+
+        let set: HashSet<SsoString<16>> = [inline, heap].into_iter().collect();
+        assert_eq!(set.len(), 1);
+        assert!(set.contains("same"), "Lookups by &str work through Borrow");
+        let mut sorted: Vec<SsoString<16>> = ["abd", "abc"].map(SsoString::from).into();
+        sorted.sort();
+        assert_eq!(sorted, ["abc", "abd"]);
+    }
+
+    #[test]
+    fn test_write_macro_and_formatting() {
+        let mut string = SsoString::<4>::new();
+        write!(string, "{}-{}", 12, 34).unwrap();
+        assert!(!string.is_inline());
+        assert_eq!(format!("{string} {string:?}"), r#"12-34 "12-34""#);
+    }
+
+    #[test]
+    fn test_edge_case_multibyte_characters_and_zero_capacity() {
+        let mut string = SsoString::<5>::new();
+        string.push('é');
+        string.push('🦀');
+        assert_eq!(string.len(), 6);
+        assert!(!string.is_inline(), "The crab's four bytes did not fit");
+        assert_eq!(string.chars().count(), 2);
+
+        let mut empty = SsoString::<0>::new();
+        assert!(empty.is_inline() && empty.is_empty());
+        empty.push('x');
+        assert!(!empty.is_inline());
+    }
+}
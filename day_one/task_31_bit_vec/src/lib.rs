@@ -0,0 +1,394 @@
+use std::fmt;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A growable vector of bits packed into `u64` words
+///
+/// Bit `i` lives in word `i / 64` at position `i % 64`. Bits past `len` in the
+/// last word are always zero, so counting and comparing can work a word at a time.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct BitVec {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BitVec {
+    /// Creates an empty bit vector
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a bit vector of `len` zero bits
+    pub fn with_len(len: usize) -> Self {
+        BitVec {
+            words: vec![0; len.div_ceil(WORD_BITS)],
+            len,
+        }
+    }
+
+    /// Returns the number of bits
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if there are no bits
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns bit `index`, or `None` if it is out of range
+    pub fn get(&self, index: usize) -> Option<bool> {
+        (index < self.len).then(|| self.words[index / WORD_BITS] & mask(index) != 0)
+    }
+
+    /// Sets bit `index` to `value`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of range.
+    pub fn set(&mut self, index: usize, value: bool) {
+        self.check(index);
+        let word = &mut self.words[index / WORD_BITS];
+        if value {
+            *word |= mask(index);
+        } else {
+            *word &= !mask(index);
+        }
+    }
+
+    /// Flips bit `index` and returns its new value
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of range.
+    pub fn toggle(&mut self, index: usize) -> bool {
+        self.check(index);
+        let word = &mut self.words[index / WORD_BITS];
+        *word ^= mask(index);
+        *word & mask(index) != 0
+    }
+
+    /// Appends a bit
+    pub fn push(&mut self, value: bool) {
+        if self.len.is_multiple_of(WORD_BITS) {
+            self.words.push(0);
+        }
+        self.len += 1;
+        self.set(self.len - 1, value);
+    }
+
+    /// Grows or shrinks to `len` bits, filling new bits with `value`
+    pub fn resize(&mut self, len: usize, value: bool) {
+        let old_len = self.len;
+        self.words.resize(len.div_ceil(WORD_BITS), 0);
+        self.len = len;
+        if len < old_len {
+            self.clear_tail();
+        } else if value {
+            for index in old_len..len {
+                self.set(index, true);
+            }
+        }
+    }
+
+    /// Sets every bit to `value`
+    pub fn fill(&mut self, value: bool) {
+        self.words.fill(if value { u64::MAX } else { 0 });
+        self.clear_tail();
+    }
+
+    /// Returns the number of set bits
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// Returns the number of clear bits
+    pub fn count_zeros(&self) -> usize {
+        self.len - self.count_ones()
+    }
+
+    /// Checks if any bit is set
+    pub fn any(&self) -> bool {
+        self.words.iter().any(|&word| word != 0)
+    }
+
+    /// Returns the index of the first set bit
+    pub fn first_one(&self) -> Option<usize> {
+        self.iter_ones().next()
+    }
+
+    /// Returns the index of the first clear bit
+    pub fn first_zero(&self) -> Option<usize> {
+        self.words
+            .iter()
+            .enumerate()
+            .find(|(_, &word)| word != u64::MAX)
+            .map(|(i, word)| i * WORD_BITS + word.trailing_ones() as usize)
+            .filter(|&index| index < self.len)
+    }
+
+    /// Returns the number of set bits before `index` (the rank of `index`)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than `len`.
+    pub fn rank(&self, index: usize) -> usize {
+        assert!(index <= self.len, "rank index {index} out of range");
+        let (whole, rest) = (index / WORD_BITS, index % WORD_BITS);
+        let full: usize = self.words[..whole]
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum();
+        let partial = if rest == 0 {
+            0
+        } else {
+            (self.words[whole] & (mask(rest) - 1)).count_ones() as usize
+        };
+        full + partial
+    }
+
+    /// Returns the index of the `n`th set bit, counting from zero (select is the inverse of rank)
+    pub fn select(&self, n: usize) -> Option<usize> {
+        let mut remaining = n;
+        for (i, &word) in self.words.iter().enumerate() {
+            let ones = word.count_ones() as usize;
+            if remaining < ones {
+                // Drop the lowest set bits until the wanted one is lowest
+                let mut word = word;
+                for _ in 0..remaining {
+                    word &= word - 1;
+                }
+                return Some(i * WORD_BITS + word.trailing_zeros() as usize);
+            }
+            remaining -= ones;
+        }
+        None
+    }
+
+    /// Iterates over every bit, from index 0
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.len).map(|index| self.words[index / WORD_BITS] & mask(index) != 0)
+    }
+
+    /// Iterates over the indices of the set bits, in increasing order
+    pub fn iter_ones(&self) -> IterOnes<'_> {
+        IterOnes {
+            words: &self.words,
+            index: 0,
+            current: self.words.first().copied().unwrap_or(0),
+        }
+    }
+
+    /// Returns the underlying words, lowest bits first
+    pub fn as_words(&self) -> &[u64] {
+        &self.words
+    }
+
+    fn check(&self, index: usize) {
+        assert!(
+            index < self.len,
+            "bit index {index} out of range for length {}",
+            self.len
+        );
+    }
+
+    // Restores the invariant that bits past `len` are zero
+    fn clear_tail(&mut self) {
+        let rest = self.len % WORD_BITS;
+        if rest != 0 {
+            if let Some(last) = self.words.last_mut() {
+                *last &= mask(rest) - 1;
+            }
+        }
+    }
+
+    // Applies `op` word by word; the shorter operand counts as zero-padded
+    // and the result takes the longer length
+    fn combine(&mut self, other: &BitVec, op: impl Fn(u64, u64) -> u64) {
+        if other.len > self.len {
+            self.resize(other.len, false);
+        }
+        for (i, word) in self.words.iter_mut().enumerate() {
+            *word = op(*word, other.words.get(i).copied().unwrap_or(0));
+        }
+    }
+}
+
+fn mask(index: usize) -> u64 {
+    1 << (index % WORD_BITS)
+}
+
+/// Iterator over the indices of a `BitVec`'s set bits
+pub struct IterOnes<'a> {
+    words: &'a [u64],
+    index: usize,
+    // The unvisited set bits of `words[index]`
+    current: u64,
+}
+
+impl Iterator for IterOnes<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while self.current == 0 {
+            self.index += 1;
+            self.current = *self.words.get(self.index)?;
+        }
+        let bit = self.current.trailing_zeros() as usize;
+        self.current &= self.current - 1;
+        Some(self.index * WORD_BITS + bit)
+    }
+}
+
+impl FromIterator<bool> for BitVec {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut bits = BitVec::new();
+        for bit in iter {
+            bits.push(bit);
+        }
+        bits
+    }
+}
+
+impl fmt::Debug for BitVec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BitVec[")?;
+        for bit in self.iter() {
+            write!(f, "{}", bit as u8)?;
+        }
+        write!(f, "]")
+    }
+}
+
+macro_rules! impl_bit_op {
+    ($op:ident, $method:ident, $assign:ident, $assign_method:ident, $word_op:expr) => {
+        impl $assign<&BitVec> for BitVec {
+            fn $assign_method(&mut self, other: &BitVec) {
+                self.combine(other, $word_op);
+            }
+        }
+
+        impl $op for &BitVec {
+            type Output = BitVec;
+
+            fn $method(self, other: &BitVec) -> BitVec {
+                let mut result = self.clone();
+                result.combine(other, $word_op);
+                result
+            }
+        }
+    };
+}
+
+impl_bit_op!(BitAnd, bitand, BitAndAssign, bitand_assign, |a, b| a & b);
+impl_bit_op!(BitOr, bitor, BitOrAssign, bitor_assign, |a, b| a | b);
+impl_bit_op!(BitXor, bitxor, BitXorAssign, bitxor_assign, |a, b| a ^ b);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bits(pattern: &str) -> BitVec {
+        pattern.chars().map(|c| c == '1').collect()
+    }
+
+    #[test]
+    fn test_happy_path_set_get_toggle() {
+        let mut bits = BitVec::with_len(130);
+        bits.set(0, true);
+        bits.set(64, true);
+        bits.set(129, true);
+        assert_eq!(bits.get(64), Some(true));
+        assert_eq!(bits.get(65), Some(false));
+        assert_eq!(bits.get(130), None);
+
+        assert!(!bits.toggle(64));
+        assert!(bits.toggle(1));
+        assert_eq!(bits.iter_ones().collect::<Vec<_>>(), vec![0, 1, 129]);
+        assert_eq!((bits.count_ones(), bits.count_zeros()), (3, 127));
+    }
+
+    #[test]
+    fn test_first_zero_and_first_one() {
+        let mut bits = BitVec::with_len(200);
+        assert_eq!((bits.first_one(), bits.first_zero()), (None, Some(0)));
+        bits.fill(true);
+        assert_eq!(
+            bits.first_zero(),
+            None,
+            "Padding bits past len do not count"
+        );
+        bits.set(150, false);
+        assert_eq!((bits.first_one(), bits.first_zero()), (Some(0), Some(150)));
+    }
+
+    #[test]
+    fn test_rank_and_select_are_inverse() {
+        let bits: BitVec = (0..300).map(|i| i % 7 == 0 || i % 11 == 0).collect();
+        let ones: Vec<usize> = bits.iter_ones().collect();
+        for (n, &index) in ones.iter().enumerate() {
+            assert_eq!(bits.select(n), Some(index));
+            assert_eq!(bits.rank(index), n);
+        }
+        assert_eq!(bits.select(ones.len()), None);
+        assert_eq!(bits.rank(bits.len()), ones.len());
+    }
+
+    #[test]
+    fn test_bitwise_operations() {
+        let a = bits("1100");
+        let b = bits("1010");
+        assert_eq!(&a & &b, bits("1000"));
+        assert_eq!(&a | &b, bits("1110"));
+        assert_eq!(&a ^ &b, bits("0110"));
+
+        let mut c = a.clone();
+        c ^= &a;
+        assert!(!c.any());
+    }
+
+    #[test]
+    fn test_edge_case_operands_of_different_lengths() {
+        let short = bits("11");
+        let long = bits("10101");
+        assert_eq!(&short | &long, bits("11101"));
+        assert_eq!(&short & &long, bits("10000"));
+        assert_eq!((&long & &short).len(), 5);
+    }
+
+    #[test]
+    fn test_resize_push_and_tail_invariant() {
+        let mut bits = BitVec::new();
+        for i in 0..70 {
+            bits.push(i % 2 == 0);
+        }
+        assert_eq!(bits.count_ones(), 35);
+        bits.resize(3, false);
+        assert_eq!(bits, self::bits("101"));
+        assert_eq!(bits.as_words(), &[0b101]);
+        bits.resize(66, true);
+        assert_eq!(bits.count_ones(), 65);
+        assert_eq!(format!("{:?}", self::bits("0110")), "BitVec[0110]");
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn test_unhappy_path_set_out_of_range_panics() {
+        BitVec::with_len(64).set(64, true);
+    }
+
+    #[test]
+    fn test_edge_case_empty() {
+        let bits = BitVec::new();
+        assert!(bits.is_empty());
+        assert_eq!(bits.iter_ones().next(), None);
+        assert_eq!(
+            (bits.first_zero(), bits.select(0), bits.rank(0)),
+            (None, None, 0)
+        );
+    }
+}
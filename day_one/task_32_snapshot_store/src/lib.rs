@@ -0,0 +1,358 @@
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use task_15_raii_wrapper::file_lock::{FileLock, LockMode};
+use task_15_raii_wrapper::FileWrapper;
+use task_27_page_alloc::{MappedFile, PageAllocator, PrivateMappedFile};
+
+/// A file-backed byte buffer with copy-on-write working state, cheap snapshots
+/// and atomic commits
+///
+/// The committed state is the file on disk, mapped read-only and shared by
+/// every `Snapshot`. Edits go to a private (copy-on-write) mapping of the same
+/// file, so only the pages actually touched are copied and the file never sees
+/// a half-made change. `commit` writes the working copy to a temporary sibling
+/// and renames it over the file; existing snapshots keep mapping the old file.
+///
+/// One store owns a path at a time: `open` takes an exclusive `FileLock` on a
+/// `.lock` sibling and holds it until the store is dropped. Dropping the store
+/// discards uncommitted changes.
+#[derive(Debug)]
+pub struct SnapshotStore {
+    path: PathBuf,
+    committed: Snapshot,
+    working: Working,
+    dirty: bool,
+    _lock: FileLock,
+}
+
+#[derive(Debug)]
+enum Working {
+    // Unchanged pages still come from the page cache
+    Mapped(PrivateMappedFile),
+    // After a resize, or when the committed file is empty (which cannot be mapped)
+    Owned(Vec<u8>),
+}
+
+impl Working {
+    fn as_slice(&self) -> &[u8] {
+        match self {
+            Working::Mapped(map) => map,
+            Working::Owned(bytes) => bytes,
+        }
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [u8] {
+        match self {
+            Working::Mapped(map) => map,
+            Working::Owned(bytes) => bytes,
+        }
+    }
+}
+
+impl SnapshotStore {
+    /// Opens the store at `path`, creating an empty file if there is none
+    ///
+    /// Fails with `WouldBlock` if another store already has `path` open.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let lock =
+            FileLock::try_lock(lock_path(&path)?, LockMode::Exclusive)?.ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::WouldBlock,
+                    "the snapshot store is open elsewhere",
+                )
+            })?;
+        OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)?;
+
+        let (committed, working) = map(&path, 0)?;
+        Ok(SnapshotStore {
+            path,
+            committed,
+            working,
+            dirty: false,
+            _lock: lock,
+        })
+    }
+
+    /// Returns the working state, including uncommitted changes
+    pub fn data(&self) -> &[u8] {
+        self.working.as_slice()
+    }
+
+    /// Returns the working state for editing in place
+    pub fn data_mut(&mut self) -> &mut [u8] {
+        self.dirty = true;
+        self.working.as_mut_slice()
+    }
+
+    /// Copies `bytes` into the working state at `offset`, growing it with zeros if needed
+    pub fn write_at(&mut self, offset: usize, bytes: &[u8]) {
+        let end = offset + bytes.len();
+        if end > self.len() {
+            self.resize(end);
+        }
+        self.data_mut()[offset..end].copy_from_slice(bytes);
+    }
+
+    /// Grows or shrinks the working state to `len` bytes, filling new bytes with zeros
+    ///
+    /// A mapping has a fixed length, so this moves the working state into an
+    /// owned buffer until the next commit.
+    pub fn resize(&mut self, len: usize) {
+        if len == self.len() {
+            return;
+        }
+        if let Working::Mapped(map) = &self.working {
+            self.working = Working::Owned(map.to_vec());
+        }
+        if let Working::Owned(bytes) = &mut self.working {
+            bytes.resize(len, 0);
+        }
+        self.dirty = true;
+    }
+
+    /// Returns the length of the working state in bytes
+    pub fn len(&self) -> usize {
+        self.data().len()
+    }
+
+    /// Checks if the working state is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Checks if there are changes since the last commit
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Returns the path of the backing file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns a read-only view of the last committed state
+    ///
+    /// Snapshots share one mapping and are unaffected by later edits and commits.
+    pub fn snapshot(&self) -> Snapshot {
+        self.committed.clone()
+    }
+
+    /// Persists the working state, replacing the file atomically, and returns a snapshot of it
+    ///
+    /// If writing fails the file is left as it was and the changes stay in
+    /// the working state. Committing a clean store does nothing.
+    pub fn commit(&mut self) -> io::Result<Snapshot> {
+        if !self.dirty {
+            return Ok(self.snapshot());
+        }
+        let mut file = FileWrapper::atomic(&self.path)?;
+        file.write_bytes(self.data())?;
+        file.commit()?;
+
+        let (committed, working) = map(&self.path, self.committed.version + 1)?;
+        self.committed = committed;
+        self.working = working;
+        self.dirty = false;
+        Ok(self.snapshot())
+    }
+
+    /// Throws away uncommitted changes, returning to the last committed state
+    pub fn discard(&mut self) -> io::Result<()> {
+        let (_, working) = map(&self.path, self.committed.version)?;
+        self.working = working;
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+/// A read-only view of a `SnapshotStore`'s state as of one commit
+///
+/// Cloning is cheap: clones share the same mapping.
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    // `None` for an empty file, which cannot be mapped
+    map: Option<Arc<MappedFile>>,
+    version: u64,
+}
+
+impl Snapshot {
+    /// Returns how many commits this store instance had made when the snapshot was taken
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+}
+
+impl Deref for Snapshot {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.map.as_deref().map_or(&[], |map| map)
+    }
+}
+
+fn lock_path(path: &Path) -> io::Result<PathBuf> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Path has no file name"))?;
+    Ok(path.with_file_name(format!(".{}.lock", file_name.to_string_lossy())))
+}
+
+// Maps the current file twice: shared for snapshots, private for editing
+fn map(path: &Path, version: u64) -> io::Result<(Snapshot, Working)> {
+    let file = File::open(path)?;
+    if file.metadata()?.len() == 0 {
+        return Ok((Snapshot { map: None, version }, Working::Owned(Vec::new())));
+    }
+    // SAFETY: the store holds the path's lock and only ever replaces the file
+    // by renaming a new one over it, never writing or truncating it in place
+    let (shared, private) = unsafe {
+        (
+            PageAllocator::map_file(&file)?,
+            PageAllocator::map_file_private(&file)?,
+        )
+    };
+    let snapshot = Snapshot {
+        map: Some(Arc::new(shared)),
+        version,
+    };
+    Ok((snapshot, Working::Mapped(private)))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    // Removes the store's file and lock file when the test ends
+    struct TestPath(PathBuf);
+
+    impl TestPath {
+        fn new(name: &str) -> Self {
+            static COUNTER: AtomicUsize = AtomicUsize::new(0);
+            let unique = COUNTER.fetch_add(1, Ordering::Relaxed);
+            TestPath(std::env::temp_dir().join(format!(
+                "snapshot-store-{name}-{}-{unique}",
+                std::process::id()
+            )))
+        }
+    }
+
+    impl Drop for TestPath {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+            if let Ok(lock) = lock_path(&self.0) {
+                let _ = fs::remove_file(lock);
+            }
+        }
+    }
+
+    #[test]
+    fn test_happy_path_commit_persists_across_reopen() {
+        let path = TestPath::new("reopen");
+        {
+            let mut store = SnapshotStore::open(&path.0).unwrap();
+            assert!(store.is_empty());
+            store.write_at(0, b"hello");
+            assert!(store.is_dirty());
+            store.commit().unwrap();
+            assert!(!store.is_dirty());
+        }
+        let store = SnapshotStore::open(&path.0).unwrap();
+        assert_eq!(store.data(), b"hello");
+        assert_eq!(&*store.snapshot(), b"hello");
+    }
+
+    #[test]
+    fn test_snapshots_are_isolated_from_later_commits() {
+        let path = TestPath::new("isolation");
+        let mut store = SnapshotStore::open(&path.0).unwrap();
+        store.write_at(0, b"first");
+        let first = store.commit().unwrap();
+
+        store.data_mut().copy_from_slice(b"FIRST");
+        let second = store.commit().unwrap();
+        assert_eq!(&*first, b"first", "The old mapping outlives the rename");
+        assert_eq!(&*second, b"FIRST");
+        assert_eq!((first.version(), second.version()), (1, 2));
+    }
+
+    #[test]
+    fn test_uncommitted_changes_are_invisible_to_snapshots_and_the_file() {
+        let path = TestPath::new("cow");
+        let mut store = SnapshotStore::open(&path.0).unwrap();
+        store.write_at(0, b"committed");
+        store.commit().unwrap();
+
+        store.data_mut()[..3].copy_from_slice(b"COM");
+        assert_eq!(store.data(), b"COMmitted");
+        assert_eq!(&*store.snapshot(), b"committed");
+        assert_eq!(fs::read(&path.0).unwrap(), b"committed");
+    }
+
+    #[test]
+    fn test_discard_and_drop_throw_away_changes() {
+        let path = TestPath::new("discard");
+        let mut store = SnapshotStore::open(&path.0).unwrap();
+        store.write_at(0, b"keep");
+        store.commit().unwrap();
+
+        store.write_at(0, b"lose it");
+        store.discard().unwrap();
+        assert_eq!(store.data(), b"keep");
+        assert!(!store.is_dirty());
+
+        store.write_at(0, b"gone");
+        drop(store);
+        assert_eq!(SnapshotStore::open(&path.0).unwrap().data(), b"keep");
+    }
+
+    #[test]
+    fn test_resize_grows_and_shrinks_the_working_state() {
+        let path = TestPath::new("resize");
+        let mut store = SnapshotStore::open(&path.0).unwrap();
+        store.write_at(0, b"abcdef");
+        store.commit().unwrap();
+
+        store.write_at(8, b"xy");
+        assert_eq!(store.data(), b"abcdef\0\0xy");
+        store.resize(3);
+        assert_eq!(store.commit().unwrap().len(), 3);
+        assert_eq!(fs::read(&path.0).unwrap(), b"abc");
+    }
+
+    #[test]
+    fn test_unhappy_path_second_open_would_block() {
+        let path = TestPath::new("locked");
+        let store = SnapshotStore::open(&path.0).unwrap();
+        let error = SnapshotStore::open(&path.0).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::WouldBlock);
+        drop(store);
+        assert!(SnapshotStore::open(&path.0).is_ok());
+    }
+
+    #[test]
+    fn test_edge_case_committing_back_to_empty() {
+        let path = TestPath::new("empty");
+        let mut store = SnapshotStore::open(&path.0).unwrap();
+        let clean = store.commit().unwrap();
+        assert_eq!(clean.version(), 0, "A clean commit writes nothing");
+
+        store.write_at(0, b"data");
+        store.commit().unwrap();
+        store.resize(0);
+        let empty = store.commit().unwrap();
+        assert!(empty.is_empty());
+        assert!(store.is_empty());
+        assert_eq!(fs::metadata(&path.0).unwrap().len(), 0);
+    }
+}
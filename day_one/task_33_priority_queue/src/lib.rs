@@ -0,0 +1,134 @@
+pub use task_20_dary_heap::{DaryHeap, Handle, HeapError};
+
+/// Min-priority queue on a binary heap stored in a `Vec`
+///
+/// This is task 20's `DaryHeap` with two children per node: the children of
+/// index `i` sit at `2i + 1` and `2i + 2`, and every element is no greater
+/// than its children, so the smallest is always at index 0. `push` returns a
+/// `Handle` that `decrease_key` uses to find the element in O(1) before
+/// sifting it up, and `heapify` builds a queue in O(n). Wrap items in
+/// `std::cmp::Reverse` to pop the largest first.
+pub type PriorityQueue<T> = DaryHeap<T, 2>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cmp::Reverse;
+
+    #[test]
+    fn test_happy_path_pops_smallest_first() {
+        let mut queue = PriorityQueue::new();
+        for value in [5, 1, 8, 3, 9, 2, 7] {
+            queue.push(value);
+        }
+        assert_eq!(queue.peek(), Some(&1));
+        assert_eq!(queue.len(), 7);
+        assert_eq!(queue.into_sorted_vec(), vec![1, 2, 3, 5, 7, 8, 9]);
+    }
+
+    #[test]
+    fn test_heapify_matches_pushing_one_by_one() {
+        let values: Vec<u32> = (0..500).map(|i| (i * 7919) % 1013).collect();
+        let mut pushed = PriorityQueue::new();
+        for &value in &values {
+            pushed.push(value);
+        }
+        let mut sorted = values.clone();
+        sorted.sort();
+        assert_eq!(
+            PriorityQueue::from(values.clone()).into_sorted_vec(),
+            sorted
+        );
+        assert_eq!(pushed.into_sorted_vec(), sorted);
+
+        let largest_first: PriorityQueue<_> = values.into_iter().map(Reverse).collect();
+        assert_eq!(
+            largest_first.peek(),
+            Some(&Reverse(sorted[sorted.len() - 1]))
+        );
+    }
+
+    #[test]
+    fn test_decrease_key_through_heapify_handles() {
+        let (mut queue, handles) = PriorityQueue::heapify(vec![(30, "a"), (10, "b"), (20, "c")]);
+        assert_eq!(
+            queue.get(handles[0]),
+            Some(&(30, "a")),
+            "Handles follow input order"
+        );
+
+        queue.decrease_key(handles[0], (5, "a")).unwrap();
+        assert_eq!(queue.pop(), Some((5, "a")));
+        assert!(!queue.contains(handles[0]));
+
+        let d = queue.push((15, "d"));
+        queue.decrease_key(d, (1, "d")).unwrap();
+        assert_eq!(
+            queue.into_sorted_vec(),
+            vec![(1, "d"), (10, "b"), (20, "c")]
+        );
+    }
+
+    #[test]
+    fn test_dijkstra_with_decrease_key() {
+        // 0 -> 1 (4), 0 -> 2 (1), 2 -> 1 (2), 1 -> 3 (1), 2 -> 3 (5)
+        let edges: [&[(usize, u32)]; 4] = [&[(1, 4), (2, 1)], &[(3, 1)], &[(1, 2), (3, 5)], &[]];
+        let mut distance = [u32::MAX; 4];
+        let mut handles = [None; 4];
+        let mut queue = PriorityQueue::new();
+        distance[0] = 0;
+        handles[0] = Some(queue.push((0, 0)));
+        while let Some((dist, node)) = queue.pop() {
+            for &(next, weight) in edges[node] {
+                let candidate = dist + weight;
+                if candidate >= distance[next] {
+                    continue;
+                }
+                distance[next] = candidate;
+                match handles[next] {
+                    Some(handle) if queue.contains(handle) => {
+                        queue.decrease_key(handle, (candidate, next)).unwrap()
+                    }
+                    _ => handles[next] = Some(queue.push((candidate, next))),
+                }
+            }
+        }
+        assert_eq!(distance, [0, 3, 1, 4]);
+    }
+
+    #[test]
+    fn test_unhappy_path_decrease_key_errors() {
+        let mut queue = PriorityQueue::new();
+        let handle = queue.push(10);
+        assert_eq!(queue.decrease_key(handle, 11), Err(HeapError::KeyIncreased));
+        assert_eq!(
+            queue.peek(),
+            Some(&10),
+            "Rejected update leaves the queue unchanged"
+        );
+
+        queue.pop();
+        assert_eq!(queue.decrease_key(handle, 1), Err(HeapError::StaleHandle));
+    }
+
+    #[test]
+    fn test_edge_case_reused_slot_and_empty_queue() {
+        let mut queue = PriorityQueue::default();
+        let old = queue.push(1);
+        queue.pop();
+        let new = queue.push(2);
+        assert_eq!(
+            queue.get(old),
+            None,
+            "The reused slot rejects the old handle"
+        );
+        assert_eq!(queue.get(new), Some(&2));
+
+        queue.clear();
+        assert!(queue.is_empty() && !queue.contains(new));
+        assert_eq!((queue.pop(), queue.peek()), (None, None));
+        let (empty, handles) = PriorityQueue::<i32>::heapify(Vec::new());
+        assert!(empty.is_empty() && handles.is_empty());
+        assert_eq!(format!("{:?}", PriorityQueue::from(vec![2, 1])), "[1, 2]");
+    }
+}
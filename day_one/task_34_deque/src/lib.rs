@@ -0,0 +1,378 @@
+use std::fmt;
+
+/// Capacity of the first allocation, so small deques don't regrow on every push
+const MIN_CAPACITY: usize = 4;
+
+/// Double-ended queue on a growable ring buffer
+///
+/// The elements sit in `buffer` starting at `head` and wrapping past the end,
+/// so both ends can grow and shrink in O(1) without moving anything. When the
+/// buffer is full it is replaced by one twice the size, with the elements
+/// copied across in order and `head` reset to 0.
+#[derive(Clone)]
+pub struct Deque<T> {
+    buffer: Vec<Option<T>>,
+    head: usize,
+    len: usize,
+}
+
+impl<T> Deque<T> {
+    /// Creates an empty deque; nothing is allocated until the first push
+    pub fn new() -> Self {
+        Deque {
+            buffer: Vec::new(),
+            head: 0,
+            len: 0,
+        }
+    }
+
+    /// Creates an empty deque with room for `capacity` elements
+    pub fn with_capacity(capacity: usize) -> Self {
+        Deque {
+            buffer: (0..capacity).map(|_| None).collect(),
+            head: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns the number of elements
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if the deque is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns how many elements fit before the buffer has to grow
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Adds an element to the front
+    pub fn push_front(&mut self, item: T) {
+        self.grow_if_full();
+        self.head = (self.head + self.capacity() - 1) % self.capacity();
+        self.buffer[self.head] = Some(item);
+        self.len += 1;
+    }
+
+    /// Adds an element to the back
+    pub fn push_back(&mut self, item: T) {
+        self.grow_if_full();
+        let tail = self.slot(self.len);
+        self.buffer[tail] = Some(item);
+        self.len += 1;
+    }
+
+    /// Removes and returns the front element
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        let item = self.buffer[self.head].take();
+        self.head = (self.head + 1) % self.capacity();
+        self.len -= 1;
+        item
+    }
+
+    /// Removes and returns the back element
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let tail = self.slot(self.len);
+        self.buffer[tail].take()
+    }
+
+    /// Returns the front element
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Returns the back element
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.len.checked_sub(1)?)
+    }
+
+    /// Returns the element `index` places from the front
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len {
+            return None;
+        }
+        self.buffer[self.slot(index)].as_ref()
+    }
+
+    /// Returns a mutable reference to the element `index` places from the front
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len {
+            return None;
+        }
+        let slot = self.slot(index);
+        self.buffer[slot].as_mut()
+    }
+
+    /// Removes every element, keeping the allocation
+    pub fn clear(&mut self) {
+        while self.pop_back().is_some() {}
+        self.head = 0;
+    }
+
+    /// Iterates from the front to the back; `.rev()` walks back to front
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            deque: self,
+            front: 0,
+            back: self.len,
+        }
+    }
+
+    /// Buffer index of the element `index` places from the front
+    fn slot(&self, index: usize) -> usize {
+        (self.head + index) % self.capacity()
+    }
+
+    fn grow_if_full(&mut self) {
+        if self.len < self.capacity() {
+            return;
+        }
+        let capacity = (self.capacity() * 2).max(MIN_CAPACITY);
+        let mut buffer: Vec<Option<T>> = Vec::with_capacity(capacity);
+        // Unwrap the ring so the elements start at index 0 again
+        for index in 0..self.len {
+            let slot = self.slot(index);
+            buffer.push(self.buffer[slot].take());
+        }
+        buffer.resize_with(capacity, || None);
+        self.buffer = buffer;
+        self.head = 0;
+    }
+}
+
+impl<T> Default for Deque<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Deque<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for Deque<T> {
+    fn eq(&self, other: &Self) -> bool {
+        // Two deques can hold the same elements at different offsets in their buffers
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for Deque<T> {}
+
+impl<T> FromIterator<T> for Deque<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut deque = Deque::new();
+        deque.extend(iter);
+        deque
+    }
+}
+
+impl<T> Extend<T> for Deque<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push_back(item);
+        }
+    }
+}
+
+/// Borrowing iterator over a `Deque`, front to back
+pub struct Iter<'a, T> {
+    deque: &'a Deque<T>,
+    /// Logical index of the next element from the front
+    front: usize,
+    /// One past the logical index of the next element from the back
+    back: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        self.deque.get(self.front - 1)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        self.deque.get(self.back)
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<'a, T> IntoIterator for &'a Deque<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+/// Owning iterator over a `Deque`, front to back
+pub struct IntoIter<T> {
+    deque: Deque<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.deque.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.deque.len, Some(self.deque.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.deque.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for Deque<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { deque: self }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    #[test]
+    fn test_happy_path_both_ends() {
+        let mut deque = Deque::new();
+        deque.push_back(2);
+        deque.push_back(3);
+        deque.push_front(1);
+        assert_eq!((deque.front(), deque.back()), (Some(&1), Some(&3)));
+        assert_eq!(deque.len(), 3);
+
+        assert_eq!(deque.pop_front(), Some(1));
+        assert_eq!(deque.pop_back(), Some(3));
+        assert_eq!(deque.pop_back(), Some(2));
+        assert_eq!(deque.pop_front(), None);
+    }
+
+    #[test]
+    fn test_growing_while_wrapped_keeps_order() {
+        let mut deque = Deque::with_capacity(4);
+        deque.push_back(3);
+        deque.push_back(4);
+        deque.push_front(2);
+        deque.push_front(1); // head has wrapped to the end of the buffer
+        assert_eq!(deque.capacity(), 4);
+        deque.push_back(5);
+        assert_eq!(deque.capacity(), 8);
+        assert_eq!(
+            deque.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5]
+        );
+    }
+
+    #[test]
+    fn test_interleaved_operations_match_vec_deque() {
+        // A fixed pseudo-random sequence of operations, checked at every step
+        let mut deque = Deque::new();
+        let mut model = VecDeque::new();
+        let mut seed: u32 = 11;
+        for step in 0..3000 {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            match (seed >> 16) % 5 {
+                0 => assert_eq!(deque.pop_front(), model.pop_front()),
+                1 => assert_eq!(deque.pop_back(), model.pop_back()),
+                2 => {
+                    deque.push_front(step);
+                    model.push_front(step);
+                }
+                _ => {
+                    deque.push_back(step);
+                    model.push_back(step);
+                }
+            }
+            assert_eq!(deque.len(), model.len());
+            assert_eq!((deque.front(), deque.back()), (model.front(), model.back()));
+        }
+        assert!(deque.iter().eq(model.iter()));
+        assert!(deque.iter().rev().eq(model.iter().rev()));
+    }
+
+    #[test]
+    fn test_iterators_from_both_ends() {
+        let mut deque: Deque<i32> = (1..=5).collect();
+        if let Some(middle) = deque.get_mut(2) {
+            *middle *= 10;
+        }
+        let mut iter = deque.iter();
+        assert_eq!(iter.len(), 5);
+        assert_eq!((iter.next(), iter.next_back()), (Some(&1), Some(&5)));
+        assert_eq!(iter.len(), 3);
+        assert_eq!((&deque).into_iter().nth(2), Some(&30));
+
+        let owned: Vec<_> = deque.clone().into_iter().rev().collect();
+        assert_eq!(owned, vec![5, 4, 30, 2, 1]);
+        assert_eq!(format!("{:?}", deque), "[1, 2, 30, 4, 5]");
+    }
+
+    #[test]
+    fn test_equality_ignores_buffer_layout() {
+        let pushed_back: Deque<_> = (1..=3).collect();
+        let mut pushed_front = Deque::with_capacity(3);
+        for value in (1..=3).rev() {
+            pushed_front.push_front(value);
+        }
+        assert_eq!(pushed_back, pushed_front);
+    }
+
+    #[test]
+    fn test_edge_case_empty_and_cleared() {
+        let mut deque: Deque<String> = Deque::default();
+        assert_eq!(deque.capacity(), 0);
+        assert_eq!(
+            (deque.front(), deque.back(), deque.get(0)),
+            (None, None, None)
+        );
+        assert_eq!(deque.pop_back(), None);
+
+        deque.extend(["a".to_string(), "b".to_string()]);
+        deque.clear();
+        assert!(deque.is_empty());
+        assert!(deque.capacity() >= 2, "Clearing keeps the allocation");
+        assert_eq!(deque.iter().next(), None);
+    }
+}
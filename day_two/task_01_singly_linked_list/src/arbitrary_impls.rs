@@ -0,0 +1,55 @@
+use quickcheck::{Arbitrary, Gen};
+
+use crate::LinkedList;
+
+/// Any linear list, head first; cyclic lists are never generated
+impl<T: Arbitrary> Arbitrary for LinkedList<T> {
+    fn arbitrary(g: &mut Gen) -> Self {
+        LinkedList::from(Vec::arbitrary(g))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let values: Vec<T> = self.iter().cloned().collect();
+        Box::new(values.shrink().map(LinkedList::from))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::quickcheck;
+
+    #[test]
+    fn test_len_matches_iteration_and_no_cycle() {
+        fn prop(list: LinkedList<i32>) -> bool {
+            list.len() == list.iter().count()
+                && !list.has_cycle()
+                && list.is_empty() == list.iter().next().is_none()
+        }
+        quickcheck(prop as fn(LinkedList<i32>) -> bool);
+    }
+
+    #[test]
+    fn test_reverse_twice_is_identity() {
+        fn prop(mut list: LinkedList<i32>) -> bool {
+            let before: Vec<i32> = list.iter().copied().collect();
+            list.reverse();
+            let reversed: Vec<i32> = list.iter().copied().collect();
+            list.reverse();
+            let restored: Vec<i32> = list.iter().copied().collect();
+            reversed.iter().rev().eq(before.iter()) && restored == before
+        }
+        quickcheck(prop as fn(LinkedList<i32>) -> bool);
+    }
+
+    #[test]
+    fn test_get_agrees_with_iter() {
+        fn prop(list: LinkedList<i32>) -> bool {
+            list.iter()
+                .enumerate()
+                .all(|(i, value)| list.get(i) == Some(value))
+                && list.get(list.len()).is_none()
+        }
+        quickcheck(prop as fn(LinkedList<i32>) -> bool);
+    }
+}
@@ -0,0 +1,104 @@
+//! Decodes fuzzer input into list operations and checks them against a `Vec`
+
+use crate::LinkedList;
+
+/// Runs the operations encoded in `data`, panicking if the list misbehaves
+///
+/// Each byte is one operation: push or push_back (with the next byte as the
+/// value), pop or pop_back, get (indexed by the next byte), reverse, clear or a
+/// full comparison. The
+/// model `Vec` keeps the head at index 0. Cycles are never created, because
+/// `create_cycle_at` is unsafe and the model has no way to represent one.
+pub fn fuzz_ops(data: &[u8]) {
+    let mut bytes = data.iter().copied();
+    let mut list = LinkedList::new();
+    let mut model: Vec<u8> = Vec::new();
+
+    while let Some(op) = bytes.next() {
+        match op % 8 {
+            0 | 1 => {
+                let value = bytes.next().unwrap_or(op);
+                list.push(value);
+                model.insert(0, value);
+            }
+            2 => {
+                let expected = if model.is_empty() {
+                    None
+                } else {
+                    Some(model.remove(0))
+                };
+                assert_eq!(list.pop(), expected, "pop");
+            }
+            3 => {
+                let index = bytes.next().unwrap_or(0) as usize;
+                assert_eq!(list.get(index), model.get(index), "get({})", index);
+            }
+            4 => {
+                list.reverse();
+                model.reverse();
+            }
+            6 => {
+                let value = bytes.next().unwrap_or(op);
+                list.push_back(value);
+                model.push(value);
+            }
+            7 => assert_eq!(list.pop_back(), model.pop(), "pop_back"),
+            _ => {
+                if op & 0x80 != 0 {
+                    list.clear();
+                    model.clear();
+                }
+                assert!(list.iter().eq(model.iter()), "iteration order");
+                assert_eq!(list.get_all_values(), model.iter().collect::<Vec<_>>());
+            }
+        }
+        assert_eq!(list.len(), model.len(), "len");
+        assert_eq!(list.is_empty(), model.is_empty(), "is_empty");
+        assert!(
+            !list.has_cycle(),
+            "a list built from safe operations has no cycle"
+        );
+    }
+    assert!(list.iter().eq(model.iter()), "final contents");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_reverse_pop_sequence() {
+        // Push 1, 2, 3, reverse, get index 0, pop, then compare
+        fuzz_ops(&[0, 1, 0, 2, 0, 3, 4, 3, 0, 2, 5]);
+    }
+
+    #[test]
+    fn test_edge_case_empty_and_truncated_input() {
+        fuzz_ops(&[]);
+        fuzz_ops(&[2, 4, 3]); // Pop, reverse and get on an empty list
+        fuzz_ops(&[0]); // Push with its value byte missing
+        fuzz_ops(&[0, 9, 0x85, 5]); // Clear, then compare the empty list
+    }
+
+    #[test]
+    fn test_push_back_after_reverse_and_pop_back() {
+        // Push 1, push_back 2, reverse, push_back 3, pop_back, pop_back, compare
+        fuzz_ops(&[0, 1, 6, 2, 4, 6, 3, 7, 7, 5]);
+    }
+
+    #[test]
+    fn test_pseudo_random_inputs() {
+        let mut state = 0x9e37_79b9_u32;
+        for len in 0..300 {
+            let data: Vec<u8> = (0..len)
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 17;
+                    state ^= state << 5;
+                    state as u8
+                })
+                .collect();
+            fuzz_ops(&data);
+        }
+    }
+}
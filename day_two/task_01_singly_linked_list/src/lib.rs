@@ -0,0 +1,1572 @@
+#![cfg_attr(feature = "safe-only", forbid(unsafe_code))]
+
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::Hash;
+use std::ptr;
+
+#[cfg(feature = "testing")]
+mod arbitrary_impls;
+#[cfg(any(test, feature = "testing"))]
+pub mod fuzz;
+mod rc_list;
+#[cfg(feature = "serde")]
+mod serde_impls;
+mod unrolled;
+
+pub use rc_list::{intersection_node, RcLinkedList};
+pub use unrolled::UnrolledLinkedList;
+
+// Node: Each element in our chain
+// T is a generic type - means it can hold any type of data (i32, String, etc.)
+struct Node<T> {
+    value: T,                           // The actual data we're storing
+    next: Option<Box<Node<T>>>,        // Pointer to next node (None if last node)
+}
+
+// LinkedList: Container that manages our chain of nodes
+pub struct LinkedList<T> {
+    head: Option<Box<Node<T>>>,        // Points to first node (None if empty list)
+    // Last node, so push_back doesn't have to walk (null if empty list)
+    // Under `safe-only` there is no tail and push_back walks instead
+    #[cfg(not(feature = "safe-only"))]
+    tail: *mut Node<T>,
+}
+
+// The raw tail pointer only ever points into nodes the list owns, so sending
+// or sharing the list is exactly as safe as sending or sharing the values
+#[cfg(not(feature = "safe-only"))]
+unsafe impl<T: Send> Send for LinkedList<T> {}
+#[cfg(not(feature = "safe-only"))]
+unsafe impl<T: Sync> Sync for LinkedList<T> {}
+
+/// Where a cycle starts and how long it is, as found by `LinkedList::analyze_cycle`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CycleInfo {
+    pub start: usize,                   // Index of the first node in the cycle
+    pub length: usize,                  // Number of nodes in the cycle
+}
+
+/// Cycle-detection algorithm for `LinkedList::analyze_cycle_with`
+///
+/// Both run in O(n) time and O(1) space. Floyd's moves two pointers every step
+/// and needs a second lap to measure the cycle; Brent's moves one and measures
+/// the cycle while finding it, so it usually follows fewer links.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CycleStrategy {
+    #[default]
+    Floyd,
+    Brent,
+}
+
+impl<T> LinkedList<T> {
+    // Constructor: Creates empty list
+    pub fn new() -> Self {
+        LinkedList {
+            head: None,
+            #[cfg(not(feature = "safe-only"))]
+            tail: ptr::null_mut(),
+        }
+    }
+
+    // Push: Adds new element at the front (most efficient for singly linked list)
+    pub fn push(&mut self, value: T) {
+        let new_node = Box::new(Node {
+            value,                      // Store the new value
+            next: self.head.take(),     // take() moves old head to new node's next
+        });
+        self.head = Some(new_node);     // New node becomes new head
+        // With one or two nodes the tail is the new head or the Box we just moved
+        let second = self.head.as_ref().and_then(|node| node.next.as_ref());
+        if second.is_none_or(|node| node.next.is_none()) {
+            self.reset_tail();
+        }
+    }
+
+    // Push back: Adds new element at the end in O(1) by following the tail pointer
+    // On a cyclic list the new node goes between the last node and the cycle start,
+    // so the cycle stays closed and grows by one
+    #[cfg(not(feature = "safe-only"))]
+    pub fn push_back(&mut self, value: T) {
+        let mut new_node = Box::new(Node { value, next: None });
+        let slot = if self.tail.is_null() {
+            &mut self.head
+        } else {
+            // SAFETY: `tail` points at the last node, which the list owns and nothing
+            // else borrows while we hold `&mut self`
+            let tail = unsafe { &mut *self.tail };
+            new_node.next = tail.next.take();   // None, or the link that closes a cycle
+            &mut tail.next
+        };
+        // Take the pointer from the Box in its final place: moving a Box invalidates
+        // raw pointers made from it earlier
+        self.tail = ptr::addr_of_mut!(**slot.insert(new_node));
+    }
+
+    // Push back: Adds new element at the end, walking there first (no tail pointer
+    // without unsafe code, so this is O(n) under `safe-only`)
+    #[cfg(feature = "safe-only")]
+    pub fn push_back(&mut self, value: T) {
+        let mut slot = &mut self.head;
+        while let Some(node) = slot {
+            slot = &mut node.next;      // Cycles can't be built without unsafe, so this ends
+        }
+        *slot = Some(Box::new(Node { value, next: None }));
+    }
+
+    // Pop: Removes and returns first element
+    // If a cycle loops back to the head, it is broken first, since the head is freed
+    pub fn pop(&mut self) -> Option<T> {
+        if self.head.as_deref().is_some_and(|head| ptr::eq(head, self.cycle_target())) {
+            self.break_cycle();
+        }
+        let value = self.head.take().map(|node| {   // take() removes head, gives us ownership
+            self.head = node.next;      // Second node becomes new head
+            node.value                  // Return the value from removed node
+        });
+        if self.head.as_ref().is_none_or(|node| node.next.is_none()) {
+            self.reset_tail();          // Empty, or the tail's Box just moved into head
+        }
+        value
+    }
+
+    // Pop back: Removes and returns the last element
+    // O(n): a singly linked list has to walk to the node before the tail
+    // Returns None on a cyclic list, where the last node is part of the cycle
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.has_cycle() {
+            return None;
+        }
+        let mut slot = &mut self.head;
+        while slot.as_ref()?.next.is_some() {
+            slot = &mut slot.as_mut()?.next;    // Stop at the link holding the last node
+        }
+        let last = slot.take()?;
+        self.reset_tail();              // The node before it (if any) is the new tail
+        Some(last.value)
+    }
+
+    // Split: Detaches everything from `index` on into a new list, keeping the first `index` values
+    // Returns an empty list if `index` is past the end, or if the list has a cycle
+    // O(n), and no value is cloned or moved, only the link at the split point
+    pub fn split_at(&mut self, index: usize) -> LinkedList<T> {
+        let mut rest = LinkedList::new();
+        if self.has_cycle() {
+            return rest;
+        }
+        let mut slot = &mut self.head;
+        for _ in 0..index {
+            match slot {
+                Some(node) => slot = &mut node.next,
+                None => return rest,    // Index out of bounds
+            }
+        }
+        rest.head = slot.take();        // Cut the link; the old tail now belongs to `rest`
+        rest.reset_tail();
+        self.reset_tail();
+        rest
+    }
+
+    // Append: Moves every node of `other` onto the end of this list, leaving `other` empty
+    // O(1) through the tail pointer; no value is cloned
+    // On a cyclic list `other` goes in before the link that closes the cycle, as with
+    // push_back. If both lists are cyclic nothing is moved
+    #[cfg(not(feature = "safe-only"))]
+    pub fn append(&mut self, other: &mut LinkedList<T>) {
+        if self.has_cycle() && other.has_cycle() {
+            return;
+        }
+        let Some(other_head) = other.head.take() else {
+            return;                     // Nothing to append
+        };
+        let other_tail = std::mem::replace(&mut other.tail, ptr::null_mut());
+        // Moving a Box invalidates raw pointers made from it, so if other's tail is
+        // its head, the pointer is taken again after the move
+        let tail_is_head = ptr::eq(other_tail, &*other_head);
+        let slot = if self.tail.is_null() {
+            &mut self.head
+        } else {
+            // SAFETY: `tail` points at our last node and `other_tail` at other's, both
+            // owned by lists we hold `&mut` to, and the two are distinct nodes
+            let tail = unsafe { &mut *self.tail };
+            if let Some(closing) = tail.next.take() {
+                unsafe { (*other_tail).next = Some(closing) };  // Keep our cycle closed
+            }
+            &mut tail.next
+        };
+        let moved = slot.insert(other_head);
+        self.tail = if tail_is_head { ptr::addr_of_mut!(**moved) } else { other_tail };
+    }
+
+    // Append: Moves every node of `other` onto the end of this list, leaving `other` empty
+    // O(n) under `safe-only`, which has no tail pointer and has to walk to the end
+    #[cfg(feature = "safe-only")]
+    pub fn append(&mut self, other: &mut LinkedList<T>) {
+        let mut slot = &mut self.head;
+        while let Some(node) = slot {
+            slot = &mut node.next;      // Cycles can't be built without unsafe, so this ends
+        }
+        *slot = other.head.take();
+    }
+
+    // Rotate left: Moves the first `k` values to the back, so the value at `k` becomes the head
+    // `k` wraps around the length; O(n), relinking nodes without allocating
+    // A cyclic list is left unchanged, since it has no end to rotate onto
+    pub fn rotate_left(&mut self, k: usize) {
+        if !self.cycle_target().is_null() || self.head.is_none() {
+            return;
+        }
+        let mut rest = self.split_at(k % self.len());
+        rest.append(self);              // The front `k` values go after the rest
+        std::mem::swap(self, &mut rest);
+    }
+
+    // Rotate right: Moves the last `k` values to the front; the mirror of rotate_left
+    pub fn rotate_right(&mut self, k: usize) {
+        if !self.cycle_target().is_null() || self.head.is_none() {
+            return;
+        }
+        let len = self.len();
+        self.rotate_left(len - k % len);
+    }
+
+    // Dedup: Removes consecutive repeated values, keeping the first of each run
+    // Relinks in place; a cyclic list is left unchanged
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        if self.has_cycle() {
+            return;
+        }
+        let mut current = self.head.as_mut();
+        while let Some(node) = current {
+            while node.next.as_ref().is_some_and(|next| next.value == node.value) {
+                if let Some(mut repeat) = node.next.take() {
+                    node.next = repeat.next.take();    // Skip over the repeat and drop it
+                }
+            }
+            current = node.next.as_mut();
+        }
+        self.reset_tail();
+    }
+
+    // Remove duplicates: Keeps only the first occurrence of every value, wherever the
+    // repeats are. O(n) with a HashSet; relinks in place; a cyclic list is left unchanged
+    pub fn remove_duplicates(&mut self)
+    where
+        T: Hash + Eq,
+    {
+        if self.has_cycle() {
+            return;
+        }
+        // Decide first, while the values can still be borrowed, then relink
+        let mut seen = HashSet::new();
+        let keep: Vec<bool> = self.iter().map(|value| seen.insert(value)).collect();
+        let mut slot = &mut self.head;
+        for keep in keep {
+            if keep {
+                match slot {
+                    Some(node) => slot = &mut node.next,
+                    None => break,
+                }
+            } else if let Some(mut duplicate) = slot.take() {
+                *slot = duplicate.next.take();  // Unlink and drop the duplicate
+            }
+        }
+        self.reset_tail();
+    }
+
+    // Retain: Keeps only the values the predicate accepts, unlinking and dropping the rest
+    // in place; a cyclic list is left unchanged
+    pub fn retain(&mut self, mut keep: impl FnMut(&T) -> bool) {
+        self.drain_filter(|value| !keep(value)).for_each(drop);
+    }
+
+    // Drain filter: Lazily unlinks the values the predicate accepts and yields them
+    // Values the iterator hasn't reached when it is dropped stay in the list
+    // A cyclic list is left unchanged and nothing is yielded
+    pub fn drain_filter<F>(&mut self, predicate: F) -> DrainFilter<'_, T, F>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        let cyclic = !self.cycle_target().is_null();
+        DrainFilter {
+            slot: if cyclic { None } else { Some(&mut self.head) },
+            #[cfg(not(feature = "safe-only"))]
+            tail: &mut self.tail,
+            #[cfg(not(feature = "safe-only"))]
+            last_kept: ptr::null_mut(),
+            predicate,
+        }
+    }
+
+    // Check if the list is empty
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    // Get length by walking through entire list (TRAVERSAL)
+    // Modified to handle cycles safely
+    pub fn len(&self) -> usize {
+        if let Some(cycle) = self.analyze_cycle() {
+            // For cyclic lists, we can't compute normal length
+            // Return the distance to the cycle start + cycle length
+            return cycle.start + cycle.length;
+        }
+        
+        let mut count = 0;
+        let mut current = &self.head;   // Start at head
+        while let Some(node) = current {
+            count += 1;
+            current = &node.next;       // Move to next node
+        }
+        count
+    }
+
+    // Remove every node, one at a time so long lists don't drop recursively
+    // A cycle is broken first, otherwise popping would never reach the end
+    pub fn clear(&mut self) {
+        self.break_cycle();
+        while self.pop().is_some() {}
+    }
+
+    /// Unlinks the edge that closes a cycle, making the list linear again
+    /// Returns false if there was no cycle
+    /// The closing edge is the second `Box` made by `create_cycle_at`, which doesn't
+    /// own its node, so it is forgotten rather than dropped
+    pub fn break_cycle(&mut self) -> bool {
+        let Some(CycleInfo { start, length }) = self.analyze_cycle() else {
+            return false;
+        };
+        // The last node sits at start + length - 1; stop there, before following its link
+        let mut last = self.head.as_mut();
+        for _ in 1..start + length {
+            last = last.and_then(|node| node.next.as_mut());
+        }
+        if let Some(link) = last.and_then(|node| node.next.take()) {
+            std::mem::forget(link);     // The cycle start is still owned by its predecessor
+        }
+        true
+    }
+
+    // Iterate over the values from head to tail
+    // Never ends on a cyclic list - use get_all_values() there
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.head.as_deref(),
+        }
+    }
+
+    // Iterate over mutable references to the values from head to tail
+    // Stops at the last node, so a cyclic list never hands out the same value twice
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            last_addr: self.tail_ptr().addr(),
+            next: self.head.as_deref_mut(),
+        }
+    }
+
+    // Contains: Checks if any value equals `value` (SEARCH)
+    pub fn contains(&self, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.position(|candidate| candidate == value).is_some()
+    }
+
+    // Find: Returns the first value matching the predicate
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<&T> {
+        self.search().find(|value| predicate(value))
+    }
+
+    // Position: Returns the index of the first value matching the predicate
+    pub fn position(&self, predicate: impl FnMut(&T) -> bool) -> Option<usize> {
+        self.search().position(predicate)
+    }
+
+    // Walks each node once, stopping after one lap of a cycle so searches always end
+    fn search(&self) -> std::iter::Take<Iter<'_, T>> {
+        self.iter().take(self.len())
+    }
+
+    // Get element at specific index (TRAVERSAL to specific position)
+    pub fn get(&self, index: usize) -> Option<&T> {
+        let mut current = &self.head;
+        for _ in 0..index {             // Walk 'index' steps forward
+            match current {
+                Some(node) => current = &node.next,
+                None => return None,    // Index out of bounds
+            }
+        }
+        current.as_ref().map(|node| &node.value)  // Return reference to value
+    }
+    
+    // Nth from end: 0 is the last value, 1 the one before it, and so on (TWO POINTERS)
+    // The lead pointer starts n nodes ahead, so when it reaches the last node the
+    // trailing one is n from the end - a single pass, without knowing the length
+    // Returns None if n is out of range, or for a cyclic list, which has no end
+    pub fn nth_from_end(&self, n: usize) -> Option<&T> {
+        if !self.cycle_target().is_null() {
+            return None;
+        }
+        let mut lead = self.head.as_deref()?;
+        for _ in 0..n {
+            lead = lead.next.as_deref()?;   // Fewer than n + 1 nodes
+        }
+        let mut trail = self.head.as_deref()?;
+        while let Some(next) = lead.next.as_deref() {
+            lead = next;
+            trail = trail.next.as_deref()?;
+        }
+        Some(&trail.value)
+    }
+
+    // Middle: The value at index len / 2, so the second of the two middles of an
+    // even-length list (TWO POINTERS, like has_cycle)
+    // The hare moves two nodes for each of the tortoise's one; when it runs out
+    // the tortoise is halfway. Returns None if empty, or if they meet in a cycle
+    pub fn middle(&self) -> Option<&T> {
+        let mut slow = self.head.as_deref()?;
+        let mut fast = self.head.as_deref()?;
+        while let Some(next) = fast.next.as_deref() {
+            slow = slow.next.as_deref()?;
+            match next.next.as_deref() {
+                Some(after) => fast = after,
+                None => break,
+            }
+            if ptr::eq(slow, fast) {
+                return None;            // Looped round: a cyclic list has no middle
+            }
+        }
+        Some(&slow.value)
+    }
+
+    // Reverse the linked list in-place
+    // A cyclic list is left unchanged: it has no end to become the new head
+    pub fn reverse(&mut self) {
+        if !self.cycle_target().is_null() {
+            return;
+        }
+        let mut prev = None;                    // Previous node (starts as None)
+        let mut current = self.head.take();     // Current node (starts as head)
+        
+        while let Some(mut node) = current {
+            let next = node.next.take();        // Save the next node
+            node.next = prev;                   // Reverse the pointer
+            prev = Some(node);                  // Move prev forward
+            current = next;                     // Move current forward
+        }
+        
+        self.head = prev;                       // The last node becomes new head
+        self.reset_tail();                      // And the old head is the new tail
+    }
+
+    // Point tail at the last node again after relinking or moving its Box
+    // O(n), so only called where the walk is short or the operation is O(n) anyway
+    // Never called on a cyclic list, where it would not end
+    #[cfg(not(feature = "safe-only"))]
+    fn reset_tail(&mut self) {
+        self.tail = ptr::null_mut();
+        let mut current = self.head.as_mut();
+        while let Some(node) = current {
+            self.tail = ptr::addr_of_mut!(**node);  // Raw pointer straight from the Box
+            current = node.next.as_mut();
+        }
+    }
+
+    #[cfg(feature = "safe-only")]
+    fn reset_tail(&mut self) {}
+
+    // The last node, for comparing addresses only (null if empty, or under `safe-only`)
+    #[cfg(not(feature = "safe-only"))]
+    fn tail_ptr(&self) -> *const Node<T> {
+        self.tail
+    }
+
+    #[cfg(feature = "safe-only")]
+    fn tail_ptr(&self) -> *const Node<T> {
+        ptr::null()
+    }
+
+    // The node a cycle's closing link points back to, or null for a linear list
+    // O(1): only the last node of a cyclic list has a next link
+    #[cfg(not(feature = "safe-only"))]
+    fn cycle_target(&self) -> *const Node<T> {
+        if self.tail.is_null() {
+            return ptr::null();
+        }
+        // SAFETY: `tail` points at the last node, which the list owns
+        let last = unsafe { &*self.tail };
+        last.next.as_deref().map_or(ptr::null(), |node| node)
+    }
+
+    #[cfg(feature = "safe-only")]
+    fn cycle_target(&self) -> *const Node<T> {
+        ptr::null()                     // Cycles can't be built without unsafe
+    }
+    
+    /// Floyd's Cycle Detection Algorithm (Tortoise and Hare)
+    /// Returns true if a cycle exists in the linked list
+    /// Time Complexity: O(n), Space Complexity: O(1)
+    pub fn has_cycle(&self) -> bool {
+        self.floyd_meeting_point().is_some()
+    }
+
+    /// Find the start of the cycle if one exists
+    /// Returns the index of the node where the cycle begins
+    /// Time Complexity: O(n), Space Complexity: O(1)
+    pub fn find_cycle_start(&self) -> Option<usize> {
+        self.analyze_cycle().map(|info| info.start)
+    }
+
+    /// Get the length of the cycle (if one exists)
+    /// Time Complexity: O(n), Space Complexity: O(1)
+    pub fn cycle_length(&self) -> Option<usize> {
+        self.analyze_cycle().map(|info| info.length)
+    }
+
+    /// Finds where the cycle starts and how long it is in one pass, using Floyd's algorithm
+    /// Returns None for a linear list
+    pub fn analyze_cycle(&self) -> Option<CycleInfo> {
+        self.analyze_cycle_with(CycleStrategy::Floyd)
+    }
+
+    /// Same as `analyze_cycle`, with the detection algorithm chosen by `strategy`
+    /// Both find the same cycle; see `CycleStrategy` for how they differ
+    pub fn analyze_cycle_with(&self, strategy: CycleStrategy) -> Option<CycleInfo> {
+        let length = match strategy {
+            CycleStrategy::Floyd => {
+                // Walk once round the cycle from the meeting point to measure it
+                let meeting = self.floyd_meeting_point()?;
+                let mut current = meeting.next.as_deref()?;
+                let mut length = 1;
+                while !ptr::eq(current, meeting) {
+                    current = current.next.as_deref()?;
+                    length += 1;
+                }
+                length
+            }
+            CycleStrategy::Brent => self.brent_cycle_length()?,
+        };
+
+        // A lead pointer `length` nodes ahead meets the trailing one at the cycle start
+        let mut trail = self.head.as_deref()?;
+        let mut lead = trail;
+        for _ in 0..length {
+            lead = lead.next.as_deref()?;
+        }
+        let mut start = 0;
+        while !ptr::eq(trail, lead) {
+            trail = trail.next.as_deref()?;
+            lead = lead.next.as_deref()?;
+            start += 1;
+        }
+        Some(CycleInfo { start, length })
+    }
+
+    // The node where the tortoise (1 step) and the hare (2 steps) meet, if there is a cycle
+    fn floyd_meeting_point(&self) -> Option<&Node<T>> {
+        let mut slow = self.head.as_deref()?;
+        let mut fast = slow;
+        loop {
+            slow = slow.next.as_deref()?;
+            fast = fast.next.as_deref()?.next.as_deref()?;
+            // Compare addresses: the same node, not just an equal value
+            if ptr::eq(slow, fast) {
+                return Some(slow);
+            }
+        }
+    }
+
+    // Brent's algorithm: the hare runs ahead in laps of doubling length while the
+    // tortoise waits at the start of each lap. Once the lap is long enough to go
+    // round the cycle, the hare comes back to the tortoise and the lap count so far
+    // is the cycle length, without a second walk round the cycle
+    fn brent_cycle_length(&self) -> Option<usize> {
+        let mut tortoise = self.head.as_deref()?;
+        let mut hare = tortoise.next.as_deref()?;
+        let mut lap = 1;
+        let mut length = 1;
+        while !ptr::eq(tortoise, hare) {
+            if length == lap {
+                tortoise = hare;
+                lap *= 2;
+                length = 0;
+            }
+            hare = hare.next.as_deref()?;
+            length += 1;
+        }
+        Some(length)
+    }
+
+    /// Get all values in the list (safe for both cyclic and linear lists)
+    /// For cyclic lists, stops after visiting each unique node once
+    pub fn get_all_values(&self) -> Vec<&T> {
+        let mut values = Vec::new();
+        let mut current = self.head.as_ref();
+        let mut visited_count = 0;
+        let max_nodes = match self.analyze_cycle() {
+            // For cyclic lists, visit at most the distance to cycle + cycle length
+            Some(cycle) => cycle.start + cycle.length,
+            None => usize::MAX,  // No limit for linear lists
+        };
+
+        while let Some(node) = current {
+            if visited_count >= max_nodes {
+                break;  // Prevent infinite loop in cycles
+            }
+            
+            values.push(&node.value);
+            current = node.next.as_ref();
+            visited_count += 1;
+        }
+
+        values
+    }
+
+    /// Display the structure of the list (including cycle information)
+    pub fn describe_structure(&self) -> String 
+    where 
+        T: std::fmt::Display
+    {
+        if self.is_empty() {
+            return "Empty list".to_string();
+        }
+
+        match self.analyze_cycle() {
+            Some(CycleInfo { start, length }) => format!(
+                "Cyclic list: {} nodes before cycle, cycle of length {} starting at index {}",
+                start, length, start
+            ),
+            None => format!("Linear list with {} nodes", self.len()),
+        }
+    }
+}
+
+// Iter: Borrows each node in turn, following the next pointers
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.next.map(|node| {
+            self.next = node.next.as_deref();   // Step to the following node
+            &node.value
+        })
+    }
+}
+
+impl<'a, T> IntoIterator for &'a LinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+// IterMut: Same walk as Iter, handing out each value mutably
+pub struct IterMut<'a, T> {
+    next: Option<&'a mut Node<T>>,
+    last_addr: usize,                   // Where to stop instead of following a cycle back
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        self.next.take().map(|node| {
+            if (&*node as *const Node<T>).addr() != self.last_addr {
+                self.next = node.next.as_deref_mut();   // Split the node: next pointer and value
+            }
+            &mut node.value
+        })
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut LinkedList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+// DrainFilter: Walks the links like pop_back does, unlinking matches as it goes
+pub struct DrainFilter<'a, T, F> {
+    slot: Option<&'a mut Option<Box<Node<T>>>>,   // The link to the next node to test
+    #[cfg(not(feature = "safe-only"))]
+    tail: &'a mut *mut Node<T>,         // The list's tail, in case the last node goes
+    #[cfg(not(feature = "safe-only"))]
+    last_kept: *mut Node<T>,            // The tail if everything after it is removed
+    predicate: F,
+}
+
+impl<T, F: FnMut(&mut T) -> bool> Iterator for DrainFilter<'_, T, F> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        loop {
+            let slot = self.slot.take()?;
+            let remove = match slot.as_mut() {
+                Some(node) => (self.predicate)(&mut node.value),
+                None => return None,    // Reached the end
+            };
+            if remove {
+                let mut removed = slot.take()?;
+                *slot = removed.next.take();    // Relink around the removed node
+                #[cfg(not(feature = "safe-only"))]
+                match slot.as_mut() {
+                    // Moving the tail's Box invalidates the old pointer, so take it again
+                    Some(node) if node.next.is_none() => *self.tail = ptr::addr_of_mut!(**node),
+                    Some(_) => {}
+                    None => *self.tail = self.last_kept,
+                }
+                self.slot = Some(slot);
+                return Some(removed.value);
+            }
+            let node = slot.as_mut()?;
+            #[cfg(not(feature = "safe-only"))]
+            {
+                self.last_kept = ptr::addr_of_mut!(**node);
+            }
+            self.slot = Some(&mut node.next);
+        }
+    }
+}
+
+// IntoIter: Owns the list and pops values off the front
+pub struct IntoIter<T> {
+    list: LinkedList<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.list.pop()
+    }
+}
+
+impl<T> IntoIterator for LinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { list: self }
+    }
+}
+
+// Clone: Copies the values in order (a cyclic list comes out linear, each node once)
+impl<T: Clone> Clone for LinkedList<T> {
+    fn clone(&self) -> Self {
+        self.get_all_values().into_iter().cloned().collect()
+    }
+}
+
+// From<Vec>: The first element of the Vec becomes the head
+impl<T> From<Vec<T>> for LinkedList<T> {
+    fn from(values: Vec<T>) -> Self {
+        let mut list = LinkedList::new();
+        for value in values.into_iter().rev() {
+            list.push(value);           // Build from the tail so the order is kept
+        }
+        list
+    }
+}
+
+// Into<Vec>: Values from head to tail (a cyclic list comes out linear, each node once)
+impl<T> From<LinkedList<T>> for Vec<T> {
+    fn from(list: LinkedList<T>) -> Self {
+        list.into_iter().collect()      // Popping breaks a cycle once it reaches it
+    }
+}
+
+// FromIterator: Keeps the iterator's order, in O(n) with or without a tail pointer
+impl<T> FromIterator<T> for LinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        LinkedList::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+// Extend: Adds the values at the back, in order
+impl<T> Extend<T> for LinkedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.append(&mut iter.into_iter().collect());
+    }
+}
+
+// Debug: Prints the values from head to tail, safe for cyclic lists
+impl<T: fmt::Debug> fmt::Debug for LinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.get_all_values()).finish()
+    }
+}
+
+// PartialEq: Same values node for node and the same shape: equal lists either both
+// end, or both loop back to the same index. Each node is compared once, so this
+// terminates for cyclic lists too
+impl<T: PartialEq> PartialEq for LinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.find_cycle_start() == other.find_cycle_start()
+            && self.len() == other.len()
+            && self.search().eq(other.search())
+    }
+}
+
+impl<T: Eq> Eq for LinkedList<T> {}
+
+impl<T> Default for LinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Drop: The compiler's drop would recurse once per node (overflowing the stack on
+// long lists) and free a cycle's start twice, so break any cycle and pop instead
+impl<T> Drop for LinkedList<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+// Gated out under `safe-only`: there is no way to build a cycle out of `Box`es
+// without unsafe code
+#[cfg(not(feature = "safe-only"))]
+impl<T> LinkedList<T> {
+    /// Creates a cycle by connecting the last node to the node at cycle_start_index
+    /// WARNING: This is unsafe and creates memory management issues!
+    /// Only use for testing cycle detection algorithms
+    ///
+    /// # Safety
+    ///
+    /// The last node ends up holding a second `Box` to a node that is already owned.
+    /// Nothing may take that `Box` out of the list except `break_cycle`. The list's own
+    /// methods respect this: `clear`, `Drop` and `pop` (when the cycle starts at the
+    /// head) break the cycle first, `iter_mut` stops at the last node, and `reverse`,
+    /// `pop_back`, `split_at` and the dedup methods leave a cyclic list unchanged.
+    pub unsafe fn create_cycle_at(&mut self, cycle_start_index: usize) -> bool {
+        if self.head.is_none() {
+            return false;
+        }
+
+        // We need to collect the addresses of nodes as we traverse
+        let mut nodes: Vec<*mut Node<T>> = Vec::new();
+        let mut current = self.head.as_mut();
+
+        // Collect all node pointers
+        while let Some(node) = current {
+            // Raw pointer straight from the Box, so walking on through `next` keeps it valid
+            let node_ptr = ptr::addr_of_mut!(**node);
+            nodes.push(node_ptr);
+            current = node.next.as_mut();
+        }
+
+        // Check if cycle_start_index is valid
+        if cycle_start_index >= nodes.len() {
+            return false;
+        }
+
+        // Get pointers to the last node and cycle start node
+        let last_node_ptr = nodes[nodes.len() - 1];
+        let cycle_start_ptr = nodes[cycle_start_index];
+
+        // Create the cycle
+        let last_node = &mut *last_node_ptr;
+        
+        // DANGER ZONE: We're creating a non-owning pointer to an existing node
+        // This violates Rust's ownership rules and is only for testing
+        // We create a "fake" Box that doesn't actually own the memory
+        
+        // Method 1: Use a raw pointer wrapped in NonNull (safer but still unsafe)
+        use std::ptr::NonNull;
+        let fake_box = {
+            let non_null = NonNull::new(cycle_start_ptr).unwrap();
+            // This is extremely dangerous - we're telling Rust this Box owns memory it doesn't
+            Box::from_raw(non_null.as_ptr())
+        };
+        
+        last_node.next = Some(fake_box);
+        
+        true
+    }
+}
+
+// Display trait: Makes our list printable (TRAVERSAL for printing)
+// Note: This will NOT work correctly if there's a cycle (infinite loop)
+// Use with caution or modify to detect cycles
+impl<T: fmt::Display> fmt::Display for LinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(cycle) = self.analyze_cycle() {
+            return write!(f, "[Cyclic list: cycle starts at index {}, length {}]", 
+                         cycle.start, cycle.length);
+        }
+
+        let mut current = &self.head;
+        write!(f, "[")?;
+        while let Some(node) = current {
+            write!(f, "{}", node.value)?;
+            current = &node.next;
+            if current.is_some() {
+                write!(f, " -> ")?;
+            }
+        }
+        write!(f, "]")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_basic_operations() {
+        let mut list = LinkedList::new();
+        
+        // Test empty list
+        assert!(list.is_empty());
+        assert_eq!(list.len(), 0);
+        assert_eq!(list.pop(), None);
+        assert_eq!(list.get(0), None);
+        
+        // Test push and basic operations
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        
+        assert!(!list.is_empty());
+        assert_eq!(list.len(), 3);
+        assert_eq!(format!("{}", list), "[3 -> 2 -> 1]");
+        
+        // Test get
+        assert_eq!(list.get(0), Some(&3));
+        assert_eq!(list.get(1), Some(&2));
+        assert_eq!(list.get(2), Some(&1));
+        assert_eq!(list.get(3), None);
+        
+        // Test pop
+        assert_eq!(list.pop(), Some(3));
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.pop(), Some(2));
+        assert_eq!(list.pop(), Some(1));
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_reverse_operation() {
+        let mut list = LinkedList::new();
+        
+        // Test reverse empty list
+        list.reverse();
+        assert!(list.is_empty());
+        
+        // Test reverse single element
+        list.push(42);
+        list.reverse();
+        assert_eq!(format!("{}", list), "[42]");
+        
+        // Test reverse multiple elements
+        list = LinkedList::new();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        
+        let original = format!("{}", list);
+        list.reverse();
+        assert_eq!(format!("{}", list), "[1 -> 2 -> 3]");
+        
+        list.reverse();
+        assert_eq!(format!("{}", list), original);
+    }
+
+    #[test]
+    fn test_cycle_detection_linear_lists() {
+        // Empty list
+        let empty: LinkedList<i32> = LinkedList::new();
+        assert!(!empty.has_cycle());
+        assert_eq!(empty.find_cycle_start(), None);
+        assert_eq!(empty.cycle_length(), None);
+        
+        // Single node
+        let mut single = LinkedList::new();
+        single.push(1);
+        assert!(!single.has_cycle());
+        assert_eq!(single.find_cycle_start(), None);
+        assert_eq!(single.cycle_length(), None);
+        
+        // Multiple nodes
+        let mut multi = LinkedList::new();
+        for i in 1..=5 {
+            multi.push(i);
+        }
+        assert!(!multi.has_cycle());
+        assert_eq!(multi.find_cycle_start(), None);
+        assert_eq!(multi.cycle_length(), None);
+    }
+
+    #[test]
+    fn test_get_all_values() {
+        let mut list = LinkedList::new();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        
+        let values = list.get_all_values();
+        assert_eq!(values, vec![&3, &2, &1]);
+    }
+
+    #[test]
+    fn test_describe_structure() {
+        let mut list = LinkedList::new();
+        assert_eq!(list.describe_structure(), "Empty list");
+        
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        assert_eq!(list.describe_structure(), "Linear list with 3 nodes");
+    }
+
+    #[test]
+    fn test_comprehensive_workflow() {
+        let mut list = LinkedList::new();
+        
+        // Build list
+        for i in 1..=10 {
+            list.push(i);
+        }
+        
+        // Test all methods work together
+        assert_eq!(list.len(), 10);
+        assert!(!list.is_empty());
+        assert!(!list.has_cycle());
+        assert_eq!(list.get(0), Some(&10));
+        assert_eq!(list.get(9), Some(&1));
+        
+        // Reverse and test again
+        list.reverse();
+        assert_eq!(list.get(0), Some(&1));
+        assert_eq!(list.get(9), Some(&10));
+        assert!(!list.has_cycle());
+        
+        // Pop some elements
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(list.pop(), Some(2));
+        assert_eq!(list.len(), 8);
+        assert!(!list.has_cycle());
+    }
+
+    #[test]
+    fn test_iter_and_clear() {
+        let mut list = LinkedList::new();
+        for i in 1..=3 {
+            list.push(i);
+        }
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&3, &2, &1]);
+        assert_eq!((&list).into_iter().sum::<i32>(), 6);
+
+        list.clear();
+        assert!(list.is_empty());
+        assert_eq!(list.iter().next(), None);
+    }
+
+    #[test]
+    fn test_push_back_and_pop_back() {
+        let mut list = LinkedList::new();
+        list.push_back(2);              // First node is head and tail
+        list.push_back(3);
+        list.push(1);
+        list.push_back(4);
+        assert_eq!(format!("{}", list), "[1 -> 2 -> 3 -> 4]");
+
+        assert_eq!(list.pop_back(), Some(4));
+        list.push_back(5);              // Tail moved back to 3 by pop_back
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 5]);
+
+        list.reverse();
+        list.push_back(0);              // Reverse made the old head the tail
+        assert_eq!(format!("{}", list), "[5 -> 3 -> 2 -> 1 -> 0]");
+
+        while list.pop().is_some() {}
+        list.push_back(9);              // Emptying by pop resets the tail
+        assert_eq!(list.pop_back(), Some(9));
+        assert_eq!(list.pop_back(), None);
+        assert!(list.is_empty());
+    }
+
+    #[cfg(not(feature = "safe-only"))]
+    #[test]
+    fn test_push_back_keeps_a_cycle_closed() {
+        let mut list = LinkedList::new();
+        for i in (1..=4).rev() {
+            list.push(i);
+        }
+        unsafe { list.create_cycle_at(1) };     // Only looked at, then broken by drop
+        assert_eq!(list.cycle_length(), Some(3));
+
+        list.push_back(5);              // Lands between 4 and the cycle start
+        assert!(list.has_cycle());
+        assert_eq!(list.find_cycle_start(), Some(1));
+        assert_eq!(list.cycle_length(), Some(4));
+        assert_eq!(list.get_all_values(), vec![&1, &2, &3, &4, &5]);
+        assert_eq!(list.pop_back(), None, "No last node to pop in a cycle");
+    }
+
+    #[cfg(not(feature = "safe-only"))]
+    #[test]
+    fn test_break_cycle_and_drop_tear_down_cyclic_lists() {
+        let token = std::rc::Rc::new(());
+        let mut list = LinkedList::new();
+        for _ in 0..5 {
+            list.push(std::rc::Rc::clone(&token));
+        }
+        unsafe { list.create_cycle_at(2) };
+        assert!(list.break_cycle());
+        assert!(!list.has_cycle());
+        assert!(!list.break_cycle(), "Nothing left to break");
+        assert_eq!(list.len(), 5);
+
+        unsafe { list.create_cycle_at(0) };     // Cycle through the head this time
+        drop(list);
+        assert_eq!(std::rc::Rc::strong_count(&token), 1, "Every node freed once");
+
+        let mut self_loop = LinkedList::new();
+        self_loop.push(1);
+        unsafe { self_loop.create_cycle_at(0) };
+        self_loop.clear();
+        assert!(self_loop.is_empty());
+    }
+
+    #[test]
+    fn test_split_at_and_append() {
+        let mut list = LinkedList::new();
+        for i in (1..=5).rev() {
+            list.push(i);
+        }
+        let mut rest = list.split_at(2);
+        assert_eq!(list.get_all_values(), vec![&1, &2]);
+        assert_eq!(rest.get_all_values(), vec![&3, &4, &5]);
+
+        // Both halves keep a correct tail
+        list.push_back(10);
+        rest.push_back(6);
+        assert_eq!(list.get_all_values(), vec![&1, &2, &10]);
+        assert_eq!(rest.get_all_values(), vec![&3, &4, &5, &6]);
+
+        list.append(&mut rest);
+        assert!(rest.is_empty());
+        assert_eq!(list.get_all_values(), vec![&1, &2, &10, &3, &4, &5, &6]);
+        list.push_back(7);
+        assert_eq!(list.pop_back(), Some(7));
+        assert_eq!(list.len(), 7);
+    }
+
+    #[test]
+    fn test_edge_case_split_and_append_at_the_ends() {
+        let mut list = LinkedList::new();
+        list.push(2);
+        list.push(1);
+        assert!(list.split_at(3).is_empty(), "Past the end");
+        assert!(list.split_at(2).is_empty(), "Nothing after the last value");
+        assert_eq!(list.len(), 2);
+
+        let mut everything = list.split_at(0);
+        assert!(list.is_empty());
+        assert_eq!(everything.get_all_values(), vec![&1, &2]);
+
+        // Appending onto an empty list, and appending a single node
+        list.append(&mut everything);
+        let mut single = LinkedList::new();
+        single.push(3);
+        list.append(&mut single);
+        list.append(&mut LinkedList::new());
+        list.push_back(4);
+        assert_eq!(list.get_all_values(), vec![&1, &2, &3, &4]);
+    }
+
+    #[cfg(not(feature = "safe-only"))]
+    #[test]
+    fn test_append_keeps_a_cycle_closed() {
+        let mut list = LinkedList::new();
+        for i in (1..=3).rev() {
+            list.push(i);
+        }
+        unsafe { list.create_cycle_at(1) };
+        assert!(list.split_at(1).is_empty(), "Cyclic lists are not split");
+
+        let mut other = LinkedList::new();
+        other.push(5);
+        other.push(4);
+        list.append(&mut other);
+        assert!(other.is_empty());
+        assert_eq!(list.find_cycle_start(), Some(1));
+        assert_eq!(list.cycle_length(), Some(4));
+        assert_eq!(list.get_all_values(), vec![&1, &2, &3, &4, &5]);
+    }
+
+    #[test]
+    fn test_contains_find_and_position() {
+        let mut list = LinkedList::new();
+        for word in ["pear", "apple", "fig", "apple"].into_iter().rev() {
+            list.push(word.to_string());
+        }
+        assert!(list.contains(&"fig".to_string()));
+        assert!(!list.contains(&"kiwi".to_string()));
+        assert_eq!(list.find(|word| word.len() == 5), Some(&"apple".to_string()));
+        assert_eq!(list.position(|word| word == "apple"), Some(1), "The first match wins");
+        assert_eq!(list.position(|word| word.starts_with('z')), None);
+
+        let empty: LinkedList<i32> = LinkedList::new();
+        assert!(!empty.contains(&0));
+        assert_eq!(empty.find(|_| true), None);
+    }
+
+    #[cfg(not(feature = "safe-only"))]
+    #[test]
+    fn test_edge_case_search_ends_on_a_cyclic_list() {
+        let mut list = LinkedList::new();
+        for i in (1..=4).rev() {
+            list.push(i);
+        }
+        unsafe { list.create_cycle_at(1) };
+        assert!(list.contains(&4));
+        assert!(!list.contains(&9), "Stops after one lap of the cycle");
+        assert_eq!(list.position(|&value| value == 3), Some(2));
+        assert_eq!(list.find(|&value| value > 10), None);
+    }
+
+    #[test]
+    fn test_dedup_and_remove_duplicates() {
+        let build = |values: &[i32]| {
+            let mut list = LinkedList::new();
+            for &value in values.iter().rev() {
+                list.push(value);
+            }
+            list
+        };
+        let mut list = build(&[1, 1, 2, 3, 3, 3, 1, 2, 2]);
+        list.dedup();
+        assert_eq!(list.get_all_values(), vec![&1, &2, &3, &1, &2]);
+        list.remove_duplicates();
+        assert_eq!(list.get_all_values(), vec![&1, &2, &3]);
+
+        // The last node was removed both times, so the tail had to move
+        list.push_back(4);
+        assert_eq!(list.get_all_values(), vec![&1, &2, &3, &4]);
+
+        let mut words = LinkedList::new();
+        for word in ["b", "a", "b", "c", "a"] {
+            words.push(word.to_string());
+        }
+        words.remove_duplicates();
+        assert_eq!(words.len(), 3);
+        assert_eq!(words.get_all_values(), vec!["a", "c", "b"], "First occurrences win");
+    }
+
+    #[test]
+    fn test_edge_case_dedup_empty_single_and_all_equal() {
+        let mut empty: LinkedList<i32> = LinkedList::new();
+        empty.dedup();
+        empty.remove_duplicates();
+        assert!(empty.is_empty());
+
+        let mut same = LinkedList::new();
+        for _ in 0..4 {
+            same.push(7);
+        }
+        same.dedup();
+        assert_eq!(same.get_all_values(), vec![&7]);
+        same.push_back(8);
+        same.remove_duplicates();
+        assert_eq!(same.get_all_values(), vec![&7, &8]);
+    }
+
+    #[cfg(not(feature = "safe-only"))]
+    #[test]
+    fn test_unhappy_path_dedup_leaves_a_cyclic_list_alone() {
+        let mut list = LinkedList::new();
+        for value in [2, 2, 1, 1] {
+            list.push(value);
+        }
+        unsafe { list.create_cycle_at(2) };
+        list.dedup();
+        list.remove_duplicates();
+        assert_eq!(list.get_all_values(), vec![&1, &1, &2, &2]);
+        assert_eq!(list.cycle_length(), Some(2));
+    }
+
+    #[test]
+    fn test_equality_compares_values_in_order() {
+        let mut a = LinkedList::new();
+        let mut b = LinkedList::new();
+        assert_eq!(a, b, "Empty lists are equal");
+        for i in 1..=3 {
+            a.push(i);
+            b.push_back(4 - i);
+        }
+        assert_eq!(a, b);
+        assert_eq!(a.clone(), b);
+
+        b.push_back(0);
+        assert_ne!(a, b, "A longer list is not equal");
+        a.push_back(9);
+        assert_ne!(a, b, "Same length, different last value");
+    }
+
+    #[cfg(not(feature = "safe-only"))]
+    #[test]
+    fn test_equality_terminates_and_compares_cycle_structure() {
+        let build = |cycle_at| {
+            let mut list = LinkedList::new();
+            for value in [4, 3, 2, 1] {
+                list.push(value);
+            }
+            unsafe { list.create_cycle_at(cycle_at) };
+            list
+        };
+        assert_eq!(build(1), build(1));
+        assert_ne!(build(1), build(2), "Same values, different cycle start");
+
+        let linear = build(1).clone();
+        assert_ne!(build(1), linear, "A cyclic list never equals a linear one");
+        let mut shorter = build(1);
+        shorter.break_cycle();
+        shorter.pop_back();
+        unsafe { shorter.create_cycle_at(1) };
+        assert_ne!(build(1), shorter, "Shorter cycle");
+    }
+
+    #[cfg(not(feature = "safe-only"))]
+    #[test]
+    fn test_pop_reverse_and_iter_mut_respect_a_cycle() {
+        let mut list = LinkedList::new();
+        for value in [3, 2, 1] {
+            list.push(value);
+        }
+        unsafe { list.create_cycle_at(0) };
+        for value in list.iter_mut() {
+            *value *= 10;               // Each value once, then the iterator stops
+        }
+        list.reverse();
+        assert_eq!(list.get_all_values(), vec![&10, &20, &30], "Reverse leaves it alone");
+        assert!(list.has_cycle());
+
+        assert_eq!(list.pop(), Some(10), "The cycle through the head is broken first");
+        assert!(!list.has_cycle());
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![20, 30]);
+    }
+
+    #[test]
+    fn test_conversions_keep_head_to_tail_order() {
+        let mut list: LinkedList<_> = (1..=3).collect();
+        assert_eq!(list.get_all_values(), vec![&1, &2, &3]);
+        list.extend(vec![4, 5]);
+        list.push_back(6);              // The tail followed the extension
+        assert_eq!(Vec::from(list.clone()), vec![1, 2, 3, 4, 5, 6]);
+
+        let from_vec = LinkedList::from(vec!["a", "b"]);
+        assert_eq!(from_vec.get(0), Some(&"a"));
+        let back: Vec<_> = from_vec.into();
+        assert_eq!(back, vec!["a", "b"]);
+
+        let mut empty: LinkedList<i32> = Vec::new().into();
+        empty.extend(None);
+        assert!(empty.is_empty());
+        assert!(Vec::from(empty).is_empty());
+    }
+
+    #[cfg(not(feature = "safe-only"))]
+    #[test]
+    fn test_edge_case_cyclic_list_into_vec() {
+        let mut list = LinkedList::from(vec![1, 2, 3]);
+        unsafe { list.create_cycle_at(1) };
+        list.extend([4]);               // Goes in before the closing link, like push_back
+        assert_eq!(list.cycle_length(), Some(3));
+        assert_eq!(Vec::from(list), vec![1, 2, 3, 4], "Each node once");
+    }
+
+    #[test]
+    fn test_nth_from_end_and_middle() {
+        let list = LinkedList::from(vec![1, 2, 3, 4, 5]);
+        assert_eq!(list.nth_from_end(0), Some(&5));
+        assert_eq!(list.nth_from_end(1), Some(&4));
+        assert_eq!(list.nth_from_end(4), Some(&1));
+        assert_eq!(list.middle(), Some(&3));
+
+        let even = LinkedList::from(vec![1, 2, 3, 4]);
+        assert_eq!(even.middle(), Some(&3), "Second of the two middles");
+        for n in 0..4 {
+            assert_eq!(even.nth_from_end(n), even.get(3 - n));
+        }
+    }
+
+    #[test]
+    fn test_edge_case_nth_from_end_and_middle_of_short_lists() {
+        let empty: LinkedList<i32> = LinkedList::new();
+        assert_eq!((empty.nth_from_end(0), empty.middle()), (None, None));
+
+        let single = LinkedList::from(vec!['a']);
+        assert_eq!((single.nth_from_end(0), single.middle()), (Some(&'a'), Some(&'a')));
+        assert_eq!(single.nth_from_end(1), None, "Out of range");
+
+        let pair = LinkedList::from(vec!['a', 'b']);
+        assert_eq!(pair.middle(), Some(&'b'));
+        assert_eq!(pair.nth_from_end(2), None);
+    }
+
+    #[cfg(not(feature = "safe-only"))]
+    #[test]
+    fn test_unhappy_path_cyclic_list_has_no_end_or_middle() {
+        for cycle_at in [0, 2, 4] {
+            let mut list = LinkedList::from(vec![1, 2, 3, 4, 5]);
+            unsafe { list.create_cycle_at(cycle_at) };
+            assert_eq!(list.nth_from_end(0), None);
+            assert_eq!(list.middle(), None);
+        }
+    }
+
+    #[test]
+    fn test_retain_and_drain_filter() {
+        let mut list: LinkedList<_> = (1..=10).collect();
+        list.retain(|value| value % 3 != 0);
+        assert_eq!(Vec::from(list.clone()), vec![1, 2, 4, 5, 7, 8, 10]);
+
+        let evens: Vec<_> = list.drain_filter(|value| *value % 2 == 0).collect();
+        assert_eq!(evens, vec![2, 4, 8, 10]);
+        assert_eq!(list.get_all_values(), vec![&1, &5, &7]);
+
+        // The old tail (10) was drained, so push_back has to land after 7
+        list.push_back(11);
+        assert_eq!(list.pop_back(), Some(11));
+        assert_eq!(list.len(), 3);
+    }
+
+    #[test]
+    fn test_edge_case_drain_filter_stops_where_dropped() {
+        let mut list: LinkedList<_> = (1..=6).collect();
+        {
+            let mut drain = list.drain_filter(|value| {
+                *value *= 10;           // The predicate may edit values it keeps
+                *value > 20
+            });
+            assert_eq!(drain.next(), Some(30));
+        }                               // Dropped after one match
+        assert_eq!(Vec::from(list), vec![10, 20, 4, 5, 6], "Untested values untouched");
+
+        let mut all: LinkedList<_> = (1..=3).collect();
+        assert_eq!(all.drain_filter(|_| true).count(), 3);
+        assert!(all.is_empty());
+        all.push_back(1);               // The tail was reset along with the head
+        assert_eq!(all.get_all_values(), vec![&1]);
+    }
+
+    #[cfg(not(feature = "safe-only"))]
+    #[test]
+    fn test_unhappy_path_retain_leaves_a_cyclic_list_alone() {
+        let mut list = LinkedList::from(vec![1, 2, 3]);
+        unsafe { list.create_cycle_at(1) };
+        list.retain(|_| false);
+        assert_eq!(list.drain_filter(|_| true).next(), None);
+        assert_eq!(list.len(), 3);
+        assert!(list.has_cycle());
+    }
+
+    #[cfg(not(feature = "safe-only"))]
+    #[test]
+    fn test_floyd_and_brent_agree_on_every_cycle_shape() {
+        for len in 1..=12 {
+            for start in 0..len {
+                let mut list: LinkedList<_> = (0..len).collect();
+                unsafe { list.create_cycle_at(start) };
+                let expected = CycleInfo { start, length: len - start };
+                assert_eq!(list.analyze_cycle(), Some(expected));
+                assert_eq!(list.analyze_cycle_with(CycleStrategy::Brent), Some(expected));
+            }
+        }
+    }
+
+    #[test]
+    fn test_unhappy_path_analyze_cycle_of_linear_lists() {
+        for len in 0..5 {
+            let list: LinkedList<_> = (0..len).collect();
+            assert_eq!(list.analyze_cycle(), None);
+            assert_eq!(list.analyze_cycle_with(CycleStrategy::Brent), None);
+        }
+        assert_eq!(CycleStrategy::default(), CycleStrategy::Floyd);
+    }
+
+    #[test]
+    fn test_rotate_left_and_right() {
+        let mut list = LinkedList::from(vec![1, 2, 3, 4, 5]);
+        list.rotate_left(2);
+        assert_eq!(Vec::from(list.clone()), vec![3, 4, 5, 1, 2]);
+        list.push_back(6);              // The tail moved with the rotation
+        list.rotate_right(3);
+        assert_eq!(Vec::from(list.clone()), vec![1, 2, 6, 3, 4, 5]);
+        list.rotate_left(13);           // 13 wraps round to 1
+        assert_eq!(Vec::from(list), vec![2, 6, 3, 4, 5, 1]);
+    }
+
+    #[test]
+    fn test_edge_case_rotate_by_zero_length_and_empty() {
+        let mut list = LinkedList::from(vec![1, 2, 3]);
+        list.rotate_left(0);
+        list.rotate_right(3);
+        assert_eq!(Vec::from(list.clone()), vec![1, 2, 3]);
+        list.push_back(4);
+        assert_eq!(list.get(3), Some(&4));
+
+        let mut empty: LinkedList<i32> = LinkedList::new();
+        empty.rotate_left(1);
+        empty.rotate_right(1);
+        assert!(empty.is_empty());
+    }
+
+    #[cfg(not(feature = "safe-only"))]
+    #[test]
+    fn test_unhappy_path_rotate_leaves_a_cyclic_list_alone() {
+        let mut list = LinkedList::from(vec![1, 2, 3]);
+        unsafe { list.create_cycle_at(1) };
+        list.rotate_left(1);
+        list.rotate_right(1);
+        assert_eq!(list.get_all_values(), vec![&1, &2, &3]);
+        assert_eq!(list.find_cycle_start(), Some(1));
+    }
+
+    #[test]
+    fn test_edge_case_long_list_drops_without_overflow() {
+        let mut list = LinkedList::new();
+        for i in 0..1_000_000 {
+            list.push(i);
+        }
+        drop(list);                     // Recursive drop would overflow the stack here
+    }
+
+    #[test]
+    fn test_iter_mut_and_owned_iteration() {
+        let mut list = LinkedList::new();
+        for i in 1..=4 {
+            list.push(i);
+        }
+        for value in list.iter_mut() {
+            *value *= 10;
+        }
+        for value in &mut list {
+            *value += 1;
+        }
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![41, 31, 21, 11]);
+
+        let mut owned = Vec::new();
+        for value in list {
+            owned.push(value);              // Moves each value out of the list
+        }
+        assert_eq!(owned, vec![41, 31, 21, 11]);
+
+        let strings: LinkedList<String> = LinkedList::new();
+        assert_eq!(strings.into_iter().next(), None);
+    }
+}
@@ -0,0 +1,464 @@
+//! A singly linked list whose nodes are shared `Rc<RefCell<_>>`s, so cycles can
+//! be built, measured and broken without any unsafe code
+
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+type Link<T> = Option<Rc<RefCell<RcNode<T>>>>;
+
+struct RcNode<T> {
+    value: T,
+    next: Link<T>,
+}
+
+/// A linked list for experimenting with cycles safely
+///
+/// Where `LinkedList::create_cycle_at` fakes a second `Box` and is `unsafe` to
+/// use, here the node that closes a cycle just holds another `Rc` to the cycle
+/// start. Dropping the list breaks any cycle first, so nothing leaks either.
+pub struct RcLinkedList<T> {
+    head: Link<T>,
+}
+
+// Follows one link; the clone is a reference count bump, not a copy of the value
+fn next<T>(node: &Rc<RefCell<RcNode<T>>>) -> Link<T> {
+    node.borrow().next.clone()
+}
+
+impl<T> RcLinkedList<T> {
+    /// Creates an empty list
+    pub fn new() -> Self {
+        RcLinkedList { head: None }
+    }
+
+    /// Adds an element at the front
+    pub fn push(&mut self, value: T) {
+        let node = RcNode {
+            value,
+            next: self.head.take(),
+        };
+        self.head = Some(Rc::new(RefCell::new(node)));
+    }
+
+    /// Removes and returns the first element
+    ///
+    /// If the cycle starts at the head, it is broken first, since nothing
+    /// could point back at the removed node afterwards. Returns `None`, leaving
+    /// the list unchanged, if another list shares the head (see `join_at`):
+    /// its value can't be moved out while that list still holds it.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.find_cycle_start() == Some(0) {
+            self.break_cycle();
+        }
+        let head = self.head.take()?;
+        match Rc::try_unwrap(head) {
+            Ok(node) => {
+                let node = node.into_inner();
+                self.head = node.next;
+                Some(node.value)
+            }
+            Err(shared) => {
+                self.head = Some(shared);
+                None
+            }
+        }
+    }
+
+    /// Checks if the list is empty
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    /// Returns the number of distinct nodes, counting a cycle's nodes once
+    pub fn len(&self) -> usize {
+        if let (Some(start), Some(length)) = (self.find_cycle_start(), self.cycle_length()) {
+            return start + length;
+        }
+        let mut count = 0;
+        let mut current = self.head.clone();
+        while let Some(node) = current {
+            count += 1;
+            current = next(&node);
+        }
+        count
+    }
+
+    /// Points the last node back at the node at `index`, closing a cycle
+    ///
+    /// Returns `false` if `index` is out of range or the list already has a cycle.
+    pub fn create_cycle_at(&mut self, index: usize) -> bool {
+        if self.has_cycle() {
+            return false;
+        }
+        let mut target = None;
+        let mut last = None;
+        let mut current = self.head.clone();
+        let mut position = 0;
+        while let Some(node) = current {
+            if position == index {
+                target = Some(Rc::clone(&node));
+            }
+            current = next(&node);
+            last = Some(node);
+            position += 1;
+        }
+        match (target, last) {
+            (Some(target), Some(last)) => {
+                last.borrow_mut().next = Some(target);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Points the last node at the node at `index` in `other`, so from there on
+    /// the two lists share their nodes
+    ///
+    /// Returns `false` if `index` is out of range, either list has a cycle, or
+    /// the lists already share nodes. Each list only frees the nodes no other
+    /// list links to, so dropping one leaves the shared suffix intact.
+    pub fn join_at(&mut self, other: &RcLinkedList<T>, index: usize) -> bool {
+        if self.has_cycle() || other.has_cycle() || intersection_node(self, other).is_some() {
+            return false;
+        }
+        let mut target = other.head.clone();
+        for _ in 0..index {
+            target = target.and_then(|node| next(&node));
+        }
+        let Some(target) = target else {
+            return false;
+        };
+        let mut last = self.head.clone();
+        while let Some(following) = last.as_ref().and_then(next) {
+            last = Some(following);
+        }
+        match last {
+            Some(last) => last.borrow_mut().next = Some(target),
+            None => self.head = Some(target),
+        }
+        true
+    }
+
+    /// Unlinks the node that closes the cycle, making the list linear again
+    ///
+    /// Returns `false` if there was no cycle.
+    pub fn break_cycle(&mut self) -> bool {
+        let Some(start) = self.find_cycle_start() else {
+            return false;
+        };
+        let mut start_node = self.head.clone();
+        for _ in 0..start {
+            start_node = start_node.and_then(|node| next(&node));
+        }
+        let Some(start_node) = start_node else {
+            return false;
+        };
+        // Walk round the cycle to the node whose next is the start
+        let mut last = Rc::clone(&start_node);
+        while let Some(following) = next(&last) {
+            if Rc::ptr_eq(&following, &start_node) {
+                break;
+            }
+            last = following;
+        }
+        last.borrow_mut().next = None;
+        true
+    }
+
+    /// Floyd's cycle detection, comparing nodes with `Rc::ptr_eq`
+    pub fn has_cycle(&self) -> bool {
+        self.meeting_point().is_some()
+    }
+
+    /// Returns the index of the node where the cycle begins
+    pub fn find_cycle_start(&self) -> Option<usize> {
+        let mut meeting = self.meeting_point()?;
+        let mut start = self.head.clone()?;
+        let mut index = 0;
+        // One pointer from the head and one from the meeting point meet at the cycle start
+        while !Rc::ptr_eq(&start, &meeting) {
+            start = next(&start)?;
+            meeting = next(&meeting)?;
+            index += 1;
+        }
+        Some(index)
+    }
+
+    /// Returns the number of nodes in the cycle
+    pub fn cycle_length(&self) -> Option<usize> {
+        let meeting = self.meeting_point()?;
+        let mut current = next(&meeting)?;
+        let mut length = 1;
+        while !Rc::ptr_eq(&current, &meeting) {
+            current = next(&current)?;
+            length += 1;
+        }
+        Some(length)
+    }
+
+    /// Copies the values from head to tail, visiting each node once even in a cycle
+    pub fn values(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        let mut values = Vec::new();
+        self.for_each_node(|value| values.push(value.clone()));
+        values
+    }
+
+    // Calls `f` on each node's value in order, stopping after one lap of any cycle
+    fn for_each_node(&self, mut f: impl FnMut(&T)) {
+        let mut current = self.head.clone();
+        for _ in 0..self.len() {
+            let Some(node) = current else { break };
+            f(&node.borrow().value);
+            current = next(&node);
+        }
+    }
+
+    // The node where the tortoise and the hare meet, if there is a cycle
+    fn meeting_point(&self) -> Link<T> {
+        let mut slow = self.head.clone();
+        let mut fast = self.head.clone();
+        loop {
+            slow = slow.and_then(|node| next(&node));
+            fast = fast
+                .and_then(|node| next(&node))
+                .and_then(|node| next(&node));
+            match (&slow, &fast) {
+                (Some(slow), Some(fast)) if Rc::ptr_eq(slow, fast) => return Some(Rc::clone(slow)),
+                (_, None) => return None,
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Finds where two lists merge into a shared suffix
+///
+/// Returns the merge node's index in `list_a` and in `list_b`, or `None` if
+/// they share no nodes. Nodes are compared with `Rc::ptr_eq`, so equal values
+/// in separate nodes don't count. Lists with a cycle return `None`.
+/// O(n + m) time and O(1) space: the longer list is walked ahead by the
+/// difference in length, then both are walked in step until they meet.
+pub fn intersection_node<T>(
+    list_a: &RcLinkedList<T>,
+    list_b: &RcLinkedList<T>,
+) -> Option<(usize, usize)> {
+    if list_a.has_cycle() || list_b.has_cycle() {
+        return None;
+    }
+    let (len_a, len_b) = (list_a.len(), list_b.len());
+    let mut a = list_a.head.clone();
+    let mut b = list_b.head.clone();
+    let (mut index_a, mut index_b) = (0, 0);
+    while len_a - index_a > len_b - index_b {
+        a = a.and_then(|node| next(&node));
+        index_a += 1;
+    }
+    while len_b - index_b > len_a - index_a {
+        b = b.and_then(|node| next(&node));
+        index_b += 1;
+    }
+    while let (Some(node_a), Some(node_b)) = (a, b) {
+        if Rc::ptr_eq(&node_a, &node_b) {
+            return Some((index_a, index_b));
+        }
+        a = next(&node_a);
+        b = next(&node_b);
+        index_a += 1;
+        index_b += 1;
+    }
+    None
+}
+
+impl<T> Default for RcLinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for RcLinkedList<T> {
+    fn drop(&mut self) {
+        // A cycle would keep its nodes alive forever, and unlinking one node at a
+        // time stops a long list from dropping recursively
+        self.break_cycle();
+        let mut current = self.head.take();
+        while let Some(node) = current {
+            // A node another list still links to stays, along with the rest of
+            // the shared suffix
+            current = Rc::try_unwrap(node)
+                .ok()
+                .and_then(|node| node.into_inner().next);
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for RcLinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut list = f.debug_list();
+        self.for_each_node(|value| {
+            list.entry(value);
+        });
+        list.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list_of(values: &[i32]) -> RcLinkedList<i32> {
+        let mut list = RcLinkedList::new();
+        for &value in values.iter().rev() {
+            list.push(value);
+        }
+        list
+    }
+
+    #[test]
+    fn test_happy_path_push_pop_len() {
+        let mut list = list_of(&[1, 2, 3]);
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.values(), vec![1, 2, 3]);
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(format!("{:?}", list), "[2, 3]");
+        assert!(!list.has_cycle());
+    }
+
+    #[test]
+    fn test_cycle_detection_and_measurement() {
+        let mut list = list_of(&[1, 2, 3, 4, 5]);
+        assert!(list.create_cycle_at(2));
+        assert!(list.has_cycle());
+        assert_eq!(list.find_cycle_start(), Some(2));
+        assert_eq!(list.cycle_length(), Some(3));
+        assert_eq!(list.len(), 5);
+        assert_eq!(list.values(), vec![1, 2, 3, 4, 5], "Each node once");
+    }
+
+    #[test]
+    fn test_break_cycle_makes_the_list_linear() {
+        let mut list = list_of(&[1, 2, 3]);
+        list.create_cycle_at(0);
+        assert!(list.break_cycle());
+        assert!(!list.has_cycle());
+        assert!(!list.break_cycle(), "Nothing left to break");
+        assert_eq!(list.values(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_dropping_a_cyclic_list_frees_every_node() {
+        let token = Rc::new(());
+        let mut list = RcLinkedList::new();
+        for _ in 0..4 {
+            list.push(Rc::clone(&token));
+        }
+        list.create_cycle_at(1);
+        assert_eq!(Rc::strong_count(&token), 5);
+        drop(list);
+        assert_eq!(Rc::strong_count(&token), 1, "No leak and no double free");
+    }
+
+    #[test]
+    fn test_pop_breaks_a_cycle_through_the_head() {
+        let mut list = list_of(&[1, 2, 3]);
+        list.create_cycle_at(0);
+        assert_eq!(list.pop(), Some(1));
+        assert!(!list.has_cycle());
+        assert_eq!(list.values(), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_unhappy_path_invalid_cycles_are_refused() {
+        let mut empty: RcLinkedList<i32> = RcLinkedList::new();
+        assert!(!empty.create_cycle_at(0));
+        assert_eq!(empty.pop(), None);
+
+        let mut list = list_of(&[1, 2]);
+        assert!(!list.create_cycle_at(2), "Index out of range");
+        assert!(list.create_cycle_at(1));
+        assert!(!list.create_cycle_at(0), "Already cyclic");
+    }
+
+    #[test]
+    fn test_intersection_of_joined_lists() {
+        let mut a = list_of(&[1, 2]);
+        let b = list_of(&[7, 8, 9, 10]);
+        assert_eq!(
+            intersection_node(&a, &b),
+            None,
+            "Equal values aren't enough"
+        );
+        assert!(a.join_at(&b, 2));
+        assert_eq!(a.values(), vec![1, 2, 9, 10]);
+        assert_eq!(intersection_node(&a, &b), Some((2, 2)));
+        assert_eq!(intersection_node(&b, &a), Some((2, 2)));
+        assert!(!a.join_at(&b, 0), "Already sharing nodes");
+    }
+
+    #[test]
+    fn test_shared_suffix_outlives_either_list() {
+        let token = Rc::new(());
+        let mut a = RcLinkedList::new();
+        let mut b = RcLinkedList::new();
+        for _ in 0..3 {
+            a.push(Rc::clone(&token));
+            b.push(Rc::clone(&token));
+        }
+        assert!(a.join_at(&b, 1));
+        assert_eq!(a.len(), 5);
+        drop(b);
+        assert_eq!(Rc::strong_count(&token), 6, "Only b's own node was freed");
+        assert_eq!(a.len(), 5);
+
+        let mut c = RcLinkedList::new();
+        c.join_at(&a, 4);
+        assert!(a.pop().is_some());
+        drop(a);
+        assert_eq!(Rc::strong_count(&token), 2, "c still holds the last node");
+        assert!(c.pop().is_some());
+        assert_eq!(Rc::strong_count(&token), 1);
+    }
+
+    #[test]
+    fn test_unhappy_path_join_and_intersection_refusals() {
+        let mut a = list_of(&[1, 2]);
+        let mut b = list_of(&[3]);
+        assert!(!a.join_at(&b, 1), "Index out of range");
+        b.create_cycle_at(0);
+        assert!(!a.join_at(&b, 0), "Cyclic lists are refused");
+        assert_eq!(intersection_node(&a, &b), None);
+
+        let empty: RcLinkedList<i32> = RcLinkedList::new();
+        assert_eq!(intersection_node(&a, &empty), None);
+    }
+
+    #[test]
+    fn test_edge_case_join_shares_a_whole_list() {
+        let mut a = RcLinkedList::new();
+        let b = list_of(&[4, 5]);
+        assert!(a.join_at(&b, 0));
+        assert_eq!(intersection_node(&a, &b), Some((0, 0)));
+        assert_eq!(a.pop(), None, "The head belongs to b as well");
+        assert_eq!(a.values(), vec![4, 5]);
+    }
+
+    #[test]
+    fn test_edge_case_self_loop_and_long_list() {
+        let mut single = list_of(&[7]);
+        assert!(single.create_cycle_at(0));
+        assert_eq!(
+            (single.find_cycle_start(), single.cycle_length()),
+            (Some(0), Some(1))
+        );
+        assert_eq!(format!("{:?}", single), "[7]");
+
+        let mut long = RcLinkedList::new();
+        for i in 0..100_000 {
+            long.push(i);
+        }
+        drop(long); // Iterative drop, no stack overflow
+    }
+}
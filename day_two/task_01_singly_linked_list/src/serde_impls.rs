@@ -0,0 +1,132 @@
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::LinkedList;
+
+// A cyclic list: every node once, from head to tail, plus the index the tail links
+// back to. That index is also the length of the prefix before the cycle
+#[derive(Serialize)]
+struct CyclicRef<'a, T> {
+    values: Vec<&'a T>,
+    cycle_start: usize,
+}
+
+// Either shape, told apart by whether the input is a sequence or a map
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Repr<T> {
+    Linear(Vec<T>),
+    Cyclic { values: Vec<T>, cycle_start: usize },
+}
+
+/// Serializes a linear list as a sequence from head to tail, and a cyclic list
+/// as `{"values": [...], "cycle_start": n}` so the cycle survives a round trip
+impl<T: Serialize> Serialize for LinkedList<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.find_cycle_start() {
+            Some(cycle_start) => CyclicRef {
+                values: self.get_all_values(),
+                cycle_start,
+            }
+            .serialize(serializer),
+            None => serializer.collect_seq(self.get_all_values()),
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for LinkedList<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (values, cycle_start) = match Repr::deserialize(deserializer)? {
+            Repr::Linear(values) => (values, None),
+            Repr::Cyclic {
+                values,
+                cycle_start,
+            } => (values, Some(cycle_start)),
+        };
+        if cycle_start.is_some_and(|start| start >= values.len()) {
+            return Err(D::Error::custom("cycle_start is past the end of the list"));
+        }
+        let mut list = LinkedList::from(values);
+        if let Some(cycle_start) = cycle_start {
+            close_cycle(&mut list, cycle_start).map_err(D::Error::custom)?;
+        }
+        Ok(list)
+    }
+}
+
+#[cfg(not(feature = "safe-only"))]
+fn close_cycle<T>(list: &mut LinkedList<T>, cycle_start: usize) -> Result<(), &'static str> {
+    // SAFETY: the list's own methods never take the second `Box` out except
+    // through `break_cycle` (see `create_cycle_at`)
+    unsafe { list.create_cycle_at(cycle_start) };
+    Ok(())
+}
+
+#[cfg(feature = "safe-only")]
+fn close_cycle<T>(_: &mut LinkedList<T>, _: usize) -> Result<(), &'static str> {
+    Err("cyclic lists need unsafe code, which safe-only rules out")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_keeps_order() {
+        let mut list = LinkedList::new();
+        list.push(3);
+        list.push(2);
+        list.push(1);
+        let json = serde_json::to_string(&list).unwrap();
+        assert_eq!(json, "[1,2,3]");
+
+        let mut restored: LinkedList<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.len(), 3);
+        assert_eq!(restored.pop(), Some(1));
+        assert_eq!(restored.get(1), Some(&3));
+    }
+
+    #[test]
+    #[cfg(not(feature = "safe-only"))]
+    fn test_round_trip_keeps_cycle_structure() {
+        let mut list = LinkedList::new();
+        for i in 0..4 {
+            list.push(i);
+        }
+        unsafe { list.create_cycle_at(1) };
+        let json = serde_json::to_string(&list).unwrap();
+        assert_eq!(json, r#"{"values":[3,2,1,0],"cycle_start":1}"#);
+
+        let mut restored: LinkedList<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, list);
+        assert_eq!(restored.cycle_length(), Some(3));
+
+        // Popping down to the cycle start breaks the cycle rather than freeing a linked node
+        assert_eq!(restored.pop(), Some(3));
+        assert_eq!(restored.pop(), Some(2));
+        assert!(!restored.has_cycle());
+        assert_eq!(restored.len(), 2);
+    }
+
+    #[test]
+    fn test_unhappy_path_cycle_start_out_of_range() {
+        let error = serde_json::from_str::<LinkedList<i32>>(r#"{"values":[1,2],"cycle_start":2}"#)
+            .unwrap_err();
+        assert!(error.to_string().contains("past the end"));
+        assert!(serde_json::from_str::<LinkedList<i32>>(r#"{"values":[1]}"#).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "safe-only")]
+    fn test_unhappy_path_cyclic_input_under_safe_only() {
+        let error = serde_json::from_str::<LinkedList<i32>>(r#"{"values":[1],"cycle_start":0}"#)
+            .unwrap_err();
+        assert!(error.to_string().contains("safe-only"));
+    }
+
+    #[test]
+    fn test_edge_case_empty_list() {
+        let restored: LinkedList<String> = serde_json::from_str("[]").unwrap();
+        assert!(restored.is_empty());
+    }
+}
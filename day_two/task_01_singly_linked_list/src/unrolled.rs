@@ -0,0 +1,265 @@
+//! An unrolled linked list: each node holds a small array of values, so walking
+//! the list follows one pointer per `CHUNK` values instead of one per value
+
+use std::fmt;
+use std::iter::Rev;
+use std::slice;
+
+// Values per node; 16 keeps a chunk of small values within a few cache lines
+const CHUNK: usize = 16;
+
+struct Chunk<T> {
+    // The occupied slots are `values[..len]`, stored back to front, so pushing
+    // to the front of the list fills the next free slot without shifting
+    values: [Option<T>; CHUNK],
+    len: usize,
+    next: Option<Box<Chunk<T>>>,
+}
+
+impl<T> Chunk<T> {
+    fn new(next: Option<Box<Chunk<T>>>) -> Box<Self> {
+        Box::new(Chunk {
+            values: std::array::from_fn(|_| None),
+            len: 0,
+            next,
+        })
+    }
+
+    // The occupied slots in list order
+    fn iter(&self) -> Rev<slice::Iter<'_, Option<T>>> {
+        self.values[..self.len].iter().rev()
+    }
+}
+
+/// A singly linked list of fixed-size chunks with the same `push`/`pop`/`get`/`iter`
+/// API as `LinkedList`
+///
+/// Values are only ever added or removed at the front, so every chunk but the
+/// first is full and none is empty. Iterating reads values that sit next to
+/// each other in memory and `get` skips a whole chunk per step; the root crate's
+/// `bench::unrolled_list_vs_linked_list` measures how much that saves.
+pub struct UnrolledLinkedList<T> {
+    head: Option<Box<Chunk<T>>>,
+    len: usize,
+}
+
+impl<T> UnrolledLinkedList<T> {
+    /// Creates an empty list
+    pub fn new() -> Self {
+        UnrolledLinkedList { head: None, len: 0 }
+    }
+
+    /// Adds an element at the front, starting a new chunk when the first one is full
+    pub fn push(&mut self, value: T) {
+        if self.head.as_ref().is_none_or(|chunk| chunk.len == CHUNK) {
+            self.head = Some(Chunk::new(self.head.take()));
+        }
+        let chunk = self
+            .head
+            .as_mut()
+            .expect("a chunk with room was just ensured");
+        chunk.values[chunk.len] = Some(value);
+        chunk.len += 1;
+        self.len += 1;
+    }
+
+    /// Removes and returns the first element, freeing its chunk once it is empty
+    pub fn pop(&mut self) -> Option<T> {
+        let chunk = self.head.as_mut()?;
+        chunk.len -= 1;
+        let value = chunk.values[chunk.len].take();
+        if chunk.len == 0 {
+            self.head = chunk.next.take();
+        }
+        self.len -= 1;
+        value
+    }
+
+    /// Returns the element at `index`, counting from the front
+    pub fn get(&self, mut index: usize) -> Option<&T> {
+        let mut current = self.head.as_deref();
+        while let Some(chunk) = current {
+            if index < chunk.len {
+                return chunk.values[chunk.len - 1 - index].as_ref();
+            }
+            index -= chunk.len;
+            current = chunk.next.as_deref();
+        }
+        None
+    }
+
+    /// Returns the number of elements
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if the list is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Removes every element
+    pub fn clear(&mut self) {
+        // One chunk at a time, so a long list doesn't drop recursively
+        let mut current = self.head.take();
+        while let Some(mut chunk) = current {
+            current = chunk.next.take();
+        }
+        self.len = 0;
+    }
+
+    /// Iterates from front to back
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (values, next) = match self.head.as_deref() {
+            Some(chunk) => (chunk.iter(), chunk.next.as_deref()),
+            None => ([].iter().rev(), None),
+        };
+        Iter {
+            values,
+            next,
+            remaining: self.len,
+        }
+    }
+}
+
+impl<T> Default for UnrolledLinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for UnrolledLinkedList<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+/// Borrowing iterator over an `UnrolledLinkedList`
+pub struct Iter<'a, T> {
+    values: Rev<slice::Iter<'a, Option<T>>>,
+    next: Option<&'a Chunk<T>>,
+    remaining: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        loop {
+            if let Some(slot) = self.values.next() {
+                self.remaining -= 1;
+                return slot.as_ref();
+            }
+            let chunk = self.next?;
+            self.values = chunk.iter();
+            self.next = chunk.next.as_deref();
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<'a, T> IntoIterator for &'a UnrolledLinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+/// Collects so the first item ends up at the front, like `LinkedList`
+impl<T> FromIterator<T> for UnrolledLinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let values: Vec<T> = iter.into_iter().collect();
+        let mut list = UnrolledLinkedList::new();
+        for value in values.into_iter().rev() {
+            list.push(value);
+        }
+        list
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for UnrolledLinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_happy_path_push_pop_get() {
+        let mut list = UnrolledLinkedList::new();
+        list.push(3);
+        list.push(2);
+        list.push(1);
+        assert_eq!(list.len(), 3);
+        assert_eq!((list.get(0), list.get(2)), (Some(&1), Some(&3)));
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(format!("{:?}", list), "[2, 3]");
+    }
+
+    #[test]
+    fn test_values_cross_chunk_boundaries_in_order() {
+        let count = CHUNK * 3 + 5;
+        let list: UnrolledLinkedList<_> = (0..count).collect();
+        assert_eq!(list.len(), count);
+        assert_eq!(list.iter().len(), count);
+        assert!(list.iter().copied().eq(0..count));
+        for index in [0, CHUNK - 1, CHUNK, CHUNK * 3, count - 1] {
+            assert_eq!(list.get(index), Some(&index));
+        }
+    }
+
+    #[test]
+    fn test_popping_frees_emptied_chunks() {
+        let mut list: UnrolledLinkedList<_> = (0..CHUNK + 1).collect();
+        assert_eq!(list.pop(), Some(0), "The front chunk held a single value");
+        assert_eq!(list.pop(), Some(1));
+        list.push(100);
+        assert_eq!(list.get(0), Some(&100));
+        assert_eq!(list.get(CHUNK - 1), Some(&CHUNK));
+        assert!(list.iter().skip(1).copied().eq(2..=CHUNK));
+    }
+
+    #[test]
+    fn test_unhappy_path_empty_and_out_of_range() {
+        let mut list: UnrolledLinkedList<i32> = UnrolledLinkedList::new();
+        assert!(list.is_empty());
+        assert_eq!(list.pop(), None);
+        assert_eq!(list.get(0), None);
+        assert_eq!(list.iter().next(), None);
+
+        list.push(1);
+        assert_eq!(list.get(1), None);
+    }
+
+    #[test]
+    fn test_every_value_is_dropped_exactly_once() {
+        let token = std::rc::Rc::new(());
+        let mut list: UnrolledLinkedList<_> =
+            (0..CHUNK * 2).map(|_| std::rc::Rc::clone(&token)).collect();
+        drop(list.pop());
+        assert_eq!(std::rc::Rc::strong_count(&token), CHUNK * 2);
+        list.clear();
+        assert!(list.is_empty());
+        assert_eq!(std::rc::Rc::strong_count(&token), 1);
+    }
+
+    #[test]
+    fn test_edge_case_long_list_drops_without_overflow() {
+        let mut list = UnrolledLinkedList::new();
+        for i in 0..1_000_000 {
+            list.push(i);
+        }
+        assert_eq!(list.get(999_999), Some(&0));
+        drop(list);
+    }
+}
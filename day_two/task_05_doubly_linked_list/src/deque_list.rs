@@ -0,0 +1,257 @@
+use std::collections::{vec_deque, VecDeque};
+
+/// Stand-in for the pointer-based list when unsafe code is ruled out. It keeps
+/// the same API, including cursors, but keeps the values in a `VecDeque`, so
+/// inserting or removing through a cursor shifts elements instead of relinking.
+pub struct DoublyLinkedList<T> {
+    items: VecDeque<T>,
+}
+
+impl<T> DoublyLinkedList<T> {
+    /// Creates an empty list
+    pub fn new() -> Self {
+        DoublyLinkedList {
+            items: VecDeque::new(),
+        }
+    }
+
+    /// Adds an element at the front
+    pub fn push_front(&mut self, value: T) {
+        self.items.push_front(value);
+    }
+
+    /// Adds an element at the back
+    pub fn push_back(&mut self, value: T) {
+        self.items.push_back(value);
+    }
+
+    /// Removes and returns the first element
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.items.pop_front()
+    }
+
+    /// Removes and returns the last element
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.items.pop_back()
+    }
+
+    /// Returns the first element
+    pub fn front(&self) -> Option<&T> {
+        self.items.front()
+    }
+
+    /// Returns the last element
+    pub fn back(&self) -> Option<&T> {
+        self.items.back()
+    }
+
+    /// Returns the first element mutably
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.items.front_mut()
+    }
+
+    /// Returns the last element mutably
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.items.back_mut()
+    }
+
+    /// Returns the number of elements
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Checks if the list is empty
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Removes every element
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+
+    /// Iterates from front to back; `.rev()` walks back to front
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            inner: self.items.iter(),
+        }
+    }
+
+    /// Returns a read-only cursor at the first element
+    pub fn cursor_front(&self) -> Cursor<'_, T> {
+        Cursor {
+            index: 0,
+            list: self,
+        }
+    }
+
+    /// Returns a read-only cursor at the last element
+    pub fn cursor_back(&self) -> Cursor<'_, T> {
+        Cursor {
+            index: self.len().saturating_sub(1),
+            list: self,
+        }
+    }
+
+    /// Returns a cursor at the first element that can edit the list
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            index: 0,
+            list: self,
+        }
+    }
+
+    /// Returns a cursor at the last element that can edit the list
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            index: self.len().saturating_sub(1),
+            list: self,
+        }
+    }
+}
+
+impl<T> Default for DoublyLinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Borrowing iterator over a `DoublyLinkedList`, usable from both ends
+pub struct Iter<'a, T> {
+    inner: vec_deque::Iter<'a, T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        self.inner.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+/// A read-only position in a `DoublyLinkedList`
+///
+/// Besides the elements there is one "ghost" position, between the back and
+/// the front, where `current` is `None`. Moving past either end lands there,
+/// and moving on from it wraps to the other end.
+pub struct Cursor<'a, T> {
+    // The ghost position is index `len`
+    index: usize,
+    list: &'a DoublyLinkedList<T>,
+}
+
+impl<'a, T> Cursor<'a, T> {
+    /// Returns the element under the cursor, or `None` at the ghost position
+    pub fn current(&self) -> Option<&'a T> {
+        self.list.items.get(self.index)
+    }
+
+    /// Returns the cursor's index from the front, or `None` at the ghost position
+    pub fn index(&self) -> Option<usize> {
+        (self.index < self.list.len()).then_some(self.index)
+    }
+
+    /// Moves towards the back
+    pub fn move_next(&mut self) {
+        self.index = step_next(self.index, self.list.len());
+    }
+
+    /// Moves towards the front
+    pub fn move_prev(&mut self) {
+        self.index = step_prev(self.index, self.list.len());
+    }
+
+    /// Returns the element after the cursor without moving
+    pub fn peek_next(&self) -> Option<&'a T> {
+        self.list.items.get(step_next(self.index, self.list.len()))
+    }
+
+    /// Returns the element before the cursor without moving
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        self.list.items.get(step_prev(self.index, self.list.len()))
+    }
+}
+
+/// A position in a `DoublyLinkedList` that can edit the list around it
+///
+/// Moves the same way as `Cursor`, including the ghost position.
+pub struct CursorMut<'a, T> {
+    index: usize,
+    list: &'a mut DoublyLinkedList<T>,
+}
+
+impl<T> CursorMut<'_, T> {
+    /// Returns the element under the cursor, or `None` at the ghost position
+    pub fn current(&mut self) -> Option<&mut T> {
+        self.list.items.get_mut(self.index)
+    }
+
+    /// Returns the cursor's index from the front, or `None` at the ghost position
+    pub fn index(&self) -> Option<usize> {
+        (self.index < self.list.len()).then_some(self.index)
+    }
+
+    /// Moves towards the back
+    pub fn move_next(&mut self) {
+        self.index = step_next(self.index, self.list.len());
+    }
+
+    /// Moves towards the front
+    pub fn move_prev(&mut self) {
+        self.index = step_prev(self.index, self.list.len());
+    }
+
+    /// Inserts `value` after the cursor; at the ghost position that is the front
+    pub fn insert_after(&mut self, value: T) {
+        if self.index == self.list.len() {
+            self.list.items.push_front(value);
+            self.index += 1; // The ghost index follows the length
+        } else {
+            self.list.items.insert(self.index + 1, value);
+        }
+    }
+
+    /// Inserts `value` before the cursor; at the ghost position that is the back
+    pub fn insert_before(&mut self, value: T) {
+        self.list.items.insert(self.index, value);
+        self.index += 1;
+    }
+
+    /// Removes the element under the cursor and moves on to the next one
+    ///
+    /// Returns `None`, removing nothing, at the ghost position.
+    pub fn remove_current(&mut self) -> Option<T> {
+        // The next element slides into `index`, or `index` becomes the ghost
+        self.list.items.remove(self.index)
+    }
+}
+
+// One step towards the back, wrapping through the ghost position at `len`
+fn step_next(index: usize, len: usize) -> usize {
+    if index >= len {
+        0
+    } else {
+        index + 1
+    }
+}
+
+// One step towards the front, wrapping through the ghost position at `len`
+fn step_prev(index: usize, len: usize) -> usize {
+    match index {
+        0 => len,
+        index if index >= len => len.saturating_sub(1),
+        index => index - 1,
+    }
+}
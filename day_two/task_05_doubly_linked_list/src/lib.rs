@@ -0,0 +1,280 @@
+#![cfg_attr(feature = "safe-only", forbid(unsafe_code))]
+
+//! A doubly linked list: every node links to both neighbours, so the list can
+//! be pushed, popped and walked from either end.
+//!
+//! The list is built from raw `NonNull` links; `list.rs` documents the
+//! invariants that make its unsafe code sound. With the `safe-only` feature it
+//! is swapped for a `VecDeque`-backed stand-in with the same API.
+
+use std::fmt;
+
+#[cfg(feature = "safe-only")]
+mod deque_list;
+#[cfg(not(feature = "safe-only"))]
+mod list;
+
+#[cfg(feature = "safe-only")]
+pub use deque_list::{Cursor, CursorMut, DoublyLinkedList, Iter};
+#[cfg(not(feature = "safe-only"))]
+pub use list::{Cursor, CursorMut, DoublyLinkedList, Iter};
+
+// Everything below only uses the public API, so both implementations share it
+
+impl<T> FromIterator<T> for DoublyLinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = DoublyLinkedList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Extend<T> for DoublyLinkedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push_back(value);
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a DoublyLinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<T> IntoIterator for DoublyLinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { list: self }
+    }
+}
+
+/// Owning iterator over a `DoublyLinkedList`, popping from either end
+pub struct IntoIter<T> {
+    list: DoublyLinkedList<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len(), Some(self.list.len()))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.list.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T: Clone> Clone for DoublyLinkedList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for DoublyLinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+// Display: [1 <-> 2 <-> 3], matching the arrows of the singly linked list's output
+impl<T: fmt::Display> fmt::Display for DoublyLinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[")?;
+        for (i, value) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, " <-> ")?;
+            }
+            write!(f, "{}", value)?;
+        }
+        write!(f, "]")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values<T: Clone>(list: &DoublyLinkedList<T>) -> Vec<T> {
+        list.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_all_basic_operations() {
+        let mut list = DoublyLinkedList::new();
+        assert!(list.is_empty());
+        assert_eq!(list.len(), 0);
+        assert_eq!(list.to_string(), "[]");
+
+        list.push_back(2);
+        list.push_back(3);
+        list.push_front(1);
+        assert_eq!(list.len(), 3);
+        assert_eq!((list.front(), list.back()), (Some(&1), Some(&3)));
+        assert_eq!(list.to_string(), "[1 <-> 2 <-> 3]");
+
+        *list.front_mut().unwrap() = 10;
+        *list.back_mut().unwrap() = 30;
+        assert_eq!(list.pop_front(), Some(10));
+        assert_eq!(list.pop_back(), Some(30));
+        assert_eq!(list.pop_back(), Some(2));
+        assert_eq!(list.pop_front(), None);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_iter_in_both_directions() {
+        let list: DoublyLinkedList<_> = (1..=5).collect();
+        assert_eq!(values(&list), vec![1, 2, 3, 4, 5]);
+        assert_eq!(
+            list.iter().rev().copied().collect::<Vec<_>>(),
+            vec![5, 4, 3, 2, 1]
+        );
+
+        // The two ends meet in the middle without handing out a value twice
+        let mut iter = list.iter();
+        assert_eq!((iter.next(), iter.next_back()), (Some(&1), Some(&5)));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&2, &3, &4]);
+
+        let mut owned = list.clone().into_iter();
+        assert_eq!((owned.next_back(), owned.next()), (Some(5), Some(1)));
+        assert_eq!(format!("{:?}", list), "[1, 2, 3, 4, 5]");
+    }
+
+    #[test]
+    fn test_cursor_walks_both_ways_through_the_ghost() {
+        let list: DoublyLinkedList<_> = ["a", "b", "c"].into_iter().collect();
+        let mut cursor = list.cursor_front();
+        assert_eq!((cursor.current(), cursor.index()), (Some(&"a"), Some(0)));
+        assert_eq!(cursor.peek_prev(), None, "The ghost sits before the front");
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!((cursor.current(), cursor.index()), (Some(&"c"), Some(2)));
+        assert_eq!(cursor.peek_prev(), Some(&"b"));
+
+        cursor.move_next();
+        assert_eq!((cursor.current(), cursor.index()), (None, None));
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&"a"), "Wraps round to the front");
+        cursor.move_prev();
+        cursor.move_prev();
+        assert_eq!((cursor.current(), cursor.index()), (Some(&"c"), Some(2)));
+
+        let back = list.cursor_back();
+        assert_eq!((back.current(), back.peek_next()), (Some(&"c"), None));
+    }
+
+    #[test]
+    fn test_cursor_mut_inserts_and_removes() {
+        let mut list: DoublyLinkedList<_> = [1, 3, 5].into_iter().collect();
+        let mut cursor = list.cursor_front_mut();
+        cursor.insert_after(2);
+        cursor.move_next();
+        assert_eq!(
+            (cursor.current().copied(), cursor.index()),
+            (Some(2), Some(1))
+        );
+        cursor.move_next();
+        cursor.insert_before(4);
+        assert_eq!(
+            cursor.index(),
+            Some(3),
+            "Inserting before shifts the cursor's index"
+        );
+        *cursor.current().unwrap() *= 10;
+        assert_eq!(values(&list), vec![1, 2, 4, 30, 5]);
+
+        let mut cursor = list.cursor_back_mut();
+        assert_eq!(cursor.remove_current(), Some(5));
+        assert_eq!(
+            cursor.current(),
+            None,
+            "Removing the back lands on the ghost"
+        );
+        cursor.move_prev();
+        assert_eq!(cursor.remove_current(), Some(30));
+        cursor.move_prev();
+        assert_eq!(cursor.remove_current(), Some(4));
+        assert_eq!(values(&list), vec![1, 2]);
+        assert_eq!(list.back(), Some(&2));
+    }
+
+    #[test]
+    fn test_unhappy_path_empty_list_and_ghost() {
+        let mut list: DoublyLinkedList<i32> = DoublyLinkedList::new();
+        assert_eq!((list.pop_front(), list.pop_back()), (None, None));
+        assert_eq!((list.front(), list.back()), (None, None));
+        let mut cursor = list.cursor_front();
+        cursor.move_prev();
+        assert_eq!((cursor.current(), cursor.peek_next()), (None, None));
+
+        let mut cursor = list.cursor_front_mut();
+        assert_eq!(cursor.remove_current(), None);
+        cursor.insert_before(2); // At the ghost: pushes to the back
+        cursor.insert_after(1); // At the ghost: pushes to the front
+        assert_eq!(cursor.index(), None, "Still on the ghost");
+        cursor.move_next();
+        assert_eq!(cursor.current().copied(), Some(1));
+        assert_eq!(values(&list), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_edge_case_single_element_is_both_ends() {
+        let mut list = DoublyLinkedList::new();
+        list.push_front(42);
+        assert_eq!(list.front(), list.back());
+        assert_eq!(list.pop_back(), Some(42));
+        assert!(list.is_empty());
+
+        list.push_back(7);
+        list.cursor_front_mut().remove_current();
+        assert!(list.is_empty());
+        list.push_front(8);
+        assert_eq!(
+            (list.front(), list.back(), list.len()),
+            (Some(&8), Some(&8), 1)
+        );
+    }
+
+    #[test]
+    fn test_edge_case_long_list_drops_without_overflow() {
+        let mut list = DoublyLinkedList::new();
+        for i in 0..1_000_000 {
+            list.push_back(i);
+        }
+        assert_eq!(list.len(), 1_000_000);
+        drop(list);
+    }
+
+    #[test]
+    fn test_every_value_is_dropped_exactly_once() {
+        let token = std::rc::Rc::new(());
+        let mut list: DoublyLinkedList<_> = (0..6).map(|_| std::rc::Rc::clone(&token)).collect();
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        drop(cursor.remove_current());
+        drop(list.pop_back());
+        assert_eq!(std::rc::Rc::strong_count(&token), 5);
+
+        let partly_used = list.clone().into_iter().skip(2);
+        drop(partly_used);
+        drop(list);
+        assert_eq!(std::rc::Rc::strong_count(&token), 1);
+    }
+}
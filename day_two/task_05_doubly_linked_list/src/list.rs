@@ -0,0 +1,423 @@
+//! The pointer-based doubly linked list.
+//!
+//! # Safety
+//!
+//! Every node is allocated with `Box::into_raw` and owned by exactly one list.
+//! The unsafe code relies on these invariants, which every method keeps:
+//!
+//! - `head` and `tail` are both `None` when `len == 0`, and otherwise point at
+//!   the first and last of exactly `len` live nodes.
+//! - Following `next` from `head` visits every node once and ends at `tail`,
+//!   whose `next` is `None`; `prev` links are the exact mirror image.
+//! - A node is only freed (with `Box::from_raw`) after it has been unlinked,
+//!   so no pointer left in the list can dangle.
+//!
+//! Borrows follow the list itself: `&self` methods, `Iter` and `Cursor` only
+//! read nodes and hand out `&T`s tied to a shared borrow of the list, while
+//! everything that writes a node or a link (including `CursorMut`) needs
+//! `&mut self`. So a `&mut T` is never handed out while any other reference
+//! to the same value exists.
+
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+
+type Link<T> = Option<NonNull<Node<T>>>;
+
+struct Node<T> {
+    value: T,
+    prev: Link<T>,
+    next: Link<T>,
+}
+
+/// A doubly linked list with O(1) pushes and pops at both ends and cursors
+/// that walk in either direction
+pub struct DoublyLinkedList<T> {
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+    // The list owns its nodes, for the drop checker and auto traits
+    _owns: PhantomData<Box<Node<T>>>,
+}
+
+// The list owns its values like a `Box` does, so it is `Send`/`Sync` exactly when they are
+unsafe impl<T: Send> Send for DoublyLinkedList<T> {}
+unsafe impl<T: Sync> Sync for DoublyLinkedList<T> {}
+
+impl<T> DoublyLinkedList<T> {
+    /// Creates an empty list
+    pub fn new() -> Self {
+        DoublyLinkedList {
+            head: None,
+            tail: None,
+            len: 0,
+            _owns: PhantomData,
+        }
+    }
+
+    /// Adds an element at the front
+    pub fn push_front(&mut self, value: T) {
+        let node = Self::allocate(value, None, self.head);
+        match self.head {
+            // SAFETY: `head` is a live node owned by this list
+            Some(head) => unsafe { (*head.as_ptr()).prev = Some(node) },
+            None => self.tail = Some(node),
+        }
+        self.head = Some(node);
+        self.len += 1;
+    }
+
+    /// Adds an element at the back
+    pub fn push_back(&mut self, value: T) {
+        let node = Self::allocate(value, self.tail, None);
+        match self.tail {
+            // SAFETY: `tail` is a live node owned by this list
+            Some(tail) => unsafe { (*tail.as_ptr()).next = Some(node) },
+            None => self.head = Some(node),
+        }
+        self.tail = Some(node);
+        self.len += 1;
+    }
+
+    /// Removes and returns the first element
+    pub fn pop_front(&mut self) -> Option<T> {
+        // SAFETY: `head` is a live node owned by this list
+        self.head.map(|head| unsafe { self.unlink(head) })
+    }
+
+    /// Removes and returns the last element
+    pub fn pop_back(&mut self) -> Option<T> {
+        // SAFETY: `tail` is a live node owned by this list
+        self.tail.map(|tail| unsafe { self.unlink(tail) })
+    }
+
+    /// Returns the first element
+    pub fn front(&self) -> Option<&T> {
+        // SAFETY: `head` is live, and the borrow is tied to `&self`
+        self.head.map(|head| unsafe { &(*head.as_ptr()).value })
+    }
+
+    /// Returns the last element
+    pub fn back(&self) -> Option<&T> {
+        // SAFETY: `tail` is live, and the borrow is tied to `&self`
+        self.tail.map(|tail| unsafe { &(*tail.as_ptr()).value })
+    }
+
+    /// Returns the first element mutably
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        // SAFETY: `head` is live, and the borrow is tied to `&mut self`
+        self.head.map(|head| unsafe { &mut (*head.as_ptr()).value })
+    }
+
+    /// Returns the last element mutably
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        // SAFETY: `tail` is live, and the borrow is tied to `&mut self`
+        self.tail.map(|tail| unsafe { &mut (*tail.as_ptr()).value })
+    }
+
+    /// Returns the number of elements
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if the list is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Removes every element, one node at a time
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    /// Iterates from front to back; `.rev()` walks back to front
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            front: self.head,
+            back: self.tail,
+            remaining: self.len,
+            _list: PhantomData,
+        }
+    }
+
+    /// Returns a read-only cursor at the first element
+    pub fn cursor_front(&self) -> Cursor<'_, T> {
+        Cursor {
+            current: self.head,
+            index: 0,
+            list: self,
+        }
+    }
+
+    /// Returns a read-only cursor at the last element
+    pub fn cursor_back(&self) -> Cursor<'_, T> {
+        Cursor {
+            current: self.tail,
+            index: self.len.saturating_sub(1),
+            list: self,
+        }
+    }
+
+    /// Returns a cursor at the first element that can edit the list
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            current: self.head,
+            index: 0,
+            list: self,
+        }
+    }
+
+    /// Returns a cursor at the last element that can edit the list
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            current: self.tail,
+            index: self.len.saturating_sub(1),
+            list: self,
+        }
+    }
+
+    fn allocate(value: T, prev: Link<T>, next: Link<T>) -> NonNull<Node<T>> {
+        let node = Box::new(Node { value, prev, next });
+        NonNull::from(Box::leak(node))
+    }
+
+    // Links a new node between `prev` and `next`, which must be adjacent (or the
+    // list's ends when `None`), and returns it
+    //
+    // SAFETY: `prev` and `next` must be live nodes of this list, or `None`
+    unsafe fn link_between(&mut self, value: T, prev: Link<T>, next: Link<T>) -> NonNull<Node<T>> {
+        let node = Self::allocate(value, prev, next);
+        match prev {
+            Some(prev) => (*prev.as_ptr()).next = Some(node),
+            None => self.head = Some(node),
+        }
+        match next {
+            Some(next) => (*next.as_ptr()).prev = Some(node),
+            None => self.tail = Some(node),
+        }
+        self.len += 1;
+        node
+    }
+
+    // Detaches `node` from its neighbours, frees it and returns its value
+    //
+    // SAFETY: `node` must be a live node of this list; it is dangling afterwards
+    unsafe fn unlink(&mut self, node: NonNull<Node<T>>) -> T {
+        let node = Box::from_raw(node.as_ptr());
+        match node.prev {
+            Some(prev) => (*prev.as_ptr()).next = node.next,
+            None => self.head = node.next,
+        }
+        match node.next {
+            Some(next) => (*next.as_ptr()).prev = node.prev,
+            None => self.tail = node.prev,
+        }
+        self.len -= 1;
+        node.value
+    }
+}
+
+impl<T> Default for DoublyLinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Drop: Popping one node at a time frees each node exactly once without recursion
+impl<T> Drop for DoublyLinkedList<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+/// Borrowing iterator over a `DoublyLinkedList`, usable from both ends
+pub struct Iter<'a, T> {
+    front: Link<T>,
+    back: Link<T>,
+    // Counting down stops the two ends from passing each other
+    remaining: usize,
+    _list: PhantomData<&'a T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.front.map(|node| {
+            // SAFETY: nodes in `remaining` are live while the list is borrowed for 'a
+            let node = unsafe { &*node.as_ptr() };
+            self.remaining -= 1;
+            self.front = node.next;
+            &node.value
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.back.map(|node| {
+            // SAFETY: as in `next`
+            let node = unsafe { &*node.as_ptr() };
+            self.remaining -= 1;
+            self.back = node.prev;
+            &node.value
+        })
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+/// A read-only position in a `DoublyLinkedList`
+///
+/// Besides the elements there is one "ghost" position, between the back and
+/// the front, where `current` is `None`. Moving past either end lands there,
+/// and moving on from it wraps to the other end.
+pub struct Cursor<'a, T> {
+    current: Link<T>,
+    // The ghost position's index is `len`
+    index: usize,
+    list: &'a DoublyLinkedList<T>,
+}
+
+impl<'a, T> Cursor<'a, T> {
+    /// Returns the element under the cursor, or `None` at the ghost position
+    pub fn current(&self) -> Option<&'a T> {
+        // SAFETY: the list is borrowed for 'a, so the node stays live and unchanged
+        self.current.map(|node| unsafe { &(*node.as_ptr()).value })
+    }
+
+    /// Returns the cursor's index from the front, or `None` at the ghost position
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    /// Moves towards the back
+    pub fn move_next(&mut self) {
+        (self.current, self.index) = step_next(self.list, self.current, self.index);
+    }
+
+    /// Moves towards the front
+    pub fn move_prev(&mut self) {
+        (self.current, self.index) = step_prev(self.list, self.current, self.index);
+    }
+
+    /// Returns the element after the cursor without moving
+    pub fn peek_next(&self) -> Option<&'a T> {
+        let (next, _) = step_next(self.list, self.current, self.index);
+        // SAFETY: as in `current`
+        next.map(|node| unsafe { &(*node.as_ptr()).value })
+    }
+
+    /// Returns the element before the cursor without moving
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        let (prev, _) = step_prev(self.list, self.current, self.index);
+        // SAFETY: as in `current`
+        prev.map(|node| unsafe { &(*node.as_ptr()).value })
+    }
+}
+
+/// A position in a `DoublyLinkedList` that can edit the list around it
+///
+/// Moves the same way as `Cursor`, including the ghost position.
+pub struct CursorMut<'a, T> {
+    current: Link<T>,
+    index: usize,
+    list: &'a mut DoublyLinkedList<T>,
+}
+
+impl<T> CursorMut<'_, T> {
+    /// Returns the element under the cursor, or `None` at the ghost position
+    pub fn current(&mut self) -> Option<&mut T> {
+        // SAFETY: the cursor holds the list's only borrow, and this one is tied to `&mut self`
+        self.current
+            .map(|node| unsafe { &mut (*node.as_ptr()).value })
+    }
+
+    /// Returns the cursor's index from the front, or `None` at the ghost position
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    /// Moves towards the back
+    pub fn move_next(&mut self) {
+        (self.current, self.index) = step_next(self.list, self.current, self.index);
+    }
+
+    /// Moves towards the front
+    pub fn move_prev(&mut self) {
+        (self.current, self.index) = step_prev(self.list, self.current, self.index);
+    }
+
+    /// Inserts `value` after the cursor; at the ghost position that is the front
+    pub fn insert_after(&mut self, value: T) {
+        match self.current {
+            // SAFETY: `node` is live, and `node.next` is its neighbour (or `None` at the back)
+            Some(node) => unsafe {
+                let next = (*node.as_ptr()).next;
+                self.list.link_between(value, Some(node), next);
+            },
+            None => {
+                self.list.push_front(value);
+                self.index += 1; // The ghost index follows the length
+            }
+        }
+    }
+
+    /// Inserts `value` before the cursor; at the ghost position that is the back
+    pub fn insert_before(&mut self, value: T) {
+        match self.current {
+            // SAFETY: as in `insert_after`, mirrored
+            Some(node) => unsafe {
+                let prev = (*node.as_ptr()).prev;
+                self.list.link_between(value, prev, Some(node));
+            },
+            None => self.list.push_back(value),
+        }
+        self.index += 1;
+    }
+
+    /// Removes the element under the cursor and moves on to the next one
+    ///
+    /// Returns `None`, removing nothing, at the ghost position.
+    pub fn remove_current(&mut self) -> Option<T> {
+        let node = self.current?;
+        // SAFETY: `node` is a live node of the list; we step off it before it is freed
+        unsafe {
+            self.current = (*node.as_ptr()).next;
+            Some(self.list.unlink(node))
+        }
+    }
+}
+
+// One step towards the back, wrapping through the ghost position
+fn step_next<T>(list: &DoublyLinkedList<T>, current: Link<T>, index: usize) -> (Link<T>, usize) {
+    match current {
+        // SAFETY: `node` is a live node of `list`
+        Some(node) => match unsafe { (*node.as_ptr()).next } {
+            Some(next) => (Some(next), index + 1),
+            None => (None, list.len),
+        },
+        None => (list.head, 0),
+    }
+}
+
+// One step towards the front, wrapping through the ghost position
+fn step_prev<T>(list: &DoublyLinkedList<T>, current: Link<T>, index: usize) -> (Link<T>, usize) {
+    match current {
+        // SAFETY: `node` is a live node of `list`
+        Some(node) => match unsafe { (*node.as_ptr()).prev } {
+            Some(prev) => (Some(prev), index - 1),
+            None => (None, list.len),
+        },
+        None => (list.tail, list.len.saturating_sub(1)),
+    }
+}
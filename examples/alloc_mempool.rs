@@ -0,0 +1,25 @@
+use rusty_repo::alloc_mempool::{MemoryBlock, MemoryPool};
+
+fn main() {
+    let mut pool = MemoryPool::new();
+
+    // Test fixed-size allocation
+    if let Some(block) = pool.allocate_fixed_size(1024) {
+        println!("Allocated fixed-size block of size {}: {:?}", block.size, block.data);
+    }
+
+    // Test variable-size allocation
+    if let Some(block) = pool.allocate_variable_size(512, 2048) {
+        println!("Allocated variable-size block of size {}: {:?}", block.size, block.data);
+    }
+
+    // Deallocate a block
+    let block_to_deallocate = MemoryBlock {
+        size: 1024,
+        data: vec![0; 1024],
+    };
+    pool.deallocate_block(block_to_deallocate);
+
+    // Show memory pool state after deallocation
+    println!("Memory pool after deallocation: {:?}", pool);
+}
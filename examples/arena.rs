@@ -0,0 +1,71 @@
+use rusty_repo::{Interner, MemoryArena};
+
+fn main() {
+    // Create an arena with 1024 bytes
+    let mut arena = MemoryArena::new(1024);
+
+    // Allocate 100 bytes from the arena
+    if let Some(ptr) = arena.allocate(100) {
+        println!("Allocated 100 bytes.");
+
+        // Use the pointer to store values directly into the allocated memory
+        unsafe {
+            // Example: Fill the allocated memory with a simple pattern (e.g., numbers 0 to 99)
+            for i in 0..100 {
+                *ptr.add(i) = i as u8; // Store values 0 to 99 in the allocated memory
+            }
+        }
+
+        // Read back the values from the allocated memory and print them
+        unsafe {
+            let values: Vec<u8> = (0..100)
+                .map(|i| *ptr.add(i))  // Read each byte from the allocated memory
+                .collect();
+            println!("First 10 values allocated: {:?}", &values[0..10]);
+        }
+    } else {
+        println!("Failed to allocate 100 bytes.");
+    }
+
+    // Allocate another chunk (200 bytes)
+    if let Some(ptr) = arena.allocate(200) {
+        println!("Allocated 200 bytes.");
+
+        // Similarly, fill the next 200 bytes with a different pattern (e.g., values 100 to 299)
+        unsafe {
+            for i in 0..200 {
+                *ptr.add(i) = (i + 100) as u8; // Store values 100 to 299 in the allocated memory
+            }
+        }
+
+        // Read back the values from the allocated memory
+        unsafe {
+            let values: Vec<u8> = (0..200)
+                .map(|i| *ptr.add(i))  // Read each byte from the allocated memory
+                .collect();
+            println!("First 10 values allocated in second chunk: {:?}", &values[0..10]);
+        }
+    } else {
+        println!("Failed to allocate 200 bytes.");
+    }
+
+    // Check the remaining memory
+    println!("Remaining memory: {} bytes", arena.remaining());
+
+    // Reset the arena (reuse the memory block)
+    arena.reset();
+    println!("Arena has been reset.");
+    println!("Remaining memory after reset: {} bytes", arena.remaining());
+
+    // Intern identifiers: each distinct string is copied into an arena once
+    let mut interner = Interner::with_static(&["fn", "let"]);
+    let tokens = ["let", "x", "=", "x", "+", "y"];
+    let symbols: Vec<_> = tokens.iter().map(|token| interner.intern(token)).collect();
+    println!("Symbols: {:?}", symbols.iter().map(|s| s.as_u32()).collect::<Vec<_>>());
+    println!("Symbol 1 resolves to {:?}", interner.resolve(symbols[1]));
+    println!(
+        "{} distinct strings, {} bytes copied into arenas",
+        interner.len(),
+        interner.arena_bytes()
+    );
+}
@@ -0,0 +1,25 @@
+use rusty_repo::BalancedTree;
+
+fn main() {
+    // Inserting sorted keys would turn a plain BST into a linked list
+    let mut scores = BalancedTree::new();
+    for (i, name) in ["ana", "ben", "cal", "dee", "eve", "fox", "gus"]
+        .iter()
+        .enumerate()
+    {
+        scores.insert(*name, (i as u32 + 1) * 10);
+    }
+    println!("{} entries, height {}", scores.len(), scores.height());
+    println!("In order: {:?}", scores);
+
+    // Rank/select: position of a key, and key at a position
+    println!("Rank of \"dee\": {}", scores.rank("dee"));
+    println!("Median entry: {:?}", scores.select(scores.len() / 2));
+
+    scores.remove("ben");
+    println!(
+        "After removing ben: {:?}",
+        scores.keys().collect::<Vec<_>>()
+    );
+    println!("Invariants hold: {:?}", scores.validate());
+}
@@ -0,0 +1,33 @@
+use rusty_repo::bench::{self, Benchmark};
+#[cfg(not(feature = "safe-only"))]
+use rusty_repo::CustomAllocator;
+
+// Counting allocator, so `graph_allocations` can see what each graph holds;
+// `safe-only` leaves it out of the facade, and the counts are then zero
+#[cfg(not(feature = "safe-only"))]
+#[global_allocator]
+static GLOBAL: CustomAllocator = CustomAllocator;
+
+fn main() {
+    if cfg!(debug_assertions) {
+        println!("Debug build: run with --release for meaningful numbers\n");
+    }
+
+    for comparison in bench::all() {
+        println!("{}\n", comparison);
+    }
+
+    let (arena, rc) = bench::graph_allocations(10_000, 4);
+    println!("Allocations held by a 10000-node graph: ArenaGraph {arena}, Rc graph {rc}\n");
+
+    let stats = bench::queue_amortization(10_000);
+    println!(
+        "Queue over 10000 enqueues: {} transfers moved {} elements, deepest stack {}\n",
+        stats.transfers, stats.elements_moved, stats.max_stack_depth
+    );
+
+    // Custom measurements use the same harness
+    let report =
+        Benchmark::new("String formatting").run(|b| b.iter(|| format!("{}-{}", "item", 42)));
+    println!("{}", report);
+}
@@ -0,0 +1,32 @@
+use rusty_repo::BitVec;
+
+fn main() {
+    // Track which of 100 blocks are in use
+    let mut used = BitVec::with_len(100);
+    for block in [0, 1, 2, 5, 64, 99] {
+        used.set(block, true);
+    }
+    println!("Blocks in use: {:?}", used.iter_ones().collect::<Vec<_>>());
+    println!("First free block: {:?}", used.first_zero());
+    println!("{} used, {} free", used.count_ones(), used.count_zeros());
+
+    // Rank and select: how many used blocks come before block 64, and where the 4th one is
+    println!("Used blocks before 64: {}", used.rank(64));
+    println!("Index of used block #4: {:?}", used.select(4));
+
+    // Sets of small integers: even and multiples-of-three numbers below 20
+    let evens: BitVec = (0..20).map(|n| n % 2 == 0).collect();
+    let threes: BitVec = (0..20).map(|n| n % 3 == 0).collect();
+    println!(
+        "Both: {:?}",
+        (&evens & &threes).iter_ones().collect::<Vec<_>>()
+    );
+    println!(
+        "Either: {:?}",
+        (&evens | &threes).iter_ones().collect::<Vec<_>>()
+    );
+    println!(
+        "Exactly one: {:?}",
+        (&evens ^ &threes).iter_ones().collect::<Vec<_>>()
+    );
+}
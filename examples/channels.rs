@@ -0,0 +1,118 @@
+#[cfg(not(feature = "safe-only"))]
+use rusty_repo::channels::{mpmc, select, spsc, EventBus, LagPolicy, TrySendError};
+#[cfg(not(feature = "safe-only"))]
+use std::thread;
+
+#[cfg(not(feature = "safe-only"))]
+fn main() {
+    // A small buffer between a fast producer and a slower consumer
+    let (tx, rx) = spsc::channel(4);
+
+    let producer = thread::spawn(move || {
+        for line in [
+            "parse",
+            "validate",
+            "transform",
+            "store",
+            "report",
+            "archive",
+        ] {
+            tx.send(line.to_string()).unwrap();
+        }
+        println!("Producer done; dropping the sender disconnects the channel");
+    });
+
+    for (i, stage) in rx.iter().enumerate() {
+        println!("Received #{}: {}", i, stage);
+    }
+    producer.join().unwrap();
+    println!("Receiver saw the disconnect once the channel was drained");
+
+    // try_send reports backpressure instead of blocking
+    let (tx, rx) = spsc::channel(2);
+    tx.try_send(1).unwrap();
+    tx.try_send(2).unwrap();
+    match tx.try_send(3) {
+        Err(TrySendError::Full(value)) => println!("Channel full, got {} back", value),
+        other => println!("Unexpected: {:?}", other),
+    }
+    drop(rx);
+    println!("Send after the receiver dropped: {:?}", tx.send(4));
+
+    // Fan-out: several workers share one job queue, each job goes to exactly one of them
+    let (jobs_tx, jobs) = mpmc::channel(8);
+    let (results_tx, results) = mpmc::channel(8);
+    let workers: Vec<_> = (0..3)
+        .map(|id| {
+            let jobs = jobs.clone();
+            let results = results_tx.clone();
+            thread::spawn(move || {
+                for n in jobs.iter() {
+                    results.send((id, n, n * n)).unwrap();
+                }
+            })
+        })
+        .collect();
+    drop((jobs, results_tx));
+    for n in 1..=6u64 {
+        jobs_tx.send(n).unwrap();
+    }
+    drop(jobs_tx);
+    let mut squares: Vec<_> = results.iter().collect();
+    squares.sort_by_key(|&(_, n, _)| n);
+    for (worker, n, square) in squares {
+        println!("Worker {} squared {} = {}", worker, n, square);
+    }
+    for worker in workers {
+        worker.join().unwrap();
+    }
+
+    // Fan-in: wait on two channels of different types at once
+    let (alerts_tx, alerts) = mpmc::channel::<&str>(1);
+    let (ticks_tx, ticks) = mpmc::channel::<u32>(1);
+    thread::spawn(move || {
+        ticks_tx.send(1).unwrap();
+        alerts_tx.send("disk almost full").unwrap();
+    });
+    let mut open = 2;
+    while open > 0 {
+        select! {
+            recv(alerts) -> msg => match msg {
+                Ok(alert) => println!("Alert: {}", alert),
+                Err(_) => open -= 1,
+            },
+            recv(ticks) -> msg => match msg {
+                Ok(tick) => println!("Tick {}", tick),
+                Err(_) => open -= 1,
+            },
+        }
+    }
+    println!("Both channels disconnected");
+
+    // Pub/sub: each subscriber filters topics and picks how it handles falling behind
+    let bus = EventBus::new();
+    let audit = bus.subscribe("*", 16, LagPolicy::Block);
+    let dashboard = bus.subscribe("orders.*", 2, LagPolicy::DropOldest);
+    let publisher = thread::spawn(move || {
+        for id in 1..=4 {
+            bus.publish("orders.created", id);
+        }
+        bus.publish("users.created", 99);
+    });
+    publisher.join().unwrap();
+    let latest: Vec<u32> = dashboard.try_iter().map(|event| event.payload).collect();
+    println!(
+        "Dashboard saw {:?}, {} dropped while it lagged",
+        latest,
+        dashboard.dropped()
+    );
+    while let Ok(event) = audit.recv() {
+        println!("Audit: {} {}", event.topic, event.payload);
+    }
+}
+
+// The channels use unsafe code internally, so `safe-only` leaves them out of the facade
+#[cfg(feature = "safe-only")]
+fn main() {
+    println!("This example needs the channels, which `safe-only` leaves out");
+}
@@ -0,0 +1,30 @@
+use rusty_repo::circular_buffer::OverwritePolicy;
+use rusty_repo::CircularBuffer;
+
+fn main() {
+    let mut cb = CircularBuffer::<i32>::new(3);
+
+    cb.push(1);
+    cb.push(2);
+    cb.push(3); // Buffer is now full: [1, 2, 3]
+    println!("Popped: {:?}", cb.pop()); // Should print Some(1)
+    println!("Popped: {:?}", cb.pop()); // Should print Some(2)
+    cb.push(4); // Buffer now: [None, None, 4]
+    println!("Buffer size: {}", cb.size());
+
+    // A buffer that turns new elements away when full instead of overwriting
+    let mut strict = CircularBuffer::with_policy(2, OverwritePolicy::RejectWhenFull);
+    strict.push("a");
+    strict.push("b");
+    println!("Rejected: {:?}", strict.push("c")); // Should print Some("c")
+    println!("Kept: {:?}", strict.iter().collect::<Vec<_>>());
+
+    // A buffer that doubles its capacity instead of dropping anything
+    let mut growable = CircularBuffer::growable(2);
+    for i in 1..=5 {
+        growable.push(i);
+    }
+    println!("Capacity after 5 pushes: {}", growable.capacity()); // Should print 8
+    growable.shrink_to_fit();
+    println!("Capacity after shrinking: {}", growable.capacity()); // Should print 5
+}
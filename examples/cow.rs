@@ -0,0 +1,216 @@
+#[cfg(not(feature = "safe-only"))]
+use rusty_repo::cow::atomic_cow::AtomicCow;
+use rusty_repo::cow::cow_map::CowMap;
+use rusty_repo::cow::cow_vec::CowVec;
+use rusty_repo::CopyOnWrite;
+#[cfg(not(feature = "safe-only"))]
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(not(feature = "safe-only"))]
+use std::sync::{Arc, RwLock};
+#[cfg(not(feature = "safe-only"))]
+use std::thread;
+#[cfg(not(feature = "safe-only"))]
+use std::time::Duration;
+use std::time::Instant;
+
+fn main() {
+    let cow = CopyOnWrite::new(vec![1, 2, 3, 4, 5]);
+
+    // Read data
+    let data = cow.read();
+    println!("Initial data: {:?}", data);
+
+    // Modify data using write
+    cow.write(|data| {
+        data.push(6);
+    });
+
+    // Read the modified data
+    let modified_data = cow.read();
+    println!("Modified data: {:?}", modified_data);
+
+    // Subscribe to changes instead of polling read()
+    let updates = cow.subscribe();
+    cow.write(|data| data.retain(|x| x % 2 == 0));
+    if let Ok(snapshot) = updates.try_recv() {
+        println!("Subscriber received: {:?}", snapshot);
+    }
+
+    // Batch several mutations into one transaction
+    let result: Result<(), String> = cow.transaction(|data| {
+        data.push(7);
+        data.push(8);
+        Ok(())
+    });
+    println!("Committed transaction: {:?} -> {:?}", result, cow.read());
+    let result = cow.transaction(|data| {
+        data.clear();
+        Err("validation failed")
+    });
+    println!("Rolled back transaction: {:?} -> {:?}", result, cow.read());
+
+    // Is copy-on-write paying off?
+    println!("Stats: {:?}", cow.stats());
+
+    // Drop-in trait impls
+    let greeting: CopyOnWrite<String> = String::from("hello").into();
+    let empty: CopyOnWrite<String> = CopyOnWrite::default();
+    greeting.write(|text| text.push_str(", COW"));
+    println!("Display: {}, equal to default: {}", greeting, greeting == empty);
+
+    // Async variant, for services running on tokio
+    #[cfg(feature = "async")]
+    async_example();
+
+    // Lock-free variant, which `safe-only` leaves out since it is built on raw `Arc` pointers
+    #[cfg(not(feature = "safe-only"))]
+    atomic_example();
+
+    // Structural sharing: a write only clones the touched chunk/bucket
+    let flat = CopyOnWrite::new((0..1_000_000).collect::<Vec<u64>>());
+    let chunked = CopyOnWrite::new((0..1_000_000).collect::<CowVec<u64>>());
+    let start = Instant::now();
+    for i in 0..100 {
+        let _reader = flat.read();
+        flat.write(|data| data[i] += 1);
+    }
+    println!("100 writes on a shared Vec:    {:?}", start.elapsed());
+    let start = Instant::now();
+    for i in 0..100 {
+        let _reader = chunked.read();
+        chunked.write(|data| {
+            if let Some(value) = data.get_mut(i) {
+                *value += 1;
+            }
+        });
+    }
+    println!("100 writes on a shared CowVec: {:?}", start.elapsed());
+
+    let mut vec: CowVec<i32> = CowVec::new();
+    vec.push(1);
+    vec.push(2);
+    let snapshot = vec.clone();
+    vec.set(0, 10);
+    println!("CowVec popped: {:?}", vec.pop());
+    println!(
+        "CowVec: {:?} (len {}, empty: {}), snapshot first: {:?}, shared chunks: {}",
+        vec.iter().collect::<Vec<_>>(),
+        vec.len(),
+        vec.is_empty(),
+        snapshot.get(0),
+        vec.shared_chunks(&snapshot)
+    );
+
+    let mut map = CowMap::new();
+    map.insert("host", "localhost");
+    map.insert("port", "8080");
+    let snapshot = map.clone();
+    map.remove(&"port");
+    println!(
+        "CowMap: {:?} (len {}, empty: {}), snapshot has port: {}, current has port: {}",
+        map.iter().collect::<Vec<_>>(),
+        map.len(),
+        map.is_empty(),
+        snapshot.contains_key(&"port"),
+        map.get(&"port").is_some()
+    );
+}
+
+/// `AsyncCow` on a current-thread runtime
+#[cfg(feature = "async")]
+fn async_example() {
+    use rusty_repo::cow::async_cow::AsyncCow;
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .expect("failed to build tokio runtime");
+
+    runtime.block_on(async {
+        let config = AsyncCow::new(vec!["a"]);
+        config.write(|data| data.push("b")).await;
+        let (version, data) = config.snapshot().await;
+        println!("AsyncCow v{} (version() = {}): {:?}", version, config.version(), data);
+        println!("AsyncCow read: {:?}", config.read().await);
+    });
+}
+
+/// `AtomicCow`, where readers never take a lock, and its read benchmark
+#[cfg(not(feature = "safe-only"))]
+fn atomic_example() {
+    let atomic = AtomicCow::new(vec![1, 2, 3]);
+    atomic.update(|data| data.push(4));
+    let previous = atomic.store(vec![10, 20]);
+    println!("AtomicCow previous: {:?}, current: {:?}", previous, atomic.load());
+
+    println!("Running read benchmark (4 readers, 1 writer)...");
+    run_benchmark(4, 200_000);
+}
+
+/// Compare read throughput of `AtomicCow` against the `RwLock` based `CopyOnWrite`
+/// design while a single writer keeps publishing new versions.
+#[cfg(not(feature = "safe-only"))]
+fn run_benchmark(readers: usize, reads_per_reader: usize) {
+    let atomic = Arc::new(AtomicCow::new(vec![0u64; 64]));
+    let elapsed = bench(readers, reads_per_reader, {
+        let atomic = Arc::clone(&atomic);
+        move || atomic.load().len()
+    }, {
+        let atomic = Arc::clone(&atomic);
+        move || {
+            atomic.update(|data| data[0] += 1);
+        }
+    });
+    println!("AtomicCow:        {:?} for {} reads", elapsed, readers * reads_per_reader);
+
+    let locked = Arc::new(RwLock::new(Arc::new(vec![0u64; 64])));
+    let elapsed = bench(readers, reads_per_reader, {
+        let locked = Arc::clone(&locked);
+        move || locked.read().unwrap().clone().len()
+    }, {
+        let locked = Arc::clone(&locked);
+        move || {
+            let mut lock = locked.write().unwrap();
+            Arc::make_mut(&mut lock)[0] += 1;
+        }
+    });
+    println!("RwLock<Arc<T>>:   {:?} for {} reads", elapsed, readers * reads_per_reader);
+}
+
+#[cfg(not(feature = "safe-only"))]
+fn bench<R, W>(readers: usize, reads_per_reader: usize, read: R, write: W) -> Duration
+where
+    R: Fn() -> usize + Send + Clone + 'static,
+    W: Fn() + Send + 'static,
+{
+    let done = Arc::new(AtomicUsize::new(0));
+    let writer = {
+        let done = Arc::clone(&done);
+        thread::spawn(move || {
+            while done.load(Ordering::Relaxed) < readers {
+                write();
+            }
+        })
+    };
+
+    let start = Instant::now();
+    let handles: Vec<_> = (0..readers)
+        .map(|_| {
+            let read = read.clone();
+            let done = Arc::clone(&done);
+            thread::spawn(move || {
+                let mut total = 0;
+                for _ in 0..reads_per_reader {
+                    total += read();
+                }
+                done.fetch_add(1, Ordering::Relaxed);
+                total
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    let elapsed = start.elapsed();
+    writer.join().unwrap();
+    elapsed
+}
@@ -0,0 +1,26 @@
+#[cfg(not(feature = "safe-only"))]
+use rusty_repo::CustomAllocator;
+#[cfg(not(feature = "safe-only"))]
+use std::alloc::{GlobalAlloc, Layout};
+
+#[cfg(not(feature = "safe-only"))]
+fn main() {
+    // Example:
+    let layout = Layout::from_size_align(1024, 8).unwrap();
+    let ptr = unsafe { CustomAllocator.alloc(layout) };
+    if !ptr.is_null() {
+        println!("Allocated memory at: {:?}", ptr);
+        println!("Current allocated bytes: {}", CustomAllocator::now_allocated());
+        // Use the memory...
+        unsafe { CustomAllocator.dealloc(ptr, layout) };
+        println!("After deallocation, current allocated bytes: {}", CustomAllocator::now_allocated());
+    } else {
+        println!("Memory allocation failed");
+    }
+}
+
+// `CustomAllocator` implements `GlobalAlloc`, so `safe-only` leaves it out of the facade
+#[cfg(feature = "safe-only")]
+fn main() {
+    println!("This example needs `CustomAllocator`, which `safe-only` leaves out");
+}
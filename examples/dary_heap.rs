@@ -0,0 +1,118 @@
+//! Dijkstra on a random graph with d-ary heaps of several widths, compared
+//! against `std::collections::BinaryHeap` with lazy deletion.
+//!
+//! Run with `cargo run --release --example dary_heap` for meaningful timings.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::time::{Duration, Instant};
+
+use rusty_repo::dary_heap::Handle;
+use rusty_repo::DaryHeap;
+
+const NODES: usize = 50_000;
+const EDGES_PER_NODE: usize = 8;
+
+/// Small xorshift generator so the example needs no dependencies
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+fn random_graph() -> Vec<Vec<(usize, u64)>> {
+    let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+    (0..NODES)
+        .map(|_| {
+            (0..EDGES_PER_NODE)
+                .map(|_| ((rng.next() % NODES as u64) as usize, rng.next() % 1000 + 1))
+                .collect()
+        })
+        .collect()
+}
+
+/// Dijkstra with a real decrease-key: each node is in the heap at most once
+fn dijkstra_dary<const D: usize>(graph: &[Vec<(usize, u64)>]) -> Vec<u64> {
+    let mut dist = vec![u64::MAX; graph.len()];
+    let mut handles: Vec<Option<Handle>> = vec![None; graph.len()];
+    let mut heap: DaryHeap<(u64, usize), D> = DaryHeap::with_capacity(graph.len());
+    dist[0] = 0;
+    handles[0] = Some(heap.push((0, 0)));
+
+    while let Some((d, node)) = heap.pop() {
+        for &(next, weight) in &graph[node] {
+            let candidate = d + weight;
+            if candidate < dist[next] {
+                dist[next] = candidate;
+                match handles[next] {
+                    Some(handle) if heap.contains(handle) => {
+                        heap.decrease_key(handle, (candidate, next)).unwrap();
+                    }
+                    _ => handles[next] = Some(heap.push((candidate, next))),
+                }
+            }
+        }
+    }
+    dist
+}
+
+/// Dijkstra without decrease-key: push duplicates and skip stale entries
+fn dijkstra_std(graph: &[Vec<(usize, u64)>]) -> Vec<u64> {
+    let mut dist = vec![u64::MAX; graph.len()];
+    let mut heap = BinaryHeap::new();
+    dist[0] = 0;
+    heap.push(Reverse((0, 0)));
+
+    while let Some(Reverse((d, node))) = heap.pop() {
+        if d > dist[node] {
+            continue;
+        }
+        for &(next, weight) in &graph[node] {
+            let candidate = d + weight;
+            if candidate < dist[next] {
+                dist[next] = candidate;
+                heap.push(Reverse((candidate, next)));
+            }
+        }
+    }
+    dist
+}
+
+fn time(label: &str, run: impl Fn() -> Vec<u64>) -> Vec<u64> {
+    let mut best = Duration::MAX;
+    let mut result = Vec::new();
+    for _ in 0..3 {
+        let start = Instant::now();
+        result = run();
+        best = best.min(start.elapsed());
+    }
+    println!("{:<28} {:>10.2?}", label, best);
+    result
+}
+
+fn main() {
+    let graph = random_graph();
+    println!(
+        "Dijkstra over {} nodes and {} edges (best of 3):",
+        NODES,
+        NODES * EDGES_PER_NODE
+    );
+
+    let expected = time("std BinaryHeap (lazy)", || dijkstra_std(&graph));
+    let results = [
+        time("DaryHeap<_, 2> decrease-key", || dijkstra_dary::<2>(&graph)),
+        time("DaryHeap<_, 4> decrease-key", || dijkstra_dary::<4>(&graph)),
+        time("DaryHeap<_, 8> decrease-key", || dijkstra_dary::<8>(&graph)),
+    ];
+    assert!(
+        results.iter().all(|dist| *dist == expected),
+        "all heaps agree on distances"
+    );
+    let reachable = expected.iter().filter(|&&d| d != u64::MAX).count();
+    println!("All heaps agree; {} nodes reachable", reachable);
+}
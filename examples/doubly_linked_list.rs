@@ -0,0 +1,24 @@
+use rusty_repo::DoublyLinkedList;
+
+fn main() {
+    let mut list: DoublyLinkedList<_> = (2..=4).collect();
+    list.push_front(1);
+    list.push_back(5);
+    println!("List: {}", list);
+    println!("Front: {:?}, back: {:?}", list.front(), list.back());
+    println!("Backwards: {:?}", list.iter().rev().collect::<Vec<_>>());
+
+    // Walk to the middle and edit around it
+    let mut cursor = list.cursor_front_mut();
+    cursor.move_next();
+    cursor.move_next();
+    println!("Cursor at index {:?}: {:?}", cursor.index(), cursor.current());
+    cursor.insert_before(25);
+    cursor.insert_after(35);
+    println!("Removed: {:?}", cursor.remove_current());
+    println!("After editing: {}", list);
+
+    println!("Popped front: {:?}", list.pop_front());
+    println!("Popped back: {:?}", list.pop_back());
+    println!("Length: {}", list.len());
+}
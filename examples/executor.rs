@@ -0,0 +1,58 @@
+use rusty_repo::executor::{block_on, yield_now};
+use rusty_repo::Executor;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+fn main() {
+    // block_on drives a single future on the current thread
+    let answer = block_on(async { 6 * 7 });
+    println!("block_on: {}", answer);
+
+    // The executor interleaves tasks at every await point
+    let executor = Executor::new();
+    let log = Rc::new(RefCell::new(Vec::new()));
+    for name in ["download", "parse", "index"] {
+        let log = Rc::clone(&log);
+        executor.spawn(async move {
+            for step in 1..=3 {
+                log.borrow_mut().push(format!("{} step {}", name, step));
+                yield_now().await;
+            }
+        });
+    }
+    executor.run();
+    for line in log.borrow().iter() {
+        println!("{}", line);
+    }
+
+    // Tasks return values through their JoinHandle
+    let sizes: Vec<_> = ["a.txt", "bb.txt", "ccc.txt"]
+        .into_iter()
+        .map(|name| executor.spawn(async move { name.len() }))
+        .collect();
+    let total = executor.block_on(async move {
+        let mut total = 0;
+        for size in sizes {
+            total += size.await;
+        }
+        total
+    });
+    println!("Total name length: {}", total);
+
+    #[cfg(feature = "multi-thread")]
+    {
+        use rusty_repo::executor::ThreadPoolExecutor;
+        let pool = ThreadPoolExecutor::new(4);
+        let squares: Vec<_> = (1..=5u64)
+            .map(|n| pool.spawn(async move { n * n }))
+            .collect();
+        let sum = pool.block_on(async move {
+            let mut sum = 0;
+            for square in squares {
+                sum += square.await;
+            }
+            sum
+        });
+        println!("Sum of squares on the thread pool: {}", sum);
+    }
+}
@@ -0,0 +1,65 @@
+#[cfg(not(feature = "safe-only"))]
+use rusty_repo::fallback_alloc::{ArenaAllocator, Owns};
+#[cfg(not(feature = "safe-only"))]
+use rusty_repo::{CustomAllocator, FallbackAllocator, FreeListAllocator};
+#[cfg(not(feature = "safe-only"))]
+use std::alloc::{GlobalAlloc, Layout};
+
+// Small allocations come from a static region; anything that doesn't fit goes
+// to task_06's tracking system allocator
+#[cfg(not(feature = "safe-only"))]
+#[global_allocator]
+static HEAP: FallbackAllocator<FreeListAllocator<{ 64 * 1024 }>, CustomAllocator> =
+    FallbackAllocator::new(FreeListAllocator::new(), CustomAllocator);
+
+#[cfg(not(feature = "safe-only"))]
+fn main() {
+    let names: Vec<String> = (0..50).map(|i| format!("item-{}", i)).collect();
+    println!(
+        "50 small strings: from the region = {}, system bytes in use = {}",
+        HEAP.primary().contains(names[0].as_ptr()),
+        CustomAllocator::now_allocated()
+    );
+
+    let big = vec![0u8; 256 * 1024];
+    println!(
+        "A 256 KiB buffer: from the region = {}, system bytes in use = {}, fallbacks so far = {}",
+        HEAP.primary().contains(big.as_ptr()),
+        CustomAllocator::now_allocated(),
+        HEAP.fallback_count()
+    );
+    drop(big);
+    println!(
+        "After dropping it: system bytes in use = {}",
+        CustomAllocator::now_allocated()
+    );
+
+    // Composed by hand: a scratch arena in front of the free-list region
+    let scratch =
+        FallbackAllocator::new(ArenaAllocator::new(512), FreeListAllocator::<4096>::new());
+    let layout = Layout::from_size_align(200, 8).unwrap();
+    unsafe {
+        let blocks: Vec<*mut u8> = (0..4).map(|_| scratch.alloc(layout)).collect();
+        for (i, &block) in blocks.iter().enumerate() {
+            let home = if scratch.primary().owns(block) {
+                "arena"
+            } else {
+                "free list"
+            };
+            println!("Scratch block {} came from the {}", i, home);
+        }
+        for block in blocks {
+            scratch.dealloc(block, layout);
+        }
+    }
+    println!(
+        "Free-list bytes still allocated: {}",
+        scratch.secondary().allocated_bytes()
+    );
+}
+
+// The allocators implement `GlobalAlloc`, so `safe-only` leaves them out of the facade
+#[cfg(feature = "safe-only")]
+fn main() {
+    println!("This example needs the allocators, which `safe-only` leaves out");
+}
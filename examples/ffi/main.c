@@ -0,0 +1,65 @@
+/*
+ * Drives the ring buffer and memory pool through the C interface.
+ *
+ *   cargo rustc --release --lib --features ffi --crate-type staticlib
+ *   cc examples/ffi/main.c -Iinclude target/release/librusty_repo.a -lpthread -ldl -lm -o target/ffi_demo
+ *   ./target/ffi_demo
+ */
+#include <assert.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "rusty_repo.h"
+
+static void ring_buffer_demo(void) {
+    RustyByteRingBuffer *buffer = rusty_byte_ring_buffer_create(8);
+    assert(buffer != NULL);
+
+    const char *message = "hello, ring buffer";
+    for (size_t i = 0; i < strlen(message); i++) {
+        rusty_byte_ring_buffer_push(buffer, (uint8_t)message[i]);
+    }
+    assert(rusty_byte_ring_buffer_is_full(buffer));
+
+    char tail[9] = {0};
+    uint8_t byte;
+    size_t n = 0;
+    while (rusty_byte_ring_buffer_pop(buffer, &byte)) {
+        tail[n++] = (char)byte;
+    }
+    printf("ring buffer kept the last %zu bytes: \"%s\"\n", n, tail);
+    assert(strcmp(tail, "g buffer") == 0);
+    assert(rusty_byte_ring_buffer_len(buffer) == 0);
+
+    rusty_byte_ring_buffer_destroy(buffer);
+}
+
+static void memory_pool_demo(void) {
+    RustyMemoryPoolHandle *pool = rusty_memory_pool_create(256, 4);
+    assert(pool != NULL);
+
+    uint8_t *chunks[4];
+    for (int i = 0; i < 4; i++) {
+        chunks[i] = rusty_memory_pool_allocate(pool);
+        assert(chunks[i] != NULL);
+        memset(chunks[i], 'a' + i, rusty_memory_pool_chunk_size(pool));
+    }
+    assert(rusty_memory_pool_allocate(pool) == NULL);
+    printf("pool handed out 4 chunks of %zu bytes, %zu left\n",
+           rusty_memory_pool_chunk_size(pool), rusty_memory_pool_available(pool));
+
+    for (int i = 0; i < 4; i++) {
+        rusty_memory_pool_deallocate(pool, chunks[i]);
+    }
+    printf("after returning them: %zu available\n", rusty_memory_pool_available(pool));
+    assert(rusty_memory_pool_available(pool) == 4);
+
+    rusty_memory_pool_destroy(pool);
+}
+
+int main(void) {
+    ring_buffer_demo();
+    memory_pool_demo();
+    puts("ok");
+    return 0;
+}
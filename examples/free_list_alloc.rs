@@ -0,0 +1,63 @@
+#[cfg(not(feature = "safe-only"))]
+use rusty_repo::FreeListAllocator;
+#[cfg(not(feature = "safe-only"))]
+use std::alloc::{GlobalAlloc, Layout};
+
+// The whole demo, std runtime included, allocates from this 1 MiB static region
+#[cfg(not(feature = "safe-only"))]
+#[global_allocator]
+static HEAP: FreeListAllocator<{ 1024 * 1024 }> = FreeListAllocator::new();
+
+#[cfg(not(feature = "safe-only"))]
+fn report(label: &str) {
+    println!(
+        "{:<28} allocated: {:>6} B, free blocks: {}, largest free block: {:>7} B",
+        label,
+        HEAP.allocated_bytes(),
+        HEAP.free_blocks(),
+        HEAP.largest_free_block()
+    );
+}
+
+#[cfg(not(feature = "safe-only"))]
+fn main() {
+    report("At startup");
+
+    let names: Vec<String> = (0..100).map(|i| format!("user-{}", i)).collect();
+    report("After 100 strings");
+
+    let kept: Vec<&String> = names.iter().step_by(2).collect();
+    println!("Kept {} of {} names", kept.len(), names.len());
+    drop(kept);
+    drop(names);
+    report("After dropping them");
+
+    // A separate small allocator, driven by hand
+    let small = FreeListAllocator::<4096>::new();
+    let layout = Layout::from_size_align(256, 64).unwrap();
+    unsafe {
+        let blocks: Vec<*mut u8> = (0..4).map(|_| small.alloc(layout)).collect();
+        println!(
+            "4 x 256 B aligned to 64: {:?}",
+            blocks.iter().map(|p| *p as usize % 64).collect::<Vec<_>>()
+        );
+        println!(
+            "Free blocks, including alignment padding: {}",
+            small.free_blocks()
+        );
+        for &block in blocks.iter().rev() {
+            small.dealloc(block, layout);
+        }
+    }
+    println!(
+        "After freeing all, the blocks coalesce: {} free block of {} B",
+        small.free_blocks(),
+        small.largest_free_block()
+    );
+}
+
+// `FreeListAllocator` implements `GlobalAlloc`, so `safe-only` leaves it out of the facade
+#[cfg(feature = "safe-only")]
+fn main() {
+    println!("This example needs `FreeListAllocator`, which `safe-only` leaves out");
+}
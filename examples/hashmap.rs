@@ -0,0 +1,26 @@
+use rusty_repo::hashmap::FxBuildHasher;
+use rusty_repo::MyHashMap;
+
+fn main() {
+    // Default hasher: SipHash via RandomState
+    let mut ages = MyHashMap::new();
+    ages.insert("alice", 31);
+    ages.insert("bob", 27);
+    println!("Alice is {:?}", ages.get("alice"));
+    println!("Updating bob returns the old age: {:?}", ages.insert("bob", 28));
+    println!("Removed alice: {:?}, map is now {:?}", ages.remove("alice"), ages);
+
+    // Entry API for counting
+    let mut counts = MyHashMap::with_hasher(FxBuildHasher::default());
+    for word in "one fish two fish red fish blue fish".split_whitespace() {
+        *counts.entry(word).or_insert(0) += 1;
+    }
+    println!("Word counts: {:?}", counts);
+
+    // The table grows automatically
+    let mut squares = MyHashMap::with_hasher(FxBuildHasher::default());
+    for i in 0..100u64 {
+        squares.insert(i, i * i);
+    }
+    println!("{} entries, room for {} before the next resize", squares.len(), squares.capacity());
+}
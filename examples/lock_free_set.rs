@@ -0,0 +1,39 @@
+use rusty_repo::LockFreeSet;
+use std::sync::Arc;
+use std::thread;
+
+fn main() {
+    // Four workers record the ids they have seen; overlapping ids are only kept once
+    let seen = Arc::new(LockFreeSet::new());
+    let handles: Vec<_> = (0..4)
+        .map(|worker| {
+            let seen = Arc::clone(&seen);
+            thread::spawn(move || {
+                let mut new_ids = 0;
+                for id in worker * 5..worker * 5 + 10 {
+                    if seen.insert(id) {
+                        new_ids += 1;
+                    }
+                }
+                println!("Worker {} recorded {} new ids", worker, new_ids);
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    println!("Seen: {:?}", seen);
+
+    // Removals race with lookups without any locking
+    let remover = {
+        let seen = Arc::clone(&seen);
+        thread::spawn(move || (0..25).filter(|id| id % 2 == 0 && seen.remove(id)).count())
+    };
+    println!("Removed {} even ids", remover.join().unwrap());
+    println!(
+        "Contains 4? {}  Contains 5? {}",
+        seen.contains(&4),
+        seen.contains(&5)
+    );
+    println!("{} ids left: {:?}", seen.len(), seen.to_vec());
+}
@@ -0,0 +1,33 @@
+use rusty_repo::LockFreeStack;
+use std::sync::Arc;
+use std::thread;
+
+fn main() {
+    let stack = Arc::new(LockFreeStack::new());
+    let mut handles = vec![];
+
+    // Spawn threads to push values to the stack
+    for i in 0..10 {
+        let stack = Arc::clone(&stack);
+        let handle = thread::spawn(move || {
+            stack.push(i);
+            println!("Pushed: {}", i);
+        });
+        handles.push(handle);
+    }
+
+    // Wait for all threads to finish pushing
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    // Pop elements from the stack
+    let mut values = vec![];
+    while let Some(value) = stack.pop() {
+        values.push(value);
+    }
+
+    // Ensure all values are popped
+    println!("Popped values: {:?}", values);
+    assert_eq!(values.len(), 10);
+}
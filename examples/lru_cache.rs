@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+use rusty_repo::lru_cache::{Lfu, Ttl};
+use rusty_repo::{Cache, LruCache};
+
+fn main() {
+    let mut cache = LruCache::new(2);
+    cache.put("home", "<h1>Home</h1>");
+    cache.put("about", "<h1>About</h1>");
+
+    // Reading "home" makes "about" the least recently used page
+    println!("home: {:?}", cache.get("home"));
+    cache.put("contact", "<h1>Contact</h1>");
+    println!("about still cached? {}", cache.contains("about"));
+    println!("Least recently used: {:?}", cache.pop_lru());
+
+    // Entries can expire
+    let mut sessions = LruCache::with_ttl(10, Duration::from_millis(50));
+    sessions.put("token-1", "alice");
+    println!("Session right away: {:?}", sessions.get("token-1"));
+    std::thread::sleep(Duration::from_millis(60));
+    println!("Session after its TTL: {:?}", sessions.get("token-1"));
+
+    println!("Page cache stats: {:?}", cache.stats());
+    println!(
+        "Session stats: {:?}, hit rate {:.0}%",
+        sessions.stats(),
+        sessions.stats().hit_rate() * 100.0
+    );
+
+    // The generic cache takes its eviction policy as a parameter
+    let mut popular = Cache::new(2, Lfu::new());
+    popular.put("index.css", 1);
+    popular.put("app.js", 2);
+    popular.get("index.css");
+    popular.put("logo.png", 3);
+    println!("LFU kept app.js? {}", popular.contains("app.js"));
+
+    let mut tokens = Cache::new(10, Ttl::new(Duration::from_millis(50)));
+    tokens.put("token-2", "bob");
+    std::thread::sleep(Duration::from_millis(60));
+    println!("Token after its TTL: {:?}", tokens.get("token-2"));
+}
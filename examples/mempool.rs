@@ -0,0 +1,66 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
+use rusty_repo::executor::block_on;
+use rusty_repo::{MemoryPool, ResourcePool};
+
+/// Stand-in for a database connection
+#[derive(Debug)]
+struct Connection {
+    id: u32,
+    open: bool,
+}
+
+fn main() {
+    let pool = MemoryPool::new(1024, 10);
+
+    println!("Pool created with capacity for 10 chunks of 1024 bytes each.");
+
+    // Allocate some chunks
+    let chunk1 = pool.allocate().expect("First allocation should succeed");
+    let chunk2 = pool.allocate().expect("Second allocation should succeed");
+
+    println!("Allocated two chunks. Chunks available: {}", pool.available_chunks());
+
+    // Use the chunks
+    println!("Chunk1 size: {} bytes", chunk1.len());
+    println!("Chunk2 size: {} bytes", chunk2.len());
+
+    // Deallocate one chunk
+    pool.deallocate(chunk1);
+    println!("Deallocated one chunk. Chunks available: {}", pool.available_chunks());
+
+    // Try to allocate again
+    let chunk3 = pool.allocate().expect("Reallocation should succeed after deallocation");
+    println!("Reallocated a chunk. Chunks available: {}", pool.available_chunks());
+
+    // This will fail since we've used up all chunks
+    if pool.allocate().is_none() {
+        println!("Failed to allocate more chunks; pool is exhausted.");
+    }
+
+    // Deallocate remaining chunks
+    pool.deallocate(chunk2);
+    pool.deallocate(chunk3);
+    println!("All chunks deallocated. Chunks available: {}", pool.available_chunks());
+
+    // A pool of connections: created on demand, health-checked before reuse
+    let next_id = AtomicU32::new(0);
+    let connections = ResourcePool::options()
+        .max_size(2)
+        .idle_timeout(Duration::from_secs(30))
+        .health_check(|conn: &Connection| conn.open)
+        .build(move || {
+            let id = next_id.fetch_add(1, Ordering::Relaxed);
+            Ok::<_, std::io::Error>(Connection { id, open: true })
+        });
+
+    let mut conn = connections.try_acquire().expect("The pool has room");
+    println!("Checked out connection {}", conn.id);
+    conn.open = false; // the server hung up while we held it
+    drop(conn);
+
+    // The broken connection fails its health check, so a new one replaces it
+    let conn = block_on(connections.acquire()).expect("A connection is available");
+    println!("Async checkout got connection {} ({:?})", conn.id, connections);
+}
@@ -0,0 +1,72 @@
+#[cfg(not(feature = "safe-only"))]
+use rusty_repo::page_alloc::Protection;
+#[cfg(not(feature = "safe-only"))]
+use rusty_repo::alloc_mempool::MemoryPool as BlockPool;
+#[cfg(not(feature = "safe-only"))]
+use rusty_repo::{MemoryArena, MemoryPool, PageAllocator};
+
+#[cfg(not(feature = "safe-only"))]
+fn main() {
+    let page = PageAllocator::page_size();
+    println!("OS page size: {} bytes", page);
+
+    // Claim 1 GiB of address space, but only pay for the pages we commit
+    let reservation = PageAllocator::reserve(1 << 30).expect("reserve address space");
+    reservation.commit(0, 4 * page).expect("commit four pages");
+    unsafe { reservation.as_ptr().write_bytes(0x5A, 4 * page) };
+    println!(
+        "Reserved {} MiB at {:p}, committed and filled the first 4 pages",
+        reservation.len() >> 20,
+        reservation.as_ptr()
+    );
+
+    // SAFETY: the pages are only reached through raw pointers, and only read from here on
+    unsafe {
+        reservation
+            .protect(0, page, Protection::Read)
+            .expect("make the first page read-only");
+    }
+    println!("First page is now read-only; byte 0 = {:#x}", unsafe {
+        *reservation.as_ptr()
+    });
+
+    // SAFETY: nothing touches pages 1..4 again
+    unsafe {
+        reservation
+            .decommit(page, 3 * page)
+            .expect("decommit pages");
+    }
+    println!("Gave pages 1..4 back to the OS; the reservation stays in place");
+
+    let mut arena = MemoryArena::with_pages(64 * 1024).expect("page-backed arena");
+    let chunk = arena.allocate(1000).expect("arena allocation");
+    println!(
+        "A page-backed arena handed out {:p}, {} bytes left",
+        chunk,
+        arena.remaining()
+    );
+
+    let chunks = MemoryPool::with_pages(1000, 4).expect("page-backed pool");
+    let chunk = chunks.allocate().expect("pool allocation");
+    println!(
+        "A page-backed pool hands out {}-byte chunks, {} left",
+        chunk.len(),
+        chunks.available_chunks()
+    );
+    chunks.deallocate(chunk);
+
+    let mut blocks = BlockPool::with_pages();
+    let block = blocks.allocate(100).expect("block allocation");
+    println!(
+        "A page-backed block of {} bytes sits in a {}-byte mapping",
+        block.size,
+        block.data.len()
+    );
+    blocks.deallocate(block);
+}
+
+// The page allocator maps memory with unsafe code, so `safe-only` leaves it out of the facade
+#[cfg(feature = "safe-only")]
+fn main() {
+    println!("This example needs the page allocator, which `safe-only` leaves out");
+}
@@ -0,0 +1,27 @@
+"""Drives the Rust containers from Python.
+
+From the repository root: `maturin develop --release`, then `python examples/python/demo.py`.
+The same calls work line by line in a notebook.
+"""
+
+from rusty_repo import LinkedList, LruCache, Queue, Stack
+
+stack = Stack([1, 2, 3])
+stack.push("four")
+print(stack, "len", len(stack), "top", stack.peek())
+
+queue = Queue("abc")
+queue.enqueue("d")
+front = queue.dequeue()
+print(queue, "dequeued", front)
+
+linked = LinkedList(range(5))
+linked.reverse()
+print(linked, "head", linked[0], "tail", linked[-1])
+
+cache = LruCache(2)
+cache.put("x", 1)
+cache.put(("y", 2), [2])
+cache.get("x")
+cache.put("z", 3)
+print(cache, "keys, most recent first:", list(cache), f"hit rate {cache.hit_rate:.0%}")
@@ -0,0 +1,12 @@
+use rusty_repo::Queue;
+
+fn main() {
+    let mut queue = Queue::new();
+    queue.enqueue(1);
+    queue.enqueue(2);
+    println!("Dequeued: {:?}", queue.dequeue());
+    println!("Dequeued: {:?}", queue.dequeue());
+    println!("Is queue empty? {}", queue.is_empty());
+    queue.enqueue(3);
+    println!("Queue size: {}", queue.size());
+}
@@ -0,0 +1,261 @@
+use rusty_repo::raii::file_lock::{FileLock, LockMode};
+use rusty_repo::raii::rotating_file::RotatingFileWrapper;
+use rusty_repo::raii::scope_guard::ScopeGuard;
+use rusty_repo::raii::socket::{ListenerWrapper, SocketError, SocketTimeouts, TcpStreamWrapper};
+use rusty_repo::raii::temp_file::TempFile;
+use rusty_repo::raii::timer::{TimerGuard, TimingRing, TimingSink};
+use rusty_repo::raii::{DropErrorPolicy, FileWrapper, FlushPolicy};
+use rusty_repo::{defer, time_scope};
+use std::fs;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Main function to demonstrate usage
+fn main() -> io::Result<()> {
+    {
+        let mut file_wrapper = FileWrapper::new("example.txt")?;
+        file_wrapper.write("Hello, RAII!")?;
+        println!("Data written to the file successfully.");
+    } // FileWrapper goes out of scope here, and the file is automatically closed.
+
+    println!("File resource released.");
+
+    {
+        let mut file_wrapper = FileWrapper::options().append(true).open("example.txt")?;
+        file_wrapper.write(" Appended.")?;
+    }
+    println!("Appended to the existing file.");
+
+    {
+        let mut file_wrapper = FileWrapper::options().read(true).truncate(false).open("example.txt")?;
+        println!("Read back: {:?}", file_wrapper.read_to_string()?);
+        let mut word = [0u8; 4];
+        file_wrapper.read_exact_at(7, &mut word)?;
+        println!("Bytes 7..11: {:?}", String::from_utf8_lossy(&word));
+        file_wrapper.seek(SeekFrom::Start(0))?;
+        let mut first = [0u8; 5];
+        file_wrapper.read_exact(&mut first)?;
+        println!("First five bytes via Read: {:?}", String::from_utf8_lossy(&first));
+    }
+
+    // Buffered writing: compare flushing every write with flushing on drop
+    for policy in [FlushPolicy::EveryWrite, FlushPolicy::Threshold(4096), FlushPolicy::OnDrop] {
+        let start = Instant::now();
+        {
+            let mut log = FileWrapper::options()
+                .buffer_size(64 * 1024)
+                .flush_policy(policy)
+                .open("example_log.txt")?;
+            for i in 0..10_000 {
+                log.write(&format!("log line {}\n", i))?;
+            }
+            log.flush()?;
+        }
+        println!("10k log lines with {:?}: {:?}", policy, start.elapsed());
+    }
+    std::fs::remove_file("example_log.txt")?;
+
+    // Atomic writes: readers see either the old or the new file, never a partial one
+    {
+        let mut config = FileWrapper::atomic("example_config.txt")?;
+        config.write("version = 1\n")?;
+    } // renamed over example_config.txt here
+    let mut config = FileWrapper::atomic("example_config.txt")?;
+    config.write("version = 2\n")?;
+    config.commit()?;
+    let mut broken = FileWrapper::atomic("example_config.txt")?;
+    broken.write("half a conf")?;
+    broken.abort()?;
+    println!("Atomic config: {:?}", fs::read_to_string("example_config.txt")?);
+    fs::remove_file("example_config.txt")?;
+
+    // RAII for arbitrary cleanup, not just files
+    {
+        defer! { println!("Deferred cleanup ran at end of scope."); }
+        let mut connections = ScopeGuard::new(vec!["db", "cache"], |open| {
+            println!("Closing {:?}", open);
+        });
+        connections.push("queue");
+        let kept = ScopeGuard::new("session", |_| println!("never printed"));
+        println!("Dismissed guard handed back {:?}", kept.dismiss());
+    }
+
+    // Temporary files clean up after themselves
+    let scratch_path = {
+        let mut scratch = TempFile::new()?;
+        scratch.write("scratch data")?;
+        let content = scratch.read_to_string()?;
+        println!("Temp file {} contains {:?}", scratch.path().display(), content);
+        scratch.path().to_path_buf()
+    };
+    println!("Temp file removed on drop: {}", !scratch_path.exists());
+    let kept = TempFile::new_in(".")?.keep();
+    println!("Kept temp file at {}", kept.display());
+    std::fs::remove_file(kept)?;
+
+    // Advisory locks serialize writers across processes
+    {
+        let lock = FileLock::exclusive("example.lock")?;
+        println!("Holding {:?} lock on {}", lock.mode(), lock.path().display());
+        let contender = FileLock::try_lock("example.lock", LockMode::Shared)?;
+        println!("Shared lock while exclusive is held: {}", contender.is_some());
+        let waited = FileLock::lock_timeout("example.lock", LockMode::Exclusive, Duration::from_millis(20));
+        println!("Waiting 20ms for another exclusive lock: {:?}", waited.err().map(|e| e.kind()));
+    }
+    let shared = FileLock::shared("example.lock")?;
+    println!("Reacquired as {:?} after the guard was dropped", shared.mode());
+    drop(shared);
+    std::fs::remove_file("example.lock")?;
+
+    let _ = std::fs::remove_file("example_exclusive.txt");
+    {
+        let mut file_wrapper = FileWrapper::options()
+            .create_new(true)
+            .read(true)
+            .truncate(false)
+            .open("example_exclusive.txt")?;
+        file_wrapper.write("Created exclusively.")?;
+    }
+    if let Err(e) = FileWrapper::options().create_new(true).open("example_exclusive.txt") {
+        println!("Second exclusive create failed as expected: {}", e);
+    }
+    std::fs::remove_file("example_exclusive.txt")?;
+
+    // Errors in drop can't be returned, so choose how they are handled, or close() explicitly
+    {
+        let mut file_wrapper = FileWrapper::new("example_close.txt")?;
+        file_wrapper.set_drop_error_policy(DropErrorPolicy::Panic);
+        file_wrapper.write("Closed explicitly.")?;
+        println!("Explicit close: {:?}", file_wrapper.close());
+
+        let mut quiet = FileWrapper::options().append(true).open("example_close.txt")?;
+        quiet.set_drop_error_policy(DropErrorPolicy::Ignore);
+        quiet.write(" Appended quietly.")?;
+    }
+    fs::remove_file("example_close.txt")?;
+    if Path::new("/dev/full").exists() {
+        // Every write to /dev/full fails with "no space left on device"
+        let mut full = FileWrapper::options().truncate(false).open("/dev/full")?;
+        full.set_drop_error_policy(DropErrorPolicy::callback(|e| {
+            println!("Drop error handed to callback: {}", e)
+        }));
+        full.write("never stored")?;
+        println!("Default policy is {:?}", DropErrorPolicy::default());
+    }
+
+    // Size-capped logging: example_rotating.log plus at most two backups
+    {
+        let mut log = RotatingFileWrapper::new("example_rotating.log", 32, 2)?;
+        for i in 0..6 {
+            log.write(&format!("log line number {}\n", i))?;
+        }
+        log.flush()?;
+        println!(
+            "Rotating log {} holds {} bytes, backups: {}, {}",
+            log.path().display(),
+            log.current_size(),
+            log.backup_path(1).display(),
+            log.backup_path(2).display()
+        );
+        log.rotate()?;
+        for index in 1..=2 {
+            fs::remove_file(log.backup_path(index))?;
+        }
+    }
+    fs::remove_file("example_rotating.log")?;
+
+    socket_example().map_err(io::Error::other)?;
+
+    #[cfg(feature = "async")]
+    async_example()?;
+
+    // Timing guards: RAII for a measurement rather than a handle
+    {
+        time_scope!("timer demo");
+        let ring = TimingRing::new(8);
+        for step in ["parse", "validate"] {
+            time_scope!(step, TimingSink::Ring(ring.clone()));
+            thread::sleep(Duration::from_millis(1));
+        }
+        let slow = TimerGuard::with_sink(
+            "callback",
+            TimingSink::callback(|t| println!("Callback sink saw {} after {:?}", t.label, t.elapsed)),
+        );
+        println!("Callback timer running for {:?}", slow.elapsed());
+        drop(slow);
+        let _explicit = TimerGuard::new("explicit guard");
+        for timing in ring.snapshot() {
+            println!("Ring sink: {}", timing);
+        }
+        println!("Ring holds {} timings (empty: {})", ring.len(), ring.is_empty());
+    }
+    Ok(())
+}
+
+/// `AsyncFileWrapper` on a current-thread runtime, writing into the temp directory
+#[cfg(feature = "async")]
+fn async_example() -> io::Result<()> {
+    use rusty_repo::raii::async_file::AsyncFileWrapper;
+
+    let path = std::env::temp_dir().join(format!("example_async_{}.txt", std::process::id()));
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .expect("failed to build tokio runtime");
+
+    runtime.block_on(async {
+        let mut log = AsyncFileWrapper::with_policy(&path, FlushPolicy::Threshold(64)).await?;
+        for i in 0..3 {
+            log.write(&format!("async line {}\n", i)).await?;
+        }
+        log.flush().await?;
+        log.close().await?;
+        println!("AsyncFileWrapper wrote: {:?}", tokio::fs::read_to_string(&path).await?);
+
+        let mut dropped = AsyncFileWrapper::new(&path).await?;
+        dropped.write("flushed by the background task").await?;
+        drop(dropped);
+        tokio::task::yield_now().await;
+        tokio::fs::remove_file(&path).await
+    })
+}
+
+/// The same RAII pattern applied to a TCP connection on localhost
+fn socket_example() -> Result<(), SocketError> {
+    let timeouts = SocketTimeouts {
+        connect: Some(Duration::from_secs(1)),
+        read: Some(Duration::from_secs(1)),
+        write: Some(Duration::from_secs(1)),
+    };
+    let listener = ListenerWrapper::bind("127.0.0.1:0", timeouts)?;
+    let addr = listener.local_addr()?;
+    let server = thread::spawn(move || -> Result<(), SocketError> {
+        let (mut conn, peer) = listener.accept()?;
+        let mut buf = [0u8; 64];
+        let n = conn.read(&mut buf)?;
+        println!("Server got {:?} from {}", String::from_utf8_lossy(&buf[..n]), peer);
+        conn.write(b"pong")?;
+        conn.flush()
+        // conn is shut down here
+    });
+
+    {
+        let mut client = TcpStreamWrapper::connect(addr, timeouts)?;
+        println!("Connected to {}", client.peer_addr()?);
+        client.write(b"ping")?;
+        let mut reply = [0u8; 4];
+        let n = client.read(&mut reply)?;
+        println!("Client got {:?}", String::from_utf8_lossy(&reply[..n]));
+        client.shutdown()?;
+        if let Err(e) = client.write(b"too late") {
+            println!("Write after shutdown: {}", e);
+        }
+    }
+    server.join().expect("server thread panicked")?;
+
+    if let Err(e) = TcpStreamWrapper::connect(addr, timeouts) {
+        println!("Reconnecting after the listener was dropped: {}", e);
+    }
+    Ok(())
+}
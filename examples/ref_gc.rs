@@ -0,0 +1,70 @@
+use rusty_repo::ref_gc::{arc, ReferenceCountedGC};
+use std::rc::Rc;
+use std::sync::Arc;
+use std::thread;
+
+fn main() {
+    let gc = ReferenceCountedGC::new(42);
+
+    // Create multiple references using Rc
+    let data_ref1 = gc.get_data();
+    let data_ref2 = gc.get_data();
+
+    println!("Data via reference 1: {}", data_ref1.borrow().get_value());
+    println!("Data via reference 2: {}", data_ref2.borrow().get_value());
+
+    // After this point, both references are still valid, and the memory is not freed.
+    // Once the references are dropped, the memory will be freed automatically.
+
+    drop(data_ref1);  // Dropping the first reference.
+    println!("Reference count after dropping one reference: {}",
+             Rc::strong_count(&gc.data));
+
+    // Once both references go out of scope, `MyData` will be deallocated automatically.
+
+    drop(data_ref2);  // Dropping the second reference.
+    println!("Reference count after dropping second reference: {}",
+             Rc::strong_count(&gc.data));
+
+    // gc will be cleaned up at the end of main, when no more references remain.
+
+    println!("Running arc example...");
+    arc_example();
+}
+
+/// The same sharing with `Arc<Mutex<_>>`, across threads
+fn arc_example() {
+    let gc = arc::ReferenceCountedGC::new(42);
+
+    // Create multiple references using Arc
+    let data_ref1 = gc.get_data();
+    let data_ref2 = gc.get_data();
+
+    println!("Data via reference 1: {}", data_ref1.lock().unwrap().get_value());
+    println!("Data via reference 2: {}", data_ref2.lock().unwrap().get_value());
+
+    // Now let's spawn threads and share the data between them using Arc
+    let handle1 = thread::spawn({
+        let data_ref1 = Arc::clone(&data_ref1); // Clone the Arc before moving it into the thread
+        move || {
+            println!("Thread 1: {}", data_ref1.lock().unwrap().get_value());
+        }
+    });
+
+    let handle2 = thread::spawn({
+        let data_ref2 = Arc::clone(&data_ref2); // Clone the Arc before moving it into the thread
+        move || {
+            println!("Thread 2: {}", data_ref2.lock().unwrap().get_value());
+        }
+    });
+
+    // Wait for threads to complete
+    handle1.join().unwrap();
+    handle2.join().unwrap();
+
+    // The memory is freed automatically once both references are dropped and no longer in scope
+    drop(data_ref1);
+    drop(data_ref2);
+
+    println!("Memory will be cleaned up when the last reference goes out of scope.");
+}
@@ -0,0 +1,50 @@
+use rusty_repo::{RingBuffer, WindowAggregator};
+
+fn main() {
+    // Create a buffer with capacity of 3
+    let mut buffer = RingBuffer::new(3);
+
+    // Push some elements into the buffer
+    buffer.push(10);
+    buffer.push(20);
+    buffer.push(30);
+
+    // Print current buffer content by iterating over it
+    println!("Buffer content: {:?}", buffer.iter().collect::<Vec<_>>());
+
+    // Push another element, which will overwrite the oldest (10)
+    buffer.push(40);
+    println!("Buffer after pushing 40: {:?}", buffer.iter().collect::<Vec<_>>());
+
+    // Pop elements and print them
+    println!("Popped: {:?}", buffer.pop()); // Should pop 20
+    println!("Popped: {:?}", buffer.pop()); // Should pop 30
+
+    // Check remaining elements in the buffer
+    println!("Buffer content after pops: {:?}", buffer.iter().collect::<Vec<_>>());
+
+    // Push another element
+    buffer.push(50);
+    println!("Buffer after pushing 50: {:?}", buffer.iter().collect::<Vec<_>>());
+
+    // Peek the front element
+    println!("Peek: {:?}", buffer.peek()); // Should be 40
+
+    // Clear the buffer
+    buffer.clear();
+    println!("Buffer after clear: {:?}", buffer.iter().collect::<Vec<_>>());
+
+    // Rolling latency metrics over the last 5 requests
+    let mut latencies = WindowAggregator::count_window(5);
+    for ms in [12, 15, 11, 240, 13, 14, 12] {
+        latencies.push(ms);
+    }
+    println!(
+        "Last {} latencies: mean {:.1}ms, min {:?}, max {:?}, p80 {:?}",
+        latencies.count(),
+        latencies.mean().unwrap_or(0.0),
+        latencies.min(),
+        latencies.max(),
+        latencies.quantile(0.8)
+    );
+}
@@ -0,0 +1,25 @@
+use rusty_repo::segment_tree::{Max, Min, Sum};
+use rusty_repo::{LazySegmentTree, SegmentTree};
+
+fn main() {
+    // Daily sales: point updates and range totals
+    let sales = [120, 80, 200, 150, 90, 300, 60];
+    let mut totals: SegmentTree<u32, Sum> = SegmentTree::from_slice(&sales);
+    println!("Sales for days 2..5: {}", totals.query(2..5));
+    totals.update(3, 175);
+    println!("After correcting day 3: {}", totals.query(2..5));
+
+    let lows: SegmentTree<u32, Min> = SegmentTree::from_slice(&sales);
+    println!("Lowest day in the week: {}", lows.query(..));
+
+    // Temperatures: shift a whole range at once, then ask for the peak
+    let mut temps: LazySegmentTree<i32, Max> =
+        LazySegmentTree::from_slice(&[18, 21, 19, 24, 22, 20]);
+    println!("Peak temperature before the heatwave: {}", temps.query(..));
+    temps.add(2..5, 6);
+    println!(
+        "Peak after adding 6 degrees to days 2..5: {}",
+        temps.query(..)
+    );
+    println!("Peak of days 0..2 is unchanged: {}", temps.query(0..2));
+}
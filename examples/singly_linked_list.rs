@@ -0,0 +1,131 @@
+use rusty_repo::LinkedList;
+
+fn main() {
+    println!("LinkedList\n");
+
+    println!("1. Testing Basic Operations:");
+    let mut list = LinkedList::new();
+    
+    println!("   Empty list: {}", list);
+    println!("   Is empty: {}", list.is_empty());
+    println!("   Length: {}", list.len());
+    
+    // Push elements (remember: adds to front)
+    list.push(1);
+    list.push(2);
+    list.push(3);
+    list.push(4);
+    list.push(5);
+    
+    println!("   After pushing 1,2,3,4,5: {}", list);
+    println!("   Length: {}", list.len());
+    println!("   Is empty: {}", list.is_empty());
+    
+    // Test get method
+    println!("   Element at index 0: {:?}", list.get(0));
+    println!("   Element at index 2: {:?}", list.get(2));
+    println!("   Element at index 10: {:?}", list.get(10));
+    
+    // Test pop method
+    println!("   Popping: {:?}", list.pop());
+    println!("   After pop: {}", list);
+    
+    println!("\n2. Testing Cycle Detection on Linear List:");
+    println!("   Has cycle: {}", list.has_cycle());
+    println!("   Cycle start: {:?}", list.find_cycle_start());
+    println!("   Cycle length: {:?}", list.cycle_length());
+    println!("   Structure: {}", list.describe_structure());
+    
+    println!("\n3. Testing Reverse Operation:");
+    println!("   Before reverse: {}", list);
+    list.reverse();
+    println!("   After reverse: {}", list);
+    list.reverse();
+    println!("   After reverse again: {}", list);
+    
+    println!("\n4. Testing Cycle Creation nut unsafe:");
+    println!("   WARNING: Creating artificial cycle for testing...");
+    
+    // Create a new list for cycle testing
+    let mut cycle_list = LinkedList::new();
+    cycle_list.push(10);  // Index 4 (remember: push adds to front)
+    cycle_list.push(20);  // Index 3
+    cycle_list.push(30);  // Index 2
+    cycle_list.push(40);  // Index 1
+    cycle_list.push(50);  // Index 0
+    
+    println!("   List before cycle: {}", cycle_list);
+    println!("   Length before cycle: {}", cycle_list.len());
+    
+    // DANGEROUS: Create cycle from last node back to index 2
+    // (not available under `safe-only`, where the list stays linear)
+    #[cfg(not(feature = "safe-only"))]
+    unsafe {
+        let cycle_created = cycle_list.create_cycle_at(2);
+        println!("   Cycle creation successful: {}", cycle_created);
+    }
+    
+    // Test cycle detection on the cyclic list
+    println!("   Has cycle: {}", cycle_list.has_cycle());
+    
+    if cycle_list.has_cycle() {
+        println!("   Cycle start index: {:?}", cycle_list.find_cycle_start());
+        println!("   Cycle length: {:?}", cycle_list.cycle_length());
+        println!("   Structure: {}", cycle_list.describe_structure());
+        println!("   Display (safe): {}", cycle_list);
+        
+        // Show values safely
+        let values = cycle_list.get_all_values();
+        println!("   Values in list: {:?}", values);
+    }
+    // Undo the cycle; dropping the list would do this too
+    println!("   Cycle broken: {}", cycle_list.break_cycle());
+    println!("   Values after breaking: {:?}", cycle_list.get_all_values());
+    
+    println!("\n5. Testing Edge Cases:");
+    
+    // Empty list
+    let empty: LinkedList<i32> = LinkedList::new();
+    println!("   Empty list has cycle: {}", empty.has_cycle());
+    
+    // Single node
+    let mut single = LinkedList::new();
+    single.push(42);
+    println!("   Single node has cycle: {}", single.has_cycle());
+    println!("   Single node: {}", single);
+    
+    // Two nodes
+    let mut two_nodes = LinkedList::new();
+    two_nodes.push(1);
+    two_nodes.push(2);
+    println!("   Two nodes have cycle: {}", two_nodes.has_cycle());
+    println!("   Two nodes: {}", two_nodes);
+
+    let mut demo_list = LinkedList::new();
+    
+    // Build a list
+    for i in 1..=6 {
+        demo_list.push(i * 10);
+    }
+    
+    println!("   Demo list: {}", demo_list);
+    println!("   Length: {}", demo_list.len());
+    println!("   Structure: {}", demo_list.describe_structure());
+    
+    // Test all get operations
+    for i in 0..demo_list.len() {
+        println!("   Index {}: {:?}", i, demo_list.get(i));
+    }
+    
+    // Test pop until empty
+    println!("   Popping all elements:");
+    while !demo_list.is_empty() {
+        println!("     Popped: {:?}, remaining: {}", demo_list.pop(), demo_list);
+    }
+
+    println!("All methods tested successfully!");
+    println!("- Basic operations: push, pop, get, len, is_empty");
+    println!("- Advanced operations: reverse, cycle detection");
+    println!("- Unsafe operations: cycle creation");
+    println!("- Edge cases: empty, single node, linear vs cyclic");
+}
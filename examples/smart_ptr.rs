@@ -0,0 +1,24 @@
+use rusty_repo::CustomSmartPointer;
+
+fn main() {
+    // Create a CustomSmartPointer with an initial value.
+    let smart_pointer = CustomSmartPointer::new(10);
+
+    // Immutable borrow.
+    {
+        let borrowed_value = smart_pointer.borrow();
+        println!("Immutable borrow: {}", *borrowed_value);
+        // The borrowed_value goes out of scope here, allowing further borrows.
+    }
+
+    // Mutable borrow.
+    {
+        let mut borrowed_mut = smart_pointer.borrow_mut();
+        *borrowed_mut = 20;
+        println!("Mutable borrow (inside scope): {}", *borrowed_mut);
+        // The borrowed_mut goes out of scope here, allowing further borrows.
+    }
+
+    // Immutable borrow after mutation.
+    println!("Immutable borrow after mutation: {}", *smart_pointer.borrow());
+}
@@ -0,0 +1,45 @@
+use rusty_repo::SnapshotStore;
+
+fn main() -> std::io::Result<()> {
+    let path = std::env::temp_dir().join("rusty_repo_snapshot_store.bin");
+    let _ = std::fs::remove_file(&path);
+
+    let mut store = SnapshotStore::open(&path)?;
+    store.write_at(0, b"counter=0");
+    let first = store.commit()?;
+    println!(
+        "Committed v{}: {}",
+        first.version(),
+        String::from_utf8_lossy(&first)
+    );
+
+    // Edits go to private copies of the touched pages; the file and snapshots are untouched
+    store.data_mut()[8] = b'1';
+    println!("Working copy: {}", String::from_utf8_lossy(store.data()));
+    println!(
+        "On disk:      {}",
+        String::from_utf8_lossy(&std::fs::read(&path)?)
+    );
+
+    // Commit writes a temporary file and renames it over the old one
+    let second = store.commit()?;
+    println!(
+        "Committed v{}: {}",
+        second.version(),
+        String::from_utf8_lossy(&second)
+    );
+    println!(
+        "v{} still reads: {}",
+        first.version(),
+        String::from_utf8_lossy(&first)
+    );
+
+    // Uncommitted changes are thrown away by discard (or by dropping the store)
+    store.write_at(0, b"garbage!!");
+    store.discard()?;
+    println!("After discard: {}", String::from_utf8_lossy(store.data()));
+
+    drop(store);
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use std::fmt::Write;
+
+use rusty_repo::SsoString;
+
+fn main() {
+    // Short strings live inside the value, no allocation needed
+    let mut name: SsoString = SsoString::from("ferris");
+    println!(
+        "{name:?}: {} bytes, inline = {}",
+        name.len(),
+        name.is_inline()
+    );
+
+    // Growing past the inline capacity moves the text to the heap
+    name.push_str(" the crab, mascot of Rust");
+    println!(
+        "{name:?}: {} bytes, inline = {}",
+        name.len(),
+        name.is_inline()
+    );
+
+    // Truncating keeps the heap buffer until asked to shrink
+    name.truncate(6);
+    name.shrink_to_fit();
+    println!(
+        "{name:?} after shrink_to_fit: inline = {}",
+        name.is_inline()
+    );
+
+    // A smaller inline buffer, filled with write!
+    let mut label = SsoString::<8>::new();
+    write!(label, "id-{}", 42).unwrap();
+    println!("{label} uppercased is {}", label.to_uppercase());
+
+    let owned: String = label.into();
+    println!("Back to a String: {owned}");
+}
@@ -0,0 +1,51 @@
+use rusty_repo::stack::{History, StackFull};
+use rusty_repo::Stack;
+
+fn main() -> Result<(), StackFull<i32>> {
+    let mut stack = Stack::new();
+
+    // Push some elements onto the stack; an unbounded stack never rejects one
+    stack.push(10)?;
+    stack.push(20)?;
+    stack.push(30)?;
+
+    // Peek the top element
+    if let Some(top) = stack.peek() {
+        println!("Top of the stack: {}", top);
+    }
+
+    // Pop an element from the stack
+    if let Some(popped) = stack.pop() {
+        println!("Popped element: {}", popped);
+    }
+
+    // Check the size of the stack
+    println!("Current stack size: {}", stack.size());
+
+    // Check if the stack is empty
+    if stack.is_empty() {
+        println!("The stack is empty.");
+    } else {
+        println!("The stack is not empty.");
+    }
+
+    // A bounded stack hands back the item it has no room for
+    let mut bounded = Stack::with_capacity(1);
+    bounded.push(1)?;
+    if let Err(StackFull(item)) = bounded.push(2) {
+        println!("Bounded stack is full, rejected {}", item);
+    }
+
+    // Undo/redo for a text buffer, keeping the last three versions
+    let mut history = History::new(3).on_evict(|text: String| println!("Forgot {:?}", text));
+    for text in ["H", "He", "Hel", "Hell", "Hello"] {
+        history.record(text.to_string());
+    }
+    println!("Undo: {:?}", history.undo());
+    println!("Undo: {:?}", history.undo());
+    println!("Undo: {:?}", history.undo());
+    println!("Redo: {:?}", history.redo());
+    history.record("Help".to_string()); // Discards the "Hello" branch
+    println!("Current: {:?}", history.current());
+    Ok(())
+}
@@ -0,0 +1,65 @@
+#[cfg(not(feature = "safe-only"))]
+use rusty_repo::channels::mpmc;
+#[cfg(not(feature = "safe-only"))]
+use rusty_repo::ThreadPool;
+#[cfg(not(feature = "safe-only"))]
+use std::time::Instant;
+
+/// Counts primes below `n` the slow way, to give each task some real work
+#[cfg(not(feature = "safe-only"))]
+fn count_primes(range: std::ops::Range<u64>) -> usize {
+    range
+        .filter(|&n| n >= 2 && (2..).take_while(|d| d * d <= n).all(|d| n % d != 0))
+        .count()
+}
+
+#[cfg(not(feature = "safe-only"))]
+fn main() {
+    let pool = ThreadPool::with_available_parallelism();
+    println!("Pool with {} workers", pool.threads());
+
+    // Fan-out on the pool, fan-in through the repo's MPMC channel
+    let (results_tx, results) = mpmc::channel(64);
+    let started = Instant::now();
+    let chunks = 200u64;
+    for chunk in 0..chunks {
+        let results = results_tx.clone();
+        pool.spawn(move || {
+            let start = chunk * 5_000;
+            results.send(count_primes(start..start + 5_000)).unwrap();
+        });
+    }
+    drop(results_tx);
+    let primes: usize = results.iter().sum();
+    println!(
+        "{} primes below {} in {:?}",
+        primes,
+        chunks * 5_000,
+        started.elapsed()
+    );
+
+    // Nested spawns land on the spawning worker's deque and get stolen by idle workers
+    let spawner = pool.spawner();
+    pool.spawn(move || {
+        for i in 0..100u64 {
+            spawner.spawn(move || {
+                count_primes(i * 1_000..(i + 1) * 1_000);
+            });
+        }
+    });
+
+    // A panicking task does not take its worker down
+    pool.spawn(|| panic!("this task fails on purpose"));
+
+    let stats = pool.shutdown();
+    println!(
+        "Completed: {}, panicked: {}, stolen: {}",
+        stats.completed, stats.panicked, stats.stolen
+    );
+}
+
+// The MPMC channel uses unsafe code internally, so `safe-only` leaves it out of the facade
+#[cfg(feature = "safe-only")]
+fn main() {
+    println!("This example needs the MPMC channel, which `safe-only` leaves out");
+}
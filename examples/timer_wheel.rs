@@ -0,0 +1,43 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rusty_repo::TimerWheel;
+
+fn main() {
+    // Retry a flaky request with exponential backoff: the payload is the attempt number
+    let mut retries = TimerWheel::new(Duration::from_millis(1));
+    retries.schedule(Duration::from_millis(5), 1);
+    let mut attempts = 0;
+    while attempts < 3 {
+        thread::sleep(Duration::from_millis(5));
+        for attempt in retries.tick(Instant::now()) {
+            attempts = attempt;
+            println!("Attempt {attempt} failed, retrying in {}ms", 5 << attempt);
+            retries.schedule(Duration::from_millis(5 << attempt), attempt + 1);
+        }
+    }
+
+    // Callbacks work as payloads too, and pending timers can be cancelled
+    let start = Instant::now();
+    let mut callbacks: TimerWheel<Box<dyn FnOnce() -> String>> =
+        TimerWheel::starting_at(Duration::from_millis(1), start);
+    callbacks.schedule(
+        Duration::from_secs(1),
+        Box::new(|| "flush logs".to_string()),
+    );
+    let reminder = callbacks.schedule(Duration::from_secs(60), Box::new(|| "stand up".to_string()));
+    callbacks.schedule(
+        Duration::from_secs(3600),
+        Box::new(|| "rotate keys".to_string()),
+    );
+    println!(
+        "Cancelled reminder: {}",
+        callbacks.cancel(reminder).is_some()
+    );
+
+    // Jumping an hour ahead only visits the slots that hold timers
+    for callback in callbacks.tick(start + Duration::from_secs(3600)) {
+        println!("Ran: {}", callback());
+    }
+    println!("Timers left: {}", callbacks.len());
+}
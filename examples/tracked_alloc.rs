@@ -0,0 +1,43 @@
+use rusty_repo::alloc_mempool::MemoryPool as BlockPool;
+use rusty_repo::{assert_no_leaks, MemoryArena, MemoryPool, TrackedAlloc};
+
+// Any allocator that tracks its usage can be summarised the same way
+fn describe(name: &str, alloc: &dyn TrackedAlloc) {
+    println!("{:>10}: {}", name, alloc.leak_report());
+}
+
+fn main() {
+    let mut arena = MemoryArena::new(4096);
+    arena.allocate(1000);
+    arena.allocate(500);
+
+    let chunks = MemoryPool::new(256, 8);
+    let held = chunks.allocate().unwrap();
+
+    let mut blocks = BlockPool::new();
+    let block = blocks.allocate(2048).unwrap();
+    blocks.deallocate(block);
+
+    describe("arena", &arena);
+    describe("chunk pool", &*chunks);
+    describe("block pool", &blocks);
+
+    // The closure returns everything it takes, so the check passes
+    let total = assert_no_leaks!(blocks, || {
+        let a = blocks.allocate(64).unwrap();
+        let b = blocks.allocate(128).unwrap();
+        let total = a.size + b.size;
+        blocks.deallocate(a);
+        blocks.deallocate(b);
+        total
+    });
+    println!("Balanced closure used {} bytes and leaked none", total);
+
+    chunks.deallocate(held);
+    arena.reset();
+    println!(
+        "After cleanup: arena clean = {}, chunk pool clean = {}",
+        arena.leak_report().is_clean(),
+        chunks.leak_report().is_clean()
+    );
+}
@@ -0,0 +1,29 @@
+use rusty_repo::trie::ByteTrie;
+use rusty_repo::Trie;
+
+fn main() {
+    // Autocomplete over a small dictionary
+    let mut words: Trie = ["car", "card", "care", "careful", "cat", "dog"]
+        .into_iter()
+        .collect();
+    println!(
+        "Words starting with \"car\": {:?}",
+        words.words_with_prefix("car").collect::<Vec<_>>()
+    );
+    println!("Any word starting with \"do\"? {}", words.starts_with("do"));
+
+    words.remove("card");
+    println!(
+        "After removing \"card\": {:?}",
+        words.words().collect::<Vec<_>>()
+    );
+
+    // Byte-keyed variant storing values
+    let mut routes = ByteTrie::new();
+    routes.insert(b"/api/users", "list users");
+    routes.insert(b"/api/users/me", "current user");
+    routes.insert(b"/health", "health check");
+    for (path, handler) in routes.iter_prefix(b"/api") {
+        println!("{} -> {}", String::from_utf8_lossy(&path), handler);
+    }
+}
@@ -0,0 +1,7 @@
+use rusty_repo::reverse_vector;
+
+fn main() {
+    let mut numbers = vec![1, 2, 3, 4, 5];
+    reverse_vector(&mut numbers);
+    println!("Reversed: {:?}", numbers);
+}
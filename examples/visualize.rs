@@ -0,0 +1,48 @@
+use rusty_repo::{ArenaGraph, AsciiArt, BalancedTree, LinkedList, RingBuffer, ToDot};
+
+fn main() {
+    // Pipe the DOT sections through `dot -Tsvg` to get pictures
+    let mut list = LinkedList::new();
+    for value in (1..=4).rev() {
+        list.push(value);
+    }
+    println!("// A linear list\n{}", list.to_dot());
+
+    // Building a cycle takes unsafe code, which `safe-only` rules out
+    #[cfg(not(feature = "safe-only"))]
+    {
+        // SAFETY: the list is only read until its drop breaks the cycle
+        unsafe {
+            list.create_cycle_at(1);
+        }
+        println!(
+            "// The same list with the tail linked back to index 1\n{}",
+            list.to_dot()
+        );
+    }
+
+    let tree: BalancedTree<_, _> = ["delta", "alpha", "echo", "bravo", "charlie"]
+        .into_iter()
+        .map(|word| (word, word.len()))
+        .collect();
+    println!("// An AVL tree of words and their lengths\n{}", tree.to_dot());
+
+    let graph = ArenaGraph::from_edges(
+        vec!["parse", "check", "optimize", "emit"],
+        [(0, 1), (1, 2), (2, 1), (1, 3), (2, 3)],
+    );
+    println!(
+        "// A compiler pipeline as an arena graph\n{}",
+        graph.to_dot()
+    );
+
+    let mut buffer = RingBuffer::new(5);
+    for value in 1..=7 {
+        buffer.push(value * 10);
+    }
+    buffer.pop();
+    println!(
+        "A ring buffer after 7 pushes and a pop:\n{}",
+        buffer.ascii_art()
+    );
+}
@@ -0,0 +1,52 @@
+use rusty_repo::thread_pool::Spawner;
+use rusty_repo::ThreadPool;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+type Task = std::ops::Range<u64>;
+
+/// Counts primes the slow way, to give each task some real work
+fn count_primes(range: Task) -> usize {
+    range
+        .filter(|&n| n >= 2 && (2..).take_while(|d| d * d <= n).all(|d| n % d != 0))
+        .count()
+}
+
+/// Counts the primes in `range`, spawning its upper half as a new task until
+/// what is left is small enough to scan directly
+fn run(range: Task, spawner: Spawner, primes: Arc<AtomicUsize>) {
+    if range.end - range.start <= 2_000 {
+        primes.fetch_add(count_primes(range), Ordering::Relaxed);
+        return;
+    }
+    let middle = range.start + (range.end - range.start) / 2;
+    // Lands on this worker's deque, where idle workers can steal it
+    let (next, counter) = (spawner.clone(), Arc::clone(&primes));
+    spawner.spawn(move || run(middle..range.end, next, counter));
+    run(range.start..middle, spawner, primes);
+}
+
+fn main() {
+    let pool = ThreadPool::with_available_parallelism();
+    println!("Pool with {} workers", pool.threads());
+
+    // A single task starts the whole tree, so every other worker only gets work by stealing
+    let n = 2_000_000u64;
+    let primes = Arc::new(AtomicUsize::new(0));
+    let (spawner, counter) = (pool.spawner(), Arc::clone(&primes));
+    let started = Instant::now();
+    pool.spawn(move || run(0..n, spawner, counter));
+
+    let stats = pool.shutdown();
+    println!(
+        "{} primes below {} in {:?}",
+        primes.load(Ordering::Relaxed),
+        n,
+        started.elapsed()
+    );
+    println!(
+        "Ran {} tasks, {} of them stolen by idle workers",
+        stats.completed, stats.stolen
+    );
+}
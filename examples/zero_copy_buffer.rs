@@ -0,0 +1,43 @@
+use rusty_repo::ZeroCopyBuffer;
+
+fn main() {
+    // Create a new Zero-Copy Buffer with initial data
+    let buffer = ZeroCopyBuffer::new(vec![1, 2, 3, 4, 5]);
+
+    // Multiple readers
+    let reader1 = buffer.clone();
+    let reader2 = buffer.clone();
+
+    // Spawn threads to simulate concurrent reads
+    let handle1 = std::thread::spawn(move || {
+        if let Some(data) = reader1.read() {
+            println!("Reader 1: {:?}", data);
+        } else {
+            println!("Reader 1: Failed to read data");
+        }
+    });
+
+    let handle2 = std::thread::spawn(move || {
+        if let Some(data) = reader2.read() {
+            println!("Reader 2: {:?}", data);
+        } else {
+            println!("Reader 2: Failed to read data");
+        }
+    });
+
+    // Wait for readers to finish
+    handle1.join().unwrap();
+    handle2.join().unwrap();
+
+    // Update the buffer's data
+    if let Err(err) = buffer.write(vec![6, 7, 8, 9, 10]) {
+        println!("Writer: {}", err);
+    } else {
+        println!("Writer: Updated the buffer");
+    }
+
+    // Verify updated data
+    if let Some(data) = buffer.read() {
+        println!("Main Thread: Updated Data: {:?}", data);
+    }
+}
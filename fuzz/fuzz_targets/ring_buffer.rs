@@ -0,0 +1,7 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    rusty_repo::ring_buffer::fuzz::fuzz_ops(data);
+});
@@ -0,0 +1,131 @@
+#ifndef RUSTY_REPO_H
+#define RUSTY_REPO_H
+
+/* Generated by cbindgen from src/ffi.rs; do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * A `RingBuffer<u8>`: pushing onto a full buffer overwrites the oldest byte
+ */
+typedef struct RustyByteRingBuffer RustyByteRingBuffer;
+
+/**
+ * Task 07's `MemoryPool` of equally sized chunks
+ */
+typedef struct RustyMemoryPoolHandle RustyMemoryPoolHandle;
+
+/**
+ * Creates a buffer holding up to `capacity` bytes; returns null if `capacity` is 0
+ */
+struct RustyByteRingBuffer *rusty_byte_ring_buffer_create(size_t capacity);
+
+/**
+ * Appends a byte, dropping the oldest one if the buffer is full
+ *
+ * # Safety
+ *
+ * `buffer` must be null or a live pointer from `rusty_byte_ring_buffer_create`.
+ */
+void rusty_byte_ring_buffer_push(struct RustyByteRingBuffer *buffer, uint8_t byte);
+
+/**
+ * Removes the oldest byte into `*out`; returns false, leaving `*out` alone, if empty
+ *
+ * # Safety
+ *
+ * `buffer` must be null or a live pointer from `rusty_byte_ring_buffer_create`,
+ * and `out` must be null or valid for a one-byte write.
+ */
+bool rusty_byte_ring_buffer_pop(struct RustyByteRingBuffer *buffer, uint8_t *out);
+
+/**
+ * Returns the number of bytes stored
+ *
+ * # Safety
+ *
+ * `buffer` must be null or a live pointer from `rusty_byte_ring_buffer_create`.
+ */
+size_t rusty_byte_ring_buffer_len(const struct RustyByteRingBuffer *buffer);
+
+/**
+ * Checks if the next push will overwrite the oldest byte
+ *
+ * # Safety
+ *
+ * `buffer` must be null or a live pointer from `rusty_byte_ring_buffer_create`.
+ */
+bool rusty_byte_ring_buffer_is_full(const struct RustyByteRingBuffer *buffer);
+
+/**
+ * Frees the buffer
+ *
+ * # Safety
+ *
+ * `buffer` must be null or a pointer from `rusty_byte_ring_buffer_create`
+ * that has not been destroyed yet; it is dangling afterwards.
+ */
+void rusty_byte_ring_buffer_destroy(struct RustyByteRingBuffer *buffer);
+
+/**
+ * Creates a pool of `capacity` zeroed chunks of `chunk_size` bytes; returns null if either is 0
+ */
+struct RustyMemoryPoolHandle *rusty_memory_pool_create(size_t chunk_size, size_t capacity);
+
+/**
+ * Takes a chunk of `chunk_size` bytes from the pool; returns null when it is exhausted
+ *
+ * Chunks are not cleared when they come back, so a reused chunk holds
+ * whatever its previous user wrote.
+ *
+ * # Safety
+ *
+ * `pool` must be null or a live pointer from `rusty_memory_pool_create`.
+ */
+uint8_t *rusty_memory_pool_allocate(struct RustyMemoryPoolHandle *pool);
+
+/**
+ * Returns a chunk to the pool
+ *
+ * # Safety
+ *
+ * `pool` must be a live pointer from `rusty_memory_pool_create`, and `chunk`
+ * null or a pointer that pool's `rusty_memory_pool_allocate` returned and
+ * that hasn't been deallocated yet. The pool must not be destroyed while it
+ * still has chunks out.
+ */
+void rusty_memory_pool_deallocate(struct RustyMemoryPoolHandle *pool, uint8_t *chunk);
+
+/**
+ * Returns the size of every chunk, or 0 for a null pool
+ *
+ * # Safety
+ *
+ * `pool` must be null or a live pointer from `rusty_memory_pool_create`.
+ */
+size_t rusty_memory_pool_chunk_size(const struct RustyMemoryPoolHandle *pool);
+
+/**
+ * Returns how many chunks can still be allocated
+ *
+ * # Safety
+ *
+ * `pool` must be null or a live pointer from `rusty_memory_pool_create`.
+ */
+size_t rusty_memory_pool_available(const struct RustyMemoryPoolHandle *pool);
+
+/**
+ * Frees the pool and every chunk it holds
+ *
+ * # Safety
+ *
+ * `pool` must be null or a pointer from `rusty_memory_pool_create` that has
+ * not been destroyed yet, with every allocated chunk already returned.
+ */
+void rusty_memory_pool_destroy(struct RustyMemoryPoolHandle *pool);
+
+#endif  /* RUSTY_REPO_H */
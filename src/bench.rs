@@ -0,0 +1,641 @@
+//! A small benchmark harness and ready-made comparisons against `std`.
+//!
+//! Several task READMEs make performance claims (pre-allocated pools beat
+//! `malloc`, a lock-free stack beats a `Mutex`); the comparisons here let
+//! those claims be checked instead of taken on trust. Timings are only
+//! meaningful in release builds: `cargo run --release --example bench`.
+//!
+//! ```
+//! use rusty_repo::bench::Benchmark;
+//! use std::time::Duration;
+//!
+//! let report = Benchmark::new("sum")
+//!     .samples(3)
+//!     .sample_time(Duration::from_micros(100))
+//!     .run(|b| b.iter(|| (0..100u64).sum::<u64>()));
+//! println!("{}", report);
+//! ```
+
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::fmt;
+use std::hint::black_box;
+use std::rc::{Rc, Weak};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::arena::Scratch;
+#[cfg(not(feature = "safe-only"))]
+use crate::linked_list::CycleStrategy;
+use crate::queue::QueueStats;
+use crate::{
+    ArenaGraph, LinkedList, LockFreeStack, MemoryArena, MemoryPool, Queue, RingBuffer,
+    UnrolledLinkedList,
+};
+// Straight from task 6, since `safe-only` leaves the allocators out of the facade
+use task_06_alloc::CustomAllocator;
+
+/// A named measurement, configured with builder methods and consumed by `run`
+pub struct Benchmark {
+    name: String,
+    samples: usize,
+    sample_time: Duration,
+}
+
+impl Benchmark {
+    /// Creates a benchmark taking 20 samples of about 10ms each
+    pub fn new(name: impl Into<String>) -> Self {
+        Benchmark {
+            name: name.into(),
+            samples: 20,
+            sample_time: Duration::from_millis(10),
+        }
+    }
+
+    /// Sets how many samples to take; at least one
+    pub fn samples(mut self, samples: usize) -> Self {
+        self.samples = samples.max(1);
+        self
+    }
+
+    /// Sets roughly how long each sample should run
+    pub fn sample_time(mut self, sample_time: Duration) -> Self {
+        self.sample_time = sample_time;
+        self
+    }
+
+    /// Times the routine that `setup` hands to `Bencher::iter`
+    ///
+    /// The iteration count is doubled until one sample takes `sample_time`,
+    /// then every sample runs that many iterations.
+    pub fn run<F: FnMut(&mut Bencher)>(self, mut setup: F) -> Report {
+        let mut bencher = Bencher {
+            iterations: 1,
+            elapsed: Duration::ZERO,
+        };
+        loop {
+            setup(&mut bencher);
+            if bencher.elapsed >= self.sample_time || bencher.iterations >= 1 << 30 {
+                break;
+            }
+            bencher.iterations *= 2;
+        }
+
+        let samples = (0..self.samples)
+            .map(|_| {
+                setup(&mut bencher);
+                bencher.elapsed.as_nanos() as f64 / bencher.iterations as f64
+            })
+            .collect();
+        Report {
+            name: self.name,
+            iterations: bencher.iterations,
+            samples,
+        }
+    }
+}
+
+/// Runs the measured routine; handed to the closure passed to `Benchmark::run`
+pub struct Bencher {
+    iterations: u64,
+    elapsed: Duration,
+}
+
+impl Bencher {
+    /// Times `routine` over the current number of iterations
+    ///
+    /// Results go through `black_box` so the optimizer can't remove the work.
+    pub fn iter<R, F: FnMut() -> R>(&mut self, mut routine: F) {
+        let start = Instant::now();
+        for _ in 0..self.iterations {
+            black_box(routine());
+        }
+        self.elapsed = start.elapsed();
+    }
+
+    /// Like `iter`, but each iteration gets a fresh input from `setup`, which isn't timed
+    pub fn iter_with_setup<I, R, S, F>(&mut self, mut setup: S, mut routine: F)
+    where
+        S: FnMut() -> I,
+        F: FnMut(I) -> R,
+    {
+        let mut elapsed = Duration::ZERO;
+        for _ in 0..self.iterations {
+            let input = setup();
+            let start = Instant::now();
+            black_box(routine(input));
+            elapsed += start.elapsed();
+        }
+        self.elapsed = elapsed;
+    }
+}
+
+/// Per-iteration timings of one benchmark, in nanoseconds
+#[derive(Debug, Clone)]
+pub struct Report {
+    name: String,
+    iterations: u64,
+    samples: Vec<f64>,
+}
+
+impl Report {
+    /// Returns the benchmark's name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns how many iterations each sample ran
+    pub fn iterations(&self) -> u64 {
+        self.iterations
+    }
+
+    /// Returns the time per iteration of every sample
+    pub fn samples(&self) -> &[f64] {
+        &self.samples
+    }
+
+    /// Returns the median time per iteration; robust against outlier samples
+    pub fn median(&self) -> f64 {
+        let mut sorted = self.samples.clone();
+        sorted.sort_by(f64::total_cmp);
+        let mid = sorted.len() / 2;
+        if sorted.len().is_multiple_of(2) {
+            (sorted[mid - 1] + sorted[mid]) / 2.0
+        } else {
+            sorted[mid]
+        }
+    }
+
+    /// Returns the mean time per iteration
+    pub fn mean(&self) -> f64 {
+        self.samples.iter().sum::<f64>() / self.samples.len() as f64
+    }
+
+    /// Returns the fastest sample
+    pub fn min(&self) -> f64 {
+        self.samples.iter().copied().fold(f64::INFINITY, f64::min)
+    }
+
+    /// Returns the slowest sample
+    pub fn max(&self) -> f64 {
+        self.samples.iter().copied().fold(0.0, f64::max)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:<32} {:>12.1} ns/iter  (min {:.1}, max {:.1})",
+            self.name,
+            self.median(),
+            self.min(),
+            self.max()
+        )
+    }
+}
+
+/// One of the repo's structures measured against its standard library counterpart
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    pub ours: Report,
+    pub baseline: Report,
+}
+
+impl Comparison {
+    /// Returns how many times faster ours is than the baseline; below 1.0 means slower
+    pub fn speedup(&self) -> f64 {
+        self.baseline.median() / self.ours.median()
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.ours)?;
+        writeln!(f, "{}", self.baseline)?;
+        write!(f, "{:<32} {:>12.2}x", "speedup", self.speedup())
+    }
+}
+
+/// Fills a buffer of `capacity` past full, so later pushes overwrite, then drains it
+pub fn ring_buffer_vs_vec_deque(capacity: usize) -> Comparison {
+    let ops = capacity * 2;
+    let ours = Benchmark::new(format!("RingBuffer push/pop x{}", ops)).run(|b| {
+        b.iter(|| {
+            let mut buffer = RingBuffer::new(capacity);
+            for i in 0..ops {
+                buffer.push(i);
+            }
+            while let Some(item) = buffer.pop() {
+                black_box(item);
+            }
+        })
+    });
+    let baseline = Benchmark::new(format!("VecDeque push/pop x{}", ops)).run(|b| {
+        b.iter(|| {
+            let mut deque = VecDeque::with_capacity(capacity);
+            for i in 0..ops {
+                if deque.len() == capacity {
+                    deque.pop_front();
+                }
+                deque.push_back(i);
+            }
+            while let Some(item) = deque.pop_front() {
+                black_box(item);
+            }
+        })
+    });
+    Comparison { ours, baseline }
+}
+
+/// `threads` producers push `per_thread` items each, then one thread pops them all
+///
+/// Pops stay on a single thread because `LockFreeStack` only supports one consumer.
+pub fn lock_free_stack_vs_mutex_vec(threads: usize, per_thread: usize) -> Comparison {
+    let ops = threads * per_thread;
+    let ours = Benchmark::new(format!("LockFreeStack {}x{} pushes", threads, per_thread))
+        .samples(10)
+        .run(|b| {
+            b.iter(|| {
+                let stack = Arc::new(LockFreeStack::new());
+                thread::scope(|scope| {
+                    for _ in 0..threads {
+                        let stack = Arc::clone(&stack);
+                        scope.spawn(move || (0..per_thread).for_each(|i| stack.push(i)));
+                    }
+                });
+                let mut popped = 0;
+                while stack.pop().is_some() {
+                    popped += 1;
+                }
+                assert_eq!(popped, ops);
+            })
+        });
+    let baseline = Benchmark::new(format!("Mutex<Vec> {}x{} pushes", threads, per_thread))
+        .samples(10)
+        .run(|b| {
+            b.iter(|| {
+                let stack = Arc::new(Mutex::new(Vec::new()));
+                thread::scope(|scope| {
+                    for _ in 0..threads {
+                        let stack = Arc::clone(&stack);
+                        scope.spawn(move || {
+                            (0..per_thread).for_each(|i| stack.lock().unwrap().push(i))
+                        });
+                    }
+                });
+                let mut popped = 0;
+                while stack.lock().unwrap().pop().is_some() {
+                    popped += 1;
+                }
+                assert_eq!(popped, ops);
+            })
+        });
+    Comparison { ours, baseline }
+}
+
+/// Makes `count` allocations of `size` bytes and frees them all at once
+pub fn arena_vs_box(count: usize, size: usize) -> Comparison {
+    let mut arena = MemoryArena::new(count * size);
+    let ours = Benchmark::new(format!("MemoryArena {}x{}B", count, size)).run(|b| {
+        b.iter(|| {
+            for _ in 0..count {
+                black_box(arena.allocate(size));
+            }
+            arena.reset();
+        })
+    });
+    let baseline = Benchmark::new(format!("Box<[u8]> {}x{}B", count, size)).run(|b| {
+        b.iter(|| {
+            let boxes: Vec<Box<[u8]>> = (0..count)
+                .map(|_| vec![0; size].into_boxed_slice())
+                .collect();
+            black_box(boxes)
+        })
+    });
+    Comparison { ours, baseline }
+}
+
+/// Takes `count` chunks of `chunk_size` bytes and gives them back
+pub fn pool_vs_malloc(count: usize, chunk_size: usize) -> Comparison {
+    let pool = MemoryPool::new(chunk_size, count);
+    let ours = Benchmark::new(format!("MemoryPool {}x{}B", count, chunk_size)).run(|b| {
+        b.iter(|| {
+            let chunks: Vec<Vec<u8>> = (0..count).map(|_| pool.allocate().unwrap()).collect();
+            for chunk in chunks {
+                pool.deallocate(chunk);
+            }
+        })
+    });
+    let baseline = Benchmark::new(format!("vec![0; n] {}x{}B", count, chunk_size)).run(|b| {
+        b.iter(|| {
+            let chunks: Vec<Vec<u8>> = (0..count).map(|_| vec![0; chunk_size]).collect();
+            black_box(chunks)
+        })
+    });
+    Comparison { ours, baseline }
+}
+
+// The textbook pointer graph `ArenaGraph` replaces: every node and every edge
+// list is its own allocation. Edges are `Weak` so that cycles do not leak.
+struct RcNode {
+    visited: Cell<bool>,
+    edges: RefCell<Vec<Weak<RcNode>>>,
+}
+
+fn rc_graph(nodes: usize, edges: &[(usize, usize)]) -> Vec<Rc<RcNode>> {
+    let graph: Vec<Rc<RcNode>> = (0..nodes)
+        .map(|_| {
+            Rc::new(RcNode {
+                visited: Cell::new(false),
+                edges: RefCell::new(Vec::new()),
+            })
+        })
+        .collect();
+    for &(from, to) in edges {
+        graph[from]
+            .edges
+            .borrow_mut()
+            .push(Rc::downgrade(&graph[to]));
+    }
+    graph
+}
+
+// Counts the nodes reachable from the first one, then clears the marks
+fn rc_bfs(graph: &[Rc<RcNode>]) -> usize {
+    let mut queue = VecDeque::from([Rc::clone(&graph[0])]);
+    graph[0].visited.set(true);
+    let mut reached = 0;
+    while let Some(node) = queue.pop_front() {
+        reached += 1;
+        for next in node.edges.borrow().iter().filter_map(Weak::upgrade) {
+            if !next.visited.replace(true) {
+                queue.push_back(next);
+            }
+        }
+    }
+    graph.iter().for_each(|node| node.visited.set(false));
+    reached
+}
+
+// `degree` random out-edges per node, from a fixed seed so runs are comparable
+fn random_edges(nodes: usize, degree: usize) -> Vec<(usize, usize)> {
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    (0..nodes * degree)
+        .map(|i| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (i / degree, (state % nodes as u64) as usize)
+        })
+        .collect()
+}
+
+/// Breadth-first traversal of a random graph with `nodes` nodes and `degree` out-edges each
+pub fn arena_graph_vs_rc_graph(nodes: usize, degree: usize) -> Comparison {
+    let edges = random_edges(nodes, degree);
+    let arena_graph = ArenaGraph::from_edges(vec![(); nodes], edges.iter().copied());
+    let pointer_graph = rc_graph(nodes, &edges);
+    let expected = rc_bfs(&pointer_graph);
+
+    let mut scratch = Scratch::new();
+    let start = arena_graph.node(0).expect("graph has nodes");
+    let ours = Benchmark::new(format!("ArenaGraph BFS {}x{}", nodes, degree)).run(|b| {
+        b.iter(|| {
+            let mut reached = 0;
+            arena_graph.bfs(start, &mut scratch, |node| {
+                black_box(node);
+                reached += 1;
+            });
+            assert_eq!(reached, expected);
+        })
+    });
+    let baseline = Benchmark::new(format!("Rc graph BFS {}x{}", nodes, degree))
+        .run(|b| b.iter(|| black_box(rc_bfs(&pointer_graph))));
+    Comparison { ours, baseline }
+}
+
+/// Returns how many heap allocations an `ArenaGraph` and an `Rc` graph of the same shape hold
+///
+/// Counts through `CustomAllocator`, so both numbers are zero unless it is
+/// the `#[global_allocator]`, as in `examples/bench.rs`. Other threads
+/// allocating at the same time would skew the counts.
+pub fn graph_allocations(nodes: usize, degree: usize) -> (usize, usize) {
+    let edges = random_edges(nodes, degree);
+    let arena =
+        held_allocations(|| ArenaGraph::from_edges(vec![0u32; nodes], edges.iter().copied()));
+    let rc = held_allocations(|| rc_graph(nodes, &edges));
+    (arena, rc)
+}
+
+// Live allocations added by `build`'s result, counted while it is still alive
+fn held_allocations<G>(build: impl FnOnce() -> G) -> usize {
+    let before = CustomAllocator::live_allocations();
+    let graph = build();
+    let held = CustomAllocator::live_allocations().saturating_sub(before);
+    drop(graph);
+    held
+}
+
+/// Builds a list of `len` values, sums it by iterating, then reads every 64th value by index
+///
+/// Both lists are compared on the same work; the baseline is the boxed-node
+/// `LinkedList` rather than a standard library type.
+pub fn unrolled_list_vs_linked_list(len: usize) -> Comparison {
+    let ours = Benchmark::new(format!("UnrolledLinkedList x{}", len)).run(|b| {
+        b.iter(|| {
+            let mut list = UnrolledLinkedList::new();
+            for i in 0..len {
+                list.push(i);
+            }
+            black_box(list.iter().sum::<usize>());
+            for index in (0..len).step_by(64) {
+                black_box(list.get(index));
+            }
+        })
+    });
+    let baseline = Benchmark::new(format!("LinkedList x{}", len)).run(|b| {
+        b.iter(|| {
+            let mut list = LinkedList::new();
+            for i in 0..len {
+                list.push(i);
+            }
+            black_box(list.iter().sum::<usize>());
+            for index in (0..len).step_by(64) {
+                black_box(list.get(index));
+            }
+        })
+    });
+    Comparison { ours, baseline }
+}
+
+/// `analyze_cycle` with Brent's algorithm against Floyd's, on a list of `len` nodes
+/// whose second half is a cycle
+///
+/// Left out under `safe-only`, which can't build the cyclic list.
+#[cfg(not(feature = "safe-only"))]
+pub fn brent_vs_floyd(len: usize) -> Comparison {
+    let mut list: LinkedList<usize> = (0..len).collect();
+    // SAFETY: the list is only read until it drops, which breaks the cycle first
+    unsafe { list.create_cycle_at(len / 2) };
+    let ours = Benchmark::new(format!("Brent cycle analysis x{}", len))
+        .run(|b| b.iter(|| black_box(list.analyze_cycle_with(CycleStrategy::Brent))));
+    let baseline = Benchmark::new(format!("Floyd cycle analysis x{}", len))
+        .run(|b| b.iter(|| black_box(list.analyze_cycle_with(CycleStrategy::Floyd))));
+    Comparison { ours, baseline }
+}
+
+/// Enqueues `ops` values with a dequeue after every second one, then drains the rest
+///
+/// The mix keeps both of `Queue`'s stacks in use, so the timing includes the
+/// transfers between them.
+pub fn queue_vs_vec_deque(ops: usize) -> Comparison {
+    let ours = Benchmark::new(format!("Queue enqueue/dequeue x{}", ops))
+        .run(|b| b.iter(|| black_box(queue_workload(ops))));
+    let baseline = Benchmark::new(format!("VecDeque push/pop x{}", ops)).run(|b| {
+        b.iter(|| {
+            let mut deque = VecDeque::new();
+            for i in 0..ops {
+                deque.push_back(i);
+                if i % 2 == 1 {
+                    black_box(deque.pop_front());
+                }
+            }
+            while let Some(item) = deque.pop_front() {
+                black_box(item);
+            }
+        })
+    });
+    Comparison { ours, baseline }
+}
+
+/// Returns the `QueueStats` after one run of `queue_vs_vec_deque`'s workload
+///
+/// `elements_moved` stays at or below `ops`: each value crosses between the
+/// stacks at most once, which is what makes `dequeue` amortized O(1).
+pub fn queue_amortization(ops: usize) -> QueueStats {
+    queue_workload(ops).stats()
+}
+
+fn queue_workload(ops: usize) -> Queue<usize> {
+    let mut queue = Queue::new();
+    for i in 0..ops {
+        queue.enqueue(i);
+        if i % 2 == 1 {
+            black_box(queue.dequeue());
+        }
+    }
+    while let Some(item) = queue.dequeue() {
+        black_box(item);
+    }
+    queue
+}
+
+/// Runs every comparison with moderate sizes
+pub fn all() -> Vec<Comparison> {
+    vec![
+        ring_buffer_vs_vec_deque(1024),
+        lock_free_stack_vs_mutex_vec(4, 10_000),
+        arena_vs_box(1000, 64),
+        pool_vs_malloc(100, 4096),
+        arena_graph_vs_rc_graph(10_000, 4),
+        unrolled_list_vs_linked_list(4096),
+        queue_vs_vec_deque(10_000),
+        #[cfg(not(feature = "safe-only"))]
+        brent_vs_floyd(10_000),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quick(name: &str) -> Benchmark {
+        Benchmark::new(name)
+            .samples(5)
+            .sample_time(Duration::from_micros(200))
+    }
+
+    #[test]
+    fn test_happy_path_calibrates_iterations() {
+        let report = quick("spin").run(|b| b.iter(|| (0..50u64).product::<u64>()));
+        assert_eq!(report.name(), "spin");
+        assert_eq!(report.samples().len(), 5);
+        assert!(report.iterations() > 1, "Cheap work gets more iterations");
+        assert!(report.min() <= report.median() && report.median() <= report.max());
+        assert!(report.to_string().contains("ns/iter"));
+    }
+
+    #[test]
+    fn test_setup_is_not_timed() {
+        let report = quick("setup").run(|b| {
+            b.iter_with_setup(
+                || thread::sleep(Duration::from_micros(50)),
+                |()| black_box(1),
+            )
+        });
+        assert!(
+            report.median() < 50_000.0,
+            "Sleeping in setup must not count: {}",
+            report
+        );
+    }
+
+    #[test]
+    fn test_edge_case_zero_samples_become_one() {
+        let report = quick("one").samples(0).run(|b| b.iter(|| ()));
+        assert_eq!(report.samples().len(), 1);
+        assert_eq!(report.median(), report.mean());
+    }
+
+    #[test]
+    fn test_median_and_speedup() {
+        let report = |name: &str, samples: Vec<f64>| Report {
+            name: name.to_string(),
+            iterations: 1,
+            samples,
+        };
+        let comparison = Comparison {
+            ours: report("ours", vec![10.0, 1000.0, 20.0]),
+            baseline: report("baseline", vec![30.0, 50.0, 40.0, 60.0]),
+        };
+        assert_eq!(comparison.ours.median(), 20.0, "The outlier is ignored");
+        assert_eq!(comparison.baseline.median(), 45.0);
+        assert_eq!(comparison.speedup(), 2.25);
+        assert!(comparison.to_string().ends_with("2.25x"));
+    }
+
+    #[test]
+    fn test_prewritten_comparisons_run() {
+        // Tiny sizes: this checks the workloads are correct, not how fast they are
+        for comparison in [
+            ring_buffer_vs_vec_deque(8),
+            lock_free_stack_vs_mutex_vec(2, 10),
+            arena_vs_box(8, 16),
+            pool_vs_malloc(4, 64),
+            arena_graph_vs_rc_graph(16, 2),
+            unrolled_list_vs_linked_list(100),
+            queue_vs_vec_deque(100),
+        ] {
+            assert!(comparison.speedup().is_finite() && comparison.speedup() > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_queue_moves_each_value_at_most_once() {
+        let stats = queue_amortization(1000);
+        assert!(
+            stats.transfers > 1,
+            "The workload refills the dequeue stack"
+        );
+        assert!(stats.elements_moved <= 1000);
+    }
+
+    #[test]
+    #[cfg(not(feature = "safe-only"))]
+    fn test_cycle_comparison_runs() {
+        let comparison = brent_vs_floyd(16);
+        assert!(comparison.speedup().is_finite() && comparison.speedup() > 0.0);
+    }
+}
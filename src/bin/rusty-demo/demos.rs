@@ -0,0 +1,622 @@
+//! One `Demo` per structure: each parses the REPL's words into calls on the
+//! real container and describes the result as a line of text.
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use rusty_repo::{
+    AsciiArt, CircularBuffer, DaryHeap, LinkedList, LruCache, MemoryArena, MemoryPool, Queue,
+    RingBuffer, Stack, Trie,
+};
+
+/// A structure driven by REPL commands
+pub trait Demo {
+    /// `(usage, description)` for every command, shown by `help`
+    fn commands(&self) -> &'static [(&'static str, &'static str)];
+
+    /// Runs one command; `args` are the words after the command name
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, CommandError>;
+}
+
+/// Why a REPL command could not run; the REPL reports it and carries on
+#[derive(Debug, PartialEq)]
+pub enum CommandError {
+    Unknown(String),
+    MissingArgument(&'static str),
+    InvalidArgument {
+        name: &'static str,
+        value: String,
+    },
+    /// The structure refused the operation, e.g. an exhausted pool
+    Refused(String),
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommandError::Unknown(command) => {
+                write!(f, "unknown command `{command}`, try `help`")
+            }
+            CommandError::MissingArgument(name) => write!(f, "missing argument <{name}>"),
+            CommandError::InvalidArgument { name, value } => {
+                write!(f, "invalid <{name}>: `{value}`")
+            }
+            CommandError::Refused(reason) => f.write_str(reason),
+        }
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+/// Structures the demo knows: name, description, and the options it accepts
+pub const STRUCTURES: &[(&str, &str, &[&str])] = &[
+    ("stack", "LIFO stack of integers", &[]),
+    ("queue", "FIFO queue built from two stacks", &[]),
+    (
+        "ringbuffer",
+        "fixed-size buffer that overwrites the oldest value",
+        &["capacity"],
+    ),
+    (
+        "circularbuffer",
+        "fixed-size buffer with Default-filled slots",
+        &["capacity"],
+    ),
+    ("linkedlist", "singly linked list, pushing at the head", &[]),
+    (
+        "lru",
+        "least-recently-used cache from words to integers",
+        &["capacity"],
+    ),
+    ("heap", "4-ary min-heap of integers", &[]),
+    ("trie", "prefix tree of words", &[]),
+    (
+        "arena",
+        "bump allocator over one block of memory",
+        &["size"],
+    ),
+    (
+        "mempool",
+        "pool of fixed-size chunks",
+        &["chunk-size", "chunks"],
+    ),
+];
+
+/// Builds the demo for `name`, reading its options (`--name value`) with defaults
+pub fn build(name: &str, option: impl Fn(&str, usize) -> usize) -> Option<Box<dyn Demo>> {
+    let demo: Box<dyn Demo> = match name {
+        "stack" => Box::new(StackDemo(Stack::new())),
+        "queue" => Box::new(QueueDemo(Queue::new())),
+        "ringbuffer" => Box::new(RingBufferDemo(RingBuffer::new(option("capacity", 8)))),
+        "circularbuffer" => Box::new(CircularBufferDemo(CircularBuffer::new(option(
+            "capacity", 8,
+        )))),
+        "linkedlist" => Box::new(LinkedListDemo(LinkedList::new())),
+        "lru" => Box::new(LruDemo(LruCache::new(option("capacity", 4)))),
+        "heap" => Box::new(HeapDemo(DaryHeap::new())),
+        "trie" => Box::new(TrieDemo(Trie::new())),
+        "arena" => Box::new(ArenaDemo(MemoryArena::new(option("size", 4096)))),
+        "mempool" => Box::new(PoolDemo {
+            pool: MemoryPool::new(option("chunk-size", 64), option("chunks", 8)),
+            held: Vec::new(),
+            next_id: 0,
+        }),
+        _ => return None,
+    };
+    Some(demo)
+}
+
+fn arg<'a>(args: &[&'a str], index: usize, name: &'static str) -> Result<&'a str, CommandError> {
+    args.get(index)
+        .copied()
+        .ok_or(CommandError::MissingArgument(name))
+}
+
+fn parsed<T: FromStr>(args: &[&str], index: usize, name: &'static str) -> Result<T, CommandError> {
+    let value = arg(args, index, name)?;
+    value.parse().map_err(|_| CommandError::InvalidArgument {
+        name,
+        value: value.to_string(),
+    })
+}
+
+fn or_empty<T: fmt::Display>(value: Option<T>) -> String {
+    value.map_or_else(|| "(empty)".to_string(), |value| value.to_string())
+}
+
+fn list<T: fmt::Display>(values: impl Iterator<Item = T>) -> String {
+    let values: Vec<String> = values.map(|value| value.to_string()).collect();
+    format!("[{}]", values.join(", "))
+}
+
+struct StackDemo(Stack<i64>);
+
+impl Demo for StackDemo {
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("push <n>", "push onto the top"),
+            ("pop", "remove the top"),
+            ("peek", "show the top"),
+            ("clear", "remove everything"),
+            ("show", "list bottom to top"),
+            ("stats", "show the size"),
+        ]
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, CommandError> {
+        let stack = &mut self.0;
+        Ok(match command {
+            "push" => {
+                stack
+                    .push(parsed(args, 0, "n")?)
+                    .map_err(|_| CommandError::Refused("stack is full".to_string()))?;
+                format!("size {}", stack.size())
+            }
+            "pop" => or_empty(stack.pop()),
+            "peek" => or_empty(stack.peek()),
+            "clear" => {
+                stack.clear();
+                "cleared".to_string()
+            }
+            "show" => list(stack.iter()),
+            "stats" => format!("size {}", stack.size()),
+            _ => return Err(CommandError::Unknown(command.to_string())),
+        })
+    }
+}
+
+struct QueueDemo(Queue<i64>);
+
+impl Demo for QueueDemo {
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("enqueue <n> | push <n>", "add at the back"),
+            ("dequeue | pop", "remove from the front"),
+            ("clear", "remove everything"),
+            ("show", "list front to back"),
+            ("stats", "show the size"),
+        ]
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, CommandError> {
+        let queue = &mut self.0;
+        Ok(match command {
+            "enqueue" | "push" => {
+                queue.enqueue(parsed(args, 0, "n")?);
+                format!("size {}", queue.size())
+            }
+            "dequeue" | "pop" => or_empty(queue.dequeue()),
+            "clear" => {
+                queue.clear();
+                "cleared".to_string()
+            }
+            "show" => list(queue.iter()),
+            "stats" => format!("size {}", queue.size()),
+            _ => return Err(CommandError::Unknown(command.to_string())),
+        })
+    }
+}
+
+const BUFFER_COMMANDS: &[(&str, &str)] = &[
+    (
+        "push <n>",
+        "add the newest value, overwriting the oldest when full",
+    ),
+    ("pop", "remove the oldest value"),
+    ("clear", "remove everything"),
+    ("show", "draw the slots with head (H) and tail (T) markers"),
+    ("stats", "show size and capacity"),
+];
+
+struct RingBufferDemo(RingBuffer<i64>);
+
+impl Demo for RingBufferDemo {
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        BUFFER_COMMANDS
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, CommandError> {
+        let buffer = &mut self.0;
+        Ok(match command {
+            "push" => {
+                let overwrites = buffer.is_full();
+                buffer.push(parsed(args, 0, "n")?);
+                if overwrites {
+                    "overwrote the oldest value".to_string()
+                } else {
+                    format!("size {}", buffer.size())
+                }
+            }
+            "pop" => or_empty(buffer.pop()),
+            "clear" => {
+                buffer.clear();
+                "cleared".to_string()
+            }
+            "show" => buffer.ascii_art().trim_end().to_string(),
+            "stats" => format!("size {}/{}", buffer.size(), buffer.capacity()),
+            _ => return Err(CommandError::Unknown(command.to_string())),
+        })
+    }
+}
+
+struct CircularBufferDemo(CircularBuffer<i64>);
+
+impl Demo for CircularBufferDemo {
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        BUFFER_COMMANDS
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, CommandError> {
+        let buffer = &mut self.0;
+        Ok(match command {
+            "push" => {
+                let overwrites = buffer.size() == buffer.capacity();
+                buffer.push(parsed(args, 0, "n")?);
+                if overwrites {
+                    "overwrote the oldest value".to_string()
+                } else {
+                    format!("size {}", buffer.size())
+                }
+            }
+            "pop" => or_empty(buffer.pop()),
+            "clear" => {
+                buffer.clear();
+                "cleared".to_string()
+            }
+            "show" => buffer.ascii_art().trim_end().to_string(),
+            "stats" => format!("size {}/{}", buffer.size(), buffer.capacity()),
+            _ => return Err(CommandError::Unknown(command.to_string())),
+        })
+    }
+}
+
+struct LinkedListDemo(LinkedList<i64>);
+
+impl Demo for LinkedListDemo {
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("push <n>", "add at the head"),
+            ("pop", "remove the head"),
+            ("get <index>", "show the value at an index"),
+            ("reverse", "reverse the list in place"),
+            ("clear", "remove everything"),
+            ("show", "list head to tail"),
+            ("stats", "show the length"),
+        ]
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, CommandError> {
+        let linked = &mut self.0;
+        Ok(match command {
+            "push" => {
+                linked.push(parsed(args, 0, "n")?);
+                format!("length {}", linked.len())
+            }
+            "pop" => or_empty(linked.pop()),
+            "get" => {
+                let index: usize = parsed(args, 0, "index")?;
+                linked.get(index).map(ToString::to_string).ok_or_else(|| {
+                    CommandError::Refused(format!(
+                        "index {index} is out of range for length {}",
+                        linked.len()
+                    ))
+                })?
+            }
+            "reverse" => {
+                linked.reverse();
+                list(linked.iter())
+            }
+            "clear" => {
+                linked.clear();
+                "cleared".to_string()
+            }
+            "show" => list(linked.iter()),
+            "stats" => format!("length {}", linked.len()),
+            _ => return Err(CommandError::Unknown(command.to_string())),
+        })
+    }
+}
+
+struct LruDemo(LruCache<String, i64>);
+
+impl Demo for LruDemo {
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            (
+                "put <key> <n>",
+                "insert or replace, evicting the least recent entry if full",
+            ),
+            ("get <key>", "look up and mark most recently used"),
+            ("peek <key>", "look up without touching recency"),
+            ("remove <key>", "drop an entry"),
+            ("clear", "remove everything"),
+            ("show", "list entries, most recently used first"),
+            ("stats", "show size, hits, misses and evictions"),
+        ]
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, CommandError> {
+        let cache = &mut self.0;
+        Ok(match command {
+            "put" => {
+                let key = arg(args, 0, "key")?.to_string();
+                let value = parsed(args, 1, "n")?;
+                let evictions = cache.stats().evictions;
+                let old = cache.put(key, value);
+                match (old, cache.stats().evictions > evictions) {
+                    (Some(old), _) => format!("replaced {old}"),
+                    (None, true) => "inserted, evicted the least recent entry".to_string(),
+                    (None, false) => "inserted".to_string(),
+                }
+            }
+            "get" => or_empty(cache.get(arg(args, 0, "key")?)),
+            "peek" => or_empty(cache.peek(arg(args, 0, "key")?)),
+            "remove" => or_empty(cache.remove(arg(args, 0, "key")?)),
+            "clear" => {
+                cache.clear();
+                "cleared".to_string()
+            }
+            "show" => list(cache.iter().map(|(key, value)| format!("{key}: {value}"))),
+            "stats" => {
+                let stats = cache.stats();
+                format!(
+                    "size {}/{}, {} hits, {} misses, {} evictions, hit rate {:.0}%",
+                    cache.len(),
+                    cache.capacity(),
+                    stats.hits,
+                    stats.misses,
+                    stats.evictions,
+                    stats.hit_rate() * 100.0
+                )
+            }
+            _ => return Err(CommandError::Unknown(command.to_string())),
+        })
+    }
+}
+
+struct HeapDemo(DaryHeap<i64, 4>);
+
+impl Demo for HeapDemo {
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("push <n>", "insert a value"),
+            ("pop", "remove the smallest value"),
+            ("peek", "show the smallest value"),
+            ("clear", "remove everything"),
+            ("stats", "show the size"),
+        ]
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, CommandError> {
+        let heap = &mut self.0;
+        Ok(match command {
+            "push" => {
+                heap.push(parsed(args, 0, "n")?);
+                format!("size {}, smallest {}", heap.len(), or_empty(heap.peek()))
+            }
+            "pop" => or_empty(heap.pop()),
+            "peek" => or_empty(heap.peek()),
+            "clear" => {
+                heap.clear();
+                "cleared".to_string()
+            }
+            "stats" => format!("size {}", heap.len()),
+            _ => return Err(CommandError::Unknown(command.to_string())),
+        })
+    }
+}
+
+struct TrieDemo(Trie);
+
+impl Demo for TrieDemo {
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("insert <word>...", "add words"),
+            ("remove <word>", "drop a word"),
+            ("contains <word>", "check for an exact word"),
+            ("complete <prefix>", "list words starting with a prefix"),
+            ("clear", "remove everything"),
+            ("show", "list every word"),
+            ("stats", "show the number of words"),
+        ]
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, CommandError> {
+        let trie = &mut self.0;
+        Ok(match command {
+            "insert" => {
+                arg(args, 0, "word")?;
+                let added = args.iter().filter(|word| trie.insert(word)).count();
+                format!("added {added}, {} words", trie.len())
+            }
+            "remove" => {
+                let removed = trie.remove(arg(args, 0, "word")?);
+                if removed { "removed" } else { "not found" }.to_string()
+            }
+            "contains" => trie.contains(arg(args, 0, "word")?).to_string(),
+            "complete" => list(trie.words_with_prefix(arg(args, 0, "prefix")?)),
+            "clear" => {
+                trie.clear();
+                "cleared".to_string()
+            }
+            "show" => list(trie.words()),
+            "stats" => format!("{} words", trie.len()),
+            _ => return Err(CommandError::Unknown(command.to_string())),
+        })
+    }
+}
+
+struct ArenaDemo(MemoryArena);
+
+impl Demo for ArenaDemo {
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("alloc <bytes>", "bump-allocate a block"),
+            ("reset", "free every block at once"),
+            ("stats", "show used, remaining and peak bytes"),
+        ]
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, CommandError> {
+        let arena = &mut self.0;
+        Ok(match command {
+            "alloc" => {
+                let bytes: usize = parsed(args, 0, "bytes")?;
+                let offset = arena.used();
+                arena.allocate(bytes).ok_or_else(|| {
+                    CommandError::Refused(format!(
+                        "cannot allocate {bytes} bytes, {} remaining",
+                        arena.remaining()
+                    ))
+                })?;
+                format!("{bytes} bytes at offset {offset}")
+            }
+            "reset" => {
+                arena.reset();
+                "reset".to_string()
+            }
+            "stats" => format!(
+                "{} used, {} remaining, {} allocations, peak {} bytes",
+                arena.used(),
+                arena.remaining(),
+                arena.live_allocations(),
+                arena.peak_used()
+            ),
+            _ => return Err(CommandError::Unknown(command.to_string())),
+        })
+    }
+}
+
+struct PoolDemo {
+    pool: Arc<MemoryPool>,
+    /// Chunks handed out, by the id shown to the user
+    held: Vec<(usize, Vec<u8>)>,
+    next_id: usize,
+}
+
+impl Demo for PoolDemo {
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("alloc", "take a chunk, printing its id"),
+            ("free <id>", "return a chunk"),
+            ("show", "list the ids of chunks in use"),
+            ("stats", "show chunks in use, available and peak"),
+        ]
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, CommandError> {
+        Ok(match command {
+            "alloc" => {
+                let chunk = self
+                    .pool
+                    .allocate()
+                    .ok_or_else(|| CommandError::Refused("pool exhausted".to_string()))?;
+                let id = self.next_id;
+                self.next_id += 1;
+                self.held.push((id, chunk));
+                format!("chunk {id} ({} bytes)", self.pool.chunk_size())
+            }
+            "free" => {
+                let id: usize = parsed(args, 0, "id")?;
+                let position = self
+                    .held
+                    .iter()
+                    .position(|(held, _)| *held == id)
+                    .ok_or_else(|| CommandError::Refused(format!("chunk {id} is not in use")))?;
+                let (_, chunk) = self.held.swap_remove(position);
+                self.pool.deallocate(chunk);
+                format!("freed chunk {id}")
+            }
+            "show" => {
+                let mut ids: Vec<usize> = self.held.iter().map(|(id, _)| *id).collect();
+                ids.sort_unstable();
+                list(ids.iter())
+            }
+            "stats" => format!(
+                "{} in use, {} available, peak {}",
+                self.pool.chunks_in_use(),
+                self.pool.available_chunks(),
+                self.pool.peak_chunks_in_use()
+            ),
+            _ => return Err(CommandError::Unknown(command.to_string())),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn demo(name: &str) -> Box<dyn Demo> {
+        build(name, |_, default| default).unwrap()
+    }
+
+    #[test]
+    fn test_happy_path_every_structure_has_stats() {
+        for (name, _, _) in STRUCTURES {
+            let mut demo = demo(name);
+            assert!(demo.execute("stats", &[]).is_ok(), "{name}");
+            assert!(!demo.commands().is_empty());
+        }
+        assert!(build("btree", |_, default| default).is_none());
+    }
+
+    #[test]
+    fn test_happy_path_stack_session() {
+        let mut stack = demo("stack");
+        stack.execute("push", &["5"]).unwrap();
+        stack.execute("push", &["7"]).unwrap();
+        assert_eq!(stack.execute("show", &[]).unwrap(), "[5, 7]");
+        assert_eq!(stack.execute("pop", &[]).unwrap(), "7");
+        assert_eq!(stack.execute("peek", &[]).unwrap(), "5");
+    }
+
+    #[test]
+    fn test_lru_reports_evictions() {
+        let mut lru = build("lru", |_, _| 1).unwrap();
+        assert_eq!(lru.execute("put", &["a", "1"]).unwrap(), "inserted");
+        assert_eq!(
+            lru.execute("put", &["b", "2"]).unwrap(),
+            "inserted, evicted the least recent entry"
+        );
+        assert_eq!(lru.execute("get", &["a"]).unwrap(), "(empty)");
+    }
+
+    #[test]
+    fn test_unhappy_path_bad_commands() {
+        let mut queue = demo("queue");
+        assert_eq!(
+            queue.execute("push", &[]),
+            Err(CommandError::MissingArgument("n"))
+        );
+        assert_eq!(
+            queue.execute("push", &["five"]),
+            Err(CommandError::InvalidArgument {
+                name: "n",
+                value: "five".to_string()
+            })
+        );
+        assert_eq!(
+            queue.execute("peek", &[]),
+            Err(CommandError::Unknown("peek".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_edge_case_exhausted_allocators_refuse() {
+        let mut arena = build("arena", |_, _| 16).unwrap();
+        assert_eq!(
+            arena.execute("alloc", &["10"]).unwrap(),
+            "10 bytes at offset 0"
+        );
+        let err = arena.execute("alloc", &["10"]).unwrap_err();
+        assert_eq!(err.to_string(), "cannot allocate 10 bytes, 6 remaining");
+
+        let mut pool = build("mempool", |_, _| 1).unwrap();
+        assert_eq!(pool.execute("alloc", &[]).unwrap(), "chunk 0 (1 bytes)");
+        assert!(pool.execute("alloc", &[]).is_err());
+        assert_eq!(pool.execute("free", &["0"]).unwrap(), "freed chunk 0");
+        assert!(pool.execute("free", &["0"]).is_err(), "Double free");
+    }
+}
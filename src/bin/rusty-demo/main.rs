@@ -0,0 +1,228 @@
+//! `rusty-demo <structure> [--option value]...` opens a REPL on one of the
+//! containers. Commands are read line by line from stdin, so a session can
+//! also be piped in: `printf 'push 5\npop\n' | rusty-demo stack`.
+
+mod demos;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::process::ExitCode;
+
+use demos::{Demo, STRUCTURES};
+
+/// Problems with the command line, reported before the REPL starts
+#[derive(Debug, PartialEq)]
+enum ArgError {
+    UnknownStructure(String),
+    UnknownOption { structure: String, option: String },
+    MissingValue(String),
+    InvalidValue { option: String, value: String },
+}
+
+impl fmt::Display for ArgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArgError::UnknownStructure(name) => write!(f, "unknown structure `{name}`"),
+            ArgError::UnknownOption { structure, option } => {
+                write!(f, "`{structure}` has no option `{option}`")
+            }
+            ArgError::MissingValue(option) => write!(f, "`{option}` needs a value"),
+            ArgError::InvalidValue { option, value } => {
+                write!(f, "`{option}` needs a positive integer, got `{value}`")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ArgError {}
+
+fn usage() -> String {
+    let mut text =
+        String::from("usage: rusty-demo <structure> [--option value]...\n\nStructures:\n");
+    for (name, description, options) in STRUCTURES {
+        let options: Vec<String> = options
+            .iter()
+            .map(|option| format!("--{option} N"))
+            .collect();
+        text.push_str(&format!("  {name:<15} {description}"));
+        if !options.is_empty() {
+            text.push_str(&format!(" [{}]", options.join(" ")));
+        }
+        text.push('\n');
+    }
+    text.push_str("\nInside the REPL, `help` lists the structure's commands and `quit` exits.");
+    text
+}
+
+/// Parses `<structure> [--option value]...` into the structure's demo
+fn parse_args(args: &[String]) -> Result<(&str, Box<dyn Demo>), ArgError> {
+    let name = args[0].as_str();
+    let (_, _, accepted) = STRUCTURES
+        .iter()
+        .find(|(structure, _, _)| *structure == name)
+        .ok_or_else(|| ArgError::UnknownStructure(name.to_string()))?;
+
+    let mut options = HashMap::new();
+    let mut rest = args[1..].iter();
+    while let Some(flag) = rest.next() {
+        let option = flag
+            .strip_prefix("--")
+            .filter(|option| accepted.contains(option))
+            .ok_or_else(|| ArgError::UnknownOption {
+                structure: name.to_string(),
+                option: flag.clone(),
+            })?;
+        let value = rest
+            .next()
+            .ok_or_else(|| ArgError::MissingValue(flag.clone()))?;
+        // Zero would make the buffers, cache and allocators unusable
+        let parsed = value
+            .parse::<usize>()
+            .ok()
+            .filter(|&parsed| parsed > 0)
+            .ok_or_else(|| ArgError::InvalidValue {
+                option: flag.clone(),
+                value: value.clone(),
+            })?;
+        options.insert(option, parsed);
+    }
+
+    let demo = demos::build(name, |option, default| {
+        options.get(option).copied().unwrap_or(default)
+    })
+    .expect("every listed structure can be built");
+    Ok((name, demo))
+}
+
+/// Runs one REPL line, returning `None` when the session should end
+fn run_line(demo: &mut dyn Demo, line: &str) -> Option<String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let Some((&command, args)) = words.split_first() else {
+        return Some(String::new());
+    };
+    Some(match command {
+        "quit" | "exit" => return None,
+        "help" => {
+            let mut text = String::new();
+            for (usage, description) in demo.commands() {
+                text.push_str(&format!("  {usage:<24} {description}\n"));
+            }
+            text.push_str(&format!("  {:<24} {}", "quit", "leave the REPL"));
+            text
+        }
+        _ if command.starts_with('#') => String::new(),
+        _ => match demo.execute(command, args) {
+            Ok(output) => output,
+            Err(err) => format!("error: {err}"),
+        },
+    })
+}
+
+fn repl(name: &str, demo: &mut dyn Demo) -> io::Result<()> {
+    let stdin = io::stdin();
+    let interactive = stdin.is_terminal();
+    let mut stdout = io::stdout();
+    if interactive {
+        writeln!(stdout, "{name} demo; type `help` for commands")?;
+    }
+    let mut lines = stdin.lock().lines();
+    loop {
+        if interactive {
+            write!(stdout, "{name}> ")?;
+            stdout.flush()?;
+        }
+        let Some(line) = lines.next().transpose()? else {
+            return Ok(());
+        };
+        match run_line(demo, &line) {
+            Some(output) if output.is_empty() => {}
+            Some(output) => writeln!(stdout, "{output}")?,
+            None => return Ok(()),
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.is_empty() || matches!(args[0].as_str(), "help" | "--help" | "-h") {
+        println!("{}", usage());
+        return ExitCode::SUCCESS;
+    }
+    let (name, mut demo) = match parse_args(&args) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            eprintln!("error: {err}\n\n{}", usage());
+            return ExitCode::from(2);
+        }
+    };
+    match repl(name, demo.as_mut()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_happy_path_options_reach_the_structure() {
+        let args = args("arena --size 8");
+        let (name, mut demo) = parse_args(&args).unwrap();
+        assert_eq!(name, "arena");
+        assert_eq!(
+            run_line(demo.as_mut(), "alloc 8").unwrap(),
+            "8 bytes at offset 0"
+        );
+        assert!(run_line(demo.as_mut(), "alloc 1")
+            .unwrap()
+            .starts_with("error:"));
+    }
+
+    #[test]
+    fn test_unhappy_path_bad_arguments() {
+        let err = |line: &str| parse_args(&args(line)).err().unwrap();
+        assert_eq!(
+            err("btree"),
+            ArgError::UnknownStructure("btree".to_string())
+        );
+        assert_eq!(
+            err("stack --capacity 3"),
+            ArgError::UnknownOption {
+                structure: "stack".to_string(),
+                option: "--capacity".to_string()
+            }
+        );
+        assert_eq!(
+            err("lru --capacity"),
+            ArgError::MissingValue("--capacity".to_string())
+        );
+        assert_eq!(
+            err("ringbuffer --capacity 0"),
+            ArgError::InvalidValue {
+                option: "--capacity".to_string(),
+                value: "0".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_edge_case_repl_lines() {
+        let (_, mut demo) = parse_args(&args("stack")).unwrap();
+        assert_eq!(run_line(demo.as_mut(), "   ").unwrap(), "");
+        assert_eq!(run_line(demo.as_mut(), "# a comment").unwrap(), "");
+        assert!(run_line(demo.as_mut(), "help")
+            .unwrap()
+            .contains("push <n>"));
+        assert!(run_line(demo.as_mut(), "quit").is_none());
+        assert!(usage().contains("ringbuffer"));
+    }
+}
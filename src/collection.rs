@@ -0,0 +1,319 @@
+//! Traits shared by the containers, so generic code can treat them alike.
+//!
+//! The task crates keep their own inherent methods (`size()` on the stack and
+//! queues, `len()` elsewhere); these traits give all of them the same names.
+//! Generic test helpers and benchmarks take `impl Collection` or
+//! `impl OrderedCollection` instead of one concrete container.
+
+use std::hash::Hash;
+
+use crate::circular_buffer::StaticCircularBuffer;
+use crate::{
+    BalancedTree, CircularBuffer, DaryHeap, DoublyLinkedList, LinkedList, LruCache, MyHashMap,
+    Queue, Stack, Trie,
+};
+
+/// A container that knows its size and can be emptied
+pub trait Collection {
+    /// Returns the number of elements
+    fn len(&self) -> usize;
+
+    /// Checks if there are no elements
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes every element
+    fn clear(&mut self);
+}
+
+/// A collection of single values with a well-defined iteration order
+///
+/// The order is the one each container documents: bottom to top for `Stack`,
+/// front to back for `Queue`, oldest to newest for the ring buffers, and head
+/// to tail for `LinkedList`, and front to back for `DoublyLinkedList`.
+pub trait OrderedCollection: Collection {
+    type Item;
+
+    /// Iterates over the elements in the container's order
+    fn iter(&self) -> impl Iterator<Item = &Self::Item>;
+}
+
+impl<T> Collection for Stack<T> {
+    fn len(&self) -> usize {
+        self.size()
+    }
+
+    fn is_empty(&self) -> bool {
+        Stack::is_empty(self)
+    }
+
+    fn clear(&mut self) {
+        Stack::clear(self)
+    }
+}
+
+impl<T> OrderedCollection for Stack<T> {
+    type Item = T;
+
+    fn iter(&self) -> impl Iterator<Item = &T> {
+        Stack::iter(self)
+    }
+}
+
+impl<T> Collection for Queue<T> {
+    fn len(&self) -> usize {
+        self.size()
+    }
+
+    fn is_empty(&self) -> bool {
+        Queue::is_empty(self)
+    }
+
+    fn clear(&mut self) {
+        Queue::clear(self)
+    }
+}
+
+impl<T> OrderedCollection for Queue<T> {
+    type Item = T;
+
+    fn iter(&self) -> impl Iterator<Item = &T> {
+        Queue::iter(self)
+    }
+}
+
+// Also covers `RingBuffer`, which is the same type
+impl<T> Collection for CircularBuffer<T> {
+    fn len(&self) -> usize {
+        self.size()
+    }
+
+    fn is_empty(&self) -> bool {
+        CircularBuffer::is_empty(self)
+    }
+
+    fn clear(&mut self) {
+        CircularBuffer::clear(self)
+    }
+}
+
+impl<T> OrderedCollection for CircularBuffer<T> {
+    type Item = T;
+
+    fn iter(&self) -> impl Iterator<Item = &T> {
+        CircularBuffer::iter(self)
+    }
+}
+
+impl<T, const N: usize> Collection for StaticCircularBuffer<T, N> {
+    fn len(&self) -> usize {
+        self.size()
+    }
+
+    fn is_empty(&self) -> bool {
+        StaticCircularBuffer::is_empty(self)
+    }
+
+    fn clear(&mut self) {
+        StaticCircularBuffer::clear(self)
+    }
+}
+
+impl<T, const N: usize> OrderedCollection for StaticCircularBuffer<T, N> {
+    type Item = T;
+
+    fn iter(&self) -> impl Iterator<Item = &T> {
+        StaticCircularBuffer::iter(self)
+    }
+}
+
+impl<T> Collection for LinkedList<T> {
+    fn len(&self) -> usize {
+        LinkedList::len(self)
+    }
+
+    // O(1), unlike counting the nodes
+    fn is_empty(&self) -> bool {
+        LinkedList::is_empty(self)
+    }
+
+    fn clear(&mut self) {
+        LinkedList::clear(self)
+    }
+}
+
+impl<T> OrderedCollection for LinkedList<T> {
+    type Item = T;
+
+    fn iter(&self) -> impl Iterator<Item = &T> {
+        LinkedList::iter(self)
+    }
+}
+
+impl<T> Collection for DoublyLinkedList<T> {
+    fn len(&self) -> usize {
+        DoublyLinkedList::len(self)
+    }
+
+    fn clear(&mut self) {
+        DoublyLinkedList::clear(self)
+    }
+}
+
+impl<T> OrderedCollection for DoublyLinkedList<T> {
+    type Item = T;
+
+    fn iter(&self) -> impl Iterator<Item = &T> {
+        DoublyLinkedList::iter(self)
+    }
+}
+
+impl<K, V, S> Collection for MyHashMap<K, V, S> {
+    fn len(&self) -> usize {
+        MyHashMap::len(self)
+    }
+
+    fn clear(&mut self) {
+        MyHashMap::clear(self)
+    }
+}
+
+impl<K, V> Collection for BalancedTree<K, V> {
+    fn len(&self) -> usize {
+        BalancedTree::len(self)
+    }
+
+    fn clear(&mut self) {
+        BalancedTree::clear(self)
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> Collection for LruCache<K, V> {
+    fn len(&self) -> usize {
+        LruCache::len(self)
+    }
+
+    fn clear(&mut self) {
+        LruCache::clear(self)
+    }
+}
+
+impl<T: Ord, const D: usize> Collection for DaryHeap<T, D> {
+    fn len(&self) -> usize {
+        DaryHeap::len(self)
+    }
+
+    fn clear(&mut self) {
+        DaryHeap::clear(self)
+    }
+}
+
+impl Collection for Trie {
+    fn len(&self) -> usize {
+        Trie::len(self)
+    }
+
+    fn clear(&mut self) {
+        Trie::clear(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RingBuffer;
+
+    // One check for every collection: clearing leaves it empty
+    fn assert_clears<C: Collection>(collection: &mut C, expected_len: usize) {
+        assert_eq!(collection.len(), expected_len);
+        assert_eq!(collection.is_empty(), expected_len == 0);
+        collection.clear();
+        assert_eq!(collection.len(), 0);
+        assert!(collection.is_empty());
+    }
+
+    fn ordered<C: OrderedCollection<Item = i32>>(collection: &C) -> Vec<i32> {
+        collection.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_happy_path_sequences_in_documented_order() {
+        let mut stack = Stack::new();
+        let mut queue = Queue::new();
+        let mut ring = RingBuffer::new(4);
+        let mut circular = CircularBuffer::new(4);
+        let mut fixed: StaticCircularBuffer<i32, 4> = StaticCircularBuffer::new();
+        let mut list = LinkedList::new();
+        let mut doubly = DoublyLinkedList::new();
+        for i in 1..=3 {
+            stack.push(i).unwrap();
+            queue.enqueue(i);
+            ring.push(i);
+            circular.push(i);
+            fixed.push(i);
+            list.push(i);
+            doubly.push_back(i);
+        }
+        assert_eq!(ordered(&stack), vec![1, 2, 3]);
+        assert_eq!(ordered(&queue), vec![1, 2, 3]);
+        assert_eq!(ordered(&ring), vec![1, 2, 3]);
+        assert_eq!(ordered(&circular), vec![1, 2, 3]);
+        assert_eq!(ordered(&fixed), vec![1, 2, 3]);
+        assert_eq!(ordered(&list), vec![3, 2, 1], "Pushes go to the head");
+        assert_eq!(ordered(&doubly), vec![1, 2, 3]);
+
+        assert_clears(&mut stack, 3);
+        assert_clears(&mut queue, 3);
+        assert_clears(&mut ring, 3);
+        assert_clears(&mut circular, 3);
+        assert_clears(&mut fixed, 3);
+        assert_clears(&mut list, 3);
+        assert_clears(&mut doubly, 3);
+    }
+
+    #[test]
+    fn test_maps_heaps_and_sets() {
+        let mut map: MyHashMap<_, _> = [("a", 1), ("b", 2)].into_iter().collect();
+        let mut tree: BalancedTree<_, _> = [(1, "one")].into_iter().collect();
+        let mut cache = LruCache::new(2);
+        cache.put("k", 1);
+        let mut heap = DaryHeap::<i32, 4>::new();
+        heap.push(5);
+        heap.push(1);
+        let mut trie: Trie = ["to", "tea", "ten"].into_iter().collect();
+
+        assert_clears(&mut map, 2);
+        assert_clears(&mut tree, 1);
+        assert_clears(&mut cache, 1);
+        assert_clears(&mut heap, 2);
+        assert_clears(&mut trie, 3);
+    }
+
+    #[test]
+    fn test_edge_case_wrapped_buffers_iterate_oldest_first() {
+        let mut ring = RingBuffer::new(3);
+        let mut circular = CircularBuffer::new(3);
+        for i in 1..=5 {
+            ring.push(i);
+            circular.push(i);
+        }
+        assert_eq!(ordered(&ring), ordered(&circular));
+        assert_eq!(ordered(&circular), vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn test_trait_objects_for_collection() {
+        let mut stack = Stack::new();
+        stack.push(1).unwrap();
+        stack.push(2).unwrap();
+        let mut trie: Trie = ["x"].into_iter().collect();
+        let collections: [&mut dyn Collection; 2] = [&mut stack, &mut trie];
+        let total: usize = collections.iter().map(|c| c.len()).sum();
+        assert_eq!(total, 3);
+        for collection in collections {
+            collection.clear();
+            assert!(collection.is_empty());
+        }
+    }
+}
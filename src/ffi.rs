@@ -0,0 +1,278 @@
+//! C interface to the byte ring buffer and the fixed-size memory pool.
+//!
+//! Both structures are handed to C as opaque pointers from a `*_create`
+//! function and must be released with the matching `*_destroy`. Passing a
+//! null handle is always safe and does nothing (or reports empty/false).
+//! The header `include/rusty_repo.h` is generated from this module with
+//! `cbindgen --output include/rusty_repo.h`; build the library with
+//! `cargo rustc --release --lib --features ffi --crate-type staticlib`.
+
+use std::ptr;
+use std::sync::Arc;
+
+use crate::mempool::MemoryPool;
+use crate::RingBuffer;
+
+/// A `RingBuffer<u8>`: pushing onto a full buffer overwrites the oldest byte
+pub struct ByteRingBuffer {
+    inner: RingBuffer<u8>,
+}
+
+/// Task 07's `MemoryPool` of equally sized chunks
+pub struct MemoryPoolHandle {
+    pool: Arc<MemoryPool>,
+}
+
+/// Creates a buffer holding up to `capacity` bytes; returns null if `capacity` is 0
+#[no_mangle]
+pub extern "C" fn rusty_byte_ring_buffer_create(capacity: usize) -> *mut ByteRingBuffer {
+    if capacity == 0 {
+        return ptr::null_mut();
+    }
+    Box::into_raw(Box::new(ByteRingBuffer {
+        inner: RingBuffer::new(capacity),
+    }))
+}
+
+/// Appends a byte, dropping the oldest one if the buffer is full
+///
+/// # Safety
+///
+/// `buffer` must be null or a live pointer from `rusty_byte_ring_buffer_create`.
+#[no_mangle]
+pub unsafe extern "C" fn rusty_byte_ring_buffer_push(buffer: *mut ByteRingBuffer, byte: u8) {
+    if let Some(buffer) = buffer.as_mut() {
+        buffer.inner.push(byte);
+    }
+}
+
+/// Removes the oldest byte into `*out`; returns false, leaving `*out` alone, if empty
+///
+/// # Safety
+///
+/// `buffer` must be null or a live pointer from `rusty_byte_ring_buffer_create`,
+/// and `out` must be null or valid for a one-byte write.
+#[no_mangle]
+pub unsafe extern "C" fn rusty_byte_ring_buffer_pop(
+    buffer: *mut ByteRingBuffer,
+    out: *mut u8,
+) -> bool {
+    let Some(buffer) = buffer.as_mut() else {
+        return false;
+    };
+    if out.is_null() || buffer.inner.is_empty() {
+        return false;
+    }
+    match buffer.inner.pop() {
+        Some(byte) => {
+            out.write(byte);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Returns the number of bytes stored
+///
+/// # Safety
+///
+/// `buffer` must be null or a live pointer from `rusty_byte_ring_buffer_create`.
+#[no_mangle]
+pub unsafe extern "C" fn rusty_byte_ring_buffer_len(buffer: *const ByteRingBuffer) -> usize {
+    buffer.as_ref().map_or(0, |buffer| buffer.inner.size())
+}
+
+/// Checks if the next push will overwrite the oldest byte
+///
+/// # Safety
+///
+/// `buffer` must be null or a live pointer from `rusty_byte_ring_buffer_create`.
+#[no_mangle]
+pub unsafe extern "C" fn rusty_byte_ring_buffer_is_full(buffer: *const ByteRingBuffer) -> bool {
+    buffer.as_ref().is_some_and(|buffer| buffer.inner.is_full())
+}
+
+/// Frees the buffer
+///
+/// # Safety
+///
+/// `buffer` must be null or a pointer from `rusty_byte_ring_buffer_create`
+/// that has not been destroyed yet; it is dangling afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn rusty_byte_ring_buffer_destroy(buffer: *mut ByteRingBuffer) {
+    if !buffer.is_null() {
+        drop(Box::from_raw(buffer));
+    }
+}
+
+/// Creates a pool of `capacity` zeroed chunks of `chunk_size` bytes; returns null if either is 0
+#[no_mangle]
+pub extern "C" fn rusty_memory_pool_create(
+    chunk_size: usize,
+    capacity: usize,
+) -> *mut MemoryPoolHandle {
+    if chunk_size == 0 || capacity == 0 {
+        return ptr::null_mut();
+    }
+    Box::into_raw(Box::new(MemoryPoolHandle {
+        pool: MemoryPool::new(chunk_size, capacity),
+    }))
+}
+
+/// Takes a chunk of `chunk_size` bytes from the pool; returns null when it is exhausted
+///
+/// Chunks are not cleared when they come back, so a reused chunk holds
+/// whatever its previous user wrote.
+///
+/// # Safety
+///
+/// `pool` must be null or a live pointer from `rusty_memory_pool_create`.
+#[no_mangle]
+pub unsafe extern "C" fn rusty_memory_pool_allocate(pool: *mut MemoryPoolHandle) -> *mut u8 {
+    let Some(handle) = pool.as_ref() else {
+        return ptr::null_mut();
+    };
+    match handle.pool.allocate() {
+        // A boxed slice has no spare capacity, so the chunk size alone is enough to rebuild it
+        Some(chunk) => Box::into_raw(chunk.into_boxed_slice()).cast(),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Returns a chunk to the pool
+///
+/// # Safety
+///
+/// `pool` must be a live pointer from `rusty_memory_pool_create`, and `chunk`
+/// null or a pointer that pool's `rusty_memory_pool_allocate` returned and
+/// that hasn't been deallocated yet. The pool must not be destroyed while it
+/// still has chunks out.
+#[no_mangle]
+pub unsafe extern "C" fn rusty_memory_pool_deallocate(pool: *mut MemoryPoolHandle, chunk: *mut u8) {
+    let Some(handle) = pool.as_ref() else {
+        return;
+    };
+    if chunk.is_null() {
+        return;
+    }
+    let len = handle.pool.chunk_size();
+    let chunk = Box::from_raw(ptr::slice_from_raw_parts_mut(chunk, len));
+    handle.pool.deallocate(chunk.into_vec());
+}
+
+/// Returns the size of every chunk, or 0 for a null pool
+///
+/// # Safety
+///
+/// `pool` must be null or a live pointer from `rusty_memory_pool_create`.
+#[no_mangle]
+pub unsafe extern "C" fn rusty_memory_pool_chunk_size(pool: *const MemoryPoolHandle) -> usize {
+    pool.as_ref().map_or(0, |handle| handle.pool.chunk_size())
+}
+
+/// Returns how many chunks can still be allocated
+///
+/// # Safety
+///
+/// `pool` must be null or a live pointer from `rusty_memory_pool_create`.
+#[no_mangle]
+pub unsafe extern "C" fn rusty_memory_pool_available(pool: *const MemoryPoolHandle) -> usize {
+    pool.as_ref()
+        .map_or(0, |handle| handle.pool.available_chunks())
+}
+
+/// Frees the pool and every chunk it holds
+///
+/// # Safety
+///
+/// `pool` must be null or a pointer from `rusty_memory_pool_create` that has
+/// not been destroyed yet, with every allocated chunk already returned.
+#[no_mangle]
+pub unsafe extern "C" fn rusty_memory_pool_destroy(pool: *mut MemoryPoolHandle) {
+    if !pool.is_null() {
+        drop(Box::from_raw(pool));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::slice;
+
+    #[test]
+    fn test_happy_path_ring_buffer_round_trip() {
+        unsafe {
+            let buffer = rusty_byte_ring_buffer_create(4);
+            for byte in b"hello" {
+                rusty_byte_ring_buffer_push(buffer, *byte);
+            }
+            assert!(rusty_byte_ring_buffer_is_full(buffer));
+            assert_eq!(rusty_byte_ring_buffer_len(buffer), 4);
+
+            let mut out = 0u8;
+            let mut popped = Vec::new();
+            while rusty_byte_ring_buffer_pop(buffer, &mut out) {
+                popped.push(out);
+            }
+            assert_eq!(popped, b"ello", "The oldest byte was overwritten");
+            rusty_byte_ring_buffer_destroy(buffer);
+        }
+    }
+
+    #[test]
+    fn test_happy_path_pool_chunks_are_reused() {
+        unsafe {
+            let pool = rusty_memory_pool_create(64, 2);
+            assert_eq!(rusty_memory_pool_chunk_size(pool), 64);
+            let a = rusty_memory_pool_allocate(pool);
+            let b = rusty_memory_pool_allocate(pool);
+            assert!(!a.is_null() && !b.is_null());
+            assert!(rusty_memory_pool_allocate(pool).is_null(), "Exhausted");
+
+            slice::from_raw_parts_mut(a, 64).fill(7);
+            rusty_memory_pool_deallocate(pool, a);
+            assert_eq!(rusty_memory_pool_available(pool), 1);
+            let reused = rusty_memory_pool_allocate(pool);
+            assert_eq!(slice::from_raw_parts(reused, 64), &[7; 64][..]);
+
+            rusty_memory_pool_deallocate(pool, reused);
+            rusty_memory_pool_deallocate(pool, b);
+            assert_eq!(rusty_memory_pool_available(pool), 2);
+            rusty_memory_pool_destroy(pool);
+        }
+    }
+
+    #[test]
+    fn test_unhappy_path_zero_sizes_return_null() {
+        assert!(rusty_byte_ring_buffer_create(0).is_null());
+        assert!(rusty_memory_pool_create(0, 4).is_null());
+        assert!(rusty_memory_pool_create(16, 0).is_null());
+    }
+
+    #[test]
+    fn test_edge_case_null_handles_are_ignored() {
+        unsafe {
+            let mut out = 42u8;
+            rusty_byte_ring_buffer_push(ptr::null_mut(), 1);
+            assert!(!rusty_byte_ring_buffer_pop(ptr::null_mut(), &mut out));
+            assert_eq!(out, 42);
+            assert_eq!(rusty_byte_ring_buffer_len(ptr::null()), 0);
+            rusty_byte_ring_buffer_destroy(ptr::null_mut());
+
+            assert!(rusty_memory_pool_allocate(ptr::null_mut()).is_null());
+            rusty_memory_pool_deallocate(ptr::null_mut(), ptr::null_mut());
+            assert_eq!(rusty_memory_pool_available(ptr::null()), 0);
+            rusty_memory_pool_destroy(ptr::null_mut());
+
+            let buffer = rusty_byte_ring_buffer_create(1);
+            assert!(!rusty_byte_ring_buffer_pop(buffer, &mut out), "Empty");
+            rusty_byte_ring_buffer_push(buffer, 9);
+            assert!(
+                !rusty_byte_ring_buffer_pop(buffer, ptr::null_mut()),
+                "No place to write"
+            );
+            assert_eq!(rusty_byte_ring_buffer_len(buffer), 1, "The byte stays put");
+            rusty_byte_ring_buffer_destroy(buffer);
+        }
+    }
+}
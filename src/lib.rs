@@ -0,0 +1,152 @@
+//! Library facade over the challenge tasks.
+//!
+//! Each task lives in its own crate under `day_*/`; this crate re-exports them as
+//! modules, and the most commonly used types at the top level, so the whole
+//! collection can be used as a single dependency. Runnable demos live in `examples/`.
+//! The `Collection` and `OrderedCollection` traits give the containers a common
+//! interface for generic code, `bench` measures them against `std`, and
+//! `visualize` renders their shape as Graphviz DOT or ASCII art.
+//! The `ffi`, `wasm` and `python` features expose a few of them to C, JavaScript and Python.
+//! Under `safe-only` the crate forbids unsafe code, so the `ffi` module is left out,
+//! and so are the re-exports of crates that use unsafe code internally: the channels,
+//! `cow::atomic_cow` and the allocators from tasks 6 and 25–27.
+
+#![cfg_attr(feature = "safe-only", forbid(unsafe_code))]
+
+pub mod bench;
+pub mod collection;
+#[cfg(all(feature = "ffi", not(feature = "safe-only")))]
+pub mod ffi;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod visualize;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use task_01_singly_linked_list as linked_list;
+pub use task_05_doubly_linked_list as doubly_linked_list;
+pub use task_01_stack as stack;
+pub use task_02_queue as queue;
+pub use task_03_vector as vector;
+pub use task_04_circular_buffer as circular_buffer;
+pub use task_05_ring_buffer as ring_buffer;
+#[cfg(not(feature = "safe-only"))]
+pub use task_06_alloc as custom_allocator;
+pub use task_07_mempool as mempool;
+pub use task_08_smart_ptr as smart_ptr;
+pub use task_09_lock_free as lock_free;
+pub use task_10_arena_alloc as arena;
+pub use task_11_ref_gc as ref_gc;
+pub use task_12_alloc_mempool as alloc_mempool;
+pub use task_13_buffer as buffer;
+pub use task_14_cow as cow;
+pub use task_15_raii_wrapper as raii;
+pub use task_16_hashmap as hashmap;
+pub use task_17_trie as trie;
+pub use task_18_lru_cache as lru_cache;
+pub use task_19_balanced_tree as balanced_tree;
+pub use task_20_dary_heap as dary_heap;
+pub use task_21_segment_tree as segment_tree;
+#[cfg(not(feature = "safe-only"))]
+pub use task_22_channels as channels;
+pub use task_23_thread_pool as thread_pool;
+pub use task_24_executor as executor;
+#[cfg(not(feature = "safe-only"))]
+pub use task_25_free_list_alloc as free_list_alloc;
+#[cfg(not(feature = "safe-only"))]
+pub use task_26_fallback_alloc as fallback_alloc;
+#[cfg(not(feature = "safe-only"))]
+pub use task_27_page_alloc as page_alloc;
+pub use task_28_tracked_alloc as tracked_alloc;
+pub use task_29_timer_wheel as timer_wheel;
+pub use task_30_sso_string as sso_string;
+pub use task_31_bit_vec as bit_vec;
+pub use task_32_snapshot_store as snapshot_store;
+pub use task_33_priority_queue as priority_queue;
+pub use task_34_deque as deque;
+
+pub use arena::{ArenaGraph, ArenaHandle, Interner, MemoryArena};
+pub use balanced_tree::BalancedTree;
+pub use bit_vec::BitVec;
+pub use buffer::ZeroCopyBuffer;
+pub use circular_buffer::CircularBuffer;
+pub use collection::{Collection, OrderedCollection};
+pub use cow::CopyOnWrite;
+#[cfg(not(feature = "safe-only"))]
+pub use custom_allocator::CustomAllocator;
+pub use dary_heap::DaryHeap;
+pub use deque::Deque;
+pub use doubly_linked_list::DoublyLinkedList;
+#[cfg(not(feature = "safe-only"))]
+pub use fallback_alloc::FallbackAllocator;
+pub use executor::Executor;
+#[cfg(not(feature = "safe-only"))]
+pub use free_list_alloc::FreeListAllocator;
+pub use hashmap::MyHashMap;
+pub use linked_list::{LinkedList, RcLinkedList, UnrolledLinkedList};
+pub use lock_free::{LockFreeList, LockFreeSet, LockFreeStack};
+pub use lru_cache::{Cache, EvictionPolicy, LruCache};
+pub use mempool::{MemoryPool, ResourcePool};
+#[cfg(not(feature = "safe-only"))]
+pub use page_alloc::PageAllocator;
+pub use priority_queue::PriorityQueue;
+pub use queue::Queue;
+pub use ring_buffer::{RingBuffer, WindowAggregator};
+pub use segment_tree::{LazySegmentTree, SegmentTree};
+pub use smart_ptr::CustomSmartPointer;
+pub use snapshot_store::SnapshotStore;
+pub use sso_string::SsoString;
+pub use stack::Stack;
+pub use thread_pool::ThreadPool;
+pub use timer_wheel::TimerWheel;
+pub use tracked_alloc::{assert_no_leaks, TrackedAlloc};
+pub use trie::Trie;
+pub use vector::reverse_vector;
+pub use visualize::{AsciiArt, ToDot};
+
+pub use raii::file_lock::FileLock;
+pub use raii::rotating_file::RotatingFileWrapper;
+pub use raii::scope_guard::ScopeGuard;
+pub use raii::socket::{ListenerWrapper, TcpStreamWrapper};
+pub use raii::temp_file::TempFile;
+pub use raii::timer::TimerGuard;
+pub use raii::{defer, time_scope, FileWrapper};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reexports_are_usable_together() {
+        let mut stack = Stack::new();
+        let mut queue = Queue::new();
+        let mut ring = RingBuffer::new(2);
+        for i in 1..=3 {
+            stack.push(i).unwrap();
+            queue.enqueue(i);
+            ring.push(i);
+        }
+        assert_eq!(stack.pop(), Some(3));
+        assert_eq!(queue.dequeue(), Some(1));
+        assert_eq!(ring.iter().collect::<Vec<_>>(), vec![&2, &3]);
+
+        let mut list = LinkedList::new();
+        list.push(1);
+        assert_eq!(list.len(), 1);
+
+        let cow = CopyOnWrite::new(vec![1]);
+        cow.write(|data| data.push(2));
+        assert_eq!(*cow.read(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_raii_wrappers_through_facade() -> std::io::Result<()> {
+        let mut temp = TempFile::new()?;
+        {
+            let _lock = FileLock::exclusive(temp.path())?;
+            temp.write("locked write")?;
+        }
+        assert_eq!(temp.read_to_string()?, "locked write");
+        Ok(())
+    }
+}
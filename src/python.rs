@@ -0,0 +1,500 @@
+//! PyO3 classes so the containers can be driven from Python.
+//!
+//! `Stack`, `Queue`, `LinkedList` and `LruCache` hold arbitrary Python objects
+//! and support `len()`, `iter()` and `repr()`. Iterators walk a snapshot
+//! taken when iteration starts, so mutating a container mid-loop is safe.
+//! Cache keys may be any hashable object. Build the extension module with
+//! `maturin develop --features python` (see `pyproject.toml`), then
+//! `import rusty_repo`.
+
+use std::hash::{Hash, Hasher};
+
+use pyo3::exceptions::{PyIndexError, PyOverflowError, PyValueError};
+use pyo3::prelude::*;
+
+use crate::stack::StackFull;
+use crate::{LinkedList, LruCache, Queue, Stack};
+
+/// Iterator over a snapshot of a container's values
+#[pyclass(name = "Iterator")]
+pub struct PyValueIter {
+    values: std::vec::IntoIter<Py<PyAny>>,
+}
+
+#[pymethods]
+impl PyValueIter {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self) -> Option<Py<PyAny>> {
+        self.values.next()
+    }
+}
+
+fn snapshot<'a>(py: Python<'_>, values: impl Iterator<Item = &'a Py<PyAny>>) -> PyValueIter {
+    let values: Vec<_> = values.map(|value| value.clone_ref(py)).collect();
+    PyValueIter {
+        values: values.into_iter(),
+    }
+}
+
+// `Name([a, b, c])`, using each value's own repr
+fn repr<'a>(
+    py: Python<'_>,
+    name: &str,
+    values: impl Iterator<Item = &'a Py<PyAny>>,
+) -> PyResult<String> {
+    let items = values
+        .map(|value| Ok(value.bind(py).repr()?.to_string()))
+        .collect::<PyResult<Vec<_>>>()?;
+    Ok(format!("{name}([{}])", items.join(", ")))
+}
+
+// The stacks built here are unbounded, so this only guards against a future bound
+fn full<T>(err: StackFull<T>) -> PyErr {
+    PyOverflowError::new_err(err.to_string())
+}
+
+/// `Stack` of Python objects; iterates bottom to top
+#[pyclass(name = "Stack")]
+pub struct PyStack {
+    inner: Stack<Py<PyAny>>,
+}
+
+#[pymethods]
+impl PyStack {
+    #[new]
+    #[pyo3(signature = (iterable=None))]
+    fn new(iterable: Option<&Bound<'_, PyAny>>) -> PyResult<Self> {
+        let mut stack = Stack::new();
+        if let Some(iterable) = iterable {
+            for value in iterable.try_iter()? {
+                stack.push(value?.unbind()).map_err(full)?;
+            }
+        }
+        Ok(PyStack { inner: stack })
+    }
+
+    /// Raises `OverflowError` if the stack is full
+    fn push(&mut self, value: Py<PyAny>) -> PyResult<()> {
+        self.inner.push(value).map_err(full)
+    }
+
+    /// Raises `IndexError` if the stack is empty
+    fn pop(&mut self) -> PyResult<Py<PyAny>> {
+        self.inner
+            .pop()
+            .ok_or_else(|| PyIndexError::new_err("pop from empty Stack"))
+    }
+
+    /// Returns the top value, or `None` if the stack is empty
+    fn peek(&self, py: Python<'_>) -> Option<Py<PyAny>> {
+        self.inner.peek().map(|value| value.clone_ref(py))
+    }
+
+    fn clear(&mut self) {
+        self.inner.clear();
+    }
+
+    fn __len__(&self) -> usize {
+        self.inner.size()
+    }
+
+    fn __iter__(&self, py: Python<'_>) -> PyValueIter {
+        snapshot(py, self.inner.iter())
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        repr(py, "Stack", self.inner.iter())
+    }
+}
+
+/// `Queue` of Python objects; iterates front to back
+#[pyclass(name = "Queue")]
+pub struct PyQueue {
+    inner: Queue<Py<PyAny>>,
+}
+
+#[pymethods]
+impl PyQueue {
+    #[new]
+    #[pyo3(signature = (iterable=None))]
+    fn new(iterable: Option<&Bound<'_, PyAny>>) -> PyResult<Self> {
+        let mut queue = Queue::new();
+        if let Some(iterable) = iterable {
+            for value in iterable.try_iter()? {
+                queue.enqueue(value?.unbind());
+            }
+        }
+        Ok(PyQueue { inner: queue })
+    }
+
+    fn enqueue(&mut self, value: Py<PyAny>) {
+        self.inner.enqueue(value);
+    }
+
+    /// Raises `IndexError` if the queue is empty
+    fn dequeue(&mut self) -> PyResult<Py<PyAny>> {
+        self.inner
+            .dequeue()
+            .ok_or_else(|| PyIndexError::new_err("dequeue from empty Queue"))
+    }
+
+    /// Returns the front value, or `None` if the queue is empty
+    fn peek(&self, py: Python<'_>) -> Option<Py<PyAny>> {
+        self.inner.iter().next().map(|value| value.clone_ref(py))
+    }
+
+    fn clear(&mut self) {
+        self.inner.clear();
+    }
+
+    fn __len__(&self) -> usize {
+        self.inner.size()
+    }
+
+    fn __iter__(&self, py: Python<'_>) -> PyValueIter {
+        snapshot(py, self.inner.iter())
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        repr(py, "Queue", self.inner.iter())
+    }
+}
+
+/// `LinkedList` of Python objects; `push` adds at the head, and iteration goes head to tail
+#[pyclass(name = "LinkedList")]
+pub struct PyLinkedList {
+    inner: LinkedList<Py<PyAny>>,
+}
+
+#[pymethods]
+impl PyLinkedList {
+    /// Keeps the iterable's order, so `list(LinkedList(xs)) == list(xs)`
+    #[new]
+    #[pyo3(signature = (iterable=None))]
+    fn new(iterable: Option<&Bound<'_, PyAny>>) -> PyResult<Self> {
+        let list = match iterable {
+            Some(iterable) => iterable
+                .try_iter()?
+                .map(|value| value.map(Bound::unbind))
+                .collect::<PyResult<LinkedList<_>>>()?,
+            None => LinkedList::new(),
+        };
+        Ok(PyLinkedList { inner: list })
+    }
+
+    fn push(&mut self, value: Py<PyAny>) {
+        self.inner.push(value);
+    }
+
+    /// Removes the head; raises `IndexError` if the list is empty
+    fn pop(&mut self) -> PyResult<Py<PyAny>> {
+        self.inner
+            .pop()
+            .ok_or_else(|| PyIndexError::new_err("pop from empty LinkedList"))
+    }
+
+    fn reverse(&mut self) {
+        self.inner.reverse();
+    }
+
+    fn clear(&mut self) {
+        self.inner.clear();
+    }
+
+    fn __len__(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Walks from the head, so indexing is O(n); negative indexes count from the tail
+    fn __getitem__(&self, py: Python<'_>, index: isize) -> PyResult<Py<PyAny>> {
+        let len = self.inner.len() as isize;
+        let index = if index < 0 { index + len } else { index };
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| self.inner.get(index))
+            .map(|value| value.clone_ref(py))
+            .ok_or_else(|| PyIndexError::new_err("LinkedList index out of range"))
+    }
+
+    fn __iter__(&self, py: Python<'_>) -> PyValueIter {
+        snapshot(py, self.inner.iter())
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        repr(py, "LinkedList", self.inner.iter())
+    }
+}
+
+/// A hashable Python object used as a cache key
+///
+/// The hash is computed once, on the way in; equality defers to Python's `==`.
+struct PyKey {
+    hash: isize,
+    object: Py<PyAny>,
+}
+
+impl PyKey {
+    fn new(object: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(PyKey {
+            hash: object.hash()?,
+            object: object.clone().unbind(),
+        })
+    }
+}
+
+impl Hash for PyKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_isize(self.hash);
+    }
+}
+
+impl PartialEq for PyKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.hash == other.hash
+            && (self.object.is(&other.object)
+                || Python::attach(|py| {
+                    self.object
+                        .bind(py)
+                        .eq(other.object.bind(py))
+                        .unwrap_or(false)
+                }))
+    }
+}
+
+impl Eq for PyKey {}
+
+impl Clone for PyKey {
+    fn clone(&self) -> Self {
+        Python::attach(|py| PyKey {
+            hash: self.hash,
+            object: self.object.clone_ref(py),
+        })
+    }
+}
+
+/// `LruCache` with hashable keys; iterates over keys from most to least recently used
+#[pyclass(name = "LruCache")]
+pub struct PyLruCache {
+    inner: LruCache<PyKey, Py<PyAny>>,
+}
+
+#[pymethods]
+impl PyLruCache {
+    /// Raises `ValueError` if `capacity` is 0
+    #[new]
+    fn new(capacity: usize) -> PyResult<Self> {
+        if capacity == 0 {
+            return Err(PyValueError::new_err("LruCache capacity must be non-zero"));
+        }
+        Ok(PyLruCache {
+            inner: LruCache::new(capacity),
+        })
+    }
+
+    /// Returns the value it replaced, or `None`
+    fn put(&mut self, key: &Bound<'_, PyAny>, value: Py<PyAny>) -> PyResult<Option<Py<PyAny>>> {
+        Ok(self.inner.put(PyKey::new(key)?, value))
+    }
+
+    /// Marks `key` most recently used; returns `default` on a miss
+    #[pyo3(signature = (key, default=None))]
+    fn get(
+        &mut self,
+        py: Python<'_>,
+        key: &Bound<'_, PyAny>,
+        default: Option<Py<PyAny>>,
+    ) -> PyResult<Option<Py<PyAny>>> {
+        let key = PyKey::new(key)?;
+        Ok(self
+            .inner
+            .get(&key)
+            .map(|value| value.clone_ref(py))
+            .or(default))
+    }
+
+    /// Like `get`, but leaves recency and statistics alone
+    #[pyo3(signature = (key, default=None))]
+    fn peek(
+        &self,
+        py: Python<'_>,
+        key: &Bound<'_, PyAny>,
+        default: Option<Py<PyAny>>,
+    ) -> PyResult<Option<Py<PyAny>>> {
+        let key = PyKey::new(key)?;
+        Ok(self
+            .inner
+            .peek(&key)
+            .map(|value| value.clone_ref(py))
+            .or(default))
+    }
+
+    /// Returns the removed value, or `None`
+    fn remove(&mut self, key: &Bound<'_, PyAny>) -> PyResult<Option<Py<PyAny>>> {
+        Ok(self.inner.remove(&PyKey::new(key)?))
+    }
+
+    fn clear(&mut self) {
+        self.inner.clear();
+    }
+
+    #[getter]
+    fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+
+    #[getter]
+    fn hit_rate(&self) -> f64 {
+        self.inner.stats().hit_rate()
+    }
+
+    fn __contains__(&self, key: &Bound<'_, PyAny>) -> PyResult<bool> {
+        Ok(self.inner.contains(&PyKey::new(key)?))
+    }
+
+    fn __len__(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn __iter__(&self, py: Python<'_>) -> PyValueIter {
+        snapshot(py, self.inner.iter().map(|(key, _)| &key.object))
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        let items = self
+            .inner
+            .iter()
+            .map(|(key, value)| {
+                Ok(format!(
+                    "{}: {}",
+                    key.object.bind(py).repr()?,
+                    value.bind(py).repr()?
+                ))
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        Ok(format!(
+            "LruCache(capacity={}, {{{}}})",
+            self.inner.capacity(),
+            items.join(", ")
+        ))
+    }
+}
+
+/// The `rusty_repo` Python module
+#[pymodule]
+fn rusty_repo(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyStack>()?;
+    module.add_class::<PyQueue>()?;
+    module.add_class::<PyLinkedList>()?;
+    module.add_class::<PyLruCache>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pyo3::types::PyDict;
+    use std::ffi::CStr;
+
+    // Runs a Python snippet with the module imported as `rr`
+    fn run_python(code: &CStr) {
+        Python::initialize();
+        Python::attach(|py| {
+            let module = PyModule::new(py, "rusty_repo").unwrap();
+            rusty_repo(&module).unwrap();
+            let globals = PyDict::new(py);
+            globals.set_item("rr", module).unwrap();
+            if let Err(err) = py.run(code, Some(&globals), None) {
+                err.print(py);
+                panic!("Python snippet failed: {err}");
+            }
+        });
+    }
+
+    #[test]
+    fn test_happy_path_stack_and_queue_protocols() {
+        run_python(
+            cr#"
+s = rr.Stack([1, "two"])
+s.push(3.0)
+assert len(s) == 3 and list(s) == [1, "two", 3.0]
+assert s.pop() == 3.0 and s.peek() == "two"
+assert repr(s) == "Stack([1, 'two'])"
+
+q = rr.Queue(range(3))
+q.enqueue(3)
+assert q.dequeue() == 0 and q.peek() == 1
+assert [x * 10 for x in q] == [10, 20, 30]
+q.clear()
+assert not q and q.peek() is None
+"#,
+        );
+    }
+
+    #[test]
+    fn test_happy_path_linked_list_keeps_iterable_order() {
+        run_python(
+            cr#"
+l = rr.LinkedList(["a", "b", "c"])
+assert list(l) == ["a", "b", "c"]
+l.push("z")
+assert l[0] == "z" and l[-1] == "c" and len(l) == 4
+l.reverse()
+assert list(l) == ["c", "b", "a", "z"]
+assert repr(l) == "LinkedList(['c', 'b', 'a', 'z'])"
+"#,
+        );
+    }
+
+    #[test]
+    fn test_happy_path_lru_cache_with_python_keys() {
+        run_python(
+            cr#"
+c = rr.LruCache(2)
+c.put((1, 2), "tuple")
+c.put("k", [1])
+assert c.get((1, 2)) == "tuple"
+c.put(3, "three")
+assert "k" not in c, "k was least recently used"
+assert list(c) == [3, (1, 2)]
+assert c.get("missing", "fallback") == "fallback"
+assert c.put(3, "new") == "three" and c.remove(3) == "new"
+assert c.hit_rate == 0.5 and c.capacity == 2 and len(c) == 1
+"#,
+        );
+    }
+
+    #[test]
+    fn test_unhappy_path_errors_are_python_exceptions() {
+        run_python(
+            cr#"
+def raises(exc, f):
+    try:
+        f()
+    except exc:
+        return True
+    return False
+
+assert raises(IndexError, rr.Stack().pop)
+assert raises(IndexError, rr.Queue().dequeue)
+assert raises(IndexError, lambda: rr.LinkedList([1])[1])
+assert raises(ValueError, lambda: rr.LruCache(0))
+assert raises(TypeError, lambda: rr.LruCache(1).put([], 1)), "Lists are unhashable"
+"#,
+        );
+    }
+
+    #[test]
+    fn test_edge_case_iterators_are_snapshots() {
+        run_python(
+            cr#"
+s = rr.Stack([1, 2])
+it = iter(s)
+s.clear()
+assert list(it) == [1, 2] and len(s) == 0
+"#,
+        );
+    }
+}
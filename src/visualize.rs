@@ -0,0 +1,330 @@
+//! Renderers for looking at a structure's shape while debugging.
+//!
+//! `ToDot` writes Graphviz DOT for the pointer-based structures: `LinkedList`,
+//! with the edge that closes a cycle drawn in red, `DoublyLinkedList`, with
+//! its `prev` links dashed, `BalancedTree` and `ArenaGraph`. Render
+//! the output with `dot -Tsvg out.dot -o out.svg`. `AsciiArt` draws the ring
+//! buffers slot by slot, marking the head (oldest element) and the tail (next
+//! write).
+
+use std::fmt::{Display, Write};
+
+use crate::balanced_tree::NodeRef;
+use crate::{ArenaGraph, BalancedTree, CircularBuffer, DoublyLinkedList, LinkedList};
+
+/// Describes a structure as a Graphviz `digraph`
+pub trait ToDot {
+    fn to_dot(&self) -> String;
+}
+
+/// Draws a structure as plain text
+pub trait AsciiArt {
+    fn ascii_art(&self) -> String;
+}
+
+// DOT string literals only need quotes and backslashes escaped
+fn escape(label: impl Display) -> String {
+    label.to_string().replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+impl<T: Display> ToDot for LinkedList<T> {
+    fn to_dot(&self) -> String {
+        // Cycle-safe: each node is visited once even if the tail links back
+        let values = self.get_all_values();
+        let mut dot =
+            String::from("digraph LinkedList {\n    rankdir=LR;\n    node [shape=box];\n");
+        dot.push_str("    head [shape=plaintext];\n");
+        for (i, value) in values.iter().enumerate() {
+            writeln!(dot, "    n{i} [label=\"{}\"];", escape(value)).unwrap();
+        }
+
+        let Some(last) = values.len().checked_sub(1) else {
+            dot.push_str("    null [shape=point];\n    head -> null;\n}\n");
+            return dot;
+        };
+        dot.push_str("    head -> n0;\n");
+        for i in 0..last {
+            writeln!(dot, "    n{i} -> n{};", i + 1).unwrap();
+        }
+        match self.find_cycle_start() {
+            Some(start) => writeln!(
+                dot,
+                "    n{last} -> n{start} [color=red, penwidth=2, label=\"cycle\"];"
+            )
+            .unwrap(),
+            None => writeln!(dot, "    null [shape=point];\n    n{last} -> null;").unwrap(),
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+impl<T: Display> ToDot for DoublyLinkedList<T> {
+    fn to_dot(&self) -> String {
+        let mut dot = String::from(
+            "digraph DoublyLinkedList {\n    rankdir=LR;\n    node [shape=box];\n",
+        );
+        dot.push_str("    head [shape=plaintext];\n    tail [shape=plaintext];\n");
+        for (i, value) in self.iter().enumerate() {
+            writeln!(dot, "    n{i} [label=\"{}\"];", escape(value)).unwrap();
+        }
+
+        let Some(last) = self.len().checked_sub(1) else {
+            dot.push_str("    null [shape=point];\n    head -> null;\n    tail -> null;\n}\n");
+            return dot;
+        };
+        writeln!(dot, "    head -> n0;\n    tail -> n{last};").unwrap();
+        // `next` links solid, `prev` links dashed back the other way
+        for i in 0..last {
+            writeln!(dot, "    n{i} -> n{};", i + 1).unwrap();
+            writeln!(dot, "    n{} -> n{i} [style=dashed];", i + 1).unwrap();
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+impl<K: Display, V: Display> ToDot for BalancedTree<K, V> {
+    fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph BalancedTree {\n    node [shape=ellipse];\n");
+        if let Some(root) = self.root() {
+            let mut next_id = 0;
+            write_subtree(&mut dot, root, &mut next_id);
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+impl<T: Display> ToDot for ArenaGraph<T> {
+    fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph ArenaGraph {\n    node [shape=circle];\n");
+        for node in self.nodes() {
+            writeln!(dot, "    {node} [label=\"{}\"];", escape(self.value(node))).unwrap();
+        }
+        for node in self.nodes() {
+            for next in self.neighbors(node) {
+                writeln!(dot, "    {node} -> {next};").unwrap();
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+// Writes `node` and its descendants in preorder, returning the node's id
+fn write_subtree<K: Display, V: Display>(
+    dot: &mut String,
+    node: NodeRef<'_, K, V>,
+    next_id: &mut usize,
+) -> usize {
+    let id = *next_id;
+    *next_id += 1;
+    writeln!(
+        dot,
+        "    n{id} [label=\"{}: {}\\nh={}\"];",
+        escape(node.key()),
+        escape(node.value()),
+        node.height()
+    )
+    .unwrap();
+
+    let children = [node.left(), node.right()];
+    if children.iter().all(Option::is_none) {
+        return id;
+    }
+    // A lone child still gets a placeholder sibling so left and right stay apart
+    for (child, side) in children.into_iter().zip(["L", "R"]) {
+        match child {
+            Some(child) => {
+                let child_id = write_subtree(dot, child, next_id);
+                writeln!(dot, "    n{id} -> n{child_id} [label=\"{side}\"];").unwrap();
+            }
+            None => writeln!(
+                dot,
+                "    n{id}_{side} [shape=point];\n    n{id} -> n{id}_{side};"
+            )
+            .unwrap(),
+        }
+    }
+    id
+}
+
+// Also covers `RingBuffer`, which is the same type
+impl<T: Display> AsciiArt for CircularBuffer<T> {
+    fn ascii_art(&self) -> String {
+        draw_buffer(
+            self.slots(),
+            self.head_index(),
+            self.tail_index(),
+            self.size(),
+            self.capacity(),
+        )
+    }
+}
+
+// One boxed cell per slot, with slot numbers and H/T markers underneath:
+//
+//     2/4 used
+//     +----+----+----+----+
+//     | 5  |    |    | 4  |
+//     +----+----+----+----+
+//       0    1    2    3
+//            T         H
+fn draw_buffer<'a, T: Display + 'a>(
+    slots: impl Iterator<Item = Option<&'a T>>,
+    head: usize,
+    tail: usize,
+    size: usize,
+    capacity: usize,
+) -> String {
+    let cells: Vec<String> = slots
+        .map(|slot| slot.map_or_else(String::new, ToString::to_string))
+        .collect();
+    let markers: Vec<&str> = (0..cells.len())
+        .map(|i| match (i == head, i == tail) {
+            (true, true) => "HT",
+            (true, false) => "H",
+            (false, true) => "T",
+            (false, false) => "",
+        })
+        .collect();
+    let width = cells
+        .iter()
+        .map(|cell| cell.chars().count())
+        .chain([cells.len().saturating_sub(1).to_string().len(), 2])
+        .max()
+        .unwrap_or(0);
+
+    let border = format!(
+        "+{}\n",
+        format!("{}+", "-".repeat(width + 2)).repeat(cells.len())
+    );
+    let row = |items: &mut dyn Iterator<Item = String>, edge: &str| {
+        let line: String = items
+            .map(|item| format!(" {item:^width$} {edge}"))
+            .collect();
+        format!("{edge}{line}").trim_end().to_string() + "\n"
+    };
+
+    let mut art = format!("{size}/{capacity} used\n");
+    art.push_str(&border);
+    art.push_str(&row(&mut cells.iter().cloned(), "|"));
+    art.push_str(&border);
+    art.push_str(&row(&mut (0..cells.len()).map(|i| i.to_string()), " "));
+    art.push_str(&row(&mut markers.iter().map(|m| m.to_string()), " "));
+    art
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RingBuffer;
+
+    #[test]
+    fn test_happy_path_linear_list_ends_in_null() {
+        let mut list = LinkedList::new();
+        for value in [3, 2, 1] {
+            list.push(value);
+        }
+        let dot = list.to_dot();
+        assert!(dot.starts_with("digraph LinkedList {"));
+        assert!(dot.contains("n0 [label=\"1\"]"));
+        assert!(dot.contains("head -> n0;\n    n0 -> n1;\n    n1 -> n2;"));
+        assert!(dot.contains("n2 -> null;"));
+        assert!(!dot.contains("color=red"));
+    }
+
+    #[test]
+    #[cfg(not(feature = "safe-only"))]
+    fn test_happy_path_cycle_edge_is_highlighted() {
+        let mut list = LinkedList::new();
+        for value in [4, 3, 2, 1] {
+            list.push(value);
+        }
+        // SAFETY: the list is only read until its drop breaks the cycle
+        assert!(unsafe { list.create_cycle_at(1) });
+        let dot = list.to_dot();
+
+        assert!(dot.contains("n3 -> n1 [color=red, penwidth=2, label=\"cycle\"];"));
+        assert!(!dot.contains("null"));
+        assert_eq!(dot.matches("[label=").count(), 4, "Each node appears once");
+    }
+
+    #[test]
+    fn test_happy_path_doubly_linked_list_links_both_ways() {
+        let list: DoublyLinkedList<_> = ["a", "b", "c"].into_iter().collect();
+        let dot = list.to_dot();
+        assert!(dot.starts_with("digraph DoublyLinkedList {"));
+        assert!(dot.contains("head -> n0;\n    tail -> n2;"));
+        assert!(dot.contains("n0 -> n1;\n    n1 -> n0 [style=dashed];"));
+        assert!(dot.contains("n2 -> n1 [style=dashed];"));
+        assert!(DoublyLinkedList::<i32>::new()
+            .to_dot()
+            .contains("head -> null;\n    tail -> null;"));
+    }
+
+    #[test]
+    fn test_happy_path_graph_nodes_and_edges() {
+        let graph = ArenaGraph::from_edges(vec!["a", "b\"", "c"], [(0, 1), (1, 2), (2, 0)]);
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph ArenaGraph {"));
+        assert!(dot.contains("n1 [label=\"b\\\"\"];"));
+        assert!(dot.contains("n0 -> n1;\n    n1 -> n2;\n    n2 -> n0;"));
+    }
+
+    #[test]
+    fn test_happy_path_tree_edges_and_placeholders() {
+        let tree: BalancedTree<_, _> = [(2, "b"), (1, "a"), (3, "c"), (4, "d")]
+            .into_iter()
+            .collect();
+        let dot = tree.to_dot();
+        assert!(dot.contains("n0 [label=\"2: b\\nh=3\"];"));
+        assert!(dot.contains("n0 -> n1 [label=\"L\"];"));
+        assert!(dot.contains("n0 -> n2 [label=\"R\"];"));
+        // 3 has only a right child, so its left gets a placeholder
+        assert!(dot.contains("n2_L [shape=point];"));
+        assert!(dot.contains("n2 -> n3 [label=\"R\"];"));
+        assert!(BalancedTree::<i32, i32>::new()
+            .to_dot()
+            .ends_with("node [shape=ellipse];\n}\n"));
+    }
+
+    #[test]
+    fn test_wrapped_buffers_mark_head_and_tail() {
+        let mut ring = RingBuffer::new(4);
+        for value in 1..=5 {
+            ring.push(value);
+        }
+        ring.pop();
+        ring.pop();
+        let expected = "\
+2/4 used
++----+----+----+----+
+| 5  |    |    | 4  |
++----+----+----+----+
+  0    1    2    3
+       T         H
+";
+        assert_eq!(ring.ascii_art(), expected);
+
+        let mut circular = CircularBuffer::new(4);
+        for value in 1..=5 {
+            circular.push(value);
+        }
+        circular.pop();
+        circular.pop();
+        assert_eq!(circular.ascii_art(), expected);
+    }
+
+    #[test]
+    fn test_edge_case_empty_buffer_and_escaped_labels() {
+        let ring: RingBuffer<i32> = RingBuffer::new(2);
+        assert!(ring.ascii_art().ends_with("  HT\n"));
+
+        let mut list = LinkedList::new();
+        list.push("say \"hi\"");
+        assert!(list.to_dot().contains(r#"n0 [label="say \"hi\""];"#));
+        assert!(LinkedList::<i32>::new().to_dot().contains("head -> null;"));
+    }
+}
@@ -0,0 +1,309 @@
+//! `wasm-bindgen` wrappers so the containers can be driven from JavaScript.
+//!
+//! Each wrapper is exported under the container's own name (`Stack`, `Queue`,
+//! `RingBuffer`, `LruCache`) with camelCase methods. Values are JS numbers,
+//! cache keys are strings, an empty result is `undefined`, and `toArray()`
+//! returns a `Float64Array` in the container's iteration order. Build for the
+//! browser with
+//! `cargo rustc --release --lib --target wasm32-unknown-unknown --features wasm --crate-type cdylib`
+//! and `wasm-bindgen --target web --out-dir examples/wasm/pkg target/wasm32-unknown-unknown/release/rusty_repo.wasm`;
+//! `examples/wasm/index.html` loads the result.
+
+use wasm_bindgen::prelude::*;
+
+use crate::{LruCache, Queue, RingBuffer, Stack};
+
+/// `Stack` of numbers
+#[wasm_bindgen(js_name = Stack)]
+pub struct JsStack {
+    inner: Stack<f64>,
+}
+
+#[wasm_bindgen(js_class = Stack)]
+impl JsStack {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> JsStack {
+        JsStack {
+            inner: Stack::new(),
+        }
+    }
+
+    /// Throws if the stack is full
+    pub fn push(&mut self, value: f64) -> Result<(), String> {
+        self.inner.push(value).map_err(|err| err.to_string())
+    }
+
+    pub fn pop(&mut self) -> Option<f64> {
+        self.inner.pop()
+    }
+
+    pub fn peek(&self) -> Option<f64> {
+        self.inner.peek().copied()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.inner.size()
+    }
+
+    #[wasm_bindgen(js_name = isEmpty)]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.inner.clear();
+    }
+
+    /// Bottom to top
+    #[wasm_bindgen(js_name = toArray)]
+    pub fn to_array(&self) -> Vec<f64> {
+        self.inner.iter().copied().collect()
+    }
+}
+
+impl Default for JsStack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `Queue` of numbers
+#[wasm_bindgen(js_name = Queue)]
+#[derive(Default)]
+pub struct JsQueue {
+    inner: Queue<f64>,
+}
+
+#[wasm_bindgen(js_class = Queue)]
+impl JsQueue {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> JsQueue {
+        JsQueue {
+            inner: Queue::new(),
+        }
+    }
+
+    pub fn enqueue(&mut self, value: f64) {
+        self.inner.enqueue(value);
+    }
+
+    pub fn dequeue(&mut self) -> Option<f64> {
+        self.inner.dequeue()
+    }
+
+    /// The value `dequeue` would return next
+    pub fn peek(&self) -> Option<f64> {
+        self.inner.iter().next().copied()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.inner.size()
+    }
+
+    #[wasm_bindgen(js_name = isEmpty)]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.inner.clear();
+    }
+
+    /// Front to back
+    #[wasm_bindgen(js_name = toArray)]
+    pub fn to_array(&self) -> Vec<f64> {
+        self.inner.iter().copied().collect()
+    }
+}
+
+/// `RingBuffer` of numbers; pushing onto a full buffer overwrites the oldest value
+#[wasm_bindgen(js_name = RingBuffer)]
+pub struct JsRingBuffer {
+    inner: RingBuffer<f64>,
+    capacity: usize,
+}
+
+#[wasm_bindgen(js_class = RingBuffer)]
+impl JsRingBuffer {
+    /// Throws if `capacity` is 0
+    #[wasm_bindgen(constructor)]
+    pub fn new(capacity: usize) -> Result<JsRingBuffer, String> {
+        if capacity == 0 {
+            return Err("RingBuffer capacity must be non-zero".to_string());
+        }
+        Ok(JsRingBuffer {
+            inner: RingBuffer::new(capacity),
+            capacity,
+        })
+    }
+
+    pub fn push(&mut self, value: f64) {
+        self.inner.push(value);
+    }
+
+    pub fn pop(&mut self) -> Option<f64> {
+        self.inner.pop()
+    }
+
+    pub fn peek(&self) -> Option<f64> {
+        self.inner.peek().copied()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.inner.size()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    #[wasm_bindgen(js_name = isEmpty)]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    #[wasm_bindgen(js_name = isFull)]
+    pub fn is_full(&self) -> bool {
+        self.inner.is_full()
+    }
+
+    pub fn clear(&mut self) {
+        self.inner.clear();
+    }
+
+    /// Oldest to newest
+    #[wasm_bindgen(js_name = toArray)]
+    pub fn to_array(&self) -> Vec<f64> {
+        self.inner.iter().copied().collect()
+    }
+}
+
+/// `LruCache` from strings to numbers, without TTLs
+#[wasm_bindgen(js_name = LruCache)]
+pub struct JsLruCache {
+    inner: LruCache<String, f64>,
+}
+
+#[wasm_bindgen(js_class = LruCache)]
+impl JsLruCache {
+    /// Throws if `capacity` is 0
+    #[wasm_bindgen(constructor)]
+    pub fn new(capacity: usize) -> Result<JsLruCache, String> {
+        if capacity == 0 {
+            return Err("LruCache capacity must be non-zero".to_string());
+        }
+        Ok(JsLruCache {
+            inner: LruCache::new(capacity),
+        })
+    }
+
+    /// Returns the value it replaced
+    pub fn put(&mut self, key: String, value: f64) -> Option<f64> {
+        self.inner.put(key, value)
+    }
+
+    /// Marks `key` most recently used
+    pub fn get(&mut self, key: &str) -> Option<f64> {
+        self.inner.get(key).copied()
+    }
+
+    /// Leaves recency and statistics alone
+    pub fn peek(&self, key: &str) -> Option<f64> {
+        self.inner.peek(key).copied()
+    }
+
+    pub fn has(&self, key: &str) -> bool {
+        self.inner.contains(key)
+    }
+
+    /// Returns the removed value
+    pub fn delete(&mut self, key: &str) -> Option<f64> {
+        self.inner.remove(key)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.inner.len()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+
+    #[wasm_bindgen(getter, js_name = hitRate)]
+    pub fn hit_rate(&self) -> f64 {
+        self.inner.stats().hit_rate()
+    }
+
+    pub fn clear(&mut self) {
+        self.inner.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_happy_path_stack_and_queue_orders() {
+        let mut stack = JsStack::new();
+        let mut queue = JsQueue::new();
+        for value in [1.0, 2.0, 3.0] {
+            stack.push(value).unwrap();
+            queue.enqueue(value);
+        }
+        assert_eq!(stack.to_array(), vec![1.0, 2.0, 3.0]);
+        assert_eq!(stack.peek(), Some(3.0));
+        assert_eq!(stack.pop(), Some(3.0));
+
+        assert_eq!(queue.peek(), Some(1.0));
+        assert_eq!(queue.dequeue(), Some(1.0));
+        queue.enqueue(4.0);
+        assert_eq!(queue.to_array(), vec![2.0, 3.0, 4.0]);
+        assert_eq!(queue.length(), 3);
+
+        queue.clear();
+        assert!(queue.is_empty());
+        assert_eq!(queue.dequeue(), None, "undefined in JS");
+    }
+
+    #[test]
+    fn test_happy_path_ring_buffer_overwrites() {
+        let mut ring = JsRingBuffer::new(3).unwrap();
+        for value in 1..=5 {
+            ring.push(value as f64);
+        }
+        assert!(ring.is_full());
+        assert_eq!((ring.length(), ring.capacity()), (3, 3));
+        assert_eq!(ring.to_array(), vec![3.0, 4.0, 5.0]);
+        assert_eq!(ring.pop(), Some(3.0));
+        assert_eq!(ring.peek(), Some(4.0));
+    }
+
+    #[test]
+    fn test_happy_path_lru_cache_evicts_least_recent() {
+        let mut cache = JsLruCache::new(2).unwrap();
+        cache.put("a".to_string(), 1.0);
+        cache.put("b".to_string(), 2.0);
+        assert_eq!(cache.get("a"), Some(1.0));
+        cache.put("c".to_string(), 3.0);
+
+        assert!(!cache.has("b"), "b was least recently used");
+        assert_eq!(cache.put("a".to_string(), 10.0), Some(1.0));
+        assert_eq!(cache.delete("c"), Some(3.0));
+        assert_eq!(cache.get("c"), None);
+        assert_eq!(cache.hit_rate(), 0.5);
+        assert_eq!(cache.length(), 1);
+    }
+
+    #[test]
+    fn test_unhappy_path_zero_capacity_is_rejected() {
+        assert!(JsRingBuffer::new(0).is_err());
+        assert!(JsLruCache::new(0).is_err());
+    }
+}
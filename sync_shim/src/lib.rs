@@ -0,0 +1,112 @@
+//! Synchronisation primitives that are either `std`'s or, when built with
+//! `RUSTFLAGS="--cfg loom"`, [loom](https://docs.rs/loom)'s model-checked
+//! replacements.
+//!
+//! The concurrent tasks import `Arc`, locks, `Condvar`, atomics, `thread` and `UnsafeCell`
+//! from here instead of from `std`, so the same code runs normally and under
+//! `loom::model`, which explores every interleaving of a small test. Loom's
+//! types only work inside a model, so crates switch their ordinary tests off
+//! under `cfg(loom)` and keep the models in `loom_tests` modules. `--lib` skips
+//! the doctests, which would run loom types outside a model:
+//!
+//! ```text
+//! RUSTFLAGS="--cfg loom" cargo test --lib --release -p task_22_channels
+//! ```
+
+#[cfg(not(loom))]
+pub use std::sync::{Arc, Condvar, Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+#[cfg(loom)]
+pub use loom::sync::{Arc, Condvar, Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// Runs `f` under every interleaving loom can find; only exists under `cfg(loom)`
+#[cfg(loom)]
+pub use loom::model;
+
+pub mod atomic {
+    #[cfg(not(loom))]
+    pub use std::sync::atomic::{
+        fence, AtomicBool, AtomicIsize, AtomicPtr, AtomicU64, AtomicUsize, Ordering,
+    };
+
+    #[cfg(loom)]
+    pub use loom::sync::atomic::{
+        fence, AtomicBool, AtomicIsize, AtomicPtr, AtomicU64, AtomicUsize, Ordering,
+    };
+}
+
+pub mod thread {
+    #[cfg(not(loom))]
+    pub use std::thread::{current, park, spawn, yield_now, JoinHandle, Thread};
+
+    #[cfg(loom)]
+    pub use loom::thread::{current, park, spawn, yield_now, JoinHandle, Thread};
+}
+
+pub mod hint {
+    /// Loom's version yields to the scheduler, so spin-wait loops terminate in a model
+    #[cfg(not(loom))]
+    pub use std::hint::spin_loop;
+
+    #[cfg(loom)]
+    pub use loom::hint::spin_loop;
+}
+
+pub mod cell {
+    #[cfg(loom)]
+    pub use loom::cell::UnsafeCell;
+
+    /// `std::cell::UnsafeCell` behind loom's closure-based API
+    ///
+    /// Loom can only check accesses it sees, so its cell hands out the raw
+    /// pointer inside `with`/`with_mut` rather than through `get`. This wrapper
+    /// gives the std build the same shape at no cost.
+    #[cfg(not(loom))]
+    #[derive(Debug, Default)]
+    pub struct UnsafeCell<T>(std::cell::UnsafeCell<T>);
+
+    #[cfg(not(loom))]
+    impl<T> UnsafeCell<T> {
+        pub const fn new(value: T) -> Self {
+            UnsafeCell(std::cell::UnsafeCell::new(value))
+        }
+
+        /// Calls `f` with a pointer for reading the value
+        #[inline]
+        pub fn with<R>(&self, f: impl FnOnce(*const T) -> R) -> R {
+            f(self.0.get())
+        }
+
+        /// Calls `f` with a pointer for writing the value
+        #[inline]
+        pub fn with_mut<R>(&self, f: impl FnOnce(*mut T) -> R) -> R {
+            f(self.0.get())
+        }
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::cell::UnsafeCell;
+    use super::*;
+
+    #[test]
+    fn test_unsafe_cell_reads_and_writes() {
+        let cell = UnsafeCell::new(1);
+        // SAFETY: the cell is not shared
+        cell.with_mut(|ptr| unsafe { *ptr += 1 });
+        assert_eq!(cell.with(|ptr| unsafe { *ptr }), 2);
+    }
+
+    #[test]
+    fn test_primitives_are_std_without_loom() {
+        let counter = Arc::new(atomic::AtomicUsize::new(0));
+        let handle = thread::spawn({
+            let counter = Arc::clone(&counter);
+            move || counter.fetch_add(1, atomic::Ordering::SeqCst)
+        });
+        handle.join().unwrap();
+        let _: std::sync::Arc<_> = counter.clone();
+        assert_eq!(counter.load(atomic::Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,145 @@
+//! Checks the metrics emitted with the `tracing` feature. Each test installs
+//! its own thread-local recorder, so they don't see each other's numbers.
+#![cfg(feature = "tracing")]
+
+use std::alloc::{GlobalAlloc, Layout};
+use std::thread;
+
+use metrics_util::debugging::{DebugValue, DebuggingRecorder, Snapshotter};
+use rusty_repo::{alloc_mempool, mempool, LockFreeStack, MemoryArena};
+// Straight from task 6, since `safe-only` leaves the allocators out of the facade
+use task_06_alloc::CustomAllocator;
+
+// Runs `f` against a fresh recorder and returns everything it recorded by name
+fn record(f: impl FnOnce()) -> Vec<(String, DebugValue)> {
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+    metrics::with_local_recorder(&recorder, f);
+    by_name(&snapshotter)
+}
+
+fn by_name(snapshotter: &Snapshotter) -> Vec<(String, DebugValue)> {
+    snapshotter
+        .snapshot()
+        .into_vec()
+        .into_iter()
+        .map(|(key, _, _, value)| (key.key().name().to_string(), value))
+        .collect()
+}
+
+fn counter(metrics: &[(String, DebugValue)], name: &str) -> u64 {
+    metrics
+        .iter()
+        .filter_map(|(key, value)| match value {
+            DebugValue::Counter(count) if key == name => Some(*count),
+            _ => None,
+        })
+        .sum()
+}
+
+fn gauge(metrics: &[(String, DebugValue)], name: &str) -> Option<f64> {
+    metrics.iter().find_map(|(key, value)| match value {
+        DebugValue::Gauge(level) if key == name => Some(level.into_inner()),
+        _ => None,
+    })
+}
+
+#[test]
+fn test_arena_counts_allocations_failures_and_resets() {
+    let metrics = record(|| {
+        let mut arena = MemoryArena::new(16);
+        arena.allocate(8).unwrap();
+        arena.allocate(4).unwrap();
+        assert!(arena.allocate(8).is_none());
+        arena.reset();
+    });
+    assert_eq!(counter(&metrics, "arena.allocations"), 2);
+    assert_eq!(counter(&metrics, "arena.allocation_failures"), 1);
+    assert_eq!(counter(&metrics, "arena.resets"), 1);
+    assert_eq!(gauge(&metrics, "arena.bytes_used"), Some(0.0));
+}
+
+#[test]
+fn test_pools_count_hits_and_misses() {
+    let metrics = record(|| {
+        let pool = mempool::MemoryPool::new(32, 1);
+        let chunk = pool.allocate().unwrap();
+        assert!(pool.allocate().is_none());
+        pool.deallocate(chunk);
+    });
+    assert_eq!(counter(&metrics, "mempool.hits"), 1);
+    assert_eq!(counter(&metrics, "mempool.misses"), 1);
+    assert_eq!(gauge(&metrics, "mempool.chunks_in_use"), Some(0.0));
+
+    let metrics = record(|| {
+        let mut pool = alloc_mempool::MemoryPool::new();
+        let block = pool.allocate(64).unwrap(); // nothing free yet
+        pool.deallocate(block);
+        let block = pool.allocate(64).unwrap(); // reuses the returned block
+        assert!(pool.allocate_variable_size(1, 8).is_none());
+        pool.deallocate(block);
+    });
+    assert_eq!(counter(&metrics, "alloc_mempool.hits"), 1);
+    assert_eq!(counter(&metrics, "alloc_mempool.misses"), 2);
+    assert_eq!(gauge(&metrics, "alloc_mempool.bytes_in_use"), Some(0.0));
+}
+
+#[test]
+fn test_custom_allocator_reports_sizes() {
+    let layout = Layout::from_size_align(256, 8).unwrap();
+    let metrics = record(|| unsafe {
+        let ptr = CustomAllocator.alloc(layout);
+        assert!(!ptr.is_null());
+        CustomAllocator.dealloc(ptr, layout);
+    });
+    assert_eq!(counter(&metrics, "custom_allocator.allocations"), 1);
+    assert_eq!(counter(&metrics, "custom_allocator.deallocations"), 1);
+    let sizes = metrics.iter().find_map(|(key, value)| match value {
+        DebugValue::Histogram(sizes) if key == "custom_allocator.allocation_bytes" => Some(
+            sizes
+                .iter()
+                .map(|size| size.into_inner())
+                .collect::<Vec<_>>(),
+        ),
+        _ => None,
+    });
+    assert_eq!(sizes, Some(vec![256.0]));
+}
+
+#[test]
+fn test_lock_free_stack_counts_operations() {
+    let metrics = record(|| {
+        let stack = LockFreeStack::new();
+        stack.push(1);
+        stack.push(2);
+        assert_eq!(stack.pop(), Some(2));
+        assert_eq!(stack.pop(), Some(1));
+        assert_eq!(stack.pop(), None);
+    });
+    assert_eq!(counter(&metrics, "lock_free_stack.operations"), 4);
+    // A single thread never loses a compare-exchange
+    assert_eq!(counter(&metrics, "lock_free_stack.cas_retries"), 0);
+}
+
+#[test]
+fn test_lock_free_stack_reports_from_every_thread() {
+    // Worker threads can't see a local recorder, so this is the one test that
+    // installs the global one; the other tests' local recorders take priority
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+    recorder.install().unwrap();
+
+    let stack = LockFreeStack::new();
+    thread::scope(|scope| {
+        for worker in 0..4 {
+            let stack = &stack;
+            scope.spawn(move || {
+                for value in 0..1_000 {
+                    stack.push(worker * 1_000 + value);
+                }
+            });
+        }
+    });
+    let metrics = by_name(&snapshotter);
+    assert_eq!(counter(&metrics, "lock_free_stack.operations"), 4_000);
+}
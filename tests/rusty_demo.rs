@@ -0,0 +1,50 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+// Pipes a session into the binary and returns what it printed
+fn session(args: &[&str], input: &str) -> (bool, String, String) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rusty-demo"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    (
+        output.status.success(),
+        String::from_utf8(output.stdout).unwrap(),
+        String::from_utf8(output.stderr).unwrap(),
+    )
+}
+
+#[test]
+fn test_ringbuffer_session() {
+    let (ok, stdout, _) = session(
+        &["ringbuffer", "--capacity", "2"],
+        "push 1\npush 2\npush 3\npop\nbogus\nstats\nquit\npush 4\n",
+    );
+    assert!(ok);
+    assert_eq!(
+        stdout,
+        "size 1\nsize 2\noverwrote the oldest value\n2\n\
+         error: unknown command `bogus`, try `help`\nsize 1/2\n"
+    );
+}
+
+#[test]
+fn test_usage_and_bad_arguments() {
+    let (ok, stdout, _) = session(&[], "");
+    assert!(ok);
+    assert!(stdout.contains("arena") && stdout.contains("--size N"));
+
+    let (ok, _, stderr) = session(&["arena", "--size", "lots"], "");
+    assert!(!ok);
+    assert!(stderr.starts_with("error: `--size` needs a positive integer, got `lots`"));
+}